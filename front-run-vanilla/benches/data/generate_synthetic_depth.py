@@ -0,0 +1,102 @@
+#!/usr/bin/env python3
+"""Write a synthetic BTCUSDT @depth@100ms sample for orderbook_bench.
+
+Not a recording: a seeded random walk shaped like the futures diff stream
+(0.1 tick, a REST-style snapshot frame first, then diffs every 100ms that
+mostly touch the levels near the spread, with deletions as qty "0"). It
+keeps replay_depth_diffs measuring something until real frames are
+dropped next to it; see README.md.
+
+    python3 benches/data/generate_synthetic_depth.py > benches/data/synthetic-btcusdt-depth.jsonl
+"""
+
+import json
+import random
+
+SEED = 7
+FRAMES = 3000
+SNAPSHOT_LEVELS = 200
+TICK = 1  # prices are kept in ticks of 0.1
+START_MS = 1_709_251_200_000  # 2024-03-01 00:00:00 UTC
+SYMBOL = "BTCUSDT"
+
+rng = random.Random(SEED)
+
+
+def price(ticks):
+    return f"{ticks / 10:.2f}"
+
+
+def qty():
+    # Mostly small resting size, occasionally a large clip
+    size = rng.lognormvariate(-1.2, 1.1)
+    return f"{min(size, 25.0):.3f}"
+
+
+def frame(update_id, prev_id, event_ms, bids, asks, first_id=None):
+    data = {
+        "e": "depthUpdate",
+        "E": event_ms,
+        "T": event_ms - rng.randint(1, 5),
+        "s": SYMBOL,
+        "U": first_id if first_id is not None else update_id,
+        "u": update_id,
+        "pu": prev_id,
+        "b": [[price(p), q] for p, q in bids],
+        "a": [[price(p), q] for p, q in asks],
+    }
+    return json.dumps({"stream": "btcusdt@depth@100ms", "data": data}, separators=(",", ":"))
+
+
+def main():
+    best_bid = 620_000  # 62000.0
+    bids = {best_bid - i * TICK: qty() for i in range(SNAPSHOT_LEVELS)}
+    asks = {best_bid + TICK + i * TICK: qty() for i in range(SNAPSHOT_LEVELS)}
+
+    update_id = 4_000_000_000
+    lines = [frame(update_id, update_id - 1, START_MS, sorted(bids.items(), reverse=True),
+                   sorted(asks.items()))]
+
+    for n in range(1, FRAMES):
+        event_ms = START_MS + n * 100
+        first_id = update_id + 1
+        update_id += rng.randint(3, 40)
+        bid_changes, ask_changes = {}, {}
+
+        # Touch drifts a few ticks at a time; levels it crosses are removed
+        move = rng.choice([-3, -2, -1, -1, 0, 0, 0, 0, 1, 1, 2, 3])
+        if move:
+            best_bid += move * TICK
+            for p in [p for p in bids if p > best_bid]:
+                del bids[p]
+                bid_changes[p] = "0"
+            for p in [p for p in asks if p <= best_bid]:
+                del asks[p]
+                ask_changes[p] = "0"
+        best_ask = min(asks) if asks else best_bid + TICK
+        if best_bid not in bids:
+            bids[best_bid] = bid_changes[best_bid] = qty()
+        if best_ask - best_bid > 4 * TICK:
+            best_ask = best_bid + TICK
+            asks[best_ask] = ask_changes[best_ask] = qty()
+
+        # Size changes and cancels, concentrated near the touch
+        for book, changes, side in ((bids, bid_changes, -1), (asks, ask_changes, 1)):
+            touch = best_bid if side < 0 else best_ask
+            for _ in range(rng.randint(2, 12)):
+                p = touch + side * int(rng.expovariate(0.15)) * TICK
+                if p in book and rng.random() < 0.3 and p != touch:
+                    del book[p]
+                    changes[p] = "0"
+                else:
+                    book[p] = changes[p] = qty()
+
+        lines.append(frame(update_id, first_id - 1, event_ms,
+                           sorted(bid_changes.items(), reverse=True), sorted(ask_changes.items()),
+                           first_id=first_id))
+
+    print("\n".join(lines))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,37 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use front_run_vanilla::{Position, PositionManager, RiskLimits, RiskManager, Side};
+use rust_decimal_macros::dec;
+
+/// Benchmark the pre-trade risk check run before every entry
+/// TARGET: well under the 50-100ms signal-to-fill budget
+fn bench_risk_check(c: &mut Criterion) {
+    let mut manager = RiskManager::new(RiskLimits::default(), dec!(10000));
+
+    c.bench_function("can_open_position", |b| {
+        b.iter(|| {
+            black_box(manager.can_open_position(black_box(dec!(100)), black_box(dec!(0)))).ok();
+        });
+    });
+}
+
+/// Benchmark exit checks across the open positions
+fn bench_exit_checks(c: &mut Criterion) {
+    let mut positions = PositionManager::new();
+    for i in 0..5 {
+        let symbol = format!("SYM{}USDT", i);
+        positions.open_position(Position::new(symbol, Side::Buy, dec!(100), dec!(1), dec!(0.04))).unwrap();
+    }
+
+    c.bench_function("exit_checks_5_positions", |b| {
+        b.iter(|| {
+            for position in positions.open_positions() {
+                black_box(position.take_profit_hit(black_box(dec!(100.2)), dec!(15)));
+                black_box(position.stop_loss_hit(black_box(dec!(100.2)), dec!(10)));
+                black_box(position.unrealized_pnl(dec!(100.2)));
+            }
+        });
+    });
+}
+
+criterion_group!(benches, bench_risk_check, bench_exit_checks);
+criterion_main!(benches);
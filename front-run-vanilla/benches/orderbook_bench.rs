@@ -1,7 +1,15 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId, Throughput};
 use front_run_vanilla::OrderBook;
 use front_run_vanilla::Side;
+use front_run_vanilla::backtest::{BacktestEvent, DataFormat, DateRange, HistoricalDataSource};
+use front_run_vanilla::strategy::StrategyParams;
 use rust_decimal_macros::dec;
+use std::path::PathBuf;
+
+/// Combined-stream depth frames replayed by `bench_replay_depth_diffs`: a
+/// bundled synthetic sample plus any recordings dropped next to it
+/// (override with `DEPTH_SAMPLE_DIR`; see benches/data/README.md)
+const DEPTH_SAMPLE_DIR: &str = "benches/data";
 
 /// Benchmark order book update performance
 /// TARGET: <1ms for update operations
@@ -50,11 +58,11 @@ fn bench_imbalance_calculation(c: &mut Criterion) {
     // Populate with realistic depth
     for i in 0..20 {
         let price = dec!(100.0) - rust_decimal::Decimal::from(i);
-        ob.update_level(Side::Buy, price, dec!(1.0 + rust_decimal::Decimal::from(i) * dec!(0.1))).unwrap();
+        ob.update_level(Side::Buy, price, dec!(1.0) + rust_decimal::Decimal::from(i) * dec!(0.1)).unwrap();
     }
     for i in 0..20 {
         let price = dec!(101.0) + rust_decimal::Decimal::from(i);
-        ob.update_level(Side::Sell, price, dec!(1.0 + rust_decimal::Decimal::from(i) * dec!(0.1))).unwrap();
+        ob.update_level(Side::Sell, price, dec!(1.0) + rust_decimal::Decimal::from(i) * dec!(0.1)).unwrap();
     }
     
     let mut group = c.benchmark_group("imbalance_calculation");
@@ -128,12 +136,59 @@ fn bench_concurrent_updates(c: &mut Criterion) {
     });
 }
 
+/// Replay depth diffs through the book and imbalance detector
+///
+/// Real diffs touch a few levels each, mostly near the touch, and keep a
+/// deep book; the single-level benchmarks above miss both. Throughput is
+/// reported in updates per second, one fresh book and detector per pass.
+fn bench_replay_depth_diffs(c: &mut Criterion) {
+    let dir = std::env::var("DEPTH_SAMPLE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DEPTH_SAMPLE_DIR));
+    let range = DateRange::parse("2000-01-01", "2099-12-31").unwrap();
+    let updates: Vec<_> = HistoricalDataSource::new(&dir, DataFormat::Jsonl, range)
+        .load()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|event| match event {
+            BacktestEvent::OrderBookUpdate { timestamp, bids, asks } => Some((timestamp, bids, asks)),
+            BacktestEvent::Trade { .. } => None,
+        })
+        .collect();
+    if updates.is_empty() {
+        eprintln!("Skipping replay_depth_diffs: no recorded depth frames in {}", dir.display());
+        return;
+    }
+
+    let params = StrategyParams::default();
+    let mut group = c.benchmark_group("replay_depth_diffs");
+    group.throughput(Throughput::Elements(updates.len() as u64));
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::from_parameter(updates.len()), |b| {
+        b.iter(|| {
+            let ob = OrderBook::new("BTCUSDT");
+            let mut detector = params.imbalance_detector();
+            for (timestamp, bids, asks) in &updates {
+                for (price, qty) in bids {
+                    ob.update_level(Side::Buy, *price, *qty).unwrap();
+                }
+                for (price, qty) in asks {
+                    ob.update_level(Side::Sell, *price, *qty).unwrap();
+                }
+                black_box(detector.calculate_signal_at(&ob, *timestamp));
+            }
+        });
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_update_level,
     bench_top_of_book,
     bench_imbalance_calculation,
     bench_get_depth,
-    bench_concurrent_updates
+    bench_concurrent_updates,
+    bench_replay_depth_diffs
 );
 criterion_main!(benches);
@@ -0,0 +1,83 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use front_run_vanilla::{OrderBook, Side, Signal, Trade};
+use front_run_vanilla::strategy::StrategyParams;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::{Duration, SystemTime};
+
+/// Book with 20 levels per side, slightly bid-heavy
+fn populated_book() -> OrderBook {
+    let ob = OrderBook::new("BTCUSDT");
+    for i in 0..20 {
+        let offset = Decimal::from(i);
+        ob.update_level(Side::Buy, dec!(100.0) - offset, dec!(1.5) + offset * dec!(0.1)).unwrap();
+        ob.update_level(Side::Sell, dec!(101.0) + offset, dec!(1.0) + offset * dec!(0.1)).unwrap();
+    }
+    ob
+}
+
+fn trade(id: u64, side: Side, timestamp: SystemTime) -> Trade {
+    Trade {
+        id,
+        price: dec!(100.5),
+        quantity: dec!(0.5),
+        side,
+        timestamp,
+        is_buyer_maker: side == Side::Sell,
+    }
+}
+
+/// Benchmark one imbalance evaluation against a warmed-up detector
+/// TARGET: <2ms
+fn bench_imbalance_signal(c: &mut Criterion) {
+    let ob = populated_book();
+    let mut detector = StrategyParams::default().imbalance_detector();
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    for i in 0..200 {
+        detector.calculate_signal_at(&ob, start + Duration::from_millis(i * 100));
+    }
+
+    c.bench_function("imbalance_signal", |b| {
+        b.iter(|| {
+            black_box(detector.calculate_signal_at(black_box(&ob), start));
+        });
+    });
+}
+
+/// Benchmark trade processing through a full flow window
+fn bench_flow_signal(c: &mut Criterion) {
+    let mut analyzer = StrategyParams::default().flow_analyzer();
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let mut id = 0u64;
+
+    c.bench_function("flow_process_trade", |b| {
+        b.iter(|| {
+            id += 1;
+            let side = if id.is_multiple_of(3) { Side::Sell } else { Side::Buy };
+            let timestamp = start + Duration::from_millis(id);
+            black_box(analyzer.process_trade(trade(id, side, timestamp)));
+        });
+    });
+}
+
+/// Benchmark voting over a primary and two confirming signals
+fn bench_aggregate(c: &mut Criterion) {
+    let aggregator = StrategyParams::default().signal_aggregator();
+    let signal = |strength: f64| Signal {
+        strength,
+        direction: Side::Buy,
+        confidence: 0.8,
+        timestamp: SystemTime::UNIX_EPOCH,
+        components: vec![],
+    };
+    let signals = vec![signal(3.5), signal(2.0), signal(1.8)];
+
+    c.bench_function("aggregate_signals", |b| {
+        b.iter(|| {
+            black_box(aggregator.aggregate(black_box(signals.clone())));
+        });
+    });
+}
+
+criterion_group!(benches, bench_imbalance_signal, bench_flow_signal, bench_aggregate);
+criterion_main!(benches);
@@ -0,0 +1,209 @@
+use crate::backtest::{
+    BacktestConfig, BacktestEngine, BacktestEvent, BacktestResults, StrategyRunner, format_metric,
+};
+use crate::storage::{BookSnapshot, ObservationRecorder};
+use anyhow::Result;
+
+/// Named results of one variant in a comparison
+#[derive(Debug, Clone)]
+pub struct VariantResults {
+    pub name: String,
+    pub results: BacktestResults,
+}
+
+/// Replay of one variant: the front-run engine, or a built-in `Strategy`
+/// picked by `[strategy] kind`
+enum Replay {
+    Engine(Box<BacktestEngine>),
+    Runner(Box<StrategyRunner>),
+}
+
+impl Replay {
+    fn new(config: BacktestConfig) -> Self {
+        match config.strategy.builtin_strategy(&config.instrument) {
+            Some(strategy) => Replay::Runner(Box::new(StrategyRunner::new(config, strategy))),
+            None => Replay::Engine(Box::new(BacktestEngine::new(config))),
+        }
+    }
+
+    fn with_recorder(self, recorder: ObservationRecorder) -> Self {
+        match self {
+            Replay::Engine(engine) => Replay::Engine(Box::new(engine.with_recorder(recorder))),
+            Replay::Runner(runner) => Replay::Runner(runner),
+        }
+    }
+
+    fn warm_start(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        match self {
+            Replay::Engine(engine) => engine.warm_start(snapshot),
+            Replay::Runner(runner) => runner.warm_start(snapshot),
+        }
+    }
+
+    fn process_event(&mut self, event: BacktestEvent) -> Result<()> {
+        match self {
+            Replay::Engine(engine) => engine.process_event(event),
+            Replay::Runner(runner) => runner.process_event(event),
+        }
+    }
+
+    fn results(&self) -> BacktestResults {
+        match self {
+            Replay::Engine(engine) => engine.get_results(),
+            Replay::Runner(runner) => runner.get_results(),
+        }
+    }
+}
+
+/// Runs several strategy/config variants over the same event stream
+///
+/// Every event is replayed into each variant's engine in turn, so the data
+/// is loaded and iterated once no matter how many variants are compared.
+/// Variants whose `[strategy] kind` is a built-in strategy other than
+/// `front_run` run in a `StrategyRunner`.
+pub struct StrategyComparison {
+    variants: Vec<(String, Replay)>,
+}
+
+impl StrategyComparison {
+    pub fn new() -> Self {
+        Self { variants: Vec::new() }
+    }
+
+    pub fn with_variant(mut self, name: &str, config: BacktestConfig) -> Self {
+        self.variants.push((name.to_string(), Replay::new(config)));
+        self
+    }
+
+    /// Capture the first variant's detector outputs and market samples
+    /// (front-run variants only)
+    pub fn with_recorder(mut self, recorder: ObservationRecorder) -> Self {
+        if !self.variants.is_empty() {
+            let (name, replay) = self.variants.remove(0);
+            self.variants.insert(0, (name, replay.with_recorder(recorder)));
+        }
+        self
+    }
+
+    pub fn variant_count(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Seed every variant's book from a recorded snapshot
+    pub fn warm_start(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        for (_, replay) in &mut self.variants {
+            replay.warm_start(snapshot)?;
+        }
+        Ok(())
+    }
+
+    /// Feed one event to every variant
+    pub fn process_event(&mut self, event: &BacktestEvent) -> Result<()> {
+        for (_, replay) in &mut self.variants {
+            replay.process_event(event.clone())?;
+        }
+        Ok(())
+    }
+
+    pub fn results(&self) -> Vec<VariantResults> {
+        self.variants.iter()
+            .map(|(name, replay)| VariantResults {
+                name: name.clone(),
+                results: replay.results(),
+            })
+            .collect()
+    }
+}
+
+impl Default for StrategyComparison {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Table row: label and how to format it from a variant's results
+type MetricRow = (&'static str, fn(&BacktestResults) -> String);
+
+/// Side-by-side metrics table, one column per variant
+pub fn comparison_table(variants: &[VariantResults]) -> String {
+    let rows: [MetricRow; 11] = [
+        ("Final Equity", |r| format!("{:.2}", r.final_equity)),
+        ("Return %", |r| format!("{:.2}", r.total_return_pct)),
+        ("Trades", |r| r.total_trades.to_string()),
+        ("Win Rate %", |r| format_metric(r.metrics.win_rate.map(|rate| rate * 100.0))),
+        ("Profit Factor", |r| r.metrics.profit_factor.to_string()),
+        ("Avg Win", |r| r.metrics.average_win.map_or_else(|| "n/a".to_string(), |win| format!("{:.2}", win))),
+        ("Avg Loss", |r| r.metrics.average_loss.map_or_else(|| "n/a".to_string(), |loss| format!("{:.2}", loss))),
+        ("Max DD %", |r| format!("{:.2}", r.max_drawdown_pct)),
+        ("Sharpe", |r| format_metric(r.metrics.sharpe_ratio)),
+        ("Sharpe/Trade", |r| format_metric(r.metrics.per_trade_sharpe)),
+        ("Risk Blocks", |r| r.risk_blocked_entries.to_string()),
+    ];
+
+    let width = variants.iter()
+        .map(|v| v.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(12);
+
+    let mut table = format!("{:<14}", "Metric");
+    for variant in variants {
+        table.push_str(&format!(" | {:>width$}", variant.name, width = width));
+    }
+    table.push('\n');
+    table.push_str(&"-".repeat(14 + variants.len() * (width + 3)));
+    table.push('\n');
+
+    for (label, metric) in rows {
+        table.push_str(&format!("{:<14}", label));
+        for variant in variants {
+            table.push_str(&format!(" | {:>width$}", metric(&variant.results), width = width));
+        }
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{StrategyKind, StrategyParams};
+    use rust_decimal::Decimal;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_variants_see_same_events() {
+        let mut comparison = StrategyComparison::new()
+            .with_variant("base", BacktestConfig::default())
+            .with_variant("low_fee", BacktestConfig {
+                commission_bps: Decimal::ZERO,
+                ..BacktestConfig::default()
+            })
+            .with_variant("maker", BacktestConfig {
+                strategy: StrategyParams {
+                    kind: StrategyKind::SpreadCapture,
+                    ..StrategyParams::default()
+                },
+                ..BacktestConfig::default()
+            });
+
+        for i in 0..10u64 {
+            let event = BacktestEvent::OrderBookUpdate {
+                timestamp: UNIX_EPOCH + Duration::from_millis(i * 100),
+                bids: vec![(Decimal::from(100), Decimal::ONE)],
+                asks: vec![(Decimal::from(101), Decimal::ONE)],
+            };
+            comparison.process_event(&event).unwrap();
+        }
+
+        let results = comparison.results();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].results.equity_curve.len(), 10);
+        assert_eq!(results[1].results.equity_curve.len(), 10);
+        assert_eq!(results[2].results.equity_curve.len(), 10);
+
+        let table = comparison_table(&results);
+        assert!(table.contains("low_fee"));
+        assert_eq!(table.lines().count(), 13);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::backtest::BacktestEvent;
+use crate::data::{Side, Trade};
+use crate::exchange::binance::{AggTrade, DepthUpdate};
+use anyhow::{Result, anyhow};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// On-disk format of historical market data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    /// One Binance combined-stream frame per line
+    /// (`{"stream": ..., "data": ...}`), raw or as spilled by `FrameCapture`
+    Jsonl,
+    /// data.binance.vision aggTrades CSV (trades only, no book)
+    Csv,
+}
+
+impl DataFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            DataFormat::Jsonl => "jsonl",
+            DataFormat::Csv => "csv",
+        }
+    }
+}
+
+impl FromStr for DataFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "jsonl" => Ok(DataFormat::Jsonl),
+            "csv" => Ok(DataFormat::Csv),
+            other => Err(anyhow!("Unknown data format '{}' (use jsonl or csv)", other)),
+        }
+    }
+}
+
+/// Inclusive date range, e.g. 2024-03-01..=2024-03-07 (UTC days)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl DateRange {
+    /// Parse `YYYY-MM-DD` start and end dates
+    pub fn parse(start: &str, end: &str) -> Result<Self> {
+        let start_date = NaiveDate::parse_from_str(start, "%Y-%m-%d")
+            .map_err(|e| anyhow!("Invalid start date '{}': {}", start, e))?;
+        let end_date = NaiveDate::parse_from_str(end, "%Y-%m-%d")
+            .map_err(|e| anyhow!("Invalid end date '{}': {}", end, e))?;
+        if end_date < start_date {
+            return Err(anyhow!("End date {} is before start date {}", end, start));
+        }
+        Ok(Self { start_date, end_date })
+    }
+
+    /// First instant of the start date
+    pub fn start(&self) -> SystemTime {
+        day_start(self.start_date)
+    }
+
+    /// First instant after the end date (exclusive bound)
+    pub fn end(&self) -> SystemTime {
+        day_start(self.end_date) + Duration::from_secs(86_400)
+    }
+
+    pub fn contains(&self, time: SystemTime) -> bool {
+        time >= self.start() && time < self.end()
+    }
+
+    /// Whether a file may hold data in range, judged by a `YYYY-MM-DD` in
+    /// its name (files without a date are always read)
+    fn includes_file(&self, name: &str) -> bool {
+        match date_in_name(name) {
+            Some(date) => date >= self.start_date && date <= self.end_date,
+            None => true,
+        }
+    }
+}
+
+fn day_start(date: NaiveDate) -> SystemTime {
+    let secs = date.and_hms_opt(0, 0, 0)
+        .map(|dt| dt.and_utc().timestamp())
+        .unwrap_or(0);
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+fn date_in_name(name: &str) -> Option<NaiveDate> {
+    let bytes = name.as_bytes();
+    (0..bytes.len().saturating_sub(9)).find_map(|i| {
+        name.get(i..i + 10)
+            .and_then(|candidate| NaiveDate::parse_from_str(candidate, "%Y-%m-%d").ok())
+    })
+}
+
+/// Historical events loaded from a directory of recordings
+///
+/// Every file with the format's extension is read (files dated outside the
+/// range are skipped by name), events outside the range are dropped, and
+/// the result is merged into timestamp order so separately recorded depth
+/// and trade files interleave correctly.
+pub struct HistoricalDataSource {
+    dir: PathBuf,
+    format: DataFormat,
+    range: DateRange,
+    symbol: Option<String>,
+}
+
+impl HistoricalDataSource {
+    pub fn new(dir: impl Into<PathBuf>, format: DataFormat, range: DateRange) -> Self {
+        Self {
+            dir: dir.into(),
+            format,
+            range,
+            symbol: None,
+        }
+    }
+
+    /// Only keep JSONL events for this symbol (CSV files carry no symbol)
+    pub fn with_symbol(mut self, symbol: &str) -> Self {
+        self.symbol = Some(symbol.to_uppercase());
+        self
+    }
+
+    /// Files that will be read, in name order
+    pub fn files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)
+            .map_err(|e| anyhow!("Cannot read data dir {}: {}", self.dir.display(), e))?
+        {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            let matches_format = path.extension().and_then(|e| e.to_str()) == Some(self.format.extension());
+            if path.is_file() && matches_format && self.range.includes_file(name) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Load all events in range, sorted by timestamp
+    pub fn load(&self) -> Result<Vec<BacktestEvent>> {
+        let mut events = Vec::new();
+        for path in self.files()? {
+            self.load_file(&path, &mut events)?;
+        }
+
+        events.sort_by_key(BacktestEvent::timestamp);
+        Ok(events)
+    }
+
+    fn load_file(&self, path: &Path, events: &mut Vec<BacktestEvent>) -> Result<()> {
+        let contents = std::fs::read_to_string(path)?;
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let event = match self.format {
+                DataFormat::Jsonl => self.parse_frame(line),
+                DataFormat::Csv => parse_agg_trade_csv(line),
+            };
+            if let Some(event) = event {
+                if self.range.contains(event.timestamp()) {
+                    events.push(event);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_frame(&self, line: &str) -> Option<BacktestEvent> {
+        let mut value: serde_json::Value = serde_json::from_str(line).ok()?;
+
+        // FrameCapture spills wrap the raw text: {"received_ms":..,"frame":"..."}
+        if let Some(frame) = value.get("frame").and_then(|f| f.as_str()) {
+            value = serde_json::from_str(frame).ok()?;
+        }
+
+        let stream = value.get("stream")?.as_str()?.to_string();
+        let data = value.get_mut("data")?.take();
+
+        if stream.contains("depth") {
+            let update: DepthUpdate = serde_json::from_value(data).ok()?;
+            if !self.wants(&update.symbol) {
+                return None;
+            }
+            Some(BacktestEvent::OrderBookUpdate {
+                timestamp: UNIX_EPOCH + Duration::from_millis(update.event_time),
+                bids: update.parse_bids(),
+                asks: update.parse_asks(),
+            })
+        } else if stream.contains("aggTrade") {
+            let agg_trade: AggTrade = serde_json::from_value(data).ok()?;
+            if !self.wants(&agg_trade.symbol) {
+                return None;
+            }
+            let trade = agg_trade.to_trade()?;
+            Some(BacktestEvent::Trade { timestamp: trade.timestamp, trade })
+        } else {
+            None
+        }
+    }
+
+    fn wants(&self, symbol: &str) -> bool {
+        self.symbol.as_deref().is_none_or(|wanted| wanted.eq_ignore_ascii_case(symbol))
+    }
+}
+
+/// agg_trade_id,price,quantity,first_trade_id,last_trade_id,transact_time,is_buyer_maker
+///
+/// Header rows fail to parse and are skipped. Newer spot files use
+/// microsecond timestamps; futures files use milliseconds.
+fn parse_agg_trade_csv(line: &str) -> Option<BacktestEvent> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() < 7 {
+        return None;
+    }
+
+    let id = fields[0].parse::<u64>().ok()?;
+    let price = fields[1].parse::<Decimal>().ok()?;
+    let quantity = fields[2].parse::<Decimal>().ok()?;
+    let time = fields[5].parse::<u64>().ok()?;
+    let is_buyer_maker = fields[6].eq_ignore_ascii_case("true");
+
+    let timestamp = if time > 100_000_000_000_000 {
+        UNIX_EPOCH + Duration::from_micros(time)
+    } else {
+        UNIX_EPOCH + Duration::from_millis(time)
+    };
+
+    let trade = Trade {
+        id,
+        price,
+        quantity,
+        side: if is_buyer_maker { Side::Sell } else { Side::Buy },
+        timestamp,
+        is_buyer_maker,
+    };
+    Some(BacktestEvent::Trade { timestamp, trade })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-01T00:00:00Z and 2024-03-08T00:00:00Z in ms
+    const MAR_1: u64 = 1_709_251_200_000;
+    const MAR_8: u64 = 1_709_856_000_000;
+
+    fn depth_frame(ms: u64, symbol: &str) -> String {
+        format!(
+            r#"{{"stream":"{}@depth@100ms","data":{{"e":"depthUpdate","E":{},"s":"{}","U":1,"u":2,"b":[["100.0","1.0"]],"a":[["100.5","1.0"]]}}}}"#,
+            symbol.to_lowercase(), ms, symbol,
+        )
+    }
+
+    #[test]
+    fn test_date_range_and_file_names() {
+        let range = DateRange::parse("2024-03-01", "2024-03-07").unwrap();
+        assert!(range.contains(UNIX_EPOCH + Duration::from_millis(MAR_1)));
+        assert!(!range.contains(UNIX_EPOCH + Duration::from_millis(MAR_8)));
+
+        assert!(range.includes_file("BTCUSDT-aggTrades-2024-03-07.csv"));
+        assert!(!range.includes_file("BTCUSDT-aggTrades-2024-03-08.csv"));
+        assert!(range.includes_file("recording.jsonl"));
+
+        assert!(DateRange::parse("2024-03-07", "2024-03-01").is_err());
+    }
+
+    #[test]
+    fn test_load_filters_and_merges() {
+        let dir = std::env::temp_dir().join(format!("data_source_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let lines = [
+            depth_frame(MAR_1 + 2_000, "BTCUSDT"),
+            depth_frame(MAR_1 + 1_000, "ETHUSDT"),
+            depth_frame(MAR_8, "BTCUSDT"),
+        ];
+        std::fs::write(dir.join("depth.jsonl"), lines.join("\n")).unwrap();
+        std::fs::write(
+            dir.join("BTCUSDT-aggTrades-2024-03-01.csv"),
+            format!("agg_trade_id,price,quantity,first_trade_id,last_trade_id,transact_time,is_buyer_maker\n7,100.2,0.5,1,1,{},false\n", MAR_1 + 1_000),
+        ).unwrap();
+
+        let range = DateRange::parse("2024-03-01", "2024-03-07").unwrap();
+        let jsonl = HistoricalDataSource::new(&dir, DataFormat::Jsonl, range)
+            .with_symbol("BTCUSDT")
+            .load()
+            .unwrap();
+        assert_eq!(jsonl.len(), 1);
+
+        let csv = HistoricalDataSource::new(&dir, DataFormat::Csv, range).load().unwrap();
+        assert_eq!(csv.len(), 1);
+        assert!(matches!(&csv[0], BacktestEvent::Trade { trade, .. } if trade.side == Side::Buy));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,330 @@
+use crate::storage::DetectorObservation;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A detector output that counts as an emitted signal once its magnitude
+/// reaches `threshold` (e.g. imbalance `z_score` at `imbalance_threshold`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalRule {
+    pub detector: String,
+    pub value: String,
+    pub threshold: f64,
+}
+
+impl SignalRule {
+    pub fn new(detector: &str, value: &str, threshold: f64) -> Self {
+        Self {
+            detector: detector.to_string(),
+            value: value.to_string(),
+            threshold,
+        }
+    }
+
+    /// Signal value if `observation` fires under this rule
+    fn fires(&self, observation: &DetectorObservation) -> Option<f64> {
+        if observation.detector != self.detector {
+            return None;
+        }
+        observation.values.get(&self.value)
+            .copied()
+            .filter(|value| value.abs() >= self.threshold)
+    }
+}
+
+/// How closely the replay has to reproduce the live stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftConfig {
+    /// Max timestamp gap for a replay observation to stand in for a live one
+    pub time_tolerance_ms: u64,
+
+    /// Max absolute difference before a matched value counts as diverged
+    pub value_tolerance: f64,
+
+    pub signals: Vec<SignalRule>,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self {
+            time_tolerance_ms: 50,
+            value_tolerance: 1e-6,
+            signals: Vec::new(),
+        }
+    }
+}
+
+/// A signal one stream emitted and the other did not
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalMismatch {
+    pub detector: String,
+    pub timestamp_ms: u64,
+    /// Signal value on each side (`None` = did not fire or no observation)
+    pub live: Option<f64>,
+    pub replay: Option<f64>,
+}
+
+/// Per-detector agreement between the live and replayed streams
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetectorDrift {
+    pub detector: String,
+    pub live: usize,
+    pub replay: usize,
+    pub matched: usize,
+    /// Live observations with no replay counterpart within tolerance
+    pub missing: usize,
+    /// Replay observations with no live counterpart
+    pub extra: usize,
+    /// Matched pairs where any shared value differs beyond tolerance
+    pub diverged: usize,
+    /// Replay minus live timestamp over matched pairs
+    pub mean_offset_ms: f64,
+    pub max_abs_offset_ms: u64,
+    /// Largest absolute difference per value over matched pairs
+    pub max_value_delta: BTreeMap<String, f64>,
+}
+
+/// Result of diffing a live observation capture against its replay
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// Overlap of the two captures the comparison was restricted to
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub detectors: Vec<DetectorDrift>,
+    /// Matched observations whose relative order differs between the
+    /// streams (e.g. a trade evaluated before a depth update in one engine
+    /// and after it in the other)
+    pub reordered: usize,
+    pub live_signals: usize,
+    pub replay_signals: usize,
+    pub matched_signals: usize,
+    pub signal_mismatches: Vec<SignalMismatch>,
+}
+
+impl DriftReport {
+    pub fn print_summary(&self) {
+        println!("Window: {} .. {} ({}s)", self.start_ms, self.end_ms,
+            self.end_ms.saturating_sub(self.start_ms) / 1000);
+        for drift in &self.detectors {
+            println!("[{}] live {} | replay {} | matched {} | missing {} | extra {} | diverged {}",
+                drift.detector, drift.live, drift.replay, drift.matched,
+                drift.missing, drift.extra, drift.diverged);
+            println!("    offset ms mean {:+.1}, max {}", drift.mean_offset_ms, drift.max_abs_offset_ms);
+            for (name, delta) in &drift.max_value_delta {
+                println!("    max |Δ{}| {:.6}", name, delta);
+            }
+        }
+        println!("Reordered observations: {}", self.reordered);
+        println!("Signals: live {} | replay {} | matched {} | mismatched {}",
+            self.live_signals, self.replay_signals, self.matched_signals, self.signal_mismatches.len());
+    }
+
+    /// Whether the replay reproduced the live stream within tolerance
+    pub fn is_clean(&self) -> bool {
+        self.reordered == 0
+            && self.signal_mismatches.is_empty()
+            && self.detectors.iter().all(|d| d.missing == 0 && d.extra == 0 && d.diverged == 0)
+    }
+}
+
+/// Diff a live capture against the replay of the same session
+///
+/// Observations are paired per detector in time order, each live one with
+/// the next unused replay one within `time_tolerance_ms`. Only the time
+/// range both captures cover is compared, so a replay of part of a
+/// session does not report the rest as missing.
+pub fn compare_observations(
+    live: &[DetectorObservation],
+    replay: &[DetectorObservation],
+    config: &DriftConfig,
+) -> DriftReport {
+    let bounds = |stream: &[DetectorObservation]| {
+        let min = stream.iter().map(|o| o.timestamp_ms).min()?;
+        let max = stream.iter().map(|o| o.timestamp_ms).max()?;
+        Some((min, max))
+    };
+    let (Some((live_start, live_end)), Some((replay_start, replay_end))) = (bounds(live), bounds(replay)) else {
+        return DriftReport::default();
+    };
+    let start_ms = live_start.max(replay_start);
+    let end_ms = live_end.min(replay_end);
+
+    // Keep each stream's emission order: that order is what is compared
+    let index = |stream: &[DetectorObservation]| {
+        let mut by_detector: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, observation) in stream.iter().enumerate() {
+            if (start_ms..=end_ms).contains(&observation.timestamp_ms) {
+                by_detector.entry(observation.detector.clone()).or_default().push(i);
+            }
+        }
+        for indices in by_detector.values_mut() {
+            indices.sort_by_key(|&i| stream[i].timestamp_ms);
+        }
+        by_detector
+    };
+    let live_index = index(live);
+    let replay_index = index(replay);
+
+    let signal = |observation: &DetectorObservation| {
+        config.signals.iter().find_map(|rule| rule.fires(observation))
+    };
+
+    let mut report = DriftReport { start_ms, end_ms, ..Default::default() };
+    let mut pairs = Vec::new();
+    let empty = Vec::new();
+
+    let detectors: std::collections::BTreeSet<&String> = live_index.keys().chain(replay_index.keys()).collect();
+    for detector in detectors {
+        let live_obs = live_index.get(detector).unwrap_or(&empty);
+        let replay_obs = replay_index.get(detector).unwrap_or(&empty);
+        let mut drift = DetectorDrift {
+            detector: detector.clone(),
+            live: live_obs.len(),
+            replay: replay_obs.len(),
+            ..Default::default()
+        };
+        let mut unmatched_replay = Vec::new();
+        let mut offset_sum = 0i64;
+
+        let mut j = 0;
+        for &l in live_obs {
+            let l_time = live[l].timestamp_ms;
+            while j < replay_obs.len() && replay[replay_obs[j]].timestamp_ms + config.time_tolerance_ms < l_time {
+                unmatched_replay.push(replay_obs[j]);
+                j += 1;
+            }
+
+            let candidate = replay_obs.get(j)
+                .filter(|&&r| replay[r].timestamp_ms <= l_time + config.time_tolerance_ms);
+            let Some(&r) = candidate else {
+                drift.missing += 1;
+                if let Some(value) = signal(&live[l]) {
+                    report.signal_mismatches.push(SignalMismatch {
+                        detector: detector.clone(),
+                        timestamp_ms: l_time,
+                        live: Some(value),
+                        replay: None,
+                    });
+                }
+                continue;
+            };
+            j += 1;
+
+            drift.matched += 1;
+            pairs.push((l, r));
+            let offset = replay[r].timestamp_ms as i64 - l_time as i64;
+            offset_sum += offset;
+            drift.max_abs_offset_ms = drift.max_abs_offset_ms.max(offset.unsigned_abs());
+
+            let mut diverged = false;
+            for (name, live_value) in &live[l].values {
+                let Some(replay_value) = replay[r].values.get(name) else {
+                    continue;
+                };
+                let delta = (replay_value - live_value).abs();
+                let max = drift.max_value_delta.entry(name.clone()).or_insert(0.0);
+                *max = max.max(delta);
+                diverged |= delta > config.value_tolerance;
+            }
+            if diverged {
+                drift.diverged += 1;
+            }
+
+            match (signal(&live[l]), signal(&replay[r])) {
+                (Some(_), Some(_)) => report.matched_signals += 1,
+                (None, None) => {}
+                (live_value, replay_value) => report.signal_mismatches.push(SignalMismatch {
+                    detector: detector.clone(),
+                    timestamp_ms: l_time,
+                    live: live_value,
+                    replay: replay_value,
+                }),
+            }
+        }
+        unmatched_replay.extend_from_slice(&replay_obs[j..]);
+
+        drift.extra = unmatched_replay.len();
+        for r in unmatched_replay {
+            if let Some(value) = signal(&replay[r]) {
+                report.signal_mismatches.push(SignalMismatch {
+                    detector: detector.clone(),
+                    timestamp_ms: replay[r].timestamp_ms,
+                    live: None,
+                    replay: Some(value),
+                });
+            }
+        }
+        if drift.matched > 0 {
+            drift.mean_offset_ms = offset_sum as f64 / drift.matched as f64;
+        }
+        report.detectors.push(drift);
+    }
+
+    report.live_signals = live_index.values().flatten().filter(|&&i| signal(&live[i]).is_some()).count();
+    report.replay_signals = replay_index.values().flatten().filter(|&&i| signal(&replay[i]).is_some()).count();
+
+    // Walk matched pairs in live emission order; any pair emitted earlier
+    // in the replay than one before it was reordered
+    pairs.sort_by_key(|(l, _)| *l);
+    let mut latest_replay = None;
+    for (_, r) in pairs {
+        match latest_replay {
+            Some(latest) if r < latest => report.reordered += 1,
+            _ => latest_replay = Some(r),
+        }
+    }
+
+    report.signal_mismatches.sort_by_key(|mismatch| mismatch.timestamp_ms);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn observation(detector: &str, ms: u64, name: &str, value: f64) -> DetectorObservation {
+        DetectorObservation::new(detector, UNIX_EPOCH + Duration::from_millis(ms)).with(name, value)
+    }
+
+    #[test]
+    fn test_reports_timing_ordering_and_signal_drift() {
+        let live = vec![
+            observation("imbalance", 1000, "z_score", 1.0),
+            observation("flow", 1005, "imbalance", 0.2),
+            observation("imbalance", 1100, "z_score", 3.5),
+            observation("imbalance", 1200, "z_score", 0.5),
+            observation("imbalance", 1300, "z_score", 3.2),
+        ];
+        // Replay evaluates the trade ahead of the book, stamps one update
+        // late, drops one and lands just under the threshold on another
+        let replay = vec![
+            observation("flow", 1005, "imbalance", 0.2),
+            observation("imbalance", 1000, "z_score", 1.0),
+            observation("imbalance", 1120, "z_score", 3.5),
+            observation("imbalance", 1300, "z_score", 2.9),
+        ];
+        let config = DriftConfig {
+            time_tolerance_ms: 50,
+            signals: vec![SignalRule::new("imbalance", "z_score", 3.0)],
+            ..Default::default()
+        };
+
+        let report = compare_observations(&live, &replay, &config);
+
+        let imbalance = report.detectors.iter().find(|d| d.detector == "imbalance").unwrap();
+        assert_eq!((imbalance.matched, imbalance.missing, imbalance.extra), (3, 1, 0));
+        assert_eq!(imbalance.max_abs_offset_ms, 20);
+        assert_eq!(imbalance.diverged, 1);
+        assert!((imbalance.max_value_delta["z_score"] - 0.3).abs() < 1e-9);
+
+        assert_eq!(report.reordered, 1);
+        assert_eq!((report.live_signals, report.replay_signals, report.matched_signals), (2, 1, 1));
+        assert_eq!(report.signal_mismatches, vec![SignalMismatch {
+            detector: "imbalance".to_string(),
+            timestamp_ms: 1300,
+            live: Some(3.2),
+            replay: None,
+        }]);
+        assert!(!report.is_clean());
+    }
+}
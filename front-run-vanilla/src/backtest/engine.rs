@@ -1,8 +1,23 @@
 use crate::data::{OrderBook, Trade, Side};
-use crate::strategy::{ImbalanceDetector, FlowAnalyzer, SignalAggregator, CompositeSignal};
-use crate::risk::{Position, PositionManager, RiskManager, RiskLimits};
+use crate::strategy::{
+    ActivityMonitor, ImbalanceDetector, FlowAnalyzer, SignalAggregator, CompositeSignal,
+    MarketQualityGate, ReentryGuard, SizingMode, TakeProfitSchedule, StrategyParams, ThresholdAdjustment,
+    ThresholdCalibrator,
+};
+use crate::backtest::{
+    ExitTiming, FillModel, FillModelConfig, IntrabarOrdering, MetricsSummary, OutageKind,
+    OutageSchedule, OutageStats, OutageWindow, format_metric,
+};
+use crate::backtest::portfolio::{self, PortfolioRisk, SharedPortfolioRisk};
+use crate::exchange::binance::SymbolFilters;
+use crate::exchange::{BookValidator, DepthLimit};
+use crate::storage::{BookSnapshot, ObservationRecorder, market_observation};
+use crate::risk::{
+    EventCalendar, Instrument, Position, PositionManager, RiskManager, RiskLimits, ViolationSeverity,
+};
 use rust_decimal::Decimal;
-use std::time::{SystemTime, Duration};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, SystemTime};
 use serde::{Serialize, Deserialize};
 use anyhow::Result;
 
@@ -11,13 +26,74 @@ use anyhow::Result;
 pub struct BacktestConfig {
     pub symbol: String,
     pub initial_capital: Decimal,
+    /// Entry notional, risk per trade or equity percent, per `sizing`
     pub position_size: Decimal,
-    pub take_profit_bps: Decimal,
-    pub stop_loss_bps: Decimal,
-    pub max_hold_time_ms: u64,
+    /// How `position_size` becomes entry notional, as live; equity is the
+    /// simulated equity including open PnL
+    #[serde(default)]
+    pub sizing: SizingMode,
+    /// Same parameters the live trader reads from `[strategy]`
+    pub strategy: StrategyParams,
     pub slippage_bps: Decimal,
     pub commission_bps: Decimal,
+    /// Fee on resting quotes filled as maker (built-in maker strategies)
+    #[serde(default = "default_maker_commission_bps")]
+    pub maker_commission_bps: Decimal,
     pub latency_ms: u64,
+    #[serde(default)]
+    pub fill_model: FillModelConfig,
+    /// Same-timestamp and exit-check ordering (`[backtest.ordering]`)
+    #[serde(default)]
+    pub ordering: IntrabarOrdering,
+    /// Limits enforced exactly as live, on replay time (`[risk]`)
+    #[serde(default)]
+    pub risk_limits: RiskLimits,
+    /// Scale entry notional 0.5x-2x by signal confidence, as live sizing does
+    #[serde(default)]
+    pub scale_by_confidence: bool,
+    /// Tick/step/min-notional rules from an `exchangeInfo` snapshot; when
+    /// set, simulated orders are rounded and rejected as the exchange would
+    #[serde(default)]
+    pub exchange_filters: Option<SymbolFilters>,
+    /// Contract denomination, as live (`[backtest.instrument]`); inverse
+    /// PnL and fees are converted to USD at the fill price
+    #[serde(default)]
+    pub instrument: Instrument,
+    /// Return bucketing and risk-free rate for the Sharpe ratio
+    #[serde(default)]
+    pub sharpe: SharpeConfig,
+    /// Book levels kept per side, as live (0 = unlimited)
+    #[serde(default)]
+    pub max_book_levels: usize,
+    /// Simulated exchange outages on replay time (`[[backtest.outages]]`)
+    #[serde(default)]
+    pub outages: Vec<OutageWindow>,
+    /// Entry blackouts from `[calendar]`, evaluated at replay time
+    #[serde(skip)]
+    pub calendar: Option<EventCalendar>,
+}
+
+fn default_maker_commission_bps() -> Decimal {
+    Decimal::from(2)
+}
+
+/// How the annualized Sharpe ratio is computed (`[backtest.sharpe]`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharpeConfig {
+    /// Equity is sampled every `bucket_secs` and returns taken between samples
+    pub bucket_secs: u64,
+    /// Annual risk-free rate (0.04 = 4%), de-annualized per bucket
+    pub risk_free_rate: f64,
+}
+
+impl Default for SharpeConfig {
+    fn default() -> Self {
+        Self {
+            bucket_secs: 3600,
+            risk_free_rate: 0.0,
+        }
+    }
 }
 
 impl Default for BacktestConfig {
@@ -26,12 +102,22 @@ impl Default for BacktestConfig {
             symbol: "BTCUSDT".to_string(),
             initial_capital: Decimal::from(10000),
             position_size: Decimal::from(1000),
-            take_profit_bps: Decimal::from(10),
-            stop_loss_bps: Decimal::from(5),
-            max_hold_time_ms: 5000,
+            sizing: SizingMode::FixedNotional,
+            strategy: StrategyParams::default(),
             slippage_bps: Decimal::from(2),
             commission_bps: Decimal::from(4),
+            maker_commission_bps: default_maker_commission_bps(),
             latency_ms: 100,
+            fill_model: FillModelConfig::default(),
+            ordering: IntrabarOrdering::default(),
+            risk_limits: RiskLimits::default(),
+            scale_by_confidence: false,
+            exchange_filters: None,
+            instrument: Instrument::linear(),
+            sharpe: SharpeConfig::default(),
+            max_book_levels: 0,
+            outages: Vec::new(),
+            calendar: None,
         }
     }
 }
@@ -50,6 +136,15 @@ pub enum BacktestEvent {
     },
 }
 
+impl BacktestEvent {
+    pub fn timestamp(&self) -> SystemTime {
+        match self {
+            BacktestEvent::OrderBookUpdate { timestamp, .. } => *timestamp,
+            BacktestEvent::Trade { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
 /// Simulated fill with slippage
 #[derive(Debug, Clone)]
 pub struct SimulatedFill {
@@ -57,82 +152,255 @@ pub struct SimulatedFill {
     pub quantity: Decimal,
     pub slippage: Decimal,
     pub commission: Decimal,
+    pub latency_ms: u64,
+}
+
+/// Order sent to the simulated exchange
+#[derive(Debug, Clone)]
+enum SimulatedOrder {
+    /// Entry for `notional`, with the take profit set when the signal fired
+    Entry { side: Side, notional: Decimal, take_profit_bps: Decimal },
+    /// Market exit of the open position
+    Exit { symbol: String },
+}
+
+/// Order waiting out its sampled latency
+#[derive(Debug, Clone)]
+struct InFlightOrder {
+    order: SimulatedOrder,
+    /// Replay time the order reaches the exchange and fills
+    due: SystemTime,
+    latency_ms: u64,
 }
 
 /// Backtesting engine
 pub struct BacktestEngine {
     config: BacktestConfig,
     orderbook: OrderBook,
+    book_validator: BookValidator,
+    depth_limit: Option<DepthLimit>,
     position_manager: PositionManager,
-    risk_manager: RiskManager,
+    /// Own risk state, or the portfolio's when run as one of several symbols
+    risk: SharedPortfolioRisk,
+    market_quality: MarketQualityGate,
+    take_profit: TakeProfitSchedule,
+    calendar: Option<EventCalendar>,
+    fill_model: FillModel,
+    /// Order sent but not yet filled; at most one at a time
+    in_flight: Option<InFlightOrder>,
+    outages: OutageSchedule,
+    /// Decision time of an exit waiting out a no-fill window
+    pending_exit: Option<SystemTime>,
+    outage_stats: OutageStats,
     
     // Signal generators
     imbalance_detector: ImbalanceDetector,
     flow_analyzer: FlowAnalyzer,
     signal_aggregator: SignalAggregator,
+    calibrator: ThresholdCalibrator,
+    activity: ActivityMonitor,
+    reentry: ReentryGuard,
+    /// Detector outputs and market samples for labeling
+    recorder: Option<ObservationRecorder>,
     
     // State tracking
     current_time: SystemTime,
     equity: Decimal,
     equity_curve: Vec<(SystemTime, Decimal)>,
+    /// Keep every equity point, or only the latest
+    keep_equity_curve: bool,
     trades: Vec<BacktestTrade>,
+    risk_events: Vec<RiskEvent>,
+    risk_blocked_entries: usize,
+    filter_rejected_entries: usize,
+    reentry_blocked_entries: usize,
 }
 
 impl BacktestEngine {
     pub fn new(config: BacktestConfig) -> Self {
         let orderbook = OrderBook::new(&config.symbol);
+        let book_validator = BookValidator::new(&config.symbol);
+        let depth_limit = (config.max_book_levels > 0).then(|| DepthLimit::new(config.max_book_levels));
         let position_manager = PositionManager::new();
+        let risk = PortfolioRisk::shared(
+            RiskManager::new(config.risk_limits.clone(), config.initial_capital),
+        );
+        let market_quality = config.strategy.market_quality_gate();
+        let take_profit = config.strategy.take_profit_schedule();
+        let fill_model = FillModel::new(config.fill_model.clone());
+        let outages = OutageSchedule::new(config.outages.clone());
+        let calendar = config.calendar.clone();
         
-        let risk_limits = RiskLimits {
-            max_position_size: config.position_size * Decimal::from(5),
-            max_portfolio_exposure: config.initial_capital,
-            max_daily_loss: config.initial_capital * Decimal::from_f64_retain(0.05).unwrap(),
-            max_drawdown_percent: Decimal::from(10),
-            max_trades_per_hour: 30,
-            max_trades_per_day: 200,
-            max_acceptable_latency_ms: 500,
-        };
-        
-        let risk_manager = RiskManager::new(risk_limits, config.initial_capital);
-        
-        let imbalance_detector = ImbalanceDetector::new(5, 100, 3.0);
-        let flow_analyzer = FlowAnalyzer::new(20, 5000, 0.6);
-        let signal_aggregator = SignalAggregator::new(3.0, 1.5, 2);
+        let imbalance_detector = config.strategy.imbalance_detector();
+        let flow_analyzer = config.strategy.flow_analyzer();
+        let signal_aggregator = config.strategy.signal_aggregator();
+        let calibrator = config.strategy.threshold_calibrator();
+        let activity = config.strategy.activity_monitor();
+        let reentry = config.strategy.reentry_guard();
+
+        let initial_capital = config.initial_capital;
 
         Self {
             config,
             orderbook,
+            book_validator,
+            depth_limit,
             position_manager,
-            risk_manager,
+            risk,
+            market_quality,
+            take_profit,
+            calendar,
+            fill_model,
+            in_flight: None,
+            outages,
+            pending_exit: None,
+            outage_stats: OutageStats::default(),
             imbalance_detector,
             flow_analyzer,
             signal_aggregator,
+            calibrator,
+            activity,
+            reentry,
+            recorder: None,
             current_time: SystemTime::UNIX_EPOCH,
-            equity: config.initial_capital,
+            equity: initial_capital,
             equity_curve: vec![],
+            keep_equity_curve: true,
             trades: vec![],
+            risk_events: vec![],
+            risk_blocked_entries: 0,
+            filter_rejected_entries: 0,
+            reentry_blocked_entries: 0,
         }
     }
 
+    /// Block entries around scheduled events (evaluated at replay time)
+    pub fn with_calendar(mut self, calendar: EventCalendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// Record every detector output plus a market sample per evaluation,
+    /// as the live trader does with `record_observations`
+    pub fn with_recorder(mut self, recorder: ObservationRecorder) -> Self {
+        self.imbalance_detector = self.imbalance_detector.with_recorder(recorder.clone());
+        self.flow_analyzer = self.flow_analyzer.with_recorder(recorder.clone());
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Keep only the latest equity point, for engines run indefinitely on
+    /// a live feed (drawdown in the results is then meaningless)
+    pub fn without_equity_curve(mut self) -> Self {
+        self.keep_equity_curve = false;
+        self
+    }
+
+    /// Share risk limits and exposure with other engines in a portfolio
+    pub fn with_shared_risk(mut self, risk: SharedPortfolioRisk) -> Self {
+        self.risk = risk;
+        self
+    }
+
+    /// Base entry notional
+    pub fn position_size(&self) -> Decimal {
+        self.config.position_size
+    }
+
+    /// Change the base entry notional (portfolio rebalancing)
+    pub fn set_position_size(&mut self, position_size: Decimal) {
+        self.config.position_size = position_size;
+    }
+
+    /// Start from a recorded book instead of an empty one
+    ///
+    /// Diff-only recordings otherwise need a while before the book (and
+    /// every depth-based signal) looks like the live one.
+    pub fn warm_start(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        self.book_validator.check_symbol(&snapshot.symbol)?;
+        snapshot.restore(&self.orderbook)?;
+        self.enforce_depth_limit()
+    }
+
+    /// Round and reject simulated orders by the exchange's filters
+    pub fn set_exchange_filters(&mut self, filters: SymbolFilters) {
+        self.config.exchange_filters = Some(filters);
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.config.symbol
+    }
+
+    /// Completed trades so far
+    pub fn trades(&self) -> &[BacktestTrade] {
+        &self.trades
+    }
+
+    pub fn mid_price(&self) -> Option<Decimal> {
+        self.orderbook.get_mid_price()
+    }
+
+    /// Realized equity plus open PnL at the current mid
+    pub fn current_equity(&self) -> Decimal {
+        self.equity_curve.last().map_or(self.equity, |(_, equity)| *equity)
+    }
+
+    /// Process an event tagged with the symbol it was recorded for
+    ///
+    /// Fails without touching the book if the symbol is not this engine's.
+    pub fn process_event_for(&mut self, symbol: &str, event: BacktestEvent) -> Result<()> {
+        self.book_validator.check_symbol(symbol)?;
+        self.process_event(event)
+    }
+
     /// Process a single market event
+    ///
+    /// Book updates run exits and entries in the order set by
+    /// `config.ordering.exit_timing`, then record equity. Orders fill once
+    /// their sampled latency has passed, at the mid of the book as it
+    /// stood then.
     pub fn process_event(&mut self, event: BacktestEvent) -> Result<()> {
+        // Daily risk window starts at the first replayed event
+        if self.current_time == SystemTime::UNIX_EPOCH {
+            portfolio::lock(&self.risk).start_at(event.timestamp());
+        }
+
+        // Orders sent earlier fill on the book as it stood when they arrived
+        self.fill_due_order(event.timestamp())?;
+
+        // Data outage: the strategy sees nothing, but the book keeps up
+        // and equity is still marked at the true mid
+        if self.outages.active(OutageKind::FrozenData, event.timestamp()) {
+            self.current_time = event.timestamp();
+            self.outage_stats.frozen_events += 1;
+            if let BacktestEvent::OrderBookUpdate { bids, asks, .. } = event {
+                self.apply_book_update(bids, asks)?;
+                self.record_equity();
+            }
+            return Ok(());
+        }
+
         match event {
             BacktestEvent::OrderBookUpdate { timestamp, bids, asks } => {
                 self.current_time = timestamp;
-                
-                // Update order book
-                for (price, qty) in bids {
-                    self.orderbook.update_level(Side::Buy, price, qty)?;
-                }
-                for (price, qty) in asks {
-                    self.orderbook.update_level(Side::Sell, price, qty)?;
-                }
 
-                // Check for signals
-                self.check_signals()?;
-
-                // Check exits for open positions
-                self.check_exits()?;
+                match self.config.ordering.exit_timing {
+                    ExitTiming::BeforeBookUpdate => {
+                        self.check_exits()?;
+                        self.apply_book_update(bids, asks)?;
+                        self.check_signals()?;
+                    }
+                    ExitTiming::BeforeEntries => {
+                        self.apply_book_update(bids, asks)?;
+                        self.check_exits()?;
+                        self.check_signals()?;
+                    }
+                    ExitTiming::AfterEntries => {
+                        self.apply_book_update(bids, asks)?;
+                        self.check_signals()?;
+                        self.check_exits()?;
+                    }
+                }
 
                 // Record equity
                 self.record_equity();
@@ -151,18 +419,60 @@ impl BacktestEngine {
         Ok(())
     }
 
+    fn apply_book_update(
+        &mut self,
+        bids: Vec<(Decimal, Decimal)>,
+        asks: Vec<(Decimal, Decimal)>,
+    ) -> Result<()> {
+        for (price, qty) in &bids {
+            self.orderbook.update_level(Side::Buy, *price, *qty)?;
+        }
+        for (price, qty) in &asks {
+            self.orderbook.update_level(Side::Sell, *price, *qty)?;
+        }
+        self.book_validator.resolve_crossed(&self.orderbook, &bids, &asks)?;
+        self.enforce_depth_limit()
+    }
+
+    fn enforce_depth_limit(&self) -> Result<()> {
+        if let Some(limit) = &self.depth_limit {
+            limit.enforce(&self.orderbook)?;
+        }
+        Ok(())
+    }
+
     /// Check for trading signals
     fn check_signals(&mut self) -> Result<()> {
+        let mid = self.orderbook.get_mid_price();
+
+        // Score earlier signals and retune thresholds on replay time
+        if let (true, Some(mid)) = (self.calibrator.is_enabled(), mid) {
+            self.calibrator.record_mid(mid, self.current_time);
+            if let Some(adjustment) = self.calibrator.adjust(&mut self.signal_aggregator, self.current_time) {
+                self.imbalance_detector.set_threshold(adjustment.primary_to);
+            }
+        }
+
+        if let Some(recorder) = &self.recorder {
+            if let Some(observation) = market_observation(&self.orderbook, self.current_time) {
+                recorder.record(observation);
+            }
+        }
+
         let mut signals = Vec::new();
 
         // Check imbalance
-        if let Some(signal) = self.imbalance_detector.calculate_signal(&self.orderbook) {
+        if let Some(signal) = self.imbalance_detector.calculate_signal_at(&self.orderbook, self.current_time) {
             signals.push(signal);
         }
 
         // Aggregate signals
         if let Some(composite) = self.signal_aggregator.aggregate(signals) {
-            if composite.is_tradeable(2) {
+            if composite.is_tradeable(self.config.strategy.min_confirming_signals) {
+                if let Some(mid) = mid {
+                    self.calibrator.record_signal(&composite, mid, self.current_time);
+                }
+                self.activity.record_signal(self.current_time);
                 self.execute_signal(composite)?;
             }
         }
@@ -171,7 +481,7 @@ impl BacktestEngine {
     }
 
     /// Process individual signal
-    fn process_signal(&mut self, signal: crate::data::Signal) -> Result<()> {
+    fn process_signal(&mut self, _signal: crate::data::Signal) -> Result<()> {
         // In backtesting, we aggregate all signals before executing
         // This is handled in check_signals()
         Ok(())
@@ -179,37 +489,130 @@ impl BacktestEngine {
 
     /// Execute a trading signal
     fn execute_signal(&mut self, signal: CompositeSignal) -> Result<()> {
-        // Don't trade if already have position
-        if self.position_manager.position_count() > 0 {
+        // Don't trade if already have position (or one is on its way)
+        if self.position_manager.position_count() > 0 || self.in_flight.is_some() {
             return Ok(());
         }
 
-        // Check risk limits
-        let position_size = self.config.position_size;
-        let current_exposure = self.position_manager.total_exposure();
-        
-        if let Err(_) = self.risk_manager.can_open_position(position_size, current_exposure) {
-            return Ok(()); // Skip trade if risk check fails
+        // Size as live: sizing mode first, then confidence scaling
+        let Some(base_notional) = self.config.sizing.base_notional(
+            self.config.position_size,
+            self.config.strategy.stop_loss_bps(),
+            self.current_equity(),
+        ) else {
+            return Ok(());
+        };
+        let position_size = if self.config.scale_by_confidence {
+            let multiplier = Decimal::from_f64_retain(0.5 + 1.5 * signal.confidence)
+                .unwrap_or(Decimal::ONE);
+            base_notional * multiplier
+        } else {
+            base_notional
+        };
+
+        // Skip trade if risk check fails, same as live; exposure is combined
+        // across every symbol sharing this risk state
+        let check = {
+            let mut risk = portfolio::lock(&self.risk);
+            let combined_exposure = risk.total_exposure();
+            risk.risk_manager_mut().can_open_position_at(
+                position_size,
+                combined_exposure,
+                self.current_time,
+            )
+        };
+        if let Err(violation) = check {
+            self.record_risk_violation(violation.reason, violation.severity);
+            return Ok(());
+        }
+
+        // Skip trade inside a scheduled event blackout
+        if let Some(calendar) = &self.calendar {
+            if !calendar.entries_allowed(self.current_time) {
+                return Ok(());
+            }
+        }
+
+        // Skip trade if the book is too wide or too thin
+        if self.market_quality.check(&self.orderbook).is_err() {
+            return Ok(());
+        }
+
+        // No entry while the exchange is not filling or accepting orders
+        if self.outages.active(OutageKind::NoFills, self.current_time)
+            || self.outages.active(OutageKind::RejectOrders, self.current_time)
+        {
+            self.outage_stats.blocked_entries += 1;
+            return Ok(());
         }
 
         // Get current price
         let current_price = self.orderbook.get_mid_price()
             .ok_or_else(|| anyhow::anyhow!("No mid price available"))?;
 
-        // Simulate fill with slippage and latency
-        let fill = self.simulate_fill(signal.direction, current_price, position_size)?;
+        // Not back into the level the last trade in this direction left
+        if self.reentry.check(signal.direction, current_price, self.current_time).is_err() {
+            self.reentry_blocked_entries += 1;
+            return Ok(());
+        }
+
+        // Strength-scaled take profit (same rule as live)
+        self.submit(SimulatedOrder::Entry {
+            side: signal.direction,
+            notional: position_size,
+            take_profit_bps: self.take_profit.take_profit_bps(signal.overall_strength),
+        })
+    }
+
+    /// Fill an entry that reached the exchange at `price`
+    fn fill_entry(
+        &mut self,
+        side: Side,
+        price: Decimal,
+        position_size: Decimal,
+        take_profit_bps: Decimal,
+        latency_ms: u64,
+    ) -> Result<()> {
+        // Simulate fill with slippage
+        let fill = self.simulate_fill(side, price, position_size, latency_ms)?;
+
+        // Round to the exchange's step and skip orders it would reject
+        let mut quantity = fill.quantity;
+        if let Some(filters) = &self.config.exchange_filters {
+            quantity = filters.round_quantity(quantity);
+            if filters.check(quantity, fill.price).is_err() {
+                self.filter_rejected_entries += 1;
+                return Ok(());
+            }
+        }
+        portfolio::lock(&self.risk).risk_manager_mut().record_latency(fill.latency_ms);
+
+        // Entries may fill only partially; fees scale with the filled part
+        let fill_ratio = self.fill_model.sample_fill_ratio();
+        let mut filled = quantity * fill_ratio;
+        if let Some(filters) = &self.config.exchange_filters {
+            filled = filters.round_quantity(filled);
+        }
+        // Below one lot (or one inverse contract)
+        if filled.is_zero() {
+            return Ok(());
+        }
+        let fees = self.config.instrument.notional(filled, fill.price)
+            * (self.config.commission_bps / Decimal::from(10000));
 
-        // Create position
-        let quantity = position_size / fill.price;
         let position = Position::new(
             self.config.symbol.clone(),
-            signal.direction,
+            side,
             fill.price,
-            quantity,
-            fill.commission,
-        );
+            filled,
+            fees,
+        )
+        .with_instrument(self.config.instrument)
+        .with_entry_time(self.current_time)
+        .with_take_profit(take_profit_bps);
 
         self.position_manager.open_position(position)?;
+        self.publish_exposure();
 
         Ok(())
     }
@@ -221,16 +624,49 @@ impl BacktestEngine {
             None => return Ok(()),
         };
 
+        // An exit sent during a no-fill window fills once it is over
+        if self.pending_exit.is_some() && !self.outages.active(OutageKind::NoFills, self.current_time) {
+            for position in self.position_manager.open_positions().to_vec() {
+                self.close_position(&position.symbol)?;
+            }
+        }
+
         let positions = self.position_manager.open_positions().to_vec();
 
+        // Open losses count toward drawdown and daily loss; flatten on breach
+        let settlement_usd = self.config.instrument.settlement_usd(current_price, Decimal::ONE);
+        let unrealized = self.position_manager
+            .total_unrealized_pnl(&[(self.config.symbol.clone(), current_price)]) * settlement_usd;
+        let marked = portfolio::lock(&self.risk).mark_to_market(&self.config.symbol, unrealized);
+        if let (Err(violation), false) = (marked, positions.is_empty()) {
+            self.record_risk_event(violation.reason, violation.severity);
+            for position in positions {
+                self.close_position(&position.symbol)?;
+            }
+            return Ok(());
+        }
+
+        let stop_loss_bps = self.config.strategy.stop_loss_bps();
+        let max_hold_time_ms = self.config.strategy.max_hold_time_ms;
+        let imbalance_z = self.imbalance_detector.last_z_score();
+
         for position in positions {
+            let take_profit_bps = position.take_profit_bps
+                .unwrap_or_else(|| self.config.strategy.take_profit_bps());
+            let loss_capped = portfolio::lock(&self.risk).risk_manager()
+                .check_trade_loss(position.unrealized_pnl(current_price) * settlement_usd)
+                .is_err();
             let should_exit = 
-                position.take_profit_hit(current_price, self.config.take_profit_bps) ||
-                position.stop_loss_hit(current_price, self.config.stop_loss_bps) ||
-                position.is_expired(self.config.max_hold_time_ms);
+                position.take_profit_hit(current_price, take_profit_bps) ||
+                position.stop_loss_hit(current_price, stop_loss_bps) ||
+                position.is_expired_at(max_hold_time_ms, self.current_time) ||
+                loss_capped ||
+                self.config.strategy.signal_decay_exit_z
+                    .zip(imbalance_z)
+                    .is_some_and(|(exit_z, z_score)| position.signal_decayed(z_score, exit_z));
 
             if should_exit {
-                self.close_position(&position.symbol, current_price)?;
+                self.close_position(&position.symbol)?;
             }
         }
 
@@ -238,14 +674,45 @@ impl BacktestEngine {
     }
 
     /// Close a position
-    fn close_position(&mut self, symbol: &str, current_price: Decimal) -> Result<()> {
+    ///
+    /// Rejected during an order outage (the next exit check retries);
+    /// during a no-fill window the exit waits and fills after it.
+    fn close_position(&mut self, symbol: &str) -> Result<()> {
+        // The exit already sent is still on its way
+        if self.in_flight.is_some() {
+            return Ok(());
+        }
+        if self.outages.active(OutageKind::RejectOrders, self.current_time) {
+            self.outage_stats.rejected_exits += 1;
+            return Ok(());
+        }
+        if self.outages.active(OutageKind::NoFills, self.current_time) {
+            self.pending_exit.get_or_insert(self.current_time);
+            return Ok(());
+        }
+        if let Some(decided) = self.pending_exit.take() {
+            let delay_ms = self.current_time.duration_since(decided).unwrap_or_default().as_millis() as u64;
+            self.outage_stats.delayed_exits += 1;
+            self.outage_stats.max_exit_delay_ms = self.outage_stats.max_exit_delay_ms.max(delay_ms);
+        }
+
+        self.submit(SimulatedOrder::Exit { symbol: symbol.to_string() })
+    }
+
+    /// Fill an exit that reached the exchange at `current_price`
+    fn fill_exit(&mut self, symbol: &str, current_price: Decimal, latency_ms: u64) -> Result<()> {
         let position = self.position_manager.get_position(symbol)
+            .cloned()
             .ok_or_else(|| anyhow::anyhow!("Position not found"))?;
 
-        let position_size = position.entry_price * position.quantity;
+        let overrun_ms = (position.age_at(self.current_time).as_millis() as u64)
+            .saturating_sub(self.config.strategy.max_hold_time_ms);
+        self.outage_stats.max_hold_overrun_ms = self.outage_stats.max_hold_overrun_ms.max(overrun_ms);
+
+        let position_size = position.notional_value();
 
         // Simulate fill
-        let fill = self.simulate_fill(position.side.opposite(), current_price, position_size)?;
+        let fill = self.simulate_fill(position.side.opposite(), current_price, position_size, latency_ms)?;
 
         // Close position
         let realized_pnl = self.position_manager.close_position(
@@ -254,9 +721,19 @@ impl BacktestEngine {
             fill.commission,
         )?;
 
-        // Record trade
-        self.risk_manager.record_trade(realized_pnl);
+        // Record trade (in USD)
+        let settlement_usd = self.config.instrument.settlement_usd(fill.price, Decimal::ONE);
+        let realized_pnl = realized_pnl * settlement_usd;
+        portfolio::lock(&self.risk).record_trade_at(symbol, realized_pnl, self.current_time);
+        self.publish_exposure();
         self.equity += realized_pnl;
+        self.reentry.record_exit(position.side, fill.price, self.current_time);
+
+        // Frequency spiking while edge fades is logged as a risk warning
+        self.activity.record_trade(realized_pnl, position_size, self.current_time);
+        if let Some(warning) = self.activity.check(self.current_time) {
+            self.record_risk_event(warning.reason, warning.severity);
+        }
 
         // Store trade for analysis
         self.trades.push(BacktestTrade {
@@ -267,35 +744,113 @@ impl BacktestEngine {
             exit_price: fill.price,
             quantity: position.quantity,
             pnl: realized_pnl,
-            fees: position.fees_paid + fill.commission,
+            fees: (position.fees_paid + fill.commission) * settlement_usd,
         });
 
         Ok(())
     }
 
+    /// Send an order; it fills after a latency drawn from the fill model
+    fn submit(&mut self, order: SimulatedOrder) -> Result<()> {
+        let latency_ms = self.fill_model.sample_latency_ms(self.config.latency_ms);
+        let order = InFlightOrder {
+            order,
+            due: self.current_time + Duration::from_millis(latency_ms),
+            latency_ms,
+        };
+        if latency_ms == 0 {
+            return self.fill_order(order);
+        }
+        self.in_flight = Some(order);
+        Ok(())
+    }
+
+    /// Fill the in-flight order if it is due by `now`
+    ///
+    /// Runs before the event stamped `now` is applied, so the fill sees the
+    /// last book at or before the order's arrival.
+    fn fill_due_order(&mut self, now: SystemTime) -> Result<()> {
+        match self.in_flight.take_if(|order| order.due <= now) {
+            Some(order) => self.fill_order(order),
+            None => Ok(()),
+        }
+    }
+
+    fn fill_order(&mut self, order: InFlightOrder) -> Result<()> {
+        // Nothing to fill against yet: keep waiting
+        let Some(price) = self.orderbook.get_mid_price() else {
+            self.in_flight = Some(order);
+            return Ok(());
+        };
+        self.current_time = self.current_time.max(order.due);
+
+        match order.order {
+            SimulatedOrder::Entry { side, notional, take_profit_bps } => {
+                self.fill_entry(side, price, notional, take_profit_bps, order.latency_ms)
+            }
+            SimulatedOrder::Exit { symbol } => self.fill_exit(&symbol, price, order.latency_ms),
+        }
+    }
+
+    fn publish_exposure(&self) {
+        let exposure = self.position_manager.total_exposure();
+        portfolio::lock(&self.risk).set_exposure(&self.config.symbol, exposure);
+    }
+
+    /// Count a blocked entry; log it when the reason changes
+    ///
+    /// Repeated blocks for the same reason (e.g. every signal while the
+    /// hourly limit is hit) are counted but recorded once.
+    fn record_risk_violation(&mut self, reason: String, severity: ViolationSeverity) {
+        self.risk_blocked_entries += 1;
+        self.record_risk_event(reason, severity);
+    }
+
+    /// Log a risk decision unless it repeats the previous one
+    fn record_risk_event(&mut self, reason: String, severity: ViolationSeverity) {
+        // Halted reasons embed the halt cause, so compare the full text
+        if self.risk_events.last().is_none_or(|last| last.reason != reason) {
+            self.risk_events.push(RiskEvent {
+                time: self.current_time,
+                reason,
+                severity,
+            });
+        }
+    }
+
     /// Simulate order fill with slippage and commission
     fn simulate_fill(
-        &self,
+        &mut self,
         side: Side,
         price: Decimal,
         notional: Decimal,
+        latency_ms: u64,
     ) -> Result<SimulatedFill> {
-        // Add slippage (unfavorable for us)
-        let slippage_factor = self.config.slippage_bps / Decimal::from(10000);
+        // Add slippage (unfavorable for us), plus the adverse move when this
+        // fill was picked off
+        let adverse_bps = Decimal::from_f64_retain(self.fill_model.sample_adverse_bps())
+            .unwrap_or_default();
+        let slippage_factor = (self.config.slippage_bps + adverse_bps) / Decimal::from(10000);
         let slippage = match side {
             Side::Buy => price * slippage_factor,   // Pay more
             Side::Sell => -(price * slippage_factor), // Receive less
         };
 
-        let filled_price = price + slippage;
-        let quantity = notional / filled_price;
-        let commission = notional * (self.config.commission_bps / Decimal::from(10000));
+        let filled_price = match &self.config.exchange_filters {
+            Some(filters) => filters.round_price(side, price + slippage),
+            None => self.config.instrument.round_price(price + slippage),
+        };
+        let quantity = self.config.instrument.quantity(notional, filled_price, Decimal::ONE);
+        // Fees are charged in the settlement asset
+        let commission = notional * (self.config.commission_bps / Decimal::from(10000))
+            / self.config.instrument.settlement_usd(filled_price, Decimal::ONE);
 
         Ok(SimulatedFill {
             price: filled_price,
             quantity,
             slippage: slippage.abs(),
             commission,
+            latency_ms,
         })
     }
 
@@ -306,20 +861,32 @@ impl BacktestEngine {
 
         let unrealized_pnl = self.position_manager.total_unrealized_pnl(
             &[(self.config.symbol.clone(), current_price)]
-        );
+        ) * self.config.instrument.settlement_usd(current_price, Decimal::ONE);
 
         let total_equity = self.equity + unrealized_pnl;
+        if !self.keep_equity_curve {
+            self.equity_curve.clear();
+        }
         self.equity_curve.push((self.current_time, total_equity));
     }
 
     /// Get backtest results
     pub fn get_results(&self) -> BacktestResults {
-        BacktestResults::new(
+        let mut results = BacktestResults::new(
             self.config.clone(),
             self.trades.clone(),
             self.equity_curve.clone(),
             self.equity,
-        )
+        );
+        results.risk_events = self.risk_events.clone();
+        results.risk_blocked_entries = self.risk_blocked_entries;
+        results.filter_rejected_entries = self.filter_rejected_entries;
+        results.reentry_blocked_entries = self.reentry_blocked_entries;
+        results.crossed_books = self.book_validator.metrics().crossed_books.load(Ordering::Relaxed);
+        results.threshold_adjustments = self.calibrator.adjustments().to_vec();
+        results.halt_reason = portfolio::lock(&self.risk).risk_manager().halt_reason().map(str::to_string);
+        results.outages = self.outage_stats.clone();
+        results
     }
 }
 
@@ -336,6 +903,14 @@ pub struct BacktestTrade {
     pub fees: Decimal,
 }
 
+/// Risk manager decision during replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskEvent {
+    pub time: SystemTime,
+    pub reason: String,
+    pub severity: ViolationSeverity,
+}
+
 /// Backtest results with metrics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BacktestResults {
@@ -348,15 +923,34 @@ pub struct BacktestResults {
     pub total_trades: usize,
     pub winning_trades: usize,
     pub losing_trades: usize,
-    pub win_rate: f64,
-    pub profit_factor: f64,
-    pub average_win: Decimal,
-    pub average_loss: Decimal,
-    pub largest_win: Decimal,
-    pub largest_loss: Decimal,
     pub max_drawdown: Decimal,
     pub max_drawdown_pct: Decimal,
-    pub sharpe_ratio: f64,
+    /// Win rate, profit factor, Sharpe and per-trade statistics
+    pub metrics: MetricsSummary,
+    /// Entries refused by the risk manager
+    #[serde(default)]
+    pub risk_blocked_entries: usize,
+    /// Distinct risk blocks and halts, in replay order
+    #[serde(default)]
+    pub risk_events: Vec<RiskEvent>,
+    /// Entries the exchange filters would have rejected
+    #[serde(default)]
+    pub filter_rejected_entries: usize,
+    /// Entries blocked near the last same-direction exit (`[strategy.reentry]`)
+    #[serde(default)]
+    pub reentry_blocked_entries: usize,
+    /// Book updates that left the book crossed and were repaired
+    #[serde(default)]
+    pub crossed_books: u64,
+    /// Online calibration changes (`[strategy.calibration]`)
+    #[serde(default)]
+    pub threshold_adjustments: Vec<ThresholdAdjustment>,
+    /// Set if the circuit breaker was tripped at the end of the run
+    #[serde(default)]
+    pub halt_reason: Option<String>,
+    /// Cost of `[[backtest.outages]]`, plus the hold-time overrun of exits
+    #[serde(default)]
+    pub outages: OutageStats,
 }
 
 impl BacktestResults {
@@ -369,55 +963,13 @@ impl BacktestResults {
         let total_return = final_equity - config.initial_capital;
         let total_return_pct = (total_return / config.initial_capital) * Decimal::from(100);
         
-        let winning_trades: Vec<_> = trades.iter()
-            .filter(|t| t.pnl > Decimal::ZERO)
-            .collect();
-        let losing_trades: Vec<_> = trades.iter()
-            .filter(|t| t.pnl < Decimal::ZERO)
-            .collect();
-
-        let win_rate = if trades.is_empty() {
-            0.0
-        } else {
-            winning_trades.len() as f64 / trades.len() as f64
-        };
-
-        let total_wins: Decimal = winning_trades.iter().map(|t| t.pnl).sum();
-        let total_losses: Decimal = losing_trades.iter().map(|t| t.pnl.abs()).sum();
-
-        let profit_factor = if total_losses.is_zero() {
-            if total_wins.is_zero() { 0.0 } else { f64::INFINITY }
-        } else {
-            (total_wins / total_losses).to_string().parse().unwrap_or(0.0)
-        };
-
-        let average_win = if winning_trades.is_empty() {
-            Decimal::ZERO
-        } else {
-            total_wins / Decimal::from(winning_trades.len())
-        };
-
-        let average_loss = if losing_trades.is_empty() {
-            Decimal::ZERO
-        } else {
-            total_losses / Decimal::from(losing_trades.len())
-        };
-
-        let largest_win = winning_trades.iter()
-            .map(|t| t.pnl)
-            .max()
-            .unwrap_or(Decimal::ZERO);
-
-        let largest_loss = losing_trades.iter()
-            .map(|t| t.pnl)
-            .min()
-            .unwrap_or(Decimal::ZERO);
+        let winning_trades = trades.iter().filter(|t| t.pnl > Decimal::ZERO).count();
+        let losing_trades = trades.iter().filter(|t| t.pnl < Decimal::ZERO).count();
 
         // Calculate max drawdown
         let (max_dd, max_dd_pct) = Self::calculate_max_drawdown(&equity_curve, config.initial_capital);
 
-        // Calculate Sharpe ratio (simplified, assuming 0 risk-free rate)
-        let sharpe_ratio = Self::calculate_sharpe_ratio(&trades);
+        let metrics = MetricsSummary::new(&trades, &equity_curve, config.initial_capital, &config.sharpe);
 
         Self {
             config,
@@ -427,17 +979,19 @@ impl BacktestResults {
             total_return,
             total_return_pct,
             total_trades: trades.len(),
-            winning_trades: winning_trades.len(),
-            losing_trades: losing_trades.len(),
-            win_rate,
-            profit_factor,
-            average_win,
-            average_loss,
-            largest_win,
-            largest_loss,
+            winning_trades,
+            losing_trades,
             max_drawdown: max_dd,
             max_drawdown_pct: max_dd_pct,
-            sharpe_ratio,
+            metrics,
+            risk_blocked_entries: 0,
+            risk_events: Vec::new(),
+            filter_rejected_entries: 0,
+            reentry_blocked_entries: 0,
+            crossed_books: 0,
+            threshold_adjustments: Vec::new(),
+            halt_reason: None,
+            outages: OutageStats::default(),
         }
     }
 
@@ -468,30 +1022,6 @@ impl BacktestResults {
         (max_dd, max_dd_pct)
     }
 
-    fn calculate_sharpe_ratio(trades: &[BacktestTrade]) -> f64 {
-        if trades.len() < 2 {
-            return 0.0;
-        }
-
-        let returns: Vec<f64> = trades.iter()
-            .map(|t| t.pnl.to_string().parse::<f64>().unwrap_or(0.0))
-            .collect();
-
-        let mean_return = returns.iter().sum::<f64>() / returns.len() as f64;
-        
-        let variance = returns.iter()
-            .map(|r| (r - mean_return).powi(2))
-            .sum::<f64>() / returns.len() as f64;
-
-        let std_dev = variance.sqrt();
-
-        if std_dev == 0.0 {
-            0.0
-        } else {
-            mean_return / std_dev
-        }
-    }
-
     /// Print results summary
     pub fn print_summary(&self) {
         println!("\n╔════════════════════════════════════════════════╗");
@@ -506,24 +1036,53 @@ impl BacktestResults {
         println!("║ Total Trades: {:<34} ║", self.total_trades);
         println!("║ Winning Trades: {:<32} ║", self.winning_trades);
         println!("║ Losing Trades: {:<33} ║", self.losing_trades);
-        println!("║ Win Rate: {:<36.2}% ║", self.win_rate * 100.0);
-        println!("║ Profit Factor: {:<33.2} ║", self.profit_factor);
+        println!("║ Win Rate: {:<37} ║", percent(self.metrics.win_rate.map(|rate| rate * 100.0)));
+        println!("║ Profit Factor: {:<33} ║", self.metrics.profit_factor);
         println!("╠════════════════════════════════════════════════╣");
-        println!("║ Average Win: ${:<32} ║", self.average_win);
-        println!("║ Average Loss: ${:<31} ║", self.average_loss);
-        println!("║ Largest Win: ${:<32} ║", self.largest_win);
-        println!("║ Largest Loss: ${:<31} ║", self.largest_loss);
+        println!("║ Average Win: ${:<32} ║", money(self.metrics.average_win));
+        println!("║ Average Loss: ${:<31} ║", money(self.metrics.average_loss));
+        println!("║ Largest Win: ${:<32} ║", money(self.metrics.largest_win));
+        println!("║ Largest Loss: ${:<31} ║", money(self.metrics.largest_loss));
         println!("╠════════════════════════════════════════════════╣");
         println!("║ Max Drawdown: ${:<30} ║", self.max_drawdown);
         println!("║ Max Drawdown %: {:<29.2}% ║", self.max_drawdown_pct);
-        println!("║ Sharpe Ratio: {:<34.2} ║", self.sharpe_ratio);
+        println!("║ Sharpe (annualized): {:<27} ║", format_metric(self.metrics.sharpe_ratio));
+        println!("║ Sharpe (per trade): {:<28} ║", format_metric(self.metrics.per_trade_sharpe));
+        println!("║ Mean Trade Return: {:<29} ║", percent(self.metrics.mean_trade_return_pct));
+        println!("║ Trade Return Std: {:<30} ║", percent(self.metrics.trade_return_std_pct));
+        println!("╠════════════════════════════════════════════════╣");
+        println!("║ Risk-Blocked Entries: {:<26} ║", self.risk_blocked_entries);
+        println!("║ Filter-Rejected Entries: {:<23} ║", self.filter_rejected_entries);
+        println!("║ Re-entry Blocks: {:<31} ║", self.reentry_blocked_entries);
+        println!("║ Crossed Books Repaired: {:<24} ║", self.crossed_books);
+        println!("║ Threshold Adjustments: {:<25} ║", self.threshold_adjustments.len());
+        println!("║ Halted: {:<40} ║", self.halt_reason.as_deref().unwrap_or("no"));
+        println!("║ Max Hold Overrun: {:<28}ms ║", self.outages.max_hold_overrun_ms);
+        if !self.config.outages.is_empty() {
+            println!("╠════════════════════════════════════════════════╣");
+            println!("║ Outage Windows: {:<32} ║", self.config.outages.len());
+            println!("║ Frozen Events: {:<33} ║", self.outages.frozen_events);
+            println!("║ Outage-Blocked Entries: {:<24} ║", self.outages.blocked_entries);
+            println!("║ Rejected Exits: {:<32} ║", self.outages.rejected_exits);
+            println!("║ Delayed Exits: {:<33} ║", self.outages.delayed_exits);
+            println!("║ Max Exit Delay: {:<30}ms ║", self.outages.max_exit_delay_ms);
+        }
         println!("╚════════════════════════════════════════════════╝\n");
     }
 }
 
+fn percent(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| format!("{:.2}%", value))
+}
+
+fn money(value: Option<Decimal>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| value.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_backtest_engine_creation() {
@@ -533,4 +1092,91 @@ mod tests {
         assert_eq!(engine.equity, Decimal::from(10000));
         assert_eq!(engine.position_manager.position_count(), 0);
     }
+
+    #[test]
+    fn test_fill_priced_after_recorded_latency() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        let config = BacktestConfig {
+            slippage_bps: Decimal::ZERO,
+            fill_model: FillModelConfig {
+                latency_samples_ms: vec![250],
+                ..FillModelConfig::default()
+            },
+            ..BacktestConfig::default()
+        };
+        let mut engine = BacktestEngine::new(config);
+        let update = |ms: u64, bids: Vec<(i64, i64)>, asks: Vec<(i64, i64)>| BacktestEvent::OrderBookUpdate {
+            timestamp: start + Duration::from_millis(ms),
+            bids: bids.into_iter().map(|(p, q)| (Decimal::from(p), Decimal::from(q))).collect(),
+            asks: asks.into_iter().map(|(p, q)| (Decimal::from(p), Decimal::from(q))).collect(),
+        };
+
+        engine.process_event(update(0, vec![(100, 1)], vec![(102, 1)])).unwrap();
+        engine.submit(SimulatedOrder::Entry {
+            side: Side::Buy,
+            notional: Decimal::from(1000),
+            take_profit_bps: Decimal::from(1000),
+        }).unwrap();
+
+        // Mid moves to 105 while the order is on its way
+        engine.process_event(update(100, vec![(100, 0), (104, 1)], vec![(102, 0), (106, 1)])).unwrap();
+        assert_eq!(engine.position_manager.position_count(), 0);
+
+        // Fills at the mid when it arrived, not the one it was sent at
+        engine.process_event(update(400, vec![(104, 0), (108, 1)], vec![(106, 0), (110, 1)])).unwrap();
+        let position = engine.position_manager.get_position("BTCUSDT").unwrap();
+        assert_eq!(position.entry_price, Decimal::from(105));
+        assert_eq!(position.entry_time, start + Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_simulated_fill_on_tick_grid() {
+        let config = BacktestConfig {
+            exchange_filters: Some(SymbolFilters {
+                symbol: "BTCUSDT".to_string(),
+                tick_size: Decimal::from(10),
+                step_size: Decimal::new(1, 3),
+                min_qty: Decimal::new(1, 3),
+                max_qty: None,
+                min_notional: Decimal::from(100),
+            }),
+            ..BacktestConfig::default()
+        };
+        let mut engine = BacktestEngine::new(config);
+
+        // 2 bps slippage moves 50000 by 10: buys round up, sells down
+        let buy = engine.simulate_fill(Side::Buy, Decimal::from(50001), Decimal::from(1000), 0).unwrap();
+        let sell = engine.simulate_fill(Side::Sell, Decimal::from(50001), Decimal::from(1000), 0).unwrap();
+        assert_eq!(buy.price, Decimal::from(50020));
+        assert_eq!(sell.price, Decimal::from(49990));
+    }
+
+    #[test]
+    fn test_frozen_data_hides_updates_but_keeps_book() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        let config = BacktestConfig {
+            outages: vec![OutageWindow {
+                kind: OutageKind::FrozenData,
+                start: start.into(),
+                duration_ms: 1000,
+                repeat_secs: 0,
+            }],
+            ..BacktestConfig::default()
+        };
+        let mut engine = BacktestEngine::new(config);
+        let update = |ms: u64, bids: Vec<(i64, i64)>, asks: Vec<(i64, i64)>| BacktestEvent::OrderBookUpdate {
+            timestamp: start + Duration::from_millis(ms),
+            bids: bids.into_iter().map(|(p, q)| (Decimal::from(p), Decimal::from(q))).collect(),
+            asks: asks.into_iter().map(|(p, q)| (Decimal::from(p), Decimal::from(q))).collect(),
+        };
+
+        engine.process_event(update(500, vec![(100, 1)], vec![(102, 1)])).unwrap();
+        engine.process_event(update(1500, vec![(100, 0), (99, 1)], vec![(102, 0), (101, 1)])).unwrap();
+
+        // The frozen update still reached the book and the equity curve
+        assert_eq!(engine.mid_price(), Some(Decimal::from(100)));
+        let results = engine.get_results();
+        assert_eq!(results.outages.frozen_events, 1);
+        assert_eq!(results.equity_curve.len(), 2);
+    }
 }
@@ -0,0 +1,254 @@
+use crate::strategy::JournalFill;
+use anyhow::{Result, anyhow};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Fill realism parameters for simulated execution
+///
+/// Defaults reproduce the old behaviour: every order fills in full at the
+/// fixed configured latency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FillModelConfig {
+    /// Probability that a resting (maker) order gets filled at all
+    pub maker_fill_probability: f64,
+    /// Probability that an order fills only partially
+    pub partial_fill_probability: f64,
+    /// Smallest filled fraction of a partial fill (uniform in [min, 1))
+    pub min_partial_fill_ratio: f64,
+    /// Recorded live latencies to sample from (empty = fixed latency)
+    pub latency_samples_ms: Vec<u64>,
+    /// Extra adverse move on fills that were picked off (off by default)
+    pub adverse_selection: AdverseSelection,
+    /// RNG seed so simulated runs are reproducible
+    pub seed: u64,
+}
+
+/// How often our fills are followed by a move against us, and by how much
+///
+/// Mid-based fills assume we trade at a price that then moves randomly;
+/// in practice fills come more readily just before the price moves
+/// against us. A fill is picked off with `probability` and then costs an
+/// extra `move_bps`. Estimate both from live fills over recorded data
+/// (`backtester resimulate`); zero disables the penalty.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdverseSelection {
+    /// Share of fills followed by an adverse mid move
+    pub probability: f64,
+    /// Mean adverse move of those fills (bps)
+    pub move_bps: f64,
+    /// Markout horizon the estimate was measured over
+    pub horizon_ms: u64,
+}
+
+impl Default for AdverseSelection {
+    fn default() -> Self {
+        Self {
+            probability: 0.0,
+            move_bps: 0.0,
+            horizon_ms: 1000,
+        }
+    }
+}
+
+impl AdverseSelection {
+    pub fn is_enabled(&self) -> bool {
+        self.probability > 0.0 && self.move_bps > 0.0
+    }
+}
+
+impl Default for FillModelConfig {
+    fn default() -> Self {
+        Self {
+            maker_fill_probability: 1.0,
+            partial_fill_probability: 0.0,
+            min_partial_fill_ratio: 1.0,
+            latency_samples_ms: Vec::new(),
+            adverse_selection: AdverseSelection::default(),
+            seed: 42,
+        }
+    }
+}
+
+impl FillModelConfig {
+    /// Use the per-order latencies of a live fill journal (`journal.jsonl`)
+    pub fn with_latencies_from_journal<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        self.latency_samples_ms = load_latency_samples(path)?;
+        Ok(self)
+    }
+
+    pub fn with_adverse_selection(mut self, adverse_selection: AdverseSelection) -> Self {
+        self.adverse_selection = adverse_selection;
+        self
+    }
+}
+
+/// Stochastic fill model: maker fill probability, partial fills and latency jitter
+pub struct FillModel {
+    config: FillModelConfig,
+    rng: StdRng,
+}
+
+impl FillModel {
+    pub fn new(config: FillModelConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self { config, rng }
+    }
+
+    /// Whether a resting maker order would have been filled
+    pub fn maker_fills(&mut self) -> bool {
+        self.rng.gen_bool(self.config.maker_fill_probability.clamp(0.0, 1.0))
+    }
+
+    /// Fraction of the requested quantity that gets filled (0, 1]
+    pub fn sample_fill_ratio(&mut self) -> Decimal {
+        let partial = self.rng.gen_bool(self.config.partial_fill_probability.clamp(0.0, 1.0));
+        let min_ratio = self.config.min_partial_fill_ratio.clamp(0.0, 1.0);
+
+        if !partial || min_ratio >= 1.0 {
+            return Decimal::ONE;
+        }
+
+        let ratio = self.rng.gen_range(min_ratio..1.0);
+        Decimal::from_f64_retain(ratio)
+            .unwrap_or(Decimal::ONE)
+            .max(Decimal::new(1, 4)) // never a zero-size fill
+    }
+
+    /// Signal-to-fill latency, sampled from recorded latencies when available
+    pub fn sample_latency_ms(&mut self, fallback_ms: u64) -> u64 {
+        let samples = &self.config.latency_samples_ms;
+        if samples.is_empty() {
+            return fallback_ms;
+        }
+        samples[self.rng.gen_range(0..samples.len())]
+    }
+
+    /// Extra adverse move for this fill (bps), 0 unless it was picked off
+    ///
+    /// Draws nothing when disabled, so runs without the penalty stay
+    /// identical to before.
+    pub fn sample_adverse_bps(&mut self) -> f64 {
+        let adverse = self.config.adverse_selection;
+        if !adverse.is_enabled() {
+            return 0.0;
+        }
+        if self.rng.gen_bool(adverse.probability.clamp(0.0, 1.0)) {
+            adverse.move_bps
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Extract the decision-to-fill latency of every order in a fill journal
+///
+/// Per-order latencies keep the tail a stats snapshot's running average
+/// hides. Lines that fail to parse are skipped; a file with no fills is an
+/// error, since sampling would silently fall back to the fixed latency.
+pub fn load_latency_samples<P: AsRef<Path>>(path: P) -> Result<Vec<u64>> {
+    let file = std::fs::File::open(path.as_ref())?;
+    let mut samples = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        if let Ok(fill) = serde_json::from_str::<JournalFill>(&line?) {
+            samples.push(fill.latency_ms);
+        }
+    }
+
+    if samples.is_empty() {
+        return Err(anyhow!("No journaled fills in {}", path.as_ref().display()));
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_model_is_deterministic_full_fill() {
+        let mut model = FillModel::new(FillModelConfig::default());
+
+        for _ in 0..100 {
+            assert!(model.maker_fills());
+            assert_eq!(model.sample_fill_ratio(), Decimal::ONE);
+            assert_eq!(model.sample_latency_ms(100), 100);
+            assert_eq!(model.sample_adverse_bps(), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_partial_fills_and_latency_jitter() {
+        let mut model = FillModel::new(FillModelConfig {
+            maker_fill_probability: 0.0,
+            partial_fill_probability: 1.0,
+            min_partial_fill_ratio: 0.25,
+            latency_samples_ms: vec![20, 80, 300],
+            adverse_selection: AdverseSelection::default(),
+            seed: 7,
+        });
+
+        for _ in 0..100 {
+            assert!(!model.maker_fills());
+
+            let ratio = model.sample_fill_ratio();
+            assert!(ratio >= Decimal::new(25, 2) && ratio < Decimal::ONE);
+
+            let latency = model.sample_latency_ms(100);
+            assert!([20, 80, 300].contains(&latency));
+        }
+    }
+
+    #[test]
+    fn test_adverse_selection_penalty_rate() {
+        let config = FillModelConfig::default().with_adverse_selection(AdverseSelection {
+            probability: 0.3,
+            move_bps: 4.0,
+            horizon_ms: 1000,
+        });
+        let mut model = FillModel::new(config);
+
+        let draws: Vec<f64> = (0..2000).map(|_| model.sample_adverse_bps()).collect();
+        assert!(draws.iter().all(|bps| *bps == 0.0 || *bps == 4.0));
+
+        let picked_off = draws.iter().filter(|bps| **bps > 0.0).count() as f64 / draws.len() as f64;
+        assert!((picked_off - 0.3).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_latency_samples_per_journaled_order() {
+        use crate::data::Side;
+        use crate::strategy::JournalLeg;
+
+        let path = std::env::temp_dir().join(format!("latency_journal_test_{}.jsonl", std::process::id()));
+        let lines: Vec<String> = [(JournalLeg::Entry, 40), (JournalLeg::Exit, 900)].into_iter()
+            .map(|(leg, latency_ms)| serde_json::to_string(&JournalFill {
+                decision_time_ms: 0,
+                account: "primary".to_string(),
+                symbol: "BTCUSDT".to_string(),
+                leg,
+                side: Side::Buy,
+                decision_mid: Decimal::from(100),
+                executed_price: Decimal::from(100),
+                executed_qty: Decimal::ONE,
+                fees: Decimal::ZERO,
+                latency_ms,
+                explanation: None,
+                experiment: Default::default(),
+            }).unwrap())
+            .collect();
+        std::fs::write(&path, format!("{}\nnot json\n{}\n", lines[0], lines[1])).unwrap();
+        assert_eq!(load_latency_samples(&path).unwrap(), vec![40, 900]);
+
+        // A stats file has no fills to sample
+        std::fs::write(&path, "{\"timestamp_ms\":0}\n").unwrap();
+        assert!(load_latency_samples(&path).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
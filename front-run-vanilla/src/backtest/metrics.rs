@@ -0,0 +1,351 @@
+use crate::backtest::{BacktestTrade, SharpeConfig};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// Gross wins over gross losses, with its degenerate cases spelled out
+///
+/// A bare f64 would be infinite without losses, which JSON can't carry
+/// and which sorts above every real ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ProfitFactor {
+    Ratio(f64),
+    /// Winning trades but no losing one
+    NoLosses,
+    /// Neither winning nor losing trades
+    NoTrades,
+}
+
+impl ProfitFactor {
+    fn from_totals(wins: Decimal, losses: Decimal) -> Self {
+        match (wins.is_zero(), losses.is_zero()) {
+            (true, true) => ProfitFactor::NoTrades,
+            (false, true) => ProfitFactor::NoLosses,
+            _ => (wins / losses).to_f64().map_or(ProfitFactor::NoTrades, ProfitFactor::Ratio),
+        }
+    }
+
+    /// The ratio, if there is a finite one
+    pub fn ratio(&self) -> Option<f64> {
+        match self {
+            ProfitFactor::Ratio(ratio) => Some(*ratio),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ProfitFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfitFactor::Ratio(ratio) => f.pad(&format!("{:.2}", ratio)),
+            ProfitFactor::NoLosses => f.pad("no losses"),
+            ProfitFactor::NoTrades => f.pad("n/a"),
+        }
+    }
+}
+
+/// Trade and return statistics of a run
+///
+/// Every metric that is undefined for the run (no trades, no losers, a
+/// single return bucket, zero variance) is `None` or an explicit
+/// `ProfitFactor` case rather than 0 or infinity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSummary {
+    /// Share of trades with positive PnL
+    pub win_rate: Option<f64>,
+    pub profit_factor: ProfitFactor,
+    pub average_win: Option<Decimal>,
+    /// Mean size of losing trades (positive)
+    pub average_loss: Option<Decimal>,
+    pub largest_win: Option<Decimal>,
+    /// Most negative trade PnL
+    pub largest_loss: Option<Decimal>,
+    /// Annualized Sharpe of equity returns over uniform time buckets, net
+    /// of the risk-free rate
+    pub sharpe_ratio: Option<f64>,
+    /// Mean / standard deviation of per-trade returns (not annualized)
+    pub per_trade_sharpe: Option<f64>,
+    /// Mean trade PnL as a share of its entry notional (%)
+    pub mean_trade_return_pct: Option<f64>,
+    /// Standard deviation of trade returns (%)
+    pub trade_return_std_pct: Option<f64>,
+}
+
+impl MetricsSummary {
+    pub fn new(
+        trades: &[BacktestTrade],
+        equity_curve: &[(SystemTime, Decimal)],
+        initial_capital: Decimal,
+        sharpe: &SharpeConfig,
+    ) -> Self {
+        let wins: Vec<Decimal> = trades.iter().map(|t| t.pnl).filter(|pnl| *pnl > Decimal::ZERO).collect();
+        let losses: Vec<Decimal> = trades.iter().map(|t| t.pnl).filter(|pnl| *pnl < Decimal::ZERO).collect();
+        let total_wins: Decimal = wins.iter().sum();
+        let total_losses: Decimal = losses.iter().map(|pnl| pnl.abs()).sum();
+
+        let average = |total: Decimal, count: usize| {
+            (count > 0).then(|| total / Decimal::from(count))
+        };
+
+        // Trades whose return can't be computed are left out, not counted as 0
+        let trade_returns: Vec<f64> = trades.iter()
+            .filter_map(|t| {
+                let notional = t.entry_price * t.quantity;
+                if notional.is_zero() {
+                    None
+                } else {
+                    (t.pnl / notional).to_f64()
+                }
+            })
+            .collect();
+        let trade_stats = mean_and_std(&trade_returns);
+
+        Self {
+            win_rate: (!trades.is_empty()).then(|| wins.len() as f64 / trades.len() as f64),
+            profit_factor: ProfitFactor::from_totals(total_wins, total_losses),
+            average_win: average(total_wins, wins.len()),
+            average_loss: average(total_losses, losses.len()),
+            largest_win: wins.iter().max().copied(),
+            largest_loss: losses.iter().min().copied(),
+            sharpe_ratio: annualized_sharpe(equity_curve, initial_capital, sharpe),
+            per_trade_sharpe: trade_stats.and_then(|(mean, std_dev)| ratio(mean, std_dev)),
+            mean_trade_return_pct: trade_stats.map(|(mean, _)| mean * 100.0),
+            trade_return_std_pct: trade_stats.map(|(_, std_dev)| std_dev * 100.0),
+        }
+    }
+}
+
+/// "n/a" for undefined metrics, two decimals otherwise
+pub fn format_metric(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| format!("{:.2}", value))
+}
+
+/// Annualized Sharpe of equity returns sampled every `bucket_secs`
+///
+/// Equity is taken as of each bucket boundary from the first curve
+/// point, so flat periods count as zero returns. A trailing partial
+/// bucket is kept with a weight equal to its share of a full bucket.
+/// Crypto trades around the clock, so a year is 365 days of buckets.
+fn annualized_sharpe(
+    equity_curve: &[(SystemTime, Decimal)],
+    initial_capital: Decimal,
+    config: &SharpeConfig,
+) -> Option<f64> {
+    let bucket_secs = config.bucket_secs.max(1);
+    let returns = bucket_returns(equity_curve, initial_capital, Duration::from_secs(bucket_secs))?;
+
+    let buckets_per_year = 365.0 * 86_400.0 / bucket_secs as f64;
+    let risk_free = (1.0 + config.risk_free_rate).powf(1.0 / buckets_per_year) - 1.0;
+    let excess: Vec<BucketReturn> = returns.iter()
+        .map(|r| BucketReturn { value: r.value - risk_free * r.weight, weight: r.weight })
+        .collect();
+
+    let (mean, std_dev) = weighted_mean_and_std(&excess)?;
+    ratio(mean, std_dev).map(|sharpe| sharpe * buckets_per_year.sqrt())
+}
+
+/// Return over one bucket; `weight` is the share of a full bucket it spans
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BucketReturn {
+    value: f64,
+    weight: f64,
+}
+
+/// Returns between equity samples taken every `bucket`, starting at the
+/// first curve point, plus a weighted return for a trailing partial
+/// bucket (`None` if a sample can't be converted)
+fn bucket_returns(
+    equity_curve: &[(SystemTime, Decimal)],
+    initial_capital: Decimal,
+    bucket: Duration,
+) -> Option<Vec<BucketReturn>> {
+    let (Some((start, _)), Some((end, _))) = (equity_curve.first(), equity_curve.last()) else {
+        return Some(Vec::new());
+    };
+
+    let mut samples = Vec::new();
+    let mut equity = initial_capital;
+    let mut points = equity_curve.iter().peekable();
+    let mut at = *start;
+    while at <= *end {
+        while let Some((_, value)) = points.next_if(|(time, _)| *time <= at) {
+            equity = *value;
+        }
+        samples.push((equity, 1.0));
+        at += bucket;
+    }
+
+    // The curve ends inside a bucket: close it at the last point
+    let last_boundary = at - bucket;
+    if *end > last_boundary {
+        let elapsed = end.duration_since(last_boundary).unwrap_or_default();
+        let (_, final_equity) = equity_curve[equity_curve.len() - 1];
+        samples.push((final_equity, elapsed.as_secs_f64() / bucket.as_secs_f64()));
+    }
+
+    samples.windows(2)
+        .map(|pair| {
+            let ((from, _), (to, weight)) = (pair[0], pair[1]);
+            if from.is_zero() {
+                None
+            } else {
+                ((to - from) / from).to_f64().map(|value| BucketReturn { value, weight })
+            }
+        })
+        .collect()
+}
+
+/// Mean and standard deviation per full bucket of returns over buckets of
+/// varying length (`None` with fewer than two returns)
+///
+/// A return over a fraction `w` of a bucket is taken to have mean `w * mean`
+/// and variance `w * variance`. With every weight at 1 this is the plain
+/// mean and sample standard deviation.
+fn weighted_mean_and_std(returns: &[BucketReturn]) -> Option<(f64, f64)> {
+    if returns.len() < 2 {
+        return None;
+    }
+    let total_weight: f64 = returns.iter().map(|r| r.weight).sum();
+    let mean = returns.iter().map(|r| r.value).sum::<f64>() / total_weight;
+    let variance = returns.iter()
+        .map(|r| (r.value - mean * r.weight).powi(2) / r.weight)
+        .sum::<f64>() / (returns.len() - 1) as f64;
+    Some((mean, variance.sqrt()))
+}
+
+/// Mean and sample standard deviation (`None` with fewer than two values)
+fn mean_and_std(values: &[f64]) -> Option<(f64, f64)> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter()
+        .map(|v| (v - mean).powi(2))
+        .sum::<f64>() / (values.len() - 1) as f64;
+    Some((mean, variance.sqrt()))
+}
+
+fn ratio(mean: f64, std_dev: f64) -> Option<f64> {
+    (std_dev > 0.0).then(|| mean / std_dev)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Side;
+
+    fn trade(pnl: i64) -> BacktestTrade {
+        BacktestTrade {
+            entry_time: SystemTime::UNIX_EPOCH,
+            exit_time: SystemTime::UNIX_EPOCH,
+            side: Side::Buy,
+            entry_price: Decimal::from(100),
+            exit_price: Decimal::from(100),
+            quantity: Decimal::from(10),
+            pnl: Decimal::from(pnl),
+            fees: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_edge_cases_are_typed_and_serializable() {
+        let config = SharpeConfig::default();
+
+        let empty = MetricsSummary::new(&[], &[], Decimal::from(10000), &config);
+        assert_eq!(empty.win_rate, None);
+        assert_eq!(empty.profit_factor, ProfitFactor::NoTrades);
+        assert_eq!(empty.average_loss, None);
+        assert_eq!(empty.sharpe_ratio, None);
+
+        let winners = MetricsSummary::new(&[trade(10), trade(30)], &[], Decimal::from(10000), &config);
+        assert_eq!(winners.win_rate, Some(1.0));
+        assert_eq!(winners.profit_factor, ProfitFactor::NoLosses);
+        assert_eq!(winners.largest_loss, None);
+        assert_eq!(winners.mean_trade_return_pct, Some(2.0));
+
+        // Round-trips through JSON, unlike f64::INFINITY
+        let json = serde_json::to_string(&winners).unwrap();
+        assert_eq!(serde_json::from_str::<MetricsSummary>(&json).unwrap(), winners);
+
+        let mixed = MetricsSummary::new(&[trade(30), trade(-10)], &[], Decimal::from(10000), &config);
+        assert_eq!(mixed.profit_factor.ratio(), Some(3.0));
+        assert_eq!(format!("{:>6}", mixed.profit_factor), "  3.00");
+        assert_eq!(format_metric(empty.per_trade_sharpe), "n/a");
+    }
+
+    #[test]
+    fn test_annualized_sharpe_over_time_buckets() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let hour = Duration::from_secs(3600);
+        // Hourly +1%, +0.5%, +1%, +0.5%, with intra-hour noise that must be ignored
+        let curve = vec![
+            (start, Decimal::from(10000)),
+            (start + hour / 2, Decimal::from(9000)),
+            (start + hour, Decimal::new(10100, 0)),
+            (start + hour * 2, Decimal::new(101505, 1)),
+            (start + hour * 3, Decimal::new(10252005, 3)),
+            (start + hour * 4, Decimal::new(10303265025, 6)),
+        ];
+
+        let returns = bucket_returns(&curve, Decimal::from(10000), hour).unwrap();
+        assert_eq!(returns.len(), 4);
+        assert!(returns.iter().all(|r| r.weight == 1.0));
+
+        // Mean 0.0075, sample std 0.0025 * sqrt(4/3), so 3 * sqrt(3) / 2
+        // per hour, times sqrt(365 * 24) hours a year
+        let sharpe = annualized_sharpe(&curve, Decimal::from(10000), &SharpeConfig::default()).unwrap();
+        assert!((sharpe - 243.16661).abs() < 1e-4, "sharpe {}", sharpe);
+
+        // A risk-free rate lowers it; a flat curve has none
+        let with_rate = SharpeConfig { risk_free_rate: 0.05, ..SharpeConfig::default() };
+        assert!(annualized_sharpe(&curve, Decimal::from(10000), &with_rate).unwrap() < sharpe);
+        let flat = vec![(start, Decimal::from(10000)), (start + hour * 3, Decimal::from(10000))];
+        assert_eq!(annualized_sharpe(&flat, Decimal::from(10000), &SharpeConfig::default()), None);
+    }
+
+    #[test]
+    fn test_trailing_partial_bucket_is_weighted() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let hour = Duration::from_secs(3600);
+        // +1%, +0.5%, then +0.25% over the final half hour
+        let curve = vec![
+            (start, Decimal::from(10000)),
+            (start + hour, Decimal::from(10100)),
+            (start + hour * 2, Decimal::new(101505, 1)),
+            (start + hour * 5 / 2, Decimal::new(1017587625, 5)),
+        ];
+
+        let returns = bucket_returns(&curve, Decimal::from(10000), hour).unwrap();
+        assert_eq!(returns.len(), 3);
+        assert_eq!(returns[2].weight, 0.5);
+        assert!((returns[2].value - 0.0025).abs() < 1e-12);
+
+        // Mean 0.0175 / 2.5 = 0.007 an hour; variance
+        // (0.003^2 + 0.002^2 + 0.001^2 / 0.5) / 2 = 7.5e-6
+        let expected = 0.007 / 7.5e-6_f64.sqrt() * 8760.0_f64.sqrt();
+        let sharpe = annualized_sharpe(&curve, Decimal::from(10000), &SharpeConfig::default()).unwrap();
+        assert!((sharpe - expected).abs() < 1e-6);
+        assert!((sharpe - 239.23210).abs() < 1e-4, "sharpe {}", sharpe);
+    }
+
+    #[test]
+    fn test_run_shorter_than_one_bucket() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let curve = vec![
+            (start, Decimal::from(10000)),
+            (start + Duration::from_secs(600), Decimal::from(10200)),
+            (start + Duration::from_secs(900), Decimal::from(10100)),
+        ];
+
+        // The only bucket is partial; it is kept, not dropped
+        let returns = bucket_returns(&curve, Decimal::from(10000), Duration::from_secs(3600)).unwrap();
+        assert_eq!(returns, vec![BucketReturn { value: 0.01, weight: 0.25 }]);
+
+        // One return has no spread, so the Sharpe is undefined
+        let summary = MetricsSummary::new(&[], &curve, Decimal::from(10000), &SharpeConfig::default());
+        assert_eq!(summary.sharpe_ratio, None);
+    }
+}
@@ -1,6 +1,41 @@
+pub mod compare;
+pub mod data_source;
+pub mod drift;
 pub mod engine;
+pub mod fill_model;
+pub mod metrics;
+pub mod ordering;
+pub mod outage;
+pub mod paper;
+pub mod portfolio;
+pub mod report;
+pub mod resimulate;
+pub mod runner;
+pub mod sensitivity;
+pub mod shadow;
+pub mod walk_forward;
 
 pub use engine::{
     BacktestEngine, BacktestConfig, BacktestEvent,
-    BacktestResults, BacktestTrade, SimulatedFill,
+    BacktestResults, BacktestTrade, RiskEvent, SharpeConfig, SimulatedFill,
 };
+pub use compare::{StrategyComparison, VariantResults, comparison_table};
+pub use data_source::{DataFormat, DateRange, HistoricalDataSource};
+pub use drift::{DetectorDrift, DriftConfig, DriftReport, SignalMismatch, SignalRule, compare_observations};
+pub use fill_model::{AdverseSelection, FillModel, FillModelConfig, load_latency_samples};
+pub use metrics::{MetricsSummary, ProfitFactor, format_metric};
+pub use ordering::{ExitTiming, IntrabarOrdering, SameTimestampOrder};
+pub use outage::{OutageKind, OutageSchedule, OutageStats, OutageWindow};
+pub use paper::{PaperBroker, PaperConfig, PaperExits, PaperFill};
+pub use portfolio::{
+    AllocationPolicy, PortfolioBacktest, PortfolioConfig, PortfolioResults, PortfolioRisk,
+    SharedPortfolioRisk, SymbolContribution,
+};
+pub use report::html_report;
+pub use runner::StrategyRunner;
+pub use resimulate::{
+    ExecutionGap, FillComparison, estimate_adverse_selection, load_journal, resimulate,
+};
+pub use sensitivity::{CostScenario, CostSensitivity, cost_sensitivity};
+pub use shadow::{ShadowConfig, ShadowStrategy, ShadowTrade};
+pub use walk_forward::{ThresholdProposal, load_recent, recalibrate};
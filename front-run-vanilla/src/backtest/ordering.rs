@@ -0,0 +1,154 @@
+use crate::backtest::BacktestEvent;
+use serde::{Serialize, Deserialize};
+
+/// Which events go first when several share a timestamp
+///
+/// Depth and trade recordings are usually separate streams merged by time,
+/// so ties are common at millisecond resolution and their order is an
+/// artifact of how the files were merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SameTimestampOrder {
+    /// Keep the order the data source produced
+    AsRecorded,
+    /// Trades first: trades at the same instant are treated as having
+    /// printed before the book change
+    TradesFirst,
+    /// Book updates first: trades at the same instant are treated as
+    /// happening after the book change
+    BookFirst,
+}
+
+/// When open positions are checked for exit on a book update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitTiming {
+    /// Exits use the book as it was before this update is applied, i.e.
+    /// the exit decision is made on the last price we actually saw
+    BeforeBookUpdate,
+    /// Apply the update, check exits, then look for entries. A position
+    /// closed here can be re-entered on the same update.
+    BeforeEntries,
+    /// Apply the update, look for entries, then check exits. A position
+    /// opened on this update is exit-checked immediately at the same mid.
+    AfterEntries,
+}
+
+/// Ordering semantics for events that happen "at the same time"
+///
+/// Per book update the engine always runs exits and entries against a
+/// single consistent book and records equity last; `exit_timing` decides
+/// where exits fall in that sequence. `same_timestamp` is applied to the
+/// event stream before replay with [`IntrabarOrdering::sort_events`].
+///
+/// Defaults reproduce the original engine behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntrabarOrdering {
+    pub same_timestamp: SameTimestampOrder,
+    pub exit_timing: ExitTiming,
+}
+
+impl Default for IntrabarOrdering {
+    fn default() -> Self {
+        Self {
+            same_timestamp: SameTimestampOrder::AsRecorded,
+            exit_timing: ExitTiming::AfterEntries,
+        }
+    }
+}
+
+impl IntrabarOrdering {
+    /// Reorder events within each run of equal timestamps
+    ///
+    /// Only ties are touched (stable), so events already in time order stay
+    /// in time order and same-kind events keep their recorded sequence.
+    pub fn sort_events(&self, events: &mut [BacktestEvent]) {
+        if self.same_timestamp == SameTimestampOrder::AsRecorded {
+            return;
+        }
+
+        let rank = |event: &BacktestEvent| -> u8 {
+            match (self.same_timestamp, event) {
+                (SameTimestampOrder::TradesFirst, BacktestEvent::Trade { .. }) => 0,
+                (SameTimestampOrder::TradesFirst, BacktestEvent::OrderBookUpdate { .. }) => 1,
+                (SameTimestampOrder::BookFirst, BacktestEvent::OrderBookUpdate { .. }) => 0,
+                (SameTimestampOrder::BookFirst, BacktestEvent::Trade { .. }) => 1,
+                (SameTimestampOrder::AsRecorded, _) => 0,
+            }
+        };
+
+        let mut start = 0;
+        while start < events.len() {
+            let timestamp = events[start].timestamp();
+            let end = events[start..].iter()
+                .position(|event| event.timestamp() != timestamp)
+                .map_or(events.len(), |offset| start + offset);
+
+            events[start..end].sort_by_key(rank);
+            start = end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Side, Trade};
+    use rust_decimal::Decimal;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn book(ms: u64) -> BacktestEvent {
+        BacktestEvent::OrderBookUpdate {
+            timestamp: UNIX_EPOCH + Duration::from_millis(ms),
+            bids: vec![],
+            asks: vec![],
+        }
+    }
+
+    fn trade(ms: u64) -> BacktestEvent {
+        let timestamp = UNIX_EPOCH + Duration::from_millis(ms);
+        BacktestEvent::Trade {
+            timestamp,
+            trade: Trade {
+                id: ms,
+                price: Decimal::from(100),
+                quantity: Decimal::ONE,
+                side: Side::Buy,
+                timestamp,
+                is_buyer_maker: false,
+            },
+        }
+    }
+
+    fn kinds(events: &[BacktestEvent]) -> Vec<(u64, char)> {
+        events.iter()
+            .map(|event| {
+                let ms = event.timestamp().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+                match event {
+                    BacktestEvent::OrderBookUpdate { .. } => (ms, 'B'),
+                    BacktestEvent::Trade { .. } => (ms, 'T'),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_only_ties_are_reordered() {
+        let mut events = vec![trade(1), book(2), trade(2), book(3), trade(3)];
+
+        let trades_first = IntrabarOrdering {
+            same_timestamp: SameTimestampOrder::TradesFirst,
+            ..IntrabarOrdering::default()
+        };
+        trades_first.sort_events(&mut events);
+        assert_eq!(kinds(&events), vec![(1, 'T'), (2, 'T'), (2, 'B'), (3, 'T'), (3, 'B')]);
+
+        let book_first = IntrabarOrdering {
+            same_timestamp: SameTimestampOrder::BookFirst,
+            ..IntrabarOrdering::default()
+        };
+        book_first.sort_events(&mut events);
+        assert_eq!(kinds(&events), vec![(1, 'T'), (2, 'B'), (2, 'T'), (3, 'B'), (3, 'T')]);
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// What the exchange stops doing during an outage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutageKind {
+    /// Orders are accepted but nothing fills: entries are dropped, and an
+    /// exit decided inside the window fills at the first book after it
+    NoFills,
+    /// Orders are rejected: entries are dropped and exits retried on every
+    /// update until one goes through
+    RejectOrders,
+    /// No market data reaches the strategy; the book is caught up in the
+    /// background, as the resync snapshot on reconnect would
+    FrozenData,
+}
+
+/// One simulated outage (`[[backtest.outages]]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutageWindow {
+    pub kind: OutageKind,
+    pub start: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Repeat every this many seconds after `start` (0 = once)
+    #[serde(default)]
+    pub repeat_secs: u64,
+}
+
+impl OutageWindow {
+    pub fn contains(&self, at: SystemTime) -> bool {
+        let start = SystemTime::from(self.start);
+        let Ok(since_start) = at.duration_since(start) else {
+            return false;
+        };
+        let elapsed_ms = since_start.as_millis() as u64;
+        let into_window_ms = match self.repeat_secs {
+            0 => elapsed_ms,
+            repeat => elapsed_ms % (repeat * 1000),
+        };
+        into_window_ms < self.duration_ms
+    }
+}
+
+/// Outage windows checked on replay time
+#[derive(Debug, Clone, Default)]
+pub struct OutageSchedule {
+    windows: Vec<OutageWindow>,
+}
+
+impl OutageSchedule {
+    pub fn new(windows: Vec<OutageWindow>) -> Self {
+        Self { windows }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Whether an outage of `kind` covers `at`
+    pub fn active(&self, kind: OutageKind, at: SystemTime) -> bool {
+        self.windows.iter().any(|window| window.kind == kind && window.contains(at))
+    }
+}
+
+/// What the simulated outages cost the strategy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutageStats {
+    /// Book updates and trades the strategy never saw
+    pub frozen_events: usize,
+    /// Entries dropped because orders did not fill or were rejected
+    pub blocked_entries: usize,
+    /// Exit attempts rejected (every retry counts)
+    pub rejected_exits: usize,
+    /// Exits that filled only once a no-fill window ended
+    pub delayed_exits: usize,
+    /// Longest wait from exit decision to fill
+    pub max_exit_delay_ms: u64,
+    /// Longest hold beyond `max_hold_time_ms`, outage or not; without
+    /// outages this is bounded by the gap between book updates
+    pub max_hold_overrun_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::time::Duration;
+
+    #[test]
+    fn test_repeating_window_by_kind() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let schedule = OutageSchedule::new(vec![OutageWindow {
+            kind: OutageKind::RejectOrders,
+            start,
+            duration_ms: 30_000,
+            repeat_secs: 3600,
+        }]);
+        let at = |secs: u64| SystemTime::from(start) + Duration::from_secs(secs);
+
+        assert!(!schedule.active(OutageKind::RejectOrders, SystemTime::from(start) - Duration::from_secs(1)));
+        assert!(schedule.active(OutageKind::RejectOrders, at(0)));
+        assert!(!schedule.active(OutageKind::RejectOrders, at(30)));
+        assert!(schedule.active(OutageKind::RejectOrders, at(7200 + 29)));
+        assert!(!schedule.active(OutageKind::NoFills, at(0)));
+    }
+}
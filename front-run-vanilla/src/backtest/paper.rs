@@ -0,0 +1,361 @@
+use crate::backtest::{FillModel, FillModelConfig};
+use crate::data::{OrderBook, Side};
+use crate::risk::{Instrument, Position, PositionManager};
+use crate::strategy::{ExitFill, ExitReason, JournalLeg, passive_exit_price};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Simulated execution for the paper trader (`[paper]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaperConfig {
+    /// Maker fill probability, partial fills and latency samples
+    pub fill_model: FillModelConfig,
+    /// journal.jsonl of a live session to sample order latency from (empty =
+    /// `fill_model.latency_samples_ms`)
+    pub latency_journal_file: String,
+    /// Order latency when there are no samples
+    pub latency_ms: u64,
+    pub maker_fee_bps: Decimal,
+    pub taker_fee_bps: Decimal,
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            fill_model: FillModelConfig::default(),
+            latency_journal_file: String::new(),
+            latency_ms: 100,
+            maker_fee_bps: Decimal::from(2),
+            taker_fee_bps: Decimal::from(4),
+        }
+    }
+}
+
+impl PaperConfig {
+    /// Fill model with the recorded latencies loaded, if a file is set
+    pub fn fill_model(&self) -> Result<FillModelConfig> {
+        if self.latency_journal_file.is_empty() {
+            return Ok(self.fill_model.clone());
+        }
+        self.fill_model.clone().with_latencies_from_journal(Path::new(&self.latency_journal_file))
+    }
+}
+
+/// Exit rules of the paper position, as the live engine applies them
+#[derive(Debug, Clone, Copy)]
+pub struct PaperExits {
+    pub stop_loss_bps: Decimal,
+    pub max_hold_time_ms: u64,
+}
+
+/// A fill the simulated venue made
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperFill {
+    pub leg: JournalLeg,
+    pub side: Side,
+    /// Average price over every part of the fill
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub fees: Decimal,
+    /// Decision to fill
+    pub latency_ms: u64,
+    /// Why an exit was sent and the PnL it booked
+    pub exit: Option<(ExitReason, Decimal)>,
+}
+
+#[derive(Debug, Clone)]
+enum PaperOrder {
+    Entry { side: Side, notional: Decimal, take_profit_bps: Decimal },
+    /// Market close of what the resting limit (quantity, price) left
+    Exit { reason: ExitReason, limit: (Decimal, Decimal) },
+}
+
+#[derive(Debug, Clone)]
+struct InFlight {
+    order: PaperOrder,
+    due: SystemTime,
+    latency_ms: u64,
+}
+
+/// Paper venue: orders from the live signal stream fill on the live book
+/// through a `FillModel` and are booked in a `PositionManager`
+///
+/// Market orders reach the venue after a sampled latency and fill at the
+/// touch as it stands then; entries may fill only partially. A take
+/// profit rests at the touch first and fills there (as maker, partially
+/// or not at all) with the model's probabilities; the rest of it, and
+/// every other exit, goes to market. One position at a time.
+pub struct PaperBroker {
+    symbol: String,
+    instrument: Instrument,
+    exits: PaperExits,
+    fill_model: FillModel,
+    latency_ms: u64,
+    maker_fee_rate: Decimal,
+    taker_fee_rate: Decimal,
+    position_manager: PositionManager,
+    in_flight: Option<InFlight>,
+}
+
+impl PaperBroker {
+    pub fn new(symbol: &str, config: &PaperConfig, exits: PaperExits) -> Result<Self> {
+        let bps = Decimal::from(10_000);
+        Ok(Self {
+            symbol: symbol.to_string(),
+            instrument: Instrument::linear(),
+            exits,
+            fill_model: FillModel::new(config.fill_model()?),
+            latency_ms: config.latency_ms,
+            maker_fee_rate: config.maker_fee_bps / bps,
+            taker_fee_rate: config.taker_fee_bps / bps,
+            position_manager: PositionManager::new(),
+            in_flight: None,
+        })
+    }
+
+    pub fn with_instrument(mut self, instrument: Instrument) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    pub fn position_manager(&self) -> &PositionManager {
+        &self.position_manager
+    }
+
+    /// Whether an entry would be taken: flat, with nothing on its way
+    pub fn is_idle(&self) -> bool {
+        self.position_manager.position_count() == 0 && self.in_flight.is_none()
+    }
+
+    /// Send a market entry for `notional` (USD); ignored unless idle
+    pub fn submit_entry(&mut self, side: Side, notional: Decimal, take_profit_bps: Decimal, now: SystemTime) -> bool {
+        if !self.is_idle() {
+            return false;
+        }
+        self.submit(PaperOrder::Entry { side, notional, take_profit_bps }, now);
+        true
+    }
+
+    /// Fill what has reached the venue by `now`, then check the open
+    /// position's exits against `book`
+    pub fn on_book(&mut self, book: &OrderBook, now: SystemTime) -> Result<Vec<PaperFill>> {
+        let mut fills = Vec::new();
+        let (Some(best_bid), Some(best_ask)) = book.get_top_of_book() else {
+            return Ok(fills);
+        };
+
+        if let Some(order) = self.in_flight.take_if(|order| order.due <= now) {
+            fills.extend(self.fill(order, best_bid, best_ask)?);
+        }
+        if self.in_flight.is_none() {
+            fills.extend(self.check_exit((best_bid + best_ask) / Decimal::from(2), best_bid, best_ask, now)?);
+        }
+        Ok(fills)
+    }
+
+    fn submit(&mut self, order: PaperOrder, now: SystemTime) {
+        let latency_ms = self.fill_model.sample_latency_ms(self.latency_ms);
+        self.in_flight = Some(InFlight {
+            order,
+            due: now + Duration::from_millis(latency_ms),
+            latency_ms,
+        });
+    }
+
+    fn fill(&mut self, order: InFlight, best_bid: Decimal, best_ask: Decimal) -> Result<Option<PaperFill>> {
+        match order.order {
+            PaperOrder::Entry { side, notional, take_profit_bps } => {
+                let price = match side {
+                    Side::Buy => best_ask,
+                    Side::Sell => best_bid,
+                };
+                let quantity = self.instrument.round_quantity(
+                    self.instrument.quantity(notional, price, Decimal::ONE) * self.fill_model.sample_fill_ratio(),
+                );
+                if quantity <= Decimal::ZERO {
+                    return Ok(None);
+                }
+                let fees = self.instrument.notional(quantity, price) * self.taker_fee_rate;
+                self.position_manager.open_position(
+                    Position::new(self.symbol.clone(), side, price, quantity, fees)
+                        .with_instrument(self.instrument)
+                        .with_entry_time(order.due)
+                        .with_take_profit(take_profit_bps),
+                )?;
+                Ok(Some(PaperFill {
+                    leg: JournalLeg::Entry,
+                    side,
+                    price,
+                    quantity,
+                    fees,
+                    latency_ms: order.latency_ms,
+                    exit: None,
+                }))
+            }
+            PaperOrder::Exit { reason, limit } => {
+                let Some(position) = self.position_manager.get_position(&self.symbol) else {
+                    return Ok(None);
+                };
+                let close_side = position.side.opposite();
+                let market_price = match close_side {
+                    Side::Buy => best_ask,
+                    Side::Sell => best_bid,
+                };
+                let fill = ExitFill {
+                    limit_qty: limit.0,
+                    limit_price: limit.1,
+                    market_qty: position.quantity - limit.0,
+                    market_price,
+                    ..ExitFill::default()
+                };
+                self.close(reason, fill, order.latency_ms).map(Some)
+            }
+        }
+    }
+
+    fn check_exit(
+        &mut self,
+        mid: Decimal,
+        best_bid: Decimal,
+        best_ask: Decimal,
+        now: SystemTime,
+    ) -> Result<Option<PaperFill>> {
+        let Some(position) = self.position_manager.get_position(&self.symbol).cloned() else {
+            return Ok(None);
+        };
+        let reason = if position.take_profit_bps.is_some_and(|bps| position.take_profit_hit(mid, bps)) {
+            ExitReason::TakeProfit
+        } else if position.stop_loss_hit(mid, self.exits.stop_loss_bps) {
+            ExitReason::StopLoss
+        } else if position.is_expired_at(self.exits.max_hold_time_ms, now) {
+            ExitReason::MaxHoldTime
+        } else {
+            return Ok(None);
+        };
+
+        // The take profit rests at the touch; whatever it does not get
+        // goes to market
+        let mut limit = (Decimal::ZERO, Decimal::ZERO);
+        if reason == ExitReason::TakeProfit && self.fill_model.maker_fills() {
+            let quantity = self.instrument.round_quantity(position.quantity * self.fill_model.sample_fill_ratio());
+            limit = (quantity, passive_exit_price(position.side.opposite(), best_bid, best_ask));
+        }
+        if limit.0 >= position.quantity {
+            let fill = ExitFill {
+                limit_qty: position.quantity,
+                limit_price: limit.1,
+                ..ExitFill::default()
+            };
+            return self.close(reason, fill, 0).map(Some);
+        }
+
+        self.submit(PaperOrder::Exit { reason, limit }, now);
+        Ok(None)
+    }
+
+    fn close(&mut self, reason: ExitReason, fill: ExitFill, latency_ms: u64) -> Result<PaperFill> {
+        let side = self.position_manager.get_position(&self.symbol)
+            .map(|position| position.side.opposite())
+            .ok_or_else(|| anyhow::anyhow!("Position not found: {}", self.symbol))?;
+        let price = fill.average_price();
+        let fees = fill.fees(&self.instrument, self.maker_fee_rate, self.taker_fee_rate);
+        let pnl = self.position_manager.close_position(&self.symbol, price, fees)?;
+        Ok(PaperFill {
+            leg: JournalLeg::Exit,
+            side,
+            price,
+            quantity: fill.quantity(),
+            fees,
+            latency_ms,
+            exit: Some((reason, pnl)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::UNIX_EPOCH;
+
+    fn book(bid: Decimal, ask: Decimal) -> OrderBook {
+        let book = OrderBook::new("BTCUSDT");
+        book.apply_depth(&[(bid, dec!(10))], &[(ask, dec!(10))]).unwrap();
+        book
+    }
+
+    fn broker(fill_model: FillModelConfig) -> PaperBroker {
+        let config = PaperConfig {
+            fill_model,
+            latency_ms: 250,
+            ..PaperConfig::default()
+        };
+        let exits = PaperExits { stop_loss_bps: dec!(5), max_hold_time_ms: 60_000 };
+        PaperBroker::new("BTCUSDT", &config, exits).unwrap()
+    }
+
+    #[test]
+    fn test_entry_fills_partially_after_latency() {
+        let mut broker = broker(FillModelConfig {
+            partial_fill_probability: 1.0,
+            min_partial_fill_ratio: 0.5,
+            ..FillModelConfig::default()
+        });
+        let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        assert!(broker.submit_entry(Side::Buy, dec!(1000), dec!(10), start));
+        assert!(!broker.submit_entry(Side::Buy, dec!(1000), dec!(10), start));
+
+        // Still on its way
+        assert!(broker.on_book(&book(dec!(99), dec!(100)), start + Duration::from_millis(100)).unwrap().is_empty());
+
+        // Fills at the ask it meets, for part of the 10 requested
+        let fills = broker.on_book(&book(dec!(101), dec!(102)), start + Duration::from_millis(250)).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!((fills[0].leg, fills[0].price, fills[0].latency_ms), (JournalLeg::Entry, dec!(102), 250));
+        let quantity = fills[0].quantity;
+        assert!(quantity >= dec!(1000) / dec!(102) * dec!(0.5) && quantity < dec!(1000) / dec!(102));
+        assert_eq!(broker.position_manager().get_position("BTCUSDT").unwrap().quantity, quantity);
+    }
+
+    #[test]
+    fn test_missed_maker_take_profit_goes_to_market() {
+        let mut broker = broker(FillModelConfig {
+            maker_fill_probability: 0.0,
+            ..FillModelConfig::default()
+        });
+        let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        broker.submit_entry(Side::Buy, dec!(1000), dec!(10), start);
+        broker.on_book(&book(dec!(99.99), dec!(100)), start + Duration::from_millis(250)).unwrap();
+
+        // Take profit hit: the resting limit is never reached, so the close
+        // is sent to market and fills at the bid it meets
+        let hit = start + Duration::from_secs(1);
+        assert!(broker.on_book(&book(dec!(101), dec!(101.2)), hit).unwrap().is_empty());
+        let fills = broker.on_book(&book(dec!(100.5), dec!(100.7)), hit + Duration::from_millis(250)).unwrap();
+
+        assert_eq!(fills.len(), 1);
+        let (reason, pnl) = fills[0].exit.unwrap();
+        assert_eq!((fills[0].side, fills[0].price, reason), (Side::Sell, dec!(100.5), ExitReason::TakeProfit));
+        assert_eq!(fills[0].fees, dec!(100.5) * dec!(10) * dec!(0.0004));
+        assert_eq!(pnl, broker.position_manager().total_realized_pnl());
+        assert!(broker.is_idle());
+    }
+
+    #[test]
+    fn test_maker_take_profit_fills_at_touch() {
+        let mut broker = broker(FillModelConfig::default());
+        let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        broker.submit_entry(Side::Buy, dec!(1000), dec!(10), start);
+        broker.on_book(&book(dec!(99.99), dec!(100)), start + Duration::from_millis(250)).unwrap();
+
+        // Filled in full as maker at the ask, with no latency to wait out
+        let fills = broker.on_book(&book(dec!(101), dec!(101.2)), start + Duration::from_secs(1)).unwrap();
+        assert_eq!((fills[0].price, fills[0].latency_ms), (dec!(101.2), 0));
+        assert_eq!(fills[0].fees, dec!(101.2) * dec!(10) * dec!(0.0002));
+        assert_eq!(broker.position_manager().closed_positions().len(), 1);
+    }
+}
@@ -0,0 +1,415 @@
+use crate::backtest::{BacktestConfig, BacktestEngine, BacktestEvent, BacktestResults};
+use crate::exchange::binance::SymbolFilters;
+use crate::risk::{RiskManager, RiskViolation};
+use crate::strategy::SizingMode;
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+/// How the portfolio's per-entry notional is split across symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationPolicy {
+    /// Every symbol gets the same share
+    EqualWeight,
+    /// Shares inversely proportional to recent mid-return volatility, so
+    /// each symbol contributes similar risk
+    VolatilityParity,
+    /// Equal shares scaled 0.5x-2x by signal confidence, like live sizing
+    SignalStrength,
+}
+
+/// `[backtest.portfolio]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortfolioConfig {
+    pub allocation: AllocationPolicy,
+    /// Book updates per symbol in the volatility estimate
+    pub volatility_window: usize,
+}
+
+impl Default for PortfolioConfig {
+    fn default() -> Self {
+        Self {
+            allocation: AllocationPolicy::EqualWeight,
+            volatility_window: 500,
+        }
+    }
+}
+
+/// Risk state shared by every engine in a portfolio backtest
+///
+/// One `RiskManager` sees every symbol's trades, so daily loss, drawdown
+/// and trade-rate limits apply to the portfolio as a whole, and exposure
+/// checks use the combined open notional.
+pub struct PortfolioRisk {
+    risk_manager: RiskManager,
+    exposure: HashMap<String, Decimal>,
+    /// Open PnL per symbol, marked to market into the risk manager
+    unrealized: HashMap<String, Decimal>,
+    started: bool,
+}
+
+impl PortfolioRisk {
+    pub fn new(risk_manager: RiskManager) -> Self {
+        Self {
+            risk_manager,
+            exposure: HashMap::new(),
+            unrealized: HashMap::new(),
+            started: false,
+        }
+    }
+
+    pub fn shared(risk_manager: RiskManager) -> SharedPortfolioRisk {
+        Arc::new(Mutex::new(Self::new(risk_manager)))
+    }
+
+    pub fn risk_manager(&self) -> &RiskManager {
+        &self.risk_manager
+    }
+
+    pub fn risk_manager_mut(&mut self) -> &mut RiskManager {
+        &mut self.risk_manager
+    }
+
+    /// Start the daily window at the first replayed event of any symbol
+    pub fn start_at(&mut self, now: SystemTime) {
+        if !self.started {
+            self.started = true;
+            self.risk_manager.reset_day_at(now);
+        }
+    }
+
+    pub fn set_exposure(&mut self, symbol: &str, exposure: Decimal) {
+        self.exposure.insert(symbol.to_string(), exposure);
+    }
+
+    /// Open notional across all symbols
+    pub fn total_exposure(&self) -> Decimal {
+        self.exposure.values().copied().sum()
+    }
+
+    /// Update `symbol`'s open PnL and mark the combined book to market
+    pub fn mark_to_market(&mut self, symbol: &str, unrealized_pnl: Decimal) -> Result<(), RiskViolation> {
+        self.unrealized.insert(symbol.to_string(), unrealized_pnl);
+        let total = self.unrealized.values().copied().sum();
+        self.risk_manager.mark_to_market(total)
+    }
+
+    /// Record a closed trade; its open PnL is now realized
+    pub fn record_trade_at(&mut self, symbol: &str, pnl: Decimal, now: SystemTime) {
+        self.unrealized.remove(symbol);
+        self.risk_manager.record_trade_at(pnl, now);
+    }
+}
+
+pub type SharedPortfolioRisk = Arc<Mutex<PortfolioRisk>>;
+
+pub(crate) fn lock(risk: &SharedPortfolioRisk) -> MutexGuard<'_, PortfolioRisk> {
+    risk.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Rolling standard deviation of mid-to-mid returns
+struct Volatility {
+    last_mid: Option<f64>,
+    returns: VecDeque<f64>,
+    window: usize,
+    std_dev: Option<f64>,
+}
+
+impl Volatility {
+    fn new(window: usize) -> Self {
+        Self {
+            last_mid: None,
+            returns: VecDeque::with_capacity(window),
+            window,
+            std_dev: None,
+        }
+    }
+
+    fn record(&mut self, mid: f64) {
+        if let Some(last) = self.last_mid.filter(|last| *last > 0.0) {
+            self.returns.push_back(mid / last - 1.0);
+            if self.returns.len() > self.window {
+                self.returns.pop_front();
+            }
+        }
+        self.last_mid = Some(mid);
+        self.std_dev = self.compute();
+    }
+
+    fn std_dev(&self) -> Option<f64> {
+        self.std_dev
+    }
+
+    fn compute(&self) -> Option<f64> {
+        if self.returns.len() < 2 {
+            return None;
+        }
+        let n = self.returns.len() as f64;
+        let mean = self.returns.iter().sum::<f64>() / n;
+        let variance = self.returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+        Some(variance.sqrt()).filter(|sd| *sd > 0.0)
+    }
+}
+
+struct PortfolioLeg {
+    symbol: String,
+    engine: BacktestEngine,
+    volatility: Volatility,
+}
+
+/// Symbol's share of the portfolio result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolContribution {
+    pub symbol: String,
+    pub pnl: Decimal,
+    /// Share of total portfolio PnL (%), signed
+    pub contribution_pct: f64,
+    pub trades: usize,
+    pub risk_blocked_entries: usize,
+}
+
+/// Portfolio backtest outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioResults {
+    /// Combined trades and equity, as a single-account backtest
+    pub portfolio: BacktestResults,
+    pub contributions: Vec<SymbolContribution>,
+    pub symbols: Vec<BacktestResults>,
+}
+
+impl PortfolioResults {
+    pub fn print_summary(&self) {
+        self.portfolio.print_summary();
+        println!("{:<12} {:>14} {:>10} {:>8} {:>8}", "Symbol", "PnL", "Share %", "Trades", "Blocked");
+        for c in &self.contributions {
+            println!("{:<12} {:>14.2} {:>10.1} {:>8} {:>8}",
+                c.symbol, c.pnl, c.contribution_pct, c.trades, c.risk_blocked_entries);
+        }
+    }
+}
+
+/// Multi-symbol backtest: one engine per symbol, shared capital and risk
+///
+/// `config.position_size` is the portfolio's per-entry notional budget and
+/// `config.initial_capital` its total capital; both are split across
+/// symbols by the allocation policy.
+pub struct PortfolioBacktest {
+    config: BacktestConfig,
+    portfolio: PortfolioConfig,
+    legs: Vec<PortfolioLeg>,
+    risk: SharedPortfolioRisk,
+    current_time: SystemTime,
+    equity_curve: Vec<(SystemTime, Decimal)>,
+}
+
+impl PortfolioBacktest {
+    pub fn new(config: BacktestConfig, portfolio: PortfolioConfig, symbols: &[String]) -> Result<Self> {
+        if symbols.is_empty() {
+            return Err(anyhow!("Portfolio backtest needs at least one symbol"));
+        }
+
+        let risk = PortfolioRisk::shared(RiskManager::new(
+            config.risk_limits.clone(),
+            config.initial_capital,
+        ));
+        let count = Decimal::from(symbols.len());
+        // A leg's equity is already its share of capital, so an equity
+        // percentage must not be split again
+        let size_divisor = match config.sizing {
+            SizingMode::EquityPercent => Decimal::ONE,
+            _ => count,
+        };
+
+        let legs = symbols.iter()
+            .map(|symbol| {
+                let leg_config = BacktestConfig {
+                    symbol: symbol.clone(),
+                    initial_capital: config.initial_capital / count,
+                    position_size: config.position_size / size_divisor,
+                    scale_by_confidence: portfolio.allocation == AllocationPolicy::SignalStrength,
+                    exchange_filters: config.exchange_filters.clone()
+                        .filter(|filters| filters.symbol.eq_ignore_ascii_case(symbol)),
+                    ..config.clone()
+                };
+                PortfolioLeg {
+                    symbol: symbol.clone(),
+                    engine: BacktestEngine::new(leg_config).with_shared_risk(Arc::clone(&risk)),
+                    volatility: Volatility::new(portfolio.volatility_window),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            config,
+            portfolio,
+            legs,
+            risk,
+            current_time: SystemTime::UNIX_EPOCH,
+            equity_curve: Vec::new(),
+        })
+    }
+
+    /// Apply exchange filters to the leg trading `filters.symbol`
+    pub fn set_exchange_filters(&mut self, filters: SymbolFilters) -> Result<()> {
+        let leg = self.legs.iter_mut()
+            .find(|leg| leg.symbol.eq_ignore_ascii_case(&filters.symbol))
+            .ok_or_else(|| anyhow!("Symbol not in portfolio: {}", filters.symbol))?;
+        leg.engine.set_exchange_filters(filters);
+        Ok(())
+    }
+
+    /// Route one symbol's event to its engine
+    pub fn process_event(&mut self, symbol: &str, event: BacktestEvent) -> Result<()> {
+        let idx = self.legs.iter()
+            .position(|leg| leg.symbol.eq_ignore_ascii_case(symbol))
+            .ok_or_else(|| anyhow!("Symbol not in portfolio: {}", symbol))?;
+
+        self.current_time = event.timestamp();
+        let is_book_update = matches!(event, BacktestEvent::OrderBookUpdate { .. });
+
+        if self.portfolio.allocation == AllocationPolicy::VolatilityParity {
+            self.rebalance_volatility_parity();
+        }
+        self.legs[idx].engine.process_event_for(symbol, event)?;
+
+        if is_book_update {
+            let leg = &mut self.legs[idx];
+            // A mid with no f64 value is left out of the volatility window
+            if let Some(mid) = leg.engine.mid_price().and_then(|mid| mid.to_f64()) {
+                leg.volatility.record(mid);
+            }
+            self.record_equity();
+        }
+        Ok(())
+    }
+
+    /// Resize each symbol's entry notional to inverse-volatility weights
+    ///
+    /// Symbols without enough history yet get the average weight.
+    fn rebalance_volatility_parity(&mut self) {
+        let inverse: Vec<Option<f64>> = self.legs.iter()
+            .map(|leg| leg.volatility.std_dev().map(|sd| 1.0 / sd))
+            .collect();
+        let known: Vec<f64> = inverse.iter().flatten().copied().collect();
+        if known.is_empty() {
+            return;
+        }
+        let fallback = known.iter().sum::<f64>() / known.len() as f64;
+        let total: f64 = inverse.iter().map(|w| w.unwrap_or(fallback)).sum();
+
+        // Equity percentages are relative to the leg, so weights average to 1
+        let scale = match self.config.sizing {
+            SizingMode::EquityPercent => self.legs.len() as f64,
+            _ => 1.0,
+        };
+
+        for (leg, weight) in self.legs.iter_mut().zip(&inverse) {
+            let share = weight.unwrap_or(fallback) / total * scale;
+            let size = self.config.position_size * Decimal::from_f64_retain(share).unwrap_or_default();
+            leg.engine.set_position_size(size);
+        }
+    }
+
+    fn record_equity(&mut self) {
+        let equity = self.legs.iter().map(|leg| leg.engine.current_equity()).sum();
+        self.equity_curve.push((self.current_time, equity));
+    }
+
+    pub fn results(&self) -> Result<PortfolioResults> {
+        let symbols: Vec<BacktestResults> = self.legs.iter()
+            .map(|leg| leg.engine.get_results())
+            .collect();
+
+        let mut trades: Vec<_> = symbols.iter().flat_map(|r| r.trades.clone()).collect();
+        trades.sort_by_key(|trade| trade.exit_time);
+
+        let final_equity = symbols.iter().map(|r| r.final_equity).sum();
+        let mut portfolio = BacktestResults::new(
+            self.config.clone(),
+            trades,
+            self.equity_curve.clone(),
+            final_equity,
+        );
+        portfolio.risk_blocked_entries = symbols.iter().map(|r| r.risk_blocked_entries).sum();
+        portfolio.filter_rejected_entries = symbols.iter().map(|r| r.filter_rejected_entries).sum();
+        portfolio.reentry_blocked_entries = symbols.iter().map(|r| r.reentry_blocked_entries).sum();
+        portfolio.crossed_books = symbols.iter().map(|r| r.crossed_books).sum();
+        portfolio.halt_reason = lock(&self.risk).risk_manager().halt_reason().map(str::to_string);
+
+        let total_pnl = portfolio.total_return;
+        let contributions = symbols.iter()
+            .map(|r| {
+                let contribution_pct = if total_pnl.is_zero() {
+                    0.0
+                } else {
+                    let pct = r.total_return / total_pnl.abs() * Decimal::from(100);
+                    pct.to_f64().ok_or_else(|| anyhow!("{} contribution {}% is out of f64 range", r.config.symbol, pct))?
+                };
+                Ok(SymbolContribution {
+                    symbol: r.config.symbol.clone(),
+                    pnl: r.total_return,
+                    contribution_pct,
+                    trades: r.total_trades,
+                    risk_blocked_entries: r.risk_blocked_entries,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(PortfolioResults { portfolio, contributions, symbols })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_volatility_parity_favors_calm_symbol() {
+        let config = BacktestConfig::default();
+        let portfolio = PortfolioConfig {
+            allocation: AllocationPolicy::VolatilityParity,
+            volatility_window: 50,
+        };
+        let symbols = vec!["CALM".to_string(), "WILD".to_string()];
+        let mut backtest = PortfolioBacktest::new(config, portfolio, &symbols).unwrap();
+
+        // CALM alternates mids 100/101, WILD 100/110
+        for i in 0..20u64 {
+            let timestamp = UNIX_EPOCH + Duration::from_millis(i * 100);
+            for (symbol, step) in [("CALM", 1), ("WILD", 10)] {
+                let (mid, prev) = if i % 2 == 0 { (100, 100 + step) } else { (100 + step, 100) };
+                backtest.process_event(symbol, BacktestEvent::OrderBookUpdate {
+                    timestamp,
+                    bids: vec![(Decimal::from(prev - 1), Decimal::ZERO), (Decimal::from(mid - 1), Decimal::ONE)],
+                    asks: vec![(Decimal::from(prev + 1), Decimal::ZERO), (Decimal::from(mid + 1), Decimal::ONE)],
+                }).unwrap();
+            }
+        }
+        backtest.rebalance_volatility_parity();
+
+        let calm_size = backtest.legs[0].engine.position_size();
+        let wild_size = backtest.legs[1].engine.position_size();
+        assert!(calm_size > wild_size);
+        assert!(backtest.process_event("OTHER", BacktestEvent::OrderBookUpdate {
+            timestamp: UNIX_EPOCH,
+            bids: vec![],
+            asks: vec![],
+        }).is_err());
+    }
+
+    #[test]
+    fn test_shared_risk_sums_exposure() {
+        let mut risk = PortfolioRisk::new(RiskManager::new(Default::default(), Decimal::from(10000)));
+        risk.set_exposure("A", Decimal::from(3000));
+        risk.set_exposure("B", Decimal::from(4000));
+        risk.set_exposure("A", Decimal::from(1000));
+        assert_eq!(risk.total_exposure(), Decimal::from(5000));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::backtest::{BacktestResults, VariantResults, comparison_table};
+use rust_decimal::prelude::ToPrimitive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Points kept per equity curve in the chart
+const MAX_CHART_POINTS: usize = 1000;
+
+const CHART_WIDTH: f64 = 900.0;
+const CHART_HEIGHT: f64 = 360.0;
+
+const COLORS: [&str; 8] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#ff7f0e",
+    "#9467bd", "#8c564b", "#e377c2", "#17becf",
+];
+
+/// Self-contained HTML report: metrics table and overlayed equity curves
+///
+/// The chart is inline SVG so the file opens anywhere without network
+/// access. A single run is just a comparison of one.
+pub fn html_report(title: &str, variants: &[VariantResults]) -> String {
+    let curves: Vec<Vec<(f64, f64)>> = variants.iter()
+        .map(|v| equity_points(&v.results))
+        .collect();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(title)));
+    html.push_str("<style>body{font-family:sans-serif;margin:2em}pre{background:#f6f6f6;padding:1em}</style>\n");
+    html.push_str("</head><body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+
+    html.push_str("<h2>Equity</h2>\n");
+    html.push_str(&equity_chart(variants, &curves));
+
+    html.push_str("<h2>Metrics</h2>\n");
+    html.push_str(&format!("<pre>{}</pre>\n", escape(&comparison_table(variants))));
+    html.push_str("</body></html>\n");
+    html
+}
+
+fn equity_chart(variants: &[VariantResults], curves: &[Vec<(f64, f64)>]) -> String {
+    let all = curves.iter().flatten();
+    let (min_t, max_t, min_e, max_e) = all.fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(min_t, max_t, min_e, max_e), (t, e)| (min_t.min(*t), max_t.max(*t), min_e.min(*e), max_e.max(*e)),
+    );
+    if min_t > max_t {
+        return "<p>No equity data</p>\n".to_string();
+    }
+
+    let span_t = (max_t - min_t).max(1.0);
+    let span_e = (max_e - min_e).max(1e-9);
+    let x = |t: f64| (t - min_t) / span_t * CHART_WIDTH;
+    let y = |e: f64| CHART_HEIGHT - (e - min_e) / span_e * CHART_HEIGHT;
+
+    let mut svg = format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"-60 -10 {vw} {vh}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
+        w = CHART_WIDTH + 80.0, h = CHART_HEIGHT + 60.0,
+        vw = CHART_WIDTH + 80.0, vh = CHART_HEIGHT + 60.0,
+    );
+    svg.push_str(&format!(
+        "<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#ccc\"/>\n",
+        CHART_WIDTH, CHART_HEIGHT,
+    ));
+    svg.push_str(&format!("<text x=\"-55\" y=\"10\" font-size=\"11\">{:.0}</text>\n", max_e));
+    svg.push_str(&format!("<text x=\"-55\" y=\"{}\" font-size=\"11\">{:.0}</text>\n", CHART_HEIGHT, min_e));
+
+    for (i, (variant, curve)) in variants.iter().zip(curves).enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        let points: Vec<String> = curve.iter()
+            .map(|(t, e)| format!("{:.1},{:.1}", x(*t), y(*e)))
+            .collect();
+        svg.push_str(&format!(
+            "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\" points=\"{}\"/>\n",
+            color, points.join(" "),
+        ));
+
+        let legend_y = CHART_HEIGHT + 20.0 + (i / 4) as f64 * 15.0;
+        let legend_x = (i % 4) as f64 * 220.0;
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"12\" fill=\"{}\">{}</text>\n",
+            legend_x, legend_y, color, escape(&variant.name),
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Equity curve as (seconds since epoch, equity), downsampled for the chart
+fn equity_points(results: &BacktestResults) -> Vec<(f64, f64)> {
+    let curve = &results.equity_curve;
+    let step = (curve.len() / MAX_CHART_POINTS).max(1);
+
+    curve.iter()
+        .enumerate()
+        .filter(|(i, _)| i % step == 0 || *i == curve.len() - 1)
+        .filter_map(|(_, (time, equity))| Some((seconds(*time), equity.to_f64()?)))
+        .collect()
+}
+
+fn seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,351 @@
+use crate::backtest::{AdverseSelection, BacktestConfig, BacktestEvent, FillModel};
+use crate::data::{OrderBook, Side};
+use crate::strategy::{JournalFill, JournalLeg};
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One live fill next to what the backtest fill model would have done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillComparison {
+    pub decision_time_ms: u64,
+    pub leg: JournalLeg,
+    pub side: Side,
+    /// Recorded mid at the decision time
+    pub reference_mid: Decimal,
+    pub realized_price: Decimal,
+    pub modeled_price: Decimal,
+    /// Adverse move from reference mid to fill (bps, positive = worse)
+    pub realized_slippage_bps: f64,
+    pub modeled_slippage_bps: f64,
+    pub realized_latency_ms: u64,
+    pub modeled_latency_ms: u64,
+}
+
+/// Gap between modeled and realized execution over a journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionGap {
+    pub fills: Vec<FillComparison>,
+    /// Journal fills outside the recorded data, or whose slippage could
+    /// not be priced
+    pub unmatched: usize,
+    pub mean_realized_slippage_bps: f64,
+    pub mean_modeled_slippage_bps: f64,
+    pub mean_realized_latency_ms: f64,
+    pub mean_modeled_latency_ms: f64,
+    /// `slippage_bps` that would make modeled slippage match realized on
+    /// average, keeping the latency model as configured
+    pub suggested_slippage_bps: f64,
+    /// Post-fill markout of the live fills, over the configured
+    /// `adverse_selection.horizon_ms`
+    pub adverse_selection: AdverseSelection,
+}
+
+impl ExecutionGap {
+    pub fn print_summary(&self) {
+        println!("Re-simulated fills: {} ({} outside recorded data or unpriced)", self.fills.len(), self.unmatched);
+        println!("Slippage bps  realized {:>8.2} | modeled {:>8.2}",
+            self.mean_realized_slippage_bps, self.mean_modeled_slippage_bps);
+        println!("Latency ms    realized {:>8.1} | modeled {:>8.1}",
+            self.mean_realized_latency_ms, self.mean_modeled_latency_ms);
+        println!("Suggested slippage_bps: {:.2}", self.suggested_slippage_bps);
+        println!("Adverse selection ({}ms markout): probability {:.2}, move_bps {:.2}",
+            self.adverse_selection.horizon_ms,
+            self.adverse_selection.probability,
+            self.adverse_selection.move_bps);
+    }
+}
+
+/// Load a live trade journal (`journal.jsonl`)
+///
+/// Lines that fail to parse are skipped.
+pub fn load_journal<P: AsRef<Path>>(path: P) -> Result<Vec<JournalFill>> {
+    let file = std::fs::File::open(path)?;
+    let mut fills = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        if let Ok(fill) = serde_json::from_str::<JournalFill>(&line?) {
+            fills.push(fill);
+        }
+    }
+
+    Ok(fills)
+}
+
+/// Re-simulate journaled live fills under the backtest fill model
+///
+/// Each fill is re-priced from the recorded book: the decision mid at the
+/// journal timestamp is the reference, and the modeled fill is the mid
+/// after a sampled model latency, moved `slippage_bps` (plus any sampled
+/// adverse-selection penalty) against us, exactly as `BacktestEngine`
+/// fills orders. Only fills for `config.symbol` are
+/// used, so the recording must be of that symbol.
+pub fn resimulate(
+    journal: &[JournalFill],
+    events: &[BacktestEvent],
+    config: &BacktestConfig,
+) -> Result<ExecutionGap> {
+    let mids = mid_series(events, &config.symbol)?;
+    let mut fill_model = FillModel::new(config.fill_model.clone());
+    let slippage = config.slippage_bps / Decimal::from(10000);
+
+    let recorded_until = mids.last().map_or(UNIX_EPOCH, |(time, _)| *time);
+
+    let mut fills = Vec::new();
+    let mut unmatched = 0;
+
+    for fill in journal.iter().filter(|fill| fill.symbol.eq_ignore_ascii_case(&config.symbol)) {
+        let decision_time = UNIX_EPOCH + Duration::from_millis(fill.decision_time_ms);
+        let modeled_latency_ms = fill_model.sample_latency_ms(config.latency_ms);
+        let fill_time = decision_time + Duration::from_millis(modeled_latency_ms);
+
+        // Both the decision and the modeled fill must fall inside the recording
+        let (Some(reference_mid), Some(fill_mid)) = (mid_at(&mids, decision_time), mid_at(&mids, fill_time)) else {
+            unmatched += 1;
+            continue;
+        };
+        if fill_time > recorded_until {
+            unmatched += 1;
+            continue;
+        }
+
+        let adverse = Decimal::from_f64_retain(fill_model.sample_adverse_bps())
+            .unwrap_or_default() / Decimal::from(10000);
+        let modeled_price = match fill.side {
+            Side::Buy => fill_mid * (Decimal::ONE + slippage + adverse),
+            Side::Sell => fill_mid * (Decimal::ONE - slippage - adverse),
+        };
+
+        let (Some(realized_slippage_bps), Some(modeled_slippage_bps)) = (
+            adverse_bps(fill.side, reference_mid, fill.executed_price),
+            adverse_bps(fill.side, reference_mid, modeled_price),
+        ) else {
+            unmatched += 1;
+            continue;
+        };
+
+        fills.push(FillComparison {
+            decision_time_ms: fill.decision_time_ms,
+            leg: fill.leg,
+            side: fill.side,
+            reference_mid,
+            realized_price: fill.executed_price,
+            modeled_price,
+            realized_slippage_bps,
+            modeled_slippage_bps,
+            realized_latency_ms: fill.latency_ms,
+            modeled_latency_ms,
+        });
+    }
+
+    let mean = |values: Vec<f64>| {
+        if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 }
+    };
+    let mean_realized_slippage_bps = mean(fills.iter().map(|f| f.realized_slippage_bps).collect());
+    let mean_modeled_slippage_bps = mean(fills.iter().map(|f| f.modeled_slippage_bps).collect());
+    let configured_slippage_bps = config.slippage_bps.to_f64()
+        .ok_or_else(|| anyhow!("slippage_bps {} is out of f64 range", config.slippage_bps))?;
+
+    let horizon_ms = config.fill_model.adverse_selection.horizon_ms;
+    Ok(ExecutionGap {
+        adverse_selection: markout(journal, &mids, &config.symbol, horizon_ms),
+        mean_realized_latency_ms: mean(fills.iter().map(|f| f.realized_latency_ms as f64).collect()),
+        mean_modeled_latency_ms: mean(fills.iter().map(|f| f.modeled_latency_ms as f64).collect()),
+        suggested_slippage_bps: configured_slippage_bps
+            + (mean_realized_slippage_bps - mean_modeled_slippage_bps),
+        mean_realized_slippage_bps,
+        mean_modeled_slippage_bps,
+        fills,
+        unmatched,
+    })
+}
+
+/// Estimate the adverse-selection penalty from live fills over recorded data
+///
+/// Each journaled fill of `symbol` is marked out against the recorded mid
+/// `horizon_ms` after it executed (decision time plus its recorded
+/// latency). The probability is the share of fills after which the mid
+/// moved against the fill's side; the move is the mean of those moves.
+pub fn estimate_adverse_selection(
+    journal: &[JournalFill],
+    events: &[BacktestEvent],
+    symbol: &str,
+    horizon_ms: u64,
+) -> Result<AdverseSelection> {
+    let mids = mid_series(events, symbol)?;
+    Ok(markout(journal, &mids, symbol, horizon_ms))
+}
+
+fn markout(
+    journal: &[JournalFill],
+    mids: &[(SystemTime, Decimal)],
+    symbol: &str,
+    horizon_ms: u64,
+) -> AdverseSelection {
+    let recorded_until = mids.last().map_or(UNIX_EPOCH, |(time, _)| *time);
+    let mut marked = 0;
+    let mut adverse_moves = Vec::new();
+
+    for fill in journal.iter().filter(|fill| fill.symbol.eq_ignore_ascii_case(symbol)) {
+        let fill_time = UNIX_EPOCH + Duration::from_millis(fill.decision_time_ms + fill.latency_ms);
+        let later = fill_time + Duration::from_millis(horizon_ms);
+        if later > recorded_until {
+            continue;
+        }
+        let (Some(fill_mid), Some(later_mid)) = (mid_at(mids, fill_time), mid_at(mids, later)) else {
+            continue;
+        };
+        let Some(moved_bps) = adverse_bps(fill.side, fill_mid, later_mid) else {
+            continue;
+        };
+
+        marked += 1;
+        // Buying before a drop (or selling before a rise) is adverse
+        let moved = -moved_bps;
+        if moved > 0.0 {
+            adverse_moves.push(moved);
+        }
+    }
+
+    if marked == 0 || adverse_moves.is_empty() {
+        return AdverseSelection { horizon_ms, ..AdverseSelection::default() };
+    }
+    AdverseSelection {
+        probability: adverse_moves.len() as f64 / marked as f64,
+        move_bps: adverse_moves.iter().sum::<f64>() / adverse_moves.len() as f64,
+        horizon_ms,
+    }
+}
+
+/// Mid after every book update, in replay order
+fn mid_series(events: &[BacktestEvent], symbol: &str) -> Result<Vec<(SystemTime, Decimal)>> {
+    let orderbook = OrderBook::new(symbol);
+    let mut mids = Vec::new();
+
+    for event in events {
+        if let BacktestEvent::OrderBookUpdate { timestamp, bids, asks } = event {
+            for (price, qty) in bids {
+                orderbook.update_level(Side::Buy, *price, *qty)?;
+            }
+            for (price, qty) in asks {
+                orderbook.update_level(Side::Sell, *price, *qty)?;
+            }
+            if let Some(mid) = orderbook.get_mid_price() {
+                mids.push((*timestamp, mid));
+            }
+        }
+    }
+
+    Ok(mids)
+}
+
+/// Last mid at or before `time`
+fn mid_at(mids: &[(SystemTime, Decimal)], time: SystemTime) -> Option<Decimal> {
+    let idx = mids.partition_point(|(t, _)| *t <= time);
+    idx.checked_sub(1).map(|i| mids[i].1)
+}
+
+/// None when the move has no f64 value (zero reference or out of range)
+fn adverse_bps(side: Side, reference: Decimal, price: Decimal) -> Option<f64> {
+    if reference.is_zero() {
+        return None;
+    }
+    let moved = match side {
+        Side::Buy => price - reference,
+        Side::Sell => reference - price,
+    };
+    (moved / reference * Decimal::from(10000)).to_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Single-level book; `prev` levels are removed first
+    fn book(ms: u64, bid: i64, ask: i64, prev: Option<(i64, i64)>) -> BacktestEvent {
+        let mut bids = Vec::new();
+        let mut asks = Vec::new();
+        if let Some((prev_bid, prev_ask)) = prev {
+            bids.push((Decimal::from(prev_bid), Decimal::ZERO));
+            asks.push((Decimal::from(prev_ask), Decimal::ZERO));
+        }
+        bids.push((Decimal::from(bid), Decimal::ONE));
+        asks.push((Decimal::from(ask), Decimal::ONE));
+
+        BacktestEvent::OrderBookUpdate {
+            timestamp: UNIX_EPOCH + Duration::from_millis(ms),
+            bids,
+            asks,
+        }
+    }
+
+    fn fill(ms: u64, price: i64) -> JournalFill {
+        JournalFill {
+            decision_time_ms: ms,
+            account: "primary".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            leg: JournalLeg::Entry,
+            side: Side::Buy,
+            decision_mid: Decimal::from(10000),
+            executed_price: Decimal::from(price),
+            executed_qty: Decimal::ONE,
+            fees: Decimal::ZERO,
+            latency_ms: 150,
+            explanation: None,
+            experiment: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_gap_against_recorded_book() {
+        // Mid 10000 at t=0, then 10010 from t=100
+        let events = vec![
+            book(0, 9999, 10001, None),
+            book(100, 10009, 10011, Some((9999, 10001))),
+            book(200, 10009, 10011, None),
+        ];
+        let config = BacktestConfig {
+            slippage_bps: Decimal::ZERO,
+            latency_ms: 100,
+            ..BacktestConfig::default()
+        };
+
+        let journal = vec![fill(0, 10020), fill(5_000, 10020)];
+        let gap = resimulate(&journal, &events, &config).unwrap();
+
+        assert_eq!(gap.fills.len(), 1);
+        assert_eq!(gap.unmatched, 1);
+        assert_eq!(gap.fills[0].modeled_price, Decimal::from(10010));
+        assert!((gap.fills[0].realized_slippage_bps - 20.0).abs() < 1e-9);
+        assert!((gap.fills[0].modeled_slippage_bps - 10.0).abs() < 1e-9);
+        assert!((gap.suggested_slippage_bps - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_adverse_selection_markout() {
+        // Mid 10000 until t=1000, then 9990
+        let events = vec![
+            book(0, 9999, 10001, None),
+            book(1_000, 9989, 9991, Some((9999, 10001))),
+            book(5_000, 9989, 9991, None),
+        ];
+
+        let mut sell = fill(0, 10000);
+        sell.side = Side::Sell;
+        // Fills at t=150: the buy is picked off by the 10 bps drop, the sell is not
+        let journal = vec![fill(0, 10000), sell];
+
+        let adverse = estimate_adverse_selection(&journal, &events, "BTCUSDT", 1_000).unwrap();
+        assert_eq!(adverse.horizon_ms, 1_000);
+        assert!((adverse.probability - 0.5).abs() < 1e-9);
+        assert!((adverse.move_bps - 10.0).abs() < 1e-9);
+
+        // Markouts past the end of the recording are skipped
+        let late = estimate_adverse_selection(&[fill(4_500, 10000)], &events, "BTCUSDT", 1_000).unwrap();
+        assert!(!late.is_enabled());
+    }
+}
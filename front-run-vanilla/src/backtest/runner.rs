@@ -0,0 +1,392 @@
+use crate::backtest::{BacktestConfig, BacktestEvent, BacktestResults, BacktestTrade, FillModel};
+use crate::data::{OrderBook, Side};
+use crate::exchange::BookValidator;
+use crate::storage::BookSnapshot;
+use crate::strategy::{EvaluateBook, Strategy, StrategyAction, StrategyFill};
+use rust_decimal::Decimal;
+use std::time::SystemTime;
+use anyhow::Result;
+
+/// Open round trip: from flat to flat again
+#[derive(Debug, Clone)]
+struct RoundTrip {
+    side: Side,
+    entry_time: SystemTime,
+    entry_price: Decimal,
+    /// Largest absolute position reached
+    quantity: Decimal,
+    exit_notional: Decimal,
+    exit_quantity: Decimal,
+    gross_pnl: Decimal,
+    fees: Decimal,
+}
+
+/// Replays a built-in `Strategy` with simulated fills
+///
+/// Quotes rest at their price until a trade prints through it, or the
+/// opposite side of the book moves through it, and then fill in full at
+/// the quote price with `maker_commission_bps`; a quote at the touch is
+/// assumed to be behind the queue. Each time a quote is traded through it
+/// fills with `fill_model.maker_fill_probability`, otherwise it keeps
+/// resting. Quotes that would cross on arrival are
+/// dropped, as post-only orders are. Market orders fill at the touch with
+/// `slippage_bps` and `commission_bps`.
+///
+/// Positions are netted and a `BacktestTrade` is recorded each time the
+/// position returns to flat. Linear instruments only; risk limits,
+/// latency and outages are not simulated.
+pub struct StrategyRunner {
+    config: BacktestConfig,
+    strategy: Box<dyn Strategy>,
+    orderbook: OrderBook,
+    book_validator: BookValidator,
+    fill_model: FillModel,
+    /// Resting quote (price, quantity) per side
+    bid: Option<(Decimal, Decimal)>,
+    ask: Option<(Decimal, Decimal)>,
+    /// Signed position in instrument units
+    position: Decimal,
+    /// Average entry price of the open position
+    entry_price: Decimal,
+    round_trip: Option<RoundTrip>,
+    current_time: SystemTime,
+    /// Realized PnL net of fees plus initial capital
+    equity: Decimal,
+    equity_curve: Vec<(SystemTime, Decimal)>,
+    trades: Vec<BacktestTrade>,
+}
+
+impl StrategyRunner {
+    pub fn new(config: BacktestConfig, strategy: Box<dyn Strategy>) -> Self {
+        Self {
+            orderbook: OrderBook::new(&config.symbol),
+            book_validator: BookValidator::new(&config.symbol),
+            fill_model: FillModel::new(config.fill_model.clone()),
+            equity: config.initial_capital,
+            config,
+            strategy,
+            bid: None,
+            ask: None,
+            position: Decimal::ZERO,
+            entry_price: Decimal::ZERO,
+            round_trip: None,
+            current_time: SystemTime::UNIX_EPOCH,
+            equity_curve: Vec::new(),
+            trades: Vec::new(),
+        }
+    }
+
+    pub fn strategy_name(&self) -> &str {
+        self.strategy.name()
+    }
+
+    /// Signed position in instrument units
+    pub fn position(&self) -> Decimal {
+        self.position
+    }
+
+    /// Start from a recorded book instead of an empty one
+    pub fn warm_start(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        self.book_validator.check_symbol(&snapshot.symbol)?;
+        snapshot.restore(&self.orderbook)
+    }
+
+    /// Process a single market event
+    pub fn process_event(&mut self, event: BacktestEvent) -> Result<()> {
+        match event {
+            BacktestEvent::OrderBookUpdate { timestamp, bids, asks } => {
+                self.current_time = timestamp;
+                for (price, qty) in &bids {
+                    self.orderbook.update_level(Side::Buy, *price, *qty)?;
+                }
+                for (price, qty) in &asks {
+                    self.orderbook.update_level(Side::Sell, *price, *qty)?;
+                }
+                self.book_validator.resolve_crossed(&self.orderbook, &bids, &asks)?;
+
+                // The book moved through a resting quote
+                let touch = self.orderbook.evaluate(1, |view| (view.best_bid(), view.best_ask()));
+                if let (Some((price, _)), Some(best_ask)) = (self.bid, touch.1) {
+                    if best_ask < price {
+                        self.fill_quote(Side::Buy);
+                    }
+                }
+                if let (Some((price, _)), Some(best_bid)) = (self.ask, touch.0) {
+                    if best_bid > price {
+                        self.fill_quote(Side::Sell);
+                    }
+                }
+
+                let actions = self.strategy.on_book(&self.orderbook, timestamp);
+                self.apply(actions);
+                self.record_equity();
+            }
+
+            BacktestEvent::Trade { timestamp, trade } => {
+                self.current_time = timestamp;
+                if self.bid.is_some_and(|(price, _)| trade.price < price) {
+                    self.fill_quote(Side::Buy);
+                }
+                if self.ask.is_some_and(|(price, _)| trade.price > price) {
+                    self.fill_quote(Side::Sell);
+                }
+
+                let actions = self.strategy.on_trade(&trade);
+                self.apply(actions);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, actions: Vec<StrategyAction>) {
+        for action in actions {
+            match action {
+                StrategyAction::Quote { side, price, quantity } => {
+                    let (best_bid, best_ask) = self.orderbook.evaluate(1, |view| (view.best_bid(), view.best_ask()));
+                    // Post-only: a quote that would take is rejected
+                    let crosses = match side {
+                        Side::Buy => best_ask.is_some_and(|ask| price >= ask),
+                        Side::Sell => best_bid.is_some_and(|bid| price <= bid),
+                    };
+                    let quote = (!crosses && quantity > Decimal::ZERO).then_some((price, quantity));
+                    match side {
+                        Side::Buy => self.bid = quote,
+                        Side::Sell => self.ask = quote,
+                    }
+                }
+                StrategyAction::CancelQuote(Side::Buy) => self.bid = None,
+                StrategyAction::CancelQuote(Side::Sell) => self.ask = None,
+                StrategyAction::Market { side, quantity } => {
+                    let touch = self.orderbook.evaluate(1, |view| match side {
+                        Side::Buy => view.best_ask(),
+                        Side::Sell => view.best_bid(),
+                    });
+                    let Some(touch) = touch else {
+                        continue;
+                    };
+                    let slippage = touch * self.config.slippage_bps / Decimal::from(10000);
+                    let price = match side {
+                        Side::Buy => touch + slippage,
+                        Side::Sell => touch - slippage,
+                    };
+                    self.fill(side, price, quantity, false);
+                }
+            }
+        }
+    }
+
+    fn fill_quote(&mut self, side: Side) {
+        // Traded through but not reached in the queue
+        if !self.fill_model.maker_fills() {
+            return;
+        }
+        let quote = match side {
+            Side::Buy => self.bid.take(),
+            Side::Sell => self.ask.take(),
+        };
+        if let Some((price, quantity)) = quote {
+            self.fill(side, price, quantity, true);
+        }
+    }
+
+    /// Book a fill into the position and tell the strategy
+    fn fill(&mut self, side: Side, price: Decimal, quantity: Decimal, maker: bool) {
+        if quantity <= Decimal::ZERO {
+            return;
+        }
+        let fee_bps = if maker { self.config.maker_commission_bps } else { self.config.commission_bps };
+        let fee = self.config.instrument.quote_notional(quantity, price) * fee_bps / Decimal::from(10000);
+        self.equity -= fee;
+
+        let signed = match side {
+            Side::Buy => quantity,
+            Side::Sell => -quantity,
+        };
+        let mut remaining = signed;
+
+        // Reduce the open position first
+        if !self.position.is_zero() && self.position.is_sign_positive() != signed.is_sign_positive() {
+            let closed = remaining.abs().min(self.position.abs());
+            let direction = if self.position.is_sign_positive() { Decimal::ONE } else { -Decimal::ONE };
+            let pnl = (price - self.entry_price) * closed * direction * self.config.instrument.multiplier;
+            self.equity += pnl;
+            if let Some(round_trip) = self.round_trip.as_mut() {
+                round_trip.exit_notional += price * closed;
+                round_trip.exit_quantity += closed;
+                round_trip.gross_pnl += pnl;
+                round_trip.fees += fee * closed / quantity;
+            }
+            self.position -= direction * closed;
+            remaining += direction * closed;
+            if self.position.is_zero() {
+                self.close_round_trip();
+            }
+        }
+
+        // Open or add with the rest
+        if !remaining.is_zero() {
+            let size = self.position.abs();
+            self.entry_price = (self.entry_price * size + price * remaining.abs()) / (size + remaining.abs());
+            self.position += remaining;
+            let round_trip = self.round_trip.get_or_insert(RoundTrip {
+                side,
+                entry_time: self.current_time,
+                entry_price: price,
+                quantity: Decimal::ZERO,
+                exit_notional: Decimal::ZERO,
+                exit_quantity: Decimal::ZERO,
+                gross_pnl: Decimal::ZERO,
+                fees: Decimal::ZERO,
+            });
+            round_trip.entry_price = self.entry_price;
+            round_trip.quantity = round_trip.quantity.max(self.position.abs());
+            round_trip.fees += fee * remaining.abs() / quantity;
+        }
+
+        self.strategy.on_fill(&StrategyFill {
+            side,
+            price,
+            quantity,
+            maker,
+            time: self.current_time,
+        });
+    }
+
+    fn close_round_trip(&mut self) {
+        self.entry_price = Decimal::ZERO;
+        let Some(round_trip) = self.round_trip.take() else {
+            return;
+        };
+        let exit_price = if round_trip.exit_quantity.is_zero() {
+            round_trip.entry_price
+        } else {
+            round_trip.exit_notional / round_trip.exit_quantity
+        };
+        self.trades.push(BacktestTrade {
+            entry_time: round_trip.entry_time,
+            exit_time: self.current_time,
+            side: round_trip.side,
+            entry_price: round_trip.entry_price,
+            exit_price,
+            quantity: round_trip.quantity,
+            pnl: round_trip.gross_pnl - round_trip.fees,
+            fees: round_trip.fees,
+        });
+    }
+
+    fn record_equity(&mut self) {
+        let unrealized = match self.orderbook.get_mid_price() {
+            Some(mid) if !self.position.is_zero() => {
+                (mid - self.entry_price) * self.position * self.config.instrument.multiplier
+            }
+            _ => Decimal::ZERO,
+        };
+        self.equity_curve.push((self.current_time, self.equity + unrealized));
+    }
+
+    /// Results so far; an open position counts in the equity curve only
+    pub fn get_results(&self) -> BacktestResults {
+        BacktestResults::new(
+            self.config.clone(),
+            self.trades.clone(),
+            self.equity_curve.clone(),
+            self.equity,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::FillModelConfig;
+    use crate::data::Trade;
+    use rust_decimal_macros::dec;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    /// Quotes once at fixed prices
+    struct FixedQuotes(bool);
+
+    impl Strategy for FixedQuotes {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn on_book(&mut self, _book: &OrderBook, _now: SystemTime) -> Vec<StrategyAction> {
+            if std::mem::replace(&mut self.0, true) {
+                return Vec::new();
+            }
+            vec![
+                StrategyAction::Quote { side: Side::Buy, price: dec!(99.9), quantity: dec!(1) },
+                StrategyAction::Quote { side: Side::Sell, price: dec!(100.1), quantity: dec!(1) },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_quotes_fill_through_and_round_trip() {
+        let mut runner = StrategyRunner::new(BacktestConfig {
+            maker_commission_bps: Decimal::ZERO,
+            ..BacktestConfig::default()
+        }, Box::new(FixedQuotes(false)));
+        let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        let trade = |ms: u64, price: Decimal| BacktestEvent::Trade {
+            timestamp: start + Duration::from_millis(ms),
+            trade: Trade {
+                id: ms,
+                price,
+                quantity: dec!(1),
+                side: Side::Sell,
+                timestamp: start + Duration::from_millis(ms),
+                is_buyer_maker: true,
+            },
+        };
+
+        runner.process_event(BacktestEvent::OrderBookUpdate {
+            timestamp: start,
+            bids: vec![(dec!(99.5), dec!(5))],
+            asks: vec![(dec!(100.5), dec!(5))],
+        }).unwrap();
+
+        // At the quote price: still queued
+        runner.process_event(trade(100, dec!(99.9))).unwrap();
+        assert_eq!(runner.position(), Decimal::ZERO);
+
+        runner.process_event(trade(200, dec!(99.8))).unwrap();
+        assert_eq!(runner.position(), dec!(1));
+        runner.process_event(trade(300, dec!(100.2))).unwrap();
+        assert_eq!(runner.position(), Decimal::ZERO);
+
+        let results = runner.get_results();
+        assert_eq!(results.total_trades, 1);
+        assert_eq!(results.trades[0].side, Side::Buy);
+        assert_eq!(results.trades[0].pnl, dec!(0.2));
+        assert_eq!(results.final_equity, BacktestConfig::default().initial_capital + dec!(0.2));
+    }
+
+    #[test]
+    fn test_missed_maker_fill_keeps_resting() {
+        let mut runner = StrategyRunner::new(BacktestConfig {
+            fill_model: FillModelConfig {
+                maker_fill_probability: 0.0,
+                ..FillModelConfig::default()
+            },
+            ..BacktestConfig::default()
+        }, Box::new(FixedQuotes(false)));
+        let start = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+
+        runner.process_event(BacktestEvent::OrderBookUpdate {
+            timestamp: start,
+            bids: vec![(dec!(99.5), dec!(5))],
+            asks: vec![(dec!(100.5), dec!(5))],
+        }).unwrap();
+        // Asks move through the bid quote; it is never reached
+        runner.process_event(BacktestEvent::OrderBookUpdate {
+            timestamp: start + Duration::from_millis(100),
+            bids: vec![(dec!(99.5), dec!(0)), (dec!(99.7), dec!(5))],
+            asks: vec![(dec!(100.5), dec!(0)), (dec!(99.8), dec!(5))],
+        }).unwrap();
+
+        assert_eq!(runner.position(), Decimal::ZERO);
+        assert!(runner.bid.is_some());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::backtest::{BacktestResults, BacktestTrade};
+use crate::data::Side;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+
+/// Trade list outcome under one slippage/fee assumption
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostScenario {
+    pub slippage_bps: f64,
+    pub commission_bps: f64,
+    pub total_pnl: f64,
+    /// `None` without trades
+    pub win_rate: Option<f64>,
+}
+
+/// Cost sensitivity of a finished backtest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostSensitivity {
+    /// Fee levels swept for each slippage level (grid columns)
+    pub commission_bps: Vec<f64>,
+    /// Slippage-major grid: every fee level for the first slippage, ...
+    pub scenarios: Vec<CostScenario>,
+    /// Gross PnL at the mid, before any slippage or fees
+    pub gross_pnl: f64,
+    /// Per-side cost (slippage + fee, bps of notional) at which total PnL
+    /// reaches zero; `None` when the trades lose money even at zero cost
+    pub break_even_cost_bps: Option<f64>,
+}
+
+/// A trade with its costs stripped back to mid prices
+struct MidTrade {
+    side: Side,
+    entry_mid: f64,
+    exit_mid: f64,
+    quantity: f64,
+}
+
+impl MidTrade {
+    /// Undo the engine's fill model: entries and exits were filled at
+    /// `mid * (1 ± slippage)`, always against us
+    fn from_trade(trade: &BacktestTrade, slippage_bps: f64) -> Option<Self> {
+        let slip = slippage_bps / 10000.0;
+        let (entry_factor, exit_factor) = match trade.side {
+            Side::Buy => (1.0 + slip, 1.0 - slip),
+            Side::Sell => (1.0 - slip, 1.0 + slip),
+        };
+        Some(Self {
+            side: trade.side,
+            entry_mid: trade.entry_price.to_f64()? / entry_factor,
+            exit_mid: trade.exit_price.to_f64()? / exit_factor,
+            quantity: trade.quantity.to_f64()?,
+        })
+    }
+
+    fn direction(&self) -> f64 {
+        match self.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        }
+    }
+
+    fn gross_pnl(&self) -> f64 {
+        self.direction() * (self.exit_mid - self.entry_mid) * self.quantity
+    }
+
+    /// Entry plus exit notional at the mid
+    fn round_trip_notional(&self) -> f64 {
+        (self.entry_mid + self.exit_mid) * self.quantity
+    }
+
+    /// PnL with both legs filled `slippage_bps` worse than mid and charged
+    /// `commission_bps` on their filled notional
+    fn pnl(&self, slippage_bps: f64, commission_bps: f64) -> f64 {
+        let slip = slippage_bps / 10000.0;
+        let fee = commission_bps / 10000.0;
+        let entry = self.entry_mid * (1.0 + self.direction() * slip);
+        let exit = self.exit_mid * (1.0 - self.direction() * slip);
+
+        let fees = (entry + exit) * self.quantity * fee;
+        self.direction() * (exit - entry) * self.quantity - fees
+    }
+}
+
+/// Re-price a finished backtest's trades under other cost assumptions
+///
+/// Signals and entry/exit timing are taken as given: only fill prices and
+/// fees change, and quantities are kept as traded. That isolates how much
+/// of the edge survives higher costs, but ignores second-order effects such
+/// as a stop being hit earlier because of a worse fill.
+pub fn cost_sensitivity(
+    results: &BacktestResults,
+    slippage_bps: &[f64],
+    commission_bps: &[f64],
+) -> CostSensitivity {
+    let traded_slippage = results.config.slippage_bps.to_f64().unwrap_or_default();
+    let trades: Vec<MidTrade> = results.trades.iter()
+        .filter_map(|trade| MidTrade::from_trade(trade, traded_slippage))
+        .collect();
+
+    let mut scenarios = Vec::with_capacity(slippage_bps.len() * commission_bps.len());
+    for &slippage in slippage_bps {
+        for &commission in commission_bps {
+            let pnls: Vec<f64> = trades.iter().map(|t| t.pnl(slippage, commission)).collect();
+            let wins = pnls.iter().filter(|pnl| **pnl > 0.0).count();
+
+            scenarios.push(CostScenario {
+                slippage_bps: slippage,
+                commission_bps: commission,
+                total_pnl: pnls.iter().sum(),
+                win_rate: (!pnls.is_empty()).then(|| wins as f64 / pnls.len() as f64),
+            });
+        }
+    }
+
+    // PnL falls by (cost / 10000) * notional per unit of per-side cost,
+    // to first order, so break-even is gross edge over traded notional
+    let gross_pnl: f64 = trades.iter().map(MidTrade::gross_pnl).sum();
+    let notional: f64 = trades.iter().map(MidTrade::round_trip_notional).sum();
+    let break_even_cost_bps = if gross_pnl > 0.0 && notional > 0.0 {
+        Some(gross_pnl / notional * 10000.0)
+    } else {
+        None
+    };
+
+    CostSensitivity {
+        commission_bps: commission_bps.to_vec(),
+        scenarios,
+        gross_pnl,
+        break_even_cost_bps,
+    }
+}
+
+impl CostSensitivity {
+    /// Print a slippage x fee grid of total PnL
+    pub fn print_summary(&self) {
+        println!("Cost sensitivity (total PnL, slippage bps x fee bps):");
+        print!("{:>10}", "slip\\fee");
+        for fee in &self.commission_bps {
+            print!(" {:>12.1}", fee);
+        }
+        println!();
+
+        for row in self.scenarios.chunks(self.commission_bps.len().max(1)) {
+            print!("{:>10.1}", row[0].slippage_bps);
+            for scenario in row {
+                print!(" {:>12.2}", scenario.total_pnl);
+            }
+            println!();
+        }
+
+        println!("Gross PnL at mid: {:.2}", self.gross_pnl);
+        match self.break_even_cost_bps {
+            Some(cost) => println!("Break-even cost: {:.2} bps per side (slippage + fee)", cost),
+            None => println!("Break-even cost: none (no edge before costs)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::BacktestConfig;
+    use rust_decimal::Decimal;
+    use std::time::SystemTime;
+
+    fn trade(side: Side, entry: Decimal, exit: Decimal) -> BacktestTrade {
+        BacktestTrade {
+            entry_time: SystemTime::UNIX_EPOCH,
+            exit_time: SystemTime::UNIX_EPOCH,
+            side,
+            entry_price: entry,
+            exit_price: exit,
+            quantity: Decimal::ONE,
+            pnl: Decimal::ZERO,
+            fees: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_break_even_and_grid() {
+        let config = BacktestConfig {
+            slippage_bps: Decimal::ZERO,
+            ..BacktestConfig::default()
+        };
+        // Gross +20 over 420 of round-trip notional
+        let trades = vec![
+            trade(Side::Buy, Decimal::from(100), Decimal::from(110)),
+            trade(Side::Sell, Decimal::from(110), Decimal::from(100)),
+        ];
+        let results = BacktestResults::new(config, trades, vec![], Decimal::from(10000));
+
+        let sensitivity = cost_sensitivity(&results, &[0.0, 10.0], &[0.0, 4.0]);
+        assert_eq!(sensitivity.scenarios.len(), 4);
+        assert!((sensitivity.scenarios[0].total_pnl - 20.0).abs() < 1e-9);
+        assert!(sensitivity.scenarios[3].total_pnl < sensitivity.scenarios[0].total_pnl);
+
+        let break_even = sensitivity.break_even_cost_bps.unwrap();
+        assert!((break_even - 20.0 / 420.0 * 10000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::backtest::{BacktestConfig, BacktestEngine, BacktestEvent, BacktestResults, BacktestTrade};
+use crate::data::Side;
+use crate::exchange::MarketEvent;
+use crate::storage::{Experiment, JsonlWriter};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Candidate strategy run on the live feed without orders (`[shadow]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShadowConfig {
+    /// Config file whose `[strategy]` section is the candidate (empty = off)
+    pub strategy_file: String,
+    /// Name in logs and in shadow_journal.jsonl
+    pub name: String,
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            strategy_file: String::new(),
+            name: "shadow".to_string(),
+        }
+    }
+}
+
+impl ShadowConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.strategy_file.is_empty()
+    }
+}
+
+/// A completed shadow round trip, as appended to shadow_journal.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowTrade {
+    pub shadow: String,
+    pub symbol: String,
+    pub side: Side,
+    /// Milliseconds since UNIX epoch
+    pub entry_time_ms: u64,
+    pub exit_time_ms: u64,
+    pub entry_price: Decimal,
+    pub exit_price: Decimal,
+    pub quantity: Decimal,
+    /// Net of fees, USD
+    pub pnl: Decimal,
+    pub fees: Decimal,
+    #[serde(default, skip_serializing_if = "Experiment::is_empty")]
+    pub experiment: Experiment,
+}
+
+/// Second strategy configuration fed the same live events as the real one
+///
+/// Trades are simulated by a `BacktestEngine` with its own book, detectors
+/// and risk state, so a candidate parameter set can be judged on live data
+/// before it is promoted. Nothing reaches the exchange.
+pub struct ShadowStrategy {
+    name: String,
+    engine: BacktestEngine,
+    journal: Option<JsonlWriter>,
+    experiment: Experiment,
+    /// Trades already written to the journal
+    journaled: usize,
+}
+
+impl ShadowStrategy {
+    pub fn new(name: &str, config: BacktestConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            // Only trades are reported; a curve per book update would grow all session
+            engine: BacktestEngine::new(config).without_equity_curve(),
+            journal: None,
+            experiment: Experiment::default(),
+            journaled: 0,
+        }
+    }
+
+    /// Append every completed shadow trade to a journal
+    pub fn with_journal(mut self, journal: JsonlWriter) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Label journaled trades with an experiment
+    pub fn with_experiment(mut self, experiment: Experiment) -> Self {
+        self.experiment = experiment;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Simulate one live event; connection events are ignored
+    pub fn on_event(&mut self, event: &MarketEvent) -> Result<()> {
+        let Some(event) = to_backtest_event(event) else {
+            return Ok(());
+        };
+        self.engine.process_event(event)?;
+        self.journal_new_trades();
+        Ok(())
+    }
+
+    fn journal_new_trades(&mut self) {
+        let trades = self.engine.trades();
+        if trades.len() <= self.journaled {
+            return;
+        }
+        if let Some(journal) = self.journal.as_mut() {
+            for trade in &trades[self.journaled..] {
+                let record = shadow_trade(&self.name, self.engine.symbol(), trade, &self.experiment);
+                if let Err(e) = journal.append(&record) {
+                    warn!("Failed to journal shadow trade [{}]: {}", self.name, e);
+                }
+            }
+            if let Err(e) = journal.flush() {
+                warn!("Failed to flush shadow journal [{}]: {}", self.name, e);
+            }
+        }
+        self.journaled = trades.len();
+    }
+
+    /// Results so far (the equity curve holds only the latest point)
+    pub fn results(&self) -> BacktestResults {
+        self.engine.get_results()
+    }
+}
+
+/// Live event as the backtest engine's, stamped with exchange time
+fn to_backtest_event(event: &MarketEvent) -> Option<BacktestEvent> {
+    match event {
+        MarketEvent::DepthUpdate(update) => Some(BacktestEvent::OrderBookUpdate {
+            timestamp: UNIX_EPOCH + Duration::from_millis(update.event_time),
+            bids: update.parse_bids(),
+            asks: update.parse_asks(),
+        }),
+        MarketEvent::Trade(trade) => Some(BacktestEvent::Trade {
+            timestamp: trade.timestamp,
+            trade: trade.clone(),
+        }),
+        _ => None,
+    }
+}
+
+fn shadow_trade(name: &str, symbol: &str, trade: &BacktestTrade, experiment: &Experiment) -> ShadowTrade {
+    ShadowTrade {
+        shadow: name.to_string(),
+        symbol: symbol.to_string(),
+        side: trade.side,
+        entry_time_ms: epoch_ms(trade.entry_time),
+        exit_time_ms: epoch_ms(trade.exit_time),
+        entry_price: trade.entry_price,
+        exit_price: trade.exit_price,
+        quantity: trade.quantity,
+        pnl: trade.pnl,
+        fees: trade.fees,
+        experiment: experiment.clone(),
+    }
+}
+
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::binance::DepthUpdate;
+
+    #[test]
+    fn test_feeds_live_events_to_its_own_book() {
+        let mut shadow = ShadowStrategy::new("candidate", BacktestConfig {
+            symbol: "BTCUSDT".to_string(),
+            ..BacktestConfig::default()
+        });
+        let update = DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: 1_700_000_000_000,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 1,
+            final_update_id: 1,
+            prev_final_update_id: None,
+            bids: vec![["100.0".to_string(), "1".to_string()]],
+            asks: vec![["100.2".to_string(), "1".to_string()]],
+        };
+
+        shadow.on_event(&MarketEvent::Connected).unwrap();
+        shadow.on_event(&MarketEvent::DepthUpdate(update)).unwrap();
+
+        assert_eq!(shadow.engine.mid_price(), Some(Decimal::new(1001, 1)));
+        let results = shadow.results();
+        assert_eq!(results.total_trades, 0);
+        assert_eq!(results.equity_curve.len(), 1);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::backtest::{
+    BacktestConfig, BacktestEvent, DataFormat, DateRange, HistoricalDataSource, StrategyComparison,
+};
+use crate::strategy::RecalibrationConfig;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Outcome of one walk-forward run over recent recordings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdProposal {
+    /// Run time in milliseconds since UNIX epoch
+    pub timestamp_ms: u64,
+    /// Replayed window
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub events: usize,
+    pub candidates: usize,
+    pub current_primary: f64,
+    pub current_confirming: f64,
+    /// Backtest net PnL (USD) and trades at the current thresholds
+    pub current_pnl: f64,
+    pub current_trades: usize,
+    pub proposed_primary: f64,
+    pub proposed_confirming: f64,
+    pub proposed_pnl: f64,
+    pub proposed_trades: usize,
+    /// Set by the caller once the thresholds are live
+    #[serde(default)]
+    pub applied: bool,
+}
+
+impl ThresholdProposal {
+    pub fn changes_thresholds(&self) -> bool {
+        self.proposed_primary != self.current_primary || self.proposed_confirming != self.current_confirming
+    }
+}
+
+/// Events for `symbol` recorded in `dir` over the `lookback_hours` before `now`
+pub fn load_recent(dir: &Path, symbol: &str, lookback_hours: u64, now: SystemTime) -> Result<Vec<BacktestEvent>> {
+    let since = now - Duration::from_secs(lookback_hours * 3600);
+    let range = DateRange {
+        start_date: DateTime::<Utc>::from(since).date_naive(),
+        end_date: DateTime::<Utc>::from(now).date_naive(),
+    };
+
+    let mut events = HistoricalDataSource::new(dir, DataFormat::Jsonl, range)
+        .with_symbol(symbol)
+        .load()?;
+    events.retain(|event| event.timestamp() >= since && event.timestamp() <= now);
+    Ok(events)
+}
+
+/// Backtest every candidate around `current` (primary, confirming) over
+/// `events` and propose the best one
+///
+/// The best candidate by net PnL is proposed only with at least
+/// `min_trades` trades and `min_improvement_usd` over the current
+/// thresholds; otherwise the proposal keeps them. `None` without events.
+pub fn recalibrate(
+    events: &[BacktestEvent],
+    base: &BacktestConfig,
+    current: (f64, f64),
+    config: &RecalibrationConfig,
+    now: SystemTime,
+) -> Result<Option<ThresholdProposal>> {
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        return Ok(None);
+    };
+
+    let candidates = config.candidates(current);
+    let mut comparison = StrategyComparison::new();
+    for (i, (primary, confirming)) in candidates.iter().enumerate() {
+        let mut variant = base.clone();
+        variant.strategy.imbalance_threshold = *primary;
+        variant.strategy.confirming_threshold = *confirming;
+        // Thresholds are what is being searched; keep them fixed per run
+        variant.strategy.calibration.enabled = false;
+        comparison = comparison.with_variant(&i.to_string(), variant);
+    }
+    for event in events {
+        comparison.process_event(event)?;
+    }
+
+    let scored: Vec<((f64, f64), f64, usize)> = candidates.iter()
+        .zip(comparison.results())
+        .map(|(candidate, variant)| {
+            let pnl = variant.results.total_return.to_f64().unwrap_or(0.0);
+            (*candidate, pnl, variant.results.total_trades)
+        })
+        .collect();
+    let Some(&(_, current_pnl, current_trades)) = scored.iter().find(|(candidate, _, _)| *candidate == current) else {
+        return Ok(None);
+    };
+
+    let best = scored.iter()
+        .filter(|(_, _, trades)| *trades >= config.min_trades)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .filter(|(_, pnl, _)| *pnl - current_pnl > config.min_improvement_usd);
+    let (proposed, proposed_pnl, proposed_trades) = best.copied().unwrap_or((current, current_pnl, current_trades));
+
+    Ok(Some(ThresholdProposal {
+        timestamp_ms: epoch_ms(now),
+        start_ms: epoch_ms(first.timestamp()),
+        end_ms: epoch_ms(last.timestamp()),
+        events: events.len(),
+        candidates: candidates.len(),
+        current_primary: current.0,
+        current_confirming: current.1,
+        current_pnl,
+        current_trades,
+        proposed_primary: proposed.0,
+        proposed_confirming: proposed.1,
+        proposed_pnl,
+        proposed_trades,
+        applied: false,
+    }))
+}
+
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates_stay_within_step_and_bounds() {
+        let config = RecalibrationConfig {
+            grid_step: 0.25,
+            max_step: 0.5,
+            primary_max: 3.25,
+            ..Default::default()
+        };
+
+        let candidates = config.candidates((3.0, 1.5));
+        assert!(candidates.iter().all(|(primary, _)| (2.5..=3.25).contains(primary)));
+        // 2.5, 2.75, 3.0, 3.25 primaries x 1.0 ..= 2.0 confirmings
+        assert_eq!(candidates.len(), 4 * 5);
+        assert!(candidates.contains(&(3.0, 1.5)));
+
+        let proposal = recalibrate(&[], &BacktestConfig::default(), (3.0, 1.5), &config, SystemTime::now()).unwrap();
+        assert!(proposal.is_none());
+    }
+}
@@ -1,32 +1,156 @@
-use front_run_vanilla::{BacktestEngine, BacktestConfig, BacktestEvent};
+use front_run_vanilla::{BacktestConfig, Side, Trade};
+use front_run_vanilla::backtest::{
+    BacktestEvent, DataFormat, DateRange, DriftConfig, FillModelConfig, HistoricalDataSource,
+    PortfolioBacktest, SignalRule, compare_observations, StrategyComparison, comparison_table, cost_sensitivity, html_report, load_journal,
+    resimulate,
+};
+use front_run_vanilla::exchange::binance::SymbolFilters;
+use front_run_vanilla::risk::EventCalendar;
+use front_run_vanilla::storage::{
+    DetectorObservation, JsonlWriter, OBSERVATION_CAPACITY, ObservationRecorder, label_observations, load_book_snapshots, load_observations,
+};
+use front_run_vanilla::strategy::{SizingMode, StrategyParams};
+use front_run_vanilla::utils::config::{BacktestSettings, CalendarConfig, StrategyConfig};
 use rust_decimal::Decimal;
-use std::time::{SystemTime, UNIX_EPOCH, Duration};
-use clap::Parser;
+use rust_decimal::prelude::ToPrimitive;
+use std::path::Path;
+use std::time::Duration;
+use clap::{Parser, Subcommand};
 
 /// Backtest the trading strategy on historical data
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// Start date (YYYY-MM-DD)
-    #[arg(short, long, default_value = "2024-01-01")]
+    #[arg(short, long, global = true, default_value = "2024-01-01")]
     start: String,
 
     /// End date (YYYY-MM-DD)
-    #[arg(short, long, default_value = "2024-12-31")]
+    #[arg(short, long, global = true, default_value = "2024-12-31")]
     end: String,
 
     /// Symbol to backtest
-    #[arg(long, default_value = "BTCUSDT")]
+    #[arg(long, global = true, default_value = "BTCUSDT")]
     symbol: String,
 
     /// Initial capital
-    #[arg(long, default_value = "10000")]
+    #[arg(long, global = true, default_value = "10000")]
     capital: f64,
+
+    /// Config file whose [strategy] section drives the backtest
+    /// (defaults to built-in parameters); its `kind` picks the front-run
+    /// engine or a built-in spread_capture / mean_reversion strategy
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Live fill journal (journal.jsonl) to sample per-order fill latencies from
+    #[arg(long, global = true)]
+    latency_journal: Option<String>,
+
+    /// Saved exchangeInfo response; simulated orders are rounded to its
+    /// tick/step sizes and rejected below its minimums
+    #[arg(long, global = true)]
+    exchange_info: Option<String>,
+
+    /// Directory of historical recordings (synthetic data when omitted)
+    #[arg(long, global = true)]
+    data_dir: Option<String>,
+
+    /// Recording format: jsonl (combined-stream frames) or csv (aggTrades)
+    #[arg(long, global = true, default_value = "jsonl")]
+    format: String,
+
+    /// Book snapshots recorded by the live trader (book_snapshots.bin);
+    /// the book starts from the latest one at or before the first event
+    #[arg(long, global = true)]
+    book_snapshots: Option<String>,
+
+    /// Re-price the trades at these slippage levels (bps, comma separated)
+    #[arg(long, global = true, value_delimiter = ',')]
+    sweep_slippage: Vec<f64>,
+
+    /// Re-price the trades at these fee levels (bps, comma separated)
+    #[arg(long, global = true, value_delimiter = ',')]
+    sweep_fees: Vec<f64>,
+
+    /// Write every detector output plus a market sample per evaluation to
+    /// this JSONL file (first variant only), for `backtester label`
+    #[arg(long, global = true)]
+    capture: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run two or more configs over the same data and compare them
+    Compare {
+        /// Config files, one variant each (named after the file stem)
+        #[arg(required = true, num_args = 2..)]
+        configs: Vec<String>,
+
+        /// HTML report with overlayed equity curves
+        #[arg(long, default_value = "comparison_report.html")]
+        report: String,
+    },
+
+    /// Re-price live journal fills under the fill model over recorded data
+    Resimulate {
+        /// Trade journal written by the live trader (journal.jsonl)
+        journal: String,
+    },
+
+    /// Backtest several symbols as one portfolio with shared risk limits
+    Portfolio {
+        /// Symbols to trade together (comma separated)
+        #[arg(long, required = true, value_delimiter = ',')]
+        symbols: Vec<String>,
+    },
+
+    /// Turn a capture (observations.jsonl or --capture output) into a
+    /// training dataset of features and forward returns
+    Label {
+        /// Captured detector outputs and market samples
+        observations: String,
+
+        /// Forward return horizons in ms (comma separated)
+        #[arg(long, value_delimiter = ',', default_value = "1000,5000,30000")]
+        horizons_ms: Vec<u64>,
+
+        /// Labeled dataset (JSONL, one row per market sample)
+        #[arg(long, default_value = "labeled.jsonl")]
+        output: String,
+    },
+
+    /// Replay a recorded session and diff its detector outputs against the
+    /// live trader's observations.jsonl from the same session
+    Drift {
+        /// Observations recorded live (storage.record_observations)
+        live_observations: String,
+
+        /// Max timestamp gap (ms) for a replayed output to match a live one
+        #[arg(long, default_value = "50")]
+        tolerance_ms: u64,
+
+        /// Max absolute value difference before a match counts as diverged
+        #[arg(long, default_value = "0.000001")]
+        value_tolerance: f64,
+
+        /// Full report (JSON, with every mismatched signal)
+        #[arg(long, default_value = "drift_report.json")]
+        output: String,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let range = DateRange::parse(&args.start, &args.end)?;
+
+    if let Some(Command::Label { observations, horizons_ms, output }) = &args.command {
+        return run_label(observations, horizons_ms, output);
+    }
 
     println!("╔════════════════════════════════════════════════╗");
     println!("║         BACKTESTING ENGINE                     ║");
@@ -35,37 +159,132 @@ async fn main() -> anyhow::Result<()> {
     println!("Symbol: {}", args.symbol);
     println!("Period: {} to {}", args.start, args.end);
     println!("Initial Capital: ${}", args.capital);
-    println!();
 
-    // Create configuration
-    let config = BacktestConfig {
-        symbol: args.symbol.clone(),
-        initial_capital: Decimal::from_f64_retain(args.capital).unwrap(),
-        position_size: Decimal::from(1000),
-        take_profit_bps: Decimal::from(10),
-        stop_loss_bps: Decimal::from(5),
-        max_hold_time_ms: 5000,
-        slippage_bps: Decimal::from(2),
-        commission_bps: Decimal::from(4),
-        latency_ms: 100,
+    if let Some(Command::Portfolio { symbols }) = &args.command {
+        return run_portfolio(&args, symbols, range);
+    }
+
+    // Build every variant before loading data so config errors fail fast
+    let variants = match &args.command {
+        Some(Command::Compare { configs, .. }) => {
+            let mut variants = Vec::new();
+            for path in configs {
+                println!("Variant Config: {}", path);
+                let name = variant_name(path, &variants);
+                variants.push((name, backtest_config(&args, Some(path))?));
+            }
+            variants
+        }
+        None | Some(Command::Resimulate { .. }) | Some(Command::Portfolio { .. })
+        | Some(Command::Label { .. }) | Some(Command::Drift { .. }) => {
+            if let Some(path) = &args.config {
+                println!("Strategy Config: {}", path);
+            }
+            vec![("backtest".to_string(), backtest_config(&args, args.config.as_deref())?)]
+        }
     };
+    for (name, config) in &variants {
+        println!("Strategy [{}]: {:?}", name, config.strategy.kind);
+    }
 
-    // Create backtesting engine
-    let mut engine = BacktestEngine::new(config);
+    // Variants share one event stream, so they must agree on tie ordering
+    let ordering = variants[0].1.ordering;
+    if variants.iter().any(|(_, config)| config.ordering.same_timestamp != ordering.same_timestamp) {
+        anyhow::bail!("Variants disagree on [backtest.ordering] same_timestamp");
+    }
+    println!("Ordering: {:?} ties, exits {:?}", ordering.same_timestamp, ordering.exit_timing);
+    println!();
+
+    let primary_config = variants[0].1.clone();
+    let mut comparison = variants.into_iter()
+        .fold(StrategyComparison::new(), |comparison, (name, config)| {
+            comparison.with_variant(&name, config)
+        });
+
+    // Observations are drained to the file as the replay runs; a drift
+    // run also keeps them in memory for the comparison
+    let drift = matches!(args.command, Some(Command::Drift { .. }));
+    let mut capture = match &args.capture {
+        Some(path) => {
+            println!("Capture: {}", path);
+            Some(JsonlWriter::open(path)?)
+        }
+        None => None,
+    };
+    let mut observations = if capture.is_some() || drift {
+        let (recorder, rx) = ObservationRecorder::channel(OBSERVATION_CAPACITY);
+        comparison = comparison.with_recorder(recorder.clone());
+        Some((recorder, rx))
+    } else {
+        None
+    };
+    let mut replayed = Vec::new();
 
     println!("Loading historical data...");
-    
-    // NOTE: In production, you would load real historical data here
-    // For this demo, we'll generate synthetic data
-    let events = generate_synthetic_data(&args.symbol, &args.start, &args.end)?;
-    
+
+    let mut events = match &args.data_dir {
+        Some(dir) => {
+            let format: DataFormat = args.format.parse()?;
+            let source = HistoricalDataSource::new(dir, format, range)
+                .with_symbol(&args.symbol);
+            println!("Data: {} ({:?}, {} files)", dir, format, source.files()?.len());
+            source.load()?
+        }
+        None => {
+            println!("Data: synthetic (pass --data-dir for recordings)");
+            generate_synthetic_data(range)
+        }
+    };
+
+    if events.is_empty() {
+        anyhow::bail!("No events between {} and {}", args.start, args.end);
+    }
+    ordering.sort_events(&mut events);
     println!("Loaded {} events", events.len());
+
+    if let Some(path) = &args.book_snapshots {
+        let first_ms = events[0].timestamp()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let snapshot = load_book_snapshots(path)?
+            .into_iter()
+            .filter(|snapshot| snapshot.symbol.eq_ignore_ascii_case(&args.symbol) && snapshot.timestamp_ms <= first_ms)
+            .max_by_key(|snapshot| snapshot.timestamp_ms);
+        match snapshot {
+            Some(snapshot) => {
+                println!("Warm start: {} book ({} bids / {} asks, {}s before first event)",
+                    snapshot.symbol, snapshot.bids.len(), snapshot.asks.len(),
+                    (first_ms - snapshot.timestamp_ms) / 1000);
+                comparison.warm_start(&snapshot)?;
+            }
+            None => println!("Warm start: no {} snapshot before the first event in {}", args.symbol, path),
+        }
+    }
+
+    if let Some(Command::Resimulate { journal }) = &args.command {
+        let fills = load_journal(journal)?;
+        println!("Journal: {} ({} fills)", journal, fills.len());
+        println!();
+
+        let gap = resimulate(&fills, &events, &primary_config)?;
+        gap.print_summary();
+
+        std::fs::write("execution_gap.json", serde_json::to_string_pretty(&gap)?)?;
+        println!("Per-fill comparison saved to: execution_gap.json");
+        return Ok(());
+    }
     println!();
-    println!("Running backtest...");
+    println!("Running backtest ({} variant(s))...", comparison.variant_count());
 
-    // Process all events
+    // Process all events, once per variant
     for (i, event) in events.iter().enumerate() {
-        engine.process_event(event.clone())?;
+        comparison.process_event(event)?;
+
+        // Drained after every event so the bounded channel never fills
+        if let Some((_, rx)) = observations.as_mut() {
+            drain_observations(rx, capture.as_mut(), drift.then_some(&mut replayed))?;
+        }
 
         // Progress indicator
         if i % 10000 == 0 {
@@ -73,41 +292,302 @@ async fn main() -> anyhow::Result<()> {
             std::io::Write::flush(&mut std::io::stdout())?;
         }
     }
+    if let Some((recorder, mut rx)) = observations.take() {
+        drain_observations(&mut rx, capture.as_mut(), drift.then_some(&mut replayed))?;
+        if recorder.dropped() > 0 {
+            anyhow::bail!("{} observations dropped during the replay", recorder.dropped());
+        }
+    }
+    if let Some(mut writer) = capture.take() {
+        writer.flush()?;
+        println!();
+        println!("Captured {} observations", writer.records_written());
+    }
+
+    if let Some(Command::Drift { live_observations, tolerance_ms, value_tolerance, output }) = &args.command {
+        let live = load_observations(live_observations)?;
+        println!();
+        println!("Live: {} ({} observations), replay: {} observations", live_observations, live.len(), replayed.len());
+        println!();
+
+        let config = DriftConfig {
+            time_tolerance_ms: *tolerance_ms,
+            value_tolerance: *value_tolerance,
+            signals: vec![SignalRule::new("imbalance", "z_score", primary_config.strategy.imbalance_threshold)],
+        };
+        let report = compare_observations(&live, &replayed, &config);
+        report.print_summary();
+
+        std::fs::write(output, serde_json::to_string_pretty(&report)?)?;
+        println!("Drift report saved to: {}", output);
+        return Ok(());
+    }
 
     println!();
     println!();
 
-    // Get and print results
-    let results = engine.get_results();
-    results.print_summary();
+    let variants = comparison.results();
+
+    match &args.command {
+        Some(Command::Compare { report, .. }) => {
+            println!("{}", comparison_table(&variants));
+
+            let title = format!("{} comparison, {} to {}", args.symbol, args.start, args.end);
+            std::fs::write(report, html_report(&title, &variants))?;
+            println!("Report saved to: {}", report);
 
-    // Save results to JSON
-    let json = serde_json::to_string_pretty(&results)?;
-    std::fs::write("backtest_results.json", json)?;
-    println!("Results saved to: backtest_results.json");
+            let all: Vec<_> = variants.iter()
+                .map(|v| (v.name.clone(), v.results.clone()))
+                .collect();
+            std::fs::write("comparison_results.json", serde_json::to_string_pretty(&all)?)?;
+            println!("Results saved to: comparison_results.json");
+        }
+        None | Some(Command::Resimulate { .. }) | Some(Command::Portfolio { .. })
+        | Some(Command::Label { .. }) | Some(Command::Drift { .. }) => {
+            // Get and print results
+            let results = &variants[0].results;
+            results.print_summary();
+
+            // Save results to JSON
+            let json = serde_json::to_string_pretty(results)?;
+            std::fs::write("backtest_results.json", json)?;
+            println!("Results saved to: backtest_results.json");
+
+            let title = format!("{} backtest, {} to {}", args.symbol, args.start, args.end);
+            std::fs::write("backtest_report.html", html_report(&title, &variants))?;
+            println!("Report saved to: backtest_report.html");
+        }
+    }
+
+    // Cost sweep on the finished trade lists (signals are not re-run)
+    if !args.sweep_slippage.is_empty() || !args.sweep_fees.is_empty() {
+        for variant in &variants {
+            let config = &variant.results.config;
+            let slippage = sweep_levels(&args.sweep_slippage, config.slippage_bps)?;
+            let fees = sweep_levels(&args.sweep_fees, config.commission_bps)?;
+
+            println!();
+            println!("[{}]", variant.name);
+            let sensitivity = cost_sensitivity(&variant.results, &slippage, &fees);
+            sensitivity.print_summary();
+
+            let path = format!("sensitivity_{}.json", variant.name.replace(['/', '\\'], "_"));
+            std::fs::write(&path, serde_json::to_string_pretty(&sensitivity)?)?;
+            println!("Sensitivity saved to: {}", path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Backtest settings shared by every variant; `[strategy]`, `[risk]`, `[calendar]` and
+/// `[backtest.ordering]`/`[backtest.adverse_selection]`/`[backtest.sharpe]`/
+/// `[backtest.instrument]`/`[[backtest.outages]]` come from the variant's config file
+fn backtest_config(args: &Args, path: Option<&str>) -> anyhow::Result<BacktestConfig> {
+    // Strategy parameters: same [strategy] section the live trader reads
+    let (strategy, settings) = match path {
+        Some(path) => (StrategyConfig::from_file(path)?.params, BacktestSettings::from_file(path)?),
+        None => (StrategyParams::default(), BacktestSettings::default()),
+    };
+
+    Ok(BacktestConfig {
+        symbol: args.symbol.clone(),
+        initial_capital: Decimal::from_f64_retain(args.capital).unwrap(),
+        position_size: match &settings.position_sizing {
+            Some(sizing) if sizing.mode != SizingMode::FixedNotional => {
+                Decimal::from_f64_retain(sizing.base_size()).unwrap_or_default()
+            }
+            _ => Decimal::from(1000),
+        },
+        sizing: settings.position_sizing.as_ref().map(|sizing| sizing.mode).unwrap_or_default(),
+        strategy,
+        slippage_bps: Decimal::from(2),
+        commission_bps: Decimal::from(4),
+        maker_commission_bps: Decimal::from(2),
+        latency_ms: 100,
+        fill_model: match &args.latency_journal {
+            Some(path) => FillModelConfig::default().with_latencies_from_journal(path)?,
+            None => FillModelConfig::default(),
+        }
+        .with_adverse_selection(settings.adverse_selection),
+        ordering: settings.ordering,
+        risk_limits: settings.risk_limits.unwrap_or_default(),
+        scale_by_confidence: false,
+        exchange_filters: match &args.command {
+            // Portfolio legs load their own symbol's filters
+            Some(Command::Portfolio { .. }) => None,
+            _ => load_filters(args, &args.symbol)?,
+        },
+        instrument: settings.instrument,
+        sharpe: settings.sharpe,
+        max_book_levels: settings.max_book_levels,
+        outages: settings.outages,
+        calendar: match settings.calendar {
+            Some(calendar) if !calendar.source.is_empty() => Some(load_calendar(&calendar)?),
+            _ => None,
+        },
+    })
+}
+
+/// `[calendar]` read from disk; a URL serves today's events, not the
+/// ones in effect over the replayed range, so it has to be saved first
+fn load_calendar(calendar: &CalendarConfig) -> anyhow::Result<EventCalendar> {
+    if EventCalendar::is_url(&calendar.source) {
+        anyhow::bail!(
+            "[calendar] source {} is a URL; save the calendar to a file to replay its blackouts",
+            calendar.source,
+        );
+    }
+    let loaded = EventCalendar::from_file(&calendar.source, calendar.blackout.clone())?;
+    println!("Event calendar: {} events from {}", loaded.len(), calendar.source);
+    Ok(loaded)
+}
+
+/// Label a capture with forward returns for supervised training
+fn run_label(observations: &str, horizons_ms: &[u64], output: &str) -> anyhow::Result<()> {
+    let captured = load_observations(observations)?;
+    let samples = label_observations(&captured, horizons_ms);
+
+    let mut writer = JsonlWriter::open(output)?;
+    for sample in &samples {
+        writer.append(sample)?;
+    }
+    writer.flush()?;
+
+    println!("Labeled {} samples from {} observations (horizons {:?} ms)",
+        samples.len(), captured.len(), horizons_ms);
+    println!("Dataset saved to: {}", output);
+    Ok(())
+}
+
+/// Move recorded observations to the capture file and/or the drift buffer
+fn drain_observations(
+    rx: &mut tokio::sync::mpsc::Receiver<DetectorObservation>,
+    mut writer: Option<&mut JsonlWriter>,
+    mut replayed: Option<&mut Vec<DetectorObservation>>,
+) -> anyhow::Result<()> {
+    while let Ok(observation) = rx.try_recv() {
+        if let Some(writer) = writer.as_mut() {
+            writer.append(&observation)?;
+        }
+        if let Some(replayed) = replayed.as_mut() {
+            replayed.push(observation);
+        }
+    }
+    Ok(())
+}
+
+/// `symbol`'s filters from `--exchange-info`, if given
+fn load_filters(args: &Args, symbol: &str) -> anyhow::Result<Option<SymbolFilters>> {
+    match &args.exchange_info {
+        Some(path) => Ok(Some(SymbolFilters::load(path, symbol)?)),
+        None => Ok(None),
+    }
+}
+
+/// Run every symbol through one portfolio and report each one's share
+///
+/// Capital and the per-entry budget are split by `[backtest.portfolio]`
+/// allocation; `[risk]` limits apply to the combined book.
+fn run_portfolio(args: &Args, symbols: &[String], range: DateRange) -> anyhow::Result<()> {
+    let config = backtest_config(args, args.config.as_deref())?;
+    let settings = match &args.config {
+        Some(path) => BacktestSettings::from_file(path)?,
+        None => BacktestSettings::default(),
+    };
+    println!("Symbols: {}", symbols.join(", "));
+    println!("Allocation: {:?}", settings.portfolio.allocation);
+    println!();
+
+    let ordering = config.ordering;
+    let mut backtest = PortfolioBacktest::new(config, settings.portfolio, symbols)?;
+    for symbol in symbols {
+        if let Some(filters) = load_filters(args, symbol)? {
+            backtest.set_exchange_filters(filters)?;
+        }
+    }
+
+    println!("Loading historical data...");
+    let mut events = Vec::new();
+    for symbol in symbols {
+        let mut symbol_events = match &args.data_dir {
+            Some(dir) => HistoricalDataSource::new(dir, args.format.parse()?, range)
+                .with_symbol(symbol)
+                .load()?,
+            None => generate_synthetic_data(range),
+        };
+        ordering.sort_events(&mut symbol_events);
+        println!("{}: {} events", symbol, symbol_events.len());
+        events.extend(symbol_events.into_iter().map(|event| (symbol.clone(), event)));
+    }
 
+    if events.is_empty() {
+        anyhow::bail!("No events between {} and {}", args.start, args.end);
+    }
+
+    // Interleave symbols by time; each symbol's own ties keep their order
+    events.sort_by_key(|(_, event)| event.timestamp());
+
+    println!();
+    println!("Running portfolio backtest...");
+    for (symbol, event) in events {
+        backtest.process_event(&symbol, event)?;
+    }
+
+    let results = backtest.results()?;
+    results.print_summary();
+
+    std::fs::write("portfolio_results.json", serde_json::to_string_pretty(&results)?)?;
+    println!("Results saved to: portfolio_results.json");
     Ok(())
 }
 
+/// Swept levels, or just the configured level when none were given
+fn sweep_levels(levels: &[f64], configured: Decimal) -> anyhow::Result<Vec<f64>> {
+    if levels.is_empty() {
+        let configured = configured.to_f64()
+            .ok_or_else(|| anyhow::anyhow!("configured level {} is out of f64 range", configured))?;
+        Ok(vec![configured])
+    } else {
+        Ok(levels.to_vec())
+    }
+}
+
+/// File stem of the config, or the full path if that stem is taken
+fn variant_name(path: &str, existing: &[(String, BacktestConfig)]) -> String {
+    let stem = Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(path)
+        .to_string();
+
+    if existing.iter().any(|(name, _)| *name == stem) {
+        path.to_string()
+    } else {
+        stem
+    }
+}
+
 /// Generate synthetic market data for backtesting demonstration
-/// In production, replace this with actual historical data loading
-fn generate_synthetic_data(
-    symbol: &str,
-    start: &str,
-    end: &str,
-) -> anyhow::Result<Vec<BacktestEvent>> {
+///
+/// Starts at the beginning of the range and stops at its end or after
+/// 100,000 book updates (about 3 hours at 100ms intervals).
+fn generate_synthetic_data(range: DateRange) -> Vec<BacktestEvent> {
     use rand::Rng;
     let mut rng = rand::thread_rng();
 
     let mut events = Vec::new();
-    let mut current_time = UNIX_EPOCH + Duration::from_secs(1704067200); // 2024-01-01
+    let mut current_time = range.start();
     let mut current_price = Decimal::from(100000); // Starting price
 
-    // Generate 100,000 events (about 1 day of data at 100ms intervals)
     for _ in 0..100000 {
+        if current_time >= range.end() {
+            break;
+        }
+
         // Random walk for price
         let change = rng.gen_range(-0.001..0.001);
-        current_price = current_price * (Decimal::ONE + Decimal::from_f64_retain(change).unwrap());
+        current_price *= Decimal::ONE + Decimal::from_f64_retain(change).unwrap();
 
         // Generate bid/ask levels
         let spread = current_price * Decimal::from_f64_retain(0.0001).unwrap();
@@ -133,14 +613,14 @@ fn generate_synthetic_data(
 
         // Occasionally add trades
         if rng.gen_bool(0.1) {
-            let trade = crate::data::Trade {
+            let trade = Trade {
                 id: events.len() as u64,
                 price: current_price,
                 quantity: Decimal::from_f64_retain(rng.gen_range(0.01..0.5)).unwrap(),
                 side: if rng.gen_bool(0.5) { 
-                    crate::data::Side::Buy 
+                    Side::Buy 
                 } else { 
-                    crate::data::Side::Sell 
+                    Side::Sell 
                 },
                 timestamp: current_time,
                 is_buyer_maker: rng.gen_bool(0.5),
@@ -155,6 +635,6 @@ fn generate_synthetic_data(
         current_time += Duration::from_millis(100);
     }
 
-    Ok(events)
+    events
 }
 
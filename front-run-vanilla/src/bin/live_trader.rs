@@ -1,14 +1,44 @@
 use front_run_vanilla::{
-    OrderBook, BinanceWebSocket, BinanceRestClient, MarketEvent,
-    ImbalanceDetector, FlowAnalyzer, SignalAggregator,
-    ExecutionEngine, RiskManager, RiskLimits, Config,
+    OrderBook, BinanceWebSocket, BinanceRestClient, Exchange, MarketEvent, TimedEvent,
+    ExecutionEngine, RiskManager, Config, BacktestConfig,
 };
+use front_run_vanilla::backtest::{ShadowStrategy, ThresholdProposal, load_recent, recalibrate};
+use front_run_vanilla::exchange::binance::{
+    EventPipeline, FuturesApi, is_mainnet, load_market_context, record_live_run, require_live_opt_in, run_preflight,
+    SymbolFilters, UserDataStream,
+};
+use front_run_vanilla::risk::{EventCalendar, FleetSync, SharedState, apply_risk_command};
+use front_run_vanilla::strategy::{
+    BasisDetector, CadenceLevel, Detector, DetectorPolicy, EngineView, ExecutionOutcome, FastPathSnapshot,
+    FullBookRequest, LiquidationDetector, PolicyAction, ReferenceFilter, RejectionReason, SizingMode, SlowPathRequest,
+    StrategyControl, reconcile_accounts, snapshot_handoff,
+};
+use front_run_vanilla::storage::{
+    BookSnapshot, BookSnapshotWriter, BusMessage, BusPublisher, HeatmapSample, HeatmapWriter, JsonlWriter,
+    ObservationRecorder, WebhookNotifier, market_observation,
+};
+use front_run_vanilla::utils::config::StrategyConfig;
+#[cfg(feature = "chaos")]
+use front_run_vanilla::utils::chaos::FaultInjector;
 use rust_decimal::Decimal;
+use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tracing::{info, warn, error};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{mpsc, watch};
+use tracing::{info, warn, error, debug};
 use std::env;
 
+/// How often the quote asset's USD value is re-read for non-USD quotes
+const QUOTE_RATE_REFRESH: Duration = Duration::from_secs(10);
+
+/// Market events queued for the slow path before the fast path drops them
+const SLOW_EVENT_CAPACITY: usize = 4096;
+
+/// How often the detector policy reads hit rates and attributed PnL
+const DETECTOR_POLICY_INTERVAL: Duration = Duration::from_secs(10);
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Load environment variables
@@ -32,6 +62,26 @@ async fn main() -> anyhow::Result<()> {
     let config = Config::load()?;
     info!("✓ Configuration loaded: {}", config.general.environment);
 
+    // Only the front-run engine trades live; the built-in strategies are
+    // backtest only
+    if !config.strategy.params.kind.runs_live() {
+        anyhow::bail!(
+            "strategy.kind = {:?} is backtest only; the live trader runs front_run",
+            config.strategy.params.kind
+        );
+    }
+
+    // Mainnet production needs an explicit opt-in so a testing session
+    // can't trade real funds by picking up the wrong config
+    let live_risk_flag = env::args().any(|arg| arg == "--i-understand-live-risk");
+    require_live_opt_in(
+        &config.preflight,
+        &config.general.environment,
+        config.exchange.testnet,
+        &config.exchange.api_endpoint,
+        live_risk_flag,
+    )?;
+
     // Get API credentials
     let api_key = env::var("BINANCE_API_KEY")
         .expect("BINANCE_API_KEY not found in environment");
@@ -44,60 +94,254 @@ async fn main() -> anyhow::Result<()> {
     let orderbook = Arc::new(OrderBook::new(&config.general.symbol));
 
     // Create WebSocket connection
-    let (ws, mut event_rx) = BinanceWebSocket::new(
+    let (mut ws, mut event_rx) = BinanceWebSocket::new(
         config.general.symbol.clone(),
         config.exchange.ws_endpoint.clone(),
         Arc::clone(&orderbook),
     );
 
+    ws = ws.with_streams(config.exchange.streams.clone())?;
+
+    // Optional level TTLs so phantom levels can't poison the imbalance ratio
+    let level_metrics = if config.exchange.level_ttl_ms > 0 {
+        ws = ws.with_level_ttl(Duration::from_millis(config.exchange.level_ttl_ms));
+        info!("✓ Order book level TTL: {}ms", config.exchange.level_ttl_ms);
+        ws.level_metrics()
+    } else {
+        None
+    };
+
+    // Optional raw frame capture for reproducing parse failures
+    if config.exchange.frame_capture_size > 0 {
+        let dir = Path::new(&config.storage.data_dir).join("frames");
+        info!("✓ Raw frame capture: last {} frames -> {}", config.exchange.frame_capture_size, dir.display());
+        ws = ws.with_frame_capture(config.exchange.frame_capture_size, dir);
+    }
+
+    // Dropped-diff tracking; clear the book if too many recent diffs were gapped
+    if config.exchange.resync_gap_rate > 0.0 {
+        ws = ws.with_resync_gap_rate(config.exchange.resync_gap_rate);
+    }
+
+    // Snapshot + replay so the book is complete from the first diff and
+    // after every gap, instead of filling in as levels happen to change
+    if config.exchange.depth_snapshot_limit > 0 {
+        let snapshots = BinanceRestClient::new(String::new(), String::new(), config.exchange.api_endpoint.clone())
+            .with_api(FuturesApi::for_instrument(&config.general.instrument));
+        ws = ws.with_depth_snapshots(Arc::new(snapshots), config.exchange.depth_snapshot_limit);
+        info!("✓ Order book snapshots: {} levels", config.exchange.depth_snapshot_limit);
+    }
+    let sequence_metrics = ws.sequence_metrics();
+    let book_metrics = ws.book_validation_metrics();
+    let feed_latency = ws.feed_latency_metrics();
+
+    if config.exchange.silence_timeout_ms > 0 {
+        ws = ws.with_silence_timeout(Duration::from_millis(config.exchange.silence_timeout_ms));
+    }
+
+    // Bound the book so far levels from a long session don't pile up
+    if config.exchange.max_book_levels > 0 {
+        ws = ws.with_max_book_levels(config.exchange.max_book_levels);
+        info!("✓ Order book depth cap: {} levels per side", config.exchange.max_book_levels);
+    }
+    let depth_metrics = ws.depth_limit_metrics();
+
+    // Fault injection for drills, driven by "chaos ..." commands on stdin
+    #[cfg(feature = "chaos")]
+    let faults = FaultInjector::new();
+    #[cfg(feature = "chaos")]
+    {
+        warn!("⚠️  Chaos build: faults can be injected with 'chaos ...' commands");
+        ws = ws.with_faults(faults.clone());
+    }
+
     // Start WebSocket in background
     tokio::spawn(async move {
         ws.run().await;
     });
 
-    // Create REST client for order execution
+    // Optionally reduce what the strategy sees; the book still gets every diff
+    let event_pipeline = EventPipeline::from_config(&config.exchange.event_filter);
+    let filter_metrics = if event_pipeline.is_empty() {
+        None
+    } else {
+        info!("✓ Event filter: {:?}", config.exchange.event_filter);
+        let metrics = event_pipeline.metrics();
+        event_rx = event_pipeline.spawn(event_rx);
+        Some(metrics)
+    };
+
+    // Reference feed (e.g. BTCUSDT) for alts whose signals lag the leader
+    let mut reference_filter = None;
+    if !config.reference.symbol.is_empty() && config.reference.symbol != config.general.symbol {
+        let reference_book = Arc::new(OrderBook::new(&config.reference.symbol));
+        let (reference_ws, mut reference_rx) = BinanceWebSocket::new(
+            config.reference.symbol.clone(),
+            config.exchange.ws_endpoint.clone(),
+            Arc::clone(&reference_book),
+        );
+
+        tokio::spawn(async move {
+            reference_ws.run().await;
+        });
+        // Book updates happen inside the WS task; just drain the events
+        tokio::spawn(async move {
+            while reference_rx.recv().await.is_some() {}
+        });
+
+        info!("✓ Reference feed: {} ({}ms horizon)",
+            config.reference.symbol, config.reference.horizon_ms);
+        reference_filter = Some((ReferenceFilter::new(config.reference.clone()), reference_book));
+    }
+
+    // Spot book for the same symbol (basis confirming signal / veto)
+    let mut basis = None;
+    if !config.basis.spot_ws_endpoint.is_empty() {
+        let spot_book = Arc::new(OrderBook::new(&config.general.symbol));
+        let (spot_ws, mut spot_rx) = BinanceWebSocket::new(
+            config.general.symbol.clone(),
+            config.basis.spot_ws_endpoint.clone(),
+            Arc::clone(&spot_book),
+        );
+
+        tokio::spawn(async move {
+            spot_ws.run().await;
+        });
+        tokio::spawn(async move {
+            while spot_rx.recv().await.is_some() {}
+        });
+
+        info!("✓ Spot feed for basis: {}", config.basis.spot_ws_endpoint);
+        basis = Some((BasisDetector::from_config(&config.basis), spot_book));
+    }
+
+    // Liquidation cascades, fed from the forceOrder stream
+    let mut liquidation = config.exchange.streams.force_order
+        .then(|| LiquidationDetector::from_config(&config.liquidation));
+    if liquidation.is_some() {
+        info!("✓ Liquidation detector: {} notional within {}ms",
+            config.liquidation.min_notional, config.liquidation.window_ms);
+    }
+
+    // Create REST client for order execution (COIN-M /dapi for inverse
+    // contracts, in which case api_endpoint must be a dapi host)
+    let futures_api = FuturesApi::for_instrument(&config.general.instrument);
     let rest_client = BinanceRestClient::new(
-        api_key,
-        secret_key,
+        api_key.clone(),
+        secret_key.clone(),
         config.exchange.api_endpoint.clone(),
-    );
+    )
+    .with_api(futures_api);
+    #[cfg(feature = "chaos")]
+    let rest_client = rest_client.with_faults(faults.clone());
 
     // Test connectivity
     rest_client.test_connectivity().await?;
     info!("✓ Connected to Binance API");
 
-    // Create signal detectors
-    let mut imbalance_detector = ImbalanceDetector::new(
-        5,
-        100,
-        config.strategy.imbalance_threshold,
-    );
+    // Round orders to the symbol's grid; increments left at 0 in
+    // [general.instrument] are taken from exchangeInfo
+    config.general.instrument.validate()?;
+    let mut instrument = config.general.instrument;
+    let mut min_order_notional = Decimal::from_f64_retain(config.strategy.params.dust.min_notional_usd).unwrap_or_default();
+    if instrument.tick_size.is_zero() || instrument.lot_size.is_zero() {
+        let filters = rest_client.get_exchange_info().await
+            .and_then(|info| SymbolFilters::from_exchange_info(&info, &config.general.symbol));
+        match filters {
+            Ok(filters) => {
+                instrument = instrument.with_increments(
+                    if instrument.tick_size.is_zero() { filters.tick_size } else { instrument.tick_size },
+                    if instrument.lot_size.is_zero() { filters.step_size } else { instrument.lot_size },
+                );
+                if !filters.min_notional.is_zero() {
+                    min_order_notional = filters.min_notional;
+                }
+                info!(
+                    "✓ Instrument increments: tick {} lot {}, min notional {}",
+                    instrument.tick_size, instrument.lot_size, min_order_notional
+                );
+            }
+            Err(e) => warn!("⚠️  exchangeInfo unavailable, orders are not rounded: {}", e),
+        }
+    }
 
-    let mut flow_analyzer = FlowAnalyzer::new(
-        20,
-        5000,
-        0.6,
-    );
+    // Pre-flight checks: abort before any stream or order if the account
+    // or environment is not fit to trade
+    if config.preflight.enabled {
+        let report = run_preflight(
+            &rest_client,
+            &config.preflight,
+            &config.general.symbol,
+            config.exchange.testnet,
+            &config.exchange.api_endpoint,
+            &config.exchange.ws_endpoint,
+        ).await;
 
-    let signal_aggregator = SignalAggregator::new(
-        config.strategy.imbalance_threshold,
-        1.5,
-        config.strategy.min_confirming_signals,
-    );
+        info!("Pre-flight checks:");
+        for line in report.lines() {
+            info!("  {}", line);
+        }
+        if !report.passed() {
+            error!("❌ Pre-flight checks failed");
+            anyhow::bail!("pre-flight checks failed; fix the items marked ✗ above");
+        }
+        info!("✓ Pre-flight checks passed");
+    }
+
+    // Recent volatility, volume and funding, so a restart does not start blind.
+    // Best effort: a failed load is logged and trading starts without it.
+    if config.context.enabled {
+        match load_market_context(&rest_client, &config.context, &config.general.symbol).await {
+            Ok(context) => info!("✓ Market context ({} days): {}", config.context.lookback_days, context.summary()),
+            Err(e) => warn!("⚠️  Market context unavailable, starting without history: {}", e),
+        }
+    }
+
+    // Create signal detectors (same parameters as the backtester)
+    let params = &config.strategy.params;
+    let mut imbalance_detector = params.imbalance_detector();
+    let mut flow_analyzer = params.flow_analyzer();
+
+    // Optionally record raw detector observations, plus a market sample per
+    // evaluation, for offline tuning and `backtester label`
+    let mut observation_recorder = None;
+    if config.storage.record_observations {
+        let path = Path::new(&config.storage.data_dir).join("observations.jsonl");
+        let recorder = ObservationRecorder::to_file(&path, config.experiment.clone())?;
+        imbalance_detector = imbalance_detector.with_recorder(recorder.clone());
+        flow_analyzer = flow_analyzer.with_recorder(recorder.clone());
+        observation_recorder = Some(recorder);
+        info!("✓ Recording detector observations to {}", path.display());
+    }
+
+    let mut signal_aggregator = params.signal_aggregator();
+    let mut calibrator = params.threshold_calibrator();
+    if calibrator.is_enabled() {
+        info!("✓ Online threshold calibration enabled");
+    }
+
+    // Runtime strategy/detector switches, driven by commands on stdin
+    // ("status", "strategy on|off", "<detector> on|off")
+    let control = StrategyControl::new(config.strategy.enabled);
+    if !control.is_enabled() {
+        warn!("⚠️  Strategy disabled in config: managing exits only until enabled");
+    }
+    let mut detector_policy = DetectorPolicy::new(params.detector_policy.clone(), control.clone());
+
+    // Fewer, stronger entries while the feed or this loop lags behind
+    let mut cadence = params.cadence_policy();
+    if params.cadence.enabled {
+        info!("✓ Latency-aware signal cadence enabled");
+    }
 
     // Create risk manager
-    let risk_limits = RiskLimits {
-        max_position_size: Decimal::from_f64_retain(config.position_sizing.max_position_usd).unwrap(),
-        max_portfolio_exposure: Decimal::from_f64_retain(config.risk.max_portfolio_exposure_usd).unwrap(),
-        max_daily_loss: Decimal::from_f64_retain(config.risk.max_daily_loss_usd).unwrap(),
-        max_drawdown_percent: Decimal::from_f64_retain(config.risk.max_drawdown_pct).unwrap(),
-        max_trades_per_hour: config.risk.max_trades_per_hour,
-        max_trades_per_day: 200,
-        max_acceptable_latency_ms: config.latency.max_acceptable_latency_ms,
-    };
+    let risk_limits = config.risk.limits(
+        config.position_sizing.max_position_usd,
+        config.latency.max_acceptable_latency_ms,
+    );
 
     let risk_manager = RiskManager::new(
-        risk_limits,
+        risk_limits.clone(),
         Decimal::from_f64_retain(config.risk.max_portfolio_exposure_usd).unwrap(),
     );
 
@@ -106,11 +350,177 @@ async fn main() -> anyhow::Result<()> {
         rest_client,
         risk_manager,
         config.general.symbol.clone(),
-        Decimal::from_f64_retain(config.position_sizing.base_notional_usd).unwrap(),
-        Decimal::from_f64_retain(config.strategy.take_profit_bps).unwrap(),
-        Decimal::from_f64_retain(config.strategy.stop_loss_bps).unwrap(),
-        config.strategy.max_hold_time_ms,
-    );
+        Decimal::from_f64_retain(config.position_sizing.base_size()).unwrap(),
+        params.take_profit_bps(),
+        params.stop_loss_bps(),
+        params.max_hold_time_ms,
+    )
+    .with_market_quality(params.market_quality_gate())
+    .with_take_profit_schedule(params.take_profit_schedule())
+    .with_signal_decay_exit(params.signal_decay_exit_z)
+    .with_activity_monitor(params.activity_monitor())
+    .with_hit_rates(params.hit_rate_tracker())
+    .with_pnl_attribution(params.pnl_attribution())
+    .with_exit_orders(params.exits.clone())
+    .with_reentry_guard(params.reentry_guard())
+    .with_age_monitor(params.age_monitor())
+    .with_instrument(instrument)
+    .with_min_order_notional(min_order_notional)
+    .with_sizing(config.position_sizing.mode)
+    .with_experiment(config.experiment.clone());
+
+    if config.latency.max_signal_age_ms > 0 {
+        execution_engine = execution_engine
+            .with_max_signal_age(Duration::from_millis(config.latency.max_signal_age_ms));
+    }
+
+    if !config.experiment.is_empty() {
+        info!("✓ Experiment: {:?} {:?}", config.experiment.label, config.experiment.tags);
+    }
+
+    if config.storage.trade_journal {
+        let path = Path::new(&config.storage.data_dir).join("journal.jsonl");
+        execution_engine = execution_engine.with_journal(JsonlWriter::open(&path)?);
+        info!("✓ Journaling fills to {}", path.display());
+    }
+
+    // Market events, signals and fills for external consumers
+    let mut bus = None;
+    if config.storage.event_bus {
+        let path = Path::new(&config.storage.data_dir).join(config.storage.bus_encoding.file_name());
+        let publisher = BusPublisher::to_file(&path, config.storage.bus_encoding)?;
+        execution_engine = execution_engine.with_bus(publisher.clone());
+        bus = Some(publisher);
+        info!("✓ Publishing event bus ({:?}) to {}", config.storage.bus_encoding, path.display());
+    }
+
+    // Fills from ORDER_TRADE_UPDATE instead of the synchronous order response
+    if config.exchange.user_data.enabled {
+        let client = BinanceRestClient::new(api_key, secret_key, config.exchange.api_endpoint.clone())
+            .with_api(futures_api);
+        let (user_data, mut user_rx) = UserDataStream::new(
+            client,
+            config.exchange.ws_endpoint.clone(),
+            config.exchange.user_data.clone(),
+        );
+        let fill_timeout = Duration::from_millis(config.exchange.user_data.fill_timeout_ms);
+        execution_engine = execution_engine.with_order_fills(user_data.fills(), fill_timeout);
+        tokio::spawn(async move {
+            user_data.run().await;
+        });
+        tokio::spawn(async move {
+            while let Some(TimedEvent { event, .. }) = user_rx.recv().await {
+                match event {
+                    MarketEvent::OrderUpdate(update) => debug!(
+                        "Order {} {} {}: {} filled @ {}",
+                        update.order.order_id, update.order.side, update.order.status,
+                        update.order.cumulative_filled_qty, update.order.avg_price,
+                    ),
+                    MarketEvent::AccountUpdate(update) => {
+                        for position in &update.data.positions {
+                            info!(
+                                "Account update ({}): {} position {} @ {}",
+                                update.data.reason, position.symbol, position.position_amt, position.entry_price,
+                            );
+                        }
+                    }
+                    MarketEvent::Connected => info!("✓ User data stream connected"),
+                    MarketEvent::Disconnected => warn!("✗ User data stream disconnected"),
+                    _ => {}
+                }
+            }
+        });
+        info!("✓ User data stream: fills from order updates (wait up to {:?})", fill_timeout);
+    }
+
+    if config.webhooks.is_enabled() {
+        execution_engine = execution_engine.with_webhooks(WebhookNotifier::spawn(config.webhooks.clone()));
+        info!("✓ Webhooks: {} endpoint(s)", config.webhooks.urls.len());
+    }
+
+    // Small size on the first mainnet runs
+    if is_mainnet(config.exchange.testnet, &config.exchange.api_endpoint) && config.preflight.first_runs > 0 {
+        let run = record_live_run(&config.storage.data_dir)?;
+        if run <= config.preflight.first_runs {
+            let cap = Decimal::from_f64_retain(config.preflight.first_run_max_notional_usd).unwrap_or_default();
+            execution_engine = execution_engine.with_max_notional(cap);
+            warn!(
+                "⚠️  Mainnet run {} of {}: entries capped at ${} notional",
+                run, config.preflight.first_runs, cap
+            );
+        }
+    }
+
+    // Register sub-accounts (same limits, own credentials and sizing)
+    for account in &config.accounts {
+        let api_key = env::var(&account.api_key_env)
+            .map_err(|_| anyhow::anyhow!("{} not found in environment", account.api_key_env))?;
+        let secret_key = env::var(&account.secret_key_env)
+            .map_err(|_| anyhow::anyhow!("{} not found in environment", account.secret_key_env))?;
+
+        execution_engine.add_account(
+            &account.name,
+            BinanceRestClient::new(api_key, secret_key, account.api_endpoint.clone()).with_api(futures_api),
+            RiskManager::new(
+                risk_limits.clone(),
+                Decimal::from_f64_retain(account.initial_equity_usd).unwrap(),
+            ),
+            Decimal::from_f64_retain(match config.position_sizing.mode {
+                SizingMode::FixedNotional => account.base_notional_usd,
+                SizingMode::RiskPerTrade => account.risk_per_trade_usd,
+                // Same share of each account's own equity
+                SizingMode::EquityPercent => config.position_sizing.equity_percent,
+            }).unwrap(),
+        )?;
+        info!("✓ Sub-account registered: {} ({})", account.name, account.api_endpoint);
+    }
+
+    // Load scheduled event calendar (entry blackouts)
+    if !config.calendar.source.is_empty() {
+        let calendar = EventCalendar::load(
+            &config.calendar.source,
+            config.calendar.blackout.clone(),
+        ).await?;
+        info!("✓ Event calendar loaded: {} events", calendar.len());
+        execution_engine = execution_engine.with_calendar(calendar);
+    }
+    // URL calendars are refetched on the slow path; a failed fetch keeps
+    // the last good calendar in place
+    let calendar_refresh = (EventCalendar::is_url(&config.calendar.source) && config.calendar.refresh_secs > 0)
+        .then(|| Duration::from_secs(config.calendar.refresh_secs));
+    let mut last_calendar_refresh = Instant::now();
+
+    {
+        let control = control.clone();
+        let risk_handles = execution_engine.risk_handles();
+        #[cfg(feature = "chaos")]
+        let faults = faults.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(tokio::io::stdin()).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                #[cfg(feature = "chaos")]
+                if line.trim_start().starts_with("chaos") {
+                    match faults.apply_command(&line) {
+                        Ok(status) => warn!("💥 {}", status),
+                        Err(e) => warn!("💥 {}", e),
+                    }
+                    continue;
+                }
+                if let Some(status) = apply_risk_command(&risk_handles, &line) {
+                    warn!("🛑 {}", status);
+                    continue;
+                }
+                match control.apply_command(&line) {
+                    Ok(status) => info!("🎛  {}", status),
+                    Err(e) => warn!("🎛  {}", e),
+                }
+            }
+        });
+    }
+    info!("✓ Control commands on stdin: {} (halt [reason] | resume)", control.status());
 
     info!("✓ Trading engine initialized");
     info!("");
@@ -118,96 +528,869 @@ async fn main() -> anyhow::Result<()> {
     info!("Press Ctrl+C to stop");
     info!("");
 
-    let mut event_count = 0;
+    // Periodic stats snapshots for graphing live performance
+    let snapshot_interval = Duration::from_secs(config.storage.stats_snapshot_interval_secs);
+    let mut stats_writer = if config.storage.stats_snapshot_interval_secs > 0 {
+        Some(JsonlWriter::open(Path::new(&config.storage.data_dir).join("stats.jsonl"))?)
+    } else {
+        None
+    };
+    let mut last_snapshot = Instant::now();
 
-    // Main trading loop
-    while let Some(event) = event_rx.recv().await {
-        match event {
-            MarketEvent::Connected => {
-                info!("✓ WebSocket connected");
-            }
+    // Periodic full book snapshots for backtest warm-starts
+    let book_snapshot_interval = Duration::from_secs(config.storage.book_snapshot_interval_secs);
+    let mut book_writer = if config.storage.book_snapshot_interval_secs > 0 {
+        Some(BookSnapshotWriter::open(Path::new(&config.storage.data_dir).join("book_snapshots.bin"))?)
+    } else {
+        None
+    };
+    let mut last_book_snapshot = Instant::now();
+
+    // Book density around mid for heatmaps of the traded sessions
+    let heatmap_interval = Duration::from_millis(config.storage.heatmap.interval_ms);
+    let mut heatmap_writer = if config.storage.heatmap.is_enabled() {
+        let dir = Path::new(&config.storage.data_dir).join("heatmap");
+        info!("✓ Book heatmap every {}ms (±{} bps) to {}",
+            config.storage.heatmap.interval_ms, config.storage.heatmap.band_bps, dir.display());
+        Some(HeatmapWriter::open(dir, &config.general.symbol, &config.storage.heatmap)?)
+    } else {
+        None
+    };
+    let mut last_heatmap = Instant::now();
+
+    // Equity-proportional sizing reads account equity from the exchange
+    let equity_sizing = config.position_sizing.mode == SizingMode::EquityPercent;
+    let equity_refresh = Duration::from_secs(config.position_sizing.equity_refresh_secs);
+    if equity_sizing {
+        execution_engine.refresh_equity().await;
+        info!("✓ Sizing entries at {}% of account equity", config.position_sizing.equity_percent);
+    }
+    let mut last_equity_refresh = Instant::now();
+
+    // Non-USD quotes (e.g. ETHBTC) are valued at the quote asset's mark price
+    let quote_rate_symbol = config.general.quote_rate_symbol()?.map(str::to_string);
+    let quote_rate_client = BinanceRestClient::new(String::new(), String::new(), config.exchange.api_endpoint.clone());
+    if let Some(symbol) = &quote_rate_symbol {
+        let rate = quote_rate_client.get_mark_price(symbol).await?;
+        execution_engine.set_quote_usd(rate);
+        info!("✓ Quote {} valued at {} mark price: {}", config.general.quote_currency, symbol, rate);
+    }
+    let mut last_quote_refresh = Instant::now();
+
+    // Periodic check of booked PnL and fees against the exchange's income
+    // history since startup; surfaces funding and fee-tier costs
+    let started_ms = UNIX_EPOCH.elapsed().map(|d| d.as_millis() as u64).unwrap_or(0);
+    let reconciliation_interval = Duration::from_secs(config.reconciliation.interval_secs);
+    let reconciliation_tolerance = Decimal::from_f64_retain(config.reconciliation.tolerance).unwrap_or_default();
+    let mut reconciliation_writer = if config.reconciliation.enabled {
+        Some(JsonlWriter::open(Path::new(&config.storage.data_dir).join("reconciliation.jsonl"))?)
+    } else {
+        None
+    };
+    let mut last_reconciliation = Instant::now();
+
+    // Daily loss and exposure limits shared with other instances (primary account)
+    let fleet_interval = Duration::from_millis(config.shared_state.sync_interval_ms);
+    let mut fleet_sync = if config.shared_state.is_enabled() {
+        let instance = if config.shared_state.instance.is_empty() {
+            config.general.symbol.clone()
+        } else {
+            config.shared_state.instance.clone()
+        };
+        let state = SharedState::connect(&config.shared_state).await?;
+        info!("✓ Shared fleet state: {} as '{}'", config.shared_state.backend, instance);
+        Some(FleetSync::new(state, instance, &config.shared_state))
+    } else {
+        None
+    };
+    let mut last_fleet_sync = Instant::now();
 
-            MarketEvent::Disconnected => {
-                warn!("✗ WebSocket disconnected");
+    // Close positions below the minimum notional and lot residuals left by rounding
+    let dust_interval = Duration::from_secs(config.strategy.params.dust.sweep_interval_secs);
+    let mut last_dust_sweep = Instant::now();
+
+    // Live-equivalent backtest config for walk-forward runs and the shadow
+    let replay_base = BacktestConfig {
+        symbol: config.general.symbol.clone(),
+        initial_capital: Decimal::from_f64_retain(config.risk.max_portfolio_exposure_usd).unwrap_or_default(),
+        position_size: Decimal::from_f64_retain(config.position_sizing.base_size()).unwrap_or_default(),
+        sizing: config.position_sizing.mode,
+        strategy: params.clone(),
+        risk_limits: risk_limits.clone(),
+        instrument,
+        ..BacktestConfig::default()
+    };
+
+    // Candidate parameters simulated on the same events; never sent to the exchange
+    let mut shadow = None;
+    if config.shadow.is_enabled() {
+        let candidate = StrategyConfig::from_file(&config.shadow.strategy_file)?.params;
+        let mut strategy = ShadowStrategy::new(&config.shadow.name, BacktestConfig {
+            strategy: candidate,
+            ..replay_base.clone()
+        })
+        .with_experiment(config.experiment.clone());
+        if config.storage.trade_journal {
+            let path = Path::new(&config.storage.data_dir).join("shadow_journal.jsonl");
+            strategy = strategy.with_journal(JsonlWriter::open(&path)?);
+        }
+        info!("✓ Shadow strategy '{}' from {}", config.shadow.name, config.shadow.strategy_file);
+        shadow = Some(strategy);
+    }
+
+    // Walk-forward re-optimization of the thresholds over recent recordings.
+    // Runs on a blocking thread; proposals come back to the main loop, which
+    // logs every one and applies it only with `apply = true`.
+    let recalibration = params.recalibration.clone();
+    let (thresholds_tx, thresholds_rx) = watch::channel((
+        signal_aggregator.primary_threshold(),
+        signal_aggregator.confirming_threshold(),
+    ));
+    let (proposal_tx, mut proposal_rx) = mpsc::unbounded_channel::<ThresholdProposal>();
+    let mut recalibration_writer = None;
+    if recalibration.enabled {
+        recalibration_writer = Some(JsonlWriter::open(Path::new(&config.storage.data_dir).join("recalibration.jsonl"))?);
+        let data_dir = if recalibration.data_dir.is_empty() {
+            config.storage.data_dir.clone()
+        } else {
+            recalibration.data_dir.clone()
+        };
+        let base = replay_base.clone();
+        info!("✓ Walk-forward recalibration every {}s over the last {}h of {} ({})",
+            recalibration.interval_secs, recalibration.lookback_hours, data_dir,
+            if recalibration.apply { "applying within bounds" } else { "proposals only" });
+
+        let recalibration = recalibration.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(recalibration.interval_secs.max(60)));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let current = *thresholds_rx.borrow();
+                let (base, recalibration, data_dir) = (base.clone(), recalibration.clone(), data_dir.clone());
+                let lookback_hours = recalibration.lookback_hours;
+                let run = tokio::task::spawn_blocking(move || {
+                    let now = SystemTime::now();
+                    let events = load_recent(Path::new(&data_dir), &base.symbol, recalibration.lookback_hours, now)?;
+                    recalibrate(&events, &base, current, &recalibration, now)
+                }).await;
+                match run {
+                    Ok(Ok(Some(proposal))) => {
+                        if proposal_tx.send(proposal).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(Ok(None)) => warn!("Recalibration skipped: no recorded events in the last {}h", lookback_hours),
+                    Ok(Err(e)) => warn!("Recalibration failed: {}", e),
+                    Err(e) => error!("Recalibration task panicked: {}", e),
+                }
             }
+        });
+    }
 
-            MarketEvent::DepthUpdate(_) => {
-                event_count += 1;
+    // Fast path: this loop owns the execution engine and checks exits on
+    // every depth update. Detector evaluation, stats, logging and every
+    // REST or shared-state read run on the slow path task below, handed
+    // the latest snapshot (with a copy of the top of the book) every 10
+    // updates (~1 second); what it decides or reads comes back as requests applied here.
+    let (mut snapshot_tx, mut snapshot_rx) = snapshot_handoff();
+    let (request_tx, mut request_rx) = mpsc::unbounded_channel::<SlowPathRequest>();
+    let (slow_event_tx, mut slow_event_rx) = mpsc::channel::<MarketEvent>(SLOW_EVENT_CAPACITY);
+    // The shadow and the bus take every event; otherwise only trades (flow
+    // window) and liquidations (cascade window) are needed there
+    let forward_all_events = shadow.is_some() || bus.is_some();
+    // Levels the slow path evaluates: imbalance depth, and the touch
+    let handoff_levels = params.imbalance.levels.max(1);
 
-                // Check signals every 10 updates (~1 second)
-                if event_count % 10 == 0 {
-                    // Check for exit conditions first
-                    if let Some(current_price) = orderbook.get_mid_price() {
-                        if let Err(e) = execution_engine.check_exits(current_price).await {
-                            error!("Error checking exits: {}", e);
+    let slow_path = {
+        let control = control.clone();
+        let clients = execution_engine.account_clients();
+        let fleet_risk = execution_engine.risk_handle();
+        let settlement_asset = config.general.settlement_asset().to_string();
+        let feed_latency = Arc::clone(&feed_latency);
+        let symbol = config.general.symbol.clone();
+        let heatmap_config = config.storage.heatmap.clone();
+        let calendar_config = config.calendar.clone();
+        let min_confirming_signals = params.min_confirming_signals;
+        let requests = request_tx;
+        tokio::spawn(async move {
+            let mut last_stats_print = 0;
+            let mut last_policy = Instant::now();
+            let mut events_since_snapshot = 0;
+            loop {
+                let snapshot = tokio::select! {
+                    // Trades, liquidations and shadow events before the evaluation
+                    // that follows them, but no more than a full queue in between
+                    biased;
+                    Some(event) = slow_event_rx.recv(), if events_since_snapshot < SLOW_EVENT_CAPACITY => {
+                        events_since_snapshot += 1;
+                        if let Some(bus) = &bus {
+                            if let Some(message) = BusMessage::from_market_event(&event) {
+                                bus.publish(message);
+                            }
                         }
+                        if let Some(shadow) = shadow.as_mut() {
+                            if let Err(e) = shadow.on_event(&event) {
+                                warn!("Shadow strategy '{}' failed on event: {}", shadow.name(), e);
+                            }
+                        }
+                        match event {
+                            MarketEvent::Trade(trade) => {
+                                if let Some(_signal) = flow_analyzer.process_trade(trade) {
+                                    // Flow signals are captured in the aggregate below
+                                }
+                            }
+                            MarketEvent::Liquidation(force) => {
+                                if let (Some(detector), Some((side, price, quantity))) =
+                                    (liquidation.as_mut(), force.parse())
+                                {
+                                    detector.process_liquidation(side, price, quantity, force.event_time);
+                                }
+                            }
+                            _ => {}
+                        }
+                        continue;
                     }
+                    snapshot = snapshot_rx.next() => match snapshot {
+                        Some(snapshot) => {
+                            events_since_snapshot = 0;
+                            snapshot
+                        }
+                        None => break,
+                    },
+                };
+                let received = snapshot.received;
+                let book = &snapshot.book;
+
+                // Lag here includes time waiting for the handoff
+                let latency_ms = snapshot.feed_latency_ms;
+                let backlog = received.elapsed();
+                match cadence.observe(latency_ms, backlog, Instant::now()) {
+                    Some(CadenceLevel::Normal) => info!("⏱ Signal cadence back to normal"),
+                    Some(level) => warn!(
+                        "⏱ Signal cadence {:?}: feed latency {}ms, backlog {}ms (threshold x{:.2})",
+                        level, latency_ms, backlog.as_millis(), cadence.threshold_multiplier()
+                    ),
+                    None => {}
+                }
+
+                // Sample the reference mid on the same cadence
+                if let Some((filter, book)) = reference_filter.as_mut() {
+                    if let Some(mid) = book.get_mid_price() {
+                        filter.record_mid_at(mid, received.instant);
+                    }
+                }
+
+                // Switch off detectors that stopped paying (scored on the fast
+                // path, read from the engine view requested on the policy cadence)
+                let policy_due = detector_policy.is_enabled() && last_policy.elapsed() >= DETECTOR_POLICY_INTERVAL;
+                let actions = match (&snapshot.engine, policy_due) {
+                    (Some(engine), true) => {
+                        last_policy = Instant::now();
+                        detector_policy.evaluate(&engine.stats.detector_hit_rates, &engine.stats.detector_pnl)
+                    }
+                    _ => Vec::new(),
+                };
+                for action in actions {
+                    match action {
+                        PolicyAction::Disabled { detector, reason } => {
+                            error!("🛑 Detector {} switched off: {} (re-enable with \"{} on\")", detector, reason, detector);
+                            let _ = requests.send(SlowPathRequest::DetectorDisabled { detector, reason });
+                        }
+                        PolicyAction::Reinstated { detector } => {
+                            info!("✓ Detector {} re-enabled by operator; hit rate and PnL restart", detector);
+                            let _ = requests.send(SlowPathRequest::ResetDetector(detector));
+                        }
+                    }
+                }
+
+                // Score earlier signals; retune thresholds if precision drifted
+                if let (true, Some(mid)) = (calibrator.is_enabled(), snapshot.mid) {
+                    calibrator.record_mid(mid, received.wall_time());
+                    if let Some(adjustment) = calibrator.adjust(&mut signal_aggregator, received.wall_time()) {
+                        imbalance_detector.set_threshold(adjustment.primary_to);
+                        thresholds_tx.send_replace((adjustment.primary_to, adjustment.confirming_to));
+                    }
+                }
 
-                    // Check for entry signals
+                // Walk-forward proposals, applied only if still based on
+                // the live thresholds (the online calibrator may have moved them)
+                while let Ok(mut proposal) = proposal_rx.try_recv() {
+                    let current = (signal_aggregator.primary_threshold(), signal_aggregator.confirming_threshold());
+                    let summary = format!(
+                        "primary {:.2} -> {:.2}, confirming {:.2} -> {:.2} | backtest PnL ${:.2} ({} trades) vs ${:.2} ({} trades) over {} events",
+                        proposal.current_primary, proposal.proposed_primary,
+                        proposal.current_confirming, proposal.proposed_confirming,
+                        proposal.proposed_pnl, proposal.proposed_trades,
+                        proposal.current_pnl, proposal.current_trades, proposal.events,
+                    );
+                    if !proposal.changes_thresholds() {
+                        info!("🎚 Recalibration: current thresholds kept | {}", summary);
+                    } else if !recalibration.apply {
+                        info!("🎚 Recalibration proposal (not applied): {}", summary);
+                    } else if current != (proposal.current_primary, proposal.current_confirming) {
+                        warn!("🎚 Recalibration proposal dropped, thresholds moved since the run: {}", summary);
+                    } else {
+                        signal_aggregator.set_thresholds(proposal.proposed_primary, proposal.proposed_confirming);
+                        imbalance_detector.set_threshold(proposal.proposed_primary);
+                        thresholds_tx.send_replace((proposal.proposed_primary, proposal.proposed_confirming));
+                        proposal.applied = true;
+                        warn!("🎚 Recalibration applied: {}", summary);
+                    }
+                    if let Some(writer) = recalibration_writer.as_mut() {
+                        if let Err(e) = writer.append(&proposal).and_then(|_| writer.flush()) {
+                            warn!("Failed to write recalibration proposal: {}", e);
+                        }
+                    }
+                }
+
+                // Entries are evaluated less often while lagging (exits are not)
+                if cadence.should_evaluate() {
+                    // Check for entry signals. Detectors always run so their
+                    // windows stay warm; switched-off ones are just not used.
                     let mut signals = Vec::new();
+                    let event_time = UNIX_EPOCH + Duration::from_millis(snapshot.event_time_ms);
 
-                    if let Some(signal) = imbalance_detector.calculate_signal(&orderbook) {
-                        info!("📊 Imbalance signal: {:?} | Strength: {:.2}", 
-                            signal.direction, signal.strength);
-                        signals.push(signal);
-                    }
-
-                    // Aggregate and execute if tradeable
-                    if !signals.is_empty() {
-                        if let Some(composite) = signal_aggregator.aggregate(signals) {
-                            if composite.is_tradeable(config.strategy.min_confirming_signals) {
-                                info!("");
-                                info!("🎯 COMPOSITE SIGNAL GENERATED");
-                                info!("   Direction: {:?}", composite.direction);
-                                info!("   Confidence: {:.2}", composite.confidence);
-                                
-                                // Check if not halted
-                                if execution_engine.risk_manager().is_halted() {
-                                    warn!("   ⚠️  Trading halted: {}", 
-                                        execution_engine.risk_manager().halt_reason().unwrap_or("Unknown"));
-                                } else if let Some(current_price) = orderbook.get_mid_price() {
-                                    info!("   Executing trade...");
-                                    
-                                    match execution_engine.execute_signal(composite, current_price).await {
-                                        Ok(result) => {
-                                            info!("   ✅ TRADE EXECUTED");
-                                            info!("      Order ID: {}", result.order_id);
-                                            info!("      Price: {}", result.executed_price);
-                                            info!("      Quantity: {}", result.executed_qty);
-                                            info!("      Latency: {}ms", result.latency_ms);
-                                        }
-                                        Err(e) => {
-                                            error!("   ✗ Execution failed: {}", e);
-                                        }
-                                    }
+                    if let Some(recorder) = &observation_recorder {
+                        if let Some(observation) = market_observation(book, event_time) {
+                            recorder.record(observation);
+                        }
+                    }
+
+                    let mid = book.get_mid_price();
+                    if let Some(signal) = imbalance_detector.calculate_signal_at(book, event_time) {
+                        if let Some(mid) = mid {
+                            let _ = requests.send(SlowPathRequest::DetectorSignal {
+                                detector: Detector::Imbalance.name(),
+                                direction: signal.direction,
+                                mid,
+                                at: received.wall_time(),
+                            });
+                        }
+                        if control.detector_enabled(Detector::Imbalance) {
+                            info!("📊 Imbalance signal: {:?} | Strength: {:.2}",
+                                signal.direction, signal.strength);
+                            signals.push(signal);
+                        }
+                    }
+                    let _ = requests.send(SlowPathRequest::ImbalanceZ(imbalance_detector.last_z_score()));
+
+                    if let Some((detector, spot_book)) = basis.as_mut() {
+                        if let Some(signal) = detector.calculate_signal_at(book, spot_book, event_time) {
+                            if let Some(mid) = mid {
+                                let _ = requests.send(SlowPathRequest::DetectorSignal {
+                                    detector: Detector::Basis.name(),
+                                    direction: signal.direction,
+                                    mid,
+                                    at: received.wall_time(),
+                                });
+                            }
+                            if control.detector_enabled(Detector::Basis) {
+                                info!("📐 Basis signal: {:?} | Strength: {:.2}",
+                                    signal.direction, signal.strength);
+                                signals.push(signal);
+                            }
+                        }
+                    }
+
+                    if let Some(detector) = liquidation.as_mut() {
+                        if let Some(signal) = detector.calculate_signal_at(event_time) {
+                            if let Some(mid) = mid {
+                                let _ = requests.send(SlowPathRequest::DetectorSignal {
+                                    detector: Detector::Liquidation.name(),
+                                    direction: signal.direction,
+                                    mid,
+                                    at: received.wall_time(),
+                                });
+                            }
+                            if control.detector_enabled(Detector::Liquidation) {
+                                let (forced_buys, forced_sells) = detector.last_notional();
+                                info!("💥 Liquidation cascade: {:?} | Strength: {:.2} | forced buys {:.0} / sells {:.0}",
+                                    signal.direction, signal.strength, forced_buys, forced_sells);
+                                signals.push(signal);
+                            }
+                        }
+                    }
+
+                    // Strategy switched off: exits keep running, no entries
+                    if !control.is_enabled() {
+                        signals.clear();
+                    }
+
+                    // Aggregate and hand over for execution if tradeable; an
+                    // evaluation with no detector signal counts as primary too weak
+                    let composite = signal_aggregator.aggregate_or_reject(signals)
+                        .and_then(|composite| match &reference_filter {
+                            Some((filter, _)) => filter.apply(composite).ok_or_else(|| {
+                                info!("⛔ Signal blocked: {} moved {:.1} bps against it",
+                                    filter.symbol(), filter.move_bps().unwrap_or(0.0));
+                                RejectionReason::ReferenceBlocked
+                            }),
+                            None => Ok(composite),
+                        })
+                        .and_then(|mut composite| match &basis {
+                            Some((detector, _)) if detector.vetoes(composite.direction) => {
+                                info!("⛔ Signal vetoed: basis z-score {:.2} against {:?}",
+                                    detector.last_z_score().unwrap_or(0.0), composite.direction);
+                                Err(RejectionReason::BasisVeto)
+                            }
+                            Some((detector, _)) => {
+                                composite.explanation.check("basis_veto", true,
+                                    format!("z-score {:.2}", detector.last_z_score().unwrap_or(0.0)));
+                                Ok(composite)
+                            }
+                            None => Ok(composite),
+                        })
+                        .and_then(|composite| match composite.rejection(min_confirming_signals) {
+                            Some(reason) => Err(reason),
+                            None => Ok(composite),
+                        })
+                        .and_then(|composite| cadence.check(composite, signal_aggregator.primary_threshold()));
+
+                    match composite {
+                        // Switched off is not a rejection
+                        Err(_) if !control.is_enabled() => {}
+                        Err(reason) => {
+                            let _ = requests.send(SlowPathRequest::Rejection(reason));
+                        }
+                        Ok(composite) => {
+                            if let Some(mid) = mid {
+                                calibrator.record_signal(&composite, mid, received.wall_time());
+                            }
+                            if let Some(bus) = &bus {
+                                bus.publish(BusMessage::signal(&symbol, &composite));
+                            }
+                            info!("");
+                            info!("🎯 COMPOSITE SIGNAL GENERATED");
+                            info!("   Direction: {:?}", composite.direction);
+                            info!("   Confidence: {:.2}", composite.confidence);
+                            let _ = requests.send(SlowPathRequest::Enter(composite));
+                        }
+                    }
+                }
+
+                // Stats snapshots, the periodic report (every 1000 updates,
+                // ~100 seconds), reconciliation and the detector policy read
+                // the engine view the fast path attaches on request; ask for
+                // one whenever any is due without it
+                let snapshot_due = stats_writer.is_some() && last_snapshot.elapsed() >= snapshot_interval;
+                let print_due = snapshot.depth_updates >= last_stats_print + 1000;
+                let reconciliation_due = reconciliation_writer.is_some()
+                    && last_reconciliation.elapsed() >= reconciliation_interval;
+                match (&snapshot.engine, snapshot_due || print_due || reconciliation_due || policy_due) {
+                    (_, false) => {}
+                    (None, true) => {
+                        let _ = requests.send(SlowPathRequest::EngineView);
+                    }
+                    (Some(engine), true) => {
+                        // Append stats snapshots on schedule
+                        if let (Some(writer), true) = (stats_writer.as_mut(), snapshot_due) {
+                            last_snapshot = Instant::now();
+                            for stats in &engine.snapshots {
+                                if let Err(e) = writer.append(stats) {
+                                    warn!("Failed to write stats snapshot: {}", e);
                                 }
-                                info!("");
+                            }
+                            if let Err(e) = writer.flush() {
+                                warn!("Failed to flush stats snapshots: {}", e);
+                            }
+                        }
+
+                        if print_due {
+                            last_stats_print = snapshot.depth_updates;
+                            let stats = &engine.stats;
+                            info!("📈 Trading Stats:");
+                            info!("   Open Positions: {}", stats.open_positions);
+                            info!("   Closed Trades: {}", stats.closed_trades);
+                            info!("   Realized PnL: {}", stats.total_realized_pnl);
+                            info!("   Win Rate: {:.2}%", stats.win_rate * 100.0);
+                            info!("   Total Fees: {}", stats.total_fees);
+                            info!("   Not Traded: {} ({})", stats.rejections.total(), stats.rejections.summary());
+                            info!("   Activity: {:.1} signals/h, {:.1} trades/h, edge {:.2} bps (baseline {:.1} trades/h, {:.2} bps){}",
+                                stats.activity.recent.signals_per_hour, stats.activity.recent.trades_per_hour,
+                                stats.activity.recent.avg_edge_bps.unwrap_or(0.0),
+                                stats.activity.baseline.trades_per_hour,
+                                stats.activity.baseline.avg_edge_bps.unwrap_or(0.0),
+                                if stats.activity.overtrading { " ⚠️  OVERTRADING" } else { "" });
+                            info!("   Control: {}", control.status());
+                            let hit_rates: Vec<String> = stats.detector_hit_rates.iter()
+                                .map(|d| match d.hit_rate {
+                                    Some(rate) => format!("{} {:.1}% ({}/{})", d.detector, rate * 100.0, d.samples, d.signals),
+                                    None => format!("{} n/a ({})", d.detector, d.signals),
+                                })
+                                .collect();
+                            info!("   Detector Hit Rates: {}", if hit_rates.is_empty() { "none yet".to_string() } else { hit_rates.join(", ") });
+                            let detector_pnl: Vec<String> = stats.detector_pnl.iter()
+                                .map(|d| format!("{} ${} ({} trades)", d.detector, d.pnl.round_dp(2), d.trades))
+                                .collect();
+                            info!("   Detector PnL: {}", if detector_pnl.is_empty() { "none yet".to_string() } else { detector_pnl.join(", ") });
+                            let age_warnings: Vec<String> = stats.age_warnings.warnings.iter()
+                                .map(|(threshold, count)| format!("{} {}", threshold, count))
+                                .collect();
+                            info!("   Age Warnings: {} ({} stalled open)",
+                                if age_warnings.is_empty() { "off".to_string() } else { age_warnings.join(", ") },
+                                stats.age_warnings.stalled_positions);
+                            if let Some(shadow) = &shadow {
+                                let results = shadow.results();
+                                info!("   Shadow [{}]: {} trades ({} wins), PnL ${}",
+                                    shadow.name(), results.total_trades, results.winning_trades,
+                                    results.total_return.round_dp(2));
+                            }
+                            info!("   Resting Orders: {} (self-trades prevented: {})",
+                                engine.resting_orders, engine.self_trades_prevented);
+
+                            // Production ledger audit on the same cadence
+                            for report in &engine.ledger {
+                                if !report.is_consistent() {
+                                    error!("✗ Ledger discrepancy: {}",
+                                        serde_json::to_string(report).unwrap_or_else(|_| format!("{:?}", report)));
+                                }
+                            }
+                            if let Some(metrics) = &level_metrics {
+                                info!("   Stale Book Levels: {} (expired total: {})",
+                                    metrics.stale_level_count.load(Ordering::Relaxed),
+                                    metrics.expired_levels_total.load(Ordering::Relaxed));
+                            }
+                            info!("   Feed Latency: {}ms (mean {}ms, skew est {}ms)",
+                                feed_latency.last_latency_ms.load(Ordering::Relaxed),
+                                feed_latency.mean_latency_ms.load(Ordering::Relaxed),
+                                feed_latency.skew_estimate_ms.load(Ordering::Relaxed));
+                            info!("   Depth Gaps: {} (missed >= {}, max gap {}ms, resyncs {}, stale dropped {})",
+                                sequence_metrics.depth.discontinuities.load(Ordering::Relaxed),
+                                sequence_metrics.depth.missed_updates.load(Ordering::Relaxed),
+                                sequence_metrics.depth.max_gap_ms.load(Ordering::Relaxed),
+                                sequence_metrics.resyncs.load(Ordering::Relaxed),
+                                sequence_metrics.stale_depth_updates.load(Ordering::Relaxed));
+                            info!("   Book Checks: {} symbol mismatches, {} crossed ({} levels removed)",
+                                book_metrics.symbol_mismatches.load(Ordering::Relaxed),
+                                book_metrics.crossed_books.load(Ordering::Relaxed),
+                                book_metrics.crossed_levels_removed.load(Ordering::Relaxed));
+                            if let Some(metrics) = &filter_metrics {
+                                info!("   Event Filter: {} passed, {} dropped",
+                                    metrics.passed.load(Ordering::Relaxed),
+                                    metrics.dropped.load(Ordering::Relaxed));
+                            }
+                            if let Some(metrics) = &depth_metrics {
+                                info!("   Depth Cap: {} far levels evicted", metrics.evicted_levels.load(Ordering::Relaxed));
+                            }
+                            info!("   Slow Path: {} handoffs skipped, {} events dropped, {}ms behind",
+                                snapshot_rx.skipped(), snapshot.slow_events_dropped, received.elapsed().as_millis());
+                            if let Some(recorder) = &observation_recorder {
+                                info!("   Observations: {} dropped (writer behind)", recorder.dropped());
+                            }
+                            info!("");
+                        }
+
+                        // Booked PnL and fees against the exchange's income history
+                        if let (Some(writer), true) = (reconciliation_writer.as_mut(), reconciliation_due) {
+                            last_reconciliation = Instant::now();
+                            let reports = reconcile_accounts(
+                                &clients, &engine.local_pnl, &symbol, &settlement_asset, started_ms,
+                            ).await;
+                            for report in reports {
+                                if report.is_reconciled(reconciliation_tolerance) {
+                                    info!("✓ Reconciled [{}]: {}", report.account, report.summary());
+                                } else {
+                                    warn!("⚠️  Reconciliation gap [{}]: {}", report.account, report.summary());
+                                }
+                                if let Err(e) = writer.append(&report) {
+                                    warn!("Failed to write reconciliation report: {}", e);
+                                }
+                            }
+                            if let Err(e) = writer.flush() {
+                                warn!("Failed to flush reconciliation reports: {}", e);
                             }
                         }
                     }
+                }
 
-                    // Print stats every 1000 updates (~100 seconds)
-                    if event_count % 1000 == 0 {
-                        let stats = execution_engine.get_stats();
-                        info!("📈 Trading Stats:");
-                        info!("   Open Positions: {}", stats.open_positions);
-                        info!("   Closed Trades: {}", stats.closed_trades);
-                        info!("   Realized PnL: {}", stats.total_realized_pnl);
-                        info!("   Win Rate: {:.2}%", stats.win_rate * 100.0);
-                        info!("   Total Fees: {}", stats.total_fees);
-                        info!("");
+                // Book snapshots and heatmaps need every level, not the
+                // evaluated top; the fast path attaches a full copy on request
+                let book_snapshot_due = book_writer.is_some() && last_book_snapshot.elapsed() >= book_snapshot_interval;
+                let heatmap_due = heatmap_writer.is_some() && last_heatmap.elapsed() >= heatmap_interval;
+                match (&snapshot.full_book, book_snapshot_due || heatmap_due) {
+                    (_, false) => {}
+                    (None, true) => {
+                        let _ = requests.send(SlowPathRequest::FullBook);
+                    }
+                    (Some(full_book), true) => {
+                        if let (Some(writer), true) = (book_writer.as_mut(), book_snapshot_due) {
+                            last_book_snapshot = Instant::now();
+                            let capture = BookSnapshot::capture(full_book, &symbol,
+                                snapshot.event_time_ms, Some(snapshot.final_update_id));
+                            if let Err(e) = writer.append(&capture) {
+                                warn!("Failed to write book snapshot: {}", e);
+                            }
+                        }
+
+                        if let (Some(writer), true) = (heatmap_writer.as_mut(), heatmap_due) {
+                            last_heatmap = Instant::now();
+                            if let Some(sample) = HeatmapSample::capture(full_book, snapshot.event_time_ms, &heatmap_config) {
+                                if let Err(e) = writer.append(sample) {
+                                    warn!("Failed to write heatmap sample: {}", e);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Account reads and the fleet sync wait on REST and shared
+                // state round trips here, after the evaluation, never on the
+                // fast path; what they read goes back as requests
+                if equity_sizing && last_equity_refresh.elapsed() >= equity_refresh {
+                    last_equity_refresh = Instant::now();
+                    for (account, client) in &clients {
+                        match client.account_equity().await {
+                            Ok(Some(equity)) => {
+                                let _ = requests.send(SlowPathRequest::AccountEquity { account: account.clone(), equity });
+                            }
+                            Ok(None) => warn!("No equity in account info [{}]", account),
+                            Err(e) => warn!("Failed to refresh equity [{}]: {}", account, e),
+                        }
+                    }
+                }
+
+                if let Some(rate_symbol) = &quote_rate_symbol {
+                    if last_quote_refresh.elapsed() >= QUOTE_RATE_REFRESH {
+                        last_quote_refresh = Instant::now();
+                        match quote_rate_client.get_mark_price(rate_symbol).await {
+                            Ok(rate) => {
+                                let _ = requests.send(SlowPathRequest::QuoteUsd(rate));
+                            }
+                            Err(e) => warn!("Failed to refresh {} mark price: {}", rate_symbol, e),
+                        }
                     }
                 }
+
+                if let Some(refresh) = calendar_refresh {
+                    if last_calendar_refresh.elapsed() >= refresh {
+                        last_calendar_refresh = Instant::now();
+                        match EventCalendar::from_url(&calendar_config.source, calendar_config.blackout.clone()).await {
+                            Ok(calendar) => {
+                                debug!("Event calendar refreshed: {} events", calendar.len());
+                                let _ = requests.send(SlowPathRequest::Calendar(calendar));
+                            }
+                            Err(e) => warn!("Failed to refresh event calendar, keeping the last one: {}", e),
+                        }
+                    }
+                }
+
+                // Publish our figures; pick up fleet exposure and halts
+                // (applied to the shared risk handle directly)
+                if let Some(fleet) = fleet_sync.as_mut() {
+                    if last_fleet_sync.elapsed() >= fleet_interval {
+                        last_fleet_sync = Instant::now();
+                        let metrics = fleet_risk.metrics();
+                        match fleet.sync(metrics.daily_pnl + metrics.unrealized_pnl, snapshot.exposure).await {
+                            Ok(status) => {
+                                if let Some(max_exposure) = fleet.max_exposure() {
+                                    fleet_risk.write().set_fleet_exposure(status.others_exposure, max_exposure);
+                                }
+                                if let Some(reason) = status.halt {
+                                    if fleet_risk.halt(&reason) {
+                                        error!("🚨 Fleet halted: {}", reason);
+                                    }
+                                }
+                            }
+                            Err(e) => warn!("Fleet state sync failed: {}", e),
+                        }
+                    }
+                }
+
+                // Sweeping sends orders, so the engine's owner does it
+                if !dust_interval.is_zero() && last_dust_sweep.elapsed() >= dust_interval {
+                    last_dust_sweep = Instant::now();
+                    let _ = requests.send(SlowPathRequest::SweepDust);
+                }
+            }
+
+            // The open heatmap file is unreadable until its footer is written
+            if let Some(writer) = heatmap_writer {
+                if let Err(e) = writer.close() {
+                    warn!("Failed to finish heatmap file: {}", e);
+                }
             }
+        })
+    };
+
+    let mut event_count: u64 = 0;
+    let mut imbalance_z = None;
+    let mut engine_view = None;
+    let mut full_book = FullBookRequest::default();
+    let mut slow_events_dropped: u64 = 0;
+
+    // Main trading loop (fast path)
+    loop {
+        tokio::select! {
+            Some(request) = request_rx.recv() => match request {
+                SlowPathRequest::DetectorSignal { detector, direction, mid, at } => {
+                    execution_engine.record_detector_signal(detector, direction, mid, at);
+                }
+                SlowPathRequest::ImbalanceZ(z_score) => imbalance_z = z_score,
+                SlowPathRequest::Rejection(reason) => execution_engine.record_rejection(reason),
+                SlowPathRequest::DetectorDisabled { detector, reason } => {
+                    execution_engine.alert_detector_disabled(&detector, &reason);
+                }
+                SlowPathRequest::ResetDetector(detector) => execution_engine.reset_detector(&detector),
+                // Built here, once per request, and attached to the next snapshot
+                SlowPathRequest::EngineView => {
+                    if engine_view.is_none() {
+                        engine_view = Some(Arc::new(EngineView {
+                            stats: execution_engine.get_stats(),
+                            snapshots: execution_engine.stats_snapshots(),
+                            ledger: execution_engine.audit_ledger(),
+                            resting_orders: execution_engine.resting_orders().len(),
+                            self_trades_prevented: execution_engine.self_trades_prevented(),
+                            local_pnl: execution_engine.local_pnl(),
+                        }));
+                    }
+                }
+                SlowPathRequest::AccountEquity { account, equity } => {
+                    if let Err(e) = execution_engine.set_account_equity(&account, equity) {
+                        warn!("Equity not applied: {}", e);
+                    }
+                }
+                SlowPathRequest::FullBook => full_book.request(),
+                SlowPathRequest::QuoteUsd(rate) => execution_engine.set_quote_usd(rate),
+                SlowPathRequest::Calendar(calendar) => execution_engine.set_calendar(calendar),
+                SlowPathRequest::SweepDust => {
+                    if let Some(mid) = orderbook.get_mid_price() {
+                        if let Err(e) = execution_engine.sweep_dust(mid).await {
+                            warn!("Dust sweep failed: {}", e);
+                        }
+                    }
+                }
+                SlowPathRequest::Enter(composite) => {
+                    execution_engine.record_signal();
+                    for account in execution_engine.account_names() {
+                        // Check if not halted (each account has its own breaker)
+                        let risk = execution_engine.account_risk_handle(&account).unwrap();
+                        if risk.is_halted() {
+                            warn!("   ⚠️  Trading halted [{}]: {}", account,
+                                risk.halt_reason().unwrap_or_else(|| "Unknown".to_string()));
+                            execution_engine.record_account_rejection(&account, RejectionReason::Halted);
+                            continue;
+                        }
+
+                        info!("   Executing trade [{}]...", account);
+
+                        match execution_engine.execute_signal_for(&account, composite.clone(), &orderbook).await {
+                            ExecutionOutcome::Executed(result) => {
+                                info!("   ✅ TRADE EXECUTED [{}]", account);
+                                info!("      Order ID: {}", result.order_id);
+                                info!("      Price: {}", result.executed_price);
+                                info!("      Quantity: {}", result.executed_qty);
+                                info!("      Latency: {}ms", result.latency_ms);
+                            }
+                            ExecutionOutcome::Failed(e) => {
+                                error!("   ✗ Execution failed [{}]: {}", account, e);
+                            }
+                            skipped => {
+                                warn!("   ⛔ Not executed [{}]: {}", account, skipped);
+                            }
+                        }
+                    }
+                    info!("");
+                }
+            },
 
-            MarketEvent::Trade(trade) => {
-                if let Some(_signal) = flow_analyzer.process_trade(trade) {
-                    // Flow signals are captured in the aggregate above
+            event = event_rx.recv() => {
+                let Some(TimedEvent { received, event }) = event else {
+                    break;
+                };
+                // Never waits on the slow path: a full queue drops the event.
+                // Events only the shadow and the bus take give way to trades
+                // and liquidations once the queue is half full.
+                let needed = matches!(event, MarketEvent::Trade(_) | MarketEvent::Liquidation(_));
+                if needed || forward_all_events {
+                    let reserved = if needed { 0 } else { SLOW_EVENT_CAPACITY / 2 };
+                    if slow_event_tx.capacity() <= reserved || slow_event_tx.try_send(event.clone()).is_err() {
+                        slow_events_dropped += 1;
+                    }
+                }
+
+                match event {
+                    MarketEvent::Connected => {
+                        info!("✓ WebSocket connected");
+                    }
+
+                    MarketEvent::Disconnected => {
+                        warn!("✗ WebSocket disconnected");
+                    }
+
+                    MarketEvent::BookCorrupted(corrupted) => {
+                        error!("✗ {}; book cleared, resyncing", corrupted);
+                    }
+
+                    // Touch changes ahead of the depth diffs: exits react in between
+                    MarketEvent::BookTicker(ticker) => {
+                        if let Some((best_bid, _, best_ask, _)) = ticker.parse() {
+                            if let Err(e) = execution_engine.check_exits_at_touch(best_bid, best_ask, imbalance_z).await {
+                                error!("Error checking exits: {}", e);
+                            }
+                        }
+                    }
+
+                    MarketEvent::MarkPrice(_) => {}
+
+                    // Delivered on the user data stream's own channel
+                    MarketEvent::OrderUpdate(_) | MarketEvent::AccountUpdate(_) => {}
+
+                    // Consumed by the flow analyzer and liquidation detector on the slow path
+                    MarketEvent::Trade(_) | MarketEvent::Liquidation(_) => {}
+
+                    MarketEvent::DepthUpdate(update) => {
+                        event_count += 1;
+
+                        // Exits on every update, against the book as it is now
+                        if let Err(e) = execution_engine.check_exits(&orderbook, imbalance_z).await {
+                            error!("Error checking exits: {}", e);
+                        }
+
+                        // Detector scoring and the slow path handoff every 10 updates (~1 second)
+                        if !event_count.is_multiple_of(10) {
+                            continue;
+                        }
+
+                        // Stale data should trip the latency breaker like slow orders do
+                        if feed_latency.samples.load(Ordering::Relaxed) > 0 {
+                            execution_engine.record_feed_latency(feed_latency.last_latency_ms.load(Ordering::Relaxed));
+                        }
+
+                        let mid = orderbook.get_mid_price();
+
+                        // Score detectors' earlier signals for the slow path's policy
+                        if let Some(mid) = mid {
+                            execution_engine.score_detectors(mid, received.wall_time());
+                        }
+                        execution_engine.sample_risk_metrics(received.wall_time());
+
+                        snapshot_tx.publish(FastPathSnapshot {
+                            handoff: 0,
+                            depth_updates: event_count,
+                            event_time_ms: update.event_time,
+                            final_update_id: update.final_update_id,
+                            received,
+                            feed_latency_ms: feed_latency.last_latency_ms.load(Ordering::Relaxed),
+                            mid,
+                            book: Arc::new(orderbook.snapshot_top(handoff_levels)),
+                            full_book: full_book.take(&orderbook),
+                            exposure: execution_engine.position_manager().total_exposure(),
+                            slow_events_dropped,
+                            engine: engine_view.take(),
+                        });
+                    }
                 }
             }
         }
     }
 
+    // Let the slow path finish its files
+    drop(snapshot_tx);
+    drop(slow_event_tx);
+    if let Err(e) = slow_path.await {
+        error!("Slow path task failed: {}", e);
+    }
+
     Ok(())
 }
-
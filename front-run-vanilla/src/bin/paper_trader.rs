@@ -1,11 +1,17 @@
-use front_run_vanilla::{
-    OrderBook, BinanceWebSocket, MarketEvent,
-    ImbalanceDetector, FlowAnalyzer, SignalAggregator,
-};
+use front_run_vanilla::{OrderBook, BinanceRestClient, BinanceWebSocket, MarketEvent, TimedEvent};
+use front_run_vanilla::backtest::{PaperBroker, PaperExits, PaperFill};
+use front_run_vanilla::exchange::{BybitWebSocket, MarketDataStream, OkxWebSocket};
+use front_run_vanilla::strategy::RejectionCounts;
+use front_run_vanilla::sampled;
+use front_run_vanilla::storage::{BusMessage, BusPublisher};
+use front_run_vanilla::utils::Config;
+use front_run_vanilla::utils::LogSampler;
+use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{info, warn, error};
-use tracing_subscriber;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -20,71 +26,142 @@ async fn main() -> anyhow::Result<()> {
 
     // Configuration (from .env or config file in production)
     let symbol = "BTCUSDT".to_string();
-    let ws_endpoint = "wss://fstream.binance.com".to_string();
+    // PAPER_VENUE=bybit or okx runs the same strategy on that venue's USDT perps
+    let venue = std::env::var("PAPER_VENUE").unwrap_or_else(|_| "binance".to_string());
 
     // Create shared order book
     let orderbook = Arc::new(OrderBook::new(&symbol));
 
-    // Create WebSocket connection
-    let (ws, mut event_rx) = BinanceWebSocket::new(
-        symbol.clone(),
-        ws_endpoint,
-        Arc::clone(&orderbook),
-    );
+    // Create WebSocket connection and start it in background
+    let (mut event_rx, ws_handle) = match venue.as_str() {
+        "bybit" => {
+            let (ws, event_rx) = BybitWebSocket::new(
+                symbol.clone(),
+                "wss://stream.bybit.com/v5/public/linear".to_string(),
+                Arc::clone(&orderbook),
+            );
+            (event_rx, spawn_feed(ws))
+        }
+        "okx" => {
+            // Swap sizes are in contracts (0.01 BTC each)
+            let (ws, event_rx) = OkxWebSocket::new(
+                "BTC-USDT-SWAP".to_string(),
+                "wss://ws.okx.com:8443/ws/v5/public".to_string(),
+                Arc::clone(&orderbook),
+            );
+            (event_rx, spawn_feed(ws))
+        }
+        _ => {
+            let (ws, event_rx) = BinanceWebSocket::new(
+                symbol.clone(),
+                "wss://fstream.binance.com".to_string(),
+                Arc::clone(&orderbook),
+            );
+            // Public depth snapshots; the API keys are never used
+            let snapshots = BinanceRestClient::new(String::new(), String::new(), "https://fapi.binance.com".to_string());
+            let ws = ws.with_depth_snapshots(Arc::new(snapshots), 1000);
+            (event_rx, spawn_feed(ws))
+        }
+    };
+    info!("Venue: {}", venue);
 
-    // Start WebSocket in background
-    let ws_handle = tokio::spawn(async move {
-        ws.run().await;
-    });
-
-    // Create signal detectors
-    let mut imbalance_detector = ImbalanceDetector::new(
-        5,      // 5 levels
-        100,    // 100 sample window
-        3.0,    // 3.0 sigma threshold
-    );
-
-    let mut flow_analyzer = FlowAnalyzer::new(
-        20,     // 20 trades window
-        5000,   // 5 second time window
-        0.6,    // 60% flow imbalance threshold
-    );
-
-    let signal_aggregator = SignalAggregator::new(
-        3.0,    // Primary threshold
-        1.5,    // Confirming threshold
-        2,      // Min 2 confirming signals
-    );
+    // [strategy], [paper] and [storage] from CONFIG_FILE when set
+    let config = match std::env::var("CONFIG_FILE") {
+        Ok(path) => Some(Config::from_file(&path)?),
+        Err(_) => None,
+    };
+
+    // Create signal detectors (defaults match config/production.toml)
+    let params = config.as_ref()
+        .map(|config| config.strategy.params.clone())
+        .unwrap_or_default();
+    if !params.kind.runs_live() {
+        anyhow::bail!(
+            "strategy.kind = {:?} is backtest only; the paper trader runs front_run",
+            params.kind
+        );
+    }
+    let mut imbalance_detector = params.imbalance_detector();
+    let mut flow_analyzer = params.flow_analyzer();
+    let signal_aggregator = params.signal_aggregator();
+    let take_profit = params.take_profit_schedule();
+
+    // Tradeable signals are executed on a simulated venue: fills go through
+    // the [paper] fill model (maker fill probability, partial fills,
+    // latency jitter) and are booked in its position manager
+    let paper = config.as_ref().map(|config| config.paper.clone()).unwrap_or_default();
+    let exits = PaperExits {
+        stop_loss_bps: params.stop_loss_bps(),
+        max_hold_time_ms: params.max_hold_time_ms,
+    };
+    let mut broker = PaperBroker::new(&symbol, &paper, exits)?;
+    if let Some(config) = &config {
+        broker = broker.with_instrument(config.general.instrument);
+    }
+    let position_size = config.as_ref()
+        .and_then(|config| Decimal::from_f64(config.position_sizing.base_notional_usd))
+        .unwrap_or(Decimal::from(1000));
+
+    // Market events and tradeable signals for external consumers
+    let bus = match config.as_ref().map(|config| &config.storage) {
+        Some(storage) if storage.event_bus => {
+            let path = Path::new(&storage.data_dir).join(storage.bus_encoding.file_name());
+            info!("Publishing event bus ({:?}) to {}", storage.bus_encoding, path.display());
+            Some(BusPublisher::to_file(&path, storage.bus_encoding)?)
+        }
+        _ => None,
+    };
 
     // Stats tracking
     let mut event_count = 0;
     let mut signal_count = 0;
     let mut trade_signal_count = 0;
+    let mut rejections = RejectionCounts::default();
+
+    // Individual detector signals fire many times per second; sample them
+    let imbalance_log = LogSampler::new(Duration::from_secs(1));
+    let flow_log = LogSampler::new(Duration::from_secs(1));
 
     info!("System initialized. Waiting for market data...");
     info!("");
 
     // Main event loop
-    while let Some(event) = event_rx.recv().await {
+    while let Some(TimedEvent { event, .. }) = event_rx.recv().await {
+        if let Some(bus) = &bus {
+            if let Some(message) = BusMessage::from_market_event(&event) {
+                bus.publish(message);
+            }
+        }
+
         match event {
             MarketEvent::Connected => {
-                info!("✓ Connected to Binance WebSocket");
+                info!("✓ Connected to {} WebSocket", venue);
             }
 
             MarketEvent::Disconnected => {
-                warn!("✗ Disconnected from Binance WebSocket");
+                warn!("✗ Disconnected from {} WebSocket", venue);
+            }
+
+            MarketEvent::BookCorrupted(corrupted) => {
+                error!("✗ {}; book cleared, resyncing", corrupted);
             }
 
-            MarketEvent::DepthUpdate(_update) => {
+            MarketEvent::BookTicker(_) | MarketEvent::MarkPrice(_) | MarketEvent::Liquidation(_) => {}
+
+            MarketEvent::OrderUpdate(_) | MarketEvent::AccountUpdate(_) => {}
+
+            MarketEvent::DepthUpdate(update) => {
                 event_count += 1;
 
                 // Every 10 updates, check for signals
                 if event_count % 10 == 0 {
                     let mut signals = Vec::new();
 
-                    // 1. Check imbalance signal
-                    if let Some(signal) = imbalance_detector.calculate_signal(&orderbook) {
-                        info!(
+                    // 1. Check imbalance signal (stamped with exchange event time)
+                    let event_time = UNIX_EPOCH + Duration::from_millis(update.event_time);
+                    if let Some(signal) = imbalance_detector.calculate_signal_at(&orderbook, event_time) {
+                        sampled!(
+                            imbalance_log, info,
                             "📊 Imbalance Signal: {:?} | Strength: {:.2} | Confidence: {:.2}",
                             signal.direction, signal.strength, signal.confidence
                         );
@@ -94,24 +171,36 @@ async fn main() -> anyhow::Result<()> {
 
                     // 2. Aggregate signals
                     if !signals.is_empty() {
-                        if let Some(composite) = signal_aggregator.aggregate(signals) {
-                            info!("");
-                            info!("🎯 COMPOSITE SIGNAL GENERATED");
-                            info!("   Direction: {:?}", composite.direction);
-                            info!("   Strength: {:.2}", composite.overall_strength);
-                            info!("   Confidence: {:.2}", composite.confidence);
-                            info!("   Confirming: {}", composite.confirming.len());
-                            
-                            if composite.is_tradeable(2) {
-                                info!("   ✅ TRADEABLE - Would execute in live mode");
-                                trade_signal_count += 1;
+                        match signal_aggregator.aggregate_or_reject(signals) {
+                            Err(reason) => rejections.record(reason),
+                            Ok(composite) => {
+                                info!("");
+                                info!("🎯 COMPOSITE SIGNAL GENERATED");
+                                info!("   Direction: {:?}", composite.direction);
+                                info!("   Strength: {:.2}", composite.overall_strength);
+                                info!("   Confidence: {:.2}", composite.confidence);
+                                info!("   Confirming: {}", composite.confirming.len());
                                 
-                                // In live mode, this is where we'd execute:
-                                // execute_trade(composite).await;
-                            } else {
-                                info!("   ⚠ Not tradeable - insufficient confirming signals");
+                                match composite.rejection(params.min_confirming_signals) {
+                                    None => {
+                                        info!("   ✅ TRADEABLE");
+                                        trade_signal_count += 1;
+                                        if let Some(bus) = &bus {
+                                            bus.publish(BusMessage::signal(&symbol, &composite));
+                                        }
+
+                                        let take_profit_bps = take_profit.take_profit_bps(composite.overall_strength);
+                                        if broker.submit_entry(composite.direction, position_size, take_profit_bps, SystemTime::now()) {
+                                            info!("   📨 Paper {:?} entry sent for ${}", composite.direction, position_size);
+                                        }
+                                    }
+                                    Some(reason) => {
+                                        info!("   ⚠ Not tradeable - {:?}", reason);
+                                        rejections.record(reason);
+                                    }
+                                }
+                                info!("");
                             }
-                            info!("");
                         }
                     }
 
@@ -129,15 +218,29 @@ async fn main() -> anyhow::Result<()> {
                         info!("   Spread: {:?} bps", spread);
                         info!("   Book Depth: {} bids, {} asks", bid_count, ask_count);
                         info!("   Signals: {} generated, {} tradeable", signal_count, trade_signal_count);
+                        info!("   Not Traded: {}", rejections.summary());
+                        let positions = broker.position_manager();
+                        info!(
+                            "   Paper: {} trades | PnL: {} | Fees: {} | Win rate: {:.1}%",
+                            positions.closed_positions().len(), positions.total_realized_pnl(),
+                            positions.total_fees(), positions.win_rate() * 100.0
+                        );
                         info!("");
                     }
                 }
+
+                // Paper orders fill and exits are checked on every book update
+                match broker.on_book(&orderbook, SystemTime::now()) {
+                    Ok(fills) => fills.iter().for_each(log_fill),
+                    Err(e) => error!("Paper execution failed: {}", e),
+                }
             }
 
             MarketEvent::Trade(trade) => {
                 // Process trade for flow analysis
                 if let Some(signal) = flow_analyzer.process_trade(trade) {
-                    info!(
+                    sampled!(
+                        flow_log, info,
                         "💹 Flow Signal: {:?} | Strength: {:.2} | Confidence: {:.2}",
                         signal.direction, signal.strength, signal.confidence
                     );
@@ -152,3 +255,22 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn log_fill(fill: &PaperFill) {
+    match fill.exit {
+        None => info!(
+            "📝 Paper entry: {:?} {} @ {} | Fees: {} | Latency: {}ms",
+            fill.side, fill.quantity, fill.price, fill.fees, fill.latency_ms
+        ),
+        Some((reason, pnl)) => info!(
+            "📝 Paper exit ({:?}): {:?} {} @ {} | PnL: {} | Fees: {} | Latency: {}ms",
+            reason, fill.side, fill.quantity, fill.price, pnl, fill.fees, fill.latency_ms
+        ),
+    }
+}
+
+fn spawn_feed<S: MarketDataStream + 'static>(ws: S) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        ws.run().await;
+    })
+}
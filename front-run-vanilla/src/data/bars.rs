@@ -0,0 +1,244 @@
+use crate::data::types::{Side, Trade};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::SystemTime;
+
+/// Information-driven bar sampling rule
+///
+/// Bars close on activity rather than wall-clock time, so fast markets
+/// produce more bars and quiet markets fewer (López de Prado, AFML ch. 2).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BarType {
+    /// Close after this much base-asset volume has traded
+    Volume(Decimal),
+    /// Close after this much quote notional (price * qty) has traded
+    Dollar(Decimal),
+    /// Close when signed tick count exceeds its expected magnitude
+    TickImbalance {
+        /// Initial guess for ticks per bar, E[T] before any bar closes
+        expected_ticks: f64,
+        /// EWMA weight for updating E[T] and P[buy] from closed bars
+        alpha: f64,
+    },
+}
+
+/// Completed OHLCV bar with aggressor breakdown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bar {
+    pub open_time: SystemTime,
+    pub close_time: SystemTime,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    pub dollar_volume: Decimal,
+    /// Volume from aggressive buys (taker = buyer)
+    pub buy_volume: Decimal,
+    pub tick_count: usize,
+    /// Net signed ticks (buys - sells) within the bar
+    pub tick_imbalance: i64,
+}
+
+impl Bar {
+    fn open(trade: &Trade) -> Self {
+        Self {
+            open_time: trade.timestamp,
+            close_time: trade.timestamp,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: Decimal::ZERO,
+            dollar_volume: Decimal::ZERO,
+            buy_volume: Decimal::ZERO,
+            tick_count: 0,
+            tick_imbalance: 0,
+        }
+    }
+
+    fn add(&mut self, trade: &Trade) {
+        self.close_time = trade.timestamp;
+        self.high = self.high.max(trade.price);
+        self.low = self.low.min(trade.price);
+        self.close = trade.price;
+        self.volume += trade.quantity;
+        self.dollar_volume += trade.price * trade.quantity;
+        self.tick_count += 1;
+
+        match trade.side {
+            Side::Buy => {
+                self.buy_volume += trade.quantity;
+                self.tick_imbalance += 1;
+            }
+            Side::Sell => self.tick_imbalance -= 1,
+        }
+    }
+
+    /// Volume from aggressive sells
+    pub fn sell_volume(&self) -> Decimal {
+        self.volume - self.buy_volume
+    }
+
+    /// Volume-weighted average price
+    pub fn vwap(&self) -> Option<Decimal> {
+        if self.volume.is_zero() {
+            None
+        } else {
+            Some(self.dollar_volume / self.volume)
+        }
+    }
+}
+
+/// Streaming bar builder fed from the trade stream
+///
+/// Push every trade; a `Bar` is returned whenever the sampling rule fires.
+/// The trade that crosses the threshold is included in the closed bar.
+pub struct BarBuilder {
+    bar_type: BarType,
+    current: Option<Bar>,
+
+    /// Tick imbalance state: EWMA of ticks per bar and of P[buy tick]
+    expected_ticks: f64,
+    expected_buy_prob: f64,
+}
+
+impl BarBuilder {
+    pub fn new(bar_type: BarType) -> Self {
+        let expected_ticks = match &bar_type {
+            BarType::TickImbalance { expected_ticks, .. } => *expected_ticks,
+            _ => 0.0,
+        };
+
+        Self {
+            bar_type,
+            current: None,
+            expected_ticks,
+            expected_buy_prob: 0.5,
+        }
+    }
+
+    /// Add a trade; returns the bar it completes, if any
+    pub fn push(&mut self, trade: &Trade) -> Option<Bar> {
+        let bar = self.current.get_or_insert_with(|| Bar::open(trade));
+        bar.add(trade);
+
+        if self.should_close() {
+            let bar = self.current.take()?;
+            self.on_close(&bar);
+            Some(bar)
+        } else {
+            None
+        }
+    }
+
+    /// Bar in progress (not yet closed)
+    pub fn current(&self) -> Option<&Bar> {
+        self.current.as_ref()
+    }
+
+    /// Current tick imbalance threshold E[T] * |2 P[buy] - 1|
+    pub fn imbalance_threshold(&self) -> f64 {
+        self.expected_ticks * (2.0 * self.expected_buy_prob - 1.0).abs()
+    }
+
+    fn should_close(&self) -> bool {
+        let bar = match &self.current {
+            Some(bar) => bar,
+            None => return false,
+        };
+
+        match &self.bar_type {
+            BarType::Volume(threshold) => bar.volume >= *threshold,
+            BarType::Dollar(threshold) => bar.dollar_volume >= *threshold,
+            BarType::TickImbalance { .. } => {
+                // With P[buy] = 0.5 the threshold collapses to 0; require at
+                // least one tick of imbalance so bars aren't single trades
+                let threshold = self.imbalance_threshold().max(1.0);
+                bar.tick_imbalance.unsigned_abs() as f64 >= threshold
+            }
+        }
+    }
+
+    fn on_close(&mut self, bar: &Bar) {
+        if let BarType::TickImbalance { alpha, .. } = self.bar_type {
+            let ticks = bar.tick_count as f64;
+            let buy_ticks = (ticks + bar.tick_imbalance as f64) / 2.0;
+
+            self.expected_ticks = alpha * ticks + (1.0 - alpha) * self.expected_ticks;
+            self.expected_buy_prob = alpha * (buy_ticks / ticks) + (1.0 - alpha) * self.expected_buy_prob;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn trade(id: u64, price: Decimal, quantity: Decimal, side: Side) -> Trade {
+        Trade {
+            id,
+            price,
+            quantity,
+            side,
+            timestamp: UNIX_EPOCH + Duration::from_millis(id),
+            is_buyer_maker: side == Side::Sell,
+        }
+    }
+
+    #[test]
+    fn test_volume_bars() {
+        let mut builder = BarBuilder::new(BarType::Volume(dec!(2)));
+
+        assert!(builder.push(&trade(1, dec!(100), dec!(1), Side::Buy)).is_none());
+        let bar = builder.push(&trade(2, dec!(102), dec!(1.5), Side::Sell)).unwrap();
+
+        assert_eq!(bar.open, dec!(100));
+        assert_eq!(bar.high, dec!(102));
+        assert_eq!(bar.close, dec!(102));
+        assert_eq!(bar.volume, dec!(2.5));
+        assert_eq!(bar.buy_volume, dec!(1));
+        assert_eq!(bar.sell_volume(), dec!(1.5));
+        assert_eq!(bar.tick_count, 2);
+        assert!(builder.current().is_none());
+    }
+
+    #[test]
+    fn test_dollar_bars() {
+        let mut builder = BarBuilder::new(BarType::Dollar(dec!(1000)));
+
+        assert!(builder.push(&trade(1, dec!(100), dec!(5), Side::Buy)).is_none());
+        let bar = builder.push(&trade(2, dec!(100), dec!(5), Side::Buy)).unwrap();
+
+        assert_eq!(bar.dollar_volume, dec!(1000));
+        assert_eq!(bar.vwap(), Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_tick_imbalance_bars_adapt_threshold() {
+        let mut builder = BarBuilder::new(BarType::TickImbalance {
+            expected_ticks: 10.0,
+            alpha: 0.5,
+        });
+
+        // Balanced prior: first bar closes on the first net tick
+        let bar = builder.push(&trade(1, dec!(100), dec!(1), Side::Buy)).unwrap();
+        assert_eq!(bar.tick_imbalance, 1);
+
+        // A one-sided bar raises P[buy], so more imbalance is needed next time
+        assert!(builder.imbalance_threshold() > 1.0);
+
+        let mut closed = None;
+        for id in 2..20 {
+            if let Some(bar) = builder.push(&trade(id, dec!(100), dec!(1), Side::Buy)) {
+                closed = Some(bar);
+                break;
+            }
+        }
+        let bar = closed.unwrap();
+        assert!(bar.tick_imbalance as f64 >= 1.0);
+        assert!(bar.tick_count > 1);
+    }
+}
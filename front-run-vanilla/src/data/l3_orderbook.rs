@@ -0,0 +1,277 @@
+use crate::data::{OrderBook, Side};
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+/// Single resting order in an order-by-order (L3) feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct L3Order {
+    pub order_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+/// Aggregate level change produced by an L3 event
+///
+/// `quantity` is the new total at the level; zero means the level is gone.
+/// This is exactly what `OrderBook::update_level` expects, so L3 venues can
+/// drive the same L2 book the detectors already read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2Change {
+    pub side: Side,
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+/// (price, total quantity) per level, best first
+type PriceLevels = Vec<(Decimal, Decimal)>;
+
+/// Aggregated level with order count
+#[derive(Debug, Clone, Copy, Default)]
+struct Level {
+    quantity: Decimal,
+    orders: usize,
+}
+
+/// Order-by-order book for venues publishing per-order feeds
+/// (Coinbase full channel, Kraken L3)
+///
+/// Tracks individual orders by ID and maintains per-price aggregates so
+/// L2 views (depth, imbalance) come for free. Every mutation returns the
+/// resulting `L2Change`s for mirroring into an `OrderBook`.
+pub struct L3OrderBook {
+    symbol: String,
+    orders: HashMap<String, L3Order>,
+    bids: BTreeMap<Decimal, Level>,
+    asks: BTreeMap<Decimal, Level>,
+}
+
+impl L3OrderBook {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            orders: HashMap::new(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Add a new resting order
+    pub fn add(&mut self, order_id: &str, side: Side, price: Decimal, quantity: Decimal) -> Result<L2Change> {
+        if quantity <= Decimal::ZERO || price <= Decimal::ZERO {
+            return Err(anyhow!("Invalid order {}: price {} qty {}", order_id, price, quantity));
+        }
+        if self.orders.contains_key(order_id) {
+            return Err(anyhow!("Duplicate order id {}", order_id));
+        }
+
+        self.orders.insert(order_id.to_string(), L3Order {
+            order_id: order_id.to_string(),
+            side,
+            price,
+            quantity,
+        });
+
+        Ok(self.adjust_level(side, price, quantity, 1))
+    }
+
+    /// Change an order's remaining quantity (partial fill or amend down)
+    ///
+    /// Price changes are modelled as delete + add, as venues lose queue
+    /// priority on reprice anyway.
+    pub fn modify(&mut self, order_id: &str, new_quantity: Decimal) -> Result<L2Change> {
+        if new_quantity <= Decimal::ZERO {
+            return self.delete(order_id);
+        }
+
+        let order = self.orders.get_mut(order_id)
+            .ok_or_else(|| anyhow!("Unknown order id {}", order_id))?;
+
+        let delta = new_quantity - order.quantity;
+        order.quantity = new_quantity;
+        let (side, price) = (order.side, order.price);
+
+        Ok(self.adjust_level(side, price, delta, 0))
+    }
+
+    /// Remove an order (cancel or full fill)
+    pub fn delete(&mut self, order_id: &str) -> Result<L2Change> {
+        let order = self.orders.remove(order_id)
+            .ok_or_else(|| anyhow!("Unknown order id {}", order_id))?;
+
+        Ok(self.adjust_level(order.side, order.price, -order.quantity, -1))
+    }
+
+    /// Look up a resting order
+    pub fn order(&self, order_id: &str) -> Option<&L3Order> {
+        self.orders.get(order_id)
+    }
+
+    pub fn order_count(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Number of resting orders at a price level
+    pub fn orders_at(&self, side: Side, price: Decimal) -> usize {
+        self.levels(side).get(&price).map_or(0, |l| l.orders)
+    }
+
+    pub fn best_bid(&self) -> Option<(Decimal, Decimal)> {
+        self.bids.iter().next_back().map(|(p, l)| (*p, l.quantity))
+    }
+
+    pub fn best_ask(&self) -> Option<(Decimal, Decimal)> {
+        self.asks.iter().next().map(|(p, l)| (*p, l.quantity))
+    }
+
+    pub fn mid_price(&self) -> Option<Decimal> {
+        let (bid, _) = self.best_bid()?;
+        let (ask, _) = self.best_ask()?;
+        Some((bid + ask) / Decimal::from(2))
+    }
+
+    /// Top `levels` aggregated levels per side, best first
+    pub fn l2_depth(&self, levels: usize) -> (PriceLevels, PriceLevels) {
+        let bids = self.bids.iter().rev().take(levels).map(|(p, l)| (*p, l.quantity)).collect();
+        let asks = self.asks.iter().take(levels).map(|(p, l)| (*p, l.quantity)).collect();
+        (bids, asks)
+    }
+
+    /// Bid/ask depth ratio over the top `levels`, as used by the imbalance detector
+    pub fn calculate_imbalance(&self, levels: usize) -> Option<f64> {
+        let (bids, asks) = self.l2_depth(levels);
+        let bid_depth: Decimal = bids.iter().map(|(_, q)| *q).sum();
+        let ask_depth: Decimal = asks.iter().map(|(_, q)| *q).sum();
+
+        if ask_depth.is_zero() {
+            return None;
+        }
+        (bid_depth / ask_depth).to_string().parse().ok()
+    }
+
+    /// Resting order count imbalance (bid - ask) / total over the top `levels`
+    ///
+    /// Only available with L3 data: a level held by many small orders is
+    /// stickier than one large order that can vanish in a single cancel.
+    pub fn order_count_imbalance(&self, levels: usize) -> Option<f64> {
+        let bid_orders: usize = self.bids.values().rev().take(levels).map(|l| l.orders).sum();
+        let ask_orders: usize = self.asks.values().take(levels).map(|l| l.orders).sum();
+        let total = bid_orders + ask_orders;
+
+        if total == 0 {
+            return None;
+        }
+        Some((bid_orders as f64 - ask_orders as f64) / total as f64)
+    }
+
+    /// Full L2 snapshot as level changes (for seeding an `OrderBook`)
+    pub fn l2_snapshot(&self) -> Vec<L2Change> {
+        let bids = self.bids.iter().map(|(p, l)| L2Change { side: Side::Buy, price: *p, quantity: l.quantity });
+        let asks = self.asks.iter().map(|(p, l)| L2Change { side: Side::Sell, price: *p, quantity: l.quantity });
+        bids.chain(asks).collect()
+    }
+
+    /// Mirror level changes into an L2 `OrderBook`
+    pub fn apply_to(changes: &[L2Change], orderbook: &OrderBook) -> Result<()> {
+        for change in changes {
+            orderbook.update_level(change.side, change.price, change.quantity)?;
+        }
+        Ok(())
+    }
+
+    fn adjust_level(&mut self, side: Side, price: Decimal, delta: Decimal, order_delta: isize) -> L2Change {
+        let levels = self.levels_mut(side);
+        let level = levels.entry(price).or_default();
+        level.quantity += delta;
+        level.orders = level.orders.saturating_add_signed(order_delta);
+
+        let quantity = if level.orders == 0 || level.quantity <= Decimal::ZERO {
+            levels.remove(&price);
+            Decimal::ZERO
+        } else {
+            level.quantity
+        };
+
+        L2Change { side, price, quantity }
+    }
+
+    fn levels(&self, side: Side) -> &BTreeMap<Decimal, Level> {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<Decimal, Level> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_orders_aggregate_into_levels() {
+        let mut book = L3OrderBook::new("BTC-USD");
+
+        book.add("a", Side::Buy, dec!(100), dec!(1)).unwrap();
+        let change = book.add("b", Side::Buy, dec!(100), dec!(2)).unwrap();
+        assert_eq!(change, L2Change { side: Side::Buy, price: dec!(100), quantity: dec!(3) });
+        assert_eq!(book.orders_at(Side::Buy, dec!(100)), 2);
+
+        book.add("c", Side::Sell, dec!(101), dec!(1.5)).unwrap();
+        assert_eq!(book.best_bid(), Some((dec!(100), dec!(3))));
+        assert_eq!(book.mid_price(), Some(dec!(100.5)));
+        assert_eq!(book.calculate_imbalance(5), Some(2.0));
+        assert_eq!(book.order_count_imbalance(5), Some(1.0 / 3.0));
+
+        assert!(book.add("a", Side::Buy, dec!(99), dec!(1)).is_err());
+    }
+
+    #[test]
+    fn test_modify_and_delete_update_levels() {
+        let mut book = L3OrderBook::new("BTC-USD");
+        book.add("a", Side::Sell, dec!(101), dec!(2)).unwrap();
+        book.add("b", Side::Sell, dec!(101), dec!(1)).unwrap();
+
+        let change = book.modify("a", dec!(0.5)).unwrap();
+        assert_eq!(change.quantity, dec!(1.5));
+
+        book.delete("b").unwrap();
+        let change = book.delete("a").unwrap();
+        assert_eq!(change.quantity, Decimal::ZERO);
+        assert!(book.best_ask().is_none());
+        assert_eq!(book.order_count(), 0);
+
+        assert!(book.delete("a").is_err());
+    }
+
+    #[test]
+    fn test_changes_drive_l2_orderbook() {
+        let mut book = L3OrderBook::new("BTC-USD");
+        let l2 = OrderBook::new("BTC-USD");
+
+        let changes = vec![
+            book.add("a", Side::Buy, dec!(100), dec!(1)).unwrap(),
+            book.add("b", Side::Sell, dec!(101), dec!(1)).unwrap(),
+            book.add("c", Side::Sell, dec!(102), dec!(1)).unwrap(),
+        ];
+        L3OrderBook::apply_to(&changes, &l2).unwrap();
+        assert_eq!(l2.get_book_depth_count(), (1, 2));
+
+        let change = book.delete("c").unwrap();
+        L3OrderBook::apply_to(&[change], &l2).unwrap();
+        assert_eq!(l2.get_book_depth_count(), (1, 1));
+        assert_eq!(l2.get_mid_price(), book.mid_price());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod types;
+pub mod orderbook;
+pub mod bars;
+pub mod l3_orderbook;
+
+pub use types::{Side, PriceLevel, Trade, Order, OrderType, OrderStatus, Signal, SignalComponent};
+pub use orderbook::OrderBook;
+pub use bars::{Bar, BarBuilder, BarType};
+pub use l3_orderbook::{L3OrderBook, L3Order, L2Change};
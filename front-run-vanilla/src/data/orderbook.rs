@@ -0,0 +1,285 @@
+use crate::data::types::{PriceLevel, Side};
+use anyhow::{Result, bail};
+use dashmap::DashMap;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+/// L2 order book (aggregated quantity per price level)
+///
+/// Each side is a `DashMap` so point reads (best bid, level counts) never
+/// wait on the WebSocket task. Price -> quantity; a zero quantity removes
+/// the level, matching Binance depth diff semantics.
+///
+/// Updates hold `update_lock` for writing and `get_depth` holds it for
+/// reading, so a depth copy sees both sides as of the same point: whole
+/// `apply_depth` diffs, never half of one.
+pub struct OrderBook {
+    symbol: String,
+    bids: Arc<DashMap<Decimal, Decimal>>,
+    asks: Arc<DashMap<Decimal, Decimal>>,
+    last_update: Arc<RwLock<Option<SystemTime>>>,
+    update_lock: Arc<RwLock<()>>,
+}
+
+impl OrderBook {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            bids: Arc::new(DashMap::new()),
+            asks: Arc::new(DashMap::new()),
+            last_update: Arc::new(RwLock::new(None)),
+            update_lock: Arc::new(RwLock::new(())),
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Set the quantity at a price level (zero removes the level)
+    #[inline]
+    pub fn update_level(&self, side: Side, price: Decimal, quantity: Decimal) -> Result<()> {
+        let _guard = self.update_lock.write().unwrap_or_else(|e| e.into_inner());
+        self.set_level(side, price, quantity)?;
+        self.touch();
+        Ok(())
+    }
+
+    /// Apply one depth diff (both sides) as a single update
+    ///
+    /// Readers of `get_depth` see either none or all of the diff. Levels
+    /// before an invalid one are kept, as with repeated `update_level`.
+    pub fn apply_depth(&self, bids: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) -> Result<()> {
+        let _guard = self.update_lock.write().unwrap_or_else(|e| e.into_inner());
+        let sides = [(Side::Buy, bids), (Side::Sell, asks)];
+        for (side, levels) in sides {
+            for (price, quantity) in levels {
+                self.set_level(side, *price, *quantity)?;
+            }
+        }
+        self.touch();
+        Ok(())
+    }
+
+    fn set_level(&self, side: Side, price: Decimal, quantity: Decimal) -> Result<()> {
+        if quantity.is_sign_negative() && !quantity.is_zero() {
+            bail!("Negative quantity {} at {} for {}", quantity, price, self.symbol);
+        }
+        if price <= Decimal::ZERO {
+            bail!("Non-positive price {} for {}", price, self.symbol);
+        }
+
+        let book = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+
+        if quantity.is_zero() {
+            book.remove(&price);
+        } else {
+            book.insert(price, quantity);
+        }
+        Ok(())
+    }
+
+    fn touch(&self) {
+        *self.last_update.write().unwrap_or_else(|e| e.into_inner()) = Some(SystemTime::now());
+    }
+
+    /// Drop every level on both sides
+    pub fn clear(&self) {
+        let _guard = self.update_lock.write().unwrap_or_else(|e| e.into_inner());
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Independent copy of the book as of now
+    ///
+    /// Both sides are copied under one read lock, so the copy holds whole
+    /// diffs only; later updates to this book don't reach it.
+    pub fn snapshot(&self) -> OrderBook {
+        let _guard = self.update_lock.read().unwrap_or_else(|e| e.into_inner());
+        let copy = |side: &DashMap<Decimal, Decimal>| {
+            side.iter().map(|entry| (*entry.key(), *entry.value())).collect::<DashMap<_, _>>()
+        };
+        Self {
+            symbol: self.symbol.clone(),
+            bids: Arc::new(copy(&self.bids)),
+            asks: Arc::new(copy(&self.asks)),
+            last_update: Arc::new(RwLock::new(self.last_update())),
+            update_lock: Arc::new(RwLock::new(())),
+        }
+    }
+
+    /// Independent copy of the top `levels` per side
+    ///
+    /// Like `snapshot`, but only the levels a reader evaluates are copied,
+    /// which keeps handing the book to another task cheap on a deep book.
+    pub fn snapshot_top(&self, levels: usize) -> OrderBook {
+        let (bids, asks) = self.get_depth(levels);
+        let copy = |side: Vec<PriceLevel>| {
+            side.into_iter().map(|level| (level.price, level.quantity)).collect::<DashMap<_, _>>()
+        };
+        Self {
+            symbol: self.symbol.clone(),
+            bids: Arc::new(copy(bids)),
+            asks: Arc::new(copy(asks)),
+            last_update: Arc::new(RwLock::new(self.last_update())),
+            update_lock: Arc::new(RwLock::new(())),
+        }
+    }
+
+    /// Time of the last applied level update
+    pub fn last_update(&self) -> Option<SystemTime> {
+        *self.last_update.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.iter().map(|entry| *entry.key()).max()
+    }
+
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.iter().map(|entry| *entry.key()).min()
+    }
+
+    /// (best bid, best ask)
+    pub fn get_top_of_book(&self) -> (Option<Decimal>, Option<Decimal>) {
+        (self.best_bid(), self.best_ask())
+    }
+
+    /// Mid of the best bid and ask (None unless both sides are present)
+    pub fn get_mid_price(&self) -> Option<Decimal> {
+        let (bid, ask) = self.get_top_of_book();
+        Some((bid? + ask?) / Decimal::from(2))
+    }
+
+    /// Best ask minus best bid, in bps of mid
+    pub fn get_spread_bps(&self) -> Option<Decimal> {
+        let (bid, ask) = self.get_top_of_book();
+        let (bid, ask) = (bid?, ask?);
+        let mid = (bid + ask) / Decimal::from(2);
+        if mid.is_zero() {
+            return None;
+        }
+        Some((ask - bid) / mid * Decimal::from(10000))
+    }
+
+    /// Top `levels` per side, best first, copied under one read lock
+    pub fn get_depth(&self, levels: usize) -> (Vec<PriceLevel>, Vec<PriceLevel>) {
+        let guard = self.update_lock.read().unwrap_or_else(|e| e.into_inner());
+        let mut bids: Vec<PriceLevel> = self.bids.iter()
+            .map(|entry| PriceLevel::new(*entry.key(), *entry.value()))
+            .collect();
+        let mut asks: Vec<PriceLevel> = self.asks.iter()
+            .map(|entry| PriceLevel::new(*entry.key(), *entry.value()))
+            .collect();
+        drop(guard);
+
+        bids.sort_unstable_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_unstable_by_key(|level| level.price);
+        bids.truncate(levels);
+        asks.truncate(levels);
+
+        (bids, asks)
+    }
+
+    /// Bid/ask depth ratio over the top `levels`
+    ///
+    /// > 1.0 means more resting bids than asks. None if the ask side is empty.
+    pub fn calculate_imbalance(&self, levels: usize) -> Option<f64> {
+        let (bids, asks) = self.get_depth(levels);
+        let bid_depth: Decimal = bids.iter().map(|level| level.quantity).sum();
+        let ask_depth: Decimal = asks.iter().map(|level| level.quantity).sum();
+
+        if ask_depth.is_zero() {
+            return None;
+        }
+        (bid_depth / ask_depth).to_f64()
+    }
+
+    /// Number of (bid, ask) levels currently in the book
+    pub fn get_book_depth_count(&self) -> (usize, usize) {
+        (self.bids.len(), self.asks.len())
+    }
+}
+
+impl std::fmt::Debug for OrderBook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (bids, asks) = self.get_book_depth_count();
+        f.debug_struct("OrderBook")
+            .field("symbol", &self.symbol)
+            .field("bids", &bids)
+            .field("asks", &asks)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_update_and_remove_levels() {
+        let ob = OrderBook::new("BTCUSDT");
+        ob.update_level(Side::Buy, dec!(100), dec!(1)).unwrap();
+        ob.update_level(Side::Buy, dec!(99), dec!(2)).unwrap();
+        ob.update_level(Side::Sell, dec!(101), dec!(1)).unwrap();
+        assert_eq!(ob.get_book_depth_count(), (2, 1));
+        assert_eq!(ob.get_top_of_book(), (Some(dec!(100)), Some(dec!(101))));
+
+        ob.update_level(Side::Buy, dec!(100), Decimal::ZERO).unwrap();
+        assert_eq!(ob.best_bid(), Some(dec!(99)));
+        assert!(ob.update_level(Side::Buy, dec!(98), dec!(-1)).is_err());
+    }
+
+    #[test]
+    fn test_mid_spread_and_imbalance() {
+        let ob = OrderBook::new("BTCUSDT");
+        assert_eq!(ob.get_mid_price(), None);
+
+        ob.update_level(Side::Buy, dec!(99.99), dec!(3)).unwrap();
+        ob.update_level(Side::Buy, dec!(99.98), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(100.01), dec!(2)).unwrap();
+
+        assert_eq!(ob.get_mid_price(), Some(dec!(100.00)));
+        assert_eq!(ob.get_spread_bps(), Some(dec!(2)));
+        assert_eq!(ob.calculate_imbalance(5), Some(2.0));
+
+        let (bids, asks) = ob.get_depth(1);
+        assert_eq!(bids, vec![PriceLevel::new(dec!(99.99), dec!(3))]);
+        assert_eq!(asks.len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_is_detached() {
+        let ob = OrderBook::new("BTCUSDT");
+        ob.apply_depth(&[(dec!(100), dec!(1))], &[(dec!(101), dec!(2))]).unwrap();
+
+        let copy = ob.snapshot();
+        ob.apply_depth(&[(dec!(100), Decimal::ZERO)], &[(dec!(101), dec!(5))]).unwrap();
+
+        assert_eq!(copy.get_top_of_book(), (Some(dec!(100)), Some(dec!(101))));
+        assert_eq!(copy.get_depth(1).1, vec![PriceLevel::new(dec!(101), dec!(2))]);
+        assert!(copy.last_update().is_some());
+        assert_eq!(ob.best_bid(), None);
+    }
+
+    #[test]
+    fn test_snapshot_top_copies_best_levels() {
+        let ob = OrderBook::new("BTCUSDT");
+        ob.apply_depth(
+            &[(dec!(100), dec!(1)), (dec!(99), dec!(2)), (dec!(98), dec!(3))],
+            &[(dec!(101), dec!(1)), (dec!(102), dec!(2))],
+        ).unwrap();
+
+        let copy = ob.snapshot_top(2);
+        ob.apply_depth(&[(dec!(100), Decimal::ZERO)], &[]).unwrap();
+
+        assert_eq!(copy.get_book_depth_count(), (2, 2));
+        assert_eq!(copy.get_top_of_book(), (Some(dec!(100)), Some(dec!(101))));
+        assert_eq!(copy.get_depth(5).0, vec![PriceLevel::new(dec!(100), dec!(1)), PriceLevel::new(dec!(99), dec!(2))]);
+    }
+}
@@ -0,0 +1,128 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// Order / trade side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    /// The other side of the book
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+}
+
+/// Single aggregated order book level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+impl PriceLevel {
+    pub fn new(price: Decimal, quantity: Decimal) -> Self {
+        Self { price, quantity }
+    }
+}
+
+/// Executed trade from the public tape
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub id: u64,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    /// Taker (aggressor) side
+    pub side: Side,
+    pub timestamp: SystemTime,
+    pub is_buyer_maker: bool,
+}
+
+impl Trade {
+    /// Taker bought (lifted the ask)
+    pub fn is_aggressive_buy(&self) -> bool {
+        self.side == Side::Buy
+    }
+
+    /// Taker sold (hit the bid)
+    pub fn is_aggressive_sell(&self) -> bool {
+        self.side == Side::Sell
+    }
+}
+
+/// Order type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderType {
+    Market,
+    Limit,
+}
+
+/// Order lifecycle state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected,
+}
+
+/// Order we placed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Order {
+    pub id: u64,
+    pub symbol: String,
+    pub side: Side,
+    pub order_type: OrderType,
+    /// Limit price (None for market orders)
+    pub price: Option<Decimal>,
+    pub quantity: Decimal,
+    pub filled_quantity: Decimal,
+    pub status: OrderStatus,
+    pub timestamp: SystemTime,
+}
+
+/// Output of a single detector
+#[derive(Debug, Clone)]
+pub struct Signal {
+    /// Signed strength (z-score for statistical detectors)
+    pub strength: f64,
+    pub direction: Side,
+    /// 0.0 - 1.0
+    pub confidence: f64,
+    pub timestamp: SystemTime,
+    /// Inputs behind the signal, for logging and analysis
+    pub components: Vec<SignalComponent>,
+}
+
+impl Signal {
+    /// Strength regardless of direction
+    pub fn abs_strength(&self) -> f64 {
+        self.strength.abs()
+    }
+}
+
+/// Named input to a signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalComponent {
+    pub name: String,
+    pub value: f64,
+    /// Contribution to the signal (0.0 = informational only)
+    pub weight: f64,
+}
+
+impl SignalComponent {
+    pub fn new(name: &str, value: f64, weight: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            weight,
+        }
+    }
+}
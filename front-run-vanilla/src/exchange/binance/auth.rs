@@ -31,6 +31,8 @@ pub fn get_timestamp() -> u64 {
 /// 
 /// Example:
 /// ```
+/// use front_run_vanilla::exchange::binance::auth::build_signed_query;
+///
 /// let params = vec![
 ///     ("symbol", "BTCUSDT"),
 ///     ("side", "BUY"),
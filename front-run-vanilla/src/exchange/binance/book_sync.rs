@@ -0,0 +1,264 @@
+use crate::exchange::binance::types::DepthUpdate;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Snapshot and replay counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct BookSyncMetrics {
+    /// REST snapshots requested (startup, reconnect and gaps)
+    pub snapshots: AtomicU64,
+    /// Snapshots that were older than every buffered diff and had to be refetched
+    pub stale_snapshots: AtomicU64,
+    /// Buffered diffs replayed on top of a snapshot
+    pub replayed_diffs: AtomicU64,
+    /// Diffs dropped as already covered by the snapshot or the book
+    pub dropped_diffs: AtomicU64,
+    /// Diffs discarded because the buffer was full while waiting for a snapshot
+    pub buffer_overflows: AtomicU64,
+}
+
+/// What the caller must do after `on_diff` / `on_snapshot`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncStep {
+    /// Apply these diffs to the book, in order
+    Apply(Vec<DepthUpdate>),
+    /// Nothing to apply (diff dropped, or buffered behind a pending snapshot)
+    Wait,
+    /// The book can't be trusted: load a REST snapshot into a cleared book
+    /// and hand its `lastUpdateId` to `on_snapshot`
+    NeedSnapshot,
+}
+
+#[derive(Debug)]
+enum SyncState {
+    /// No usable book; diffs are kept until a snapshot arrives
+    Buffering(Vec<DepthUpdate>),
+    /// Snapshot loaded; waiting for the diff that spans its `lastUpdateId`
+    Bridging(u64),
+    /// In sync; holds the `u` of the last applied diff
+    Live(u64),
+}
+
+/// Keeps a local book in step with the Binance diff depth stream
+///
+/// Implements Binance's documented procedure for a local order book:
+/// buffer the stream, fetch a REST snapshot, drop buffered diffs the
+/// snapshot already covers, then apply from the diff that spans the
+/// snapshot's `lastUpdateId`. After that every diff must follow the
+/// previous one (`pu` = last `u` on futures, `U` = last `u` + 1 on spot);
+/// any break invalidates the book and starts over from a new snapshot.
+///
+/// Fetching the snapshot is left to the caller, so this stays free of I/O.
+pub struct BookSync {
+    state: SyncState,
+    /// Diffs kept while waiting for a snapshot; older ones are dropped
+    max_buffer: usize,
+    metrics: Arc<BookSyncMetrics>,
+}
+
+impl BookSync {
+    /// Starts unsynced: the first diff asks for a snapshot
+    pub fn new(max_buffer: usize) -> Self {
+        Self {
+            state: SyncState::Buffering(Vec::new()),
+            max_buffer: max_buffer.max(1),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Shared handle to the sync counters
+    pub fn metrics(&self) -> Arc<BookSyncMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Whether diffs are currently being applied to the book
+    pub fn is_live(&self) -> bool {
+        matches!(self.state, SyncState::Live(_))
+    }
+
+    /// Forget the book (reconnect); the next diff asks for a snapshot
+    pub fn invalidate(&mut self) {
+        self.state = SyncState::Buffering(Vec::new());
+    }
+
+    /// Feed one diff from the stream
+    pub fn on_diff(&mut self, update: DepthUpdate) -> SyncStep {
+        match self.state {
+            SyncState::Buffering(ref mut buffer) => {
+                buffer.push(update);
+                if buffer.len() > self.max_buffer {
+                    buffer.remove(0);
+                    self.metrics.buffer_overflows.fetch_add(1, Ordering::Relaxed);
+                }
+                SyncStep::NeedSnapshot
+            }
+            SyncState::Bridging(snapshot_id) => {
+                if covered_by_snapshot(&update, snapshot_id) {
+                    self.metrics.dropped_diffs.fetch_add(1, Ordering::Relaxed);
+                    SyncStep::Wait
+                } else if spans_snapshot(&update, snapshot_id) {
+                    self.state = SyncState::Live(update.final_update_id);
+                    SyncStep::Apply(vec![update])
+                } else {
+                    // Diffs after the snapshot were missed: it is too old
+                    self.metrics.stale_snapshots.fetch_add(1, Ordering::Relaxed);
+                    self.state = SyncState::Buffering(vec![update]);
+                    SyncStep::NeedSnapshot
+                }
+            }
+            SyncState::Live(last_id) => {
+                if update.final_update_id <= last_id {
+                    self.metrics.dropped_diffs.fetch_add(1, Ordering::Relaxed);
+                    SyncStep::Wait
+                } else if follows(&update, last_id) {
+                    self.state = SyncState::Live(update.final_update_id);
+                    SyncStep::Apply(vec![update])
+                } else {
+                    self.state = SyncState::Buffering(vec![update]);
+                    SyncStep::NeedSnapshot
+                }
+            }
+        }
+    }
+
+    /// A snapshot with `last_update_id` was loaded into a cleared book
+    ///
+    /// Returns the buffered diffs to replay on top of it, or `NeedSnapshot`
+    /// if the snapshot predates every buffered diff.
+    pub fn on_snapshot(&mut self, last_update_id: u64) -> SyncStep {
+        self.metrics.snapshots.fetch_add(1, Ordering::Relaxed);
+        let buffered = match std::mem::replace(&mut self.state, SyncState::Bridging(last_update_id)) {
+            SyncState::Buffering(buffer) => buffer,
+            _ => Vec::new(),
+        };
+
+        let mut replay = Vec::new();
+        for update in buffered {
+            match self.on_diff(update) {
+                SyncStep::Apply(diffs) => replay.extend(diffs),
+                SyncStep::Wait => {}
+                SyncStep::NeedSnapshot => return SyncStep::NeedSnapshot,
+            }
+        }
+
+        self.metrics.replayed_diffs.fetch_add(replay.len() as u64, Ordering::Relaxed);
+        SyncStep::Apply(replay)
+    }
+}
+
+/// Everything in the diff is already in a snapshot taken at `snapshot_id`
+fn covered_by_snapshot(update: &DepthUpdate, snapshot_id: u64) -> bool {
+    match update.prev_final_update_id {
+        // Futures: drop u < lastUpdateId
+        Some(_) => update.final_update_id < snapshot_id,
+        // Spot: drop u <= lastUpdateId
+        None => update.final_update_id <= snapshot_id,
+    }
+}
+
+/// First diff to apply after a snapshot taken at `snapshot_id`
+fn spans_snapshot(update: &DepthUpdate, snapshot_id: u64) -> bool {
+    match update.prev_final_update_id {
+        // Futures: U <= lastUpdateId <= u
+        Some(_) => update.first_update_id <= snapshot_id && update.final_update_id >= snapshot_id,
+        // Spot: U <= lastUpdateId + 1 <= u
+        None => update.first_update_id <= snapshot_id + 1 && update.final_update_id > snapshot_id,
+    }
+}
+
+/// The diff directly continues from the last applied `u`
+fn follows(update: &DepthUpdate, last_id: u64) -> bool {
+    match update.prev_final_update_id {
+        Some(pu) => pu == last_id,
+        None => update.first_update_id == last_id + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(first: u64, last: u64, prev: u64) -> DepthUpdate {
+        DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: last,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            prev_final_update_id: Some(prev),
+            bids: vec![],
+            asks: vec![],
+        }
+    }
+
+    fn ids(step: SyncStep) -> Vec<u64> {
+        match step {
+            SyncStep::Apply(diffs) => diffs.iter().map(|d| d.final_update_id).collect(),
+            other => panic!("expected diffs to apply, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_replays_buffered_diffs() {
+        let mut sync = BookSync::new(100);
+        assert_eq!(sync.on_diff(diff(90, 100, 89)), SyncStep::NeedSnapshot);
+        assert_eq!(sync.on_diff(diff(101, 110, 100)), SyncStep::NeedSnapshot);
+        assert_eq!(sync.on_diff(diff(111, 120, 110)), SyncStep::NeedSnapshot);
+
+        // lastUpdateId 105: 100 is covered, 110 spans it, 120 follows
+        assert_eq!(ids(sync.on_snapshot(105)), vec![110, 120]);
+        assert!(sync.is_live());
+        assert_eq!(ids(sync.on_diff(diff(121, 130, 120))), vec![130]);
+        assert_eq!(sync.metrics().dropped_diffs.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_gap_then_recovery() {
+        let mut sync = BookSync::new(100);
+        sync.on_diff(diff(1, 10, 0));
+        assert_eq!(ids(sync.on_snapshot(5)), vec![10]);
+
+        // pu 15 != last u 10: diffs were lost
+        assert_eq!(sync.on_diff(diff(16, 20, 15)), SyncStep::NeedSnapshot);
+        assert!(!sync.is_live());
+        assert_eq!(sync.on_diff(diff(21, 30, 20)), SyncStep::NeedSnapshot);
+
+        // Snapshot at 25 recovers: 20 is dropped, 30 spans it
+        assert_eq!(ids(sync.on_snapshot(25)), vec![30]);
+        assert_eq!(ids(sync.on_diff(diff(31, 40, 30))), vec![40]);
+        assert_eq!(sync.metrics().snapshots.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_snapshot_older_than_buffer_is_refetched() {
+        let mut sync = BookSync::new(100);
+        sync.on_diff(diff(50, 60, 49));
+
+        // Snapshot at 40 misses 41..49
+        assert_eq!(sync.on_snapshot(40), SyncStep::NeedSnapshot);
+        assert_eq!(ids(sync.on_snapshot(55)), vec![60]);
+        assert_eq!(sync.metrics().stale_snapshots.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_empty_buffer_waits_for_spanning_diff() {
+        let mut sync = BookSync::new(100);
+        sync.on_diff(diff(1, 10, 0));
+        assert_eq!(ids(sync.on_snapshot(15)), Vec::<u64>::new());
+
+        assert_eq!(sync.on_diff(diff(11, 14, 10)), SyncStep::Wait);
+        assert_eq!(ids(sync.on_diff(diff(15, 18, 14))), vec![18]);
+    }
+
+    #[test]
+    fn test_spot_continuity_rules() {
+        let spot = |first, last| DepthUpdate { prev_final_update_id: None, ..diff(first, last, 0) };
+        let mut sync = BookSync::new(100);
+        sync.on_diff(spot(1, 10));
+
+        // Spot: drop u <= lastUpdateId, first applied has U <= id + 1 <= u
+        assert_eq!(ids(sync.on_snapshot(10)), Vec::<u64>::new());
+        assert_eq!(ids(sync.on_diff(spot(11, 12))), vec![12]);
+        assert_eq!(sync.on_diff(spot(14, 15)), SyncStep::NeedSnapshot);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::exchange::binance::{BinanceRestClient, FundingRate, Kline};
+use anyhow::{Result, bail};
+use serde::{Serialize, Deserialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Binance caps klines per request at 1500 and funding entries at 1000
+const KLINE_PAGE: usize = 1500;
+const FUNDING_PAGE: usize = 1000;
+
+/// Historical market context loaded at startup (`[context]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextConfig {
+    pub enabled: bool,
+    /// Days of klines and funding to pull
+    pub lookback_days: u32,
+    /// Kline interval ("1m", "5m", "1h", ...)
+    pub kline_interval: String,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lookback_days: 3,
+            kline_interval: "5m".to_string(),
+        }
+    }
+}
+
+/// Volatility, volume and funding over the lookback
+///
+/// Built from closed klines only, so a restart sees the same history a
+/// long-running process would have accumulated. `returns` is kept so
+/// rolling estimators can be warmed with it rather than starting empty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketContext {
+    pub symbol: String,
+    pub klines: usize,
+    pub funding_samples: usize,
+    /// Close-to-close log returns, oldest first
+    pub returns: Vec<f64>,
+    /// Standard deviation of `returns`, in bps per kline
+    pub volatility_bps: Option<f64>,
+    /// `volatility_bps` scaled to a year, as a fraction
+    pub annualized_volatility: Option<f64>,
+    /// Mean quote-asset volume per hour
+    pub quote_volume_per_hour: Option<f64>,
+    pub mean_funding_rate: Option<f64>,
+    pub last_funding_rate: Option<f64>,
+}
+
+impl MarketContext {
+    /// Summarize klines and funding; malformed entries are skipped
+    pub fn from_history(symbol: &str, klines: &[Kline], funding: &[FundingRate], interval_ms: u64) -> Self {
+        let closes: Vec<f64> = klines.iter().filter_map(|k| k.close.parse().ok()).collect();
+        let returns: Vec<f64> = closes.windows(2)
+            .filter(|pair| pair[0] > 0.0 && pair[1] > 0.0)
+            .map(|pair| (pair[1] / pair[0]).ln())
+            .collect();
+
+        let volatility = std_dev(&returns);
+        let per_year = (365.0 * 86_400_000.0 / interval_ms.max(1) as f64).sqrt();
+
+        let quote_volume: f64 = klines.iter().filter_map(|k| k.quote_volume.parse::<f64>().ok()).sum();
+        let hours = klines.len() as f64 * interval_ms as f64 / 3_600_000.0;
+
+        let rates: Vec<f64> = funding.iter().filter_map(|f| f.funding_rate.parse().ok()).collect();
+
+        Self {
+            symbol: symbol.to_string(),
+            klines: klines.len(),
+            funding_samples: rates.len(),
+            volatility_bps: volatility.map(|sd| sd * 10000.0),
+            annualized_volatility: volatility.map(|sd| sd * per_year),
+            quote_volume_per_hour: (hours > 0.0).then(|| quote_volume / hours),
+            mean_funding_rate: (!rates.is_empty()).then(|| rates.iter().sum::<f64>() / rates.len() as f64),
+            last_funding_rate: rates.last().copied(),
+            returns,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let show = |value: Option<f64>, scale: f64, precision: usize| {
+            value.map_or_else(|| "n/a".to_string(), |v| format!("{:.*}", precision, v * scale))
+        };
+        format!(
+            "{} klines: vol {} bps/kline ({}% annualized), {} quote/h | {} funding: mean {}%, last {}%",
+            self.klines,
+            show(self.volatility_bps, 1.0, 2),
+            show(self.annualized_volatility, 100.0, 1),
+            show(self.quote_volume_per_hour, 1.0, 0),
+            self.funding_samples,
+            show(self.mean_funding_rate, 100.0, 4),
+            show(self.last_funding_rate, 100.0, 4),
+        )
+    }
+}
+
+/// Kline interval in milliseconds (`"5m"`, `"1h"`, ...)
+pub fn interval_ms(interval: &str) -> Option<u64> {
+    let unit = interval.chars().last()?;
+    let count = &interval[..interval.len() - unit.len_utf8()];
+    let unit_ms = match unit {
+        'm' => 60_000,
+        'h' => 3_600_000,
+        'd' => 86_400_000,
+        'w' => 7 * 86_400_000,
+        _ => return None,
+    };
+    count.parse::<u64>().ok().filter(|n| *n > 0).map(|n| n * unit_ms)
+}
+
+/// Pull the last `lookback_days` of klines and funding for `symbol`
+///
+/// Pages forward from the start of the lookback; the still-open kline is
+/// dropped so volatility is only measured on complete bars.
+pub async fn load_market_context(
+    client: &BinanceRestClient,
+    config: &ContextConfig,
+    symbol: &str,
+) -> Result<MarketContext> {
+    let Some(interval) = interval_ms(&config.kline_interval) else {
+        bail!("unsupported kline interval: {}", config.kline_interval);
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let start = now.saturating_sub(config.lookback_days as u64 * 86_400_000);
+
+    let mut klines = Vec::new();
+    let mut from = start;
+    loop {
+        let page = client.get_klines(symbol, &config.kline_interval, from, KLINE_PAGE).await?;
+        let Some(last) = page.last() else { break };
+        from = last.close_time + 1;
+        let full = page.len() == KLINE_PAGE;
+        klines.extend(page);
+        if !full {
+            break;
+        }
+    }
+    klines.retain(|k| k.close_time < now);
+
+    let mut funding = Vec::new();
+    let mut from = start;
+    loop {
+        let page = client.get_funding_history(symbol, from, FUNDING_PAGE).await?;
+        let Some(last) = page.last() else { break };
+        from = last.funding_time + 1;
+        let full = page.len() == FUNDING_PAGE;
+        funding.extend(page);
+        if !full {
+            break;
+        }
+    }
+
+    Ok(MarketContext::from_history(symbol, &klines, &funding, interval))
+}
+
+fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(open_time: u64, close: &str, quote_volume: &str) -> Kline {
+        Kline {
+            open_time,
+            open: close.to_string(),
+            high: close.to_string(),
+            low: close.to_string(),
+            close: close.to_string(),
+            volume: "0".to_string(),
+            close_time: open_time + 299_999,
+            quote_volume: quote_volume.to_string(),
+            trades: 0,
+        }
+    }
+
+    #[test]
+    fn test_context_from_history() {
+        assert_eq!(interval_ms("5m"), Some(300_000));
+        assert_eq!(interval_ms("1h"), Some(3_600_000));
+        assert_eq!(interval_ms("0m"), None);
+        assert_eq!(interval_ms("5x"), None);
+
+        // Alternating +1% / -1% over 12 five-minute klines (one hour)
+        let closes = ["100", "101", "100", "101", "100", "101", "100", "101", "100", "101", "100", "101"];
+        let klines: Vec<Kline> = closes.iter().enumerate()
+            .map(|(i, close)| kline(i as u64 * 300_000, close, "1000"))
+            .collect();
+        let funding = vec![
+            FundingRate { symbol: "BTCUSDT".to_string(), funding_rate: "0.0001".to_string(), funding_time: 0 },
+            FundingRate { symbol: "BTCUSDT".to_string(), funding_rate: "0.0003".to_string(), funding_time: 1 },
+        ];
+
+        let context = MarketContext::from_history("BTCUSDT", &klines, &funding, 300_000);
+
+        assert_eq!(context.returns.len(), 11);
+        let vol = context.volatility_bps.unwrap();
+        assert!(vol > 95.0 && vol < 110.0, "vol {}", vol);
+        assert!((context.quote_volume_per_hour.unwrap() - 12_000.0).abs() < 1e-6);
+        assert!((context.mean_funding_rate.unwrap() - 0.0002).abs() < 1e-12);
+        assert_eq!(context.last_funding_rate, Some(0.0003));
+
+        let empty = MarketContext::from_history("BTCUSDT", &[], &[], 300_000);
+        assert_eq!(empty.volatility_bps, None);
+        assert_eq!(empty.quote_volume_per_hour, None);
+    }
+}
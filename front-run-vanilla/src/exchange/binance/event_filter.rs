@@ -0,0 +1,273 @@
+use crate::exchange::binance::websocket::{MarketEvent, TimedEvent};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Event filtering between the connector and consumers (`[exchange.event_filter]`)
+///
+/// Defaults pass every event through.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventFilterConfig {
+    /// Drop trades smaller than this (base asset, 0 = keep all)
+    pub min_trade_qty: f64,
+    /// Drop events whose update/trade ID was already delivered
+    pub dedupe: bool,
+    /// Deliver at most one event of each type per interval
+    pub throttle: ThrottleConfig,
+}
+
+/// Minimum gap between delivered events, per type (ms, 0 = unthrottled)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThrottleConfig {
+    pub depth_ms: u64,
+    pub trade_ms: u64,
+    pub book_ticker_ms: u64,
+    pub mark_price_ms: u64,
+}
+
+/// Type of a `MarketEvent`, for per-type rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Depth,
+    Trade,
+    BookTicker,
+    MarkPrice,
+    Liquidation,
+    /// Connected, Disconnected and BookCorrupted
+    Connection,
+    /// OrderUpdate and AccountUpdate
+    UserData,
+}
+
+impl EventKind {
+    pub fn of(event: &MarketEvent) -> Self {
+        match event {
+            MarketEvent::DepthUpdate(_) => Self::Depth,
+            MarketEvent::Trade(_) => Self::Trade,
+            MarketEvent::BookTicker(_) => Self::BookTicker,
+            MarketEvent::MarkPrice(_) => Self::MarkPrice,
+            MarketEvent::Liquidation(_) => Self::Liquidation,
+            MarketEvent::Connected | MarketEvent::Disconnected | MarketEvent::BookCorrupted(_) => Self::Connection,
+            MarketEvent::OrderUpdate(_) | MarketEvent::AccountUpdate(_) => Self::UserData,
+        }
+    }
+}
+
+/// One stage of an `EventPipeline`
+pub trait EventFilter: Send {
+    /// Whether `event` should reach consumers
+    fn accept(&mut self, event: &TimedEvent) -> bool;
+}
+
+/// Drops trades below a minimum quantity
+pub struct MinTradeQuantity(pub Decimal);
+
+impl EventFilter for MinTradeQuantity {
+    fn accept(&mut self, event: &TimedEvent) -> bool {
+        match &event.event {
+            MarketEvent::Trade(trade) => trade.quantity >= self.0,
+            _ => true,
+        }
+    }
+}
+
+/// Drops events at or below the latest ID already delivered for their type
+///
+/// Catches frames replayed around a reconnect. Mark price and liquidation
+/// updates carry no ID and are keyed by event time instead.
+#[derive(Default)]
+pub struct Dedupe {
+    last_ids: HashMap<EventKind, u64>,
+}
+
+impl EventFilter for Dedupe {
+    fn accept(&mut self, event: &TimedEvent) -> bool {
+        let id = match &event.event {
+            MarketEvent::DepthUpdate(update) => update.final_update_id,
+            MarketEvent::Trade(trade) => trade.id,
+            MarketEvent::BookTicker(ticker) => ticker.update_id,
+            MarketEvent::MarkPrice(mark) => mark.event_time,
+            MarketEvent::Liquidation(force) => force.event_time,
+            _ => return true,
+        };
+        let last = self.last_ids.entry(EventKind::of(&event.event)).or_insert(0);
+        if id <= *last {
+            return false;
+        }
+        *last = id;
+        true
+    }
+}
+
+/// Delivers at most one event of `kind` per `interval` of receive time
+pub struct Throttle {
+    kind: EventKind,
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Throttle {
+    pub fn new(kind: EventKind, interval: Duration) -> Self {
+        Self { kind, interval, last: None }
+    }
+}
+
+impl EventFilter for Throttle {
+    fn accept(&mut self, event: &TimedEvent) -> bool {
+        if EventKind::of(&event.event) != self.kind {
+            return true;
+        }
+        let now = event.received.instant;
+        if self.last.is_some_and(|last| now.duration_since(last) < self.interval) {
+            return false;
+        }
+        self.last = Some(now);
+        true
+    }
+}
+
+/// Counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct EventFilterMetrics {
+    pub passed: AtomicU64,
+    pub dropped: AtomicU64,
+}
+
+/// Ordered filter stages between a connector and its consumers
+///
+/// An event is delivered only if every stage accepts it; stages after the
+/// first rejection are not consulted. Connection and user data events always
+/// pass, so consumers never miss a disconnect, a book reset or a fill. The connector is
+/// unchanged: the book is still maintained from every depth diff, only the
+/// events consumers see are reduced.
+#[derive(Default)]
+pub struct EventPipeline {
+    filters: Vec<Box<dyn EventFilter>>,
+    metrics: Arc<EventFilterMetrics>,
+}
+
+impl EventPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pipeline for `config`, in the order quantity, dedupe, throttles
+    pub fn from_config(config: &EventFilterConfig) -> Self {
+        let mut pipeline = Self::new();
+        if config.min_trade_qty > 0.0 {
+            let min = Decimal::from_f64_retain(config.min_trade_qty).unwrap_or_default();
+            pipeline = pipeline.with(MinTradeQuantity(min));
+        }
+        if config.dedupe {
+            pipeline = pipeline.with(Dedupe::default());
+        }
+
+        let throttle = &config.throttle;
+        for (kind, ms) in [
+            (EventKind::Depth, throttle.depth_ms),
+            (EventKind::Trade, throttle.trade_ms),
+            (EventKind::BookTicker, throttle.book_ticker_ms),
+            (EventKind::MarkPrice, throttle.mark_price_ms),
+        ] {
+            if ms > 0 {
+                pipeline = pipeline.with(Throttle::new(kind, Duration::from_millis(ms)));
+            }
+        }
+        pipeline
+    }
+
+    /// Append a stage
+    pub fn with(mut self, filter: impl EventFilter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Shared handle to the pass/drop counters
+    pub fn metrics(&self) -> Arc<EventFilterMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Run `event` through every stage
+    pub fn accept(&mut self, event: &TimedEvent) -> bool {
+        let accepted = matches!(EventKind::of(&event.event), EventKind::Connection | EventKind::UserData)
+            || self.filters.iter_mut().all(|filter| filter.accept(event));
+
+        let counter = if accepted { &self.metrics.passed } else { &self.metrics.dropped };
+        counter.fetch_add(1, Ordering::Relaxed);
+        accepted
+    }
+
+    /// Filter `events` on a background task; returns the reduced stream
+    pub fn spawn(mut self, mut events: mpsc::UnboundedReceiver<TimedEvent>) -> mpsc::UnboundedReceiver<TimedEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if self.accept(&event) && tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::types::{Side, Trade};
+    use crate::exchange::binance::websocket::ReceiveTime;
+    use rust_decimal_macros::dec;
+    use std::time::UNIX_EPOCH;
+
+    fn trade(id: u64, quantity: Decimal, received: ReceiveTime) -> TimedEvent {
+        TimedEvent {
+            received,
+            event: MarketEvent::Trade(Trade {
+                id,
+                price: dec!(50000),
+                quantity,
+                side: Side::Buy,
+                timestamp: UNIX_EPOCH,
+                is_buyer_maker: false,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_stages() {
+        let mut pipeline = EventPipeline::from_config(&EventFilterConfig {
+            min_trade_qty: 0.01,
+            dedupe: true,
+            throttle: ThrottleConfig { trade_ms: 100, ..ThrottleConfig::default() },
+        });
+        let start = ReceiveTime::now();
+        let at = |ms: u64| ReceiveTime { instant: start.instant + Duration::from_millis(ms), ..start };
+
+        assert!(pipeline.accept(&trade(1, dec!(0.5), at(0))));
+        // Too small
+        assert!(!pipeline.accept(&trade(2, dec!(0.001), at(200))));
+        // Already delivered
+        assert!(!pipeline.accept(&trade(1, dec!(0.5), at(200))));
+        // Within the throttle interval of trade 1
+        assert!(!pipeline.accept(&trade(3, dec!(0.5), at(50))));
+        assert!(pipeline.accept(&trade(4, dec!(0.5), at(150))));
+
+        // Connection events bypass every stage
+        assert!(pipeline.accept(&TimedEvent { received: at(151), event: MarketEvent::Disconnected }));
+
+        let metrics = pipeline.metrics();
+        assert_eq!(metrics.passed.load(Ordering::Relaxed), 3);
+        assert_eq!(metrics.dropped.load(Ordering::Relaxed), 3);
+        assert!(EventPipeline::from_config(&EventFilterConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Feed latency gauges exposed for monitoring and the risk manager
+#[derive(Debug, Default)]
+pub struct FeedLatencyMetrics {
+    /// Latest one-way latency estimate (ms)
+    pub last_latency_ms: AtomicU64,
+    /// Rolling mean one-way latency estimate (ms)
+    pub mean_latency_ms: AtomicU64,
+    /// Minimum (receive - event_time) over the window; approximates clock
+    /// skew plus the best-case network delay (ms, may be negative)
+    pub skew_estimate_ms: AtomicI64,
+    /// Messages measured
+    pub samples: AtomicU64,
+}
+
+/// One-way latency from exchange `event_time` to local receipt
+///
+/// `received - event_time` mixes real delay with clock skew between our
+/// host and the exchange. The window minimum bounds the skew: a negative
+/// minimum proves the local clock is behind, so every sample is shifted up
+/// by it. With a non-negative minimum the raw difference is used as-is,
+/// which is an upper bound on delay if clocks are NTP-synced.
+pub struct FeedLatency {
+    raw: VecDeque<i64>,
+    window: usize,
+    metrics: Arc<FeedLatencyMetrics>,
+}
+
+impl FeedLatency {
+    pub fn new(window: usize) -> Self {
+        Self {
+            raw: VecDeque::with_capacity(window),
+            window,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Shared handle to the latency gauges
+    pub fn metrics(&self) -> Arc<FeedLatencyMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Record a message received now; returns its latency estimate (ms)
+    pub fn record(&mut self, event_time_ms: u64) -> u64 {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.record_at(event_time_ms, now_ms)
+    }
+
+    /// Record a message received at `received_ms` (wall clock)
+    pub fn record_at(&mut self, event_time_ms: u64, received_ms: u64) -> u64 {
+        self.raw.push_back(received_ms as i64 - event_time_ms as i64);
+        if self.raw.len() > self.window {
+            self.raw.pop_front();
+        }
+
+        let skew = self.skew_estimate_ms();
+        let correction = skew.min(0);
+        let latency = |raw: i64| (raw - correction).max(0) as u64;
+
+        let last = self.raw.back().map_or(0, |raw| latency(*raw));
+        let mean = self.raw.iter().map(|raw| latency(*raw)).sum::<u64>() / self.raw.len() as u64;
+
+        self.metrics.last_latency_ms.store(last, Ordering::Relaxed);
+        self.metrics.mean_latency_ms.store(mean, Ordering::Relaxed);
+        self.metrics.skew_estimate_ms.store(skew, Ordering::Relaxed);
+        self.metrics.samples.fetch_add(1, Ordering::Relaxed);
+        last
+    }
+
+    /// Minimum raw difference over the window (0 before any sample)
+    pub fn skew_estimate_ms(&self) -> i64 {
+        self.raw.iter().copied().min().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_with_synced_clock() {
+        let mut latency = FeedLatency::new(10);
+
+        assert_eq!(latency.record_at(1_000, 1_020), 20);
+        assert_eq!(latency.record_at(1_100, 1_160), 60);
+
+        let metrics = latency.metrics();
+        assert_eq!(metrics.mean_latency_ms.load(Ordering::Relaxed), 40);
+        assert_eq!(metrics.skew_estimate_ms.load(Ordering::Relaxed), 20);
+    }
+
+    #[test]
+    fn test_local_clock_behind_is_corrected() {
+        let mut latency = FeedLatency::new(10);
+
+        // Local clock 500ms behind: raw differences are negative
+        latency.record_at(10_000, 9_510);
+        let last = latency.record_at(10_100, 9_700);
+
+        assert_eq!(latency.skew_estimate_ms(), -490);
+        // Second message arrived 90ms later than the fastest one
+        assert_eq!(last, 90);
+    }
+}
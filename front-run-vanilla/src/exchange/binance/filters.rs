@@ -0,0 +1,159 @@
+use crate::data::Side;
+use anyhow::{Result, anyhow};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// Reason an order would be rejected by the symbol's exchange filters
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum FilterViolation {
+    #[error("Quantity {quantity} below minimum {min_qty}")]
+    BelowMinQty { quantity: Decimal, min_qty: Decimal },
+
+    #[error("Quantity {quantity} above maximum {max_qty}")]
+    AboveMaxQty { quantity: Decimal, max_qty: Decimal },
+
+    #[error("Notional {notional} below minimum {min_notional}")]
+    BelowMinNotional { notional: Decimal, min_notional: Decimal },
+}
+
+/// Order filters for one symbol, from an `exchangeInfo` response
+///
+/// Market orders are checked against `MARKET_LOT_SIZE` when the symbol
+/// has one, `LOT_SIZE` otherwise. A zero tick or step means no rounding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolFilters {
+    pub symbol: String,
+    pub tick_size: Decimal,
+    pub step_size: Decimal,
+    pub min_qty: Decimal,
+    pub max_qty: Option<Decimal>,
+    pub min_notional: Decimal,
+}
+
+impl SymbolFilters {
+    /// Extract `symbol`'s filters from an `exchangeInfo` JSON document
+    /// (spot or futures)
+    pub fn from_exchange_info(info: &serde_json::Value, symbol: &str) -> Result<Self> {
+        let entry = info.get("symbols")
+            .and_then(|symbols| symbols.as_array())
+            .and_then(|symbols| symbols.iter().find(|s| {
+                s.get("symbol").and_then(|name| name.as_str())
+                    .is_some_and(|name| name.eq_ignore_ascii_case(symbol))
+            }))
+            .ok_or_else(|| anyhow!("{} not found in exchangeInfo", symbol))?;
+
+        let filters = entry.get("filters")
+            .and_then(|filters| filters.as_array())
+            .ok_or_else(|| anyhow!("{} has no filters in exchangeInfo", symbol))?;
+
+        let filter = |filter_type: &str| {
+            filters.iter().find(|f| f.get("filterType").and_then(|t| t.as_str()) == Some(filter_type))
+        };
+        let field = |filter: Option<&serde_json::Value>, name: &str| -> Option<Decimal> {
+            filter?.get(name)?.as_str()?.parse().ok()
+        };
+
+        let lot_size = filter("LOT_SIZE");
+        // Spot reports a zero market step when LOT_SIZE applies instead
+        let market_lot_size = filter("MARKET_LOT_SIZE")
+            .filter(|f| field(Some(f), "stepSize").is_some_and(|step| !step.is_zero()));
+        let lot = market_lot_size.or(lot_size);
+
+        // Futures: MIN_NOTIONAL.notional; spot: NOTIONAL/MIN_NOTIONAL.minNotional
+        let min_notional = field(filter("MIN_NOTIONAL"), "notional")
+            .or_else(|| field(filter("MIN_NOTIONAL"), "minNotional"))
+            .or_else(|| field(filter("NOTIONAL"), "minNotional"))
+            .unwrap_or(Decimal::ZERO);
+
+        Ok(Self {
+            symbol: symbol.to_uppercase(),
+            tick_size: field(filter("PRICE_FILTER"), "tickSize").unwrap_or(Decimal::ZERO),
+            step_size: field(lot, "stepSize").unwrap_or(Decimal::ZERO),
+            min_qty: field(lot, "minQty").unwrap_or(Decimal::ZERO),
+            max_qty: field(lot, "maxQty").filter(|max| !max.is_zero()),
+            min_notional,
+        })
+    }
+
+    /// Load from a saved `exchangeInfo` response
+    pub fn load<P: AsRef<Path>>(path: P, symbol: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let info: serde_json::Value = serde_json::from_str(&contents)?;
+        Self::from_exchange_info(&info, symbol)
+    }
+
+    /// Round a price onto the tick grid, against us (buys up, sells down)
+    pub fn round_price(&self, side: Side, price: Decimal) -> Decimal {
+        let strategy = match side {
+            Side::Buy => RoundingStrategy::ToPositiveInfinity,
+            Side::Sell => RoundingStrategy::ToNegativeInfinity,
+        };
+        round_to(price, self.tick_size, strategy)
+    }
+
+    /// Round a quantity down onto the step grid
+    pub fn round_quantity(&self, quantity: Decimal) -> Decimal {
+        round_to(quantity, self.step_size, RoundingStrategy::ToZero)
+    }
+
+    /// Check an already rounded order against the size limits
+    pub fn check(&self, quantity: Decimal, price: Decimal) -> Result<(), FilterViolation> {
+        if quantity < self.min_qty || quantity.is_zero() {
+            return Err(FilterViolation::BelowMinQty { quantity, min_qty: self.min_qty });
+        }
+        if let Some(max_qty) = self.max_qty.filter(|max| quantity > *max) {
+            return Err(FilterViolation::AboveMaxQty { quantity, max_qty });
+        }
+        let notional = quantity * price;
+        if notional < self.min_notional {
+            return Err(FilterViolation::BelowMinNotional { notional, min_notional: self.min_notional });
+        }
+        Ok(())
+    }
+}
+
+fn round_to(value: Decimal, increment: Decimal, strategy: RoundingStrategy) -> Decimal {
+    if increment.is_zero() {
+        return value;
+    }
+    (value / increment).round_dp_with_strategy(0, strategy) * increment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const FUTURES_INFO: &str = r#"{"symbols": [{
+        "symbol": "BTCUSDT",
+        "filters": [
+            {"filterType": "PRICE_FILTER", "tickSize": "0.10", "minPrice": "556.80", "maxPrice": "4529764"},
+            {"filterType": "LOT_SIZE", "stepSize": "0.001", "minQty": "0.001", "maxQty": "1000"},
+            {"filterType": "MARKET_LOT_SIZE", "stepSize": "0.001", "minQty": "0.001", "maxQty": "120"},
+            {"filterType": "MIN_NOTIONAL", "notional": "100"}
+        ]
+    }]}"#;
+
+    #[test]
+    fn test_parse_and_round() {
+        let info: serde_json::Value = serde_json::from_str(FUTURES_INFO).unwrap();
+        let filters = SymbolFilters::from_exchange_info(&info, "btcusdt").unwrap();
+
+        assert_eq!(filters.tick_size, dec!(0.10));
+        assert_eq!(filters.max_qty, Some(dec!(120)));
+        assert_eq!(filters.min_notional, dec!(100));
+
+        assert_eq!(filters.round_price(Side::Buy, dec!(50000.03)), dec!(50000.10));
+        assert_eq!(filters.round_price(Side::Sell, dec!(50000.07)), dec!(50000.00));
+        assert_eq!(filters.round_quantity(dec!(0.0199)), dec!(0.019));
+
+        assert!(filters.check(dec!(0.019), dec!(50000)).is_ok());
+        assert!(matches!(
+            filters.check(dec!(0.001), dec!(50000)),
+            Err(FilterViolation::BelowMinNotional { .. })
+        ));
+        assert!(SymbolFilters::from_exchange_info(&info, "ETHUSDT").is_err());
+    }
+}
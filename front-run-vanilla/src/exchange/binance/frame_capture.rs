@@ -0,0 +1,152 @@
+use crate::storage::JsonlWriter;
+use anyhow::Result;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Raw WebSocket frame as received, with local receive time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedFrame {
+    pub received_ms: u64,
+    pub frame: String,
+}
+
+/// Ring buffer of the most recent raw WS frames
+///
+/// Costs one string copy per frame while nothing is wrong. When parsing
+/// fails or the book desyncs, the buffer is spilled to a JSONL file so the
+/// exact frames leading up to the failure can be replayed as a test case
+/// (`load_frames` + the normal message handler).
+pub struct FrameCapture {
+    capacity: usize,
+    dir: PathBuf,
+    frames: VecDeque<CapturedFrame>,
+    last_spill: Option<Instant>,
+    spills: u64,
+}
+
+impl FrameCapture {
+    /// Minimum time between spills, so a stream of bad frames
+    /// produces one file rather than thousands
+    const MIN_SPILL_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub fn new(capacity: usize, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            capacity,
+            dir: dir.into(),
+            frames: VecDeque::with_capacity(capacity),
+            last_spill: None,
+            spills: 0,
+        }
+    }
+
+    /// Remember a raw frame received now, evicting the oldest when full
+    pub fn record(&mut self, frame: &str) {
+        let received_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.record_at(frame, received_ms);
+    }
+
+    /// Remember a raw frame received at `received_ms` (wall clock)
+    pub fn record_at(&mut self, frame: &str, received_ms: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+
+        self.frames.push_back(CapturedFrame {
+            received_ms,
+            frame: frame.to_string(),
+        });
+    }
+
+    /// Write buffered frames to `{dir}/frames-{symbol}-{ms}-{reason}.jsonl`
+    ///
+    /// Returns None if there is nothing to write or a spill happened too
+    /// recently. The buffer is emptied so consecutive spills don't overlap.
+    pub fn spill(&mut self, symbol: &str, reason: &str) -> Result<Option<PathBuf>> {
+        if self.frames.is_empty() {
+            return Ok(None);
+        }
+        let now = Instant::now();
+        if let Some(last) = self.last_spill {
+            if now.duration_since(last) < Self::MIN_SPILL_INTERVAL {
+                return Ok(None);
+            }
+        }
+        self.last_spill = Some(now);
+
+        let first_ms = self.frames.front().map_or(0, |f| f.received_ms);
+        let reason: String = reason.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let path = self.dir.join(format!("frames-{}-{}-{}.jsonl", symbol.to_lowercase(), first_ms, reason));
+
+        let mut writer = JsonlWriter::open(&path)?;
+        for frame in self.frames.drain(..) {
+            writer.append(&frame)?;
+        }
+        writer.flush()?;
+
+        self.spills += 1;
+        Ok(Some(path))
+    }
+
+    /// Frames currently buffered
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Number of capture files written since start
+    pub fn spills(&self) -> u64 {
+        self.spills
+    }
+}
+
+/// Read a spilled capture back for replay
+pub fn load_frames<P: AsRef<Path>>(path: P) -> Result<Vec<CapturedFrame>> {
+    let contents = std::fs::read_to_string(path)?;
+    contents.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_spill_and_reload() {
+        let dir = std::env::temp_dir().join(format!("frame_capture_test_{}", std::process::id()));
+        let mut capture = FrameCapture::new(2, &dir);
+
+        capture.record("first");
+        capture.record("second");
+        capture.record("third");
+        assert_eq!(capture.len(), 2);
+
+        let path = capture.spill("BTCUSDT", "parse error").unwrap().unwrap();
+        assert!(capture.is_empty());
+        assert!(path.file_name().unwrap().to_string_lossy().ends_with("parse_error.jsonl"));
+
+        let frames: Vec<String> = load_frames(&path).unwrap().into_iter().map(|f| f.frame).collect();
+        assert_eq!(frames, vec!["second", "third"]);
+
+        // Rate limited: a second failure right away writes nothing
+        capture.record("fourth");
+        assert!(capture.spill("BTCUSDT", "parse error").unwrap().is_none());
+        assert_eq!(capture.spills(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
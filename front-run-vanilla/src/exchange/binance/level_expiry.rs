@@ -0,0 +1,174 @@
+use crate::data::{OrderBook, Side};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Counters exposed for monitoring book hygiene
+#[derive(Debug, Default)]
+pub struct LevelExpiryMetrics {
+    /// Levels found past their TTL in the most recent sweep
+    pub stale_level_count: AtomicUsize,
+    /// Levels removed by expiry since start
+    pub expired_levels_total: AtomicU64,
+    /// Full book refreshes (all tracked levels dropped)
+    pub full_refreshes: AtomicU64,
+}
+
+/// Time-to-live tracking for order book levels
+///
+/// The diff depth stream only tells us about levels that change. After a
+/// WS hiccup or a missed delete, a level can sit in the book forever and
+/// skew `calculate_imbalance`. Every level update refreshes its timestamp;
+/// levels not touched within `ttl` are deleted from the book (qty = 0, the
+/// same path a normal delete takes).
+pub struct LevelExpiry {
+    ttl: Duration,
+    bids: HashMap<Decimal, Instant>,
+    asks: HashMap<Decimal, Instant>,
+    last_sweep: Option<Instant>,
+    metrics: Arc<LevelExpiryMetrics>,
+}
+
+impl LevelExpiry {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            bids: HashMap::new(),
+            asks: HashMap::new(),
+            last_sweep: None,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Shared handle to the expiry counters
+    pub fn metrics(&self) -> Arc<LevelExpiryMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Record a level update (zero quantity = level deleted)
+    pub fn touch(&mut self, side: Side, price: Decimal, quantity: Decimal, now: Instant) {
+        let levels = self.levels_mut(side);
+        if quantity.is_zero() {
+            levels.remove(&price);
+        } else {
+            levels.insert(price, now);
+        }
+    }
+
+    /// Number of tracked levels not updated within the TTL
+    pub fn stale_level_count(&self, now: Instant) -> usize {
+        self.stale_levels(now).len()
+    }
+
+    /// Remove stale levels from the book
+    ///
+    /// Sweeps at most once per second; returns number of levels removed.
+    pub fn sweep(&mut self, orderbook: &OrderBook, now: Instant) -> Result<usize> {
+        if let Some(last) = self.last_sweep {
+            if now.duration_since(last) < Duration::from_secs(1) {
+                return Ok(0);
+            }
+        }
+        self.last_sweep = Some(now);
+
+        let stale = self.stale_levels(now);
+        self.metrics.stale_level_count.store(stale.len(), Ordering::Relaxed);
+
+        for (side, price) in &stale {
+            orderbook.update_level(*side, *price, Decimal::ZERO)?;
+            self.levels_mut(*side).remove(price);
+        }
+
+        self.metrics.expired_levels_total.fetch_add(stale.len() as u64, Ordering::Relaxed);
+        Ok(stale.len())
+    }
+
+    /// Drop every tracked level from the book
+    ///
+    /// Used after a reconnect, when deletes may have been missed for an
+    /// unknown span; the book rebuilds from the fresh stream.
+    pub fn full_refresh(&mut self, orderbook: &OrderBook) -> Result<usize> {
+        let mut removed = 0;
+        for (side, levels) in [(Side::Buy, &mut self.bids), (Side::Sell, &mut self.asks)] {
+            for price in levels.drain().map(|(price, _)| price) {
+                orderbook.update_level(side, price, Decimal::ZERO)?;
+                removed += 1;
+            }
+        }
+
+        self.metrics.stale_level_count.store(0, Ordering::Relaxed);
+        self.metrics.full_refreshes.fetch_add(1, Ordering::Relaxed);
+        Ok(removed)
+    }
+
+    fn stale_levels(&self, now: Instant) -> Vec<(Side, Decimal)> {
+        let expired = |seen: &Instant| now.duration_since(*seen) >= self.ttl;
+
+        self.bids.iter()
+            .filter(|(_, seen)| expired(seen))
+            .map(|(price, _)| (Side::Buy, *price))
+            .chain(
+                self.asks.iter()
+                    .filter(|(_, seen)| expired(seen))
+                    .map(|(price, _)| (Side::Sell, *price)),
+            )
+            .collect()
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut HashMap<Decimal, Instant> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_stale_levels_expire_from_book() {
+        let ob = OrderBook::new("BTCUSDT");
+        let mut expiry = LevelExpiry::new(Duration::from_secs(5));
+        let start = Instant::now();
+
+        for (side, price) in [(Side::Buy, dec!(100)), (Side::Buy, dec!(99)), (Side::Sell, dec!(101))] {
+            ob.update_level(side, price, dec!(1)).unwrap();
+            expiry.touch(side, price, dec!(1), start);
+        }
+
+        // Only the 100 bid keeps updating
+        let later = start + Duration::from_secs(6);
+        expiry.touch(Side::Buy, dec!(100), dec!(2), later);
+        assert_eq!(expiry.stale_level_count(later), 2);
+
+        assert_eq!(expiry.sweep(&ob, later).unwrap(), 2);
+        assert_eq!(ob.get_book_depth_count(), (1, 0));
+        assert_eq!(expiry.metrics().expired_levels_total.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_delete_untracks_and_full_refresh_clears() {
+        let ob = OrderBook::new("BTCUSDT");
+        let mut expiry = LevelExpiry::new(Duration::from_secs(5));
+        let now = Instant::now();
+
+        ob.update_level(Side::Buy, dec!(100), dec!(1)).unwrap();
+        expiry.touch(Side::Buy, dec!(100), dec!(1), now);
+        ob.update_level(Side::Sell, dec!(101), dec!(1)).unwrap();
+        expiry.touch(Side::Sell, dec!(101), dec!(1), now);
+        ob.update_level(Side::Sell, dec!(101), Decimal::ZERO).unwrap();
+        expiry.touch(Side::Sell, dec!(101), Decimal::ZERO, now);
+
+        assert_eq!(expiry.stale_level_count(now + Duration::from_secs(10)), 1);
+
+        assert_eq!(expiry.full_refresh(&ob).unwrap(), 1);
+        assert_eq!(ob.get_book_depth_count().0, 0);
+        assert_eq!(expiry.metrics().full_refreshes.load(Ordering::Relaxed), 1);
+    }
+}
@@ -2,7 +2,41 @@ pub mod types;
 pub mod websocket;
 pub mod rest;
 pub mod auth;
+pub mod level_expiry;
+pub mod frame_capture;
+pub mod feed_latency;
+pub mod sequencing;
+pub mod book_sync;
+pub mod streams;
+pub mod event_filter;
+pub mod multiplex;
+pub mod filters;
+pub mod preflight;
+pub mod context;
+pub mod reconciliation;
+pub mod user_data;
 
 pub use types::*;
-pub use websocket::{BinanceWebSocket, MarketEvent};
-pub use rest::BinanceRestClient;
+pub use websocket::{BinanceWebSocket, MarketEvent, ReceiveTime, TimedEvent, WsShutdown};
+pub use event_filter::{
+    Dedupe, EventFilter, EventFilterConfig, EventFilterMetrics, EventKind, EventPipeline,
+    MinTradeQuantity, Throttle, ThrottleConfig,
+};
+pub use rest::{BinanceRestClient, FuturesApi};
+pub use level_expiry::{LevelExpiry, LevelExpiryMetrics};
+pub use feed_latency::{FeedLatency, FeedLatencyMetrics};
+pub use frame_capture::{CapturedFrame, FrameCapture, load_frames};
+pub use streams::StreamConfig;
+pub use multiplex::{BookRegistry, MultiplexedWebSocket, SymbolEvent, plan_connections, MAX_STREAMS_PER_CONNECTION};
+pub use filters::{FilterViolation, SymbolFilters};
+pub use preflight::{
+    CheckStatus, PreflightCheck, PreflightConfig, PreflightReport, LIVE_RISK_ACK,
+    is_mainnet, record_live_run, require_live_opt_in, run_preflight,
+};
+pub use context::{ContextConfig, MarketContext, interval_ms, load_market_context};
+pub use reconciliation::{
+    IncomeSummary, LocalPnl, ReconciliationConfig, ReconciliationReport, fetch_income,
+};
+pub use user_data::{OrderFill, OrderFills, UserDataConfig, UserDataStream, parse_user_data};
+pub use sequencing::{SequenceCheck, SequenceMetrics, SequenceTracker, StreamMetrics};
+pub use book_sync::{BookSync, BookSyncMetrics, SyncStep};
@@ -0,0 +1,442 @@
+use crate::data::{OrderBook, Side};
+use crate::exchange::binance::sequencing::{SequenceCheck, SequenceTracker};
+use crate::exchange::binance::streams::StreamConfig;
+use crate::exchange::binance::types::{AggTrade, BookTicker, DepthUpdate, ForceOrder, MarkPriceUpdate};
+use crate::exchange::binance::websocket::{MarketEvent, ReceiveTime, WsShutdown};
+use crate::exchange::book_validation::{BookValidationMetrics, BookValidator};
+use crate::exchange::checksum;
+use crate::exchange::depth_limit::{DepthLimit, DepthLimitMetrics};
+use anyhow::{Result, anyhow};
+use dashmap::DashMap;
+use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn, error, debug};
+
+/// Binance cap on streams per WebSocket connection
+pub const MAX_STREAMS_PER_CONNECTION: usize = 1024;
+
+/// Streams per SUBSCRIBE request (keeps us well under the message rate limit)
+const SUBSCRIBE_BATCH: usize = 100;
+
+/// Market event tagged with the symbol it belongs to
+#[derive(Debug, Clone)]
+pub struct SymbolEvent {
+    /// Upper-case symbol, e.g. "BTCUSDT"
+    pub symbol: String,
+    pub received: ReceiveTime,
+    pub event: MarketEvent,
+}
+
+/// Per-symbol order books shared between the WS tasks and strategy code
+#[derive(Default)]
+pub struct BookRegistry {
+    books: DashMap<String, Arc<OrderBook>>,
+    validators: DashMap<String, Arc<BookValidator>>,
+}
+
+impl BookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Book for `symbol`, created on first use
+    pub fn register(&self, symbol: &str) -> Arc<OrderBook> {
+        let symbol = symbol.to_uppercase();
+        self.validators
+            .entry(symbol.clone())
+            .or_insert_with(|| Arc::new(BookValidator::new(&symbol)));
+        self.books
+            .entry(symbol.clone())
+            .or_insert_with(|| Arc::new(OrderBook::new(&symbol)))
+            .clone()
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<Arc<OrderBook>> {
+        self.books.get(&symbol.to_uppercase()).map(|book| Arc::clone(book.value()))
+    }
+
+    pub fn validator(&self, symbol: &str) -> Option<Arc<BookValidator>> {
+        self.validators.get(&symbol.to_uppercase()).map(|validator| Arc::clone(validator.value()))
+    }
+
+    /// Symbol mismatch and crossed book counters for `symbol`
+    pub fn validation_metrics(&self, symbol: &str) -> Option<Arc<BookValidationMetrics>> {
+        self.validator(symbol).map(|validator| validator.metrics())
+    }
+
+    pub fn symbols(&self) -> Vec<String> {
+        self.books.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.books.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+}
+
+/// Split symbols into connection groups so no connection exceeds `max_streams`
+///
+/// Symbols are never split across connections, so each book is fed by
+/// exactly one socket.
+pub fn plan_connections(symbols: &[String], streams_per_symbol: usize, max_streams: usize) -> Result<Vec<Vec<String>>> {
+    if streams_per_symbol == 0 || streams_per_symbol > max_streams {
+        return Err(anyhow!(
+            "{} streams per symbol does not fit the {} stream connection limit",
+            streams_per_symbol, max_streams,
+        ));
+    }
+
+    let per_connection = max_streams / streams_per_symbol;
+    Ok(symbols.chunks(per_connection).map(|chunk| chunk.to_vec()).collect())
+}
+
+/// Many symbols over a small pool of WebSocket connections
+///
+/// `BinanceWebSocket` opens one socket per symbol, which stops scaling
+/// beyond a handful of markets. Here symbols are packed into as few
+/// connections as the per-connection stream cap allows, subscribed with
+/// SUBSCRIBE requests (a 1024-stream URL is too long), and each message is
+/// routed by its stream name to the symbol's book in the `BookRegistry`.
+pub struct MultiplexedWebSocket {
+    ws_endpoint: String,
+    groups: Vec<Vec<String>>,
+    streams: StreamConfig,
+    registry: Arc<BookRegistry>,
+    depth_limit: Option<DepthLimit>,
+    event_tx: mpsc::UnboundedSender<SymbolEvent>,
+    shutdown: WsShutdown,
+}
+
+impl MultiplexedWebSocket {
+    /// Plan connections for `symbols` and register their books
+    ///
+    /// Fails if the pool would need more than `max_connections` sockets.
+    pub fn new(
+        symbols: &[String],
+        ws_endpoint: String,
+        streams: StreamConfig,
+        registry: Arc<BookRegistry>,
+        max_connections: usize,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<SymbolEvent>)> {
+        let streams_per_symbol = streams.stream_count()?;
+        let groups = plan_connections(symbols, streams_per_symbol, MAX_STREAMS_PER_CONNECTION)?;
+        if groups.len() > max_connections {
+            return Err(anyhow!(
+                "{} symbols need {} connections, limit is {}",
+                symbols.len(), groups.len(), max_connections,
+            ));
+        }
+
+        for symbol in symbols {
+            registry.register(symbol);
+        }
+
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        Ok((
+            Self {
+                ws_endpoint,
+                groups,
+                streams,
+                registry,
+                depth_limit: None,
+                event_tx,
+                shutdown: WsShutdown::new(),
+            },
+            event_rx,
+        ))
+    }
+
+    /// Keep only the best `max_levels` per side of every book
+    pub fn with_max_book_levels(mut self, max_levels: usize) -> Self {
+        self.depth_limit = Some(DepthLimit::new(max_levels));
+        self
+    }
+
+    /// Far-level eviction counters across all books (None without a cap)
+    pub fn depth_limit_metrics(&self) -> Option<Arc<DepthLimitMetrics>> {
+        self.depth_limit.as_ref().map(|limit| limit.metrics())
+    }
+
+    /// Number of WebSocket connections that will be opened
+    pub fn connection_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Handle that stops all connections
+    pub fn shutdown_handle(&self) -> WsShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Run every connection until shutdown, reconnecting each independently
+    pub async fn run(self) {
+        let this = Arc::new(self);
+        let mut tasks = Vec::new();
+
+        for (idx, group) in this.groups.iter().cloned().enumerate() {
+            let this = Arc::clone(&this);
+            tasks.push(tokio::spawn(async move { this.run_connection(idx, group).await }));
+        }
+
+        for task in tasks {
+            let _ = task.await;
+        }
+    }
+
+    async fn run_connection(&self, idx: usize, symbols: Vec<String>) {
+        let mut reconnect_delay = Duration::from_secs(1);
+        let max_reconnect_delay = Duration::from_secs(60);
+        let mut shutdown = self.shutdown.subscribe();
+
+        while !self.shutdown.is_requested() {
+            match self.connect_and_process(idx, &symbols).await {
+                Ok(_) => {
+                    info!("Multiplexed connection {} closed normally", idx);
+                    reconnect_delay = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    error!("Multiplexed connection {} error: {}. Reconnecting in {:?}", idx, e, reconnect_delay);
+                    self.broadcast(&symbols, MarketEvent::Disconnected);
+
+                    tokio::select! {
+                        _ = tokio::time::sleep(reconnect_delay) => {}
+                        _ = shutdown.changed() => {}
+                    }
+                    reconnect_delay = std::cmp::min(reconnect_delay * 2, max_reconnect_delay);
+                }
+            }
+        }
+    }
+
+    async fn connect_and_process(&self, idx: usize, symbols: &[String]) -> Result<()> {
+        let url = format!("{}/stream", self.ws_endpoint);
+        let (ws_stream, _) = connect_async(&url).await
+            .map_err(|e| anyhow!("WebSocket connection failed: {}", e))?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // Stale levels may survive a reconnect; rebuild every book from scratch
+        for symbol in symbols {
+            if let Some(book) = self.registry.get(symbol) {
+                checksum::clear_book(&book)?;
+            }
+        }
+
+        let mut names = Vec::new();
+        for symbol in symbols {
+            names.extend(self.streams.stream_names(symbol)?);
+        }
+        for (batch_id, batch) in names.chunks(SUBSCRIBE_BATCH).enumerate() {
+            let request = serde_json::json!({
+                "method": "SUBSCRIBE",
+                "params": batch,
+                "id": batch_id + 1,
+            });
+            write.send(Message::Text(request.to_string())).await
+                .map_err(|e| anyhow!("Failed to subscribe: {}", e))?;
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        info!("Multiplexed connection {}: {} symbols, {} streams", idx, symbols.len(), names.len());
+        self.broadcast(symbols, MarketEvent::Connected);
+
+        let mut sequencing: HashMap<String, SequenceTracker> = HashMap::new();
+        let mut shutdown = self.shutdown.subscribe();
+        let mut ping = tokio::time::interval(Duration::from_secs(30));
+        ping.tick().await;
+
+        loop {
+            if self.shutdown.is_requested() {
+                let _ = write.send(Message::Close(None)).await;
+                self.broadcast(symbols, MarketEvent::Disconnected);
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = shutdown.changed() => {}
+
+                _ = ping.tick() => {
+                    write.send(Message::Ping(vec![])).await
+                        .map_err(|e| anyhow!("Failed to send ping: {}", e))?;
+                }
+
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            if let Err(e) = self.process_message(&text, ReceiveTime::now(), &mut sequencing) {
+                                debug!("Error processing multiplexed message: {}", e);
+                            }
+                        }
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Err(e)) => return Err(anyhow!("WebSocket error: {}", e)),
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_message(
+        &self,
+        text: &str,
+        received: ReceiveTime,
+        sequencing: &mut HashMap<String, SequenceTracker>,
+    ) -> Result<()> {
+        #[derive(serde::Deserialize)]
+        struct StreamWrapper {
+            stream: String,
+            data: serde_json::Value,
+        }
+
+        // SUBSCRIBE acks ({"result":null,"id":1}) carry no stream
+        let wrapper: StreamWrapper = match serde_json::from_str(text) {
+            Ok(wrapper) => wrapper,
+            Err(_) => return Ok(()),
+        };
+
+        let (symbol, stream) = wrapper.stream.split_once('@')
+            .ok_or_else(|| anyhow!("Unexpected stream name {}", wrapper.stream))?;
+        let symbol = symbol.to_uppercase();
+
+        let event = if stream.starts_with("depth") {
+            let update: DepthUpdate = serde_json::from_value(wrapper.data)?;
+            let book = self.registry.get(&symbol)
+                .ok_or_else(|| anyhow!("No book registered for {}", symbol))?;
+            let validator = self.registry.validator(&symbol)
+                .ok_or_else(|| anyhow!("No book registered for {}", symbol))?;
+            validator.check_symbol(&update.symbol)?;
+
+            if self.streams.is_partial_depth() {
+                checksum::clear_book(&book)?;
+            } else {
+                let check = sequencing.entry(symbol.clone())
+                    .or_insert_with(|| SequenceTracker::new(0.0))
+                    .on_depth(update.first_update_id, update.final_update_id, update.prev_final_update_id, update.event_time);
+                match check {
+                    SequenceCheck::Gap { missed } => {
+                        warn!("{} depth gap: at least {} updates missed", symbol, missed);
+                    }
+                    SequenceCheck::Stale => {
+                        debug!("{} dropping stale depth update (u = {})", symbol, update.final_update_id);
+                        return Ok(());
+                    }
+                    SequenceCheck::InOrder | SequenceCheck::ResyncNeeded => {}
+                }
+            }
+
+            let (bids, asks) = (update.parse_bids(), update.parse_asks());
+            for (price, qty) in &bids {
+                book.update_level(Side::Buy, *price, *qty)?;
+            }
+            for (price, qty) in &asks {
+                book.update_level(Side::Sell, *price, *qty)?;
+            }
+            let uncrossed = validator.resolve_crossed(&book, &bids, &asks)?;
+            if uncrossed > 0 {
+                debug!("{} order book crossed; removed {} stale levels", symbol, uncrossed);
+            }
+            if let Some(limit) = &self.depth_limit {
+                limit.enforce(&book)?;
+            }
+            MarketEvent::DepthUpdate(update)
+        } else if stream.starts_with("aggTrade") {
+            let agg_trade: AggTrade = serde_json::from_value(wrapper.data)?;
+            match agg_trade.to_trade() {
+                Some(trade) => MarketEvent::Trade(trade),
+                None => return Ok(()),
+            }
+        } else if stream.starts_with("bookTicker") {
+            MarketEvent::BookTicker(serde_json::from_value::<BookTicker>(wrapper.data)?)
+        } else if stream.starts_with("markPrice") {
+            MarketEvent::MarkPrice(serde_json::from_value::<MarkPriceUpdate>(wrapper.data)?)
+        } else if stream.starts_with("forceOrder") {
+            MarketEvent::Liquidation(serde_json::from_value::<ForceOrder>(wrapper.data)?)
+        } else {
+            return Ok(());
+        };
+
+        let _ = self.event_tx.send(SymbolEvent { symbol, received, event });
+        Ok(())
+    }
+
+    fn broadcast(&self, symbols: &[String], event: MarketEvent) {
+        let received = ReceiveTime::now();
+        for symbol in symbols {
+            let _ = self.event_tx.send(SymbolEvent {
+                symbol: symbol.to_uppercase(),
+                received,
+                event: event.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("SYM{}USDT", i)).collect()
+    }
+
+    #[test]
+    fn test_plan_respects_stream_cap() {
+        // 2 streams per symbol -> 512 symbols per connection
+        let groups = plan_connections(&symbols(1000), 2, MAX_STREAMS_PER_CONNECTION).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].len(), 512);
+        assert_eq!(groups[1].len(), 488);
+
+        assert!(plan_connections(&symbols(1), 2000, MAX_STREAMS_PER_CONNECTION).is_err());
+    }
+
+    #[test]
+    fn test_routes_messages_to_symbol_books() {
+        let registry = Arc::new(BookRegistry::new());
+        let (ws, mut rx) = MultiplexedWebSocket::new(
+            &["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            "wss://fstream.binance.com".to_string(),
+            StreamConfig::default(),
+            Arc::clone(&registry),
+            1,
+        ).unwrap();
+        assert_eq!(ws.connection_count(), 1);
+
+        let text = r#"{
+            "stream": "ethusdt@depth@100ms",
+            "data": {"e": "depthUpdate", "E": 1, "s": "ETHUSDT", "U": 1, "u": 2,
+                     "b": [["2000.00", "1.0"]], "a": [["2000.50", "2.0"]]}
+        }"#;
+        ws.process_message(text, ReceiveTime::now(), &mut HashMap::new()).unwrap();
+
+        assert_eq!(registry.get("ETHUSDT").unwrap().get_book_depth_count(), (1, 1));
+        assert_eq!(registry.get("BTCUSDT").unwrap().get_book_depth_count(), (0, 0));
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.symbol, "ETHUSDT");
+        assert!(matches!(event.event, MarketEvent::DepthUpdate(_)));
+
+        // Subscription acks are ignored
+        ws.process_message(r#"{"result":null,"id":1}"#, ReceiveTime::now(), &mut HashMap::new()).unwrap();
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let result = MultiplexedWebSocket::new(
+            &symbols(600),
+            "wss://fstream.binance.com".to_string(),
+            StreamConfig::default(),
+            Arc::new(BookRegistry::new()),
+            1,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::exchange::binance::{BinanceRestClient, PositionRisk};
+use anyhow::{Result, bail};
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Value of `live_risk_ack` that stands in for `--i-understand-live-risk`
+pub const LIVE_RISK_ACK: &str = "I_UNDERSTAND_LIVE_RISK";
+
+/// Startup checks run before live trading (`[preflight]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreflightConfig {
+    pub enabled: bool,
+    /// Largest tolerated local/server clock difference (signed requests
+    /// are rejected outside the recvWindow)
+    pub max_clock_skew_ms: u64,
+    /// Available futures balance required to start
+    pub min_balance_usd: f64,
+    /// Leverage the symbol must be set to on the account (None = any)
+    pub expected_leverage: Option<u32>,
+    /// Spot API used for the key permission check (apiRestrictions is not
+    /// served by the futures API or the testnet; empty = skip)
+    pub spot_api_endpoint: String,
+    /// Set to `LIVE_RISK_ACK` to allow mainnet production trading without
+    /// the command-line flag
+    pub live_risk_ack: String,
+    /// Live runs on mainnet that get the notional cap below (0 = none)
+    pub first_runs: u32,
+    /// Largest entry notional during the first runs
+    pub first_run_max_notional_usd: f64,
+}
+
+impl Default for PreflightConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_clock_skew_ms: 1000,
+            min_balance_usd: 100.0,
+            expected_leverage: None,
+            spot_api_endpoint: "https://api.binance.com".to_string(),
+            live_risk_ack: String::new(),
+            first_runs: 3,
+            first_run_max_notional_usd: 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckStatus {
+    Pass,
+    /// Could not be verified; reported but does not abort
+    Skipped,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl PreflightCheck {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status, detail: detail.into() }
+    }
+
+    fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    fn fail(name: &str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+}
+
+/// Outcome of every pre-flight check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightReport {
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.status != CheckStatus::Fail)
+    }
+
+    /// One line per check, failures marked
+    pub fn lines(&self) -> Vec<String> {
+        self.checks.iter()
+            .map(|check| {
+                let mark = match check.status {
+                    CheckStatus::Pass => "✓",
+                    CheckStatus::Skipped => "–",
+                    CheckStatus::Fail => "✗",
+                };
+                format!("{} {:<16} {}", mark, check.name, check.detail)
+            })
+            .collect()
+    }
+}
+
+/// Verify the account and environment before trading `symbol`
+///
+/// Every check runs even after a failure so the report lists all problems
+/// at once. Request errors fail their check.
+pub async fn run_preflight(
+    client: &BinanceRestClient,
+    config: &PreflightConfig,
+    symbol: &str,
+    testnet: bool,
+    api_endpoint: &str,
+    ws_endpoint: &str,
+) -> PreflightReport {
+    let mut checks = vec![check_endpoints(testnet, api_endpoint, ws_endpoint)];
+
+    checks.push(match client.get_server_time().await {
+        Ok(server_ms) => check_clock_skew(server_ms, local_ms(), config.max_clock_skew_ms),
+        Err(e) => PreflightCheck::fail("clock_skew", format!("server time unavailable: {}", e)),
+    });
+
+    checks.push(if testnet || config.spot_api_endpoint.is_empty() {
+        PreflightCheck::new("api_permissions", CheckStatus::Skipped, "not available on this endpoint")
+    } else {
+        match client.get_api_restrictions(&config.spot_api_endpoint).await {
+            Ok(restrictions) => check_permissions(&restrictions),
+            Err(e) => PreflightCheck::fail("api_permissions", e.to_string()),
+        }
+    });
+
+    checks.push(match client.get_exchange_info().await {
+        Ok(info) => check_symbol(&info, symbol),
+        Err(e) => PreflightCheck::fail("symbol", e.to_string()),
+    });
+
+    checks.push(match (config.expected_leverage, client.get_position_risk(symbol).await) {
+        (None, _) => PreflightCheck::new("leverage", CheckStatus::Skipped, "no expected_leverage configured"),
+        (Some(expected), Ok(positions)) => check_leverage(&positions, expected),
+        (Some(_), Err(e)) => PreflightCheck::fail("leverage", e.to_string()),
+    });
+
+    checks.push(match client.get_account_info().await {
+        Ok(account) => check_balance(&account, config.min_balance_usd),
+        Err(e) => PreflightCheck::fail("balance", e.to_string()),
+    });
+
+    PreflightReport { checks }
+}
+
+fn local_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn is_testnet_url(url: &str) -> bool {
+    url.contains("binancefuture.com") || url.contains("testnet")
+}
+
+/// Orders would reach the real exchange
+pub fn is_mainnet(testnet: bool, api_endpoint: &str) -> bool {
+    !testnet && !is_testnet_url(api_endpoint)
+}
+
+/// Refuse mainnet production trading unless explicitly acknowledged
+///
+/// Guards against running a testing setup with a production config. The
+/// acknowledgement is `--i-understand-live-risk` or `live_risk_ack`.
+pub fn require_live_opt_in(
+    config: &PreflightConfig,
+    environment: &str,
+    testnet: bool,
+    api_endpoint: &str,
+    flag: bool,
+) -> Result<()> {
+    let acknowledged = flag || config.live_risk_ack == LIVE_RISK_ACK;
+    if environment == "production" && is_mainnet(testnet, api_endpoint) && !acknowledged {
+        bail!(
+            "{} is mainnet with environment = \"production\"; pass --i-understand-live-risk \
+             or set [preflight] live_risk_ack = \"{}\" to trade real funds",
+            api_endpoint, LIVE_RISK_ACK
+        );
+    }
+    Ok(())
+}
+
+/// Count this live run in `dir/live_runs` and return its number (1-based)
+pub fn record_live_run<P: AsRef<Path>>(dir: P) -> Result<u32> {
+    let path = dir.as_ref().join("live_runs");
+    let previous = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents.trim().parse::<u32>().unwrap_or(0),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(e.into()),
+    };
+    std::fs::create_dir_all(dir.as_ref())?;
+    std::fs::write(&path, (previous + 1).to_string())?;
+    Ok(previous + 1)
+}
+
+/// Testnet hosts are `*.binancefuture.com`; both endpoints must agree
+/// with the `testnet` flag
+pub fn check_endpoints(testnet: bool, api_endpoint: &str, ws_endpoint: &str) -> PreflightCheck {
+    let mismatched: Vec<&str> = [api_endpoint, ws_endpoint].into_iter()
+        .filter(|url| is_testnet_url(url) != testnet)
+        .collect();
+
+    if mismatched.is_empty() {
+        PreflightCheck::pass("endpoints", if testnet { "testnet" } else { "production" })
+    } else {
+        PreflightCheck::fail(
+            "endpoints",
+            format!("testnet = {} but {} {}", testnet, mismatched.join(", "),
+                if testnet { "is production" } else { "is testnet" }),
+        )
+    }
+}
+
+pub fn check_clock_skew(server_ms: u64, local_ms: u64, max_skew_ms: u64) -> PreflightCheck {
+    let skew = server_ms.abs_diff(local_ms);
+    let detail = format!("{}ms (max {}ms)", skew, max_skew_ms);
+    if skew <= max_skew_ms {
+        PreflightCheck::pass("clock_skew", detail)
+    } else {
+        PreflightCheck::fail("clock_skew", detail)
+    }
+}
+
+/// Key must allow futures and must not allow withdrawals
+pub fn check_permissions(restrictions: &serde_json::Value) -> PreflightCheck {
+    let flag = |name: &str| restrictions.get(name).and_then(|v| v.as_bool());
+
+    match (flag("enableFutures"), flag("enableWithdrawals")) {
+        (Some(true), Some(false)) => PreflightCheck::pass("api_permissions", "futures enabled, withdrawals disabled"),
+        (Some(true), Some(true)) => PreflightCheck::fail("api_permissions", "key can withdraw; create a trade-only key"),
+        (Some(false), _) => PreflightCheck::fail("api_permissions", "futures trading not enabled on key"),
+        _ => PreflightCheck::fail("api_permissions", "unrecognized apiRestrictions response"),
+    }
+}
+
+pub fn check_symbol(exchange_info: &serde_json::Value, symbol: &str) -> PreflightCheck {
+    let status = exchange_info.get("symbols")
+        .and_then(|symbols| symbols.as_array())
+        .and_then(|symbols| symbols.iter().find(|s| {
+            s.get("symbol").and_then(|name| name.as_str()) == Some(symbol)
+        }))
+        .and_then(|s| s.get("status"))
+        .and_then(|status| status.as_str());
+
+    match status {
+        Some("TRADING") => PreflightCheck::pass("symbol", format!("{} TRADING", symbol)),
+        Some(other) => PreflightCheck::fail("symbol", format!("{} status {}", symbol, other)),
+        None => PreflightCheck::fail("symbol", format!("{} not listed", symbol)),
+    }
+}
+
+pub fn check_leverage(positions: &[PositionRisk], expected: u32) -> PreflightCheck {
+    let leverages: Vec<&str> = positions.iter().map(|p| p.leverage.as_str()).collect();
+    let all_match = !leverages.is_empty()
+        && leverages.iter().all(|l| l.parse::<u32>().ok() == Some(expected));
+
+    if all_match {
+        PreflightCheck::pass("leverage", format!("{}x", expected))
+    } else {
+        PreflightCheck::fail("leverage", format!("expected {}x, account has {:?}", expected, leverages))
+    }
+}
+
+pub fn check_balance(account: &serde_json::Value, min_balance_usd: f64) -> PreflightCheck {
+    let available = account.get("availableBalance")
+        .and_then(|balance| balance.as_str())
+        .and_then(|balance| balance.parse::<f64>().ok());
+
+    match available {
+        Some(balance) if balance >= min_balance_usd => {
+            PreflightCheck::pass("balance", format!("{:.2} available (min {:.2})", balance, min_balance_usd))
+        }
+        Some(balance) => {
+            PreflightCheck::fail("balance", format!("{:.2} available, below min {:.2}", balance, min_balance_usd))
+        }
+        None => PreflightCheck::fail("balance", "availableBalance missing from account info"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_consistency() {
+        assert_eq!(
+            check_endpoints(false, "https://fapi.binance.com", "wss://fstream.binance.com").status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            check_endpoints(true, "https://testnet.binancefuture.com", "wss://fstream.binance.com").status,
+            CheckStatus::Fail
+        );
+        assert_eq!(
+            check_endpoints(false, "https://testnet.binancefuture.com", "wss://stream.binancefuture.com").status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_account_checks() {
+        let withdraw_key = serde_json::json!({"enableFutures": true, "enableWithdrawals": true});
+        assert_eq!(check_permissions(&withdraw_key).status, CheckStatus::Fail);
+
+        let info = serde_json::json!({"symbols": [{"symbol": "BTCUSDT", "status": "SETTLING"}]});
+        assert_eq!(check_symbol(&info, "BTCUSDT").status, CheckStatus::Fail);
+        assert_eq!(check_symbol(&info, "ETHUSDT").status, CheckStatus::Fail);
+
+        let account = serde_json::json!({"availableBalance": "250.5"});
+        assert_eq!(check_balance(&account, 100.0).status, CheckStatus::Pass);
+        assert_eq!(check_balance(&account, 500.0).status, CheckStatus::Fail);
+
+        assert_eq!(check_clock_skew(10_000, 11_500, 1000).status, CheckStatus::Fail);
+
+        let report = PreflightReport {
+            checks: vec![
+                PreflightCheck::pass("a", ""),
+                PreflightCheck::new("b", CheckStatus::Skipped, ""),
+            ],
+        };
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn test_live_opt_in_and_run_counter() {
+        let mainnet = "https://fapi.binance.com";
+        let mut config = PreflightConfig::default();
+
+        assert!(require_live_opt_in(&config, "production", false, mainnet, false).is_err());
+        assert!(require_live_opt_in(&config, "production", false, mainnet, true).is_ok());
+        assert!(require_live_opt_in(&config, "production", false, "https://testnet.binancefuture.com", false).is_ok());
+        assert!(require_live_opt_in(&config, "paper", false, mainnet, false).is_ok());
+
+        config.live_risk_ack = LIVE_RISK_ACK.to_string();
+        assert!(require_live_opt_in(&config, "production", false, mainnet, false).is_ok());
+
+        let dir = std::env::temp_dir().join(format!("preflight_test_{}", std::process::id()));
+        assert_eq!(record_live_run(&dir).unwrap(), 1);
+        assert_eq!(record_live_run(&dir).unwrap(), 2);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,195 @@
+use crate::exchange::binance::{BinanceRestClient, IncomeRecord};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+
+/// Binance caps income entries per request at 1000
+const INCOME_PAGE: usize = 1000;
+
+/// Periodic comparison of local PnL with the exchange's income history
+/// (`[reconciliation]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconciliationConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    /// Gap in the settlement asset reported as a mismatch
+    pub tolerance: f64,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 3600,
+            tolerance: 0.01,
+        }
+    }
+}
+
+/// What the local model booked, in the settlement asset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LocalPnl {
+    /// Realized PnL before fees (the exchange's REALIZED_PNL)
+    pub realized_pnl: Decimal,
+    /// Fees on every fill, including entries of open positions
+    pub fees: Decimal,
+}
+
+/// Income history summed by type, signed as the exchange reports it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncomeSummary {
+    pub records: usize,
+    pub realized_pnl: Decimal,
+    /// Negative: fees paid
+    pub commission: Decimal,
+    pub funding: Decimal,
+    /// Other income types in the settlement asset (insurance clear, ...)
+    pub other: BTreeMap<String, Decimal>,
+    /// Entries in other assets, e.g. fees paid in BNB
+    pub other_assets: BTreeMap<String, Decimal>,
+}
+
+impl IncomeSummary {
+    /// Sum `records` for `symbol`; amounts in assets other than `asset`
+    /// are kept apart since they cannot be added
+    pub fn from_records(records: &[IncomeRecord], symbol: &str, asset: &str) -> Self {
+        let mut summary = Self::default();
+        for record in records.iter().filter(|r| r.symbol.eq_ignore_ascii_case(symbol)) {
+            let Ok(amount) = record.income.parse::<Decimal>() else {
+                continue;
+            };
+            summary.records += 1;
+            if !record.asset.eq_ignore_ascii_case(asset) {
+                *summary.other_assets.entry(record.asset.to_uppercase()).or_default() += amount;
+                continue;
+            }
+            match record.income_type.as_str() {
+                "REALIZED_PNL" => summary.realized_pnl += amount,
+                "COMMISSION" => summary.commission += amount,
+                "FUNDING_FEE" => summary.funding += amount,
+                other => *summary.other.entry(other.to_string()).or_default() += amount,
+            }
+        }
+        summary
+    }
+
+    /// Everything that moved the balance in the settlement asset
+    pub fn net(&self) -> Decimal {
+        self.realized_pnl + self.commission + self.funding + self.other.values().sum::<Decimal>()
+    }
+}
+
+/// Local books against the exchange's for one account and window
+///
+/// Gaps are exchange minus local: a negative `fee_gap` means more was paid
+/// than modelled (fee tier, taker instead of maker); funding and `other`
+/// are costs the local model does not book at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub account: String,
+    pub symbol: String,
+    pub asset: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub local: LocalPnl,
+    pub exchange: IncomeSummary,
+}
+
+impl ReconciliationReport {
+    pub fn pnl_gap(&self) -> Decimal {
+        self.exchange.realized_pnl - self.local.realized_pnl
+    }
+
+    pub fn fee_gap(&self) -> Decimal {
+        self.exchange.commission + self.local.fees
+    }
+
+    /// Balance change the exchange reports minus what was booked locally
+    pub fn net_gap(&self) -> Decimal {
+        self.exchange.net() - (self.local.realized_pnl - self.local.fees)
+    }
+
+    /// PnL and fees agree within `tolerance`; funding alone is not a mismatch
+    pub fn is_reconciled(&self, tolerance: Decimal) -> bool {
+        self.pnl_gap().abs() <= tolerance && self.fee_gap().abs() <= tolerance
+    }
+
+    /// e.g. "realized 12.5 vs 12.5 local | fees -4.1 vs 4 local (gap -0.1) | funding -0.8 | net gap -0.9 USDT"
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "realized {} vs {} local | fees {} vs {} local (gap {}) | funding {} | net gap {} {}",
+            self.exchange.realized_pnl, self.local.realized_pnl,
+            self.exchange.commission, self.local.fees, self.fee_gap(),
+            self.exchange.funding, self.net_gap(), self.asset,
+        );
+        for (income_type, amount) in &self.exchange.other {
+            summary.push_str(&format!(" | {} {}", income_type.to_lowercase(), amount));
+        }
+        for (asset, amount) in &self.exchange.other_assets {
+            summary.push_str(&format!(" | {} {} (not in net)", amount, asset));
+        }
+        summary
+    }
+}
+
+/// Income for `symbol` over `[start_ms, end_ms]`, paging past Binance's
+/// per-request cap
+pub async fn fetch_income(
+    client: &BinanceRestClient,
+    symbol: &str,
+    start_ms: u64,
+    end_ms: u64,
+) -> Result<Vec<IncomeRecord>> {
+    let mut records = Vec::new();
+    let mut from = start_ms;
+    loop {
+        let page = client.get_income_history(symbol, from, end_ms, INCOME_PAGE).await?;
+        let full = page.len() == INCOME_PAGE;
+        let last_time = page.last().map(|record| record.time);
+        records.extend(page);
+        match last_time {
+            Some(time) if full && time < end_ms => from = time + 1,
+            _ => return Ok(records),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_funding_and_fee_tier_surface_as_gaps() {
+        let records: Vec<IncomeRecord> = serde_json::from_str(r#"[
+            {"symbol":"BTCUSDT","incomeType":"COMMISSION","income":"-0.40","asset":"USDT","time":1},
+            {"symbol":"BTCUSDT","incomeType":"REALIZED_PNL","income":"10.0","asset":"USDT","time":2},
+            {"symbol":"BTCUSDT","incomeType":"COMMISSION","income":"-0.44","asset":"USDT","time":2},
+            {"symbol":"BTCUSDT","incomeType":"FUNDING_FEE","income":"-1.25","asset":"USDT","time":3},
+            {"symbol":"BTCUSDT","incomeType":"COMMISSION","income":"-0.001","asset":"BNB","time":4},
+            {"symbol":"ETHUSDT","incomeType":"REALIZED_PNL","income":"99","asset":"USDT","time":5},
+            {"symbol":"","incomeType":"TRANSFER","income":"500","asset":"USDT","time":6}
+        ]"#).unwrap();
+
+        let report = ReconciliationReport {
+            account: "primary".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            asset: "USDT".to_string(),
+            start_ms: 0,
+            end_ms: 10,
+            local: LocalPnl { realized_pnl: dec!(10), fees: dec!(0.8) },
+            exchange: IncomeSummary::from_records(&records, "BTCUSDT", "USDT"),
+        };
+
+        assert_eq!(report.exchange.records, 5);
+        assert_eq!(report.pnl_gap(), dec!(0));
+        assert_eq!(report.fee_gap(), dec!(-0.04));
+        assert_eq!(report.net_gap(), dec!(-1.29));
+        assert_eq!(report.exchange.other_assets["BNB"], dec!(-0.001));
+        assert!(report.is_reconciled(dec!(0.05)));
+        assert!(!report.is_reconciled(dec!(0.01)));
+        assert!(report.summary().contains("funding -1.25"));
+    }
+}
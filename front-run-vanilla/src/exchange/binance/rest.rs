@@ -1,17 +1,62 @@
-use crate::data::{Side, Order, OrderType};
-use crate::exchange::binance::{auth, types::OrderResponse};
+use crate::data::Side;
+#[cfg(feature = "chaos")]
+use crate::utils::chaos::FaultInjector;
+use crate::exchange::binance::{
+    auth,
+    types::{DepthSnapshot, FundingRate, IncomeRecord, Kline, ListenKeyResponse, OrderResponse, PositionRisk},
+};
+use crate::risk::Instrument;
+use crate::strategy::OcoLeg;
 use anyhow::{Result, anyhow};
 use reqwest::Client;
 use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
 use std::time::Duration;
 use tracing::{info, error};
 
+/// Which Binance futures API a client talks to
+///
+/// The two share request formats; they differ in path prefix and in
+/// what quantities mean (base asset vs. whole contracts).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FuturesApi {
+    /// USDⓈ-M (`/fapi`), linear contracts
+    #[default]
+    UsdM,
+    /// COIN-M (`/dapi`), inverse perpetual and delivery contracts
+    CoinM,
+}
+
+impl FuturesApi {
+    /// The API that trades `instrument`
+    pub fn for_instrument(instrument: &Instrument) -> Self {
+        if instrument.inverse {
+            Self::CoinM
+        } else {
+            Self::UsdM
+        }
+    }
+
+    /// Path of `endpoint` (e.g. "order") at the version this API serves it
+    pub fn path(&self, endpoint: &str) -> String {
+        match self {
+            Self::UsdM if matches!(endpoint, "account" | "positionRisk") => format!("/fapi/v2/{}", endpoint),
+            Self::UsdM => format!("/fapi/v1/{}", endpoint),
+            Self::CoinM => format!("/dapi/v1/{}", endpoint),
+        }
+    }
+}
+
 /// Binance Futures REST API client
 pub struct BinanceRestClient {
     client: Client,
     api_key: String,
     secret_key: String,
     base_url: String,
+    api: FuturesApi,
+    #[cfg(feature = "chaos")]
+    faults: Option<FaultInjector>,
 }
 
 impl BinanceRestClient {
@@ -27,9 +72,43 @@ impl BinanceRestClient {
             api_key,
             secret_key,
             base_url,
+            api: FuturesApi::UsdM,
+            #[cfg(feature = "chaos")]
+            faults: None,
+        }
+    }
+
+    /// Talk to COIN-M (`/dapi`) instead of USDⓈ-M; `base_url` must match
+    /// (e.g. https://dapi.binance.com)
+    pub fn with_api(mut self, api: FuturesApi) -> Self {
+        self.api = api;
+        self
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}{}", self.base_url, self.api.path(endpoint))
+    }
+
+    /// Inject latency and rejects into order requests (chaos drills)
+    #[cfg(feature = "chaos")]
+    pub fn with_faults(mut self, faults: FaultInjector) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    #[cfg(feature = "chaos")]
+    async fn inject_faults(&self) -> Result<()> {
+        match &self.faults {
+            Some(faults) => faults.before_request().await,
+            None => Ok(()),
         }
     }
 
+    #[cfg(not(feature = "chaos"))]
+    async fn inject_faults(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Place a market order
     /// 
     /// CRITICAL: This is the execution path with strict latency requirements
@@ -44,14 +123,76 @@ impl BinanceRestClient {
             Side::Sell => "SELL",
         };
 
+        let quantity = quantity.to_string();
+        let params = vec![
+            ("symbol", symbol),
+            ("side", side_str),
+            ("type", "MARKET"),
+            ("quantity", &quantity),
+            // Exchange-side backstop: expire our resting order, not this one
+            ("selfTradePreventionMode", "EXPIRE_MAKER"),
+        ];
+
+        self.execute_signed_request("order", &params).await
+    }
+
+    /// Place a reduce-only market order (exempt from MIN_NOTIONAL, so it
+    /// can close residual positions a plain order could not)
+    pub async fn place_reduce_only_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        quantity: Decimal,
+    ) -> Result<OrderResponse> {
+        let side_str = match side {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        };
+
+        let quantity = quantity.to_string();
         let params = vec![
             ("symbol", symbol),
             ("side", side_str),
             ("type", "MARKET"),
-            ("quantity", &quantity.to_string()),
+            ("quantity", &quantity),
+            ("reduceOnly", "true"),
         ];
 
-        self.execute_signed_request("/fapi/v1/order", &params).await
+        self.execute_signed_request("order", &params).await
+    }
+
+    /// Place a reduce-only trigger that closes at market once the mark
+    /// price reaches `stop_price` (`TAKE_PROFIT_MARKET` / `STOP_MARKET`)
+    pub async fn place_exit_trigger(
+        &self,
+        symbol: &str,
+        side: Side,
+        leg: OcoLeg,
+        stop_price: Decimal,
+        quantity: Decimal,
+    ) -> Result<OrderResponse> {
+        let side_str = match side {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        };
+        let order_type = match leg {
+            OcoLeg::TakeProfit => "TAKE_PROFIT_MARKET",
+            OcoLeg::StopLoss => "STOP_MARKET",
+        };
+
+        let stop_price = stop_price.to_string();
+        let quantity = quantity.to_string();
+        let params = vec![
+            ("symbol", symbol),
+            ("side", side_str),
+            ("type", order_type),
+            ("stopPrice", &stop_price),
+            ("quantity", &quantity),
+            ("reduceOnly", "true"),
+            ("workingType", "MARK_PRICE"),
+        ];
+
+        self.execute_signed_request("order", &params).await
     }
 
     /// Place a limit order
@@ -67,27 +208,33 @@ impl BinanceRestClient {
             Side::Sell => "SELL",
         };
 
+        let price = price.to_string();
+        let quantity = quantity.to_string();
         let params = vec![
             ("symbol", symbol),
             ("side", side_str),
             ("type", "LIMIT"),
             ("timeInForce", "GTC"),  // Good-Till-Cancel
-            ("price", &price.to_string()),
-            ("quantity", &quantity.to_string()),
+            ("price", &price),
+            ("quantity", &quantity),
+            ("selfTradePreventionMode", "EXPIRE_MAKER"),
         ];
 
-        self.execute_signed_request("/fapi/v1/order", &params).await
+        self.execute_signed_request("order", &params).await
     }
 
     /// Cancel an order
     pub async fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<OrderResponse> {
+        self.inject_faults().await?;
+
+        let order_id = order_id.to_string();
         let params = vec![
             ("symbol", symbol),
-            ("orderId", &order_id.to_string()),
+            ("orderId", &order_id),
         ];
 
         let query_string = auth::build_signed_query(&params, &self.secret_key);
-        let url = format!("{}/fapi/v1/order?{}", self.base_url, query_string);
+        let url = format!("{}?{}", self.url("order"), query_string);
 
         let response = self.client
             .delete(&url)
@@ -104,11 +251,37 @@ impl BinanceRestClient {
         Ok(order_response)
     }
 
+    /// Query an order's status and fills
+    pub async fn get_order(&self, symbol: &str, order_id: u64) -> Result<OrderResponse> {
+        let order_id = order_id.to_string();
+        let params = vec![
+            ("symbol", symbol),
+            ("orderId", &order_id),
+        ];
+
+        let query_string = auth::build_signed_query(&params, &self.secret_key);
+        let url = format!("{}?{}", self.url("order"), query_string);
+
+        let response = self.client
+            .get(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get order failed: {}", error_text));
+        }
+
+        let order_response = response.json::<OrderResponse>().await?;
+        Ok(order_response)
+    }
+
     /// Get account information
     pub async fn get_account_info(&self) -> Result<serde_json::Value> {
         let params = vec![];
         let query_string = auth::build_signed_query(&params, &self.secret_key);
-        let url = format!("{}/fapi/v2/account?{}", self.base_url, query_string);
+        let url = format!("{}?{}", self.url("account"), query_string);
 
         let response = self.client
             .get(&url)
@@ -125,14 +298,213 @@ impl BinanceRestClient {
         Ok(info)
     }
 
+    /// Query open position for a symbol
+    pub async fn get_position_risk(&self, symbol: &str) -> Result<Vec<PositionRisk>> {
+        let params = vec![("symbol", symbol)];
+        let query_string = auth::build_signed_query(&params, &self.secret_key);
+        let url = format!("{}?{}", self.url("positionRisk"), query_string);
+
+        let response = self.client
+            .get(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get position risk failed: {}", error_text));
+        }
+
+        let positions = response.json::<Vec<PositionRisk>>().await?;
+        Ok(positions)
+    }
+
+    /// Exchange trading rules and symbol status
+    pub async fn get_exchange_info(&self) -> Result<serde_json::Value> {
+        let url = self.url("exchangeInfo");
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get exchange info failed: {}", error_text));
+        }
+
+        Ok(response.json::<serde_json::Value>().await?)
+    }
+
+    /// Current mark price of `symbol` (public endpoint)
+    pub async fn get_mark_price(&self, symbol: &str) -> Result<Decimal> {
+        let url = format!("{}?symbol={}", self.url("premiumIndex"), symbol);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get mark price failed: {}", error_text));
+        }
+
+        // COIN-M answers with an array (one entry per contract of the pair)
+        let body = response.json::<serde_json::Value>().await?;
+        let entry = if body.is_array() { &body[0] } else { &body };
+        entry["markPrice"].as_str()
+            .and_then(|price| price.parse::<Decimal>().ok())
+            .ok_or_else(|| anyhow!("No markPrice in premium index for {}", symbol))
+    }
+
+    /// Order book snapshot with the top `limit` levels per side
+    ///
+    /// Public endpoint; Binance accepts limits of 5, 10, 20, 50, 100, 500
+    /// and 1000.
+    pub async fn get_depth_snapshot(&self, symbol: &str, limit: usize) -> Result<DepthSnapshot> {
+        let url = format!("{}?symbol={}&limit={}", self.url("depth"), symbol, limit);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get depth snapshot failed: {}", error_text));
+        }
+
+        Ok(response.json::<DepthSnapshot>().await?)
+    }
+
+    /// Candlesticks opening at or after `start_ms`, oldest first
+    ///
+    /// Public endpoint; Binance returns at most 1500 per request.
+    pub async fn get_klines(&self, symbol: &str, interval: &str, start_ms: u64, limit: usize) -> Result<Vec<Kline>> {
+        let url = format!(
+            "{}?symbol={}&interval={}&startTime={}&limit={}",
+            self.url("klines"), symbol, interval, start_ms, limit
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get klines failed: {}", error_text));
+        }
+
+        Ok(response.json::<Vec<Kline>>().await?)
+    }
+
+    /// Funding rates settled at or after `start_ms`, oldest first
+    ///
+    /// Public endpoint; Binance returns at most 1000 per request.
+    pub async fn get_funding_history(&self, symbol: &str, start_ms: u64, limit: usize) -> Result<Vec<FundingRate>> {
+        let url = format!(
+            "{}?symbol={}&startTime={}&limit={}",
+            self.url("fundingRate"), symbol, start_ms, limit
+        );
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get funding history failed: {}", error_text));
+        }
+
+        Ok(response.json::<Vec<FundingRate>>().await?)
+    }
+
+    /// Account income for `symbol` between `start_ms` and `end_ms`, oldest first
+    ///
+    /// Binance returns at most 1000 per request.
+    pub async fn get_income_history(
+        &self,
+        symbol: &str,
+        start_ms: u64,
+        end_ms: u64,
+        limit: usize,
+    ) -> Result<Vec<IncomeRecord>> {
+        let (start, end, limit) = (start_ms.to_string(), end_ms.to_string(), limit.to_string());
+        let params = vec![
+            ("symbol", symbol),
+            ("startTime", start.as_str()),
+            ("endTime", end.as_str()),
+            ("limit", limit.as_str()),
+        ];
+        let query_string = auth::build_signed_query(&params, &self.secret_key);
+        let url = format!("{}?{}", self.url("income"), query_string);
+
+        let response = self.client
+            .get(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get income history failed: {}", error_text));
+        }
+
+        Ok(response.json::<Vec<IncomeRecord>>().await?)
+    }
+
+    /// API key restrictions (`enableFutures`, `enableWithdrawals`, ...)
+    ///
+    /// Served by the spot API only, so the spot base URL is passed in.
+    pub async fn get_api_restrictions(&self, spot_base_url: &str) -> Result<serde_json::Value> {
+        let params = vec![];
+        let query_string = auth::build_signed_query(&params, &self.secret_key);
+        let url = format!("{}/sapi/v1/account/apiRestrictions?{}", spot_base_url, query_string);
+
+        let response = self.client
+            .get(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("Get API restrictions failed: {}", error_text));
+        }
+
+        Ok(response.json::<serde_json::Value>().await?)
+    }
+
+    /// Start a user data stream, returning its listen key
+    pub async fn start_user_data_stream(&self) -> Result<String> {
+        let response = self.user_data_stream_request(reqwest::Method::POST).await?;
+        let listen_key = response.json::<ListenKeyResponse>().await?;
+        Ok(listen_key.listen_key)
+    }
+
+    /// Keep the user data stream alive (must be called within 60 minutes)
+    pub async fn keepalive_user_data_stream(&self) -> Result<()> {
+        self.user_data_stream_request(reqwest::Method::PUT).await?;
+        Ok(())
+    }
+
+    /// Close the user data stream
+    pub async fn close_user_data_stream(&self) -> Result<()> {
+        self.user_data_stream_request(reqwest::Method::DELETE).await?;
+        Ok(())
+    }
+
+    /// Listen key endpoints are API-key authenticated, not signed
+    async fn user_data_stream_request(&self, method: reqwest::Method) -> Result<reqwest::Response> {
+        let url = self.url("listenKey");
+
+        let response = self.client
+            .request(method.clone(), &url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("User data stream {} failed: {}", method, error_text));
+        }
+
+        Ok(response)
+    }
+
     /// Execute signed POST request
     async fn execute_signed_request(
         &self,
         endpoint: &str,
         params: &[(&str, &str)],
     ) -> Result<OrderResponse> {
+        self.inject_faults().await?;
+
         let query_string = auth::build_signed_query(params, &self.secret_key);
-        let url = format!("{}{}", self.base_url, endpoint);
+        let url = self.url(endpoint);
 
         info!("Executing order: {} with params: {}", url, query_string);
 
@@ -159,7 +531,7 @@ impl BinanceRestClient {
 
     /// Test connectivity to Binance API
     pub async fn test_connectivity(&self) -> Result<()> {
-        let url = format!("{}/fapi/v1/ping", self.base_url);
+        let url = self.url("ping");
         let response = self.client.get(&url).send().await?;
 
         if response.status().is_success() {
@@ -171,7 +543,7 @@ impl BinanceRestClient {
 
     /// Get exchange time (useful for time sync)
     pub async fn get_server_time(&self) -> Result<u64> {
-        let url = format!("{}/fapi/v1/time", self.base_url);
+        let url = self.url("time");
         let response = self.client.get(&url).send().await?;
 
         #[derive(serde::Deserialize)]
@@ -201,6 +573,18 @@ mod tests {
         assert_eq!(client.base_url, "https://testnet.binancefuture.com");
     }
 
+    #[test]
+    fn test_api_paths() {
+        assert_eq!(FuturesApi::UsdM.path("order"), "/fapi/v1/order");
+        assert_eq!(FuturesApi::UsdM.path("positionRisk"), "/fapi/v2/positionRisk");
+        assert_eq!(FuturesApi::CoinM.path("positionRisk"), "/dapi/v1/positionRisk");
+
+        let inverse = Instrument::inverse(Decimal::from(100));
+        let client = BinanceRestClient::new(String::new(), String::new(), "https://dapi.binance.com".to_string())
+            .with_api(FuturesApi::for_instrument(&inverse));
+        assert_eq!(client.url("order"), "https://dapi.binance.com/dapi/v1/order");
+    }
+
     // Note: Integration tests with real API should be in tests/ directory
     // and require valid credentials
 }
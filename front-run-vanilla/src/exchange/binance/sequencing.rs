@@ -0,0 +1,282 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters for a single stream
+#[derive(Debug, Default)]
+pub struct StreamMetrics {
+    /// Messages seen
+    pub messages: AtomicU64,
+    /// Messages whose ID did not follow the previous one
+    pub discontinuities: AtomicU64,
+    /// IDs skipped across discontinuities (lower bound)
+    pub missed_updates: AtomicU64,
+    /// Largest event_time gap between consecutive messages (ms)
+    pub max_gap_ms: AtomicU64,
+}
+
+impl StreamMetrics {
+    fn record(&self, gap_ms: Option<u64>, missed: Option<u64>) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        if let Some(gap) = gap_ms {
+            self.max_gap_ms.fetch_max(gap, Ordering::Relaxed);
+        }
+        if let Some(missed) = missed {
+            self.discontinuities.fetch_add(1, Ordering::Relaxed);
+            self.missed_updates.fetch_add(missed, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Sequencing counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct SequenceMetrics {
+    pub depth: StreamMetrics,
+    pub trades: StreamMetrics,
+    /// Resyncs triggered by the gap rate threshold
+    pub resyncs: AtomicU64,
+    /// Depth diffs dropped as duplicate or older than the book
+    pub stale_depth_updates: AtomicU64,
+}
+
+/// Result of checking a depth update's sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    InOrder,
+    /// Update IDs skipped; `missed` is a lower bound on lost updates
+    Gap { missed: u64 },
+    /// Gap rate crossed the threshold; the book can no longer be trusted
+    ResyncNeeded,
+    /// Already applied or older than what was applied; must be dropped
+    Stale,
+}
+
+/// Last seen position in a stream
+#[derive(Debug, Default)]
+struct StreamPosition {
+    last_id: Option<u64>,
+    last_event_time: Option<u64>,
+}
+
+impl StreamPosition {
+    fn gap_ms(&mut self, event_time: u64) -> Option<u64> {
+        let gap = self.last_event_time.map(|last| event_time.saturating_sub(last));
+        self.last_event_time = Some(event_time);
+        gap
+    }
+}
+
+/// Tracks event_time gaps and update_id continuity per stream
+///
+/// Depth diffs are only valid applied in order: futures streams carry the
+/// previous update's final ID (`pu`), spot streams must start at the last
+/// final ID + 1. A dropped diff leaves the book silently wrong, so beyond
+/// counting gaps the tracker can ask for a resync once too many of the
+/// recent updates were discontinuous.
+///
+/// The highest final ID applied to the book survives `reset`, so diffs
+/// replayed or delivered late around a reconnect are reported as stale
+/// instead of re-applying old levels over newer state.
+pub struct SequenceTracker {
+    depth: StreamPosition,
+    trades: StreamPosition,
+    /// Highest depth `u` accepted so far
+    applied_final_id: Option<u64>,
+
+    /// Discontinuity flags for the last `window` depth updates
+    recent: VecDeque<bool>,
+    window: usize,
+    /// Fraction of recent updates that may be gaps before resync (0 = never)
+    resync_gap_rate: f64,
+
+    metrics: Arc<SequenceMetrics>,
+}
+
+impl SequenceTracker {
+    pub fn new(resync_gap_rate: f64) -> Self {
+        Self {
+            depth: StreamPosition::default(),
+            trades: StreamPosition::default(),
+            applied_final_id: None,
+            recent: VecDeque::new(),
+            window: 100,
+            resync_gap_rate,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Shared handle to the sequencing counters
+    pub fn metrics(&self) -> Arc<SequenceMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Check a depth diff (`U`, `u`, optional futures `pu`, `E`)
+    pub fn on_depth(
+        &mut self,
+        first_update_id: u64,
+        final_update_id: u64,
+        prev_final_update_id: Option<u64>,
+        event_time: u64,
+    ) -> SequenceCheck {
+        // Binance update IDs only increase, so anything at or below the
+        // last applied u is a duplicate or arrived out of order
+        if self.applied_final_id.is_some_and(|applied| final_update_id <= applied) {
+            self.metrics.stale_depth_updates.fetch_add(1, Ordering::Relaxed);
+            return SequenceCheck::Stale;
+        }
+        self.applied_final_id = Some(final_update_id);
+
+        let gap_ms = self.depth.gap_ms(event_time);
+
+        let missed = match self.depth.last_id {
+            None => None,
+            Some(last) => match prev_final_update_id {
+                // Futures: pu must equal our last u; the count is unknown,
+                // so report the ID distance as a lower bound
+                Some(pu) if pu != last => Some(pu.saturating_sub(last).max(1)),
+                Some(_) => None,
+                // Spot: U must not skip past last u + 1
+                None if first_update_id > last + 1 => Some(first_update_id - last - 1),
+                None => None,
+            },
+        };
+        self.depth.last_id = Some(final_update_id);
+
+        self.metrics.depth.record(gap_ms, missed);
+
+        self.recent.push_back(missed.is_some());
+        if self.recent.len() > self.window {
+            self.recent.pop_front();
+        }
+
+        match missed {
+            None => SequenceCheck::InOrder,
+            Some(missed) if !self.gap_rate_exceeded() => SequenceCheck::Gap { missed },
+            Some(_) => {
+                self.metrics.resyncs.fetch_add(1, Ordering::Relaxed);
+                self.reset();
+                SequenceCheck::ResyncNeeded
+            }
+        }
+    }
+
+    /// Check an aggTrade (`a`, `E`); returns trades missed, if any
+    pub fn on_trade(&mut self, agg_trade_id: u64, event_time: u64) -> Option<u64> {
+        let gap_ms = self.trades.gap_ms(event_time);
+
+        let missed = match self.trades.last_id {
+            Some(last) if agg_trade_id > last + 1 => Some(agg_trade_id - last - 1),
+            _ => None,
+        };
+        if self.trades.last_id.is_none_or(|last| agg_trade_id > last) {
+            self.trades.last_id = Some(agg_trade_id);
+        }
+
+        self.metrics.trades.record(gap_ms, missed);
+        missed
+    }
+
+    /// Share of the recent depth updates that were discontinuous
+    pub fn gap_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        self.recent.iter().filter(|gap| **gap).count() as f64 / self.recent.len() as f64
+    }
+
+    /// Forget stream positions (after reconnect or resync)
+    ///
+    /// The applied high-water mark is kept: update IDs carry across
+    /// connections, so it still identifies stale diffs.
+    pub fn reset(&mut self) {
+        self.depth = StreamPosition::default();
+        self.trades = StreamPosition::default();
+        self.recent.clear();
+    }
+
+    fn gap_rate_exceeded(&self) -> bool {
+        // Require a reasonably full window so one early gap can't trip it
+        self.resync_gap_rate > 0.0
+            && self.recent.len() * 2 >= self.window
+            && self.gap_rate() > self.resync_gap_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spot_and_futures_continuity() {
+        let mut tracker = SequenceTracker::new(0.0);
+        let metrics = tracker.metrics();
+
+        // Spot style: U = last u + 1
+        assert_eq!(tracker.on_depth(1, 5, None, 1_000), SequenceCheck::InOrder);
+        assert_eq!(tracker.on_depth(6, 9, None, 1_100), SequenceCheck::InOrder);
+        assert_eq!(tracker.on_depth(13, 15, None, 1_600), SequenceCheck::Gap { missed: 3 });
+
+        // Futures style: pu = last u
+        tracker.reset();
+        assert_eq!(tracker.on_depth(100, 110, Some(90), 2_000), SequenceCheck::InOrder);
+        assert_eq!(tracker.on_depth(111, 120, Some(110), 2_100), SequenceCheck::InOrder);
+        assert!(matches!(tracker.on_depth(131, 140, Some(125), 2_200), SequenceCheck::Gap { .. }));
+
+        assert_eq!(metrics.depth.messages.load(Ordering::Relaxed), 6);
+        assert_eq!(metrics.depth.discontinuities.load(Ordering::Relaxed), 2);
+        assert_eq!(metrics.depth.max_gap_ms.load(Ordering::Relaxed), 500);
+    }
+
+    #[test]
+    fn test_stale_diffs_dropped_across_reset() {
+        let mut tracker = SequenceTracker::new(0.0);
+        let metrics = tracker.metrics();
+
+        assert_eq!(tracker.on_depth(100, 110, Some(90), 1_000), SequenceCheck::InOrder);
+        assert_eq!(tracker.on_depth(111, 120, Some(110), 1_100), SequenceCheck::InOrder);
+
+        // Duplicate and late diffs
+        assert_eq!(tracker.on_depth(111, 120, Some(110), 1_100), SequenceCheck::Stale);
+        assert_eq!(tracker.on_depth(100, 110, Some(90), 1_000), SequenceCheck::Stale);
+
+        // Reconnect: a replay of an old diff is still stale, newer ones apply
+        tracker.reset();
+        assert_eq!(tracker.on_depth(105, 115, Some(104), 1_200), SequenceCheck::Stale);
+        assert_eq!(tracker.on_depth(121, 130, Some(120), 1_300), SequenceCheck::InOrder);
+
+        assert_eq!(metrics.depth.messages.load(Ordering::Relaxed), 3);
+        assert_eq!(metrics.stale_depth_updates.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_trade_gaps() {
+        let mut tracker = SequenceTracker::new(0.0);
+
+        assert_eq!(tracker.on_trade(10, 1_000), None);
+        assert_eq!(tracker.on_trade(11, 1_050), None);
+        assert_eq!(tracker.on_trade(15, 1_100), Some(3));
+
+        let metrics = tracker.metrics();
+        assert_eq!(metrics.trades.missed_updates.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_resync_when_gap_rate_exceeded() {
+        let mut tracker = SequenceTracker::new(0.1);
+        let mut id = 0;
+        let mut resync = false;
+
+        for i in 0..100u64 {
+            // Every 5th update skips ahead: 20% gap rate
+            id += if i % 5 == 4 { 10 } else { 1 };
+            if tracker.on_depth(id, id, None, i * 100) == SequenceCheck::ResyncNeeded {
+                resync = true;
+                break;
+            }
+        }
+
+        assert!(resync);
+        assert_eq!(tracker.metrics().resyncs.load(Ordering::Relaxed), 1);
+        assert_eq!(tracker.gap_rate(), 0.0);
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::{Result, anyhow};
+use serde::{Serialize, Deserialize};
+
+/// Market data streams to subscribe to (`[exchange.streams]`)
+///
+/// Defaults reproduce the original `@depth@100ms` + `@aggTrade` pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamConfig {
+    /// Depth update speed: 100, 250 or 500 ms
+    pub depth_speed_ms: u64,
+    /// 0 = diff depth; 5, 10 or 20 = partial book snapshots of that many levels
+    pub depth_levels: usize,
+    /// Aggregated trades (required by the flow detector)
+    pub agg_trade: bool,
+    /// Best bid/ask on every change (the live trader checks exits on each)
+    pub book_ticker: bool,
+    /// Mark price and funding rate, every second
+    pub mark_price: bool,
+    /// Liquidation orders, at most one per second (the liquidation detector)
+    pub force_order: bool,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            depth_speed_ms: 100,
+            depth_levels: 0,
+            agg_trade: true,
+            book_ticker: false,
+            mark_price: false,
+            force_order: false,
+        }
+    }
+}
+
+impl StreamConfig {
+    /// Whether depth arrives as full top-N snapshots rather than diffs
+    pub fn is_partial_depth(&self) -> bool {
+        self.depth_levels > 0
+    }
+
+    /// Streams subscribed per symbol
+    pub fn stream_count(&self) -> Result<usize> {
+        Ok(self.stream_names("")?.len())
+    }
+
+    /// Stream names for `symbol`, e.g. `btcusdt@depth@100ms`
+    pub fn stream_names(&self, symbol: &str) -> Result<Vec<String>> {
+        let symbol = symbol.to_lowercase();
+
+        // 250ms is the venue default and takes no suffix
+        let speed = match self.depth_speed_ms {
+            100 | 500 => format!("@{}ms", self.depth_speed_ms),
+            250 => String::new(),
+            other => return Err(anyhow!("Unsupported depth speed {}ms (use 100, 250 or 500)", other)),
+        };
+        let levels = match self.depth_levels {
+            0 => String::new(),
+            5 | 10 | 20 => self.depth_levels.to_string(),
+            other => return Err(anyhow!("Unsupported partial depth {} levels (use 5, 10 or 20)", other)),
+        };
+
+        let mut streams = vec![format!("{}@depth{}{}", symbol, levels, speed)];
+        if self.agg_trade {
+            streams.push(format!("{}@aggTrade", symbol));
+        }
+        if self.book_ticker {
+            streams.push(format!("{}@bookTicker", symbol));
+        }
+        if self.mark_price {
+            streams.push(format!("{}@markPrice@1s", symbol));
+        }
+        if self.force_order {
+            streams.push(format!("{}@forceOrder", symbol));
+        }
+        Ok(streams)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_streams_match_original_pair() {
+        let streams = StreamConfig::default().stream_names("BTCUSDT").unwrap();
+        assert_eq!(streams, vec!["btcusdt@depth@100ms", "btcusdt@aggTrade"]);
+    }
+
+    #[test]
+    fn test_custom_stream_set() {
+        let config = StreamConfig {
+            depth_speed_ms: 250,
+            depth_levels: 20,
+            agg_trade: false,
+            book_ticker: true,
+            mark_price: true,
+            force_order: true,
+        };
+        let streams = config.stream_names("ETHUSDT").unwrap();
+        assert_eq!(
+            streams,
+            vec!["ethusdt@depth20", "ethusdt@bookTicker", "ethusdt@markPrice@1s", "ethusdt@forceOrder"]
+        );
+
+        let invalid = StreamConfig { depth_speed_ms: 300, ..StreamConfig::default() };
+        assert!(invalid.stream_names("ETHUSDT").is_err());
+    }
+}
@@ -1,8 +1,9 @@
 use rust_decimal::Decimal;
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
 
 /// Binance WebSocket depth update message
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct DepthUpdate {
     #[serde(rename = "e")]
     pub event_type: String,  // "depthUpdate"
@@ -18,6 +19,9 @@ pub struct DepthUpdate {
     
     #[serde(rename = "u")]
     pub final_update_id: u64,
+
+    #[serde(rename = "pu", default)]
+    pub prev_final_update_id: Option<u64>,  // Futures only: previous update's "u"
     
     #[serde(rename = "b")]
     pub bids: Vec<[String; 2]>,  // [["price", "quantity"], ...]
@@ -29,27 +33,51 @@ pub struct DepthUpdate {
 impl DepthUpdate {
     /// Parse bid levels into Decimal tuples
     pub fn parse_bids(&self) -> Vec<(Decimal, Decimal)> {
-        self.bids.iter()
-            .filter_map(|level| {
-                let price = level[0].parse::<Decimal>().ok()?;
-                let qty = level[1].parse::<Decimal>().ok()?;
-                Some((price, qty))
-            })
-            .collect()
+        parse_levels(&self.bids)
     }
 
     /// Parse ask levels into Decimal tuples
     pub fn parse_asks(&self) -> Vec<(Decimal, Decimal)> {
-        self.asks.iter()
-            .filter_map(|level| {
-                let price = level[0].parse::<Decimal>().ok()?;
-                let qty = level[1].parse::<Decimal>().ok()?;
-                Some((price, qty))
-            })
-            .collect()
+        parse_levels(&self.asks)
     }
 }
 
+/// REST order book snapshot (`GET /fapi/v1/depth`)
+///
+/// Starting point for the diff stream: diffs are replayed on top of it
+/// from `last_update_id` on (see `BookSync`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DepthSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    pub last_update_id: u64,
+
+    pub bids: Vec<[String; 2]>,  // [["price", "quantity"], ...]
+
+    pub asks: Vec<[String; 2]>,  // [["price", "quantity"], ...]
+}
+
+impl DepthSnapshot {
+    /// Parse bid levels into Decimal tuples
+    pub fn parse_bids(&self) -> Vec<(Decimal, Decimal)> {
+        parse_levels(&self.bids)
+    }
+
+    /// Parse ask levels into Decimal tuples
+    pub fn parse_asks(&self) -> Vec<(Decimal, Decimal)> {
+        parse_levels(&self.asks)
+    }
+}
+
+fn parse_levels(levels: &[[String; 2]]) -> Vec<(Decimal, Decimal)> {
+    levels.iter()
+        .filter_map(|level| {
+            let price = level[0].parse::<Decimal>().ok()?;
+            let qty = level[1].parse::<Decimal>().ok()?;
+            Some((price, qty))
+        })
+        .collect()
+}
+
 /// Binance aggregated trade message
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AggTrade {
@@ -88,7 +116,7 @@ impl AggTrade {
     /// Convert to our Trade type
     pub fn to_trade(&self) -> Option<crate::data::types::Trade> {
         use crate::data::types::{Trade, Side};
-        use std::time::{SystemTime, UNIX_EPOCH, Duration};
+        use std::time::{UNIX_EPOCH, Duration};
 
         let price = self.price.parse::<Decimal>().ok()?;
         let quantity = self.quantity.parse::<Decimal>().ok()?;
@@ -113,6 +141,138 @@ impl AggTrade {
     }
 }
 
+/// Best bid/ask update (`@bookTicker`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BookTicker {
+    #[serde(rename = "u")]
+    pub update_id: u64,
+
+    #[serde(rename = "E", default)]
+    pub event_time: u64,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "b")]
+    pub best_bid: String,
+
+    #[serde(rename = "B")]
+    pub best_bid_qty: String,
+
+    #[serde(rename = "a")]
+    pub best_ask: String,
+
+    #[serde(rename = "A")]
+    pub best_ask_qty: String,
+}
+
+impl BookTicker {
+    /// Parse (bid, bid_qty, ask, ask_qty)
+    pub fn parse(&self) -> Option<(Decimal, Decimal, Decimal, Decimal)> {
+        Some((
+            self.best_bid.parse().ok()?,
+            self.best_bid_qty.parse().ok()?,
+            self.best_ask.parse().ok()?,
+            self.best_ask_qty.parse().ok()?,
+        ))
+    }
+}
+
+/// Mark price and funding update (`@markPrice`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MarkPriceUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "p")]
+    pub mark_price: String,
+
+    #[serde(rename = "i")]
+    pub index_price: String,
+
+    #[serde(rename = "r")]
+    pub funding_rate: String,
+
+    #[serde(rename = "T")]
+    pub next_funding_time: u64,
+}
+
+/// Forced liquidation order (`@forceOrder`)
+///
+/// Binance pushes at most one snapshot per symbol per second (the latest
+/// liquidation in that second), so totals built from this stream are a
+/// lower bound on what was actually liquidated.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ForceOrder {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "o")]
+    pub order: LiquidationOrder,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LiquidationOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "S")]
+    pub side: String,  // "SELL" = a long was liquidated
+
+    #[serde(rename = "o")]
+    pub order_type: String,
+
+    #[serde(rename = "f")]
+    pub time_in_force: String,
+
+    #[serde(rename = "q")]
+    pub quantity: String,
+
+    #[serde(rename = "p")]
+    pub price: String,
+
+    #[serde(rename = "ap")]
+    pub avg_price: String,
+
+    #[serde(rename = "X")]
+    pub status: String,
+
+    #[serde(rename = "l")]
+    pub last_filled_qty: String,
+
+    #[serde(rename = "z")]
+    pub filled_qty: String,
+
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+}
+
+impl ForceOrder {
+    /// Parse (side, average fill price, filled quantity)
+    ///
+    /// Side is the forced order's own side: a `Sell` closes a liquidated
+    /// long and pushes price down. Unfilled orders fall back to the order
+    /// price and quantity.
+    pub fn parse(&self) -> Option<(crate::data::types::Side, Decimal, Decimal)> {
+        use crate::data::types::Side;
+
+        let side = match self.order.side.as_str() {
+            "BUY" => Side::Buy,
+            "SELL" => Side::Sell,
+            _ => return None,
+        };
+        let filled: Decimal = self.order.filled_qty.parse().ok()?;
+        let avg_price: Decimal = self.order.avg_price.parse().ok()?;
+        if filled > Decimal::ZERO && avg_price > Decimal::ZERO {
+            return Some((side, avg_price, filled));
+        }
+        Some((side, self.order.price.parse().ok()?, self.order.quantity.parse().ok()?))
+    }
+}
+
 /// Binance WebSocket message wrapper
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -133,6 +293,10 @@ pub struct OrderResponse {
     pub client_order_id: String,
     
     pub price: String,
+
+    /// Average fill price ("0" or absent until something fills)
+    #[serde(rename = "avgPrice", default)]
+    pub avg_price: String,
     
     #[serde(rename = "origQty")]
     pub orig_qty: String,
@@ -154,6 +318,230 @@ pub struct OrderResponse {
     pub update_time: u64,
 }
 
+/// Position entry from `/fapi/v2/positionRisk`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PositionRisk {
+    pub symbol: String,
+
+    #[serde(rename = "positionAmt")]
+    pub position_amt: String,
+
+    #[serde(rename = "entryPrice")]
+    pub entry_price: String,
+
+    #[serde(rename = "markPrice")]
+    pub mark_price: String,
+
+    #[serde(rename = "unRealizedProfit")]
+    pub unrealized_profit: String,
+
+    #[serde(rename = "positionSide")]
+    pub position_side: String,  // "BOTH", "LONG" or "SHORT"
+
+    #[serde(default)]
+    pub leverage: String,
+}
+
+/// Listen key for the user data stream
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListenKeyResponse {
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// `ORDER_TRADE_UPDATE` from the user data stream
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "o")]
+    pub order: OrderUpdateDetails,
+}
+
+/// Order state carried by an `ORDER_TRADE_UPDATE`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderUpdateDetails {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+
+    #[serde(rename = "S")]
+    pub side: String,  // "BUY" or "SELL"
+
+    #[serde(rename = "o")]
+    pub order_type: String,
+
+    #[serde(rename = "x")]
+    pub execution_type: String,  // "NEW", "TRADE", "CANCELED", "EXPIRED", ...
+
+    #[serde(rename = "X")]
+    pub status: String,  // "NEW", "PARTIALLY_FILLED", "FILLED", ...
+
+    #[serde(rename = "i")]
+    pub order_id: u64,
+
+    #[serde(rename = "l")]
+    pub last_filled_qty: String,
+
+    #[serde(rename = "z")]
+    pub cumulative_filled_qty: String,
+
+    #[serde(rename = "L")]
+    pub last_filled_price: String,
+
+    #[serde(rename = "ap")]
+    pub avg_price: String,
+
+    /// Commission of this execution (absent when nothing traded)
+    #[serde(rename = "n", default)]
+    pub commission: Option<String>,
+
+    #[serde(rename = "N", default)]
+    pub commission_asset: Option<String>,
+
+    #[serde(rename = "m", default)]
+    pub is_maker: bool,
+
+    #[serde(rename = "t", default)]
+    pub trade_id: u64,
+}
+
+impl OrderUpdateDetails {
+    /// Whether the order can no longer fill
+    pub fn is_final(&self) -> bool {
+        matches!(self.status.as_str(), "FILLED" | "CANCELED" | "EXPIRED" | "REJECTED" | "EXPIRED_IN_MATCH")
+    }
+}
+
+/// `ACCOUNT_UPDATE` from the user data stream (balance/position changes)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountUpdate {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "a")]
+    pub data: AccountUpdateData,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountUpdateData {
+    /// What caused the update ("ORDER", "FUNDING_FEE", "DEPOSIT", ...)
+    #[serde(rename = "m")]
+    pub reason: String,
+
+    #[serde(rename = "B", default)]
+    pub balances: Vec<BalanceUpdate>,
+
+    #[serde(rename = "P", default)]
+    pub positions: Vec<PositionUpdate>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BalanceUpdate {
+    #[serde(rename = "a")]
+    pub asset: String,
+
+    #[serde(rename = "wb")]
+    pub wallet_balance: String,
+
+    #[serde(rename = "cw")]
+    pub cross_wallet_balance: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PositionUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "pa")]
+    pub position_amt: String,
+
+    #[serde(rename = "ep")]
+    pub entry_price: String,
+
+    #[serde(rename = "up")]
+    pub unrealized_pnl: String,
+
+    #[serde(rename = "ps")]
+    pub position_side: String,
+}
+
+/// Candlestick from `/fapi/v1/klines`, served as a JSON array per kline
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "KlineRow")]
+pub struct Kline {
+    pub open_time: u64,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub close_time: u64,
+    pub quote_volume: String,
+    pub trades: u64,
+}
+
+/// `[openTime, open, high, low, close, volume, closeTime, quoteVolume,
+/// trades, takerBuyBase, takerBuyQuote, ignore]`; the taker columns and
+/// the trailing placeholder are skipped
+#[derive(Deserialize)]
+struct KlineRow(u64, String, String, String, String, String, u64, String, u64, IgnoredAny, IgnoredAny, IgnoredAny);
+
+impl From<KlineRow> for Kline {
+    fn from(row: KlineRow) -> Self {
+        Self {
+            open_time: row.0,
+            open: row.1,
+            high: row.2,
+            low: row.3,
+            close: row.4,
+            volume: row.5,
+            close_time: row.6,
+            quote_volume: row.7,
+            trades: row.8,
+        }
+    }
+}
+
+/// Funding rate entry from `/fapi/v1/fundingRate`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundingRate {
+    pub symbol: String,
+
+    #[serde(rename = "fundingRate")]
+    pub funding_rate: String,
+
+    #[serde(rename = "fundingTime")]
+    pub funding_time: u64,
+}
+
+/// Account income entry from `/fapi/v1/income` (signed; negative = paid)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IncomeRecord {
+    /// Empty for account-level entries (transfers)
+    #[serde(default)]
+    pub symbol: String,
+
+    /// "REALIZED_PNL", "COMMISSION", "FUNDING_FEE", "TRANSFER", ...
+    #[serde(rename = "incomeType")]
+    pub income_type: String,
+
+    pub income: String,
+
+    pub asset: String,
+
+    pub time: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +589,102 @@ mod tests {
         assert_eq!(trade.side, crate::data::types::Side::Buy);
         assert!(!trade.is_buyer_maker);
     }
+
+    #[test]
+    fn test_parse_book_ticker_and_mark_price() {
+        let json = r#"{
+            "e": "bookTicker",
+            "u": 400900217,
+            "E": 1568014460893,
+            "T": 1568014460891,
+            "s": "BTCUSDT",
+            "b": "25.35190000",
+            "B": "31.21000000",
+            "a": "25.36520000",
+            "A": "40.66000000"
+        }"#;
+        let ticker: BookTicker = serde_json::from_str(json).unwrap();
+        let (bid, _, ask, _) = ticker.parse().unwrap();
+        assert!(bid < ask);
+
+        let json = r#"{
+            "e": "markPriceUpdate",
+            "E": 1562305380000,
+            "s": "BTCUSDT",
+            "p": "11794.15000000",
+            "i": "11784.62659091",
+            "P": "11784.25641265",
+            "r": "0.00038167",
+            "T": 1562306400000
+        }"#;
+        let mark: MarkPriceUpdate = serde_json::from_str(json).unwrap();
+        assert_eq!(mark.funding_rate, "0.00038167");
+    }
+
+    #[test]
+    fn test_parse_force_order() {
+        let json = r#"{
+            "e": "forceOrder",
+            "E": 1568014460893,
+            "o": {
+                "s": "BTCUSDT",
+                "S": "SELL",
+                "o": "LIMIT",
+                "f": "IOC",
+                "q": "0.014",
+                "p": "9910",
+                "ap": "9910",
+                "X": "FILLED",
+                "l": "0.014",
+                "z": "0.014",
+                "T": 1568014460893
+            }
+        }"#;
+        let force: ForceOrder = serde_json::from_str(json).unwrap();
+        let (side, price, qty) = force.parse().unwrap();
+        assert_eq!(side, crate::data::types::Side::Sell);
+        assert_eq!(price, Decimal::from(9910));
+        assert_eq!(qty, "0.014".parse::<Decimal>().unwrap());
+    }
+
+    #[test]
+    fn test_parse_kline_and_funding() {
+        let json = r#"[[
+            1499040000000, "0.01634790", "0.80000000", "0.01575800", "0.01577100",
+            "148976.11427815", 1499644799999, "2434.19055334", 308,
+            "1756.87402397", "28.46694368", "0"
+        ]]"#;
+        let klines: Vec<Kline> = serde_json::from_str(json).unwrap();
+        assert_eq!(klines[0].close, "0.01577100");
+        assert_eq!(klines[0].close_time, 1499644799999);
+        assert_eq!(klines[0].trades, 308);
+
+        let json = r#"[{
+            "symbol": "BTCUSDT",
+            "fundingRate": "-0.03750000",
+            "fundingTime": 1570608000000,
+            "markPrice": "34287.54619963"
+        }]"#;
+        let funding: Vec<FundingRate> = serde_json::from_str(json).unwrap();
+        assert_eq!(funding[0].funding_rate, "-0.03750000");
+    }
+
+    #[test]
+    fn test_parse_position_risk() {
+        let json = r#"[{
+            "symbol": "BTCUSDT",
+            "positionAmt": "0.001",
+            "entryPrice": "50000.0",
+            "markPrice": "50010.0",
+            "unRealizedProfit": "0.01",
+            "liquidationPrice": "0",
+            "leverage": "10",
+            "positionSide": "BOTH"
+        }]"#;
+
+        let positions: Vec<PositionRisk> = serde_json::from_str(json).unwrap();
+        assert_eq!(positions[0].position_amt, "0.001");
+        assert_eq!(positions[0].position_side, "BOTH");
+        assert_eq!(positions[0].leverage, "10");
+    }
 }
@@ -0,0 +1,370 @@
+use crate::exchange::binance::rest::BinanceRestClient;
+use crate::exchange::binance::types::{AccountUpdate, OrderTradeUpdate};
+use crate::exchange::binance::websocket::{MarketEvent, ReceiveTime, TimedEvent, WsShutdown};
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Notify};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn, error, debug};
+
+/// Account event stream (`[exchange.user_data]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserDataConfig {
+    /// Subscribe to order and account updates and take fills from them
+    pub enabled: bool,
+    /// Listen key refresh interval (Binance expires keys after 60 minutes)
+    pub keepalive_secs: u64,
+    /// How long an order waits for its final ORDER_TRADE_UPDATE before
+    /// falling back to the REST response
+    pub fill_timeout_ms: u64,
+}
+
+impl Default for UserDataConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keepalive_secs: 1800,
+            fill_timeout_ms: 2000,
+        }
+    }
+}
+
+/// Fill state of one order, accumulated from its ORDER_TRADE_UPDATEs
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderFill {
+    pub symbol: String,
+    /// Latest order status ("PARTIALLY_FILLED", "FILLED", ...)
+    pub status: String,
+    pub filled_qty: Decimal,
+    pub avg_price: Decimal,
+    /// Commission over all executions, in `commission_asset`
+    pub commission: Decimal,
+    pub commission_asset: Option<String>,
+    pub is_final: bool,
+}
+
+/// Orders tracked at once; the oldest are dropped beyond this
+const MAX_TRACKED_ORDERS: usize = 1000;
+
+#[derive(Default)]
+struct FillBook {
+    orders: HashMap<u64, OrderFill>,
+    /// Order IDs by first update, for eviction
+    arrival: Vec<u64>,
+}
+
+/// Order fills reported by the user data stream, shared with whoever
+/// placed the orders
+///
+/// Updates are keyed by order ID and can arrive before the REST call
+/// that placed the order returns, so a waiter finds them either way.
+#[derive(Clone, Default)]
+pub struct OrderFills {
+    book: Arc<Mutex<FillBook>>,
+    updated: Arc<Notify>,
+}
+
+impl OrderFills {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one update into its order's state
+    pub fn record(&self, update: &OrderTradeUpdate) {
+        let order = &update.order;
+        let parse = |value: &str| value.parse::<Decimal>().unwrap_or(Decimal::ZERO);
+
+        let mut book = self.book.lock().unwrap_or_else(|e| e.into_inner());
+        if !book.orders.contains_key(&order.order_id) {
+            book.arrival.push(order.order_id);
+            if book.arrival.len() > MAX_TRACKED_ORDERS {
+                let oldest = book.arrival.remove(0);
+                book.orders.remove(&oldest);
+            }
+        }
+        let fill = book.orders.entry(order.order_id).or_insert_with(|| OrderFill {
+            symbol: order.symbol.clone(),
+            status: String::new(),
+            filled_qty: Decimal::ZERO,
+            avg_price: Decimal::ZERO,
+            commission: Decimal::ZERO,
+            commission_asset: None,
+            is_final: false,
+        });
+
+        // Cumulative quantity and average price supersede earlier updates;
+        // commission is per execution and adds up
+        fill.status = order.status.clone();
+        fill.filled_qty = fill.filled_qty.max(parse(&order.cumulative_filled_qty));
+        let avg_price = parse(&order.avg_price);
+        if !avg_price.is_zero() {
+            fill.avg_price = avg_price;
+        }
+        if order.execution_type == "TRADE" {
+            fill.commission += order.commission.as_deref().map(parse).unwrap_or_default();
+            if order.commission_asset.is_some() {
+                fill.commission_asset = order.commission_asset.clone();
+            }
+        }
+        fill.is_final |= order.is_final();
+        drop(book);
+
+        self.updated.notify_waiters();
+    }
+
+    /// Remove and return `order_id`'s state once it can no longer fill
+    pub fn take_final(&self, order_id: u64) -> Option<OrderFill> {
+        let mut book = self.book.lock().unwrap_or_else(|e| e.into_inner());
+        if !book.orders.get(&order_id)?.is_final {
+            return None;
+        }
+        book.arrival.retain(|id| *id != order_id);
+        book.orders.remove(&order_id)
+    }
+
+    /// Wait up to `timeout` for `order_id` to reach a final status
+    pub async fn wait_final(&self, order_id: u64, timeout: Duration) -> Option<OrderFill> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Registered before the check so an update in between still wakes us
+            let updated = self.updated.notified();
+            if let Some(fill) = self.take_final(order_id) {
+                return Some(fill);
+            }
+            if tokio::time::timeout_at(deadline.into(), updated).await.is_err() {
+                return self.take_final(order_id);
+            }
+        }
+    }
+}
+
+/// Binance user data stream: listen key lifecycle plus the account
+/// WebSocket
+///
+/// Emits `MarketEvent::OrderUpdate` and `MarketEvent::AccountUpdate` on
+/// its own channel and records every order update into `fills()`. The
+/// listen key is refreshed every `keepalive_secs`; on disconnect, a failed
+/// refresh or `listenKeyExpired` a fresh key is requested and the socket
+/// reconnected.
+pub struct UserDataStream {
+    client: BinanceRestClient,
+    ws_endpoint: String,
+    config: UserDataConfig,
+    event_tx: mpsc::UnboundedSender<TimedEvent>,
+    fills: OrderFills,
+    shutdown: WsShutdown,
+}
+
+impl UserDataStream {
+    pub fn new(
+        client: BinanceRestClient,
+        ws_endpoint: String,
+        config: UserDataConfig,
+    ) -> (Self, mpsc::UnboundedReceiver<TimedEvent>) {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        (
+            Self {
+                client,
+                ws_endpoint,
+                config,
+                event_tx,
+                fills: OrderFills::new(),
+                shutdown: WsShutdown::new(),
+            },
+            event_rx,
+        )
+    }
+
+    /// Fill state fed by this stream (keep it before spawning `run`)
+    pub fn fills(&self) -> OrderFills {
+        self.fills.clone()
+    }
+
+    /// Handle that stops `run` from another task (keep it before spawning)
+    pub fn shutdown_handle(&self) -> WsShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Run until shut down, reconnecting with a new listen key on errors
+    pub async fn run(&self) {
+        let mut reconnect_delay = Duration::from_secs(1);
+        let max_reconnect_delay = Duration::from_secs(60);
+        let mut shutdown = self.shutdown.subscribe();
+
+        while !self.shutdown.is_requested() {
+            let result = match self.client.start_user_data_stream().await {
+                Ok(listen_key) => {
+                    let result = self.connect_and_process(&listen_key).await;
+                    if let Err(e) = self.client.close_user_data_stream().await {
+                        debug!("Closing user data stream failed: {}", e);
+                    }
+                    result
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(_) => {
+                    info!("User data stream closed normally");
+                    reconnect_delay = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    error!("User data stream error: {}. Reconnecting in {:?}", e, reconnect_delay);
+                    self.emit(MarketEvent::Disconnected);
+
+                    tokio::select! {
+                        _ = tokio::time::sleep(reconnect_delay) => {}
+                        _ = shutdown.changed() => {}
+                    }
+                    reconnect_delay = std::cmp::min(reconnect_delay * 2, max_reconnect_delay);
+                }
+            }
+        }
+    }
+
+    async fn connect_and_process(&self, listen_key: &str) -> Result<()> {
+        let url = format!("{}/ws/{}", self.ws_endpoint, listen_key);
+        let (ws_stream, _) = connect_async(&url).await
+            .map_err(|e| anyhow!("User data connection failed: {}", e))?;
+
+        info!("User data stream connected");
+        self.emit(MarketEvent::Connected);
+
+        let (mut write, mut read) = ws_stream.split();
+        let mut shutdown = self.shutdown.subscribe();
+        let mut keepalive = tokio::time::interval(Duration::from_secs(self.config.keepalive_secs.max(60)));
+        keepalive.tick().await;
+
+        loop {
+            if self.shutdown.is_requested() {
+                let _ = write.send(Message::Close(None)).await;
+                self.emit(MarketEvent::Disconnected);
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = shutdown.changed() => {}
+
+                _ = keepalive.tick() => {
+                    self.client.keepalive_user_data_stream().await
+                        .map_err(|e| anyhow!("Listen key keepalive failed: {}", e))?;
+                    debug!("User data listen key refreshed");
+                }
+
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            if let Some(event) = parse_user_data(&text)? {
+                                if let MarketEvent::OrderUpdate(update) = &event {
+                                    self.fills.record(update);
+                                }
+                                self.emit(event);
+                            }
+                        }
+                        Some(Ok(Message::Close(_))) | None => {
+                            info!("User data stream closed by server");
+                            return Err(anyhow!("connection closed"));
+                        }
+                        Some(Err(e)) => return Err(anyhow!("User data WebSocket error: {}", e)),
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn emit(&self, event: MarketEvent) {
+        let _ = self.event_tx.send(TimedEvent { received: ReceiveTime::now(), event });
+    }
+}
+
+/// Parse one user data frame
+///
+/// Ok(None) for event types that are not forwarded; an expired listen key
+/// is an error so the stream reconnects with a new one.
+pub fn parse_user_data(text: &str) -> Result<Option<MarketEvent>> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| anyhow!("Failed to parse user data frame: {}", e))?;
+
+    match value.get("e").and_then(|e| e.as_str()) {
+        Some("ORDER_TRADE_UPDATE") => {
+            let update: OrderTradeUpdate = serde_json::from_value(value)
+                .map_err(|e| anyhow!("Failed to parse order update: {}", e))?;
+            Ok(Some(MarketEvent::OrderUpdate(update)))
+        }
+        Some("ACCOUNT_UPDATE") => {
+            let update: AccountUpdate = serde_json::from_value(value)
+                .map_err(|e| anyhow!("Failed to parse account update: {}", e))?;
+            Ok(Some(MarketEvent::AccountUpdate(update)))
+        }
+        Some("listenKeyExpired") => Err(anyhow!("listen key expired")),
+        Some(other) => {
+            debug!("Ignoring user data event {}", other);
+            Ok(None)
+        }
+        None => {
+            warn!("User data frame without event type");
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn order_update(execution: &str, status: &str, last: &str, cumulative: &str, avg: &str, commission: &str) -> String {
+        format!(r#"{{
+            "e": "ORDER_TRADE_UPDATE", "E": 1700000000100, "T": 1700000000099,
+            "o": {{
+                "s": "BTCUSDT", "c": "cid", "S": "BUY", "o": "MARKET", "x": "{}", "X": "{}",
+                "i": 8886774, "l": "{}", "z": "{}", "L": "42000.1", "ap": "{}",
+                "n": "{}", "N": "USDT", "m": false, "t": 12
+            }}
+        }}"#, execution, status, last, cumulative, avg, commission)
+    }
+
+    #[tokio::test]
+    async fn test_order_updates_accumulate_into_fill() {
+        let fills = OrderFills::new();
+        let record = |text: String| match parse_user_data(&text).unwrap() {
+            Some(MarketEvent::OrderUpdate(update)) => fills.record(&update),
+            other => panic!("unexpected {:?}", other),
+        };
+
+        record(order_update("NEW", "NEW", "0", "0", "0", "0"));
+        record(order_update("TRADE", "PARTIALLY_FILLED", "0.010", "0.010", "42000.1", "0.168"));
+        assert!(fills.take_final(8886774).is_none());
+        assert!(fills.wait_final(8886774, Duration::from_millis(10)).await.is_none());
+
+        // The final update arrives while waiting
+        let waiter = {
+            let fills = fills.clone();
+            tokio::spawn(async move { fills.wait_final(8886774, Duration::from_secs(5)).await })
+        };
+        tokio::task::yield_now().await;
+        record(order_update("TRADE", "FILLED", "0.014", "0.024", "42000.2", "0.2352"));
+
+        let fill = waiter.await.unwrap().unwrap();
+        assert_eq!(fill.status, "FILLED");
+        assert_eq!(fill.filled_qty, dec!(0.024));
+        assert_eq!(fill.avg_price, dec!(42000.2));
+        assert_eq!(fill.commission, dec!(0.4032));
+        assert_eq!(fill.commission_asset.as_deref(), Some("USDT"));
+        assert!(fills.take_final(8886774).is_none());
+
+        let account = r#"{"e":"ACCOUNT_UPDATE","E":1,"T":1,"a":{"m":"ORDER",
+            "B":[{"a":"USDT","wb":"1000.5","cw":"1000.5"}],
+            "P":[{"s":"BTCUSDT","pa":"0.024","ep":"42000.2","up":"0","ps":"BOTH"}]}}"#;
+        assert!(matches!(parse_user_data(account).unwrap(), Some(MarketEvent::AccountUpdate(_))));
+        assert!(parse_user_data(r#"{"e":"listenKeyExpired","E":1}"#).is_err());
+        assert!(parse_user_data(r#"{"e":"MARGIN_CALL","E":1}"#).unwrap().is_none());
+    }
+}
@@ -1,41 +1,153 @@
-use crate::data::{OrderBook, Trade};
-use crate::exchange::binance::types::{BinanceMessage, DepthUpdate, AggTrade};
+use crate::data::{OrderBook, Side, Trade};
+use crate::exchange::binance::book_sync::{BookSync, BookSyncMetrics, SyncStep};
+use crate::exchange::binance::feed_latency::{FeedLatency, FeedLatencyMetrics};
+use crate::exchange::binance::frame_capture::FrameCapture;
+use crate::exchange::binance::level_expiry::{LevelExpiry, LevelExpiryMetrics};
+use crate::exchange::binance::rest::BinanceRestClient;
+use crate::exchange::binance::sequencing::{SequenceCheck, SequenceMetrics, SequenceTracker};
+use crate::exchange::binance::streams::StreamConfig;
+use crate::exchange::binance::types::{
+    AccountUpdate, DepthSnapshot, DepthUpdate, AggTrade, BookTicker, ForceOrder, MarkPriceUpdate,
+    OrderTradeUpdate,
+};
+use crate::exchange::book_validation::{BookValidationMetrics, BookValidator};
+use crate::exchange::checksum::{self, BookCorrupted};
+use crate::exchange::depth_limit::{DepthLimit, DepthLimitMetrics};
+use crate::utils::LogSampler;
+#[cfg(feature = "chaos")]
+use crate::utils::chaos::FaultInjector;
 use anyhow::{Result, anyhow};
 use futures_util::{SinkExt, StreamExt};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{info, warn, error, debug};
-use std::sync::Arc;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Snapshots fetched for one diff before giving up until the next diff
+const MAX_SNAPSHOT_ATTEMPTS: usize = 3;
 
 /// Events emitted by the WebSocket stream
 #[derive(Debug, Clone)]
 pub enum MarketEvent {
     DepthUpdate(DepthUpdate),
     Trade(Trade),
+    BookTicker(BookTicker),
+    MarkPrice(MarkPriceUpdate),
+    /// Someone else's position was force-closed (`@forceOrder`)
+    Liquidation(ForceOrder),
     Connected,
     Disconnected,
+    /// Local book failed venue checksum validation and was cleared for resync
+    BookCorrupted(BookCorrupted),
+    /// Our order changed state or traded (user data stream)
+    OrderUpdate(OrderTradeUpdate),
+    /// Our balances or positions changed (user data stream)
+    AccountUpdate(AccountUpdate),
+}
+
+/// Local time a frame was read off the socket
+///
+/// Taken once per frame and carried with every event parsed from it, so
+/// latency, staleness and live-vs-backtest comparisons all measure from
+/// the same instant instead of each calling `Instant::now()` later.
+#[derive(Debug, Clone, Copy)]
+pub struct ReceiveTime {
+    /// Monotonic; use for local ages and intervals
+    pub instant: Instant,
+    /// Wall clock (ms since epoch) at the same read, for comparing with
+    /// exchange `event_time`s
+    pub wall_ms: u64,
+}
+
+impl ReceiveTime {
+    pub fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+            wall_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        }
+    }
+
+    /// `wall_ms` as a `SystemTime`
+    pub fn wall_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.wall_ms)
+    }
+
+    /// Time since the frame was received
+    pub fn elapsed(&self) -> Duration {
+        self.instant.elapsed()
+    }
+}
+
+/// `MarketEvent` stamped with when it was received
+#[derive(Debug, Clone)]
+pub struct TimedEvent {
+    pub received: ReceiveTime,
+    pub event: MarketEvent,
+}
+
+/// Handle for stopping a running `BinanceWebSocket` from another task
+#[derive(Clone)]
+pub struct WsShutdown(Arc<watch::Sender<bool>>);
+
+impl WsShutdown {
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(watch::channel(false).0))
+    }
+
+    /// Close the current connection and stop reconnecting
+    pub fn shutdown(&self) {
+        self.0.send_replace(true);
+    }
+
+    pub(crate) fn is_requested(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<bool> {
+        self.0.subscribe()
+    }
 }
 
 /// WebSocket connection manager with auto-reconnect
 pub struct BinanceWebSocket {
     symbol: String,
+    ws_endpoint: String,
     ws_url: String,
-    event_tx: mpsc::UnboundedSender<MarketEvent>,
+    streams: StreamConfig,
+    event_tx: mpsc::UnboundedSender<TimedEvent>,
     orderbook: Arc<OrderBook>,
+    book_validator: BookValidator,
+    depth_limit: Option<DepthLimit>,
+    message_errors: LogSampler,
+    level_expiry: Option<Mutex<LevelExpiry>>,
+    frame_capture: Option<Mutex<FrameCapture>>,
+    sequencing: Mutex<SequenceTracker>,
+    /// REST client and level limit for snapshots (None = rebuild from diffs)
+    depth_snapshots: Option<(Arc<BinanceRestClient>, usize)>,
+    book_sync: Mutex<BookSync>,
+    feed_latency: Mutex<FeedLatency>,
+    silence_timeout: Option<Duration>,
+    shutdown: WsShutdown,
+    #[cfg(feature = "chaos")]
+    faults: Option<FaultInjector>,
 }
 
 impl BinanceWebSocket {
     /// Create new WebSocket manager
     /// 
-    /// Streams:
+    /// Streams (change with `with_streams`):
     /// - {symbol}@depth@100ms - Order book updates every 100ms
     /// - {symbol}@aggTrade - Aggregated trades
     pub fn new(
         symbol: String,
         ws_endpoint: String,
         orderbook: Arc<OrderBook>,
-    ) -> (Self, mpsc::UnboundedReceiver<MarketEvent>) {
+    ) -> (Self, mpsc::UnboundedReceiver<TimedEvent>) {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         
         // Build WebSocket URL with combined streams
@@ -45,24 +157,161 @@ impl BinanceWebSocket {
 
         (
             Self {
+                book_validator: BookValidator::new(&symbol),
+                depth_limit: None,
                 symbol,
+                ws_endpoint,
                 ws_url,
+                streams: StreamConfig::default(),
                 event_tx,
                 orderbook,
+                // Malformed messages repeat at stream rate; one line per 10s
+                message_errors: LogSampler::new(Duration::from_secs(10)),
+                level_expiry: None,
+                frame_capture: None,
+                sequencing: Mutex::new(SequenceTracker::new(0.0)),
+                depth_snapshots: None,
+                book_sync: Mutex::new(BookSync::new(1000)),
+                feed_latency: Mutex::new(FeedLatency::new(1000)),
+                silence_timeout: None,
+                shutdown: WsShutdown::new(),
+                #[cfg(feature = "chaos")]
+                faults: None,
             },
             event_rx,
         )
     }
 
+    /// Subscribe to a different stream set (depth speed/mode, extra streams)
+    pub fn with_streams(mut self, streams: StreamConfig) -> Result<Self> {
+        let names = streams.stream_names(&self.symbol)?;
+        self.ws_url = format!("{}/stream?streams={}", self.ws_endpoint, names.join("/"));
+        self.streams = streams;
+        Ok(self)
+    }
+
+    /// Expire levels not updated within `ttl`
+    pub fn with_level_ttl(mut self, ttl: Duration) -> Self {
+        self.level_expiry = Some(Mutex::new(LevelExpiry::new(ttl)));
+        self
+    }
+
+    /// Keep only the best `max_levels` per side, evicting far levels
+    pub fn with_max_book_levels(mut self, max_levels: usize) -> Self {
+        self.depth_limit = Some(DepthLimit::new(max_levels));
+        self
+    }
+
+    /// Reconnect if no data message arrives within `window`
+    ///
+    /// A half-open TCP connection or a stalled stream can keep the socket
+    /// looking healthy (pings go out, pongs may even come back) while no
+    /// market data flows; only the absence of data gives it away.
+    pub fn with_silence_timeout(mut self, window: Duration) -> Self {
+        self.silence_timeout = Some(window);
+        self
+    }
+
+    /// Clear the book once more than `rate` of recent depth diffs were gapped
+    pub fn with_resync_gap_rate(mut self, rate: f64) -> Self {
+        self.sequencing = Mutex::new(SequenceTracker::new(rate));
+        self
+    }
+
+    /// Build the book from REST snapshots plus replayed diffs
+    ///
+    /// Follows Binance's procedure for a local book: on startup, reconnect
+    /// and every sequence gap, the diffs are held back while a snapshot of
+    /// `limit` levels is fetched, then replayed from its `lastUpdateId`.
+    /// Without this the book is cleared and rebuilt from the diffs alone.
+    pub fn with_depth_snapshots(mut self, rest: Arc<BinanceRestClient>, limit: usize) -> Self {
+        self.depth_snapshots = Some((rest, limit));
+        self
+    }
+
+    /// Snapshot and replay counters
+    pub fn book_sync_metrics(&self) -> Arc<BookSyncMetrics> {
+        self.book_sync.lock().unwrap_or_else(|e| e.into_inner()).metrics()
+    }
+
+    /// Inject frame latency, drops and disconnects (chaos drills)
+    ///
+    /// Delays are applied before the frame is stamped, so they show up as
+    /// feed latency exactly like a slow network would.
+    #[cfg(feature = "chaos")]
+    pub fn with_faults(mut self, faults: FaultInjector) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /// Sequencing and drop counters
+    pub fn sequence_metrics(&self) -> Arc<SequenceMetrics> {
+        self.sequencing.lock().unwrap_or_else(|e| e.into_inner()).metrics()
+    }
+
+    /// Symbol mismatch and crossed book counters
+    pub fn book_validation_metrics(&self) -> Arc<BookValidationMetrics> {
+        self.book_validator.metrics()
+    }
+
+    /// Far-level eviction counters (None without a depth cap)
+    pub fn depth_limit_metrics(&self) -> Option<Arc<DepthLimitMetrics>> {
+        self.depth_limit.as_ref().map(|limit| limit.metrics())
+    }
+
+    /// Exchange-to-local latency and clock skew estimates
+    pub fn feed_latency_metrics(&self) -> Arc<FeedLatencyMetrics> {
+        self.feed_latency.lock().unwrap_or_else(|e| e.into_inner()).metrics()
+    }
+
+    /// Keep the last `capacity` raw frames and spill them to `dir` on errors
+    pub fn with_frame_capture(mut self, capacity: usize, dir: impl Into<PathBuf>) -> Self {
+        self.frame_capture = Some(Mutex::new(FrameCapture::new(capacity, dir)));
+        self
+    }
+
+    /// Write captured frames to disk (no-op if capture is disabled)
+    ///
+    /// Called on parse errors; also usable by callers that detect a desync.
+    pub fn spill_frames(&self, reason: &str) {
+        let capture = match &self.frame_capture {
+            Some(capture) => capture,
+            None => return,
+        };
+
+        let mut capture = capture.lock().unwrap_or_else(|e| e.into_inner());
+        match capture.spill(&self.symbol, reason) {
+            Ok(Some(path)) => warn!("Captured raw frames written to {}", path.display()),
+            Ok(None) => {}
+            Err(e) => error!("Failed to write captured frames: {}", e),
+        }
+    }
+
+    /// Level expiry counters (`None` if TTLs are disabled)
+    pub fn level_metrics(&self) -> Option<Arc<LevelExpiryMetrics>> {
+        self.level_expiry.as_ref().map(|e| e.lock().unwrap_or_else(|e| e.into_inner()).metrics())
+    }
+
+    /// Handle that stops `run` from another task (keep it before spawning)
+    pub fn shutdown_handle(&self) -> WsShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Close the current connection and stop reconnecting
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+
     /// Start WebSocket connection with auto-reconnect
     /// 
-    /// This runs indefinitely, automatically reconnecting on errors.
-    /// Use tokio::spawn to run in background.
+    /// Runs until `shutdown()` is called, automatically reconnecting on
+    /// errors. Use tokio::spawn to run in background.
     pub async fn run(&self) {
         let mut reconnect_delay = Duration::from_secs(1);
         let max_reconnect_delay = Duration::from_secs(60);
+        let mut shutdown = self.shutdown.subscribe();
 
-        loop {
+        while !self.shutdown.is_requested() {
             info!("Connecting to Binance WebSocket: {}", self.ws_url);
 
             match self.connect_and_process().await {
@@ -73,9 +322,12 @@ impl BinanceWebSocket {
                 Err(e) => {
                     error!("WebSocket error: {}. Reconnecting in {:?}", e, reconnect_delay);
                     
-                    let _ = self.event_tx.send(MarketEvent::Disconnected);
+                    self.emit(ReceiveTime::now(), MarketEvent::Disconnected);
                     
-                    tokio::time::sleep(reconnect_delay).await;
+                    tokio::select! {
+                        _ = tokio::time::sleep(reconnect_delay) => {}
+                        _ = shutdown.changed() => {}
+                    }
                     
                     // Exponential backoff
                     reconnect_delay = std::cmp::min(
@@ -93,52 +345,114 @@ impl BinanceWebSocket {
             .map_err(|e| anyhow!("WebSocket connection failed: {}", e))?;
 
         info!("WebSocket connected successfully");
-        let _ = self.event_tx.send(MarketEvent::Connected);
+
+        self.on_connected()?;
+        self.emit(ReceiveTime::now(), MarketEvent::Connected);
 
         let (mut write, mut read) = ws_stream.split();
 
-        // Spawn ping task to keep connection alive
-        let ping_interval = Duration::from_secs(30);
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(ping_interval);
-            loop {
-                interval.tick().await;
-                if write.send(Message::Ping(vec![])).await.is_err() {
-                    break;
-                }
+        // Everything below is scoped to this connection: the ping timer,
+        // the writer and the reader are all dropped when we return, so a
+        // reconnect never leaves an orphaned ping task behind.
+        let mut shutdown = self.shutdown.subscribe();
+        let mut ping = tokio::time::interval(Duration::from_secs(30));
+        ping.tick().await;
+
+        // Only data frames feed the watchdog: pongs to our own pings prove
+        // the socket, not the stream.
+        let mut last_data = Instant::now();
+
+        loop {
+            if self.shutdown.is_requested() {
+                info!("WebSocket shutdown requested");
+                let _ = write.send(Message::Close(None)).await;
+                self.emit(ReceiveTime::now(), MarketEvent::Disconnected);
+                return Ok(());
             }
-        });
-
-        // Process incoming messages
-        while let Some(msg) = read.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    if let Err(e) = self.process_message(&text).await {
-                        warn!("Error processing message: {}", e);
-                    }
-                }
-                Ok(Message::Ping(_)) => {
-                    debug!("Received ping");
-                }
-                Ok(Message::Pong(_)) => {
-                    debug!("Received pong");
+
+            let silence_deadline = last_data + self.silence_timeout.unwrap_or_default();
+
+            tokio::select! {
+                _ = shutdown.changed() => {}
+
+                _ = ping.tick() => {
+                    write.send(Message::Ping(vec![])).await
+                        .map_err(|e| anyhow!("Failed to send ping: {}", e))?;
                 }
-                Ok(Message::Close(_)) => {
-                    info!("Received close frame");
-                    break;
+
+                _ = tokio::time::sleep_until(silence_deadline.into()), if self.silence_timeout.is_some() => {
+                    return Err(anyhow!(
+                        "No data received for {:?}, forcing reconnect",
+                        self.silence_timeout.unwrap_or_default(),
+                    ));
                 }
-                Err(e) => {
-                    return Err(anyhow!("WebSocket error: {}", e));
+
+                msg = read.next() => {
+                    let msg = match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    };
+
+                    match msg {
+                        Ok(Message::Text(text)) => {
+                            #[cfg(feature = "chaos")]
+                            if let Some(faults) = &self.faults {
+                                if faults.take_disconnect() {
+                                    return Err(anyhow!("chaos: injected disconnect"));
+                                }
+                                if !faults.on_frame().await {
+                                    continue;
+                                }
+                            }
+                            let received = ReceiveTime::now();
+                            last_data = received.instant;
+                            if let Some(capture) = &self.frame_capture {
+                                capture.lock().unwrap_or_else(|e| e.into_inner()).record_at(&text, received.wall_ms);
+                            }
+                            if let Err(e) = self.process_message(&text, received).await {
+                                crate::sampled!(self.message_errors, warn, "Error processing message: {}", e);
+                                self.spill_frames("message_error");
+                            }
+                        }
+                        Ok(Message::Ping(_)) => {
+                            debug!("Received ping");
+                        }
+                        Ok(Message::Pong(_)) => {
+                            debug!("Received pong");
+                        }
+                        Ok(Message::Close(_)) => {
+                            info!("Received close frame");
+                            break;
+                        }
+                        Err(e) => {
+                            return Err(anyhow!("WebSocket error: {}", e));
+                        }
+                        _ => {}
+                    }
                 }
-                _ => {}
             }
         }
 
         Ok(())
     }
 
+    /// Invalidate everything learned on the previous connection
+    ///
+    /// Deletes may have been missed while disconnected, so the old levels
+    /// can't be trusted whether or not TTLs are on: start from a clean book.
+    fn on_connected(&self) -> Result<()> {
+        let (bid_levels, ask_levels) = self.orderbook.get_book_depth_count();
+        self.resync_book()?;
+        if bid_levels + ask_levels > 0 {
+            info!("Cleared {} order book levels after reconnect", bid_levels + ask_levels);
+        }
+        self.sequencing.lock().unwrap_or_else(|e| e.into_inner()).reset();
+        self.book_sync.lock().unwrap_or_else(|e| e.into_inner()).invalidate();
+        Ok(())
+    }
+
     /// Process a single WebSocket message
-    async fn process_message(&self, text: &str) -> Result<()> {
+    async fn process_message(&self, text: &str, received: ReceiveTime) -> Result<()> {
         // Binance streams come wrapped in {"stream": "...", "data": {...}}
         #[derive(serde::Deserialize)]
         struct StreamWrapper {
@@ -151,46 +465,192 @@ impl BinanceWebSocket {
 
         // Determine message type from stream name
         if wrapper.stream.contains("depth") {
-            self.process_depth_update(&wrapper.data).await?;
+            self.process_depth_update(&wrapper.data, received).await?;
         } else if wrapper.stream.contains("aggTrade") {
-            self.process_agg_trade(&wrapper.data).await?;
+            self.process_agg_trade(&wrapper.data, received).await?;
+        } else if wrapper.stream.contains("bookTicker") {
+            let ticker: BookTicker = serde_json::from_value(wrapper.data)
+                .map_err(|e| anyhow!("Failed to parse book ticker: {}", e))?;
+            self.emit(received, MarketEvent::BookTicker(ticker));
+        } else if wrapper.stream.contains("markPrice") {
+            let mark: MarkPriceUpdate = serde_json::from_value(wrapper.data)
+                .map_err(|e| anyhow!("Failed to parse mark price: {}", e))?;
+            self.emit(received, MarketEvent::MarkPrice(mark));
+        } else if wrapper.stream.contains("forceOrder") {
+            let force: ForceOrder = serde_json::from_value(wrapper.data)
+                .map_err(|e| anyhow!("Failed to parse force order: {}", e))?;
+            self.emit(received, MarketEvent::Liquidation(force));
         }
 
         Ok(())
     }
 
     /// Process depth update and update order book
-    async fn process_depth_update(&self, data: &serde_json::Value) -> Result<()> {
+    async fn process_depth_update(&self, data: &serde_json::Value, received: ReceiveTime) -> Result<()> {
         let update: DepthUpdate = serde_json::from_value(data.clone())
             .map_err(|e| anyhow!("Failed to parse depth update: {}", e))?;
 
-        // Update order book with bids
-        for (price, qty) in update.parse_bids() {
-            self.orderbook.update_level(crate::data::Side::Buy, price, qty)?;
+        if let Err(mismatch) = self.book_validator.check_symbol(&update.symbol) {
+            warn!("{}", mismatch);
+            return Ok(());
+        }
+
+        self.feed_latency.lock().unwrap_or_else(|e| e.into_inner()).record_at(update.event_time, received.wall_ms);
+
+        // Partial depth is a full top-N snapshot: replace rather than merge.
+        // Otherwise detect dropped diffs before applying this one.
+        let check = if self.streams.is_partial_depth() {
+            self.resync_book()?;
+            SequenceCheck::InOrder
+        } else {
+            self.sequencing.lock().unwrap_or_else(|e| e.into_inner()).on_depth(
+                update.first_update_id,
+                update.final_update_id,
+                update.prev_final_update_id,
+                update.event_time,
+            )
+        };
+        match check {
+            SequenceCheck::InOrder => {}
+            SequenceCheck::Gap { missed } => debug!("Depth stream gap: at least {} updates missed", missed),
+            SequenceCheck::Stale => {
+                debug!("Dropping stale depth update (u = {})", update.final_update_id);
+                return Ok(());
+            }
+            SequenceCheck::ResyncNeeded if self.depth_snapshots.is_some() => {
+                // BookSync already refetches on every gap
+                warn!("Depth stream gap rate too high");
+                self.spill_frames("desync");
+            }
+            SequenceCheck::ResyncNeeded => {
+                warn!("Depth stream gap rate too high; clearing order book to resync");
+                self.spill_frames("desync");
+                self.resync_book()?;
+            }
+        }
+
+        if self.depth_snapshots.is_some() && !self.streams.is_partial_depth() {
+            let step = self.book_sync.lock().unwrap_or_else(|e| e.into_inner()).on_diff(update);
+            return self.sync_book(step, received).await;
+        }
+        self.apply_diff(update, received)
+    }
+
+    /// Carry out a `BookSync` step: apply diffs or reload from a snapshot
+    ///
+    /// Frames queue up on the socket while the snapshot is fetched, which
+    /// is the buffering Binance asks for; the sync state covers the rest.
+    async fn sync_book(&self, mut step: SyncStep, received: ReceiveTime) -> Result<()> {
+        let Some((rest, limit)) = &self.depth_snapshots else {
+            return Ok(());
+        };
+
+        for _ in 0..MAX_SNAPSHOT_ATTEMPTS {
+            match step {
+                SyncStep::Apply(diffs) => {
+                    for update in diffs {
+                        self.apply_diff(update, received)?;
+                    }
+                    return Ok(());
+                }
+                SyncStep::Wait => return Ok(()),
+                SyncStep::NeedSnapshot => {
+                    info!("Loading {} order book snapshot", self.symbol);
+                    let snapshot = rest.get_depth_snapshot(&self.symbol, *limit).await?;
+                    self.load_snapshot(&snapshot, received)?;
+                    step = self.book_sync.lock().unwrap_or_else(|e| e.into_inner())
+                        .on_snapshot(snapshot.last_update_id);
+                }
+            }
+        }
+
+        Err(anyhow!("{} order book still out of sync after {} snapshots", self.symbol, MAX_SNAPSHOT_ATTEMPTS))
+    }
+
+    /// Replace the book with a REST snapshot
+    fn load_snapshot(&self, snapshot: &DepthSnapshot, received: ReceiveTime) -> Result<()> {
+        self.resync_book()?;
+        let bids = snapshot.parse_bids();
+        let asks = snapshot.parse_asks();
+        self.orderbook.apply_depth(&bids, &asks)?;
+
+        if let Some(expiry) = &self.level_expiry {
+            let mut expiry = expiry.lock().unwrap_or_else(|e| e.into_inner());
+            for (price, qty) in bids {
+                expiry.touch(Side::Buy, price, qty, received.instant);
+            }
+            for (price, qty) in asks {
+                expiry.touch(Side::Sell, price, qty, received.instant);
+            }
         }
+        Ok(())
+    }
 
-        // Update order book with asks
-        for (price, qty) in update.parse_asks() {
-            self.orderbook.update_level(crate::data::Side::Sell, price, qty)?;
+    /// Apply one in-sequence diff to the book and publish it
+    fn apply_diff(&self, update: DepthUpdate, received: ReceiveTime) -> Result<()> {
+        let bids = update.parse_bids();
+        let asks = update.parse_asks();
+
+        // Apply both sides as one update so readers never see half a diff
+        self.orderbook.apply_depth(&bids, &asks)?;
+
+        let uncrossed = self.book_validator.resolve_crossed(&self.orderbook, &bids, &asks)?;
+        if uncrossed > 0 {
+            debug!("Order book crossed; removed {} stale levels", uncrossed);
+        }
+        if let Some(limit) = &self.depth_limit {
+            limit.enforce(&self.orderbook)?;
         }
 
-        // Send event
-        let _ = self.event_tx.send(MarketEvent::DepthUpdate(update));
+        // Refresh level ages and drop phantom levels
+        if let Some(expiry) = &self.level_expiry {
+            let mut expiry = expiry.lock().unwrap_or_else(|e| e.into_inner());
+            let now = received.instant;
+            for (price, qty) in bids {
+                expiry.touch(Side::Buy, price, qty, now);
+            }
+            for (price, qty) in asks {
+                expiry.touch(Side::Sell, price, qty, now);
+            }
+            let expired = expiry.sweep(&self.orderbook, now)?;
+            if expired > 0 {
+                debug!("Expired {} stale order book levels", expired);
+            }
+        }
+
+        self.emit(received, MarketEvent::DepthUpdate(update));
 
         Ok(())
     }
 
+    /// Drop the whole book so it rebuilds from the live diff stream
+    fn resync_book(&self) -> Result<()> {
+        // Expiry only knows the levels it saw; clear the rest too
+        if let Some(expiry) = &self.level_expiry {
+            expiry.lock().unwrap_or_else(|e| e.into_inner()).full_refresh(&self.orderbook)?;
+        }
+        checksum::clear_book(&self.orderbook)
+    }
+
     /// Process aggregated trade
-    async fn process_agg_trade(&self, data: &serde_json::Value) -> Result<()> {
+    async fn process_agg_trade(&self, data: &serde_json::Value, received: ReceiveTime) -> Result<()> {
         let agg_trade: AggTrade = serde_json::from_value(data.clone())
             .map_err(|e| anyhow!("Failed to parse agg trade: {}", e))?;
 
+        self.sequencing.lock().unwrap_or_else(|e| e.into_inner())
+            .on_trade(agg_trade.agg_trade_id, agg_trade.event_time);
+        self.feed_latency.lock().unwrap_or_else(|e| e.into_inner()).record_at(agg_trade.event_time, received.wall_ms);
+
         if let Some(trade) = agg_trade.to_trade() {
-            let _ = self.event_tx.send(MarketEvent::Trade(trade));
+            self.emit(received, MarketEvent::Trade(trade));
         }
 
         Ok(())
     }
+
+    fn emit(&self, received: ReceiveTime, event: MarketEvent) {
+        let _ = self.event_tx.send(TimedEvent { received, event });
+    }
 }
 
 #[cfg(test)]
@@ -224,4 +684,75 @@ mod tests {
         let update: DepthUpdate = serde_json::from_value(wrapper.data).unwrap();
         assert_eq!(update.symbol, "BTCUSDT");
     }
+
+    #[tokio::test]
+    async fn test_gap_reloads_snapshot_and_replays() {
+        use rust_decimal_macros::dec;
+
+        let frame = |first: u64, last: u64, prev: u64, bid: &str, qty: &str| format!(
+            r#"{{"stream": "btcusdt@depth@100ms", "data": {{"e": "depthUpdate", "E": {}, "s": "BTCUSDT",
+                "U": {}, "u": {}, "pu": {}, "b": [["{}", "{}"]], "a": []}}}}"#,
+            last, first, last, prev, bid, qty,
+        );
+        let snapshot = |id: u64, bid: &str| format!(
+            r#"{{"lastUpdateId": {}, "bids": [["{}", "2"]], "asks": [["101", "1"]]}}"#, id, bid,
+        );
+
+        let mut server = mockito::Server::new_async().await;
+        let first = server.mock("GET", "/fapi/v1/depth")
+            .match_query(mockito::Matcher::Any)
+            .with_body(snapshot(105, "99"))
+            .expect(1)
+            .create_async().await;
+
+        let orderbook = Arc::new(OrderBook::new("BTCUSDT"));
+        let rest = Arc::new(BinanceRestClient::new(String::new(), String::new(), server.url()));
+        let (ws, _rx) = BinanceWebSocket::new("BTCUSDT".to_string(), "wss://unused".to_string(), Arc::clone(&orderbook));
+        let ws = ws.with_depth_snapshots(rest, 1000);
+        let received = ReceiveTime::now();
+
+        // Startup: snapshot at 105, the diff ending at 110 spans it
+        ws.process_message(&frame(101, 110, 100, "100", "1"), received).await.unwrap();
+        first.assert_async().await;
+        assert_eq!(orderbook.get_depth(5).0.len(), 2);
+        ws.process_message(&frame(111, 120, 110, "98", "3"), received).await.unwrap();
+        assert_eq!(orderbook.get_book_depth_count(), (3, 1));
+
+        // pu 125 != 120: diffs were lost, the book is rebuilt from a new snapshot
+        let second = server.mock("GET", "/fapi/v1/depth")
+            .match_query(mockito::Matcher::Any)
+            .with_body(snapshot(128, "97"))
+            .expect(1)
+            .create_async().await;
+        ws.process_message(&frame(126, 130, 125, "96", "4"), received).await.unwrap();
+        second.assert_async().await;
+
+        // Old levels are gone; snapshot plus the spanning diff remain
+        let (bids, _) = orderbook.get_depth(10);
+        let prices: Vec<_> = bids.iter().map(|level| level.price).collect();
+        assert_eq!(prices, vec![dec!(97), dec!(96)]);
+
+        ws.process_message(&frame(131, 140, 130, "95", "1"), received).await.unwrap();
+        assert_eq!(orderbook.get_book_depth_count(), (3, 1));
+        assert_eq!(ws.book_sync_metrics().snapshots.load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_reconnect_clears_book_without_level_ttl() {
+        use rust_decimal_macros::dec;
+
+        let orderbook = Arc::new(OrderBook::new("BTCUSDT"));
+        let (ws, _rx) = BinanceWebSocket::new("BTCUSDT".to_string(), "wss://unused".to_string(), Arc::clone(&orderbook));
+        orderbook.update_level(Side::Buy, dec!(100), dec!(1)).unwrap();
+        orderbook.update_level(Side::Sell, dec!(101), dec!(1)).unwrap();
+
+        ws.on_connected().unwrap();
+        assert_eq!(orderbook.get_book_depth_count(), (0, 0));
+
+        // With TTLs on, levels the expiry never saw are cleared as well
+        let ws = ws.with_level_ttl(Duration::from_secs(30));
+        orderbook.update_level(Side::Buy, dec!(100), dec!(1)).unwrap();
+        ws.on_connected().unwrap();
+        assert_eq!(orderbook.get_book_depth_count(), (0, 0));
+    }
 }
@@ -0,0 +1,154 @@
+use crate::data::{OrderBook, Side};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+
+/// An update addressed to a different symbol than the book it reached
+#[derive(Debug, Clone, Error)]
+#[error("{update} update rejected by the {book} order book")]
+pub struct SymbolMismatch {
+    pub book: String,
+    pub update: String,
+}
+
+/// Counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct BookValidationMetrics {
+    /// Updates rejected for carrying another symbol
+    pub symbol_mismatches: AtomicU64,
+    /// Times the book was found crossed (best bid >= best ask)
+    pub crossed_books: AtomicU64,
+    /// Levels removed to uncross the book
+    pub crossed_levels_removed: AtomicU64,
+}
+
+/// Guards one symbol's book against misrouted updates and crossed states
+///
+/// A crossed book means some levels are stale (a missed delete or a diff
+/// applied out of order). The side the latest update set at the top is
+/// taken as current and the other side's overlapping levels are removed;
+/// if the update set neither top, the whole overlap is removed since
+/// neither side can be trusted there.
+pub struct BookValidator {
+    symbol: String,
+    metrics: Arc<BookValidationMetrics>,
+}
+
+impl BookValidator {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Shared handle to the validation counters
+    pub fn metrics(&self) -> Arc<BookValidationMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Accept only updates for this book's symbol (case-insensitive)
+    pub fn check_symbol(&self, symbol: &str) -> Result<(), SymbolMismatch> {
+        if symbol.eq_ignore_ascii_case(&self.symbol) {
+            return Ok(());
+        }
+        self.metrics.symbol_mismatches.fetch_add(1, Ordering::Relaxed);
+        Err(SymbolMismatch {
+            book: self.symbol.clone(),
+            update: symbol.to_string(),
+        })
+    }
+
+    /// Uncross the book after applying `bids`/`asks`; returns levels removed
+    pub fn resolve_crossed(
+        &self,
+        orderbook: &OrderBook,
+        bids: &[(Decimal, Decimal)],
+        asks: &[(Decimal, Decimal)],
+    ) -> Result<usize> {
+        let (best_bid, best_ask) = match top_of_book(orderbook) {
+            (Some(bid), Some(ask)) if bid >= ask => (bid, ask),
+            _ => return Ok(0),
+        };
+        self.metrics.crossed_books.fetch_add(1, Ordering::Relaxed);
+
+        let set_by_update = |levels: &[(Decimal, Decimal)], price: Decimal| {
+            levels.iter().any(|(p, qty)| *p == price && !qty.is_zero())
+        };
+        let (drop_bids, drop_asks) = match (set_by_update(bids, best_bid), set_by_update(asks, best_ask)) {
+            (true, false) => (false, true),
+            (false, true) => (true, false),
+            _ => (true, true),
+        };
+
+        let (bid_levels, ask_levels) = orderbook.get_book_depth_count();
+        let (book_bids, book_asks) = orderbook.get_depth(bid_levels.max(ask_levels));
+
+        let mut removed = 0;
+        if drop_bids {
+            for level in book_bids.iter().filter(|level| level.price >= best_ask) {
+                orderbook.update_level(Side::Buy, level.price, Decimal::ZERO)?;
+                removed += 1;
+            }
+        }
+        if drop_asks {
+            for level in book_asks.iter().filter(|level| level.price <= best_bid) {
+                orderbook.update_level(Side::Sell, level.price, Decimal::ZERO)?;
+                removed += 1;
+            }
+        }
+
+        self.metrics.crossed_levels_removed.fetch_add(removed as u64, Ordering::Relaxed);
+        Ok(removed)
+    }
+}
+
+fn top_of_book(orderbook: &OrderBook) -> (Option<Decimal>, Option<Decimal>) {
+    let (bids, asks) = orderbook.get_depth(1);
+    (bids.first().map(|level| level.price), asks.first().map(|level| level.price))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_rejects_other_symbol() {
+        let validator = BookValidator::new("btcusdt");
+
+        assert!(validator.check_symbol("BTCUSDT").is_ok());
+        let mismatch = validator.check_symbol("ETHUSDT").unwrap_err();
+        assert_eq!(mismatch.book, "BTCUSDT");
+        assert_eq!(validator.metrics().symbol_mismatches.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_fresh_bid_removes_stale_asks() {
+        let ob = OrderBook::new("BTCUSDT");
+        let validator = BookValidator::new("BTCUSDT");
+
+        ob.update_level(Side::Buy, dec!(99), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(100), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(101), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(102), dec!(1)).unwrap();
+        assert_eq!(validator.resolve_crossed(&ob, &[], &[]).unwrap(), 0);
+
+        // A new bid at 101 crosses the asks at 100 and 101, which are stale
+        let bids = [(dec!(101), dec!(2))];
+        ob.update_level(Side::Buy, dec!(101), dec!(2)).unwrap();
+
+        assert_eq!(validator.resolve_crossed(&ob, &bids, &[]).unwrap(), 2);
+        assert_eq!(top_of_book(&ob), (Some(dec!(101)), Some(dec!(102))));
+
+        let metrics = validator.metrics();
+        assert_eq!(metrics.crossed_books.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.crossed_levels_removed.load(Ordering::Relaxed), 2);
+    }
+}
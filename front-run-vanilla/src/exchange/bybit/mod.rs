@@ -0,0 +1,7 @@
+pub mod types;
+pub mod websocket;
+pub mod rest;
+
+pub use types::*;
+pub use websocket::BybitWebSocket;
+pub use rest::BybitRestClient;
@@ -0,0 +1,262 @@
+use crate::data::types::{Side, Trade};
+use crate::exchange::binance::{DepthUpdate, OrderResponse};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Public v5 stream message (`{"topic": ..., "type": ..., "ts": ..., "data": ...}`)
+///
+/// Subscription acks and pongs carry no topic.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StreamMessage {
+    #[serde(default)]
+    pub topic: Option<String>,
+
+    /// "snapshot" or "delta"
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+
+    #[serde(default)]
+    pub ts: u64,
+
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// `orderbook.{depth}.{symbol}` payload
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BybitOrderbook {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "b")]
+    pub bids: Vec<[String; 2]>,
+
+    #[serde(rename = "a")]
+    pub asks: Vec<[String; 2]>,
+
+    /// Update id; 1 after a service restart (treat as a snapshot)
+    #[serde(rename = "u")]
+    pub update_id: u64,
+
+    #[serde(default)]
+    pub seq: u64,
+}
+
+impl BybitOrderbook {
+    /// As a Binance-shaped depth update, so the strategy sees one format
+    ///
+    /// Bybit deltas are contiguous, so the update covers `update_id` alone
+    /// and `prev_update_id` is the one before it.
+    pub fn to_depth_update(&self, event_time: u64, prev_update_id: Option<u64>) -> DepthUpdate {
+        DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time,
+            symbol: self.symbol.clone(),
+            first_update_id: self.update_id,
+            final_update_id: self.update_id,
+            prev_final_update_id: prev_update_id,
+            bids: self.bids.clone(),
+            asks: self.asks.clone(),
+        }
+    }
+}
+
+/// One entry of a `publicTrade.{symbol}` payload
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BybitTrade {
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    /// Taker side, "Buy" or "Sell"
+    #[serde(rename = "S")]
+    pub side: String,
+
+    #[serde(rename = "v")]
+    pub size: String,
+
+    #[serde(rename = "p")]
+    pub price: String,
+
+    /// Trade id (a UUID on linear perpetuals)
+    #[serde(rename = "i")]
+    pub trade_id: String,
+}
+
+impl BybitTrade {
+    /// Convert to our Trade type
+    pub fn to_trade(&self) -> Option<Trade> {
+        let price = self.price.parse::<Decimal>().ok()?;
+        let quantity = self.size.parse::<Decimal>().ok()?;
+        let side = parse_side(&self.side)?;
+
+        Some(Trade {
+            id: trade_id(&self.trade_id),
+            price,
+            quantity,
+            side,
+            timestamp: UNIX_EPOCH + Duration::from_millis(self.trade_time),
+            is_buyer_maker: side == Side::Sell,
+        })
+    }
+}
+
+/// Numeric trade ids as is; UUIDs folded into a u64 (FNV-1a)
+fn trade_id(id: &str) -> u64 {
+    id.parse().unwrap_or_else(|_| {
+        id.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    })
+}
+
+pub(crate) fn parse_side(side: &str) -> Option<Side> {
+    match side {
+        "Buy" => Some(Side::Buy),
+        "Sell" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+/// REST response envelope; `ret_code` 0 is success
+#[derive(Debug, Clone, Deserialize)]
+pub struct BybitResponse<T> {
+    #[serde(rename = "retCode")]
+    pub ret_code: i64,
+
+    #[serde(rename = "retMsg")]
+    pub ret_msg: String,
+
+    pub result: T,
+}
+
+/// `result` of list endpoints
+#[derive(Debug, Clone, Deserialize)]
+pub struct BybitList<T> {
+    #[serde(default = "Vec::new")]
+    pub list: Vec<T>,
+}
+
+/// Order from `/v5/order/realtime` or `/v5/order/history`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BybitOrder {
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+
+    #[serde(rename = "orderLinkId", default)]
+    pub order_link_id: String,
+
+    pub symbol: String,
+
+    #[serde(default)]
+    pub price: String,
+
+    pub qty: String,
+
+    pub side: String,
+
+    /// "New", "PartiallyFilled", "Filled", "Cancelled", "Rejected", ...
+    #[serde(rename = "orderStatus")]
+    pub order_status: String,
+
+    #[serde(rename = "avgPrice", default)]
+    pub avg_price: String,
+
+    #[serde(rename = "cumExecQty", default)]
+    pub cum_exec_qty: String,
+
+    #[serde(rename = "timeInForce", default)]
+    pub time_in_force: String,
+
+    #[serde(rename = "orderType")]
+    pub order_type: String,
+
+    #[serde(rename = "updatedTime", default)]
+    pub updated_time: String,
+}
+
+impl BybitOrder {
+    /// Whether the order can no longer fill
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self.order_status.as_str(),
+            "Filled" | "Cancelled" | "Rejected" | "PartiallyFilledCanceled" | "Deactivated"
+        )
+    }
+
+    /// As the Binance-shaped order report the execution engine reads;
+    /// `order_id` is our numeric id, sent as the `orderLinkId`
+    pub fn to_order_response(&self, order_id: u64) -> OrderResponse {
+        let status = match self.order_status.as_str() {
+            "New" | "Created" | "Untriggered" => "NEW",
+            "PartiallyFilled" => "PARTIALLY_FILLED",
+            "Filled" => "FILLED",
+            "Cancelled" | "PartiallyFilledCanceled" => "CANCELED",
+            "Rejected" => "REJECTED",
+            _ => "EXPIRED",
+        };
+        let or_zero = |value: &str| if value.is_empty() { "0".to_string() } else { value.to_string() };
+
+        OrderResponse {
+            order_id,
+            symbol: self.symbol.clone(),
+            client_order_id: self.order_id.clone(),
+            price: or_zero(&self.price),
+            avg_price: or_zero(&self.avg_price),
+            orig_qty: self.qty.clone(),
+            executed_qty: or_zero(&self.cum_exec_qty),
+            status: status.to_string(),
+            time_in_force: self.time_in_force.clone(),
+            order_type: self.order_type.to_uppercase(),
+            side: self.side.to_uppercase(),
+            update_time: self.updated_time.parse().unwrap_or(0),
+        }
+    }
+}
+
+/// Account from `/v5/account/wallet-balance`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BybitWallet {
+    /// Equity in USD across the unified account
+    #[serde(rename = "totalEquity", default)]
+    pub total_equity: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trades_and_orders() {
+        let message: StreamMessage = serde_json::from_str(r#"{
+            "topic": "publicTrade.BTCUSDT", "type": "snapshot", "ts": 1672304486868,
+            "data": [{"T": 1672304486865, "s": "BTCUSDT", "S": "Sell", "v": "0.001",
+                      "p": "16578.50", "L": "PlusTick", "i": "20f43950-d8dd-5b31-9112-a178eb6023af", "BT": false}]
+        }"#).unwrap();
+        let trades: Vec<BybitTrade> = serde_json::from_value(message.data).unwrap();
+        let trade = trades[0].to_trade().unwrap();
+        assert_eq!(trade.side, Side::Sell);
+        assert!(trade.is_buyer_maker);
+        assert_eq!(trade.price, Decimal::new(1657850, 2));
+        assert_eq!(trade.id, trade_id("20f43950-d8dd-5b31-9112-a178eb6023af"));
+        assert_eq!(trade_id("12345"), 12345);
+
+        let order: BybitOrder = serde_json::from_str(r#"{
+            "orderId": "fd4300ae-7847-404e-b947-b46980a4d140", "orderLinkId": "42",
+            "symbol": "ETHUSDT", "price": "", "qty": "0.10", "side": "Buy",
+            "orderStatus": "Filled", "avgPrice": "1805.15", "cumExecQty": "0.10",
+            "timeInForce": "IOC", "orderType": "Market", "updatedTime": "1672217748277"
+        }"#).unwrap();
+        assert!(order.is_final());
+        let response = order.to_order_response(42);
+        assert_eq!(response.order_id, 42);
+        assert_eq!(response.status, "FILLED");
+        assert_eq!(response.side, "BUY");
+        assert_eq!(response.executed_qty, "0.10");
+        assert_eq!(response.price, "0");
+        assert_eq!(response.update_time, 1672217748277);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::data::{OrderBook, Side};
+use crate::exchange::binance::{MarketEvent, ReceiveTime, TimedEvent, WsShutdown};
+use crate::exchange::bybit::types::{BybitOrderbook, BybitTrade, StreamMessage};
+use crate::exchange::checksum;
+use crate::exchange::MarketDataStream;
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info};
+
+/// Order book depth subscribed by default (1, 50, 200 or 500 on linear)
+pub const DEFAULT_DEPTH: usize = 50;
+
+/// Bybit expects an application-level ping at least every 20s
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Bybit USDT perpetual depth and trade stream
+///
+/// Feeds the same `OrderBook` and emits the same `MarketEvent`s as
+/// `BinanceWebSocket`: book messages become `DepthUpdate`s and public
+/// trades become `Trade`s, so the strategy runs unchanged on either venue.
+pub struct BybitWebSocket {
+    symbol: String,
+    ws_url: String,
+    depth: usize,
+    event_tx: mpsc::UnboundedSender<TimedEvent>,
+    orderbook: Arc<OrderBook>,
+    /// Last applied book update id on the current connection
+    last_update_id: Mutex<Option<u64>>,
+    shutdown: WsShutdown,
+}
+
+impl BybitWebSocket {
+    /// `ws_endpoint` is the public linear stream, e.g.
+    /// wss://stream.bybit.com/v5/public/linear
+    pub fn new(
+        symbol: String,
+        ws_endpoint: String,
+        orderbook: Arc<OrderBook>,
+    ) -> (Self, mpsc::UnboundedReceiver<TimedEvent>) {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        (
+            Self {
+                symbol: symbol.to_uppercase(),
+                ws_url: ws_endpoint,
+                depth: DEFAULT_DEPTH,
+                event_tx,
+                orderbook,
+                last_update_id: Mutex::new(None),
+                shutdown: WsShutdown::new(),
+            },
+            event_rx,
+        )
+    }
+
+    /// Subscribe to a different book depth
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Handle that stops `run` from another task (keep it before spawning)
+    pub fn shutdown_handle(&self) -> WsShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Close the current connection and stop reconnecting
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+
+    fn topics(&self) -> Vec<String> {
+        vec![
+            format!("orderbook.{}.{}", self.depth, self.symbol),
+            format!("publicTrade.{}", self.symbol),
+        ]
+    }
+
+    /// Start WebSocket connection with auto-reconnect
+    ///
+    /// Runs until `shutdown()` is called. A gap in book updates drops the
+    /// connection; the resubscription starts from a fresh snapshot.
+    pub async fn run(&self) {
+        let mut reconnect_delay = Duration::from_secs(1);
+        let max_reconnect_delay = Duration::from_secs(60);
+        let mut shutdown = self.shutdown.subscribe();
+
+        while !self.shutdown.is_requested() {
+            info!("Connecting to Bybit WebSocket: {}", self.ws_url);
+
+            match self.connect_and_process().await {
+                Ok(_) => {
+                    info!("Bybit WebSocket connection closed normally");
+                    reconnect_delay = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    error!("Bybit WebSocket error: {}. Reconnecting in {:?}", e, reconnect_delay);
+                    self.emit(ReceiveTime::now(), MarketEvent::Disconnected);
+
+                    tokio::select! {
+                        _ = tokio::time::sleep(reconnect_delay) => {}
+                        _ = shutdown.changed() => {}
+                    }
+                    reconnect_delay = std::cmp::min(reconnect_delay * 2, max_reconnect_delay);
+                }
+            }
+        }
+    }
+
+    async fn connect_and_process(&self) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.ws_url).await
+            .map_err(|e| anyhow!("WebSocket connection failed: {}", e))?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let request = serde_json::json!({ "op": "subscribe", "args": self.topics() });
+        write.send(Message::Text(request.to_string())).await
+            .map_err(|e| anyhow!("Failed to subscribe: {}", e))?;
+
+        // The subscription opens with a snapshot; drop whatever was left
+        *self.last_update_id.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        checksum::clear_book(&self.orderbook)?;
+        info!("Bybit WebSocket connected: {}", self.topics().join(", "));
+        self.emit(ReceiveTime::now(), MarketEvent::Connected);
+
+        let mut shutdown = self.shutdown.subscribe();
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        ping.tick().await;
+
+        loop {
+            if self.shutdown.is_requested() {
+                let _ = write.send(Message::Close(None)).await;
+                self.emit(ReceiveTime::now(), MarketEvent::Disconnected);
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = shutdown.changed() => {}
+
+                _ = ping.tick() => {
+                    write.send(Message::Text(r#"{"op":"ping"}"#.to_string())).await
+                        .map_err(|e| anyhow!("Failed to send ping: {}", e))?;
+                }
+
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(Message::Text(text))) => self.process_message(&text, ReceiveTime::now())?,
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Err(e)) => return Err(anyhow!("WebSocket error: {}", e)),
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply one stream message to the book and emit its events
+    ///
+    /// Only a book update gap is an error (the caller reconnects);
+    /// unparseable messages are logged and skipped.
+    fn process_message(&self, text: &str, received: ReceiveTime) -> Result<()> {
+        let message: StreamMessage = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(e) => {
+                debug!("Unparseable Bybit message: {}", e);
+                return Ok(());
+            }
+        };
+        // Subscription acks and pongs
+        let Some(topic) = message.topic else {
+            return Ok(());
+        };
+
+        if topic.starts_with("orderbook.") {
+            let book: BybitOrderbook = match serde_json::from_value(message.data) {
+                Ok(book) => book,
+                Err(e) => {
+                    debug!("Error parsing Bybit order book: {}", e);
+                    return Ok(());
+                }
+            };
+            let mut last_update_id = self.last_update_id.lock().unwrap_or_else(|e| e.into_inner());
+            let snapshot = message.kind.as_deref() == Some("snapshot") || book.update_id == 1;
+            if snapshot {
+                checksum::clear_book(&self.orderbook)?;
+            } else {
+                match *last_update_id {
+                    Some(last) if book.update_id <= last => return Ok(()),
+                    Some(last) if book.update_id == last + 1 => {}
+                    last => {
+                        return Err(anyhow!(
+                            "{} book update gap: {:?} -> {}", self.symbol, last, book.update_id
+                        ));
+                    }
+                }
+            }
+
+            let update = book.to_depth_update(message.ts, *last_update_id);
+            for (price, qty) in update.parse_bids() {
+                self.orderbook.update_level(Side::Buy, price, qty)?;
+            }
+            for (price, qty) in update.parse_asks() {
+                self.orderbook.update_level(Side::Sell, price, qty)?;
+            }
+            *last_update_id = Some(book.update_id);
+            self.emit(received, MarketEvent::DepthUpdate(update));
+        } else if topic.starts_with("publicTrade.") {
+            let trades: Vec<BybitTrade> = match serde_json::from_value(message.data) {
+                Ok(trades) => trades,
+                Err(e) => {
+                    debug!("Error parsing Bybit trades: {}", e);
+                    return Ok(());
+                }
+            };
+            for trade in trades.iter().filter_map(BybitTrade::to_trade) {
+                self.emit(received, MarketEvent::Trade(trade));
+            }
+        }
+        Ok(())
+    }
+
+    fn emit(&self, received: ReceiveTime, event: MarketEvent) {
+        let _ = self.event_tx.send(TimedEvent { received, event });
+    }
+}
+
+impl MarketDataStream for BybitWebSocket {
+    async fn run(&self) {
+        BybitWebSocket::run(self).await
+    }
+
+    fn shutdown(&self) {
+        BybitWebSocket::shutdown(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_delta_and_gap() {
+        let book = Arc::new(OrderBook::new("BTCUSDT"));
+        let (ws, mut rx) = BybitWebSocket::new(
+            "btcusdt".to_string(),
+            "wss://stream.bybit.com/v5/public/linear".to_string(),
+            Arc::clone(&book),
+        );
+        assert_eq!(ws.topics(), vec!["orderbook.50.BTCUSDT", "publicTrade.BTCUSDT"]);
+
+        let snapshot = r#"{"topic": "orderbook.50.BTCUSDT", "type": "snapshot", "ts": 1000,
+            "data": {"s": "BTCUSDT", "b": [["100.0", "1"], ["99.5", "2"]], "a": [["100.5", "3"]], "u": 10, "seq": 1}}"#;
+        ws.process_message(snapshot, ReceiveTime::now()).unwrap();
+        assert_eq!(book.get_book_depth_count(), (2, 1));
+
+        let delta = r#"{"topic": "orderbook.50.BTCUSDT", "type": "delta", "ts": 1100,
+            "data": {"s": "BTCUSDT", "b": [["99.5", "0"]], "a": [], "u": 11, "seq": 2}}"#;
+        ws.process_message(delta, ReceiveTime::now()).unwrap();
+        assert_eq!(book.get_book_depth_count(), (1, 1));
+
+        let events: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert_eq!(events.len(), 2);
+        match &events[1].event {
+            MarketEvent::DepthUpdate(update) => {
+                assert_eq!(update.final_update_id, 11);
+                assert_eq!(update.prev_final_update_id, Some(10));
+                assert_eq!(update.event_time, 1100);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        // Update 12 never arrived
+        let gapped = delta.replace(r#""u": 11"#, r#""u": 13"#);
+        assert!(ws.process_message(&gapped, ReceiveTime::now()).is_err());
+
+        let trades = r#"{"topic": "publicTrade.BTCUSDT", "type": "snapshot", "ts": 1200,
+            "data": [{"T": 1199, "s": "BTCUSDT", "S": "Buy", "v": "0.5", "p": "100.5", "i": "7"}]}"#;
+        ws.process_message(trades, ReceiveTime::now()).unwrap();
+        assert!(matches!(rx.try_recv().unwrap().event, MarketEvent::Trade(trade) if trade.side == Side::Buy));
+
+        // Pongs are ignored
+        ws.process_message(r#"{"success": true, "ret_msg": "pong", "op": "ping"}"#, ReceiveTime::now()).unwrap();
+    }
+}
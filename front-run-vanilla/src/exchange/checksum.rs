@@ -0,0 +1,231 @@
+use crate::data::{OrderBook, Side};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+/// Order book checksum format published by the venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumScheme {
+    /// OKX: top 25 levels interleaved `bid_px:bid_sz:ask_px:ask_sz:...`,
+    /// CRC32 sent as a signed i32
+    Okx,
+    /// Kraken: top 10 asks then top 10 bids, each price and qty with the
+    /// decimal point and leading zeros removed, concatenated, CRC32
+    Kraken,
+}
+
+impl ChecksumScheme {
+    fn depth(&self) -> usize {
+        match self {
+            ChecksumScheme::Okx => 25,
+            ChecksumScheme::Kraken => 10,
+        }
+    }
+}
+
+/// Book state diverged from the venue's checksum
+///
+/// Carries enough of the local book to diagnose the desync (missed delete,
+/// out-of-order diff, price formatting drift) from logs alone.
+#[derive(Debug, Clone, Error)]
+#[error("{symbol} book corrupted: checksum {computed} != expected {expected} ({bid_levels} bids / {ask_levels} asks, top {best_bid:?} / {best_ask:?})")]
+pub struct BookCorrupted {
+    pub symbol: String,
+    pub scheme: ChecksumScheme,
+    pub expected: u32,
+    pub computed: u32,
+    pub best_bid: Option<Decimal>,
+    pub best_ask: Option<Decimal>,
+    pub bid_levels: usize,
+    pub ask_levels: usize,
+    /// String the local checksum was computed over
+    pub payload: String,
+}
+
+/// Validates the local book against venue checksums after each diff
+///
+/// Binance does not publish checksums; this is for connectors that do.
+/// On mismatch the book is cleared so it rebuilds from a fresh snapshot,
+/// and the returned `BookCorrupted` should be forwarded as
+/// `MarketEvent::BookCorrupted`.
+pub struct ChecksumValidator {
+    symbol: String,
+    scheme: ChecksumScheme,
+    checks: u64,
+    mismatches: u64,
+}
+
+impl ChecksumValidator {
+    pub fn new(symbol: &str, scheme: ChecksumScheme) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            scheme,
+            checks: 0,
+            mismatches: 0,
+        }
+    }
+
+    /// Checksum of the local book under this scheme
+    pub fn compute(&self, orderbook: &OrderBook) -> u32 {
+        crc32(self.payload(orderbook).as_bytes())
+    }
+
+    /// Compare the local book with the venue checksum
+    ///
+    /// OKX sends a signed value; pass it as `checksum as u32`.
+    pub fn validate(&mut self, orderbook: &OrderBook, expected: u32) -> Result<(), BookCorrupted> {
+        self.checks += 1;
+
+        let payload = self.payload(orderbook);
+        let computed = crc32(payload.as_bytes());
+        if computed == expected {
+            return Ok(());
+        }
+
+        self.mismatches += 1;
+        let (bid_levels, ask_levels) = orderbook.get_book_depth_count();
+        let (bids, asks) = orderbook.get_depth(1);
+
+        Err(BookCorrupted {
+            symbol: self.symbol.clone(),
+            scheme: self.scheme,
+            expected,
+            computed,
+            best_bid: bids.first().map(|level| level.price),
+            best_ask: asks.first().map(|level| level.price),
+            bid_levels,
+            ask_levels,
+            payload,
+        })
+    }
+
+    /// Validate and, on mismatch, clear the book so it resyncs from snapshot
+    pub fn validate_or_resync(&mut self, orderbook: &OrderBook, expected: u32) -> Result<Option<BookCorrupted>> {
+        match self.validate(orderbook, expected) {
+            Ok(()) => Ok(None),
+            Err(corrupted) => {
+                clear_book(orderbook)?;
+                Ok(Some(corrupted))
+            }
+        }
+    }
+
+    /// Checksums validated since start
+    pub fn checks(&self) -> u64 {
+        self.checks
+    }
+
+    /// Checksum mismatches since start
+    pub fn mismatches(&self) -> u64 {
+        self.mismatches
+    }
+
+    fn payload(&self, orderbook: &OrderBook) -> String {
+        let (bids, asks) = orderbook.get_depth(self.scheme.depth());
+
+        match self.scheme {
+            ChecksumScheme::Okx => {
+                let mut parts = Vec::with_capacity((bids.len() + asks.len()) * 2);
+                for i in 0..bids.len().max(asks.len()) {
+                    if let Some(bid) = bids.get(i) {
+                        parts.push(bid.price.to_string());
+                        parts.push(bid.quantity.to_string());
+                    }
+                    if let Some(ask) = asks.get(i) {
+                        parts.push(ask.price.to_string());
+                        parts.push(ask.quantity.to_string());
+                    }
+                }
+                parts.join(":")
+            }
+            ChecksumScheme::Kraken => {
+                let compact = |value: Decimal| {
+                    let digits = value.to_string().replace('.', "");
+                    digits.trim_start_matches('0').to_string()
+                };
+
+                asks.iter()
+                    .chain(bids.iter())
+                    .flat_map(|level| [compact(level.price), compact(level.quantity)])
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Remove every level from the book
+pub fn clear_book(orderbook: &OrderBook) -> Result<()> {
+    let (bid_levels, ask_levels) = orderbook.get_book_depth_count();
+    let (bids, asks) = orderbook.get_depth(bid_levels.max(ask_levels));
+
+    for level in bids {
+        orderbook.update_level(Side::Buy, level.price, Decimal::ZERO)?;
+    }
+    for level in asks {
+        orderbook.update_level(Side::Sell, level.price, Decimal::ZERO)?;
+    }
+    Ok(())
+}
+
+/// CRC-32 (IEEE 802.3), as used by OKX and Kraken
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBook {
+        let ob = OrderBook::new("BTC-USDT");
+        ob.update_level(Side::Buy, dec!(3366.1), dec!(7)).unwrap();
+        ob.update_level(Side::Buy, dec!(3366), dec!(6)).unwrap();
+        ob.update_level(Side::Sell, dec!(3366.8), dec!(9)).unwrap();
+        ob
+    }
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_payload_formats() {
+        let ob = book();
+
+        let okx = ChecksumValidator::new("BTC-USDT", ChecksumScheme::Okx);
+        assert_eq!(okx.payload(&ob), "3366.1:7:3366.8:9:3366:6");
+
+        let kraken = ChecksumValidator::new("BTC-USDT", ChecksumScheme::Kraken);
+        assert_eq!(kraken.payload(&ob), "33668933661733666");
+    }
+
+    #[test]
+    fn test_mismatch_reports_and_resyncs() {
+        let ob = book();
+        let mut validator = ChecksumValidator::new("BTC-USDT", ChecksumScheme::Okx);
+
+        let expected = validator.compute(&ob);
+        assert!(validator.validate_or_resync(&ob, expected).unwrap().is_none());
+
+        // A missed delete leaves a phantom level behind
+        ob.update_level(Side::Buy, dec!(3365), dec!(1)).unwrap();
+        let corrupted = validator.validate_or_resync(&ob, expected).unwrap().unwrap();
+
+        assert_eq!(corrupted.expected, expected);
+        assert_eq!(corrupted.bid_levels, 3);
+        assert_eq!(corrupted.best_bid, Some(dec!(3366.1)));
+        assert_eq!(validator.mismatches(), 1);
+        assert_eq!(validator.checks(), 2);
+        assert_eq!(ob.get_book_depth_count(), (0, 0));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::data::{OrderBook, Side};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct DepthLimitMetrics {
+    /// Far levels evicted to stay within the cap
+    pub evicted_levels: AtomicU64,
+}
+
+/// Caps how many levels per side a book retains
+///
+/// The diff stream reports every level that changes, however far from mid,
+/// and a long session accumulates thousands of them. Only the best
+/// `max_levels` per side are kept; anything beyond is evicted through the
+/// normal delete path. An evicted level is re-added by its next diff, so
+/// depth right at the cap can be understated: keep the cap well above the
+/// deepest level any signal reads.
+pub struct DepthLimit {
+    max_levels: usize,
+    metrics: Arc<DepthLimitMetrics>,
+}
+
+impl DepthLimit {
+    pub fn new(max_levels: usize) -> Self {
+        Self {
+            max_levels,
+            metrics: Default::default(),
+        }
+    }
+
+    pub fn max_levels(&self) -> usize {
+        self.max_levels
+    }
+
+    /// Shared handle to the eviction counters
+    pub fn metrics(&self) -> Arc<DepthLimitMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Evict levels beyond the best `max_levels` per side; returns the count
+    pub fn enforce(&self, orderbook: &OrderBook) -> Result<usize> {
+        let (bid_levels, ask_levels) = orderbook.get_book_depth_count();
+        if bid_levels <= self.max_levels && ask_levels <= self.max_levels {
+            return Ok(0);
+        }
+
+        let (bids, asks) = orderbook.get_depth(bid_levels.max(ask_levels));
+        let mut evicted = 0;
+        for (side, levels) in [(Side::Buy, &bids), (Side::Sell, &asks)] {
+            for level in levels.iter().skip(self.max_levels) {
+                orderbook.update_level(side, level.price, Decimal::ZERO)?;
+                evicted += 1;
+            }
+        }
+
+        self.metrics.evicted_levels.fetch_add(evicted as u64, Ordering::Relaxed);
+        Ok(evicted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_evicts_far_levels_only() {
+        let ob = OrderBook::new("BTCUSDT");
+        let limit = DepthLimit::new(2);
+
+        for i in 0..4 {
+            ob.update_level(Side::Buy, dec!(99) - Decimal::from(i), dec!(1)).unwrap();
+        }
+        ob.update_level(Side::Sell, dec!(100), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(101), dec!(1)).unwrap();
+
+        assert_eq!(limit.enforce(&ob).unwrap(), 2);
+        assert_eq!(ob.get_book_depth_count(), (2, 2));
+
+        let (bids, _) = ob.get_depth(2);
+        assert_eq!(bids.iter().map(|level| level.price).collect::<Vec<_>>(), vec![dec!(99), dec!(98)]);
+
+        assert_eq!(limit.enforce(&ob).unwrap(), 0);
+        assert_eq!(limit.metrics().evicted_levels.load(Ordering::Relaxed), 2);
+    }
+}
@@ -1,4 +1,16 @@
 pub mod binance;
+pub mod bybit;
+pub mod checksum;
+pub mod book_validation;
+pub mod depth_limit;
+pub mod okx;
+pub mod venue;
 
 // Re-export commonly used items
-pub use binance::{BinanceWebSocket, BinanceRestClient, MarketEvent};
+pub use binance::{BinanceWebSocket, BinanceRestClient, MarketEvent, TimedEvent};
+pub use bybit::{BybitRestClient, BybitWebSocket};
+pub use checksum::{BookCorrupted, ChecksumScheme, ChecksumValidator};
+pub use book_validation::{BookValidationMetrics, BookValidator, SymbolMismatch};
+pub use depth_limit::{DepthLimit, DepthLimitMetrics};
+pub use okx::{OkxRestClient, OkxWebSocket};
+pub use venue::{Exchange, MarketDataStream};
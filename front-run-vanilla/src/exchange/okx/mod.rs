@@ -0,0 +1,7 @@
+pub mod types;
+pub mod websocket;
+pub mod rest;
+
+pub use types::*;
+pub use websocket::OkxWebSocket;
+pub use rest::OkxRestClient;
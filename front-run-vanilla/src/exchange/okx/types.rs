@@ -0,0 +1,273 @@
+use crate::data::types::{Side, Trade};
+use crate::exchange::binance::{DepthUpdate, OrderResponse};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Channel and instrument a push message belongs to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChannelArg {
+    pub channel: String,
+    #[serde(rename = "instId", default)]
+    pub inst_id: String,
+}
+
+/// Public v5 push message (`{"arg": ..., "action": ..., "data": [...]}`)
+///
+/// Subscription acks and errors carry an `event` instead of data.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PushMessage {
+    #[serde(default)]
+    pub arg: Option<ChannelArg>,
+
+    /// "snapshot" or "update" (books only)
+    #[serde(default)]
+    pub action: Option<String>,
+
+    /// "subscribe", "unsubscribe" or "error"
+    #[serde(default)]
+    pub event: Option<String>,
+
+    #[serde(default)]
+    pub msg: String,
+
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// One `books` channel entry
+///
+/// Levels are `[price, size, "0", order_count]`; sizes of swaps are in
+/// contracts. The strings are kept as sent, since the checksum is computed
+/// over them.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OkxBook {
+    pub asks: Vec<Vec<String>>,
+    pub bids: Vec<Vec<String>>,
+
+    /// Milliseconds since UNIX epoch, as a string
+    pub ts: String,
+
+    /// CRC32 of the top 25 levels after this update, signed
+    #[serde(default)]
+    pub checksum: Option<i64>,
+
+    /// `seq_id` of the previous update (-1 on snapshots)
+    #[serde(rename = "prevSeqId", default)]
+    pub prev_seq_id: i64,
+
+    #[serde(rename = "seqId", default)]
+    pub seq_id: i64,
+}
+
+impl OkxBook {
+    /// As a Binance-shaped depth update, so the strategy sees one format
+    pub fn to_depth_update(&self, inst_id: &str) -> DepthUpdate {
+        let levels = |levels: &[Vec<String>]| -> Vec<[String; 2]> {
+            levels.iter()
+                .filter(|level| level.len() >= 2)
+                .map(|level| [level[0].clone(), level[1].clone()])
+                .collect()
+        };
+
+        DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: self.ts.parse().unwrap_or(0),
+            symbol: inst_id.to_string(),
+            first_update_id: self.seq_id.max(0) as u64,
+            final_update_id: self.seq_id.max(0) as u64,
+            prev_final_update_id: (self.prev_seq_id >= 0).then_some(self.prev_seq_id as u64),
+            bids: levels(&self.bids),
+            asks: levels(&self.asks),
+        }
+    }
+}
+
+/// One `trades` channel entry
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OkxTrade {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+
+    #[serde(rename = "tradeId")]
+    pub trade_id: String,
+
+    pub px: String,
+
+    /// Contracts for swaps
+    pub sz: String,
+
+    /// Taker side, "buy" or "sell"
+    pub side: String,
+
+    pub ts: String,
+}
+
+impl OkxTrade {
+    /// Convert to our Trade type
+    pub fn to_trade(&self) -> Option<Trade> {
+        let price = self.px.parse::<Decimal>().ok()?;
+        let quantity = self.sz.parse::<Decimal>().ok()?;
+        let side = parse_side(&self.side)?;
+
+        Some(Trade {
+            id: self.trade_id.parse().ok()?,
+            price,
+            quantity,
+            side,
+            timestamp: UNIX_EPOCH + Duration::from_millis(self.ts.parse().ok()?),
+            is_buyer_maker: side == Side::Sell,
+        })
+    }
+}
+
+pub(crate) fn parse_side(side: &str) -> Option<Side> {
+    match side {
+        "buy" => Some(Side::Buy),
+        "sell" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+/// REST response envelope; `code` "0" is success
+#[derive(Debug, Clone, Deserialize)]
+pub struct OkxResponse<T> {
+    pub code: String,
+
+    #[serde(default)]
+    pub msg: String,
+
+    #[serde(default = "Vec::new")]
+    pub data: Vec<T>,
+}
+
+/// Per-order result of place and cancel requests
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OkxOrderAck {
+    #[serde(rename = "ordId", default)]
+    pub ord_id: String,
+
+    #[serde(rename = "sCode", default)]
+    pub s_code: String,
+
+    #[serde(rename = "sMsg", default)]
+    pub s_msg: String,
+}
+
+/// Order from `/api/v5/trade/order`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OkxOrder {
+    #[serde(rename = "ordId")]
+    pub ord_id: String,
+
+    #[serde(rename = "clOrdId", default)]
+    pub cl_ord_id: String,
+
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+
+    #[serde(default)]
+    pub px: String,
+
+    pub sz: String,
+
+    pub side: String,
+
+    #[serde(rename = "ordType")]
+    pub ord_type: String,
+
+    /// "live", "partially_filled", "filled", "canceled", "mmp_canceled"
+    pub state: String,
+
+    #[serde(rename = "avgPx", default)]
+    pub avg_px: String,
+
+    #[serde(rename = "accFillSz", default)]
+    pub acc_fill_sz: String,
+
+    #[serde(rename = "uTime", default)]
+    pub u_time: String,
+}
+
+impl OkxOrder {
+    /// Whether the order can no longer fill
+    pub fn is_final(&self) -> bool {
+        matches!(self.state.as_str(), "filled" | "canceled" | "mmp_canceled")
+    }
+
+    /// As the Binance-shaped order report the execution engine reads;
+    /// `order_id` is our numeric id, sent as the `clOrdId`
+    pub fn to_order_response(&self, order_id: u64) -> OrderResponse {
+        let status = match self.state.as_str() {
+            "live" => "NEW",
+            "partially_filled" => "PARTIALLY_FILLED",
+            "filled" => "FILLED",
+            _ => "CANCELED",
+        };
+        let or_zero = |value: &str| if value.is_empty() { "0".to_string() } else { value.to_string() };
+        let time_in_force = match self.ord_type.as_str() {
+            "market" | "ioc" | "optimal_limit_ioc" => "IOC",
+            "fok" => "FOK",
+            "post_only" => "GTX",
+            _ => "GTC",
+        };
+
+        OrderResponse {
+            order_id,
+            symbol: self.inst_id.clone(),
+            client_order_id: self.ord_id.clone(),
+            price: or_zero(&self.px),
+            avg_price: or_zero(&self.avg_px),
+            orig_qty: self.sz.clone(),
+            executed_qty: or_zero(&self.acc_fill_sz),
+            status: status.to_string(),
+            time_in_force: time_in_force.to_string(),
+            order_type: self.ord_type.to_uppercase(),
+            side: self.side.to_uppercase(),
+            update_time: self.u_time.parse().unwrap_or(0),
+        }
+    }
+}
+
+/// Account from `/api/v5/account/balance`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OkxBalance {
+    /// Total equity in USD
+    #[serde(rename = "totalEq", default)]
+    pub total_eq: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_books_trades_and_orders() {
+        let message: PushMessage = serde_json::from_str(r#"{
+            "arg": {"channel": "books", "instId": "BTC-USDT-SWAP"},
+            "action": "update",
+            "data": [{"asks": [["8476.98", "415", "0", "13"]], "bids": [["8476.5", "0", "0", "0"]],
+                      "ts": "1597026383085", "checksum": -855196043, "prevSeqId": 123455, "seqId": 123456}]
+        }"#).unwrap();
+        let books: Vec<OkxBook> = serde_json::from_value(message.data).unwrap();
+        let update = books[0].to_depth_update("BTC-USDT-SWAP");
+        assert_eq!(update.event_time, 1597026383085);
+        assert_eq!(update.prev_final_update_id, Some(123455));
+        assert_eq!(update.parse_asks(), vec![(Decimal::new(847698, 2), Decimal::from(415))]);
+        assert_eq!(books[0].checksum, Some(-855196043));
+
+        let trade: OkxTrade = serde_json::from_str(r#"{"instId": "BTC-USDT-SWAP", "tradeId": "130639474",
+            "px": "42219.9", "sz": "12", "side": "sell", "ts": "1630048897897", "count": "3"}"#).unwrap();
+        let trade = trade.to_trade().unwrap();
+        assert_eq!((trade.id, trade.side, trade.is_buyer_maker), (130639474, Side::Sell, true));
+
+        let order: OkxOrder = serde_json::from_str(r#"{"ordId": "312269865356374016", "clOrdId": "42",
+            "instId": "BTC-USDT-SWAP", "px": "", "sz": "2", "side": "buy", "ordType": "market",
+            "state": "filled", "avgPx": "42220.1", "accFillSz": "2", "uTime": "1630048897900"}"#).unwrap();
+        assert!(order.is_final());
+        let response = order.to_order_response(42);
+        assert_eq!(response.status, "FILLED");
+        assert_eq!(response.avg_price, "42220.1");
+        assert_eq!(response.time_in_force, "IOC");
+    }
+}
@@ -0,0 +1,345 @@
+use crate::data::{OrderBook, Side};
+use crate::exchange::binance::{MarketEvent, ReceiveTime, TimedEvent, WsShutdown};
+use crate::exchange::checksum::{self, ChecksumScheme, ChecksumValidator};
+use crate::exchange::okx::types::{OkxBook, OkxTrade, PushMessage};
+use crate::exchange::MarketDataStream;
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+/// OKX closes connections idle for 30s; ping a little before that
+const PING_INTERVAL: Duration = Duration::from_secs(25);
+
+/// Book state of the current subscription
+struct BookSync {
+    /// `seqId` of the last applied message; `None` until a snapshot
+    last_seq_id: Option<i64>,
+    validator: ChecksumValidator,
+}
+
+/// OKX depth (`books`) and trade stream for one instrument
+///
+/// Feeds the same `OrderBook` and emits the same `MarketEvent`s as
+/// `BinanceWebSocket`. Every book message is verified against OKX's
+/// checksum of the top 25 levels; on a mismatch or a sequence gap the book
+/// is cleared, `MarketEvent::BookCorrupted` is emitted (mismatches only)
+/// and the channel is resubscribed for a fresh snapshot.
+pub struct OkxWebSocket {
+    inst_id: String,
+    ws_url: String,
+    event_tx: mpsc::UnboundedSender<TimedEvent>,
+    orderbook: Arc<OrderBook>,
+    sync: Mutex<BookSync>,
+    shutdown: WsShutdown,
+}
+
+impl OkxWebSocket {
+    /// `inst_id` is OKX's instrument id (e.g. "BTC-USDT-SWAP") and
+    /// `ws_endpoint` the public stream, e.g. wss://ws.okx.com:8443/ws/v5/public
+    pub fn new(
+        inst_id: String,
+        ws_endpoint: String,
+        orderbook: Arc<OrderBook>,
+    ) -> (Self, mpsc::UnboundedReceiver<TimedEvent>) {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+        (
+            Self {
+                sync: Mutex::new(BookSync {
+                    last_seq_id: None,
+                    validator: ChecksumValidator::new(&inst_id, ChecksumScheme::Okx),
+                }),
+                inst_id,
+                ws_url: ws_endpoint,
+                event_tx,
+                orderbook,
+                shutdown: WsShutdown::new(),
+            },
+            event_rx,
+        )
+    }
+
+    /// Handle that stops `run` from another task (keep it before spawning)
+    pub fn shutdown_handle(&self) -> WsShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Close the current connection and stop reconnecting
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown();
+    }
+
+    /// Checksums validated and mismatched since start
+    pub fn checksum_stats(&self) -> (u64, u64) {
+        let sync = self.sync.lock().unwrap_or_else(|e| e.into_inner());
+        (sync.validator.checks(), sync.validator.mismatches())
+    }
+
+    fn request(&self, op: &str, channels: &[&str]) -> String {
+        let args: Vec<_> = channels.iter()
+            .map(|channel| serde_json::json!({ "channel": channel, "instId": self.inst_id }))
+            .collect();
+        serde_json::json!({ "op": op, "args": args }).to_string()
+    }
+
+    /// Start WebSocket connection with auto-reconnect
+    ///
+    /// Runs until `shutdown()` is called.
+    pub async fn run(&self) {
+        let mut reconnect_delay = Duration::from_secs(1);
+        let max_reconnect_delay = Duration::from_secs(60);
+        let mut shutdown = self.shutdown.subscribe();
+
+        while !self.shutdown.is_requested() {
+            info!("Connecting to OKX WebSocket: {}", self.ws_url);
+
+            match self.connect_and_process().await {
+                Ok(_) => {
+                    info!("OKX WebSocket connection closed normally");
+                    reconnect_delay = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    error!("OKX WebSocket error: {}. Reconnecting in {:?}", e, reconnect_delay);
+                    self.emit(ReceiveTime::now(), MarketEvent::Disconnected);
+
+                    tokio::select! {
+                        _ = tokio::time::sleep(reconnect_delay) => {}
+                        _ = shutdown.changed() => {}
+                    }
+                    reconnect_delay = std::cmp::min(reconnect_delay * 2, max_reconnect_delay);
+                }
+            }
+        }
+    }
+
+    async fn connect_and_process(&self) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.ws_url).await
+            .map_err(|e| anyhow!("WebSocket connection failed: {}", e))?;
+        let (mut write, mut read) = ws_stream.split();
+
+        write.send(Message::Text(self.request("subscribe", &["books", "trades"]))).await
+            .map_err(|e| anyhow!("Failed to subscribe: {}", e))?;
+
+        // The subscription opens with a snapshot; drop whatever was left
+        self.sync.lock().unwrap_or_else(|e| e.into_inner()).last_seq_id = None;
+        checksum::clear_book(&self.orderbook)?;
+        info!("OKX WebSocket connected: books, trades for {}", self.inst_id);
+        self.emit(ReceiveTime::now(), MarketEvent::Connected);
+
+        let mut shutdown = self.shutdown.subscribe();
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        ping.tick().await;
+
+        loop {
+            if self.shutdown.is_requested() {
+                let _ = write.send(Message::Close(None)).await;
+                self.emit(ReceiveTime::now(), MarketEvent::Disconnected);
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = shutdown.changed() => {}
+
+                _ = ping.tick() => {
+                    write.send(Message::Text("ping".to_string())).await
+                        .map_err(|e| anyhow!("Failed to send ping: {}", e))?;
+                }
+
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            if self.process_message(&text, ReceiveTime::now())? {
+                                write.send(Message::Text(self.request("unsubscribe", &["books"]))).await
+                                    .map_err(|e| anyhow!("Failed to unsubscribe: {}", e))?;
+                                write.send(Message::Text(self.request("subscribe", &["books"]))).await
+                                    .map_err(|e| anyhow!("Failed to resubscribe: {}", e))?;
+                            }
+                        }
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Err(e)) => return Err(anyhow!("WebSocket error: {}", e)),
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply one push message to the book and emit its events
+    ///
+    /// Returns true if the book was cleared and `books` must be
+    /// resubscribed for a new snapshot.
+    fn process_message(&self, text: &str, received: ReceiveTime) -> Result<bool> {
+        if text == "pong" {
+            return Ok(false);
+        }
+        let message: PushMessage = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(e) => {
+                debug!("Unparseable OKX message: {}", e);
+                return Ok(false);
+            }
+        };
+        if message.event.as_deref() == Some("error") {
+            return Err(anyhow!("OKX subscription error: {}", message.msg));
+        }
+        let Some(arg) = message.arg else {
+            return Ok(false);
+        };
+        if message.event.is_some() {
+            return Ok(false);
+        }
+
+        match arg.channel.as_str() {
+            "books" => {
+                let books: Vec<OkxBook> = match serde_json::from_value(message.data) {
+                    Ok(books) => books,
+                    Err(e) => {
+                        debug!("Error parsing OKX book: {}", e);
+                        return Ok(false);
+                    }
+                };
+                let snapshot = message.action.as_deref() == Some("snapshot");
+                for book in books {
+                    if self.apply_book(&book, snapshot, received)? {
+                        return Ok(true);
+                    }
+                }
+            }
+            "trades" => {
+                let trades: Vec<OkxTrade> = match serde_json::from_value(message.data) {
+                    Ok(trades) => trades,
+                    Err(e) => {
+                        debug!("Error parsing OKX trades: {}", e);
+                        return Ok(false);
+                    }
+                };
+                for trade in trades.iter().filter_map(OkxTrade::to_trade) {
+                    self.emit(received, MarketEvent::Trade(trade));
+                }
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    /// Apply a snapshot or update; true if the book had to be cleared
+    fn apply_book(&self, book: &OkxBook, snapshot: bool, received: ReceiveTime) -> Result<bool> {
+        let mut sync = self.sync.lock().unwrap_or_else(|e| e.into_inner());
+
+        if snapshot {
+            checksum::clear_book(&self.orderbook)?;
+        } else {
+            match sync.last_seq_id {
+                // Updates still in flight from before a resync
+                None => return Ok(false),
+                Some(last) if book.prev_seq_id == last => {}
+                Some(last) => {
+                    warn!("{} book sequence gap: {} -> {} (prev {}); resyncing",
+                        self.inst_id, last, book.seq_id, book.prev_seq_id);
+                    sync.last_seq_id = None;
+                    checksum::clear_book(&self.orderbook)?;
+                    return Ok(true);
+                }
+            }
+        }
+
+        let update = book.to_depth_update(&self.inst_id);
+        for (price, qty) in update.parse_bids() {
+            self.orderbook.update_level(Side::Buy, price, qty)?;
+        }
+        for (price, qty) in update.parse_asks() {
+            self.orderbook.update_level(Side::Sell, price, qty)?;
+        }
+
+        if let Some(expected) = book.checksum {
+            if let Some(corrupted) = sync.validator.validate_or_resync(&self.orderbook, expected as i32 as u32)? {
+                warn!("{}; resyncing", corrupted);
+                sync.last_seq_id = None;
+                self.emit(received, MarketEvent::BookCorrupted(corrupted));
+                return Ok(true);
+            }
+        }
+
+        sync.last_seq_id = Some(book.seq_id);
+        self.emit(received, MarketEvent::DepthUpdate(update));
+        Ok(false)
+    }
+
+    fn emit(&self, received: ReceiveTime, event: MarketEvent) {
+        let _ = self.event_tx.send(TimedEvent { received, event });
+    }
+}
+
+impl MarketDataStream for OkxWebSocket {
+    async fn run(&self) {
+        OkxWebSocket::run(self).await
+    }
+
+    fn shutdown(&self) {
+        OkxWebSocket::shutdown(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::checksum::crc32;
+
+    fn books(action: &str, bids: &str, asks: &str, checksum: i64, prev: i64, seq: i64) -> String {
+        format!(
+            r#"{{"arg": {{"channel": "books", "instId": "BTC-USDT-SWAP"}}, "action": "{}",
+                "data": [{{"bids": {}, "asks": {}, "ts": "1000", "checksum": {}, "prevSeqId": {}, "seqId": {}}}]}}"#,
+            action, bids, asks, checksum, prev, seq
+        )
+    }
+
+    #[test]
+    fn test_checksum_verified_and_mismatch_resyncs() {
+        let book = Arc::new(OrderBook::new("BTC-USDT-SWAP"));
+        let (ws, mut rx) = OkxWebSocket::new(
+            "BTC-USDT-SWAP".to_string(),
+            "wss://ws.okx.com:8443/ws/v5/public".to_string(),
+            Arc::clone(&book),
+        );
+
+        let snapshot_sum = crc32(b"3366.1:7:3366.8:9:3366:6") as i32 as i64;
+        let snapshot = books(
+            "snapshot",
+            r#"[["3366.1", "7", "0", "1"], ["3366", "6", "0", "1"]]"#,
+            r#"[["3366.8", "9", "0", "1"]]"#,
+            snapshot_sum, -1, 10,
+        );
+        assert!(!ws.process_message(&snapshot, ReceiveTime::now()).unwrap());
+        assert_eq!(book.get_book_depth_count(), (2, 1));
+
+        // Delete of 3366 arrives; checksum agrees
+        let update_sum = crc32(b"3366.1:7:3366.8:9") as i32 as i64;
+        let update = books("update", r#"[["3366", "0", "0", "0"]]"#, "[]", update_sum, 10, 11);
+        assert!(!ws.process_message(&update, ReceiveTime::now()).unwrap());
+        assert_eq!(book.get_book_depth_count(), (1, 1));
+
+        // Wrong checksum: book cleared, corruption reported, resubscribe
+        let bad = books("update", r#"[["3365", "1", "0", "1"]]"#, "[]", update_sum, 11, 12);
+        assert!(ws.process_message(&bad, ReceiveTime::now()).unwrap());
+        assert_eq!(book.get_book_depth_count(), (0, 0));
+        assert_eq!(ws.checksum_stats(), (3, 1));
+
+        // Updates in flight before the new snapshot are skipped
+        let stale = books("update", "[]", "[]", 0, 12, 13);
+        assert!(!ws.process_message(&stale, ReceiveTime::now()).unwrap());
+
+        let events: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).map(|e| e.event).collect();
+        assert!(matches!(events[0], MarketEvent::DepthUpdate(_)));
+        assert!(matches!(events[1], MarketEvent::DepthUpdate(_)));
+        assert!(matches!(&events[2], MarketEvent::BookCorrupted(c) if c.symbol == "BTC-USDT-SWAP"));
+        assert_eq!(events.len(), 3);
+
+        assert!(!ws.process_message("pong", ReceiveTime::now()).unwrap());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::data::Side;
+use crate::exchange::binance::{
+    fetch_income, BinanceRestClient, BinanceWebSocket, IncomeRecord, OrderResponse,
+};
+use crate::strategy::OcoLeg;
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use std::future::Future;
+
+/// Order routing and account access the execution engine needs from a venue
+///
+/// Order reports use Binance's `OrderResponse` shape (string quantities,
+/// "FILLED" status); other venues map their responses into it.
+pub trait Exchange: Send + Sync {
+    fn place_market_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        quantity: Decimal,
+    ) -> impl Future<Output = Result<OrderResponse>> + Send;
+
+    fn place_limit_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> impl Future<Output = Result<OrderResponse>> + Send;
+
+    /// Market order that may only reduce the position, which venues exempt
+    /// from the minimum notional; a plain market order where unsupported
+    fn place_reduce_only_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        quantity: Decimal,
+    ) -> impl Future<Output = Result<OrderResponse>> + Send {
+        self.place_market_order(symbol, side, quantity)
+    }
+
+    /// Reduce-only order that closes at market once the price reaches
+    /// `stop_price`: the take profit or stop loss leg of an OCO pair
+    fn place_exit_trigger(
+        &self,
+        symbol: &str,
+        side: Side,
+        leg: OcoLeg,
+        stop_price: Decimal,
+        quantity: Decimal,
+    ) -> impl Future<Output = Result<OrderResponse>> + Send {
+        let _ = (symbol, side, stop_price, quantity);
+        std::future::ready(Err(anyhow!("Exchange-side {:?} orders are not supported on this venue", leg)))
+    }
+
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> impl Future<Output = Result<OrderResponse>> + Send;
+
+    /// Current status and fills of an order
+    fn get_order(&self, symbol: &str, order_id: u64) -> impl Future<Output = Result<OrderResponse>> + Send;
+
+    /// Account equity in the margin asset, `None` if the venue does not report it
+    fn account_equity(&self) -> impl Future<Output = Result<Option<Decimal>>> + Send;
+
+    /// Income (realized PnL, fees, funding) for `symbol` in `[start_ms, end_ms]`
+    fn income_history(
+        &self,
+        symbol: &str,
+        start_ms: u64,
+        end_ms: u64,
+    ) -> impl Future<Output = Result<Vec<IncomeRecord>>> + Send;
+}
+
+/// A venue's market data connection, feeding events to the receiver it
+/// was created with until shut down
+pub trait MarketDataStream: Send + Sync {
+    /// Connect and stream, reconnecting as needed, until `shutdown`
+    fn run(&self) -> impl Future<Output = ()> + Send;
+
+    fn shutdown(&self);
+}
+
+impl Exchange for BinanceRestClient {
+    async fn place_market_order(&self, symbol: &str, side: Side, quantity: Decimal) -> Result<OrderResponse> {
+        BinanceRestClient::place_market_order(self, symbol, side, quantity).await
+    }
+
+    async fn place_limit_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Result<OrderResponse> {
+        BinanceRestClient::place_limit_order(self, symbol, side, price, quantity).await
+    }
+
+    async fn place_reduce_only_order(&self, symbol: &str, side: Side, quantity: Decimal) -> Result<OrderResponse> {
+        BinanceRestClient::place_reduce_only_order(self, symbol, side, quantity).await
+    }
+
+    async fn place_exit_trigger(
+        &self,
+        symbol: &str,
+        side: Side,
+        leg: OcoLeg,
+        stop_price: Decimal,
+        quantity: Decimal,
+    ) -> Result<OrderResponse> {
+        BinanceRestClient::place_exit_trigger(self, symbol, side, leg, stop_price, quantity).await
+    }
+
+    async fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<OrderResponse> {
+        BinanceRestClient::cancel_order(self, symbol, order_id).await
+    }
+
+    async fn get_order(&self, symbol: &str, order_id: u64) -> Result<OrderResponse> {
+        BinanceRestClient::get_order(self, symbol, order_id).await
+    }
+
+    async fn account_equity(&self) -> Result<Option<Decimal>> {
+        let info = self.get_account_info().await?;
+        Ok(account_equity(&info))
+    }
+
+    async fn income_history(&self, symbol: &str, start_ms: u64, end_ms: u64) -> Result<Vec<IncomeRecord>> {
+        fetch_income(self, symbol, start_ms, end_ms).await
+    }
+}
+
+impl MarketDataStream for BinanceWebSocket {
+    async fn run(&self) {
+        BinanceWebSocket::run(self).await
+    }
+
+    fn shutdown(&self) {
+        BinanceWebSocket::shutdown(self)
+    }
+}
+
+/// Wallet balance plus unrealized PnL from a futures account response
+fn account_equity(info: &serde_json::Value) -> Option<Decimal> {
+    info.get("totalMarginBalance")?.as_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_equity_from_account_info() {
+        let info = serde_json::json!({ "totalMarginBalance": "10250.50", "availableBalance": "9000.00" });
+        assert_eq!(account_equity(&info), Some(Decimal::new(1025050, 2)));
+        assert_eq!(account_equity(&serde_json::json!({})), None);
+    }
+}
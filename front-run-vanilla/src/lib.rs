@@ -3,14 +3,15 @@ pub mod exchange;
 pub mod strategy;
 pub mod risk;
 pub mod backtest;
+pub mod storage;
 pub mod utils;
 
 // Re-export commonly used types
 pub use data::{OrderBook, Side, PriceLevel, Trade, Order, Signal, SignalComponent};
-pub use exchange::{BinanceWebSocket, BinanceRestClient, MarketEvent};
+pub use exchange::{BinanceWebSocket, BinanceRestClient, Exchange, MarketDataStream, MarketEvent, TimedEvent};
 pub use strategy::{
     ImbalanceDetector, FlowAnalyzer, SignalAggregator, CompositeSignal,
-    ExecutionEngine, TradingStats,
+    ExecutionEngine, TradingStats, MarketQualityGate, TakeProfitSchedule,
 };
 pub use risk::{Position, PositionManager, RiskManager, RiskLimits};
 pub use backtest::{BacktestEngine, BacktestConfig, BacktestResults};
@@ -0,0 +1,261 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// How disruptive a scheduled event is expected to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSeverity {
+    Low,       // Minor data releases
+    Medium,    // Secondary macro prints
+    High,      // FOMC, CPI, NFP
+    Critical,  // Exchange maintenance, contract migrations
+}
+
+/// A scheduled event (maintenance window, macro release)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    pub name: String,
+    pub start: DateTime<Utc>,
+    /// End of the event itself; point-in-time releases omit it
+    #[serde(default)]
+    pub end: Option<DateTime<Utc>>,
+    pub severity: EventSeverity,
+}
+
+/// Entry blackout around an event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BlackoutWindow {
+    pub before_secs: u64,
+    pub after_secs: u64,
+}
+
+/// Blackout per severity (None = events of that severity never block)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlackoutPolicy {
+    pub low: Option<BlackoutWindow>,
+    pub medium: Option<BlackoutWindow>,
+    pub high: Option<BlackoutWindow>,
+    pub critical: Option<BlackoutWindow>,
+}
+
+impl Default for BlackoutPolicy {
+    fn default() -> Self {
+        Self {
+            low: None,
+            medium: Some(BlackoutWindow { before_secs: 60, after_secs: 120 }),
+            high: Some(BlackoutWindow { before_secs: 300, after_secs: 600 }),
+            critical: Some(BlackoutWindow { before_secs: 900, after_secs: 1800 }),
+        }
+    }
+}
+
+impl BlackoutPolicy {
+    fn window(&self, severity: EventSeverity) -> Option<BlackoutWindow> {
+        match severity {
+            EventSeverity::Low => self.low,
+            EventSeverity::Medium => self.medium,
+            EventSeverity::High => self.high,
+            EventSeverity::Critical => self.critical,
+        }
+    }
+}
+
+/// Calendar file layout (TOML or JSON)
+///
+/// ```toml
+/// [[events]]
+/// name = "FOMC rate decision"
+/// start = "2024-03-20T18:00:00Z"
+/// severity = "high"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CalendarFile {
+    #[serde(default)]
+    events: Vec<ScheduledEvent>,
+}
+
+/// Event calendar suppressing entries around scheduled events
+///
+/// Microstructure signals are meaningless when a macro print or exchange
+/// maintenance is about to reprice everything, so new entries are blocked
+/// inside each event's blackout window. Exits are never blocked.
+#[derive(Debug, Clone)]
+pub struct EventCalendar {
+    events: Vec<ScheduledEvent>,
+    policy: BlackoutPolicy,
+}
+
+impl EventCalendar {
+    /// Create calendar from events and blackout policy
+    pub fn new(events: Vec<ScheduledEvent>, policy: BlackoutPolicy) -> Self {
+        Self { events, policy }
+    }
+
+    /// Load events from a TOML or JSON file (format by extension)
+    pub fn from_file<P: AsRef<Path>>(path: P, policy: BlackoutPolicy) -> Result<Self> {
+        let contents = std::fs::read_to_string(path.as_ref())?;
+        let is_json = path.as_ref()
+            .extension()
+            .map(|ext| ext == "json")
+            .unwrap_or(false);
+
+        let file = if is_json {
+            serde_json::from_str::<CalendarFile>(&contents)?
+        } else {
+            toml::from_str::<CalendarFile>(&contents)?
+        };
+
+        Ok(Self::new(file.events, policy))
+    }
+
+    /// Fetch events from a URL serving the same TOML or JSON layout
+    pub async fn from_url(url: &str, policy: BlackoutPolicy) -> Result<Self> {
+        let response = reqwest::get(url).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Calendar fetch failed: {}", response.status()));
+        }
+
+        let body = response.text().await?;
+        let file = serde_json::from_str::<CalendarFile>(&body)
+            .or_else(|_| toml::from_str::<CalendarFile>(&body))
+            .map_err(|e| anyhow!("Failed to parse calendar: {}", e))?;
+
+        Ok(Self::new(file.events, policy))
+    }
+
+    /// Whether `source` is fetched over http(s) rather than read from disk
+    pub fn is_url(source: &str) -> bool {
+        source.starts_with("http://") || source.starts_with("https://")
+    }
+
+    /// Load from a path or http(s) URL
+    pub async fn load(source: &str, policy: BlackoutPolicy) -> Result<Self> {
+        if Self::is_url(source) {
+            Self::from_url(source, policy).await
+        } else {
+            Self::from_file(source, policy)
+        }
+    }
+
+    /// Event whose blackout window covers `now`, if any
+    pub fn active_blackout(&self, now: SystemTime) -> Option<&ScheduledEvent> {
+        let now: DateTime<Utc> = now.into();
+
+        self.events.iter().find(|event| {
+            let window = match self.policy.window(event.severity) {
+                Some(w) => w,
+                None => return false,
+            };
+
+            let start = event.start - chrono_secs(window.before_secs);
+            let end = event.end.unwrap_or(event.start) + chrono_secs(window.after_secs);
+
+            now >= start && now <= end
+        })
+    }
+
+    /// Check whether entries are allowed at `now`
+    pub fn entries_allowed(&self, now: SystemTime) -> bool {
+        self.active_blackout(now).is_none()
+    }
+
+    /// Upcoming events (start after `now`), soonest first
+    pub fn upcoming(&self, now: SystemTime) -> Vec<&ScheduledEvent> {
+        let now: DateTime<Utc> = now.into();
+        let mut events: Vec<_> = self.events.iter()
+            .filter(|e| e.start > now)
+            .collect();
+        events.sort_by_key(|e| e.start);
+        events
+    }
+
+    /// Number of loaded events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check if calendar has no events
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+fn chrono_secs(secs: u64) -> chrono::Duration {
+    chrono::Duration::from_std(Duration::from_secs(secs)).unwrap_or_else(|_| chrono::Duration::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = r#"
+        [[events]]
+        name = "FOMC"
+        start = "2024-03-20T18:00:00Z"
+        severity = "high"
+
+        [[events]]
+        name = "Binance maintenance"
+        start = "2024-03-21T02:00:00Z"
+        end = "2024-03-21T04:00:00Z"
+        severity = "critical"
+
+        [[events]]
+        name = "Minor release"
+        start = "2024-03-22T12:00:00Z"
+        severity = "low"
+    "#;
+
+    fn at(rfc3339: &str) -> SystemTime {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc).into()
+    }
+
+    fn calendar() -> EventCalendar {
+        let file: CalendarFile = toml::from_str(CALENDAR).unwrap();
+        EventCalendar::new(file.events, BlackoutPolicy::default())
+    }
+
+    #[test]
+    fn test_blocks_around_point_event() {
+        let cal = calendar();
+
+        // 5 min before through 10 min after FOMC
+        assert!(cal.entries_allowed(at("2024-03-20T17:54:00Z")));
+        assert!(!cal.entries_allowed(at("2024-03-20T17:56:00Z")));
+        assert!(!cal.entries_allowed(at("2024-03-20T18:09:00Z")));
+        assert!(cal.entries_allowed(at("2024-03-20T18:11:00Z")));
+    }
+
+    #[test]
+    fn test_blocks_for_full_event_duration() {
+        let cal = calendar();
+
+        let event = cal.active_blackout(at("2024-03-21T03:00:00Z")).unwrap();
+        assert_eq!(event.name, "Binance maintenance");
+
+        // 30 min after end still blocked for critical
+        assert!(!cal.entries_allowed(at("2024-03-21T04:25:00Z")));
+        assert!(cal.entries_allowed(at("2024-03-21T04:31:00Z")));
+    }
+
+    #[test]
+    fn test_low_severity_ignored_by_default() {
+        let cal = calendar();
+        assert!(cal.entries_allowed(at("2024-03-22T12:00:00Z")));
+    }
+
+    #[test]
+    fn test_json_format() {
+        let json = r#"{"events":[{"name":"CPI","start":"2024-03-12T12:30:00Z","severity":"high"}]}"#;
+        let file: CalendarFile = serde_json::from_str(json).unwrap();
+        let cal = EventCalendar::new(file.events, BlackoutPolicy::default());
+
+        assert_eq!(cal.len(), 1);
+        assert!(!cal.entries_allowed(at("2024-03-12T12:30:00Z")));
+        assert_eq!(cal.upcoming(at("2024-03-01T00:00:00Z")).len(), 1);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::risk::{RiskManager, RiskMetrics};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Shared handle to one account's `RiskManager`
+///
+/// The execution engine is the single writer on the trading path (entry
+/// checks, fills, marks); clones handed to monitoring and control tasks
+/// read metrics concurrently and can halt or resume without going through
+/// the engine. Locks are held only for the duration of one call, never
+/// across an await.
+#[derive(Clone)]
+pub struct RiskHandle {
+    inner: Arc<RwLock<RiskManager>>,
+}
+
+impl RiskHandle {
+    pub fn new(manager: RiskManager) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(manager)),
+        }
+    }
+
+    /// Shared access; a panic in another holder does not lock readers out
+    pub fn read(&self) -> RwLockReadGuard<'_, RiskManager> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Exclusive access for the engine's updates
+    pub fn write(&self) -> RwLockWriteGuard<'_, RiskManager> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn metrics(&self) -> RiskMetrics {
+        self.read().get_metrics()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.read().is_halted()
+    }
+
+    pub fn halt_reason(&self) -> Option<String> {
+        self.read().halt_reason().map(str::to_string)
+    }
+
+    /// Halt unless already halted, keeping the first reason
+    pub fn halt(&self, reason: &str) -> bool {
+        let mut manager = self.write();
+        if manager.is_halted() {
+            return false;
+        }
+        manager.halt_trading(reason);
+        true
+    }
+
+    /// Clear a halt (manual override)
+    pub fn resume(&self) {
+        self.write().resume_trading();
+    }
+}
+
+/// Apply an operator `halt [reason]` or `resume` to every account
+///
+/// Returns the resulting status, or `None` if `command` is not a risk
+/// command (so it can be passed on to `StrategyControl`).
+pub fn apply_risk_command(handles: &[(String, RiskHandle)], command: &str) -> Option<String> {
+    let command = command.trim();
+    let (verb, rest) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+    match verb {
+        "halt" => {
+            let reason = match rest.trim() {
+                "" => "Operator halt",
+                reason => reason,
+            };
+            for (_, handle) in handles {
+                handle.halt(reason);
+            }
+        }
+        "resume" if rest.trim().is_empty() => {
+            for (_, handle) in handles {
+                handle.resume();
+            }
+        }
+        _ => return None,
+    }
+
+    let status: Vec<String> = handles.iter()
+        .map(|(name, handle)| match handle.halt_reason() {
+            Some(reason) => format!("{}=halted ({})", name, reason),
+            None => format!("{}=trading", name),
+        })
+        .collect();
+    Some(status.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::RiskLimits;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_clones_share_state_across_threads() {
+        let handle = RiskHandle::new(RiskManager::new(RiskLimits::default(), dec!(10000)));
+        let monitor = handle.clone();
+
+        std::thread::spawn(move || assert!(monitor.halt("operator halt")))
+            .join()
+            .unwrap();
+
+        assert!(handle.is_halted());
+        assert!(!handle.halt("second reason"));
+        assert_eq!(handle.halt_reason().as_deref(), Some("operator halt"));
+
+        handle.write().record_trade(dec!(-50));
+        assert_eq!(handle.metrics().current_equity, dec!(9950));
+
+        handle.resume();
+        assert!(!handle.clone().is_halted());
+
+        let handles = vec![("primary".to_string(), handle.clone())];
+        assert_eq!(apply_risk_command(&handles, "halt news").as_deref(), Some("primary=halted (news)"));
+        assert_eq!(apply_risk_command(&handles, "resume").as_deref(), Some("primary=trading"));
+        assert_eq!(apply_risk_command(&handles, "imbalance off"), None);
+    }
+}
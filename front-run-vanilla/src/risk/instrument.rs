@@ -0,0 +1,209 @@
+use crate::data::Side;
+use anyhow::{Result, bail};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+
+/// Quote assets treated as worth exactly one USD
+const USD_QUOTES: [&str; 5] = ["USD", "USDT", "USDC", "BUSD", "FDUSD"];
+
+/// Whether `quote_currency` needs no conversion to USD
+pub fn is_usd_quote(quote_currency: &str) -> bool {
+    USD_QUOTES.contains(&quote_currency.to_ascii_uppercase().as_str())
+}
+
+/// How a symbol's quantity, notional and PnL are denominated
+/// (`[general.instrument]`)
+///
+/// Every conversion between quantity, price and money goes through here,
+/// so a venue with non-1:1 contracts needs a descriptor, not special cases.
+/// Sizing and risk limits are always in USD. Positions keep PnL and fees
+/// in the settlement asset (the quote asset for linear instruments, the
+/// base coin for inverse ones); `settlement_usd` converts them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Instrument {
+    /// Quantity in contracts of `contract_size` USD, settled in the base
+    /// coin (COIN-margined BTCUSD_PERP)
+    pub inverse: bool,
+    /// USD face value of one inverse contract (100 for BTCUSD_PERP)
+    pub contract_size: Decimal,
+    /// Quote value of a one-point price move per unit of quantity
+    /// (linear; 1 on Binance)
+    pub multiplier: Decimal,
+    /// Price increment (0 = no rounding)
+    pub tick_size: Decimal,
+    /// Quantity increment (0 = base units unrounded, whole contracts if inverse)
+    pub lot_size: Decimal,
+}
+
+impl Default for Instrument {
+    fn default() -> Self {
+        Self::linear()
+    }
+}
+
+impl Instrument {
+    /// Quantity in the base asset, settled in the quote asset (BTCUSDT,
+    /// or ETHBTC with a BTC quote)
+    pub fn linear() -> Self {
+        Self {
+            inverse: false,
+            contract_size: Decimal::ZERO,
+            multiplier: Decimal::ONE,
+            tick_size: Decimal::ZERO,
+            lot_size: Decimal::ZERO,
+        }
+    }
+
+    /// Contracts of `contract_size` USD settled in the base coin
+    pub fn inverse(contract_size: Decimal) -> Self {
+        Self {
+            inverse: true,
+            contract_size,
+            ..Self::linear()
+        }
+    }
+
+    /// Round prices and quantities to the exchange's grid
+    pub fn with_increments(mut self, tick_size: Decimal, lot_size: Decimal) -> Self {
+        self.tick_size = tick_size;
+        self.lot_size = lot_size;
+        self
+    }
+
+    /// Reject descriptors no quantity could be computed from
+    pub fn validate(&self) -> Result<()> {
+        if self.inverse && self.contract_size <= Decimal::ZERO {
+            bail!("inverse instrument needs a positive contract_size");
+        }
+        if !self.inverse && self.multiplier <= Decimal::ZERO {
+            bail!("linear instrument needs a positive multiplier");
+        }
+        Ok(())
+    }
+
+    /// Order quantity for `notional_usd` at `price`, rounded down to the lot
+    ///
+    /// `quote_usd` is the USD value of one unit of the quote asset (1 for
+    /// USD-quoted and inverse symbols).
+    pub fn quantity(&self, notional_usd: Decimal, price: Decimal, quote_usd: Decimal) -> Decimal {
+        let quantity = if self.inverse {
+            if self.contract_size.is_zero() {
+                return Decimal::ZERO;
+            }
+            (notional_usd / self.contract_size).floor()
+        } else {
+            let unit_usd = price * self.multiplier * quote_usd;
+            if unit_usd.is_zero() {
+                return Decimal::ZERO;
+            }
+            notional_usd / unit_usd
+        };
+        self.round_quantity(quantity)
+    }
+
+    /// `quantity` rounded down to the lot size
+    pub fn round_quantity(&self, quantity: Decimal) -> Decimal {
+        if self.lot_size.is_zero() {
+            return quantity;
+        }
+        (quantity / self.lot_size).floor() * self.lot_size
+    }
+
+    /// `price` rounded to the nearest tick
+    pub fn round_price(&self, price: Decimal) -> Decimal {
+        if self.tick_size.is_zero() {
+            return price;
+        }
+        (price / self.tick_size).round() * self.tick_size
+    }
+
+    /// Notional of `quantity` at `price`, in the settlement asset
+    pub fn notional(&self, quantity: Decimal, price: Decimal) -> Decimal {
+        if !self.inverse {
+            return quantity * price * self.multiplier;
+        }
+        if price.is_zero() {
+            return Decimal::ZERO;
+        }
+        quantity * self.contract_size / price
+    }
+
+    /// Notional of `quantity` at `price`, in the quote asset (USD for inverse)
+    pub fn quote_notional(&self, quantity: Decimal, price: Decimal) -> Decimal {
+        if self.inverse {
+            quantity * self.contract_size
+        } else {
+            quantity * price * self.multiplier
+        }
+    }
+
+    /// PnL of a `side` position from `entry` to `exit`, in the settlement asset
+    pub fn pnl(&self, side: Side, entry: Decimal, exit: Decimal, quantity: Decimal) -> Decimal {
+        let long_pnl = if self.inverse {
+            // The same USD face is worth fewer coins at a higher price
+            self.notional(quantity, entry) - self.notional(quantity, exit)
+        } else {
+            (exit - entry) * quantity * self.multiplier
+        };
+        match side {
+            Side::Buy => long_pnl,
+            Side::Sell => -long_pnl,
+        }
+    }
+
+    /// USD value of one unit of the settlement asset at `price`
+    pub fn settlement_usd(&self, price: Decimal, quote_usd: Decimal) -> Decimal {
+        if self.inverse {
+            price
+        } else {
+            quote_usd
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_inverse_btcusd_perp() {
+        let instrument = Instrument::inverse(dec!(100));
+
+        // $1050 buys 10 whole contracts, $1000 of face value
+        let quantity = instrument.quantity(dec!(1050), dec!(50000), dec!(1));
+        assert_eq!(quantity, dec!(10));
+        assert_eq!(instrument.quote_notional(quantity, dec!(50000)), dec!(1000));
+        assert_eq!(instrument.notional(quantity, dec!(50000)), dec!(0.02));
+
+        // Long from 50k to 55k: 0.02 BTC of face is worth 0.0181818.. BTC
+        let pnl = instrument.pnl(Side::Buy, dec!(50000), dec!(55000), quantity);
+        assert_eq!(pnl.round_dp(10), dec!(0.0018181818));
+        let pnl_usd = pnl * instrument.settlement_usd(dec!(55000), dec!(1));
+        assert_eq!(pnl_usd.round_dp(6), dec!(100));
+        assert_eq!(instrument.pnl(Side::Sell, dec!(50000), dec!(55000), quantity), -pnl);
+
+        assert!(Instrument::inverse(Decimal::ZERO).validate().is_err());
+    }
+
+    #[test]
+    fn test_linear_non_usd_quote_and_increments() {
+        // ETHBTC at 0.05 BTC with BTC marked at $50k
+        let instrument = Instrument::linear();
+        let quantity = instrument.quantity(dec!(1000), dec!(0.05), dec!(50000));
+        assert_eq!(quantity, dec!(0.4));
+
+        let pnl = instrument.pnl(Side::Buy, dec!(0.05), dec!(0.051), quantity);
+        assert_eq!(pnl, dec!(0.0004));
+        assert_eq!(pnl * instrument.settlement_usd(dec!(0.051), dec!(50000)), dec!(20));
+
+        // $1000 of BTCUSDT at 30001 on a 0.001 lot, 0.1 tick
+        let instrument = Instrument::linear().with_increments(dec!(0.1), dec!(0.001));
+        assert_eq!(instrument.quantity(dec!(1000), dec!(30001), dec!(1)), dec!(0.033));
+        assert_eq!(instrument.round_price(dec!(30001.26)), dec!(30001.3));
+
+        assert!(is_usd_quote("usdt"));
+        assert!(!is_usd_quote("BTC"));
+    }
+}
@@ -1,7 +1,7 @@
 use rust_decimal::Decimal;
 use std::collections::VecDeque;
-use std::time::{SystemTime, Duration};
-use anyhow::{Result, anyhow};
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
+use anyhow::Result;
 use serde::{Serialize, Deserialize};
 
 /// Risk limit violation error
@@ -11,7 +11,14 @@ pub struct RiskViolation {
     pub severity: ViolationSeverity,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl RiskViolation {
+    /// Refused only because the hourly or daily trade count is used up
+    pub fn is_rate_limit(&self) -> bool {
+        self.severity == ViolationSeverity::Block && self.reason.contains("trade limit")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ViolationSeverity {
     Warning,   // Log but allow trade
     Block,     // Prevent trade
@@ -28,6 +35,10 @@ pub struct RiskLimits {
     // Loss limits
     pub max_daily_loss: Decimal,
     pub max_drawdown_percent: Decimal,
+    /// Largest unrealized loss on one position before it is force-closed,
+    /// whatever the stop distance (0 = disabled)
+    #[serde(default)]
+    pub max_loss_per_trade: Decimal,
     
     // Rate limits
     pub max_trades_per_hour: usize,
@@ -44,6 +55,7 @@ impl Default for RiskLimits {
             max_portfolio_exposure: Decimal::from(10000),
             max_daily_loss: Decimal::from(500),
             max_drawdown_percent: Decimal::from(10),
+            max_loss_per_trade: Decimal::ZERO,
             max_trades_per_hour: 30,
             max_trades_per_day: 200,
             max_acceptable_latency_ms: 500,
@@ -51,6 +63,11 @@ impl Default for RiskLimits {
     }
 }
 
+/// One metrics sample per minute by default
+const METRICS_HISTORY_INTERVAL: Duration = Duration::from_secs(60);
+/// A day of one-minute samples
+const METRICS_HISTORY_CAPACITY: usize = 1440;
+
 /// Risk manager enforcing all limits
 pub struct RiskManager {
     limits: RiskLimits,
@@ -64,8 +81,12 @@ pub struct RiskManager {
     hourly_trades: VecDeque<SystemTime>,
     
     // Drawdown tracking
+    initial_equity: Decimal,
     peak_equity: Decimal,
+    /// Realized equity; open positions are added through `unrealized_pnl`
     current_equity: Decimal,
+    /// Last mark-to-market of open positions
+    unrealized_pnl: Decimal,
     
     // Latency tracking
     recent_latencies: VecDeque<u64>,
@@ -73,6 +94,15 @@ pub struct RiskManager {
     // Circuit breaker state
     trading_halted: bool,
     halt_reason: Option<String>,
+
+    /// Exposure held by other instances and the fleet-wide cap it counts
+    /// against (see `FleetSync`)
+    fleet_exposure: Option<(Decimal, Decimal)>,
+
+    /// Bounded `RiskMetrics` history for intraday equity/drawdown charts
+    metrics_history: VecDeque<RiskMetricsSample>,
+    history_interval: Duration,
+    history_capacity: usize,
 }
 
 impl RiskManager {
@@ -83,19 +113,47 @@ impl RiskManager {
             daily_trades: 0,
             day_start: SystemTime::now(),
             hourly_trades: VecDeque::new(),
+            initial_equity,
             peak_equity: initial_equity,
             current_equity: initial_equity,
+            unrealized_pnl: Decimal::ZERO,
             recent_latencies: VecDeque::new(),
             trading_halted: false,
             halt_reason: None,
+            fleet_exposure: None,
+            metrics_history: VecDeque::new(),
+            history_interval: METRICS_HISTORY_INTERVAL,
+            history_capacity: METRICS_HISTORY_CAPACITY,
         }
     }
 
+    /// Keep at most `capacity` metrics samples, one per `interval`
+    pub fn with_metrics_history(mut self, interval: Duration, capacity: usize) -> Self {
+        self.history_interval = interval;
+        self.history_capacity = capacity.max(1);
+        self
+    }
+
+    /// Equity the manager was created with
+    pub fn initial_equity(&self) -> Decimal {
+        self.initial_equity
+    }
+
     /// Check if a new position can be opened
     pub fn can_open_position(
         &mut self,
         position_size: Decimal,
         current_exposure: Decimal,
+    ) -> Result<(), RiskViolation> {
+        self.can_open_position_at(position_size, current_exposure, SystemTime::now())
+    }
+
+    /// `can_open_position` with an explicit clock (replay time in backtests)
+    pub fn can_open_position_at(
+        &mut self,
+        position_size: Decimal,
+        current_exposure: Decimal,
+        now: SystemTime,
     ) -> Result<(), RiskViolation> {
         // Check circuit breaker
         if self.trading_halted {
@@ -129,33 +187,24 @@ impl RiskManager {
             });
         }
 
-        // Check daily loss limit
-        if self.daily_pnl < -self.limits.max_daily_loss {
-            self.halt_trading("Daily loss limit exceeded");
-            return Err(RiskViolation {
-                reason: format!(
-                    "Daily loss {} exceeds limit {}",
-                    self.daily_pnl, self.limits.max_daily_loss
-                ),
-                severity: ViolationSeverity::Emergency,
-            });
+        // Check exposure across every instance sharing the fleet cap
+        if let Some((others, max_fleet_exposure)) = self.fleet_exposure {
+            if others + new_exposure > max_fleet_exposure {
+                return Err(RiskViolation {
+                    reason: format!(
+                        "Fleet exposure {} exceeds limit {}",
+                        others + new_exposure, max_fleet_exposure
+                    ),
+                    severity: ViolationSeverity::Block,
+                });
+            }
         }
 
-        // Check drawdown
-        let drawdown = self.calculate_drawdown();
-        if drawdown > self.limits.max_drawdown_percent {
-            self.halt_trading("Drawdown limit exceeded");
-            return Err(RiskViolation {
-                reason: format!(
-                    "Drawdown {}% exceeds limit {}%",
-                    drawdown, self.limits.max_drawdown_percent
-                ),
-                severity: ViolationSeverity::Emergency,
-            });
-        }
+        // Check daily loss and drawdown, open positions included
+        self.check_loss_limits()?;
 
         // Check hourly trade limit
-        self.cleanup_old_trades();
+        self.cleanup_old_trades(now);
         if self.hourly_trades.len() >= self.limits.max_trades_per_hour {
             return Err(RiskViolation {
                 reason: format!(
@@ -193,20 +242,101 @@ impl RiskManager {
         Ok(())
     }
 
+    /// Daily loss and drawdown limits on marked equity; halts on breach
+    fn check_loss_limits(&mut self) -> Result<(), RiskViolation> {
+        let daily_pnl = self.daily_pnl + self.unrealized_pnl;
+        if daily_pnl < -self.limits.max_daily_loss {
+            self.halt_trading("Daily loss limit exceeded");
+            return Err(RiskViolation {
+                reason: format!(
+                    "Daily loss {} exceeds limit {}",
+                    daily_pnl, self.limits.max_daily_loss
+                ),
+                severity: ViolationSeverity::Emergency,
+            });
+        }
+
+        let drawdown = self.calculate_drawdown();
+        if drawdown > self.limits.max_drawdown_percent {
+            self.halt_trading("Drawdown limit exceeded");
+            return Err(RiskViolation {
+                reason: format!(
+                    "Drawdown {}% exceeds limit {}%",
+                    drawdown, self.limits.max_drawdown_percent
+                ),
+                severity: ViolationSeverity::Emergency,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Mark open positions to market
+    ///
+    /// `unrealized_pnl` is the open PnL of every position this manager
+    /// covers. Drawdown and the daily loss limit count it, so a large
+    /// losing position trips the breaker while still open instead of only
+    /// once it is closed. An `Emergency` violation means the limits are
+    /// breached and open positions should be flattened.
+    pub fn mark_to_market(&mut self, unrealized_pnl: Decimal) -> Result<(), RiskViolation> {
+        self.unrealized_pnl = unrealized_pnl;
+        self.update_peak();
+        self.check_loss_limits()
+    }
+
+    /// Check an open position's unrealized PnL against the per-trade loss cap
+    ///
+    /// Catches gaps through the stop: a bps stop checked against the mid
+    /// can be jumped over, a currency cap still fires on the next check.
+    pub fn check_trade_loss(&self, unrealized_pnl: Decimal) -> Result<(), RiskViolation> {
+        if self.limits.max_loss_per_trade > Decimal::ZERO
+            && unrealized_pnl < -self.limits.max_loss_per_trade
+        {
+            return Err(RiskViolation {
+                reason: format!(
+                    "Trade loss {} exceeds per-trade cap {}",
+                    -unrealized_pnl, self.limits.max_loss_per_trade
+                ),
+                severity: ViolationSeverity::Block,
+            });
+        }
+        Ok(())
+    }
+
     /// Record a trade
     pub fn record_trade(&mut self, pnl: Decimal) {
-        self.hourly_trades.push_back(SystemTime::now());
+        self.record_trade_at(pnl, SystemTime::now());
+    }
+
+    /// `record_trade` with an explicit clock (replay time in backtests)
+    pub fn record_trade_at(&mut self, pnl: Decimal, now: SystemTime) {
+        self.hourly_trades.push_back(now);
         self.daily_trades += 1;
         self.daily_pnl += pnl;
         self.current_equity += pnl;
-
-        // Update peak equity
-        if self.current_equity > self.peak_equity {
-            self.peak_equity = self.current_equity;
-        }
+        // The closed position's mark is now realized; the next
+        // `mark_to_market` re-marks whatever is still open
+        self.unrealized_pnl = Decimal::ZERO;
+        self.update_peak();
 
         // Reset daily counters if new day
-        self.check_new_day();
+        self.check_new_day(now);
+    }
+
+    /// Start the daily loss/trade window at `now`
+    ///
+    /// Backtests call this with the first replayed timestamp, since the
+    /// window otherwise starts at construction (wall clock) time.
+    pub fn reset_day_at(&mut self, now: SystemTime) {
+        self.daily_pnl = Decimal::ZERO;
+        self.daily_trades = 0;
+        self.day_start = now;
+    }
+
+    /// Count `others` (exposure of other instances) against a fleet-wide
+    /// `max_exposure` on every entry
+    pub fn set_fleet_exposure(&mut self, others: Decimal, max_exposure: Decimal) {
+        self.fleet_exposure = Some((others, max_exposure));
     }
 
     /// Record execution latency
@@ -230,13 +360,22 @@ impl RiskManager {
         }
     }
 
-    /// Calculate current drawdown percentage
+    /// Realized equity plus the open positions' last mark
+    pub fn marked_equity(&self) -> Decimal {
+        self.current_equity + self.unrealized_pnl
+    }
+
+    fn update_peak(&mut self) {
+        self.peak_equity = self.peak_equity.max(self.marked_equity());
+    }
+
+    /// Calculate current drawdown percentage (marked to market)
     fn calculate_drawdown(&self) -> Decimal {
         if self.peak_equity.is_zero() {
             return Decimal::ZERO;
         }
 
-        let drawdown = (self.peak_equity - self.current_equity) / self.peak_equity;
+        let drawdown = (self.peak_equity - self.marked_equity()) / self.peak_equity;
         drawdown * Decimal::from(100)
     }
 
@@ -251,8 +390,8 @@ impl RiskManager {
     }
 
     /// Remove trades older than 1 hour
-    fn cleanup_old_trades(&mut self) {
-        let one_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+    fn cleanup_old_trades(&mut self, now: SystemTime) {
+        let one_hour_ago = now - Duration::from_secs(3600);
         
         while let Some(&trade_time) = self.hourly_trades.front() {
             if trade_time < one_hour_ago {
@@ -264,15 +403,13 @@ impl RiskManager {
     }
 
     /// Check if new day and reset counters
-    fn check_new_day(&mut self) {
-        let elapsed = SystemTime::now()
+    fn check_new_day(&mut self, now: SystemTime) {
+        let elapsed = now
             .duration_since(self.day_start)
             .unwrap_or(Duration::ZERO);
 
         if elapsed.as_secs() >= 86400 {  // 24 hours
-            self.daily_pnl = Decimal::ZERO;
-            self.daily_trades = 0;
-            self.day_start = SystemTime::now();
+            self.reset_day_at(now);
         }
     }
 
@@ -298,6 +435,38 @@ impl RiskManager {
         self.halt_reason.as_deref()
     }
 
+    /// Append the current metrics if `interval` has passed since the last
+    /// sample; the oldest sample is dropped once the history is full
+    pub fn sample_metrics_at(&mut self, now: SystemTime) {
+        let timestamp_ms = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64);
+        let due = self.metrics_history.back().is_none_or(|last| {
+            timestamp_ms >= last.timestamp_ms + self.history_interval.as_millis() as u64
+        });
+        if !due {
+            return;
+        }
+
+        self.metrics_history.push_back(RiskMetricsSample {
+            timestamp_ms,
+            metrics: self.get_metrics(),
+        });
+        while self.metrics_history.len() > self.history_capacity {
+            self.metrics_history.pop_front();
+        }
+    }
+
+    /// Every retained metrics sample, oldest first
+    pub fn metrics_history(&self) -> impl Iterator<Item = &RiskMetricsSample> {
+        self.metrics_history.iter()
+    }
+
+    /// Retained samples taken at or after `since`, oldest first
+    pub fn metrics_history_since(&self, since: SystemTime) -> Vec<RiskMetricsSample> {
+        let since_ms = since.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64);
+        let start = self.metrics_history.partition_point(|sample| sample.timestamp_ms < since_ms);
+        self.metrics_history.range(start..).cloned().collect()
+    }
+
     /// Get current risk metrics
     pub fn get_metrics(&self) -> RiskMetrics {
         RiskMetrics {
@@ -306,6 +475,7 @@ impl RiskManager {
             hourly_trades: self.hourly_trades.len(),
             drawdown_percent: self.calculate_drawdown(),
             current_equity: self.current_equity,
+            unrealized_pnl: self.unrealized_pnl,
             peak_equity: self.peak_equity,
             average_latency_ms: self.average_latency(),
             trading_halted: self.trading_halted,
@@ -320,12 +490,24 @@ pub struct RiskMetrics {
     pub daily_trades: usize,
     pub hourly_trades: usize,
     pub drawdown_percent: Decimal,
+    /// Realized equity
     pub current_equity: Decimal,
+    /// Open PnL at the last mark-to-market
+    #[serde(default)]
+    pub unrealized_pnl: Decimal,
     pub peak_equity: Decimal,
     pub average_latency_ms: Option<u64>,
     pub trading_halted: bool,
 }
 
+/// `RiskMetrics` at one point in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskMetricsSample {
+    /// Milliseconds since UNIX epoch
+    pub timestamp_ms: u64,
+    pub metrics: RiskMetrics,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,6 +554,19 @@ mod tests {
         assert!(manager.is_halted());
     }
 
+    #[test]
+    fn test_per_trade_loss_cap() {
+        let limits = RiskLimits { max_loss_per_trade: dec!(50), ..RiskLimits::default() };
+        let manager = RiskManager::new(limits, dec!(10000));
+
+        assert!(manager.check_trade_loss(dec!(-49)).is_ok());
+        assert!(manager.check_trade_loss(dec!(-51)).is_err());
+
+        // Disabled by default
+        let manager = RiskManager::new(RiskLimits::default(), dec!(10000));
+        assert!(manager.check_trade_loss(dec!(-1000)).is_ok());
+    }
+
     #[test]
     fn test_drawdown_limit() {
         let limits = RiskLimits::default();
@@ -385,10 +580,31 @@ mod tests {
         assert!(manager.is_halted());
     }
 
+    #[test]
+    fn test_open_loss_trips_breaker_before_close() {
+        let limits = RiskLimits { max_daily_loss: dec!(5000), ..RiskLimits::default() };
+        let mut manager = RiskManager::new(limits, dec!(10000));
+
+        // Gains raise the marked high-water mark
+        assert!(manager.mark_to_market(dec!(400)).is_ok());
+        assert_eq!(manager.get_metrics().peak_equity, dec!(10400));
+
+        // A 700 open loss is 10.6% off the peak
+        let violation = manager.mark_to_market(dec!(-700)).unwrap_err();
+        assert_eq!(violation.severity, ViolationSeverity::Emergency);
+        assert!(violation.reason.starts_with("Drawdown"));
+        assert!(manager.is_halted());
+        assert_eq!(manager.get_metrics().current_equity, dec!(10000));
+
+        // Closing realizes the loss in place of the mark
+        manager.record_trade(dec!(-700));
+        assert_eq!(manager.get_metrics().unrealized_pnl, Decimal::ZERO);
+        assert_eq!(manager.get_metrics().current_equity, dec!(9300));
+    }
+
     #[test]
     fn test_hourly_trade_limit() {
-        let mut limits = RiskLimits::default();
-        limits.max_trades_per_hour = 5;
+        let limits = RiskLimits { max_trades_per_hour: 5, ..Default::default() };
         let mut manager = RiskManager::new(limits, dec!(10000));
 
         // Record 5 trades
@@ -422,6 +638,21 @@ mod tests {
         assert!(manager.is_halted());
     }
 
+    #[test]
+    fn test_replay_clock_expires_hourly_window() {
+        let limits = RiskLimits { max_trades_per_hour: 2, ..Default::default() };
+        let mut manager = RiskManager::new(limits, dec!(10000));
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        manager.reset_day_at(start);
+        manager.record_trade_at(dec!(10), start);
+        manager.record_trade_at(dec!(10), start);
+
+        assert!(manager.can_open_position_at(dec!(1000), dec!(0), start).is_err());
+        let later = start + Duration::from_secs(3601);
+        assert!(manager.can_open_position_at(dec!(1000), dec!(0), later).is_ok());
+    }
+
     #[test]
     fn test_resume_trading() {
         let limits = RiskLimits::default();
@@ -433,4 +664,29 @@ mod tests {
         manager.resume_trading();
         assert!(!manager.is_halted());
     }
+
+    #[test]
+    fn test_metrics_history_is_sampled_and_bounded() {
+        let mut manager = RiskManager::new(RiskLimits::default(), dec!(10000))
+            .with_metrics_history(Duration::from_secs(60), 3);
+        let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        manager.sample_metrics_at(at(0));
+        manager.sample_metrics_at(at(30)); // Too soon
+        manager.record_trade_at(dec!(-200), at(45));
+        for minute in 1..=3 {
+            manager.sample_metrics_at(at(60 * minute));
+        }
+
+        let history: Vec<_> = manager.metrics_history().collect();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].timestamp_ms, 1_700_000_060_000);
+        assert_eq!(history[0].metrics.current_equity, dec!(9800));
+        assert_eq!(history[0].metrics.drawdown_percent, dec!(2));
+
+        let recent = manager.metrics_history_since(at(120));
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].timestamp_ms, 1_700_000_120_000);
+    }
 }
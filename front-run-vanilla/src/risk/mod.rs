@@ -1,5 +1,15 @@
 pub mod position;
 pub mod limits;
+pub mod handle;
+pub mod calendar;
+pub mod instrument;
+pub mod shared_state;
 
 pub use position::{Position, PositionManager};
-pub use limits::{RiskManager, RiskLimits, RiskMetrics, RiskViolation, ViolationSeverity};
+pub use limits::{
+    RiskManager, RiskLimits, RiskMetrics, RiskMetricsSample, RiskViolation, ViolationSeverity,
+};
+pub use handle::{RiskHandle, apply_risk_command};
+pub use calendar::{EventCalendar, ScheduledEvent, EventSeverity, BlackoutPolicy, BlackoutWindow};
+pub use instrument::{Instrument, is_usd_quote};
+pub use shared_state::{FleetState, FleetStatus, FleetSync, InstanceReport, SharedState, SharedStateConfig};
@@ -1,8 +1,10 @@
-use crate::data::{Side, Order};
+use crate::data::Side;
+use crate::risk::Instrument;
 use rust_decimal::Decimal;
+use std::path::Path;
 use std::time::{SystemTime, Duration};
 use serde::{Serialize, Deserialize};
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 
 /// Position tracker with real-time PnL calculation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +16,11 @@ pub struct Position {
     pub entry_time: SystemTime,
     pub realized_pnl: Decimal,
     pub fees_paid: Decimal,
+    /// Per-position take profit (bps) chosen at entry, if scaled by signal
+    pub take_profit_bps: Option<Decimal>,
+    /// Denomination of quantity and PnL; fees are in its settlement asset
+    #[serde(default)]
+    pub instrument: Instrument,
 }
 
 impl Position {
@@ -33,23 +40,38 @@ impl Position {
             entry_time: SystemTime::now(),
             realized_pnl: Decimal::ZERO,
             fees_paid: fees,
+            take_profit_bps: None,
+            instrument: Instrument::linear(),
         }
     }
 
-    /// Calculate unrealized PnL at current price
-    pub fn unrealized_pnl(&self, current_price: Decimal) -> Decimal {
-        let price_diff = match self.side {
-            Side::Buy => current_price - self.entry_price,  // Long: profit if price up
-            Side::Sell => self.entry_price - current_price, // Short: profit if price down
-        };
+    /// Set the instrument (linear, 1:1 by default)
+    pub fn with_instrument(mut self, instrument: Instrument) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Stamp the entry with `at` instead of now (replayed fills)
+    pub fn with_entry_time(mut self, at: SystemTime) -> Self {
+        self.entry_time = at;
+        self
+    }
+
+    /// Set the take profit target for this position
+    pub fn with_take_profit(mut self, take_profit_bps: Decimal) -> Self {
+        self.take_profit_bps = Some(take_profit_bps);
+        self
+    }
 
-        price_diff * self.quantity - self.fees_paid
+    /// Calculate unrealized PnL at current price, in the settlement asset
+    pub fn unrealized_pnl(&self, current_price: Decimal) -> Decimal {
+        self.instrument.pnl(self.side, self.entry_price, current_price, self.quantity) - self.fees_paid
     }
 
     /// Calculate unrealized PnL percentage
     pub fn unrealized_pnl_percent(&self, current_price: Decimal) -> Decimal {
         let pnl = self.unrealized_pnl(current_price);
-        let cost_basis = self.entry_price * self.quantity;
+        let cost_basis = self.instrument.notional(self.quantity, self.entry_price);
         
         if cost_basis.is_zero() {
             return Decimal::ZERO;
@@ -66,21 +88,30 @@ impl Position {
         pnl
     }
 
-    /// Get position notional value
+    /// Get position notional value at entry, in the quote asset
     pub fn notional_value(&self) -> Decimal {
-        self.entry_price * self.quantity
+        self.instrument.quote_notional(self.quantity, self.entry_price)
     }
 
     /// Get position age
     pub fn age(&self) -> Duration {
-        SystemTime::now()
-            .duration_since(self.entry_time)
+        self.age_at(SystemTime::now())
+    }
+
+    /// Position age as of `now`
+    pub fn age_at(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.entry_time)
             .unwrap_or(Duration::ZERO)
     }
 
     /// Check if position has been open too long
     pub fn is_expired(&self, max_hold_time_ms: u64) -> bool {
-        self.age().as_millis() as u64 > max_hold_time_ms
+        self.is_expired_at(max_hold_time_ms, SystemTime::now())
+    }
+
+    /// Check if position had been open too long as of `now`
+    pub fn is_expired_at(&self, max_hold_time_ms: u64, now: SystemTime) -> bool {
+        self.age_at(now).as_millis() as u64 > max_hold_time_ms
     }
 
     /// Check if take profit hit
@@ -96,9 +127,24 @@ impl Position {
         let target = -(stop_loss_bps / Decimal::from(100)); // Negative for loss
         pnl_pct <= target
     }
+
+    /// Check if the entry imbalance has reverted: the detector z-score, signed
+    /// in the position's direction, is back below `exit_z`
+    pub fn signal_decayed(&self, z_score: f64, exit_z: f64) -> bool {
+        let z_in_direction = match self.side {
+            Side::Buy => z_score,
+            Side::Sell => -z_score,
+        };
+        z_in_direction < exit_z
+    }
 }
 
 /// Position manager tracking all open positions
+///
+/// Serializes with its open and closed positions and running totals, so
+/// a session can be restored after a crash, moved to another host or
+/// loaded as a test fixture (`save` / `load`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PositionManager {
     positions: Vec<Position>,
     closed_positions: Vec<Position>,
@@ -116,6 +162,36 @@ impl PositionManager {
         }
     }
 
+    /// Write a snapshot to `path` as JSON
+    ///
+    /// Written to a temporary file and renamed into place, so a crash
+    /// mid-write leaves the previous snapshot intact.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read a snapshot written by `save`
+    ///
+    /// Fails if the totals do not match the closed positions, e.g. after a
+    /// hand edit that would otherwise skew PnL and win rate silently.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let manager: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+
+        let realized: Decimal = manager.closed_positions.iter().map(|p| p.realized_pnl).sum();
+        let fees: Decimal = manager.closed_positions.iter().map(|p| p.fees_paid).sum();
+        if realized != manager.total_realized_pnl || fees != manager.total_fees {
+            return Err(anyhow!(
+                "Position snapshot totals (PnL {}, fees {}) do not match closed positions (PnL {}, fees {})",
+                manager.total_realized_pnl, manager.total_fees, realized, fees
+            ));
+        }
+        Ok(manager)
+    }
+
     /// Open a new position
     pub fn open_position(&mut self, position: Position) -> Result<()> {
         self.positions.push(position);
@@ -297,6 +373,18 @@ mod tests {
         assert!(pos.stop_loss_hit(dec!(99.93), dec!(5.0)));  // -0.07% hit!
     }
 
+    #[test]
+    fn test_signal_decayed() {
+        let long = Position::new("BTCUSDT".to_string(), Side::Buy, dec!(100.0), dec!(1.0), dec!(0));
+        assert!(!long.signal_decayed(2.5, 1.0));
+        assert!(long.signal_decayed(0.8, 1.0));
+
+        // Shorts came from negative z-scores
+        let short = Position::new("BTCUSDT".to_string(), Side::Sell, dec!(100.0), dec!(1.0), dec!(0));
+        assert!(!short.signal_decayed(-2.5, 1.0));
+        assert!(short.signal_decayed(0.5, 1.0));
+    }
+
     #[test]
     fn test_position_manager() {
         let mut manager = PositionManager::new();
@@ -340,4 +428,30 @@ mod tests {
         let win_rate = manager.win_rate();
         assert!((win_rate - 0.666).abs() < 0.01); // 2/3 = 66.6%
     }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut manager = PositionManager::new();
+        manager.open_position(Position::new("BTCUSDT".into(), Side::Buy, dec!(100), dec!(1), dec!(0.04))).unwrap();
+        manager.close_position("BTCUSDT", dec!(110), dec!(0.04)).unwrap();
+        manager.open_position(
+            Position::new("ETHUSDT".into(), Side::Sell, dec!(50), dec!(2), dec!(0.04)).with_take_profit(dec!(12)),
+        ).unwrap();
+
+        let path = std::env::temp_dir().join(format!("positions_test_{}.json", std::process::id()));
+        manager.save(&path).unwrap();
+        let restored = PositionManager::load(&path).unwrap();
+        assert_eq!(restored.position_count(), 1);
+        assert_eq!(restored.get_position("ETHUSDT").unwrap().take_profit_bps, Some(dec!(12)));
+        assert_eq!(restored.total_realized_pnl(), dec!(9.92));
+        assert_eq!(restored.total_fees(), dec!(0.08));
+
+        // Totals that disagree with the closed positions are refused
+        let mut tampered = restored.clone();
+        tampered.total_realized_pnl = dec!(1000);
+        tampered.save(&path).unwrap();
+        assert!(PositionManager::load(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
 }
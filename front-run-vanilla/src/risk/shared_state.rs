@@ -0,0 +1,297 @@
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Fleet-wide risk limits shared between processes (`[shared_state]`)
+///
+/// Each instance (typically one per symbol) publishes its daily PnL and
+/// open exposure; every instance then enforces the fleet totals on top of
+/// its own `[risk]` limits. Disabled with an empty backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharedStateConfig {
+    /// "" (disabled) or "redis" (needs the `redis` feature)
+    pub backend: String,
+    pub redis_url: String,
+    /// Namespace for every key, so fleets can share one server
+    pub key_prefix: String,
+    /// Name this process reports under (empty = the trading symbol)
+    pub instance: String,
+    pub sync_interval_ms: u64,
+    /// Instances silent for longer no longer count toward fleet exposure
+    pub stale_after_ms: u64,
+    /// Fleet daily loss (realized plus open) that halts every instance (0 = disabled)
+    pub max_daily_loss_usd: f64,
+    /// Fleet open notional cap checked on every entry (0 = disabled)
+    pub max_exposure_usd: f64,
+}
+
+impl Default for SharedStateConfig {
+    fn default() -> Self {
+        Self {
+            backend: String::new(),
+            redis_url: "redis://127.0.0.1:6379".to_string(),
+            key_prefix: "front_run_vanilla".to_string(),
+            instance: String::new(),
+            sync_interval_ms: 1000,
+            stale_after_ms: 30_000,
+            max_daily_loss_usd: 0.0,
+            max_exposure_usd: 0.0,
+        }
+    }
+}
+
+impl SharedStateConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.backend.is_empty()
+    }
+}
+
+/// One instance's risk figures as last published
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceReport {
+    pub instance: String,
+    /// Realized plus open PnL for the day
+    pub daily_pnl: Decimal,
+    /// Open notional
+    pub exposure: Decimal,
+    pub updated_ms: u64,
+}
+
+/// Everything published for the current UTC day
+#[derive(Debug, Clone, Default)]
+pub struct FleetState {
+    pub reports: Vec<InstanceReport>,
+    /// Fleet halt reason, set by any instance (or an operator)
+    pub halt: Option<String>,
+}
+
+/// Where instances meet
+///
+/// Reports are stored per UTC day, so the fleet daily loss resets at
+/// midnight UTC whatever each process's own daily window is. The halt
+/// flag is not dated: it stays until an operator clears it.
+#[derive(Clone)]
+pub enum SharedState {
+    /// In-process only: instances sharing one handle (tests, simulations);
+    /// `connect` refuses it, since separate processes would never meet
+    Memory(Arc<Mutex<HashMap<String, String>>>),
+    #[cfg(feature = "redis")]
+    Redis {
+        conn: redis::aio::MultiplexedConnection,
+        prefix: String,
+    },
+}
+
+impl SharedState {
+    pub fn memory() -> Self {
+        Self::Memory(Default::default())
+    }
+
+    /// Connect the backend named in `config`
+    pub async fn connect(config: &SharedStateConfig) -> Result<Self> {
+        match config.backend.as_str() {
+            #[cfg(feature = "redis")]
+            "redis" => {
+                let client = redis::Client::open(config.redis_url.as_str())?;
+                let conn = client.get_multiplexed_tokio_connection().await?;
+                Ok(Self::Redis { conn, prefix: config.key_prefix.clone() })
+            }
+            #[cfg(not(feature = "redis"))]
+            "redis" => Err(anyhow!("shared_state backend 'redis' needs a build with --features redis")),
+            "memory" => Err(anyhow!(
+                "shared_state backend 'memory' is not shared between processes; use 'redis' or leave it empty"
+            )),
+            other => Err(anyhow!("unknown shared_state backend '{}'", other)),
+        }
+    }
+
+    pub async fn publish(&mut self, report: &InstanceReport) -> Result<()> {
+        let value = serde_json::to_string(report)?;
+        let day = utc_day(report.updated_ms);
+        match self {
+            Self::Memory(map) => {
+                map.lock().unwrap_or_else(|e| e.into_inner())
+                    .insert(format!("{}:{}", day, report.instance), value);
+            }
+            #[cfg(feature = "redis")]
+            Self::Redis { conn, prefix } => {
+                use redis::AsyncCommands;
+                let key = format!("{}:reports:{}", prefix, day);
+                let _: () = conn.hset(&key, &report.instance, value).await?;
+                let _: () = conn.expire(&key, 2 * 86_400).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reports for the UTC day containing `now_ms`, and the halt flag
+    pub async fn load(&mut self, now_ms: u64) -> Result<FleetState> {
+        let day = utc_day(now_ms);
+        let (values, halt): (Vec<String>, Option<String>) = match self {
+            Self::Memory(map) => {
+                let map = map.lock().unwrap_or_else(|e| e.into_inner());
+                let day_prefix = format!("{}:", day);
+                let values = map.iter()
+                    .filter(|(key, _)| key.starts_with(&day_prefix))
+                    .map(|(_, value)| value.clone())
+                    .collect();
+                (values, map.get("halt").cloned())
+            }
+            #[cfg(feature = "redis")]
+            Self::Redis { conn, prefix } => {
+                use redis::AsyncCommands;
+                let reports: HashMap<String, String> = conn.hgetall(format!("{}:reports:{}", prefix, day)).await?;
+                let halt: Option<String> = conn.get(format!("{}:halt", prefix)).await?;
+                (reports.into_values().collect(), halt)
+            }
+        };
+
+        let reports = values.iter()
+            .map(|value| serde_json::from_str(value))
+            .collect::<serde_json::Result<Vec<InstanceReport>>>()?;
+        Ok(FleetState { reports, halt })
+    }
+
+    /// Halt the fleet; an existing reason is kept
+    pub async fn set_halt(&mut self, reason: &str) -> Result<()> {
+        match self {
+            Self::Memory(map) => {
+                map.lock().unwrap_or_else(|e| e.into_inner())
+                    .entry("halt".to_string())
+                    .or_insert_with(|| reason.to_string());
+            }
+            #[cfg(feature = "redis")]
+            Self::Redis { conn, prefix } => {
+                use redis::AsyncCommands;
+                let _: bool = conn.set_nx(format!("{}:halt", prefix), reason).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What this instance must enforce after a sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct FleetStatus {
+    /// Fresh exposure of every other instance
+    pub others_exposure: Decimal,
+    pub fleet_daily_pnl: Decimal,
+    /// Stop entries and flatten: the fleet is halted
+    pub halt: Option<String>,
+}
+
+/// Publishes this instance's figures and enforces the fleet limits
+pub struct FleetSync {
+    state: SharedState,
+    instance: String,
+    max_daily_loss: Decimal,
+    max_exposure: Decimal,
+    stale_after_ms: u64,
+}
+
+impl FleetSync {
+    pub fn new(state: SharedState, instance: String, config: &SharedStateConfig) -> Self {
+        Self {
+            state,
+            instance,
+            max_daily_loss: Decimal::from_f64_retain(config.max_daily_loss_usd).unwrap_or_default(),
+            max_exposure: Decimal::from_f64_retain(config.max_exposure_usd).unwrap_or_default(),
+            stale_after_ms: config.stale_after_ms,
+        }
+    }
+
+    pub fn instance(&self) -> &str {
+        &self.instance
+    }
+
+    /// Fleet exposure cap (None if disabled)
+    pub fn max_exposure(&self) -> Option<Decimal> {
+        (self.max_exposure > Decimal::ZERO).then_some(self.max_exposure)
+    }
+
+    /// Publish `daily_pnl` and `exposure`, then read the fleet back
+    ///
+    /// Breaching the fleet daily loss sets the shared halt flag, so every
+    /// instance stops on its next sync.
+    pub async fn sync(&mut self, daily_pnl: Decimal, exposure: Decimal) -> Result<FleetStatus> {
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.state.publish(&InstanceReport {
+            instance: self.instance.clone(),
+            daily_pnl,
+            exposure,
+            updated_ms: now_ms,
+        }).await?;
+
+        let fleet = self.state.load(now_ms).await?;
+        let fleet_daily_pnl: Decimal = fleet.reports.iter().map(|report| report.daily_pnl).sum();
+        let others_exposure = fleet.reports.iter()
+            .filter(|report| report.instance != self.instance)
+            .filter(|report| now_ms.saturating_sub(report.updated_ms) <= self.stale_after_ms)
+            .map(|report| report.exposure)
+            .sum();
+
+        let mut halt = fleet.halt;
+        if halt.is_none() && self.max_daily_loss > Decimal::ZERO && fleet_daily_pnl < -self.max_daily_loss {
+            let reason = format!("Fleet daily loss {} exceeds limit {}", -fleet_daily_pnl, self.max_daily_loss);
+            self.state.set_halt(&reason).await?;
+            halt = Some(reason);
+        }
+
+        Ok(FleetStatus { others_exposure, fleet_daily_pnl, halt })
+    }
+}
+
+fn utc_day(epoch_ms: u64) -> u64 {
+    epoch_ms / 86_400_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::{RiskLimits, RiskManager};
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_fleet_limits_across_instances() {
+        let config = SharedStateConfig {
+            backend: "memory".to_string(),
+            max_daily_loss_usd: 500.0,
+            max_exposure_usd: 6000.0,
+            ..SharedStateConfig::default()
+        };
+        let state = SharedState::memory();
+        let mut btc = FleetSync::new(state.clone(), "BTCUSDT".to_string(), &config);
+        let mut eth = FleetSync::new(state, "ETHUSDT".to_string(), &config);
+
+        btc.sync(dec!(-300), dec!(4000)).await.unwrap();
+        let status = eth.sync(dec!(-100), dec!(1000)).await.unwrap();
+        assert_eq!(status.others_exposure, dec!(4000));
+        assert_eq!(status.halt, None);
+
+        // ETH alone is within the local 10k cap, not the fleet's 6k
+        let mut risk = RiskManager::new(RiskLimits::default(), dec!(10000));
+        risk.set_fleet_exposure(status.others_exposure, eth.max_exposure().unwrap());
+        assert!(risk.can_open_position(dec!(1000), dec!(1000)).is_ok());
+        assert!(risk.can_open_position(dec!(1500), dec!(1000)).is_err());
+
+        // Combined loss crosses 500: the halt reaches BTC too
+        let status = eth.sync(dec!(-250), dec!(1000)).await.unwrap();
+        assert_eq!(status.fleet_daily_pnl, dec!(-550));
+        assert!(status.halt.is_some());
+        assert_eq!(btc.sync(dec!(-300), dec!(0)).await.unwrap().halt, status.halt);
+    }
+
+    #[tokio::test]
+    async fn test_connect_refuses_in_process_backend() {
+        let config = |backend: &str| SharedStateConfig { backend: backend.to_string(), ..SharedStateConfig::default() };
+        // Every instance would see only its own figures
+        assert!(SharedState::connect(&config("memory")).await.is_err());
+        assert!(SharedState::connect(&config("etcd")).await.is_err());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::data::{OrderBook, Side};
+use crate::exchange::checksum::clear_book;
+use anyhow::{Result, anyhow, bail};
+use rust_decimal::Decimal;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+use tracing::warn;
+
+/// Leading bytes of every snapshot record
+const MAGIC: &[u8; 4] = b"FRBK";
+
+/// Record layout this build writes
+///
+/// Bumped only for incompatible layouts, which readers of an older build
+/// refuse. New fields are appended to the body without a bump; the body is
+/// length prefixed, so older readers skip them.
+pub const BOOK_SNAPSHOT_VERSION: u16 = 1;
+
+/// Largest record body read; a corrupt length must not allocate gigabytes
+/// (a full 5000-level book is ~320 KB)
+const MAX_BODY_BYTES: usize = 16 << 20;
+
+/// Full order book at one instant, in a compact binary encoding
+///
+/// Record layout (little endian):
+/// `magic[4] version:u16 body_len:u32 body`, with a v1 body of
+/// `symbol_len:u16 symbol timestamp_ms:u64 last_update_id:u64 (0 = unknown)
+/// bid_count:u32 (price[16] qty[16])* ask_count:u32 (price[16] qty[16])*`.
+/// Decimals use `Decimal::serialize`, so values round-trip exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookSnapshot {
+    pub symbol: String,
+    pub timestamp_ms: u64,
+    /// Final update ID applied before the snapshot, if the feed has one
+    pub last_update_id: Option<u64>,
+    /// Bids, best first
+    pub bids: Vec<(Decimal, Decimal)>,
+    /// Asks, best first
+    pub asks: Vec<(Decimal, Decimal)>,
+}
+
+impl BookSnapshot {
+    /// Copy every level of `orderbook`
+    pub fn capture(orderbook: &OrderBook, symbol: &str, timestamp_ms: u64, last_update_id: Option<u64>) -> Self {
+        let (bid_levels, ask_levels) = orderbook.get_book_depth_count();
+        let (bids, asks) = orderbook.get_depth(bid_levels.max(ask_levels));
+        Self {
+            symbol: symbol.to_uppercase(),
+            timestamp_ms,
+            last_update_id,
+            bids: bids.iter().map(|level| (level.price, level.quantity)).collect(),
+            asks: asks.iter().map(|level| (level.price, level.quantity)).collect(),
+        }
+    }
+
+    /// Replace the contents of `orderbook` with this snapshot
+    pub fn restore(&self, orderbook: &OrderBook) -> Result<()> {
+        clear_book(orderbook)?;
+        for (price, qty) in &self.bids {
+            orderbook.update_level(Side::Buy, *price, *qty)?;
+        }
+        for (price, qty) in &self.asks {
+            orderbook.update_level(Side::Sell, *price, *qty)?;
+        }
+        Ok(())
+    }
+
+    /// Encode as one record at `BOOK_SNAPSHOT_VERSION`
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(32 + (self.bids.len() + self.asks.len()) * 32);
+        body.extend_from_slice(&(self.symbol.len() as u16).to_le_bytes());
+        body.extend_from_slice(self.symbol.as_bytes());
+        body.extend_from_slice(&self.timestamp_ms.to_le_bytes());
+        body.extend_from_slice(&self.last_update_id.unwrap_or(0).to_le_bytes());
+        for levels in [&self.bids, &self.asks] {
+            body.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+            for (price, qty) in levels {
+                body.extend_from_slice(&price.serialize());
+                body.extend_from_slice(&qty.serialize());
+            }
+        }
+
+        let mut record = Vec::with_capacity(10 + body.len());
+        record.extend_from_slice(MAGIC);
+        record.extend_from_slice(&BOOK_SNAPSHOT_VERSION.to_le_bytes());
+        record.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        record.extend_from_slice(&body);
+        record
+    }
+
+    /// Read the next record; `None` at the end of stream
+    ///
+    /// A record cut short by a crash mid-append ends the stream with a
+    /// warning, so the snapshots before it still load.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Option<Self>> {
+        let mut magic = [0u8; 4];
+        match reader.read_exact(&mut magic) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        if &magic != MAGIC {
+            bail!("not a book snapshot record");
+        }
+
+        let mut header = [0u8; 6];
+        if !read_record_part(reader, &mut header)? {
+            return Ok(None);
+        }
+        let version = u16::from_le_bytes([header[0], header[1]]);
+        let body_len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        if version == 0 || version > BOOK_SNAPSHOT_VERSION {
+            bail!("book snapshot version {} not supported (newest known {})", version, BOOK_SNAPSHOT_VERSION);
+        }
+        if body_len > MAX_BODY_BYTES {
+            bail!("book snapshot body of {} bytes exceeds {} bytes", body_len, MAX_BODY_BYTES);
+        }
+
+        let mut body = vec![0u8; body_len];
+        if !read_record_part(reader, &mut body)? {
+            return Ok(None);
+        }
+        Self::decode_body(&body).map(Some)
+    }
+
+    /// Decode a single encoded record
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        Self::read_from(&mut &bytes[..])?.ok_or_else(|| anyhow!("empty book snapshot"))
+    }
+
+    fn decode_body(body: &[u8]) -> Result<Self> {
+        let mut cursor = Cursor { bytes: body };
+        let symbol_len = cursor.u16()? as usize;
+        let symbol = String::from_utf8(cursor.take(symbol_len)?.to_vec())?;
+        let timestamp_ms = cursor.u64()?;
+        let last_update_id = Some(cursor.u64()?).filter(|id| *id != 0);
+        let bids = cursor.levels()?;
+        let asks = cursor.levels()?;
+        // Anything left was appended by a newer build; skip it
+
+        Ok(Self { symbol, timestamp_ms, last_update_id, bids, asks })
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < n {
+            bail!("truncated book snapshot");
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn decimal(&mut self) -> Result<Decimal> {
+        Ok(Decimal::deserialize(self.take(16)?.try_into()?))
+    }
+
+    fn levels(&mut self) -> Result<Vec<(Decimal, Decimal)>> {
+        let count = self.u32()? as usize;
+        // Bound the allocation by what the body can actually hold
+        let mut levels = Vec::with_capacity(count.min(self.bytes.len() / 32));
+        for _ in 0..count {
+            levels.push((self.decimal()?, self.decimal()?));
+        }
+        Ok(levels)
+    }
+}
+
+/// Fill `buf` from the rest of a started record; false if the stream ends first
+fn read_record_part<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<bool> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+            warn!("Book snapshot record truncated at end of stream, ignoring it");
+            Ok(false)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Append-only file of book snapshot records
+///
+/// Opened in append mode like `JsonlWriter`, so restarts extend the archive.
+pub struct BookSnapshotWriter {
+    writer: BufWriter<File>,
+    records_written: u64,
+}
+
+impl BookSnapshotWriter {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+            records_written: 0,
+        })
+    }
+
+    /// Append one snapshot and flush it, so a crash loses at most this one
+    pub fn append(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        self.writer.write_all(&snapshot.encode())?;
+        self.writer.flush()?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Number of snapshots written since open
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+}
+
+/// Read every snapshot in a file, oldest first
+pub fn load_book_snapshots<P: AsRef<Path>>(path: P) -> Result<Vec<BookSnapshot>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut snapshots = Vec::new();
+    while let Some(snapshot) = BookSnapshot::read_from(&mut reader)? {
+        snapshots.push(snapshot);
+    }
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn snapshot() -> BookSnapshot {
+        BookSnapshot {
+            symbol: "BTCUSDT".to_string(),
+            timestamp_ms: 1_700_000_000_000,
+            last_update_id: Some(42),
+            bids: vec![(dec!(50000.1), dec!(1.5)), (dec!(50000.0), dec!(0.001))],
+            asks: vec![(dec!(50000.2), dec!(3))],
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_file() {
+        let path = std::env::temp_dir().join(format!("book_snapshot_test_{}.bin", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut writer = BookSnapshotWriter::open(&path).unwrap();
+        writer.append(&snapshot()).unwrap();
+        let later = BookSnapshot { timestamp_ms: 1_700_000_001_000, last_update_id: None, ..snapshot() };
+        writer.append(&later).unwrap();
+
+        let loaded = load_book_snapshots(&path).unwrap();
+        assert_eq!(loaded, vec![snapshot(), later]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_truncated_last_record_is_dropped() {
+        let path = std::env::temp_dir().join(format!("book_snapshot_torn_test_{}.bin", std::process::id()));
+        let mut bytes = snapshot().encode();
+        let torn = BookSnapshot { timestamp_ms: 1_700_000_001_000, ..snapshot() }.encode();
+        bytes.extend_from_slice(&torn[..torn.len() - 5]);
+        std::fs::write(&path, &bytes).unwrap();
+
+        // A crash mid-append loses only the record being written
+        assert_eq!(load_book_snapshots(&path).unwrap(), vec![snapshot()]);
+
+        // Torn inside the header too
+        std::fs::write(&path, &bytes[..snapshot().encode().len() + 7]).unwrap();
+        assert_eq!(load_book_snapshots(&path).unwrap(), vec![snapshot()]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_body_is_refused() {
+        let mut record = snapshot().encode();
+        record[6..10].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(BookSnapshot::decode(&record).is_err());
+    }
+
+    #[test]
+    fn test_versions_and_trailing_fields() {
+        // A newer build that appended a field is still readable
+        let mut record = snapshot().encode();
+        let body_len = u32::from_le_bytes(record[6..10].try_into().unwrap()) + 4;
+        record[6..10].copy_from_slice(&body_len.to_le_bytes());
+        record.extend_from_slice(&[0xAB; 4]);
+        assert_eq!(BookSnapshot::decode(&record).unwrap(), snapshot());
+
+        // An unknown layout version is refused rather than misread
+        let mut record = snapshot().encode();
+        record[4..6].copy_from_slice(&(BOOK_SNAPSHOT_VERSION + 1).to_le_bytes());
+        assert!(BookSnapshot::decode(&record).is_err());
+
+        let record = snapshot().encode();
+        assert!(BookSnapshot::decode(&record[..record.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,478 @@
+use crate::data::Side;
+use crate::exchange::MarketEvent;
+use crate::strategy::{CompositeSignal, JournalFill, JournalLeg};
+use anyhow::{Result, anyhow, bail};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+
+/// Schema version of `BusMessage` this build writes
+///
+/// Binary frames are positional, so appending a variant is compatible
+/// (older readers fail only on the new variant) but adding, removing or
+/// reordering fields is not and needs a bump. Readers refuse other versions.
+pub const BUS_FRAME_VERSION: u16 = 1;
+
+/// How published events are encoded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameEncoding {
+    /// One JSON object per line: `{"v":1,"message":{"<type>":{...}}}`
+    #[default]
+    Json,
+    /// Length-prefixed postcard: `len:u32 version:u16 payload`, little
+    /// endian, `len` counting the version and payload
+    Binary,
+}
+
+/// Event published on the bus, version 1 schema
+///
+/// Wire types are kept separate from the internal ones so internal
+/// refactors do not change the published schema. Prices and sizes are
+/// decimal strings in both encodings: every `Decimal` goes through
+/// `rust_decimal::serde::str`, since its default deserializer needs a
+/// self-describing format and postcard is not one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BusMessage {
+    Depth {
+        symbol: String,
+        event_time_ms: u64,
+        final_update_id: u64,
+        #[serde(with = "decimal_levels")]
+        bids: Vec<(Decimal, Decimal)>,
+        #[serde(with = "decimal_levels")]
+        asks: Vec<(Decimal, Decimal)>,
+    },
+    Trade {
+        id: u64,
+        #[serde(with = "rust_decimal::serde::str")]
+        price: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        quantity: Decimal,
+        /// Taker side
+        side: Side,
+        timestamp_ms: u64,
+    },
+    BookTicker {
+        symbol: String,
+        event_time_ms: u64,
+        #[serde(with = "rust_decimal::serde::str")]
+        best_bid: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        best_bid_qty: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        best_ask: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        best_ask_qty: Decimal,
+    },
+    MarkPrice {
+        symbol: String,
+        event_time_ms: u64,
+        #[serde(with = "rust_decimal::serde::str")]
+        mark_price: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        index_price: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        funding_rate: Decimal,
+    },
+    /// Feed connected (true) or lost (false)
+    Connection { connected: bool },
+    BookCorrupted {
+        symbol: String,
+        expected: u32,
+        computed: u32,
+    },
+    /// Composite signal that passed aggregation
+    Signal {
+        symbol: String,
+        timestamp_ms: u64,
+        direction: Side,
+        strength: f64,
+        confidence: f64,
+        confirming: u32,
+    },
+    Fill {
+        decision_time_ms: u64,
+        account: String,
+        symbol: String,
+        leg: JournalLeg,
+        side: Side,
+        #[serde(with = "rust_decimal::serde::str")]
+        price: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        quantity: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        fees: Decimal,
+        latency_ms: u64,
+    },
+    /// Forced liquidation of someone else's position
+    Liquidation {
+        symbol: String,
+        event_time_ms: u64,
+        /// Side of the forced order (Sell = a long was liquidated)
+        side: Side,
+        #[serde(with = "rust_decimal::serde::str")]
+        price: Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        quantity: Decimal,
+    },
+}
+
+impl BusMessage {
+    /// Market event as a bus message; None for user data events and if
+    /// its strings do not parse
+    pub fn from_market_event(event: &MarketEvent) -> Option<Self> {
+        Some(match event {
+            MarketEvent::DepthUpdate(update) => Self::Depth {
+                symbol: update.symbol.clone(),
+                event_time_ms: update.event_time,
+                final_update_id: update.final_update_id,
+                bids: update.parse_bids(),
+                asks: update.parse_asks(),
+            },
+            MarketEvent::Trade(trade) => Self::Trade {
+                id: trade.id,
+                price: trade.price,
+                quantity: trade.quantity,
+                side: trade.side,
+                timestamp_ms: epoch_ms(trade.timestamp),
+            },
+            MarketEvent::BookTicker(ticker) => {
+                let (best_bid, best_bid_qty, best_ask, best_ask_qty) = ticker.parse()?;
+                Self::BookTicker {
+                    symbol: ticker.symbol.clone(),
+                    event_time_ms: ticker.event_time,
+                    best_bid,
+                    best_bid_qty,
+                    best_ask,
+                    best_ask_qty,
+                }
+            }
+            MarketEvent::MarkPrice(mark) => Self::MarkPrice {
+                symbol: mark.symbol.clone(),
+                event_time_ms: mark.event_time,
+                mark_price: mark.mark_price.parse().ok()?,
+                index_price: mark.index_price.parse().ok()?,
+                funding_rate: mark.funding_rate.parse().ok()?,
+            },
+            MarketEvent::Connected => Self::Connection { connected: true },
+            MarketEvent::Disconnected => Self::Connection { connected: false },
+            MarketEvent::BookCorrupted(corrupted) => Self::BookCorrupted {
+                symbol: corrupted.symbol.clone(),
+                expected: corrupted.expected,
+                computed: corrupted.computed,
+            },
+            MarketEvent::Liquidation(force) => {
+                let (side, price, quantity) = force.parse()?;
+                Self::Liquidation {
+                    symbol: force.order.symbol.clone(),
+                    event_time_ms: force.event_time,
+                    side,
+                    price,
+                    quantity,
+                }
+            }
+            MarketEvent::OrderUpdate(_) | MarketEvent::AccountUpdate(_) => return None,
+        })
+    }
+
+    pub fn signal(symbol: &str, signal: &CompositeSignal) -> Self {
+        Self::Signal {
+            symbol: symbol.to_string(),
+            timestamp_ms: epoch_ms(signal.timestamp),
+            direction: signal.direction,
+            strength: signal.overall_strength,
+            confidence: signal.confidence,
+            confirming: signal.confirming.len() as u32,
+        }
+    }
+
+    pub fn fill(fill: &JournalFill) -> Self {
+        Self::Fill {
+            decision_time_ms: fill.decision_time_ms,
+            account: fill.account.clone(),
+            symbol: fill.symbol.clone(),
+            leg: fill.leg,
+            side: fill.side,
+            price: fill.executed_price,
+            quantity: fill.executed_qty,
+            fees: fill.fees,
+            latency_ms: fill.latency_ms,
+        }
+    }
+}
+
+/// `(price, quantity)` levels as decimal strings (see `BusMessage`)
+mod decimal_levels {
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(levels: &[(Decimal, Decimal)], serializer: S) -> Result<S::Ok, S::Error> {
+        let levels: Vec<(String, String)> = levels.iter()
+            .map(|(price, qty)| (price.to_string(), qty.to_string()))
+            .collect();
+        levels.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(Decimal, Decimal)>, D::Error> {
+        Vec::<(String, String)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(price, qty)| {
+                Ok((
+                    Decimal::from_str(&price).map_err(serde::de::Error::custom)?,
+                    Decimal::from_str(&qty).map_err(serde::de::Error::custom)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// JSON envelope carrying the schema version
+#[derive(Serialize, Deserialize)]
+struct JsonFrame {
+    v: u16,
+    message: BusMessage,
+}
+
+impl FrameEncoding {
+    /// File name the bus is published to under `data_dir`
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Json => "bus.jsonl",
+            Self::Binary => "bus.bin",
+        }
+    }
+
+    /// One complete frame
+    pub fn encode(&self, message: &BusMessage) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => {
+                let envelope = JsonFrame { v: BUS_FRAME_VERSION, message: message.clone() };
+                let mut frame = serde_json::to_vec(&envelope)?;
+                frame.push(b'\n');
+                Ok(frame)
+            }
+            Self::Binary => {
+                let payload = postcard::to_allocvec(message)
+                    .map_err(|e| anyhow!("Unencodable bus message: {}", e))?;
+                let mut frame = Vec::with_capacity(6 + payload.len());
+                frame.extend_from_slice(&(payload.len() as u32 + 2).to_le_bytes());
+                frame.extend_from_slice(&BUS_FRAME_VERSION.to_le_bytes());
+                frame.extend_from_slice(&payload);
+                Ok(frame)
+            }
+        }
+    }
+
+    /// Every frame in `bytes`, which must end on a frame boundary
+    pub fn decode_all(&self, bytes: &[u8]) -> Result<Vec<BusMessage>> {
+        match self {
+            Self::Json => bytes.split(|byte| *byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    let frame: JsonFrame = serde_json::from_slice(line)?;
+                    check_version(frame.v)?;
+                    Ok(frame.message)
+                })
+                .collect(),
+            Self::Binary => {
+                let mut messages = Vec::new();
+                let mut rest = bytes;
+                while !rest.is_empty() {
+                    if rest.len() < 6 {
+                        bail!("Truncated bus frame header");
+                    }
+                    let len = u32::from_le_bytes(rest[0..4].try_into()?) as usize;
+                    if len < 2 || rest.len() < 4 + len {
+                        bail!("Truncated bus frame ({} of {} bytes)", rest.len() - 4, len);
+                    }
+                    check_version(u16::from_le_bytes(rest[4..6].try_into()?))?;
+                    messages.push(postcard::from_bytes(&rest[6..4 + len])
+                        .map_err(|e| anyhow!("Undecodable bus frame: {}", e))?);
+                    rest = &rest[4 + len..];
+                }
+                Ok(messages)
+            }
+        }
+    }
+}
+
+fn check_version(version: u16) -> Result<()> {
+    if version != BUS_FRAME_VERSION {
+        bail!("Bus frame version {} (this build reads {})", version, BUS_FRAME_VERSION);
+    }
+    Ok(())
+}
+
+/// Publishes bus messages to any byte sink (file, socket, pipe)
+pub struct FrameWriter<W: Write> {
+    writer: W,
+    encoding: FrameEncoding,
+    frames_written: u64,
+}
+
+impl<W: Write> FrameWriter<W> {
+    pub fn new(writer: W, encoding: FrameEncoding) -> Self {
+        Self {
+            writer,
+            encoding,
+            frames_written: 0,
+        }
+    }
+
+    pub fn publish(&mut self, message: &BusMessage) -> Result<()> {
+        self.writer.write_all(&self.encoding.encode(message)?)?;
+        self.frames_written += 1;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+}
+
+/// Non-blocking bus sink for the feed, signal and fill producers
+///
+/// Producers run on the trading paths, so they only push into an unbounded
+/// channel; a background task owns the `FrameWriter` and does the IO.
+/// Cloning the publisher shares the same stream.
+#[derive(Debug, Clone)]
+pub struct BusPublisher {
+    tx: mpsc::UnboundedSender<BusMessage>,
+}
+
+impl BusPublisher {
+    /// Create publisher with a caller-owned receiver (tests, custom sinks)
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<BusMessage>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+
+    /// Create publisher appending `encoding` frames to a file
+    ///
+    /// Must be called from within a tokio runtime (spawns the writer task).
+    pub fn to_file<P: AsRef<Path>>(path: P, encoding: FrameEncoding) -> Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut writer = FrameWriter::new(BufWriter::new(file), encoding);
+        let (publisher, mut rx) = Self::channel();
+
+        tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                if let Err(e) = writer.publish(&message) {
+                    error!("Failed to publish bus message: {}", e);
+                    continue;
+                }
+
+                // Flush once the channel is drained to keep syscalls low
+                if rx.is_empty() {
+                    if let Err(e) = writer.flush() {
+                        warn!("Failed to flush bus: {}", e);
+                    }
+                }
+            }
+            let _ = writer.flush();
+        });
+
+        Ok(publisher)
+    }
+
+    /// Publish a message (never blocks; drops silently if sink closed)
+    pub fn publish(&self, message: BusMessage) {
+        let _ = self.tx.send(message);
+    }
+}
+
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_json_and_binary_round_trip() {
+        let messages = vec![
+            BusMessage::Depth {
+                symbol: "BTCUSDT".to_string(),
+                event_time_ms: 1_700_000_000_000,
+                final_update_id: 42,
+                bids: vec![(dec!(42000.1), dec!(1.5))],
+                asks: vec![(dec!(42000.2), dec!(0.25))],
+            },
+            BusMessage::Connection { connected: false },
+            BusMessage::Fill {
+                decision_time_ms: 1_700_000_000_100,
+                account: "primary".to_string(),
+                symbol: "BTCUSDT".to_string(),
+                leg: JournalLeg::Entry,
+                side: Side::Buy,
+                price: dec!(42000.2),
+                quantity: dec!(0.024),
+                fees: dec!(0.4032),
+                latency_ms: 35,
+            },
+        ];
+
+        for encoding in [FrameEncoding::Json, FrameEncoding::Binary] {
+            let mut writer = FrameWriter::new(Vec::new(), encoding);
+            for message in &messages {
+                writer.publish(message).unwrap();
+            }
+            assert_eq!(writer.frames_written(), 3);
+            assert_eq!(encoding.decode_all(&writer.writer).unwrap(), messages);
+        }
+
+        // Binary is the compact one
+        let json = FrameEncoding::Json.encode(&messages[0]).unwrap();
+        let binary = FrameEncoding::Binary.encode(&messages[0]).unwrap();
+        assert!(binary.len() * 2 < json.len());
+        assert!(String::from_utf8(json).unwrap().starts_with(r#"{"v":1,"message":{"depth":"#));
+
+        // Other schema versions are refused
+        let mut future = binary.clone();
+        future[4] = 2;
+        assert!(FrameEncoding::Binary.decode_all(&future).is_err());
+        assert!(FrameEncoding::Binary.decode_all(&binary[..binary.len() - 1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_publisher_appends_frames_to_file() {
+        let path = std::env::temp_dir().join(format!("bus_test_{}.bin", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let message = BusMessage::Connection { connected: true };
+
+        for _ in 0..2 {
+            let publisher = BusPublisher::to_file(&path, FrameEncoding::Binary).unwrap();
+            publisher.publish(message.clone());
+            drop(publisher);
+            // Writer task drains and flushes once every sender is gone
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+
+        // A restart appends to the same stream
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(FrameEncoding::Binary.decode_all(&bytes).unwrap(), vec![message.clone(), message]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
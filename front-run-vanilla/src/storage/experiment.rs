@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Experiment label and tags stamped onto every journaled fill, recorded
+/// observation and stats snapshot (`[experiment]`)
+///
+/// Lets the records of A/B deployments of parameter variants share a data
+/// directory and still be told apart. Empty by default, in which case
+/// nothing is added to the records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Experiment {
+    /// Free-form label, e.g. "tp-12bps-b"
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    /// Structured tags, e.g. variant = "b", owner = "research"
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+}
+
+impl Experiment {
+    pub fn is_empty(&self) -> bool {
+        self.label.is_empty() && self.tags.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_experiment_is_omitted() {
+        #[derive(Serialize, Deserialize)]
+        struct Record {
+            value: u64,
+            #[serde(default, skip_serializing_if = "Experiment::is_empty")]
+            experiment: Experiment,
+        }
+
+        let json = serde_json::to_string(&Record { value: 1, experiment: Experiment::default() }).unwrap();
+        assert_eq!(json, r#"{"value":1}"#);
+
+        let experiment = Experiment {
+            label: "tp-12bps".to_string(),
+            tags: BTreeMap::from([("variant".to_string(), "b".to_string())]),
+        };
+        let json = serde_json::to_string(&Record { value: 1, experiment: experiment.clone() }).unwrap();
+        assert_eq!(json, r#"{"value":1,"experiment":{"label":"tp-12bps","tags":{"variant":"b"}}}"#);
+        let record: Record = serde_json::from_str(&json).unwrap();
+        assert_eq!(record.experiment, experiment);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::data::OrderBook;
+use anyhow::Result;
+use arrow_array::{Float64Array, Int64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Book density recording for heatmaps (`[storage.heatmap]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeatmapConfig {
+    /// Interval between samples (0 = disabled)
+    pub interval_ms: u64,
+    /// Price range recorded on each side of mid
+    pub band_bps: f64,
+    /// Width of one price bucket
+    pub bucket_bps: f64,
+    /// Samples per Parquet file; a file becomes readable once it is full
+    /// or the trader stops
+    pub samples_per_file: usize,
+}
+
+impl Default for HeatmapConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 0,
+            band_bps: 50.0,
+            bucket_bps: 1.0,
+            samples_per_file: 3600,
+        }
+    }
+}
+
+impl HeatmapConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.interval_ms > 0 && self.band_bps > 0.0 && self.bucket_bps > 0.0
+    }
+
+    /// Buckets across the whole band
+    pub fn bucket_count(&self) -> usize {
+        if !self.is_enabled() {
+            return 0;
+        }
+        ((2.0 * self.band_bps) / self.bucket_bps).ceil() as usize
+    }
+}
+
+/// Resting size per price bucket around mid at one instant
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapSample {
+    pub timestamp_ms: u64,
+    pub mid: f64,
+    /// Bucket centres, in bps from mid (ascending)
+    pub offsets_bps: Vec<f64>,
+    pub bid_sizes: Vec<f64>,
+    pub ask_sizes: Vec<f64>,
+}
+
+impl HeatmapSample {
+    /// Bucket every level of `orderbook` within the configured band
+    ///
+    /// None while either side of the book is empty.
+    pub fn capture(orderbook: &OrderBook, timestamp_ms: u64, config: &HeatmapConfig) -> Option<Self> {
+        let mid = orderbook.get_mid_price()?.to_f64()?;
+        if mid <= 0.0 || !config.is_enabled() {
+            return None;
+        }
+
+        let buckets = config.bucket_count();
+        let offsets_bps = (0..buckets)
+            .map(|i| -config.band_bps + (i as f64 + 0.5) * config.bucket_bps)
+            .collect();
+        let mut sample = Self {
+            timestamp_ms,
+            mid,
+            offsets_bps,
+            bid_sizes: vec![0.0; buckets],
+            ask_sizes: vec![0.0; buckets],
+        };
+
+        let (bid_levels, ask_levels) = orderbook.get_book_depth_count();
+        let (bids, asks) = orderbook.get_depth(bid_levels.max(ask_levels));
+        for (levels, sizes) in [(&bids, &mut sample.bid_sizes), (&asks, &mut sample.ask_sizes)] {
+            for level in levels.iter() {
+                let (Some(price), Some(quantity)) = (level.price.to_f64(), level.quantity.to_f64()) else {
+                    continue;
+                };
+                let offset_bps = (price - mid) / mid * 10000.0;
+                // Levels are best first, so the rest of this side is further out
+                if offset_bps.abs() > config.band_bps {
+                    break;
+                }
+                let bucket = ((offset_bps + config.band_bps) / config.bucket_bps) as usize;
+                sizes[bucket.min(buckets - 1)] += quantity;
+            }
+        }
+        Some(sample)
+    }
+}
+
+/// Writes heatmap samples to Parquet, one file per `samples_per_file`
+///
+/// Rows are long format, one per sample and bucket: `timestamp_ms`, `mid`,
+/// `offset_bps`, `price` (bucket centre), `bid_size`, `ask_size`, so a
+/// time x price matrix is a pivot on `timestamp_ms` and `offset_bps`.
+/// Files are named `heatmap_<SYMBOL>_<first timestamp_ms>.parquet`; the
+/// footer is only written when a file is finished, so the file being
+/// written is not readable until it rotates or the writer is closed.
+pub struct HeatmapWriter {
+    dir: PathBuf,
+    symbol: String,
+    samples_per_file: usize,
+    writer: Option<ArrowWriter<File>>,
+    samples_in_file: usize,
+    /// Samples not yet written as a row group
+    pending: Vec<HeatmapSample>,
+}
+
+/// Samples buffered per row group
+const ROW_GROUP_SAMPLES: usize = 60;
+
+impl HeatmapWriter {
+    pub fn open<P: AsRef<Path>>(dir: P, symbol: &str, config: &HeatmapConfig) -> Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            symbol: symbol.to_uppercase(),
+            samples_per_file: config.samples_per_file.max(1),
+            writer: None,
+            samples_in_file: 0,
+            pending: Vec::new(),
+        })
+    }
+
+    fn schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("timestamp_ms", DataType::Int64, false),
+            Field::new("mid", DataType::Float64, false),
+            Field::new("offset_bps", DataType::Float64, false),
+            Field::new("price", DataType::Float64, false),
+            Field::new("bid_size", DataType::Float64, false),
+            Field::new("ask_size", DataType::Float64, false),
+        ]))
+    }
+
+    pub fn append(&mut self, sample: HeatmapSample) -> Result<()> {
+        if self.writer.is_none() {
+            let path = self.dir.join(format!("heatmap_{}_{}.parquet", self.symbol, sample.timestamp_ms));
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            self.writer = Some(ArrowWriter::try_new(File::create(path)?, Self::schema(), Some(properties))?);
+        }
+
+        self.pending.push(sample);
+        self.samples_in_file += 1;
+        if self.pending.len() >= ROW_GROUP_SAMPLES {
+            self.write_pending()?;
+        }
+        if self.samples_in_file >= self.samples_per_file {
+            self.finish_file()?;
+        }
+        Ok(())
+    }
+
+    fn write_pending(&mut self) -> Result<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let rows: usize = self.pending.iter().map(|sample| sample.offsets_bps.len()).sum();
+        let mut timestamp = Vec::with_capacity(rows);
+        let mut mid = Vec::with_capacity(rows);
+        let mut offset = Vec::with_capacity(rows);
+        let mut price = Vec::with_capacity(rows);
+        let mut bid_size = Vec::with_capacity(rows);
+        let mut ask_size = Vec::with_capacity(rows);
+        for sample in self.pending.drain(..) {
+            for (i, offset_bps) in sample.offsets_bps.iter().enumerate() {
+                timestamp.push(sample.timestamp_ms as i64);
+                mid.push(sample.mid);
+                offset.push(*offset_bps);
+                price.push(sample.mid * (1.0 + offset_bps / 10000.0));
+                bid_size.push(sample.bid_sizes[i]);
+                ask_size.push(sample.ask_sizes[i]);
+            }
+        }
+
+        let batch = RecordBatch::try_new(Self::schema(), vec![
+            Arc::new(Int64Array::from(timestamp)),
+            Arc::new(Float64Array::from(mid)),
+            Arc::new(Float64Array::from(offset)),
+            Arc::new(Float64Array::from(price)),
+            Arc::new(Float64Array::from(bid_size)),
+            Arc::new(Float64Array::from(ask_size)),
+        ])?;
+        writer.write(&batch)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write what is buffered and the footer of the current file
+    fn finish_file(&mut self) -> Result<()> {
+        self.write_pending()?;
+        if let Some(writer) = self.writer.take() {
+            writer.close()?;
+        }
+        self.samples_in_file = 0;
+        Ok(())
+    }
+
+    /// Finish the current file so it can be read
+    pub fn close(mut self) -> Result<()> {
+        self.finish_file()
+    }
+}
+
+impl Drop for HeatmapWriter {
+    fn drop(&mut self) {
+        let _ = self.finish_file();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Side;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_buckets_band_and_writes_parquet() {
+        let book = OrderBook::new("BTCUSDT");
+        book.update_level(Side::Buy, dec!(99.995), dec!(2)).unwrap();
+        book.update_level(Side::Buy, dec!(99.985), dec!(1)).unwrap();
+        book.update_level(Side::Buy, dec!(99.0), dec!(7)).unwrap();  // 100 bps out
+        book.update_level(Side::Sell, dec!(100.005), dec!(3)).unwrap();
+
+        let config = HeatmapConfig { interval_ms: 1000, band_bps: 5.0, bucket_bps: 1.0, samples_per_file: 2 };
+        let sample = HeatmapSample::capture(&book, 1_000, &config).unwrap();
+        assert_eq!(sample.offsets_bps, vec![-4.5, -3.5, -2.5, -1.5, -0.5, 0.5, 1.5, 2.5, 3.5, 4.5]);
+        assert_eq!(sample.bid_sizes[3..5], [1.0, 2.0]);
+        assert_eq!(sample.bid_sizes.iter().sum::<f64>(), 3.0);
+        assert_eq!(sample.ask_sizes[5], 3.0);
+
+        let dir = std::env::temp_dir().join(format!("heatmap_test_{}", std::process::id()));
+        let mut writer = HeatmapWriter::open(&dir, "btcusdt", &config).unwrap();
+        writer.append(sample.clone()).unwrap();
+        writer.append(HeatmapSample { timestamp_ms: 2_000, ..sample }).unwrap();
+
+        // Full after two samples: rotated and readable
+        let reader = SerializedFileReader::new(File::open(dir.join("heatmap_BTCUSDT_1000.parquet")).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 20);
+        writer.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
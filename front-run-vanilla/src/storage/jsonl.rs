@@ -0,0 +1,77 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Append-only JSON Lines writer
+///
+/// One record per line, buffered. Files are opened in append mode so a
+/// restarted process continues the same archive instead of truncating it.
+pub struct JsonlWriter {
+    writer: BufWriter<File>,
+    records_written: u64,
+}
+
+impl JsonlWriter {
+    /// Open (or create) a JSONL file for appending
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        Ok(Self {
+            writer: BufWriter::new(file),
+            records_written: 0,
+        })
+    }
+
+    /// Append a single record
+    pub fn append<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Flush buffered records to disk
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Number of records written since open
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_read_back() {
+        let path = std::env::temp_dir().join(format!("jsonl_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut writer = JsonlWriter::open(&path).unwrap();
+        writer.append(&serde_json::json!({"a": 1})).unwrap();
+        writer.append(&serde_json::json!({"a": 2})).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.records_written(), 2);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines, vec![r#"{"a":1}"#, r#"{"a":2}"#]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+use crate::data::OrderBook;
+use crate::storage::DetectorObservation;
+use crate::strategy::EvaluateBook;
+use anyhow::Result;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Detector name of the market samples recorded next to detector outputs
+pub const MARKET: &str = "market";
+
+/// Top of book at `at`, recorded on the same cadence detectors are
+/// evaluated so the labeling pass has a mid to measure returns from
+pub fn market_observation(book: &OrderBook, at: SystemTime) -> Option<DetectorObservation> {
+    book.evaluate(1, |view| {
+        let mid = view.mid()?.to_f64()?;
+        let mut observation = DetectorObservation::new(MARKET, at).with("mid", mid);
+        if let Some(spread_bps) = view.spread_bps().and_then(|spread| spread.to_f64()) {
+            observation = observation.with("spread_bps", spread_bps);
+        }
+        if let Some(quantity) = view.bids().first().and_then(|level| level.quantity.to_f64()) {
+            observation = observation.with("bid_qty", quantity);
+        }
+        if let Some(quantity) = view.asks().first().and_then(|level| level.quantity.to_f64()) {
+            observation = observation.with("ask_qty", quantity);
+        }
+        Some(observation)
+    })
+}
+
+/// One training row: every feature as of a market sample, and the mid's
+/// move over each horizon after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabeledSample {
+    pub timestamp_ms: u64,
+    pub mid: f64,
+    /// Latest value of every recorded output, keyed "detector.value"
+    pub features: BTreeMap<String, f64>,
+    /// Forward mid return in bps, keyed by horizon in ms
+    pub forward_returns_bps: BTreeMap<u64, f64>,
+}
+
+/// Read observations written by `ObservationRecorder` (unparsable lines skipped)
+pub fn load_observations<P: AsRef<Path>>(path: P) -> Result<Vec<DetectorObservation>> {
+    let file = std::fs::File::open(path)?;
+    let mut observations = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        if let Ok(observation) = serde_json::from_str::<DetectorObservation>(&line?) {
+            observations.push(observation);
+        }
+    }
+
+    Ok(observations)
+}
+
+/// Join captured outputs with forward returns at `horizons_ms`
+///
+/// Rows are emitted at market samples, carrying the latest value of each
+/// detector output at or before that time, so detectors evaluated on
+/// different clocks (book vs. trades) line up without lookahead. Each
+/// return runs to the first market sample at least the horizon later;
+/// rows too close to the end of the capture for the longest horizon, or
+/// before any detector output, are dropped.
+pub fn label_observations(observations: &[DetectorObservation], horizons_ms: &[u64]) -> Vec<LabeledSample> {
+    let mut sorted: Vec<&DetectorObservation> = observations.iter().collect();
+    // Detector outputs first on ties, so a sample sees its own evaluation
+    sorted.sort_by_key(|observation| (observation.timestamp_ms, observation.detector == MARKET));
+
+    let mids: Vec<(u64, f64)> = sorted.iter()
+        .filter(|observation| observation.detector == MARKET)
+        .filter_map(|observation| Some((observation.timestamp_ms, *observation.values.get("mid")?)))
+        .filter(|(_, mid)| *mid > 0.0)
+        .collect();
+
+    let mut features = BTreeMap::new();
+    let mut samples = Vec::new();
+    for observation in sorted {
+        for (name, value) in &observation.values {
+            features.insert(format!("{}.{}", observation.detector, name), *value);
+        }
+        if observation.detector != MARKET || features.keys().all(|key| key.starts_with("market.")) {
+            continue;
+        }
+        let Some(&mid) = observation.values.get("mid").filter(|mid| **mid > 0.0) else {
+            continue;
+        };
+
+        let forward_returns_bps: Option<BTreeMap<u64, f64>> = horizons_ms.iter()
+            .map(|&horizon| {
+                let target = observation.timestamp_ms + horizon;
+                let idx = mids.partition_point(|(time, _)| *time < target);
+                mids.get(idx).map(|(_, later)| (horizon, (later / mid - 1.0) * 10000.0))
+            })
+            .collect();
+
+        if let Some(forward_returns_bps) = forward_returns_bps {
+            samples.push(LabeledSample {
+                timestamp_ms: observation.timestamp_ms,
+                mid,
+                features: features.clone(),
+                forward_returns_bps,
+            });
+        }
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn at(ms: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_features_as_of_sample_and_forward_returns() {
+        let observations = vec![
+            DetectorObservation::new(MARKET, at(0)).with("mid", 100.0),
+            DetectorObservation::new("imbalance", at(1000)).with("z_score", 2.0),
+            DetectorObservation::new(MARKET, at(1000)).with("mid", 100.0),
+            DetectorObservation::new("flow", at(1500)).with("imbalance", 0.7),
+            DetectorObservation::new(MARKET, at(2000)).with("mid", 101.0),
+            DetectorObservation::new(MARKET, at(3500)).with("mid", 99.0),
+        ];
+
+        let samples = label_observations(&observations, &[1000, 2500]);
+
+        // t=0 has no detector output yet; t=2000 and later lack a 2.5s return
+        assert_eq!(samples.len(), 1);
+        let sample = &samples[0];
+        assert_eq!(sample.timestamp_ms, 1000);
+        assert_eq!(sample.features["imbalance.z_score"], 2.0);
+        assert!(!sample.features.contains_key("flow.imbalance"));
+        assert!((sample.forward_returns_bps[&1000] - 100.0).abs() < 1e-9);
+        assert!((sample.forward_returns_bps[&2500] + 100.0).abs() < 1e-9);
+
+        let samples = label_observations(&observations, &[1000]);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[1].features["flow.imbalance"], 0.7);
+    }
+}
@@ -0,0 +1,19 @@
+pub mod jsonl;
+pub mod observations;
+pub mod book_snapshot;
+pub mod webhook;
+pub mod labeling;
+pub mod experiment;
+pub mod heatmap;
+pub mod bus;
+
+pub use jsonl::JsonlWriter;
+pub use observations::{DetectorObservation, ObservationRecorder, OBSERVATION_CAPACITY};
+pub use book_snapshot::{
+    BookSnapshot, BookSnapshotWriter, BOOK_SNAPSHOT_VERSION, load_book_snapshots,
+};
+pub use labeling::{LabeledSample, MARKET, label_observations, load_observations, market_observation};
+pub use experiment::Experiment;
+pub use bus::{BusMessage, BusPublisher, FrameEncoding, FrameWriter, BUS_FRAME_VERSION};
+pub use heatmap::{HeatmapConfig, HeatmapSample, HeatmapWriter};
+pub use webhook::{WebhookConfig, WebhookEvent, WebhookMetrics, WebhookNotifier};
@@ -0,0 +1,162 @@
+use crate::storage::{Experiment, JsonlWriter};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tracing::{error, warn};
+
+/// Raw detector observation for offline threshold research
+///
+/// Recorded on EVERY evaluation (not only when a signal fires) so the full
+/// distribution of ratios/z-scores can be replayed against forward returns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorObservation {
+    /// Detector name, e.g. "imbalance", "flow"
+    pub detector: String,
+
+    /// Observation time in milliseconds since UNIX epoch
+    pub timestamp_ms: u64,
+
+    /// Named raw values (ratio, mean, stddev, z_score, ...)
+    pub values: BTreeMap<String, f64>,
+
+    /// Experiment the recording process ran under
+    #[serde(default, skip_serializing_if = "Experiment::is_empty")]
+    pub experiment: Experiment,
+}
+
+impl DetectorObservation {
+    /// Create an observation stamped at `timestamp`
+    pub fn new(detector: &str, timestamp: SystemTime) -> Self {
+        let timestamp_ms = timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        Self {
+            detector: detector.to_string(),
+            timestamp_ms,
+            values: BTreeMap::new(),
+            experiment: Experiment::default(),
+        }
+    }
+
+    /// Add a named value
+    pub fn with(mut self, name: &str, value: f64) -> Self {
+        self.values.insert(name.to_string(), value);
+        self
+    }
+}
+
+/// Observations queued for the file writer before new ones are dropped
+pub const OBSERVATION_CAPACITY: usize = 8192;
+
+/// Non-blocking observation sink for detectors
+///
+/// Detectors run on the hot path, so they only push into a bounded
+/// channel; a background task owns the file and does the actual IO. If
+/// the sink falls behind (slow disk) observations are dropped and
+/// counted rather than queued without limit.
+/// Cloning the recorder shares the same underlying stream.
+#[derive(Debug, Clone)]
+pub struct ObservationRecorder {
+    tx: mpsc::Sender<DetectorObservation>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl ObservationRecorder {
+    /// Create recorder with a caller-owned receiver holding up to
+    /// `capacity` observations (tests, custom sinks)
+    pub fn channel(capacity: usize) -> (Self, mpsc::Receiver<DetectorObservation>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        (Self { tx, dropped: Arc::new(AtomicU64::new(0)) }, rx)
+    }
+
+    /// Create recorder streaming to a JSONL file, stamping every
+    /// observation with `experiment` as it is written
+    ///
+    /// Must be called from within a tokio runtime (spawns the writer task).
+    pub fn to_file<P: AsRef<Path>>(path: P, experiment: Experiment) -> Result<Self> {
+        let mut writer = JsonlWriter::open(path)?;
+        let (recorder, mut rx) = Self::channel(OBSERVATION_CAPACITY);
+
+        tokio::spawn(async move {
+            while let Some(mut observation) = rx.recv().await {
+                observation.experiment = experiment.clone();
+                if let Err(e) = writer.append(&observation) {
+                    error!("Failed to write detector observation: {}", e);
+                    continue;
+                }
+
+                // Flush once the channel is drained to keep syscalls low
+                if rx.is_empty() {
+                    if let Err(e) = writer.flush() {
+                        warn!("Failed to flush observations: {}", e);
+                    }
+                }
+            }
+            let _ = writer.flush();
+        });
+
+        Ok(recorder)
+    }
+
+    /// Record an observation (never blocks; dropped and counted if the
+    /// sink is full, dropped silently if it is closed)
+    pub fn record(&self, observation: DetectorObservation) {
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(observation) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Observations dropped because the sink was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observation_serialization() {
+        let obs = DetectorObservation::new("imbalance", UNIX_EPOCH + std::time::Duration::from_millis(1500))
+            .with("ratio", 1.25)
+            .with("z_score", 3.1);
+
+        let json = serde_json::to_string(&obs).unwrap();
+        assert_eq!(
+            json,
+            r#"{"detector":"imbalance","timestamp_ms":1500,"values":{"ratio":1.25,"z_score":3.1}}"#
+        );
+    }
+
+    #[test]
+    fn test_channel_recorder() {
+        let (recorder, mut rx) = ObservationRecorder::channel(16);
+        recorder.record(DetectorObservation::new("flow", SystemTime::now()).with("imbalance", 0.7));
+
+        let obs = rx.try_recv().unwrap();
+        assert_eq!(obs.detector, "flow");
+        assert_eq!(obs.values["imbalance"], 0.7);
+    }
+
+    #[test]
+    fn test_full_recorder_drops_and_counts() {
+        let (recorder, mut rx) = ObservationRecorder::channel(2);
+        for _ in 0..5 {
+            recorder.record(DetectorObservation::new("imbalance", SystemTime::now()));
+        }
+
+        assert_eq!(recorder.dropped(), 3);
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+}
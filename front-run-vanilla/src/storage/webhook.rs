@@ -0,0 +1,218 @@
+use crate::data::Side;
+use crate::strategy::{ExitReason, JournalFill};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Outbound webhooks for trade events (`[webhooks]`)
+///
+/// Every event is POSTed as JSON to each URL. Disabled with no URLs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub urls: Vec<String>,
+    /// Event types to send ("fill", "position_opened", "position_closed",
+    /// "halt", "detector_disabled", "exit_failed"); empty sends all
+    pub events: Vec<String>,
+    /// Per-request timeout
+    pub timeout_ms: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            events: Vec::new(),
+            timeout_ms: 2000,
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.urls.is_empty()
+    }
+
+    /// Whether events of type `kind` should be sent
+    pub fn wants(&self, kind: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == kind)
+    }
+}
+
+/// Body of one webhook POST, tagged by `"event"`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// Any entry or exit fill, as written to the trade journal
+    Fill(JournalFill),
+    PositionOpened {
+        time_ms: u64,
+        account: String,
+        symbol: String,
+        side: Side,
+        entry_price: Decimal,
+        quantity: Decimal,
+    },
+    PositionClosed {
+        time_ms: u64,
+        account: String,
+        symbol: String,
+        /// Side of the position that was closed
+        side: Side,
+        entry_price: Decimal,
+        exit_price: Decimal,
+        quantity: Decimal,
+        realized_pnl: Decimal,
+        reason: ExitReason,
+    },
+    /// An account's risk manager stopped new entries
+    Halt {
+        time_ms: u64,
+        account: String,
+        reason: String,
+    },
+    /// A position passed an age threshold without progress toward its
+    /// take profit
+    PositionAging {
+        time_ms: u64,
+        account: String,
+        symbol: String,
+        side: Side,
+        age_ms: u64,
+        max_hold_time_ms: u64,
+        /// Net PnL as a fraction of the take profit
+        tp_progress: f64,
+    },
+    /// The detector policy switched a detector off
+    DetectorDisabled {
+        time_ms: u64,
+        detector: String,
+        reason: String,
+    },
+    /// An account's exit check failed; its positions may still be open
+    ExitFailed {
+        time_ms: u64,
+        account: String,
+        error: String,
+    },
+}
+
+impl WebhookEvent {
+    /// Value of the `"event"` tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Fill(_) => "fill",
+            Self::PositionOpened { .. } => "position_opened",
+            Self::PositionClosed { .. } => "position_closed",
+            Self::Halt { .. } => "halt",
+            Self::PositionAging { .. } => "position_aging",
+            Self::DetectorDisabled { .. } => "detector_disabled",
+            Self::ExitFailed { .. } => "exit_failed",
+        }
+    }
+}
+
+/// Counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct WebhookMetrics {
+    pub delivered: AtomicU64,
+    pub failed: AtomicU64,
+}
+
+/// Sends webhook events from a background task
+///
+/// `notify` only queues, so a slow or unreachable endpoint never delays an
+/// order. Deliveries are made one at a time in event order and are not
+/// retried; failures are logged and counted.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    config: Arc<WebhookConfig>,
+    tx: mpsc::UnboundedSender<WebhookEvent>,
+    metrics: Arc<WebhookMetrics>,
+}
+
+impl WebhookNotifier {
+    /// Start the delivery task (requires a Tokio runtime)
+    pub fn spawn(config: WebhookConfig) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<WebhookEvent>();
+        let metrics = Arc::new(WebhookMetrics::default());
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        let urls = config.urls.clone();
+        let task_metrics = Arc::clone(&metrics);
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                for url in &urls {
+                    let result = client.post(url).json(&event).send().await
+                        .and_then(|response| response.error_for_status());
+                    match result {
+                        Ok(_) => task_metrics.delivered.fetch_add(1, Ordering::Relaxed),
+                        Err(e) => {
+                            warn!("Webhook {} to {} failed: {}", event.kind(), url, e);
+                            task_metrics.failed.fetch_add(1, Ordering::Relaxed)
+                        }
+                    };
+                }
+            }
+        });
+
+        Self {
+            config: Arc::new(config),
+            tx,
+            metrics,
+        }
+    }
+
+    /// Shared handle to the delivery counters
+    pub fn metrics(&self) -> Arc<WebhookMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Queue `event` if its type is enabled
+    pub fn notify(&self, event: WebhookEvent) {
+        if self.config.wants(event.kind()) {
+            let _ = self.tx.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_payload_and_event_selection() {
+        let event = WebhookEvent::PositionClosed {
+            time_ms: 1_700_000_000_000,
+            account: "primary".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            entry_price: dec!(50000),
+            exit_price: dec!(50100),
+            quantity: dec!(0.01),
+            realized_pnl: dec!(1),
+            reason: ExitReason::TakeProfit,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "position_closed");
+        assert_eq!(json["reason"], "take_profit");
+        assert_eq!(json["exit_price"], "50100");
+
+        let config = WebhookConfig {
+            events: vec!["halt".to_string()],
+            ..WebhookConfig::default()
+        };
+        assert!(!config.is_enabled());
+        assert!(config.wants("halt"));
+        assert!(!config.wants(event.kind()));
+        assert!(WebhookConfig::default().wants(event.kind()));
+    }
+}
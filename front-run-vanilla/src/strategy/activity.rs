@@ -0,0 +1,241 @@
+use crate::risk::{RiskViolation, ViolationSeverity};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// Overtrading detection settings (`[strategy.activity]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActivityConfig {
+    /// Recent window the spike is measured over
+    pub window_secs: u64,
+    /// Longer history the recent window is compared against
+    pub baseline_secs: u64,
+    /// Recent trade rate at or above this multiple of the baseline is a spike
+    pub spike_ratio: f64,
+    /// Trades required in each window before comparing them
+    pub min_trades: usize,
+    /// Edge counts as deteriorating once the recent average is this far
+    /// below the baseline average
+    pub edge_drop_bps: f64,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 3600,
+            baseline_secs: 86400,
+            spike_ratio: 2.0,
+            min_trades: 5,
+            edge_drop_bps: 1.0,
+        }
+    }
+}
+
+/// Frequency and edge over one window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityWindow {
+    pub signals: usize,
+    pub trades: usize,
+    pub signals_per_hour: f64,
+    pub trades_per_hour: f64,
+    /// Mean net PnL per trade relative to entry notional (None = no trades)
+    pub avg_edge_bps: Option<f64>,
+}
+
+/// Recent activity next to the baseline it is judged against
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityStats {
+    pub recent: ActivityWindow,
+    /// History before the recent window, up to `baseline_secs`
+    pub baseline: ActivityWindow,
+    pub overtrading: bool,
+}
+
+/// Tracks signal and trade frequency and per-trade edge over rolling
+/// windows
+///
+/// Overtrading is a recent trade rate at least `spike_ratio` times the
+/// baseline rate while the recent average edge has fallen `edge_drop_bps`
+/// below the baseline's: more trades, each worth less. The baseline is
+/// the history before the recent window, so a spike does not dilute the
+/// rate it is compared with.
+pub struct ActivityMonitor {
+    config: ActivityConfig,
+    signals: VecDeque<SystemTime>,
+    /// Close time and edge in bps of each trade
+    trades: VecDeque<(SystemTime, f64)>,
+    first_seen: Option<SystemTime>,
+    overtrading: bool,
+}
+
+impl ActivityMonitor {
+    pub fn new(config: ActivityConfig) -> Self {
+        Self {
+            config,
+            signals: VecDeque::new(),
+            trades: VecDeque::new(),
+            first_seen: None,
+            overtrading: false,
+        }
+    }
+
+    /// Count a tradeable signal, traded or not
+    pub fn record_signal(&mut self, now: SystemTime) {
+        self.first_seen.get_or_insert(now);
+        self.signals.push_back(now);
+        self.prune(now);
+    }
+
+    /// Count a closed trade; edge is net PnL over entry notional
+    pub fn record_trade(&mut self, pnl: Decimal, notional: Decimal, now: SystemTime) {
+        self.first_seen.get_or_insert(now);
+        if notional > Decimal::ZERO {
+            if let Some(edge) = (pnl / notional).to_f64() {
+                self.trades.push_back((now, edge * 10000.0));
+            }
+        }
+        self.prune(now);
+    }
+
+    pub fn is_overtrading(&self) -> bool {
+        self.overtrading
+    }
+
+    /// Re-evaluate; a warning only when overtrading starts
+    ///
+    /// Stays quiet while the condition persists and re-arms once it clears.
+    pub fn check(&mut self, now: SystemTime) -> Option<RiskViolation> {
+        let stats = self.stats(now);
+        let started = stats.overtrading && !self.overtrading;
+        self.overtrading = stats.overtrading;
+
+        started.then(|| RiskViolation {
+            reason: format!(
+                "Overtrading: {:.1} trades/h vs {:.1} baseline, edge {:.2} bps vs {:.2} bps",
+                stats.recent.trades_per_hour,
+                stats.baseline.trades_per_hour,
+                stats.recent.avg_edge_bps.unwrap_or(0.0),
+                stats.baseline.avg_edge_bps.unwrap_or(0.0),
+            ),
+            severity: ViolationSeverity::Warning,
+        })
+    }
+
+    pub fn stats(&self, now: SystemTime) -> ActivityStats {
+        let window = Duration::from_secs(self.config.window_secs);
+        let recent_start = now.checked_sub(window).unwrap_or(now);
+
+        // The baseline covers what we have seen of the longer history
+        let history_start = now
+            .checked_sub(Duration::from_secs(self.config.baseline_secs))
+            .unwrap_or(now)
+            .max(self.first_seen.unwrap_or(now));
+
+        let recent = self.window(recent_start, now);
+        let baseline = self.window(history_start, recent_start);
+
+        let overtrading = recent.trades >= self.config.min_trades
+            && baseline.trades >= self.config.min_trades
+            && recent.trades_per_hour >= self.config.spike_ratio * baseline.trades_per_hour
+            && match (recent.avg_edge_bps, baseline.avg_edge_bps) {
+                (Some(recent), Some(baseline)) => recent < baseline - self.config.edge_drop_bps,
+                _ => false,
+            };
+
+        ActivityStats { recent, baseline, overtrading }
+    }
+
+    /// Activity in `[from, to)`
+    fn window(&self, from: SystemTime, to: SystemTime) -> ActivityWindow {
+        let hours = to.duration_since(from).map(|d| d.as_secs_f64() / 3600.0).unwrap_or(0.0);
+        let rate = |count: usize| if hours > 0.0 { count as f64 / hours } else { 0.0 };
+
+        let signals = self.signals.iter().filter(|t| **t >= from && **t < to).count();
+        let edges: Vec<f64> = self.trades.iter()
+            .filter(|(t, _)| *t >= from && *t < to)
+            .map(|(_, edge)| *edge)
+            .collect();
+
+        ActivityWindow {
+            signals,
+            trades: edges.len(),
+            signals_per_hour: rate(signals),
+            trades_per_hour: rate(edges.len()),
+            avg_edge_bps: (!edges.is_empty()).then(|| edges.iter().sum::<f64>() / edges.len() as f64),
+        }
+    }
+
+    fn prune(&mut self, now: SystemTime) {
+        let Some(cutoff) = now.checked_sub(Duration::from_secs(self.config.baseline_secs)) else {
+            return;
+        };
+        while self.signals.front().is_some_and(|t| *t < cutoff) {
+            self.signals.pop_front();
+        }
+        while self.trades.front().is_some_and(|(t, _)| *t < cutoff) {
+            self.trades.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_spike_with_falling_edge_warns_once() {
+        let mut monitor = ActivityMonitor::new(ActivityConfig {
+            window_secs: 3600,
+            baseline_secs: 5 * 3600,
+            spike_ratio: 2.0,
+            min_trades: 5,
+            edge_drop_bps: 1.0,
+        });
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // Four hours at 5 trades/h earning 3 bps each
+        for i in 0..20 {
+            monitor.record_trade(dec!(0.3), dec!(1000), at(i * 720));
+        }
+        // Then 20 trades in the next hour earning 0.5 bps each
+        for i in 0..20 {
+            let now = at(4 * 3600 + i * 180);
+            monitor.record_signal(now);
+            monitor.record_trade(dec!(0.05), dec!(1000), now);
+        }
+
+        let now = at(5 * 3600 - 1);
+        let stats = monitor.stats(now);
+        assert_eq!(stats.recent.trades, 20);
+        assert_eq!(stats.baseline.trades, 20);
+        assert!(stats.overtrading);
+
+        let warning = monitor.check(now).expect("overtrading warning");
+        assert_eq!(warning.severity, ViolationSeverity::Warning);
+        assert!(monitor.check(now).is_none());
+    }
+
+    #[test]
+    fn test_busy_but_profitable_is_not_overtrading() {
+        let mut monitor = ActivityMonitor::new(ActivityConfig {
+            baseline_secs: 5 * 3600,
+            ..ActivityConfig::default()
+        });
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        for i in 0..20 {
+            monitor.record_trade(dec!(0.3), dec!(1000), at(i * 720));
+        }
+        for i in 0..20 {
+            monitor.record_trade(dec!(0.3), dec!(1000), at(4 * 3600 + i * 180));
+        }
+
+        assert!(monitor.check(at(5 * 3600 - 1)).is_none());
+    }
+}
@@ -0,0 +1,188 @@
+use crate::data::Side;
+use crate::risk::Position;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+/// Warnings for positions going nowhere before the max hold timeout
+/// (`[strategy.age_warnings]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgeWarningConfig {
+    /// Fractions of `max_hold_time_ms` at which to check (empty = off)
+    pub fractions: Vec<f64>,
+    /// A position counts as stalled below this fraction of the way to its
+    /// take profit, net of fees
+    pub min_tp_progress: f64,
+}
+
+impl Default for AgeWarningConfig {
+    fn default() -> Self {
+        Self {
+            fractions: vec![0.5, 0.8],
+            min_tp_progress: 0.25,
+        }
+    }
+}
+
+/// A stalled position past one of the age thresholds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgeWarning {
+    pub symbol: String,
+    pub side: Side,
+    pub age_ms: u64,
+    /// Threshold crossed, as a fraction of `max_hold_time_ms`
+    pub fraction: f64,
+    /// Net PnL as a fraction of the take profit (negative = losing)
+    pub tp_progress: f64,
+}
+
+/// Warning counters exposed with the trading stats
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgeWarningStats {
+    /// Warnings raised per threshold, keyed e.g. "50%"
+    pub warnings: BTreeMap<String, u64>,
+    /// Open positions that have been warned about and not yet closed
+    pub stalled_positions: usize,
+}
+
+/// Age threshold state of one open position
+#[derive(Debug, Clone)]
+struct PositionAge {
+    entry_time: SystemTime,
+    /// Thresholds already crossed
+    crossed: usize,
+    warned: bool,
+}
+
+/// Flags "dead" positions at fractions of the max hold time
+///
+/// Each threshold is checked once, as the position crosses it; a position
+/// that is at least `min_tp_progress` of the way to its take profit then
+/// is left alone. Positions are keyed by symbol and entry time, so a new
+/// position on the same symbol starts over.
+#[derive(Debug, Clone)]
+pub struct AgeMonitor {
+    config: AgeWarningConfig,
+    max_hold_time_ms: u64,
+    positions: BTreeMap<String, PositionAge>,
+    counts: Vec<u64>,
+}
+
+impl AgeMonitor {
+    pub fn new(mut config: AgeWarningConfig, max_hold_time_ms: u64) -> Self {
+        config.fractions.retain(|fraction| *fraction > 0.0 && *fraction < 1.0);
+        config.fractions.sort_by(f64::total_cmp);
+        let counts = vec![0; config.fractions.len()];
+        Self {
+            config,
+            max_hold_time_ms,
+            positions: BTreeMap::new(),
+            counts,
+        }
+    }
+
+    /// Warning for `position` if it just crossed a threshold while stalled
+    pub fn check(
+        &mut self,
+        position: &Position,
+        take_profit_bps: Decimal,
+        current_price: Decimal,
+        now: SystemTime,
+    ) -> Option<AgeWarning> {
+        if self.config.fractions.is_empty() || self.max_hold_time_ms == 0 {
+            return None;
+        }
+
+        let age_ms = position.age_at(now).as_millis() as u64;
+        let elapsed = age_ms as f64 / self.max_hold_time_ms as f64;
+        let due = self.config.fractions.iter().filter(|fraction| **fraction <= elapsed).count();
+
+        let state = self.positions.entry(position.symbol.clone()).or_insert(PositionAge {
+            entry_time: position.entry_time,
+            crossed: 0,
+            warned: false,
+        });
+        if state.entry_time != position.entry_time {
+            *state = PositionAge { entry_time: position.entry_time, crossed: 0, warned: false };
+        }
+        if due <= state.crossed {
+            return None;
+        }
+        state.crossed = due;
+
+        // Net PnL in bps of entry notional against the target
+        let pnl_bps = position.unrealized_pnl_percent(current_price) * Decimal::from(100);
+        let tp_progress = if take_profit_bps.is_zero() {
+            0.0
+        } else {
+            (pnl_bps / take_profit_bps).to_f64().unwrap_or(0.0)
+        };
+        if tp_progress >= self.config.min_tp_progress {
+            return None;
+        }
+
+        state.warned = true;
+        self.counts[due - 1] += 1;
+        Some(AgeWarning {
+            symbol: position.symbol.clone(),
+            side: position.side,
+            age_ms,
+            fraction: self.config.fractions[due - 1],
+            tp_progress,
+        })
+    }
+
+    /// Forget a closed position
+    pub fn close(&mut self, symbol: &str) {
+        self.positions.remove(symbol);
+    }
+
+    pub fn stats(&self) -> AgeWarningStats {
+        AgeWarningStats {
+            warnings: self.config.fractions.iter()
+                .zip(&self.counts)
+                .map(|(fraction, count)| (format!("{:.0}%", fraction * 100.0), *count))
+                .collect(),
+            stalled_positions: self.positions.values().filter(|state| state.warned).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+
+    #[test]
+    fn test_warns_once_per_threshold_while_stalled() {
+        let mut monitor = AgeMonitor::new(AgeWarningConfig::default(), 10_000);
+        let entry = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let position = Position::new("BTCUSDT".into(), Side::Buy, dec!(100), dec!(1), Decimal::ZERO)
+            .with_entry_time(entry);
+        let at = |ms| entry + Duration::from_millis(ms);
+
+        assert_eq!(monitor.check(&position, dec!(10), dec!(100), at(4_000)), None);
+
+        // 2 bps of a 10 bps target at half the hold time
+        let warning = monitor.check(&position, dec!(10), dec!(100.02), at(5_000)).unwrap();
+        assert_eq!(warning.fraction, 0.5);
+        assert!((warning.tp_progress - 0.2).abs() < 1e-9);
+        assert_eq!(monitor.check(&position, dec!(10), dec!(100.02), at(6_000)), None);
+
+        // Halfway to target by 80%: not stalled
+        assert_eq!(monitor.check(&position, dec!(10), dec!(100.05), at(8_000)), None);
+
+        let stats = monitor.stats();
+        assert_eq!(stats.warnings["50%"], 1);
+        assert_eq!(stats.warnings["80%"], 0);
+        assert_eq!(stats.stalled_positions, 1);
+
+        monitor.close("BTCUSDT");
+        assert_eq!(monitor.stats().stalled_positions, 0);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::data::{OrderBook, Side, Trade};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::SystemTime;
+
+/// Which strategy a config runs (`[strategy] kind`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    /// Imbalance/flow taker entries with TP/SL exits (the engine's own logic)
+    #[default]
+    FrontRun,
+    /// Two-sided quotes inside the spread while imbalance is neutral
+    /// (`[strategy.spread_capture]`)
+    SpreadCapture,
+    /// Fades extreme imbalances once they start reverting
+    /// (`[strategy.mean_reversion]`)
+    MeanReversion,
+}
+
+impl StrategyKind {
+    /// Whether the live and paper traders can run it; the other kinds are
+    /// replayed by the backtester only
+    pub fn runs_live(self) -> bool {
+        matches!(self, StrategyKind::FrontRun)
+    }
+}
+
+/// Order intent returned by a `Strategy`
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrategyAction {
+    /// Rest a post-only quote on `side`, replacing any quote already there
+    Quote { side: Side, price: Decimal, quantity: Decimal },
+    /// Pull the quote on `side`
+    CancelQuote(Side),
+    /// Take `quantity` at the touch
+    Market { side: Side, quantity: Decimal },
+}
+
+/// One of the strategy's orders filled
+#[derive(Debug, Clone)]
+pub struct StrategyFill {
+    pub side: Side,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    /// Resting quote (true) or market order
+    pub maker: bool,
+    pub time: SystemTime,
+}
+
+/// Built-in strategy driven by book and trade events
+///
+/// The front-run logic lives in `ExecutionEngine`/`BacktestEngine`; other
+/// built-in strategies implement this and are replayed by
+/// `backtest::StrategyRunner`, which owns the book, fills the returned
+/// actions and reports fills back. They are backtest only: the backtester
+/// picks one from `[strategy] kind` in its `--config`, and the live and
+/// paper traders refuse to start on them.
+pub trait Strategy: Send {
+    fn name(&self) -> &str;
+
+    /// Called after every book update with the updated book
+    fn on_book(&mut self, book: &OrderBook, now: SystemTime) -> Vec<StrategyAction>;
+
+    /// Called for every public trade
+    fn on_trade(&mut self, _trade: &Trade) -> Vec<StrategyAction> {
+        Vec::new()
+    }
+
+    /// Called when one of the strategy's orders fills; a filled quote is
+    /// no longer resting
+    fn on_fill(&mut self, _fill: &StrategyFill) {}
+}
@@ -0,0 +1,213 @@
+use crate::strategy::{CompositeSignal, RejectionReason};
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, Instant};
+
+/// Entry evaluation slow-down under latency (`[strategy.cadence]`)
+///
+/// Two lag measures are watched: feed latency (exchange event time to
+/// local receipt) and backlog (local receipt to processing, i.e. how far
+/// behind the event queue the loop is). Either crossing a level's limit
+/// puts the policy in that level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CadenceConfig {
+    pub enabled: bool,
+    /// Feed latency that degrades / severely degrades the cadence
+    pub degraded_latency_ms: u64,
+    pub severe_latency_ms: u64,
+    /// Processing backlog that degrades / severely degrades the cadence
+    pub degraded_backlog_ms: u64,
+    pub severe_backlog_ms: u64,
+    /// Entry evaluations skipped: only one in this many runs
+    pub degraded_interval_multiplier: u32,
+    pub severe_interval_multiplier: u32,
+    /// Primary threshold scale for entries
+    pub degraded_threshold_multiplier: f64,
+    pub severe_threshold_multiplier: f64,
+    /// Lag must stay below a level this long before stepping back down
+    pub recovery_ms: u64,
+}
+
+impl Default for CadenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            degraded_latency_ms: 200,
+            severe_latency_ms: 500,
+            degraded_backlog_ms: 50,
+            severe_backlog_ms: 250,
+            degraded_interval_multiplier: 2,
+            severe_interval_multiplier: 5,
+            degraded_threshold_multiplier: 1.25,
+            severe_threshold_multiplier: 1.5,
+            recovery_ms: 5000,
+        }
+    }
+}
+
+/// How stale the data being traded on is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CadenceLevel {
+    Normal,
+    Degraded,
+    Severe,
+}
+
+/// Adapts how often entries are evaluated, and how strong they must be,
+/// to measured lag
+///
+/// Steps up a level as soon as a lag limit is crossed; steps down only
+/// after `recovery_ms` below the current level's limits, so a flapping
+/// feed does not flip the cadence on every update. Exits are unaffected:
+/// stale data is a reason to be choosier about entering, never about
+/// leaving.
+pub struct CadencePolicy {
+    config: CadenceConfig,
+    level: CadenceLevel,
+    /// Since when the measured lag has been below the current level
+    below_since: Option<Instant>,
+    /// Evaluation opportunities since the last evaluation that ran
+    skipped: u32,
+}
+
+impl CadencePolicy {
+    pub fn new(config: CadenceConfig) -> Self {
+        Self {
+            config,
+            level: CadenceLevel::Normal,
+            below_since: None,
+            skipped: 0,
+        }
+    }
+
+    pub fn level(&self) -> CadenceLevel {
+        self.level
+    }
+
+    /// Level for one lag measurement, before hysteresis
+    fn measured_level(&self, feed_latency_ms: u64, backlog: Duration) -> CadenceLevel {
+        let backlog_ms = backlog.as_millis() as u64;
+        let crosses = |limit: u64, value: u64| limit > 0 && value >= limit;
+        if crosses(self.config.severe_latency_ms, feed_latency_ms)
+            || crosses(self.config.severe_backlog_ms, backlog_ms)
+        {
+            CadenceLevel::Severe
+        } else if crosses(self.config.degraded_latency_ms, feed_latency_ms)
+            || crosses(self.config.degraded_backlog_ms, backlog_ms)
+        {
+            CadenceLevel::Degraded
+        } else {
+            CadenceLevel::Normal
+        }
+    }
+
+    /// Feed one lag measurement; returns the new level if it changed
+    pub fn observe(&mut self, feed_latency_ms: u64, backlog: Duration, now: Instant) -> Option<CadenceLevel> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let measured = self.measured_level(feed_latency_ms, backlog);
+        if measured >= self.level {
+            self.below_since = None;
+            if measured == self.level {
+                return None;
+            }
+            self.level = measured;
+            return Some(measured);
+        }
+
+        let since = *self.below_since.get_or_insert(now);
+        if now.duration_since(since) < Duration::from_millis(self.config.recovery_ms) {
+            return None;
+        }
+        self.below_since = None;
+        self.level = measured;
+        Some(measured)
+    }
+
+    fn interval_multiplier(&self) -> u32 {
+        match self.level {
+            CadenceLevel::Normal => 1,
+            CadenceLevel::Degraded => self.config.degraded_interval_multiplier.max(1),
+            CadenceLevel::Severe => self.config.severe_interval_multiplier.max(1),
+        }
+    }
+
+    pub fn threshold_multiplier(&self) -> f64 {
+        match self.level {
+            CadenceLevel::Normal => 1.0,
+            CadenceLevel::Degraded => self.config.degraded_threshold_multiplier,
+            CadenceLevel::Severe => self.config.severe_threshold_multiplier,
+        }
+    }
+
+    /// Whether this evaluation opportunity should run entry evaluation
+    pub fn should_evaluate(&mut self) -> bool {
+        self.skipped += 1;
+        if self.skipped < self.interval_multiplier() {
+            return false;
+        }
+        self.skipped = 0;
+        true
+    }
+
+    /// Refuse `composite` unless its primary clears the raised threshold
+    pub fn check(&self, mut composite: CompositeSignal, primary_threshold: f64) -> Result<CompositeSignal, RejectionReason> {
+        if self.level == CadenceLevel::Normal {
+            return Ok(composite);
+        }
+        let raised = primary_threshold * self.threshold_multiplier();
+        let strength = composite.primary.abs_strength();
+        if strength < raised {
+            return Err(RejectionReason::PrimaryTooWeak);
+        }
+        composite.explanation.check(
+            "cadence",
+            true,
+            format!("{:?} lag: primary {:.2} >= raised threshold {:.2}", self.level, strength, raised),
+        );
+        Ok(composite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escalates_at_once_and_recovers_after_hold() {
+        let mut policy = CadencePolicy::new(CadenceConfig { enabled: true, ..CadenceConfig::default() });
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let quiet = Duration::from_millis(5);
+
+        assert_eq!(policy.observe(20, quiet, at(0)), None);
+        assert!((0..3).all(|_| policy.should_evaluate()));
+
+        // Backlog alone degrades; latency alone goes straight to severe
+        assert_eq!(policy.observe(20, Duration::from_millis(80), at(100)), Some(CadenceLevel::Degraded));
+        assert_eq!(policy.observe(600, quiet, at(200)), Some(CadenceLevel::Severe));
+        let evaluated: Vec<bool> = (0..10).map(|_| policy.should_evaluate()).collect();
+        assert_eq!(evaluated.iter().filter(|run| **run).count(), 2);
+        assert_eq!(policy.threshold_multiplier(), 1.5);
+
+        // Back to normal only after 5s of quiet, straight from severe
+        assert_eq!(policy.observe(20, quiet, at(300)), None);
+        assert_eq!(policy.observe(20, quiet, at(5000)), None);
+        assert_eq!(policy.observe(20, quiet, at(5300)), Some(CadenceLevel::Normal));
+
+        // A spike during recovery restarts the hold
+        policy.observe(300, quiet, at(6000));
+        policy.observe(20, quiet, at(7000));
+        policy.observe(300, quiet, at(8000));
+        assert_eq!(policy.observe(20, quiet, at(12500)), None);
+        assert_eq!(policy.level(), CadenceLevel::Degraded);
+
+        // Disabled: never leaves normal
+        let mut disabled = CadencePolicy::new(CadenceConfig::default());
+        assert_eq!(disabled.observe(10_000, Duration::from_secs(1), at(0)), None);
+        assert!(disabled.should_evaluate());
+    }
+}
@@ -0,0 +1,355 @@
+use crate::data::Side;
+use crate::strategy::{CompositeSignal, SignalAggregator};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+use tracing::info;
+
+/// Online threshold calibration settings (`[strategy.calibration]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalibrationConfig {
+    pub enabled: bool,
+    /// Resolved signals in the rolling precision window
+    pub window: usize,
+    /// Resolved signals required before any adjustment
+    pub min_samples: usize,
+    /// Resolved signals between adjustments
+    pub adjust_every: usize,
+    /// Precision the thresholds are steered towards
+    pub target_precision: f64,
+    /// No adjustment while precision is within this distance of target
+    pub tolerance: f64,
+    /// Largest change to either threshold per adjustment
+    pub max_step: f64,
+    /// A signal counts as correct once mid moves this far its way
+    pub min_move_bps: f64,
+    pub primary_min: f64,
+    pub primary_max: f64,
+    pub confirming_min: f64,
+    pub confirming_max: f64,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 50,
+            min_samples: 20,
+            adjust_every: 10,
+            target_precision: 0.55,
+            tolerance: 0.05,
+            max_step: 0.1,
+            min_move_bps: 1.0,
+            primary_min: 2.5,
+            primary_max: 4.5,
+            confirming_min: 1.0,
+            confirming_max: 2.5,
+        }
+    }
+}
+
+/// Periodic walk-forward re-optimization of the thresholds over recent
+/// recordings, live only (`[strategy.recalibration]`)
+///
+/// Unlike `CalibrationConfig`, which nudges thresholds from live signal
+/// precision, this re-runs the backtest over the last `lookback_hours`
+/// for a grid of thresholds around the current ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecalibrationConfig {
+    pub enabled: bool,
+    /// Apply proposals to the running detectors; otherwise only log them
+    pub apply: bool,
+    pub interval_secs: u64,
+    pub lookback_hours: u64,
+    /// Recordings in the backtester's JSONL format (empty = storage.data_dir)
+    pub data_dir: String,
+    /// Grid spacing of candidate thresholds
+    pub grid_step: f64,
+    /// Largest change to either threshold per proposal
+    pub max_step: f64,
+    /// Backtest trades the best candidate needs in the window
+    pub min_trades: usize,
+    /// Net PnL (USD) the best candidate must add over the current thresholds
+    pub min_improvement_usd: f64,
+    pub primary_min: f64,
+    pub primary_max: f64,
+    pub confirming_min: f64,
+    pub confirming_max: f64,
+}
+
+impl Default for RecalibrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            apply: false,
+            interval_secs: 3600,
+            lookback_hours: 6,
+            data_dir: String::new(),
+            grid_step: 0.25,
+            max_step: 0.5,
+            min_trades: 10,
+            min_improvement_usd: 0.0,
+            primary_min: 2.5,
+            primary_max: 4.5,
+            confirming_min: 1.0,
+            confirming_max: 2.5,
+        }
+    }
+}
+
+impl RecalibrationConfig {
+    /// (primary, confirming) pairs within `max_step` of `current` and the
+    /// bounds, on a `grid_step` grid centred on `current` (included)
+    pub fn candidates(&self, current: (f64, f64)) -> Vec<(f64, f64)> {
+        let axis = |current: f64, min: f64, max: f64| {
+            let steps = if self.grid_step > 0.0 { (self.max_step / self.grid_step).floor() as i64 } else { 0 };
+            let mut values: Vec<f64> = (-steps..=steps)
+                .map(|i| current + i as f64 * self.grid_step)
+                .filter(|value| *value >= min && *value <= max)
+                .collect();
+            if !values.contains(&current) {
+                values.push(current);
+            }
+            values
+        };
+        let primaries = axis(current.0, self.primary_min, self.primary_max);
+        let confirmings = axis(current.1, self.confirming_min, self.confirming_max);
+
+        primaries.iter()
+            .flat_map(|primary| confirmings.iter().map(move |confirming| (*primary, *confirming)))
+            .collect()
+    }
+}
+
+/// One logged threshold change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdAdjustment {
+    pub time: SystemTime,
+    /// Rolling precision that triggered the change
+    pub precision: f64,
+    pub samples: usize,
+    pub primary_from: f64,
+    pub primary_to: f64,
+    pub confirming_from: f64,
+    pub confirming_to: f64,
+}
+
+/// Tradeable signal waiting for its outcome
+struct PendingSignal {
+    direction: Side,
+    mid: Decimal,
+    expires: SystemTime,
+}
+
+/// Adjusts `SignalAggregator` thresholds from the rolling precision of
+/// recent signals
+///
+/// A signal is correct if mid moves `min_move_bps` in its direction within
+/// the hold window, whether or not it was traded. Precision below target
+/// raises both thresholds (fewer, better signals); precision above target
+/// lowers them. Each change is at most `max_step` and stays inside the
+/// configured bounds.
+pub struct ThresholdCalibrator {
+    config: CalibrationConfig,
+    hold: Duration,
+    pending: VecDeque<PendingSignal>,
+    outcomes: VecDeque<bool>,
+    since_adjustment: usize,
+    adjustments: Vec<ThresholdAdjustment>,
+}
+
+impl ThresholdCalibrator {
+    pub fn new(config: CalibrationConfig, hold_time_ms: u64) -> Self {
+        Self {
+            outcomes: VecDeque::with_capacity(config.window),
+            config,
+            hold: Duration::from_millis(hold_time_ms),
+            pending: VecDeque::new(),
+            since_adjustment: 0,
+            adjustments: Vec::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Track a tradeable signal from the current mid
+    pub fn record_signal(&mut self, signal: &CompositeSignal, mid: Decimal, now: SystemTime) {
+        if !self.config.enabled {
+            return;
+        }
+        self.pending.push_back(PendingSignal {
+            direction: signal.direction,
+            mid,
+            expires: now + self.hold,
+        });
+    }
+
+    /// Resolve pending signals against the current mid
+    pub fn record_mid(&mut self, mid: Decimal, now: SystemTime) {
+        let min_move = Decimal::from_f64_retain(self.config.min_move_bps / 10000.0)
+            .unwrap_or(Decimal::ZERO);
+
+        let mut resolved = Vec::new();
+        self.pending.retain(|signal| {
+            if signal.mid.is_zero() {
+                return false;
+            }
+            let moved = match signal.direction {
+                Side::Buy => (mid - signal.mid) / signal.mid,
+                Side::Sell => (signal.mid - mid) / signal.mid,
+            };
+            if moved >= min_move {
+                resolved.push(true);
+                false
+            } else if now >= signal.expires {
+                resolved.push(false);
+                false
+            } else {
+                true
+            }
+        });
+
+        for outcome in resolved {
+            self.outcomes.push_back(outcome);
+            if self.outcomes.len() > self.config.window {
+                self.outcomes.pop_front();
+            }
+            self.since_adjustment += 1;
+        }
+    }
+
+    /// Rolling share of resolved signals that were correct
+    pub fn precision(&self) -> Option<f64> {
+        if self.outcomes.is_empty() {
+            return None;
+        }
+        let hits = self.outcomes.iter().filter(|hit| **hit).count();
+        Some(hits as f64 / self.outcomes.len() as f64)
+    }
+
+    /// Move the aggregator's thresholds if precision is off target
+    ///
+    /// Returns the change when one was made; it is also logged and kept
+    /// in `adjustments()`.
+    pub fn adjust(&mut self, aggregator: &mut SignalAggregator, now: SystemTime) -> Option<ThresholdAdjustment> {
+        if !self.config.enabled
+            || self.outcomes.len() < self.config.min_samples
+            || self.since_adjustment < self.config.adjust_every
+        {
+            return None;
+        }
+        self.since_adjustment = 0;
+
+        let precision = self.precision()?;
+        let error = precision - self.config.target_precision;
+        if error.abs() <= self.config.tolerance {
+            return None;
+        }
+
+        // Too many misses: be pickier. Comfortably precise: take more signals.
+        let step = if error < 0.0 { self.config.max_step } else { -self.config.max_step };
+        let primary_from = aggregator.primary_threshold();
+        let confirming_from = aggregator.confirming_threshold();
+        let primary_to = (primary_from + step).clamp(self.config.primary_min, self.config.primary_max);
+        let confirming_to = (confirming_from + step).clamp(self.config.confirming_min, self.config.confirming_max);
+
+        if primary_to == primary_from && confirming_to == confirming_from {
+            return None;
+        }
+        aggregator.set_thresholds(primary_to, confirming_to);
+
+        let adjustment = ThresholdAdjustment {
+            time: now,
+            precision,
+            samples: self.outcomes.len(),
+            primary_from,
+            primary_to,
+            confirming_from,
+            confirming_to,
+        };
+        info!(
+            "🎚 Thresholds adjusted: precision {:.2} over {} signals | primary {:.2} -> {:.2} | confirming {:.2} -> {:.2}",
+            precision, adjustment.samples, primary_from, primary_to, confirming_from, confirming_to
+        );
+        self.adjustments.push(adjustment.clone());
+        Some(adjustment)
+    }
+
+    pub fn adjustments(&self) -> &[ThresholdAdjustment] {
+        &self.adjustments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Signal;
+
+    fn composite(direction: Side) -> CompositeSignal {
+        let primary = Signal {
+            strength: 3.5,
+            direction,
+            confidence: 0.8,
+            timestamp: SystemTime::UNIX_EPOCH,
+            components: vec![],
+        };
+        CompositeSignal {
+            primary,
+            confirming: vec![],
+            overall_strength: 3.5,
+            direction,
+            confidence: 0.8,
+            timestamp: SystemTime::UNIX_EPOCH,
+            explanation: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_poor_precision_raises_thresholds_within_bounds() {
+        let config = CalibrationConfig {
+            enabled: true,
+            min_samples: 4,
+            adjust_every: 4,
+            primary_max: 3.15,
+            confirming_max: 1.7,
+            ..CalibrationConfig::default()
+        };
+        let mut calibrator = ThresholdCalibrator::new(config, 1000);
+        let mut aggregator = SignalAggregator::new(3.0, 1.5, 2);
+        let start = SystemTime::UNIX_EPOCH;
+
+        // Every buy signal is followed by a falling mid until it expires
+        for round in 0..3u64 {
+            let t = start + Duration::from_secs(round * 10);
+            for _ in 0..4 {
+                calibrator.record_signal(&composite(Side::Buy), Decimal::from(100), t);
+            }
+            calibrator.record_mid(Decimal::from(99), t + Duration::from_millis(1000));
+            calibrator.adjust(&mut aggregator, t);
+        }
+
+        assert_eq!(calibrator.precision(), Some(0.0));
+        assert_eq!(calibrator.adjustments().len(), 2);
+        assert!((aggregator.primary_threshold() - 3.15).abs() < 1e-9);
+        assert!((aggregator.confirming_threshold() - 1.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_favorable_move_resolves_as_hit() {
+        let config = CalibrationConfig { enabled: true, ..CalibrationConfig::default() };
+        let mut calibrator = ThresholdCalibrator::new(config, 1000);
+        let start = SystemTime::UNIX_EPOCH;
+
+        calibrator.record_signal(&composite(Side::Sell), Decimal::from(10000), start);
+        calibrator.record_mid(Decimal::from(10000), start + Duration::from_millis(100));
+        assert_eq!(calibrator.precision(), None);
+
+        calibrator.record_mid(Decimal::from(9998), start + Duration::from_millis(200));
+        assert_eq!(calibrator.precision(), Some(1.0));
+    }
+}
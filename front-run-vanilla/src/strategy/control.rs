@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Detector whose signals can be switched off at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detector {
+    Imbalance,
+    Basis,
+    Liquidation,
+}
+
+impl Detector {
+    pub const ALL: [Detector; 3] = [Detector::Imbalance, Detector::Basis, Detector::Liquidation];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Detector::Imbalance => "imbalance",
+            Detector::Basis => "basis",
+            Detector::Liquidation => "liquidation",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|detector| detector.name() == name)
+    }
+}
+
+/// Runtime on/off switches for the strategy and its detectors
+///
+/// Switching off only stops new entries: market data, detector state and
+/// exit management keep running so open positions are still closed and
+/// re-enabling needs no warm-up. Cloning shares the switches.
+#[derive(Debug, Clone)]
+pub struct StrategyControl {
+    enabled: Arc<AtomicBool>,
+    imbalance: Arc<AtomicBool>,
+    basis: Arc<AtomicBool>,
+    liquidation: Arc<AtomicBool>,
+}
+
+impl StrategyControl {
+    /// Starts with the strategy set to `enabled` and every detector on
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+            imbalance: Arc::new(AtomicBool::new(true)),
+            basis: Arc::new(AtomicBool::new(true)),
+            liquidation: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// New entries allowed
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Detector's signals are passed to the aggregator
+    pub fn detector_enabled(&self, detector: Detector) -> bool {
+        self.switch(detector).load(Ordering::Relaxed)
+    }
+
+    pub fn set_detector(&self, detector: Detector, enabled: bool) {
+        self.switch(detector).store(enabled, Ordering::Relaxed);
+    }
+
+    fn switch(&self, detector: Detector) -> &AtomicBool {
+        match detector {
+            Detector::Imbalance => &self.imbalance,
+            Detector::Basis => &self.basis,
+            Detector::Liquidation => &self.liquidation,
+        }
+    }
+
+    /// e.g. "strategy=on imbalance=on basis=off liquidation=on"
+    pub fn status(&self) -> String {
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        let mut status = format!("strategy={}", on_off(self.is_enabled()));
+        for detector in Detector::ALL {
+            status.push_str(&format!(" {}={}", detector.name(), on_off(self.detector_enabled(detector))));
+        }
+        status
+    }
+
+    /// Apply one control command and return the resulting status
+    ///
+    /// Commands: `status`, `strategy on|off`, `<detector> on|off`.
+    pub fn apply_command(&self, command: &str) -> Result<String, String> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let on = |word: &str| match word {
+            "on" | "enable" => Ok(true),
+            "off" | "disable" => Ok(false),
+            other => Err(format!("expected on/off, got '{}'", other)),
+        };
+
+        match words.as_slice() {
+            ["status"] => {}
+            ["strategy", state] => self.set_enabled(on(state)?),
+            [name, state] => {
+                let detector = Detector::from_name(name)
+                    .ok_or_else(|| format!("unknown detector '{}'", name))?;
+                self.set_detector(detector, on(state)?);
+            }
+            _ => return Err(format!(
+                "unknown command '{}' (status | strategy on|off | <detector> on|off)",
+                command.trim()
+            )),
+        }
+        Ok(self.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_toggle_shared_switches() {
+        let control = StrategyControl::new(true);
+        let handle = control.clone();
+
+        assert_eq!(
+            handle.apply_command("basis off").unwrap(),
+            "strategy=on imbalance=on basis=off liquidation=on"
+        );
+        assert!(!control.detector_enabled(Detector::Basis));
+
+        handle.apply_command("strategy off").unwrap();
+        assert!(!control.is_enabled());
+
+        assert!(handle.apply_command("shape off").is_err());
+        assert!(handle.apply_command("strategy maybe").is_err());
+        assert_eq!(control.status(), "strategy=off imbalance=on basis=off liquidation=on");
+    }
+}
@@ -0,0 +1,228 @@
+use crate::strategy::{Detector, DetectorHitRate, StrategyControl};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Automatic switch-off of detectors that stopped paying
+/// (`[strategy.detector_policy]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DetectorPolicyConfig {
+    pub enabled: bool,
+    /// Rolling hit rate floor (0 = not checked)
+    pub min_hit_rate: f64,
+    /// Resolved signals before the hit rate is judged
+    pub min_hit_samples: usize,
+    /// Switch off once trades a detector contributed to lost more than
+    /// this over the last `pnl_window` (USD, 0 = not checked)
+    pub max_attributed_loss_usd: f64,
+    /// Trades per detector in the attributed PnL
+    pub pnl_window: usize,
+    /// Trades before attributed PnL is judged
+    pub min_trades: usize,
+}
+
+impl Default for DetectorPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_hit_rate: 0.45,
+            min_hit_samples: 30,
+            max_attributed_loss_usd: 0.0,
+            pnl_window: 20,
+            min_trades: 10,
+        }
+    }
+}
+
+/// Realized PnL of the recent trades a detector contributed to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DetectorPnl {
+    pub detector: String,
+    /// Trades in the rolling window
+    pub trades: usize,
+    /// USD
+    pub pnl: Decimal,
+}
+
+/// Rolling per-detector PnL attribution
+///
+/// A closed trade's full PnL is attributed to every detector that was
+/// primary or confirming in its entry signal; it is not split, since a
+/// confirming detector that let a bad primary through shares the blame.
+pub struct PnlAttribution {
+    window: usize,
+    trades: BTreeMap<String, VecDeque<Decimal>>,
+}
+
+impl PnlAttribution {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            trades: BTreeMap::new(),
+        }
+    }
+
+    pub fn record_trade(&mut self, detectors: &[String], pnl_usd: Decimal) {
+        for detector in detectors {
+            let trades = self.trades.entry(detector.clone()).or_default();
+            trades.push_back(pnl_usd);
+            if trades.len() > self.window {
+                trades.pop_front();
+            }
+        }
+    }
+
+    pub fn stats(&self) -> Vec<DetectorPnl> {
+        self.trades.iter()
+            .map(|(detector, trades)| DetectorPnl {
+                detector: detector.clone(),
+                trades: trades.len(),
+                pnl: trades.iter().sum(),
+            })
+            .collect()
+    }
+
+    /// Forget `detector`'s trades
+    pub fn reset(&mut self, detector: &str) {
+        self.trades.remove(detector);
+    }
+}
+
+/// What the policy did on one evaluation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// Removed from aggregation
+    Disabled { detector: String, reason: String },
+    /// An operator switched a policy-disabled detector back on; its
+    /// record should restart so old results do not switch it off again
+    Reinstated { detector: String },
+}
+
+/// Switches detectors off through `StrategyControl` when their hit rate
+/// or attributed PnL falls below the configured floors
+///
+/// The policy never switches a detector back on: that is left to an
+/// operator (`<detector> on` on the control channel), after which the
+/// detector is judged on fresh results only.
+pub struct DetectorPolicy {
+    config: DetectorPolicyConfig,
+    control: StrategyControl,
+    disabled: BTreeSet<String>,
+}
+
+impl DetectorPolicy {
+    pub fn new(config: DetectorPolicyConfig, control: StrategyControl) -> Self {
+        Self {
+            config,
+            control,
+            disabled: BTreeSet::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Detectors currently switched off by the policy
+    pub fn disabled(&self) -> impl Iterator<Item = &str> {
+        self.disabled.iter().map(String::as_str)
+    }
+
+    pub fn evaluate(&mut self, hit_rates: &[DetectorHitRate], pnl: &[DetectorPnl]) -> Vec<PolicyAction> {
+        let reinstated: Vec<String> = self.disabled.iter()
+            .filter(|name| Detector::from_name(name).is_some_and(|d| self.control.detector_enabled(d)))
+            .cloned()
+            .collect();
+        let mut actions = Vec::new();
+        for detector in &reinstated {
+            self.disabled.remove(detector);
+            actions.push(PolicyAction::Reinstated { detector: detector.clone() });
+        }
+        if !self.config.enabled {
+            return actions;
+        }
+
+        for detector in Detector::ALL {
+            let name = detector.name();
+            if !self.control.detector_enabled(detector) || reinstated.iter().any(|r| r == name) {
+                continue;
+            }
+            let hit_rate = hit_rates.iter().find(|stats| stats.detector == name);
+            let attributed = pnl.iter().find(|stats| stats.detector == name);
+            if let Some(reason) = self.breach(hit_rate, attributed) {
+                self.control.set_detector(detector, false);
+                self.disabled.insert(name.to_string());
+                actions.push(PolicyAction::Disabled { detector: name.to_string(), reason });
+            }
+        }
+        actions
+    }
+
+    fn breach(&self, hit_rate: Option<&DetectorHitRate>, pnl: Option<&DetectorPnl>) -> Option<String> {
+        if let Some(stats) = hit_rate {
+            if let (true, Some(rate)) = (self.config.min_hit_rate > 0.0, stats.hit_rate) {
+                if stats.samples >= self.config.min_hit_samples && rate < self.config.min_hit_rate {
+                    return Some(format!("hit rate {:.1}% over {} signals below {:.1}%",
+                        rate * 100.0, stats.samples, self.config.min_hit_rate * 100.0));
+                }
+            }
+        }
+        if let Some(stats) = pnl {
+            let max_loss = Decimal::from_f64_retain(self.config.max_attributed_loss_usd).unwrap_or_default();
+            if max_loss > Decimal::ZERO && stats.trades >= self.config.min_trades && stats.pnl < -max_loss {
+                return Some(format!("attributed PnL {} over last {} trades below -{}",
+                    stats.pnl.round_dp(2), stats.trades, max_loss));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_disable_on_either_floor_and_reinstate_by_operator() {
+        let config = DetectorPolicyConfig {
+            enabled: true,
+            min_hit_rate: 0.45,
+            min_hit_samples: 20,
+            max_attributed_loss_usd: 50.0,
+            min_trades: 3,
+            ..DetectorPolicyConfig::default()
+        };
+        let control = StrategyControl::new(true);
+        let mut policy = DetectorPolicy::new(config, control.clone());
+
+        let hit_rates = vec![DetectorHitRate {
+            detector: "imbalance".to_string(),
+            signals: 40,
+            samples: 30,
+            hit_rate: Some(0.4),
+        }];
+        let mut attribution = PnlAttribution::new(20);
+        for pnl in [dec!(-30), dec!(-25), dec!(-5)] {
+            attribution.record_trade(&["basis".to_string()], pnl);
+        }
+
+        let actions = policy.evaluate(&hit_rates, &attribution.stats());
+        assert_eq!(actions.len(), 2);
+        assert!(!control.detector_enabled(Detector::Imbalance));
+        assert!(!control.detector_enabled(Detector::Basis));
+        assert_eq!(actions[1], PolicyAction::Disabled {
+            detector: "basis".to_string(),
+            reason: "attributed PnL -60 over last 3 trades below -50".to_string(),
+        });
+
+        // Operator turns imbalance back on: reinstated, not re-judged on old results
+        control.apply_command("imbalance on").unwrap();
+        let actions = policy.evaluate(&hit_rates, &attribution.stats());
+        assert_eq!(actions, vec![PolicyAction::Reinstated { detector: "imbalance".to_string() }]);
+        assert!(control.detector_enabled(Detector::Imbalance));
+        assert_eq!(policy.disabled().collect::<Vec<_>>(), vec!["basis"]);
+    }
+}
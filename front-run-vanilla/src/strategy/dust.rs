@@ -0,0 +1,109 @@
+use crate::data::Side;
+use crate::risk::Instrument;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+
+/// Residual position cleanup (`[strategy.dust]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DustConfig {
+    /// Interval between dust sweeps (0 = disabled)
+    pub sweep_interval_secs: u64,
+    /// Minimum order notional in USD; used when the exchange filters are
+    /// not available, the exchange's MIN_NOTIONAL otherwise
+    pub min_notional_usd: f64,
+}
+
+impl Default for DustConfig {
+    fn default() -> Self {
+        Self {
+            sweep_interval_secs: 60,
+            min_notional_usd: 5.0,
+        }
+    }
+}
+
+/// Split a close of `quantity` into what can be ordered on the lot grid
+/// and the residual below one lot
+pub fn split_close(instrument: &Instrument, quantity: Decimal) -> (Decimal, Decimal) {
+    let tradeable = instrument.round_quantity(quantity);
+    (tradeable, quantity - tradeable)
+}
+
+/// Position left on the exchange after closes were rounded to the lot
+///
+/// A close can only order whole lots, so a position off the lot grid
+/// (restored, reconciled or filled in odd sizes) leaves a sliver behind
+/// once the local position is closed. The sliver is recorded here,
+/// netted across sides, until enough of it adds up to a lot that a sweep
+/// can close.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DustLedger {
+    /// Signed residual quantity: positive = long, negative = short
+    net: Decimal,
+}
+
+impl DustLedger {
+    /// Record `quantity` left open on a position of `side`
+    pub fn add(&mut self, side: Side, quantity: Decimal) {
+        match side {
+            Side::Buy => self.net += quantity,
+            Side::Sell => self.net -= quantity,
+        }
+    }
+
+    pub fn net(&self) -> Decimal {
+        self.net
+    }
+
+    /// Order that closes as much of the residual as the lot grid allows
+    pub fn sweep_order(&self, instrument: &Instrument) -> Option<(Side, Decimal)> {
+        let (quantity, _) = split_close(instrument, self.net.abs());
+        if quantity.is_zero() {
+            return None;
+        }
+        let side = if self.net > Decimal::ZERO { Side::Sell } else { Side::Buy };
+        Some((side, quantity))
+    }
+
+    /// Record a sweep order on `side` that filled `quantity`
+    pub fn record_swept(&mut self, side: Side, quantity: Decimal) {
+        match side {
+            Side::Sell => self.net -= quantity,
+            Side::Buy => self.net += quantity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_residuals_accumulate_until_a_lot_can_be_swept() {
+        let instrument = Instrument::linear().with_increments(dec!(0.1), dec!(0.001));
+        assert_eq!(split_close(&instrument, dec!(0.0247)), (dec!(0.024), dec!(0.0007)));
+        assert_eq!(split_close(&Instrument::linear(), dec!(0.0247)), (dec!(0.0247), dec!(0)));
+
+        let mut dust = DustLedger::default();
+        dust.add(Side::Buy, dec!(0.0007));
+        assert_eq!(dust.sweep_order(&instrument), None);
+
+        // Two long slivers and a short one net to 0.0011 long
+        dust.add(Side::Buy, dec!(0.0006));
+        dust.add(Side::Sell, dec!(0.0002));
+        assert_eq!(dust.net(), dec!(0.0011));
+        assert_eq!(dust.sweep_order(&instrument), Some((Side::Sell, dec!(0.001))));
+
+        dust.record_swept(Side::Sell, dec!(0.001));
+        assert_eq!(dust.net(), dec!(0.0001));
+        assert_eq!(dust.sweep_order(&instrument), None);
+
+        // Short dust is bought back
+        dust.add(Side::Sell, dec!(0.0031));
+        assert_eq!(dust.sweep_order(&instrument), Some((Side::Buy, dec!(0.003))));
+        dust.record_swept(Side::Buy, dec!(0.003));
+        assert_eq!(dust.net(), dec!(0));
+    }
+}
@@ -1,9 +1,23 @@
-use crate::data::{Side, Order};
-use crate::exchange::BinanceRestClient;
-use crate::risk::{Position, PositionManager, RiskManager};
-use crate::strategy::CompositeSignal;
+use crate::data::{Side, OrderBook};
+use crate::exchange::{BinanceRestClient, Exchange};
+use crate::exchange::binance::{IncomeSummary, LocalPnl, OrderFills, OrderResponse, ReconciliationReport};
+use crate::risk::{
+    EventCalendar, Instrument, Position, PositionManager, RiskHandle, RiskManager, RiskMetricsSample,
+};
+use crate::storage::{BusMessage, BusPublisher, Experiment, JsonlWriter, WebhookEvent, WebhookNotifier};
+use crate::strategy::{
+    ActivityConfig, ActivityMonitor, ActivityStats, AgeMonitor, AgeWarningConfig, AgeWarningStats, CompositeSignal, EvaluateBook, ExitFill,
+    DetectorHitRate, DetectorPnl, ExitOrderConfig, ExitReason, HitRateConfig, HitRateTracker, LedgerReport, LedgerTally, MarketQualityGate,
+    MarketQualityViolation, OcoAction, OcoExit, OcoFlatten, OcoLeg, OcoPair, OcoTracker, PnlAttribution, ReentryGuard, RejectionCounts,
+    RejectionReason, RestingExit, RestingOrder, SelfTradeGuard, SignalExplanation, SignalRole, SizingMode,
+    DustLedger, TakeProfitSchedule, audit_ledger, passive_exit_price, split_close, trigger_price,
+};
 use rust_decimal::Decimal;
-use std::time::{SystemTime, Instant};
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, Instant, UNIX_EPOCH};
 use anyhow::{Result, anyhow};
 use tracing::{info, warn, error};
 
@@ -20,30 +34,195 @@ pub struct ExecutionResult {
     pub timestamp: SystemTime,
 }
 
-/// Execution engine with latency tracking
-pub struct ExecutionEngine {
-    client: BinanceRestClient,
+/// What became of a signal handed to `execute_signal`
+#[derive(Debug)]
+pub enum ExecutionOutcome {
+    Executed(ExecutionResult),
+    /// Refused by a risk gate: limits, breaker, cooldown, scheduled event
+    /// blackout or re-entry spacing; `detail` is the gate's own message
+    SkippedRisk { reason: RejectionReason, detail: String },
+    /// Spread or depth outside the market quality limits
+    SkippedMarketQuality(MarketQualityViolation),
+    /// Signal older than the maximum signal age when it reached execution
+    Aborted { latency: Duration },
+    /// Sizing, order placement or fill handling went wrong
+    Failed(anyhow::Error),
+}
+
+impl ExecutionOutcome {
+    pub fn is_executed(&self) -> bool {
+        matches!(self, Self::Executed(_))
+    }
+
+    /// Gate the signal was counted under, if it was refused by one
+    pub fn rejection(&self) -> Option<RejectionReason> {
+        match self {
+            Self::SkippedRisk { reason, .. } => Some(*reason),
+            Self::SkippedMarketQuality(violation) => Some(RejectionReason::from_market_quality(violation)),
+            Self::Aborted { .. } => Some(RejectionReason::LatencyAborted),
+            Self::Executed(_) | Self::Failed(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ExecutionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Executed(result) => write!(
+                f, "executed {} {:?} {} @ {} in {}ms",
+                result.order_id, result.side, result.executed_qty, result.executed_price, result.latency_ms
+            ),
+            Self::SkippedRisk { reason, detail } => write!(f, "skipped ({:?}): {}", reason, detail),
+            Self::SkippedMarketQuality(violation) => write!(f, "skipped (market quality): {}", violation),
+            Self::Aborted { latency } => write!(f, "aborted: signal {}ms old", latency.as_millis()),
+            Self::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+/// Which side of a round trip a journaled fill belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalLeg {
+    Entry,
+    Exit,
+}
+
+/// One live fill with the market state it was decided on
+///
+/// Appended to `journal.jsonl` so fills can later be re-simulated against
+/// recorded market data to calibrate the backtest fill model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalFill {
+    /// Decision time in milliseconds since UNIX epoch
+    pub decision_time_ms: u64,
+    pub account: String,
+    pub symbol: String,
+    pub leg: JournalLeg,
+    /// Side of this order (an exit is opposite to its entry)
+    pub side: Side,
+    /// Mid price the decision was made on
+    pub decision_mid: Decimal,
+    pub executed_price: Decimal,
+    pub executed_qty: Decimal,
+    pub fees: Decimal,
+    /// Decision to fill round trip
+    pub latency_ms: u64,
+    /// Why the entry was taken (entries only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<SignalExplanation>,
+    /// Experiment the fill was made under
+    #[serde(default, skip_serializing_if = "Experiment::is_empty")]
+    pub experiment: Experiment,
+}
+
+/// Name of the account passed to `ExecutionEngine::new`
+pub const PRIMARY_ACCOUNT: &str = "primary";
+
+/// Trading account (API credentials + its own positions and risk state)
+///
+/// Sub-accounts are fully isolated: a daily-loss halt on a small live probe
+/// does not stop the testnet account running alongside it, and vice versa.
+struct TradingAccount<E> {
+    name: String,
+    /// Shared with tasks that read the account off the fast path
+    client: Arc<E>,
     position_manager: PositionManager,
-    risk_manager: RiskManager,
+    /// Shared with monitoring and control tasks (`risk_handle`)
+    risk_manager: RiskHandle,
+    /// Entry notional, risk per trade or equity percent, per `SizingMode`
+    base_position_size: Decimal,
+    /// Equity last read from the exchange (`refresh_equity`)
+    equity: Option<Decimal>,
+    /// Signals this account did not trade, by gate
+    rejections: RejectionCounts,
+    /// Fills successfully written to the journal, for ledger audits
+    journaled: LedgerTally,
+    /// A halt webhook was sent for the current halt
+    halt_notified: bool,
+    /// Detectors behind the open position's entry, for PnL attribution
+    entry_sources: Vec<String>,
+    /// Last exit, against which same-direction re-entries are checked
+    reentry: ReentryGuard,
+    /// Age thresholds crossed by open positions
+    aging: AgeMonitor,
+    /// Order updates from this account's user data stream, if attached
+    order_fills: Option<OrderFills>,
+    /// Sub-lot residuals left on the exchange by rounded closes
+    dust: DustLedger,
+    /// Limit closes resting at the touch, by symbol
+    resting_exits: HashMap<String, RestingExit>,
+    /// Exchange-side TP/SL pairs, so one leg's fill cancels the other
+    oco: OcoTracker,
+    /// The TP/SL pair protecting each open position, by symbol
+    oco_exits: HashMap<String, OcoExit>,
+}
+
+/// Execution engine with latency tracking
+///
+/// Generic over the venue orders are routed to; Binance unless stated.
+pub struct ExecutionEngine<E: Exchange = BinanceRestClient> {
+    /// Accounts orders can be routed to; index 0 is the primary account
+    accounts: Vec<TradingAccount<E>>,
+    market_quality: MarketQualityGate,
+    calendar: Option<EventCalendar>,
     
     // Trading configuration
     symbol: String,
-    base_position_size: Decimal,
+    instrument: Instrument,
+    /// USD value of one unit of the quote asset (1 unless quoted in e.g. BTC)
+    quote_usd: Decimal,
+    sizing: SizingMode,
     min_size_multiplier: Decimal,
     max_size_multiplier: Decimal,
+    /// Hard cap on any entry's notional, after confidence scaling
+    max_notional: Option<Decimal>,
+    /// Signals older than this (exchange event to execution) are not traded
+    max_signal_age: Option<Duration>,
     
     // Exit parameters
-    take_profit_bps: Decimal,
+    take_profit: TakeProfitSchedule,
     stop_loss_bps: Decimal,
     max_hold_time_ms: u64,
+    signal_decay_exit_z: Option<f64>,
+    /// Limit-at-touch windows before exits go to market
+    exit_orders: ExitOrderConfig,
+    /// Template for each account's re-entry guard
+    reentry: ReentryGuard,
+    /// Template for each account's stalled-position warnings
+    aging: AgeMonitor,
     
-    // Fee rate (Binance Futures taker fee: 0.04%)
+    // Fee rates (Binance Futures taker 0.04%, maker 0.02%)
     taker_fee_rate: Decimal,
+    maker_fee_rate: Decimal,
+    /// Wait for a market order's final user data update before using the
+    /// REST response instead
+    fill_timeout: Duration,
+    /// Smallest plain order the exchange accepts, in USD (0 = no minimum)
+    min_order_notional: Decimal,
+
+    journal: Option<JsonlWriter>,
+    /// Stamped onto journaled fills and stats snapshots
+    experiment: Experiment,
+    webhooks: Option<WebhookNotifier>,
+    /// Fills published for external consumers
+    bus: Option<BusPublisher>,
+    /// Our resting orders on every account, cancelled before we cross them
+    self_trade: SelfTradeGuard,
+    /// Signals rejected before reaching any account (aggregation, filters)
+    rejections: RejectionCounts,
+    /// Signal/trade frequency and per-trade edge across all accounts
+    activity: ActivityMonitor,
+    /// Rolling hit rate of every detector's signals
+    hit_rates: HitRateTracker,
+    /// Rolling PnL of the trades each detector contributed to
+    attribution: PnlAttribution,
+    started_at: Instant,
 }
 
-impl ExecutionEngine {
+impl<E: Exchange> ExecutionEngine<E> {
     pub fn new(
-        client: BinanceRestClient,
+        client: E,
         risk_manager: RiskManager,
         symbol: String,
         base_position_size: Decimal,
@@ -51,81 +230,439 @@ impl ExecutionEngine {
         stop_loss_bps: Decimal,
         max_hold_time_ms: u64,
     ) -> Self {
-        Self {
-            client,
+        let primary = TradingAccount {
+            name: PRIMARY_ACCOUNT.to_string(),
+            client: Arc::new(client),
             position_manager: PositionManager::new(),
-            risk_manager,
-            symbol,
+            risk_manager: RiskHandle::new(risk_manager),
             base_position_size,
+            equity: None,
+            rejections: RejectionCounts::default(),
+            journaled: LedgerTally::default(),
+            halt_notified: false,
+            entry_sources: Vec::new(),
+            reentry: ReentryGuard::new(Default::default()),
+            aging: AgeMonitor::new(AgeWarningConfig::default(), max_hold_time_ms),
+            order_fills: None,
+            dust: DustLedger::default(),
+            resting_exits: HashMap::new(),
+            oco: OcoTracker::new(),
+            oco_exits: HashMap::new(),
+        };
+
+        Self {
+            accounts: vec![primary],
+            market_quality: MarketQualityGate::default(),
+            calendar: None,
+            symbol,
+            instrument: Instrument::linear(),
+            quote_usd: Decimal::ONE,
+            sizing: SizingMode::FixedNotional,
             min_size_multiplier: Decimal::from_f64_retain(0.5).unwrap(),
             max_size_multiplier: Decimal::from_f64_retain(2.0).unwrap(),
-            take_profit_bps,
+            max_notional: None,
+            max_signal_age: None,
+            take_profit: TakeProfitSchedule::fixed(take_profit_bps),
             stop_loss_bps,
             max_hold_time_ms,
+            signal_decay_exit_z: None,
+            exit_orders: ExitOrderConfig::default(),
+            reentry: ReentryGuard::new(Default::default()),
+            aging: AgeMonitor::new(AgeWarningConfig::default(), max_hold_time_ms),
             taker_fee_rate: Decimal::from_f64_retain(0.0004).unwrap(), // 0.04%
+            maker_fee_rate: Decimal::from_f64_retain(0.0002).unwrap(), // 0.02%
+            fill_timeout: Duration::from_secs(2),
+            min_order_notional: Decimal::ZERO,
+            journal: None,
+            experiment: Experiment::default(),
+            webhooks: None,
+            bus: None,
+            self_trade: SelfTradeGuard::new(),
+            rejections: RejectionCounts::default(),
+            activity: ActivityMonitor::new(ActivityConfig::default()),
+            hit_rates: HitRateTracker::new(HitRateConfig::default(), max_hold_time_ms),
+            attribution: PnlAttribution::new(20),
+            started_at: Instant::now(),
         }
     }
 
-    /// Execute a trade based on composite signal
+    /// Take the primary account's market fills from its user data stream
+    ///
+    /// A market order's synchronous response may not report the fill yet
+    /// (price "0", status NEW). With this set, entries and exits wait up to
+    /// `timeout` for the order's final ORDER_TRADE_UPDATE and use its
+    /// average price and filled quantity, falling back to the response.
+    pub fn with_order_fills(mut self, fills: OrderFills, timeout: Duration) -> Self {
+        self.accounts[0].order_fills = Some(fills);
+        self.fill_timeout = timeout;
+        self
+    }
+
+    /// Exchange minimum order notional in USD
+    ///
+    /// Closes below it are sent reduce-only (exempt from the minimum), and
+    /// `sweep_dust` closes positions that have fallen below it.
+    pub fn with_min_order_notional(mut self, min_notional_usd: Decimal) -> Self {
+        self.min_order_notional = min_notional_usd;
+        self
+    }
+
+    /// Replace the default detector hit-rate tracking
+    pub fn with_hit_rates(mut self, hit_rates: HitRateTracker) -> Self {
+        self.hit_rates = hit_rates;
+        self
+    }
+
+    /// Replace the default per-detector PnL attribution
+    pub fn with_pnl_attribution(mut self, attribution: PnlAttribution) -> Self {
+        self.attribution = attribution;
+        self
+    }
+
+    /// Set the pre-trade market quality gate
+    pub fn with_market_quality(mut self, gate: MarketQualityGate) -> Self {
+        self.market_quality = gate;
+        self
+    }
+
+    /// Scale take profit with signal strength
+    pub fn with_take_profit_schedule(mut self, schedule: TakeProfitSchedule) -> Self {
+        self.take_profit = schedule;
+        self
+    }
+
+    /// Exit once the entry imbalance reverts below `exit_z` sigma
+    pub fn with_signal_decay_exit(mut self, exit_z: Option<f64>) -> Self {
+        self.signal_decay_exit_z = exit_z;
+        self
+    }
+
+    /// Rest exits at the touch before going to market
+    pub fn with_exit_orders(mut self, exit_orders: ExitOrderConfig) -> Self {
+        self.exit_orders = exit_orders;
+        self
+    }
+
+    /// Block same-direction entries near each account's last exit
+    pub fn with_reentry_guard(mut self, guard: ReentryGuard) -> Self {
+        for account in &mut self.accounts {
+            account.reentry = guard.clone();
+        }
+        self.reentry = guard;
+        self
+    }
+
+    /// Warn about positions stalling toward the max hold time
+    pub fn with_age_monitor(mut self, aging: AgeMonitor) -> Self {
+        for account in &mut self.accounts {
+            account.aging = aging.clone();
+        }
+        self.aging = aging;
+        self
+    }
+
+    /// Size, round and settle orders as `instrument`
+    pub fn with_instrument(mut self, instrument: Instrument) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Block entries around scheduled events
+    pub fn with_calendar(mut self, calendar: EventCalendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// Swap in a refetched calendar
+    pub fn set_calendar(&mut self, calendar: EventCalendar) {
+        self.calendar = Some(calendar);
+    }
+
+    /// How accounts' base sizes are turned into entry notional
+    pub fn with_sizing(mut self, sizing: SizingMode) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    /// Replace the default overtrading detector
+    pub fn with_activity_monitor(mut self, activity: ActivityMonitor) -> Self {
+        self.activity = activity;
+        self
+    }
+
+    /// Never size an entry above `max_notional`, whatever the confidence
+    pub fn with_max_notional(mut self, max_notional: Decimal) -> Self {
+        self.max_notional = Some(max_notional);
+        self
+    }
+
+    /// Abort entries on signals older than `max_age` by the time they
+    /// reach execution; the price they were made on has likely moved
+    pub fn with_max_signal_age(mut self, max_age: Duration) -> Self {
+        self.max_signal_age = Some(max_age);
+        self
+    }
+
+    /// Append every entry and exit fill to a trade journal
+    pub fn with_journal(mut self, journal: JsonlWriter) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Label journaled fills and stats snapshots with an experiment
+    pub fn with_experiment(mut self, experiment: Experiment) -> Self {
+        self.experiment = experiment;
+        self
+    }
+
+    /// Send fills, position opens/closes and halts to webhooks
+    pub fn with_webhooks(mut self, webhooks: WebhookNotifier) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Publish every entry and exit fill on the event bus
+    pub fn with_bus(mut self, bus: BusPublisher) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Add a sub-account with its own credentials, sizing and risk limits
+    pub fn add_account(
+        &mut self,
+        name: &str,
+        client: E,
+        risk_manager: RiskManager,
+        base_position_size: Decimal,
+    ) -> Result<()> {
+        if self.account_index(name).is_some() {
+            return Err(anyhow!("Account already exists: {}", name));
+        }
+
+        self.accounts.push(TradingAccount {
+            name: name.to_string(),
+            client: Arc::new(client),
+            position_manager: PositionManager::new(),
+            risk_manager: RiskHandle::new(risk_manager),
+            base_position_size,
+            equity: None,
+            rejections: RejectionCounts::default(),
+            journaled: LedgerTally::default(),
+            halt_notified: false,
+            entry_sources: Vec::new(),
+            reentry: self.reentry.clone(),
+            aging: self.aging.clone(),
+            order_fills: None,
+            dust: DustLedger::default(),
+            resting_exits: HashMap::new(),
+            oco: OcoTracker::new(),
+            oco_exits: HashMap::new(),
+        });
+
+        Ok(())
+    }
+
+    /// Names of all accounts (primary first)
+    pub fn account_names(&self) -> Vec<String> {
+        self.accounts.iter().map(|a| a.name.clone()).collect()
+    }
+
+    fn account_index(&self, name: &str) -> Option<usize> {
+        self.accounts.iter().position(|a| a.name == name)
+    }
+
+    /// Execute a trade based on composite signal on the primary account
+    /// 
+    /// Entry price is taken from the current mid of `orderbook`.
     pub async fn execute_signal(
         &mut self,
         signal: CompositeSignal,
-        current_price: Decimal,
-    ) -> Result<ExecutionResult> {
+        orderbook: &OrderBook,
+    ) -> ExecutionOutcome {
+        self.execute_signal_for(PRIMARY_ACCOUNT, signal, orderbook).await
+    }
+
+    /// Execute a trade based on composite signal on a named account
+    ///
+    /// Skips and aborts are counted in the account's rejections before
+    /// they are returned.
+    pub async fn execute_signal_for(
+        &mut self,
+        account_name: &str,
+        signal: CompositeSignal,
+        orderbook: &OrderBook,
+    ) -> ExecutionOutcome {
+        self.try_execute_signal(account_name, signal, orderbook).await
+            .unwrap_or_else(ExecutionOutcome::Failed)
+    }
+
+    async fn try_execute_signal(
+        &mut self,
+        account_name: &str,
+        mut signal: CompositeSignal,
+        orderbook: &OrderBook,
+    ) -> Result<ExecutionOutcome> {
         let signal_time = Instant::now();
+        let decision_time = SystemTime::now();
+
+        let idx = self.account_index(account_name)
+            .ok_or_else(|| anyhow!("Unknown account: {}", account_name))?;
+
+        let age = signal.age(decision_time);
+        if self.max_signal_age.is_some_and(|max_age| age > max_age) {
+            self.accounts[idx].rejections.record(RejectionReason::LatencyAborted);
+            return Ok(ExecutionOutcome::Aborted { latency: age });
+        }
+        signal.explanation.check(
+            "signal_age",
+            true,
+            format!("{}ms since exchange event", age.as_millis()),
+        );
 
-        // 1. Calculate position size based on confidence
-        let position_size = self.calculate_position_size(signal.confidence);
+        // 0. Refuse to enter around scheduled events or into a blown-out/thin book
+        if let Some(event) = self.calendar.as_ref()
+            .and_then(|c| c.active_blackout(SystemTime::now()))
+        {
+            self.accounts[idx].rejections.record(RejectionReason::Blackout);
+            return Ok(ExecutionOutcome::SkippedRisk {
+                reason: RejectionReason::Blackout,
+                detail: format!("scheduled event: {}", event.name),
+            });
+        }
+        signal.explanation.check("calendar", true, "no active blackout");
+
+        if let Err(violation) = self.market_quality.check(orderbook) {
+            self.accounts[idx].rejections.record(RejectionReason::from_market_quality(&violation));
+            return Ok(ExecutionOutcome::SkippedMarketQuality(violation));
+        }
+        signal.explanation.check("market_quality", true, "spread and depth within limits");
+
+        let current_price = orderbook.get_mid_price()
+            .ok_or_else(|| anyhow!("No mid price available"))?;
+
+        if let Err(reason) = self.accounts[idx].reentry.check(signal.direction, current_price, decision_time) {
+            self.accounts[idx].rejections.record(RejectionReason::ReentryTooClose);
+            return Ok(ExecutionOutcome::SkippedRisk { reason: RejectionReason::ReentryTooClose, detail: reason });
+        }
+        if self.accounts[idx].reentry.is_enabled() {
+            signal.explanation.check("reentry", true, "clear of last exit");
+        }
+
+        // 1. Calculate position size from the sizing mode and confidence
+        let account = &self.accounts[idx];
+        let equity = account.equity.unwrap_or_else(|| account.risk_manager.read().marked_equity());
+        let base_notional = self.sizing
+            .base_notional(account.base_position_size, self.stop_loss_bps, equity)
+            .ok_or_else(|| anyhow!("Cannot size entry: {:?} with stop {} bps and equity {}",
+                self.sizing, self.stop_loss_bps, equity))?;
+        let position_size = self.calculate_position_size(base_notional, signal.confidence);
+
+        let account = &mut self.accounts[idx];
 
         // 2. Check risk limits
-        let current_exposure = self.position_manager.total_exposure();
-        self.risk_manager.can_open_position(position_size, current_exposure)
-            .map_err(|e| anyhow!("Risk check failed: {}", e.reason))?;
+        let current_exposure = account.position_manager.total_exposure() * self.quote_usd;
+        let check = account.risk_manager.write().can_open_position(position_size, current_exposure);
+        if let Err(violation) = check {
+            let halted = account.risk_manager.is_halted();
+            let reason = RejectionReason::from_risk_violation(&violation, halted);
+            account.rejections.record(reason);
+            self.notify_halt(idx);
+            return Ok(ExecutionOutcome::SkippedRisk { reason, detail: violation.reason });
+        }
+        signal.explanation.check(
+            "risk",
+            true,
+            format!("size {} with exposure {} [{}]", position_size, current_exposure, account.name),
+        );
 
-        // 3. Calculate quantity
-        let quantity = position_size / current_price;
+        // 3. Calculate quantity (base asset, or whole contracts for inverse)
+        let quantity = self.instrument.quantity(position_size, current_price, self.quote_usd);
+        if quantity <= Decimal::ZERO {
+            return Err(anyhow!("Entry size {} is below one contract", position_size));
+        }
 
         info!(
-            "Executing signal: {:?} | Size: {} | Qty: {} | Price: {}",
-            signal.direction, position_size, quantity, current_price
+            "Executing signal [{}]: {:?} | Size: {} | Qty: {} | Price: {}",
+            account.name, signal.direction, position_size, quantity, current_price
         );
 
+        // 3b. Pull any of our resting orders this order would trade against
+        self.cancel_crossing_orders(signal.direction, None).await?;
+        let account = &mut self.accounts[idx];
+
         // 4. Place market order
-        let order_response = self.client
+        let order_response = account.client
             .place_market_order(&self.symbol, signal.direction, quantity)
             .await?;
 
         let execution_latency = signal_time.elapsed().as_millis() as u64;
 
         // 5. Record latency
-        self.risk_manager.record_latency(execution_latency);
+        account.risk_manager.write().record_latency(execution_latency);
 
-        // 6. Parse execution result
-        let executed_price = order_response.price.parse::<Decimal>()
-            .map_err(|e| anyhow!("Failed to parse price: {}", e))?;
-        let executed_qty = order_response.executed_qty.parse::<Decimal>()
-            .map_err(|e| anyhow!("Failed to parse quantity: {}", e))?;
+        // 6. Parse execution result (from the user data stream if attached)
+        let (executed_price, executed_qty) = Self::market_fill(
+            account.order_fills.as_ref(), self.fill_timeout, &order_response,
+        ).await?;
+        if executed_qty <= Decimal::ZERO {
+            return Err(anyhow!("Market order {} did not fill ({})", order_response.order_id, order_response.status));
+        }
 
-        // 7. Calculate fees
-        let fees = executed_price * executed_qty * self.taker_fee_rate;
+        // 7. Calculate fees (settlement asset)
+        let fees = self.instrument.notional(executed_qty, executed_price) * self.taker_fee_rate;
 
-        // 8. Create position
+        // 8. Create position with strength-scaled take profit
+        let take_profit_bps = self.take_profit.take_profit_bps(signal.overall_strength);
         let position = Position::new(
             self.symbol.clone(),
             signal.direction,
             executed_price,
             executed_qty,
             fees,
-        );
+        )
+        .with_take_profit(take_profit_bps)
+        .with_instrument(self.instrument);
+
+        account.position_manager.open_position(position)?;
+        account.entry_sources = signal.explanation.sources.iter()
+            .filter(|source| source.role != SignalRole::Ignored)
+            .map(|source| source.source.clone())
+            .collect();
 
-        self.position_manager.open_position(position)?;
+        Self::notify(&self.webhooks, WebhookEvent::PositionOpened {
+            time_ms: epoch_ms(SystemTime::now()),
+            account: account.name.clone(),
+            symbol: self.symbol.clone(),
+            side: signal.direction,
+            entry_price: executed_price,
+            quantity: executed_qty,
+        });
+        Self::record_fill(&mut self.journal, &self.webhooks, &self.bus, &mut account.journaled, JournalFill {
+            decision_time_ms: epoch_ms(decision_time),
+            account: account.name.clone(),
+            symbol: self.symbol.clone(),
+            leg: JournalLeg::Entry,
+            side: signal.direction,
+            decision_mid: current_price,
+            executed_price,
+            executed_qty,
+            fees,
+            latency_ms: execution_latency,
+            explanation: Some(signal.explanation.clone()),
+            experiment: self.experiment.clone(),
+        });
 
         info!(
-            "✅ Order executed | ID: {} | Price: {} | Qty: {} | Latency: {}ms",
-            order_response.order_id, executed_price, executed_qty, execution_latency
+            "✅ Order executed [{}] | ID: {} | Price: {} | Qty: {} | Latency: {}ms",
+            account.name, order_response.order_id, executed_price, executed_qty, execution_latency
         );
+        if self.exit_orders.exchange_tp_sl {
+            self.place_oco_exit(idx, take_profit_bps).await;
+        }
+        self.notify_halt(idx);
+        self.debug_audit();
 
-        Ok(ExecutionResult {
+        Ok(ExecutionOutcome::Executed(ExecutionResult {
             order_id: order_response.order_id.to_string(),
             symbol: self.symbol.clone(),
             side: signal.direction,
@@ -134,86 +671,881 @@ impl ExecutionEngine {
             latency_ms: execution_latency,
             fees,
             timestamp: SystemTime::now(),
-        })
+        }))
+    }
+
+    /// Check exit conditions for all open positions on every account
+    ///
+    /// `imbalance_z` is the imbalance detector's latest z-score, for the
+    /// signal decay exit. Mid and touch come from one `get_depth` copy of `orderbook`.
+    pub async fn check_exits(&mut self, orderbook: &OrderBook, imbalance_z: Option<f64>) -> Result<()> {
+        let (mid, touch) = orderbook.evaluate(1, |view| {
+            (view.mid(), view.best_bid().zip(view.best_ask()))
+        });
+        let Some(current_price) = mid else {
+            return Ok(());
+        };
+        self.check_exits_at(current_price, touch, imbalance_z).await
     }
 
-    /// Check exit conditions for all open positions
-    pub async fn check_exits(&mut self, current_price: Decimal) -> Result<()> {
-        let positions = self.position_manager.open_positions().to_vec();
+    /// Check exits at a best bid/ask from outside the depth book
+    ///
+    /// The `@bookTicker` stream pushes every touch change, ahead of the
+    /// batched depth diffs, so exits can react between depth updates.
+    pub async fn check_exits_at_touch(
+        &mut self,
+        best_bid: Decimal,
+        best_ask: Decimal,
+        imbalance_z: Option<f64>,
+    ) -> Result<()> {
+        if best_bid <= Decimal::ZERO || best_ask < best_bid {
+            return Ok(());
+        }
+        let mid = (best_bid + best_ask) / Decimal::from(2);
+        self.check_exits_at(mid, Some((best_bid, best_ask)), imbalance_z).await
+    }
+
+    async fn check_exits_at(
+        &mut self,
+        current_price: Decimal,
+        touch: Option<(Decimal, Decimal)>,
+        imbalance_z: Option<f64>,
+    ) -> Result<()> {
+        // Position PnL is in the settlement asset; risk limits are in USD
+        let settlement_usd = self.instrument.settlement_usd(current_price, self.quote_usd);
+
+        // One account's failed order must not hold back the others' exits
+        let mut failures = Vec::new();
+        for idx in 0..self.accounts.len() {
+            let checked = self.check_account_exits(idx, current_price, settlement_usd, touch, imbalance_z).await;
+            if let Err(e) = checked {
+                let account = self.accounts[idx].name.clone();
+                error!("Exit check failed [{}]: {}", account, e);
+                Self::notify(&self.webhooks, WebhookEvent::ExitFailed {
+                    time_ms: epoch_ms(SystemTime::now()),
+                    account: account.clone(),
+                    error: e.to_string(),
+                });
+                failures.push(format!("[{}] {}", account, e));
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(anyhow!("Exit check failed on {} account(s): {}", failures.len(), failures.join("; ")));
+        }
+        Ok(())
+    }
+
+    async fn check_account_exits(
+        &mut self,
+        idx: usize,
+        current_price: Decimal,
+        settlement_usd: Decimal,
+        touch: Option<(Decimal, Decimal)>,
+        imbalance_z: Option<f64>,
+    ) -> Result<()> {
+        let positions = self.accounts[idx].position_manager.open_positions().to_vec();
+
+        // Open losses count toward drawdown and daily loss; flatten on breach
+        let unrealized = self.accounts[idx].position_manager
+            .total_unrealized_pnl(&[(self.symbol.clone(), current_price)]) * settlement_usd;
+        let marked = self.accounts[idx].risk_manager.write().mark_to_market(unrealized);
+        self.notify_halt(idx);
+        if let Err(violation) = marked {
+            if !positions.is_empty() {
+                warn!("🚨 {} [{}]: closing open positions", violation.reason, self.accounts[idx].name);
+                for position in &positions {
+                    self.close_position(idx, &position.symbol, current_price, ExitReason::Risk).await?;
+                }
+                return Ok(());
+            }
+        }
 
         for position in positions {
-            if self.should_exit(&position, current_price) {
-                self.close_position(&position.symbol, current_price).await?;
+            let loss_capped = self.accounts[idx].risk_manager.read()
+                .check_trade_loss(position.unrealized_pnl(current_price) * settlement_usd);
+            if let Err(violation) = &loss_capped {
+                warn!("Force-closing {} [{}]: {}", position.symbol, self.accounts[idx].name, violation.reason);
+            }
+            let reason = match loss_capped {
+                Err(_) => Some(ExitReason::Risk),
+                Ok(()) => self.exit_reason(&position, current_price, imbalance_z),
+            };
+            if self.accounts[idx].oco_exits.contains_key(&position.symbol) {
+                match reason {
+                    // Worked on the exchange: a trigger fill closes the position
+                    None | Some(ExitReason::TakeProfit) | Some(ExitReason::StopLoss) => {
+                        if reason.is_none() {
+                            self.check_age(idx, &position, current_price);
+                        }
+                        self.advance_oco_exit(idx, &position.symbol, current_price).await?;
+                    }
+                    Some(reason) => {
+                        self.close_position(idx, &position.symbol, current_price, reason).await?;
+                    }
+                }
+            } else if self.accounts[idx].resting_exits.contains_key(&position.symbol) {
+                self.advance_resting_exit(idx, &position.symbol, current_price, reason).await?;
+            } else if let Some(reason) = reason {
+                if !self.rest_exit(idx, &position.symbol, touch, reason).await? {
+                    self.close_position(idx, &position.symbol, current_price, reason).await?;
+                }
+            } else {
+                self.check_age(idx, &position, current_price);
             }
         }
 
         Ok(())
     }
 
-    /// Check if position should be exited
-    fn should_exit(&self, position: &Position, current_price: Decimal) -> bool {
+    /// Warn (log and webhook) if an open position is stalling toward its timeout
+    fn check_age(&mut self, idx: usize, position: &Position, current_price: Decimal) {
+        let take_profit_bps = position.take_profit_bps
+            .unwrap_or_else(|| self.take_profit.base_bps());
+        let now = SystemTime::now();
+        let account = &mut self.accounts[idx];
+        let Some(warning) = account.aging.check(position, take_profit_bps, current_price, now) else {
+            return;
+        };
+
+        warn!(
+            "⏳ {} [{}] {:?} open {}ms ({:.0}% of max hold) at {:.0}% of take profit",
+            warning.symbol, account.name, warning.side, warning.age_ms,
+            warning.fraction * 100.0, warning.tp_progress * 100.0
+        );
+        Self::notify(&self.webhooks, WebhookEvent::PositionAging {
+            time_ms: epoch_ms(now),
+            account: account.name.clone(),
+            symbol: warning.symbol,
+            side: warning.side,
+            age_ms: warning.age_ms,
+            max_hold_time_ms: self.max_hold_time_ms,
+            tp_progress: warning.tp_progress,
+        });
+    }
+
+    /// Why the position should be exited now, if it should
+    fn exit_reason(&self, position: &Position, current_price: Decimal, imbalance_z: Option<f64>) -> Option<ExitReason> {
         // Take profit hit
-        if position.take_profit_hit(current_price, self.take_profit_bps) {
+        let take_profit_bps = position.take_profit_bps
+            .unwrap_or_else(|| self.take_profit.base_bps());
+        if position.take_profit_hit(current_price, take_profit_bps) {
             info!("Take profit hit for {}", position.symbol);
-            return true;
+            return Some(ExitReason::TakeProfit);
         }
 
         // Stop loss hit
         if position.stop_loss_hit(current_price, self.stop_loss_bps) {
             info!("Stop loss hit for {}", position.symbol);
-            return true;
+            return Some(ExitReason::StopLoss);
         }
 
         // Time-based exit
         if position.is_expired(self.max_hold_time_ms) {
             info!("Position expired for {}", position.symbol);
-            return true;
+            return Some(ExitReason::MaxHoldTime);
+        }
+
+        // The edge was the imbalance; once it has reverted there is no reason to hold
+        if let (Some(exit_z), Some(z_score)) = (self.signal_decay_exit_z, imbalance_z) {
+            if position.signal_decayed(z_score, exit_z) {
+                info!("Signal decayed for {} (z-score {:.2})", position.symbol, z_score);
+                return Some(ExitReason::SignalDecay);
+            }
         }
 
-        false
+        None
     }
 
-    /// Close a position
-    async fn close_position(&mut self, symbol: &str, current_price: Decimal) -> Result<Decimal> {
-        let position = self.position_manager.get_position(symbol)
+    /// Rest a limit close at the touch if `reason` may wait
+    ///
+    /// Returns false (nothing placed) without a limit window for `reason`,
+    /// a known `touch` (best bid, best ask) or a whole lot to rest. The
+    /// order is then advanced by later exit checks, never waited on here.
+    async fn rest_exit(
+        &mut self,
+        account_idx: usize,
+        symbol: &str,
+        touch: Option<(Decimal, Decimal)>,
+        reason: ExitReason,
+    ) -> Result<bool> {
+        let (Some(window), Some((best_bid, best_ask))) = (self.exit_orders.limit_window(reason), touch) else {
+            return Ok(false);
+        };
+        let Some(position) = self.accounts[account_idx].position_manager.get_position(symbol) else {
+            return Ok(false);
+        };
+        let (limit_qty, _) = split_close(&self.instrument, position.quantity);
+        if limit_qty <= Decimal::ZERO {
+            return Ok(false);
+        }
+        let close_side = position.side.opposite();
+        let decision_time = SystemTime::now();
+
+        // Pull any of our resting orders the close would trade against
+        self.cancel_crossing_orders(close_side, None).await?;
+
+        let account = &mut self.accounts[account_idx];
+        let price = passive_exit_price(close_side, best_bid, best_ask);
+        let placed = account.client.place_limit_order(symbol, close_side, price, limit_qty).await?;
+        info!(
+            "Resting {:?} exit [{}]: {:?} {} {} @ {} for up to {:?}",
+            reason, account.name, close_side, limit_qty, symbol, price, window
+        );
+        account.resting_exits.insert(
+            symbol.to_string(),
+            RestingExit::new(placed.order_id, close_side, price, limit_qty, reason, window, decision_time),
+        );
+        Ok(true)
+    }
+
+    /// Move a resting limit close along without waiting on it
+    ///
+    /// Polls the order at most once per `poll_ms`. The position is closed
+    /// once the order fills, its window runs out, or `reason` (the exit
+    /// condition now, if any) must not wait as long, e.g. a stop loss hit
+    /// under a resting take profit.
+    async fn advance_resting_exit(
+        &mut self,
+        account_idx: usize,
+        symbol: &str,
+        current_price: Decimal,
+        reason: Option<ExitReason>,
+    ) -> Result<()> {
+        let now = Instant::now();
+        let poll_interval = self.exit_orders.poll_interval();
+        let account = &mut self.accounts[account_idx];
+        let Some(exit) = account.resting_exits.get_mut(symbol) else {
+            return Ok(());
+        };
+        if let Some(reason) = reason {
+            exit.escalate(reason, &self.exit_orders);
+        }
+
+        // Already settled by a close that did not complete: finish it
+        if exit.filled.is_none() && !exit.expired(now) {
+            if !exit.poll_due(now, poll_interval) {
+                return Ok(());
+            }
+            let order = account.client.get_order(symbol, exit.order_id).await?;
+            if order.status != "FILLED" {
+                return Ok(());
+            }
+            exit.filled = Some(Self::limit_fill(&order, exit.price)?);
+        }
+
+        let reason = exit.reason;
+        self.close_position(account_idx, symbol, current_price, reason).await?;
+        Ok(())
+    }
+
+    /// Protect a new position with exchange-side take profit and stop loss
+    /// triggers, tracked as an OCO pair
+    ///
+    /// If either leg can't be placed the other is cancelled and the
+    /// position is left to the local exit checks.
+    async fn place_oco_exit(&mut self, account_idx: usize, take_profit_bps: Decimal) {
+        let symbol = self.symbol.clone();
+        let account = &mut self.accounts[account_idx];
+        let Some(position) = account.position_manager.get_position(&symbol) else {
+            return;
+        };
+        let (close_side, quantity) = (position.side.opposite(), position.quantity);
+        let take_profit_price = self.instrument.round_price(
+            trigger_price(position.side, position.entry_price, take_profit_bps),
+        );
+        let stop_loss_price = self.instrument.round_price(
+            trigger_price(position.side, position.entry_price, -self.stop_loss_bps),
+        );
+
+        let take_profit = match account.client
+            .place_exit_trigger(&symbol, close_side, OcoLeg::TakeProfit, take_profit_price, quantity).await
+        {
+            Ok(order) => order,
+            Err(e) => {
+                warn!("Take profit trigger not placed [{}]: {}; exits stay local", account.name, e);
+                return;
+            }
+        };
+        let stop_loss = match account.client
+            .place_exit_trigger(&symbol, close_side, OcoLeg::StopLoss, stop_loss_price, quantity).await
+        {
+            Ok(order) => order,
+            Err(e) => {
+                warn!("Stop loss trigger not placed [{}]: {}; exits stay local", account.name, e);
+                if let Err(e) = account.client.cancel_order(&symbol, take_profit.order_id).await {
+                    error!("Failed to cancel take profit trigger {} [{}]: {}", take_profit.order_id, account.name, e);
+                }
+                return;
+            }
+        };
+
+        let pair = OcoPair {
+            symbol: symbol.clone(),
+            side: close_side,
+            quantity,
+            take_profit_id: take_profit.order_id,
+            stop_loss_id: stop_loss.order_id,
+        };
+        info!(
+            "OCO exit [{}]: {:?} {} {} | TP {} @ {} | SL {} @ {}",
+            account.name, close_side, quantity, symbol,
+            pair.take_profit_id, take_profit_price, pair.stop_loss_id, stop_loss_price
+        );
+        account.oco.register(pair.clone());
+        account.oco_exits.insert(symbol, OcoExit::new(pair, take_profit_price, stop_loss_price));
+    }
+
+    /// Poll a position's OCO legs without waiting on them
+    ///
+    /// At most once per `poll_ms`. A fill on either leg cancels the other;
+    /// once one leg has closed the whole position it is booked as a take
+    /// profit or stop loss.
+    async fn advance_oco_exit(&mut self, account_idx: usize, symbol: &str, current_price: Decimal) -> Result<()> {
+        let poll_interval = self.exit_orders.poll_interval();
+        let account = &mut self.accounts[account_idx];
+        let Some(exit) = account.oco_exits.get_mut(symbol) else {
+            return Ok(());
+        };
+        if !exit.poll_due(Instant::now(), poll_interval) {
+            return Ok(());
+        }
+
+        for leg in [OcoLeg::TakeProfit, OcoLeg::StopLoss] {
+            let order_id = exit.pair.order_id(leg);
+            if !account.oco.is_working(order_id) {
+                continue;
+            }
+            let order = account.client.get_order(symbol, order_id).await?;
+            let actions = Self::record_oco_order(&mut account.oco, exit, leg, &order)?;
+            Self::apply_oco_actions(
+                &account.client, account.order_fills.as_ref(), self.fill_timeout, &mut account.oco, exit, actions,
+            ).await?;
+        }
+
+        let reason = match exit.closing_leg() {
+            Some(OcoLeg::TakeProfit) => ExitReason::TakeProfit,
+            Some(OcoLeg::StopLoss) => ExitReason::StopLoss,
+            None => return Ok(()),
+        };
+        self.close_position(account_idx, symbol, current_price, reason).await?;
+        Ok(())
+    }
+
+    /// Cancel whichever OCO legs are still working and return what the
+    /// pair closed of the position (quantity, average price)
+    async fn settle_oco_exit(
+        client: &E,
+        order_fills: Option<&OrderFills>,
+        fill_timeout: Duration,
+        tracker: &mut OcoTracker,
+        exit: &mut OcoExit,
+    ) -> Result<(Decimal, Decimal)> {
+        let actions = [OcoLeg::TakeProfit, OcoLeg::StopLoss].into_iter()
+            .filter(|leg| tracker.is_working(exit.pair.order_id(*leg)))
+            .map(|leg| OcoAction::Cancel {
+                symbol: exit.pair.symbol.clone(),
+                order_id: exit.pair.order_id(leg),
+                leg,
+            })
+            .collect();
+        Self::apply_oco_actions(client, order_fills, fill_timeout, tracker, exit, actions).await?;
+        Ok(exit.position_fill())
+    }
+
+    /// Send what the OCO tracker asked for: cancel the other leg, or
+    /// flatten what both legs together exited beyond the position
+    ///
+    /// A cancel that loses the race to a fill brings that fill back
+    /// through the tracker, which may ask for a flatten in turn. Flatten
+    /// fills are kept on the exit and booked when the position closes.
+    async fn apply_oco_actions(
+        client: &E,
+        order_fills: Option<&OrderFills>,
+        fill_timeout: Duration,
+        tracker: &mut OcoTracker,
+        exit: &mut OcoExit,
+        mut actions: Vec<OcoAction>,
+    ) -> Result<()> {
+        while !actions.is_empty() {
+            match actions.remove(0) {
+                OcoAction::Cancel { symbol, order_id, leg } => {
+                    if !tracker.is_working(order_id) {
+                        continue;
+                    }
+                    let order = match client.cancel_order(&symbol, order_id).await {
+                        Ok(cancelled) => cancelled,
+                        Err(e) => {
+                            warn!("Cancel of {:?} leg {} failed ({}); re-reading it", leg, order_id, e);
+                            client.get_order(&symbol, order_id).await?
+                        }
+                    };
+                    actions.extend(Self::record_oco_order(tracker, exit, leg, &order)?);
+                }
+                OcoAction::Reconcile { symbol, side, quantity } => {
+                    warn!("Both OCO legs of {} filled; flattening {} with a reduce-only {:?}", symbol, quantity, side);
+                    let response = client.place_reduce_only_order(&symbol, side, quantity).await?;
+                    let (price, filled) = Self::market_fill(order_fills, fill_timeout, &response).await?;
+                    exit.record_flatten(filled, price);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fold an OCO leg's order state into the exit and the tracker
+    fn record_oco_order(
+        tracker: &mut OcoTracker,
+        exit: &mut OcoExit,
+        leg: OcoLeg,
+        order: &OrderResponse,
+    ) -> Result<Vec<OcoAction>> {
+        let (filled, avg_price) = Self::limit_fill(order, exit.trigger_price(leg))?;
+        exit.record_fill(leg, filled, avg_price);
+        let actions = if filled > Decimal::ZERO {
+            tracker.on_fill(order.order_id, filled)
+        } else {
+            Vec::new()
+        };
+        if matches!(order.status.as_str(), "CANCELED" | "EXPIRED" | "REJECTED") {
+            tracker.on_closed(order.order_id);
+        }
+        Ok(actions)
+    }
+
+    /// Close a position on the given account
+    ///
+    /// A limit close already resting for it (`rest_exit`) is cancelled
+    /// unless seen filled, and only the unfilled remainder goes to market.
+    /// Likewise for exchange-side TP/SL legs: those still working are
+    /// cancelled and what they filled counts toward the close.
+    async fn close_position(
+        &mut self,
+        account_idx: usize,
+        symbol: &str,
+        current_price: Decimal,
+        reason: ExitReason,
+    ) -> Result<Decimal> {
+        let (maker_fee_rate, taker_fee_rate) = (self.maker_fee_rate, self.taker_fee_rate);
+        let (instrument, quote_usd) = (self.instrument, self.quote_usd);
+        let (fill_timeout, min_order_notional) = (self.fill_timeout, self.min_order_notional);
+
+        // Pull any of our resting orders the close would trade against
+        let close_side = self.accounts[account_idx].position_manager.get_position(symbol)
+            .map(|position| position.side.opposite());
+        if let Some(side) = close_side {
+            self.cancel_crossing_orders(side, None).await?;
+        }
+
+        let account = &mut self.accounts[account_idx];
+
+        let position = account.position_manager.get_position(symbol)
             .ok_or_else(|| anyhow!("Position not found: {}", symbol))?;
 
         info!(
-            "Closing position: {} | Entry: {} | Current: {} | Qty: {}",
-            symbol, position.entry_price, current_price, position.quantity
+            "Closing position [{}]: {} ({:?}) | Entry: {} | Current: {} | Qty: {}",
+            account.name, symbol, reason, position.entry_price, current_price, position.quantity
         );
 
         // Determine close side (opposite of entry)
         let close_side = position.side.opposite();
 
-        // Place market order to close
-        let order_response = self.client
-            .place_market_order(symbol, close_side, position.quantity)
-            .await?;
+        let mut decision_time = SystemTime::now();
+        let mut order_time = Instant::now();
+        let mut fill = ExitFill::default();
 
-        // Parse execution price
-        let exit_price = order_response.price.parse::<Decimal>()?;
-        let exit_qty = order_response.executed_qty.parse::<Decimal>()?;
+        // Whatever the resting limit got, timed from when it was placed.
+        // It stays tracked until the close is booked: if the cancel fails
+        // the order may still be live, and once cancelled what it filled
+        // must not be sent to market again by a retried close
+        if let Some(exit) = account.resting_exits.get_mut(symbol) {
+            let filled = match exit.filled {
+                Some(filled) => filled,
+                None => Self::cancel_resting_exit(&account.client, symbol, exit).await?,
+            };
+            exit.filled = Some(filled);
+            (fill.limit_qty, fill.limit_price) = filled;
+            info!("Limit exit {} filled {} of {} @ {}", exit.order_id, fill.limit_qty, exit.quantity, fill.limit_price);
+            decision_time = exit.decision_time;
+            order_time = exit.placed_at;
+        }
+        // The pair stays tracked until the close is booked, so a cancel
+        // that fails is retried by the next close instead of leaving a
+        // working leg behind
+        if let Some(exit) = account.oco_exits.get_mut(symbol) {
+            (fill.trigger_qty, fill.trigger_price) = Self::settle_oco_exit(
+                &account.client, account.order_fills.as_ref(), fill_timeout, &mut account.oco, exit,
+            ).await?;
+            if fill.trigger_qty > Decimal::ZERO {
+                info!("OCO legs closed {} of {} @ {}", fill.trigger_qty, exit.pair.quantity, fill.trigger_price);
+            }
+        }
+
+        // Market order for whatever is left, on the lot grid; a sliver
+        // below one lot stays on the exchange as dust
+        let (remaining, residual) = split_close(&instrument, position.quantity - fill.limit_qty - fill.trigger_qty);
+        if remaining > Decimal::ZERO {
+            // A plain order below the minimum notional would be rejected
+            let remaining_usd = instrument.notional(remaining, current_price)
+                * instrument.settlement_usd(current_price, quote_usd);
+            let order_response = if remaining_usd < min_order_notional {
+                account.client.place_reduce_only_order(symbol, close_side, remaining).await?
+            } else {
+                account.client.place_market_order(symbol, close_side, remaining).await?
+            };
+            (fill.market_price, fill.market_qty) = Self::market_fill(
+                account.order_fills.as_ref(), fill_timeout, &order_response,
+            ).await?;
+        }
+        if residual > Decimal::ZERO {
+            account.dust.add(position.side, residual);
+            warn!(
+                "{} {} below the lot size left open [{}]; tracked as dust ({} net)",
+                residual, symbol, account.name, account.dust.net()
+            );
+        }
+        let latency_ms = order_time.elapsed().as_millis() as u64;
 
-        // Calculate exit fees
-        let exit_fees = exit_price * exit_qty * self.taker_fee_rate;
+        // Nothing tradeable (all dust): mark the close at the current price
+        let exit_qty = fill.quantity();
+        let exit_price = if exit_qty.is_zero() { current_price } else { fill.average_price() };
+        let exit_fees = fill.fees(&instrument, maker_fee_rate, taker_fee_rate);
 
         // Close position and get realized PnL
-        let realized_pnl = self.position_manager.close_position(symbol, exit_price, exit_fees)?;
+        let (position_side, entry_price) = (position.side, position.entry_price);
+        let entry_notional = instrument.notional(position.quantity, position.entry_price);
+        let realized_pnl = account.position_manager.close_position(symbol, exit_price, exit_fees)?;
+        account.resting_exits.remove(symbol);
+        let flattens = account.oco_exits.remove(symbol)
+            .map(|mut exit| (exit.pair.side, exit.take_flattens()));
 
-        // Record trade for risk management
-        self.risk_manager.record_trade(realized_pnl);
+        // Record trade for risk management (in USD)
+        let realized_pnl_usd = realized_pnl * instrument.settlement_usd(exit_price, quote_usd);
+        account.risk_manager.write().record_trade(realized_pnl_usd);
+        self.attribution.record_trade(&std::mem::take(&mut account.entry_sources), realized_pnl_usd);
+        let now = SystemTime::now();
+        account.reentry.record_exit(position_side, exit_price, now);
+        account.aging.close(symbol);
+        self.activity.record_trade(realized_pnl, entry_notional, now);
+        if let Some(warning) = self.activity.check(now) {
+            warn!("⚠️  {}", warning.reason);
+        }
+
+        Self::record_fill(&mut self.journal, &self.webhooks, &self.bus, &mut account.journaled, JournalFill {
+            decision_time_ms: epoch_ms(decision_time),
+            account: account.name.clone(),
+            symbol: symbol.to_string(),
+            leg: JournalLeg::Exit,
+            side: close_side,
+            decision_mid: current_price,
+            executed_price: exit_price,
+            executed_qty: exit_qty,
+            fees: exit_fees,
+            latency_ms,
+            explanation: None,
+            experiment: self.experiment.clone(),
+        });
+        Self::notify(&self.webhooks, WebhookEvent::PositionClosed {
+            time_ms: epoch_ms(SystemTime::now()),
+            account: account.name.clone(),
+            symbol: symbol.to_string(),
+            side: position_side,
+            entry_price,
+            exit_price,
+            quantity: exit_qty,
+            realized_pnl,
+            reason,
+        });
 
         info!(
             "✅ Position closed | Exit: {} | PnL: {} | Fees: {}",
             exit_price, realized_pnl, exit_fees
         );
+        if let Some((side, flattens)) = flattens {
+            for flatten in flattens {
+                self.book_oco_flatten(account_idx, symbol, side, flatten)?;
+            }
+        }
+        self.notify_halt(account_idx);
+        self.debug_audit();
 
         Ok(realized_pnl)
     }
 
+    /// Book what both OCO legs exited beyond a position as a round trip
+    /// of its own: opened on `side` by the leg that filled second and
+    /// closed by the reduce-only flatten, both as taker
+    ///
+    /// Runs after the position itself is closed, so the two never share
+    /// the symbol in the position manager.
+    fn book_oco_flatten(&mut self, account_idx: usize, symbol: &str, side: Side, flatten: OcoFlatten) -> Result<()> {
+        let instrument = self.instrument;
+        let entry_fees = instrument.notional(flatten.quantity, flatten.excess_price) * self.taker_fee_rate;
+        let exit_fees = instrument.notional(flatten.quantity, flatten.flatten_price) * self.taker_fee_rate;
+        let account = &mut self.accounts[account_idx];
+
+        account.position_manager.open_position(
+            Position::new(symbol.to_string(), side, flatten.excess_price, flatten.quantity, entry_fees)
+                .with_instrument(instrument),
+        )?;
+        let realized_pnl = account.position_manager.close_position(symbol, flatten.flatten_price, exit_fees)?;
+        account.risk_manager.write()
+            .record_trade(realized_pnl * instrument.settlement_usd(flatten.flatten_price, self.quote_usd));
+        warn!(
+            "Booked OCO double fill [{}]: {:?} {} {} @ {} flattened @ {} | PnL: {}",
+            account.name, side, flatten.quantity, symbol, flatten.excess_price, flatten.flatten_price, realized_pnl
+        );
+
+        let decision_time_ms = epoch_ms(SystemTime::now());
+        for (leg, side, price, fees) in [
+            (JournalLeg::Entry, side, flatten.excess_price, entry_fees),
+            (JournalLeg::Exit, side.opposite(), flatten.flatten_price, exit_fees),
+        ] {
+            Self::record_fill(&mut self.journal, &self.webhooks, &self.bus, &mut account.journaled, JournalFill {
+                decision_time_ms,
+                account: account.name.clone(),
+                symbol: symbol.to_string(),
+                leg,
+                side,
+                decision_mid: price,
+                executed_price: price,
+                executed_qty: flatten.quantity,
+                fees,
+                latency_ms: 0,
+                explanation: None,
+                experiment: self.experiment.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Close positions below the minimum order notional, and dust that
+    /// has added up to at least one lot, on every account
+    ///
+    /// Meant to run periodically. Returns the number of positions closed
+    /// and sweep orders sent. Dust sweeps are not booked as trades: the
+    /// PnL of a sliver was already booked with the position it came from.
+    pub async fn sweep_dust(&mut self, current_price: Decimal) -> Result<usize> {
+        let settlement_usd = self.instrument.settlement_usd(current_price, self.quote_usd);
+        let mut swept = 0;
+
+        for idx in 0..self.accounts.len() {
+            let small: Vec<String> = self.accounts[idx].position_manager.open_positions().iter()
+                .filter(|position| {
+                    self.instrument.notional(position.quantity, current_price) * settlement_usd < self.min_order_notional
+                })
+                .map(|position| position.symbol.clone())
+                .collect();
+            for symbol in small {
+                info!("🧹 Closing {} position below the minimum notional [{}]", symbol, self.accounts[idx].name);
+                self.close_position(idx, &symbol, current_price, ExitReason::Dust).await?;
+                swept += 1;
+            }
+
+            let account = &mut self.accounts[idx];
+            let Some((side, quantity)) = account.dust.sweep_order(&self.instrument) else {
+                continue;
+            };
+            let response = account.client.place_reduce_only_order(&self.symbol, side, quantity).await?;
+            let (_, filled) = Self::market_fill(account.order_fills.as_ref(), self.fill_timeout, &response).await?;
+            account.dust.record_swept(side, filled);
+            info!(
+                "🧹 Swept {} {} of dust [{}]; {} left below one lot",
+                filled, self.symbol, account.name, account.dust.net()
+            );
+            swept += 1;
+        }
+
+        Ok(swept)
+    }
+
+    /// Average price and quantity a market order filled at
+    ///
+    /// Taken from the order's final user data update when a stream is
+    /// attached and reports it within `timeout`; otherwise from the
+    /// response, preferring its average price over the order price.
+    async fn market_fill(
+        order_fills: Option<&OrderFills>,
+        timeout: Duration,
+        response: &OrderResponse,
+    ) -> Result<(Decimal, Decimal)> {
+        if let Some(fills) = order_fills {
+            match fills.wait_final(response.order_id, timeout).await {
+                Some(fill) => return Ok((fill.avg_price, fill.filled_qty)),
+                None => warn!(
+                    "No final update for order {} within {:?}; using the REST response",
+                    response.order_id, timeout
+                ),
+            }
+        }
+
+        let price = match response.avg_price.parse::<Decimal>() {
+            Ok(avg_price) if !avg_price.is_zero() => avg_price,
+            _ => response.price.parse::<Decimal>()
+                .map_err(|e| anyhow!("Failed to parse price: {}", e))?,
+        };
+        let quantity = response.executed_qty.parse::<Decimal>()
+            .map_err(|e| anyhow!("Failed to parse quantity: {}", e))?;
+        Ok((price, quantity))
+    }
+
+    /// Cancel a resting limit close and return what it filled
+    ///
+    /// A cancel that loses the race to a fill is resolved by re-reading
+    /// the order.
+    async fn cancel_resting_exit(client: &E, symbol: &str, exit: &RestingExit) -> Result<(Decimal, Decimal)> {
+        let order = match client.cancel_order(symbol, exit.order_id).await {
+            Ok(cancelled) => cancelled,
+            Err(e) => {
+                warn!("Cancel of limit exit {} failed ({}); re-reading it", exit.order_id, e);
+                client.get_order(symbol, exit.order_id).await?
+            }
+        };
+        Self::limit_fill(&order, exit.price)
+    }
+
+    /// Quantity a limit order filled and its average price (`price` if
+    /// the order does not report one)
+    fn limit_fill(order: &OrderResponse, price: Decimal) -> Result<(Decimal, Decimal)> {
+        let filled = order.executed_qty.parse::<Decimal>()?;
+        let avg_price = order.avg_price.parse::<Decimal>().ok()
+            .filter(|avg| !avg.is_zero())
+            .unwrap_or(price);
+        Ok((filled, avg_price))
+    }
+
+    /// Place a resting limit order on a named account, tracked for
+    /// self-trade prevention
+    ///
+    /// Our opposite-side orders it would cross are cancelled first.
+    pub async fn place_resting_order_for(
+        &mut self,
+        account_name: &str,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Result<u64> {
+        let idx = self.account_index(account_name)
+            .ok_or_else(|| anyhow!("Unknown account: {}", account_name))?;
+
+        let price = self.instrument.round_price(price);
+        let quantity = self.instrument.round_quantity(quantity);
+        self.cancel_crossing_orders(side, Some(price)).await?;
+
+        let account = &self.accounts[idx];
+        let response = account.client
+            .place_limit_order(&self.symbol, side, price, quantity)
+            .await?;
+        self.self_trade.register(RestingOrder {
+            order_id: response.order_id,
+            account: account.name.clone(),
+            symbol: self.symbol.clone(),
+            side,
+            price,
+            quantity,
+        });
+        Ok(response.order_id)
+    }
+
+    /// Stop tracking a resting order that filled or was cancelled elsewhere
+    pub fn resting_order_closed(&mut self, order_id: u64) {
+        self.self_trade.remove(order_id);
+    }
+
+    /// Our resting orders across all accounts
+    pub fn resting_orders(&self) -> &[RestingOrder] {
+        self.self_trade.resting_orders()
+    }
+
+    /// Resting orders cancelled so far to avoid trading with ourselves
+    pub fn self_trades_prevented(&self) -> u64 {
+        self.self_trade.prevented()
+    }
+
+    /// Cancel our resting orders an aggressive `side` order could hit
+    ///
+    /// Fails (and the aggressive order must not be sent) if any cancel fails.
+    async fn cancel_crossing_orders(&mut self, side: Side, limit: Option<Decimal>) -> Result<()> {
+        for order in self.self_trade.crossing(&self.symbol, side, limit) {
+            let idx = self.account_index(&order.account)
+                .ok_or_else(|| anyhow!("Resting order {} on unknown account {}", order.order_id, order.account))?;
+
+            warn!(
+                "Cancelling resting {:?} {} @ {} [{}] before crossing it",
+                order.side, order.order_id, order.price, order.account
+            );
+            self.accounts[idx].client.cancel_order(&order.symbol, order.order_id).await
+                .map_err(|e| anyhow!("Self-trade guard could not cancel order {}: {}", order.order_id, e))?;
+            self.self_trade.record_prevented(order.order_id);
+        }
+        Ok(())
+    }
+
+    /// Send a fill to webhooks, the event bus and the trade journal
+    ///
+    /// Journal failures are logged, never allowed to fail the trade
+    fn record_fill(
+        journal: &mut Option<JsonlWriter>,
+        webhooks: &Option<WebhookNotifier>,
+        bus: &Option<BusPublisher>,
+        tally: &mut LedgerTally,
+        fill: JournalFill,
+    ) {
+        Self::notify(webhooks, WebhookEvent::Fill(fill.clone()));
+        if let Some(bus) = bus {
+            bus.publish(BusMessage::fill(&fill));
+        }
+        if let Some(journal) = journal.as_mut() {
+            match journal.append(&fill).and_then(|_| journal.flush()) {
+                Ok(()) => tally.record(&fill),
+                Err(e) => warn!("Failed to journal {:?} fill: {}", fill.leg, e),
+            }
+        }
+    }
+
+    fn notify(webhooks: &Option<WebhookNotifier>, event: WebhookEvent) {
+        if let Some(webhooks) = webhooks {
+            webhooks.notify(event);
+        }
+    }
+
+    /// Send a halt webhook the first time an account is seen halted
+    fn notify_halt(&mut self, idx: usize) {
+        let account = &mut self.accounts[idx];
+        let halted = account.risk_manager.is_halted();
+        if halted && !account.halt_notified {
+            Self::notify(&self.webhooks, WebhookEvent::Halt {
+                time_ms: epoch_ms(SystemTime::now()),
+                account: account.name.clone(),
+                reason: account.risk_manager.halt_reason().unwrap_or_else(|| "Unknown".to_string()),
+            });
+        }
+        account.halt_notified = halted;
+    }
+
+    /// Check every account's positions, risk equity and journal agree
+    pub fn audit_ledger(&self) -> Vec<LedgerReport> {
+        self.accounts.iter()
+            .map(|account| audit_ledger(
+                &account.name,
+                &account.position_manager,
+                &account.risk_manager.read(),
+                self.journal.as_ref().map(|_| &account.journaled),
+            ))
+            .collect()
+    }
+
+    /// Debug builds assert the ledger after every fill
+    fn debug_audit(&self) {
+        if cfg!(debug_assertions) {
+            for report in self.audit_ledger() {
+                debug_assert!(report.is_consistent(), "Ledger discrepancy: {:?}", report);
+            }
+        }
+    }
+
     /// Calculate position size based on signal confidence
-    fn calculate_position_size(&self, confidence: f64) -> Decimal {
+    fn calculate_position_size(&self, base_position_size: Decimal, confidence: f64) -> Decimal {
         // Scale position size: 0.5x to 2.0x based on confidence (0.0 to 1.0)
         let confidence_decimal = Decimal::from_f64_retain(confidence).unwrap();
         
@@ -221,37 +1553,148 @@ impl ExecutionEngine {
         let multiplier = self.min_size_multiplier 
             + (self.max_size_multiplier - self.min_size_multiplier) * confidence_decimal;
 
-        self.base_position_size * multiplier
+        let size = base_position_size * multiplier;
+        match self.max_notional {
+            Some(max_notional) => size.min(max_notional),
+            None => size,
+        }
     }
 
-    /// Get position manager
+    /// Get position manager (primary account)
     pub fn position_manager(&self) -> &PositionManager {
-        &self.position_manager
+        &self.accounts[0].position_manager
+    }
+
+    /// Get position manager for a named account (e.g. to `save` it)
+    pub fn account_position_manager(&self, name: &str) -> Option<&PositionManager> {
+        self.account_index(name).map(|idx| &self.accounts[idx].position_manager)
     }
 
-    /// Get risk manager
-    pub fn risk_manager(&self) -> &RiskManager {
-        &self.risk_manager
+    /// Replace a named account's positions with a restored snapshot
+    ///
+    /// Refused while the account holds positions of its own, which the
+    /// snapshot would otherwise drop without closing them.
+    pub fn restore_positions(&mut self, name: &str, positions: PositionManager) -> Result<()> {
+        let idx = self.account_index(name)
+            .ok_or_else(|| anyhow!("Unknown account: {}", name))?;
+        let account = &mut self.accounts[idx];
+        if account.position_manager.position_count() > 0 {
+            return Err(anyhow!("Account {} already has open positions", name));
+        }
+        account.position_manager = positions;
+        Ok(())
     }
 
-    /// Get mutable risk manager
-    pub fn risk_manager_mut(&mut self) -> &mut RiskManager {
-        &mut self.risk_manager
+    /// Shared risk handle (primary account)
+    pub fn risk_handle(&self) -> RiskHandle {
+        self.accounts[0].risk_manager.clone()
     }
 
-    /// Emergency close all positions
+    /// Shared risk handle for a named account
+    pub fn account_risk_handle(&self, name: &str) -> Option<RiskHandle> {
+        self.account_index(name).map(|idx| self.accounts[idx].risk_manager.clone())
+    }
+
+    /// Every account's shared risk handle, by name (primary first)
+    pub fn risk_handles(&self) -> Vec<(String, RiskHandle)> {
+        self.accounts.iter()
+            .map(|account| (account.name.clone(), account.risk_manager.clone()))
+            .collect()
+    }
+
+    /// Update the USD value of one unit of the quote asset
+    ///
+    /// Needed for linear symbols quoted in a non-USD asset: the mark price
+    /// of e.g. BTCUSDT for an ETHBTC position. Sizing, exposure and PnL fed
+    /// to the risk managers are converted at the latest rate.
+    pub fn set_quote_usd(&mut self, quote_usd: Decimal) {
+        self.quote_usd = quote_usd;
+    }
+
+    /// Feed market data staleness into every account's latency breaker
+    ///
+    /// Order round trips only measure latency when we trade; a lagging
+    /// feed means signals are computed on an old book even when idle.
+    pub fn record_feed_latency(&mut self, latency_ms: u64) {
+        for idx in 0..self.accounts.len() {
+            self.accounts[idx].risk_manager.write().record_latency(latency_ms);
+            self.notify_halt(idx);
+        }
+    }
+
+    /// Read every account's equity from the exchange for equity sizing
+    ///
+    /// Until the first successful read (or if one fails) an account is
+    /// sized from its risk manager's marked equity.
+    pub async fn refresh_equity(&mut self) {
+        for account in &mut self.accounts {
+            match account.client.account_equity().await {
+                Ok(Some(equity)) => account.equity = Some(equity),
+                Ok(None) => warn!("No equity in account info [{}]", account.name),
+                Err(e) => warn!("Failed to refresh equity [{}]: {}", account.name, e),
+            }
+        }
+    }
+
+    /// Set a named account's equity, read from the exchange elsewhere
+    pub fn set_account_equity(&mut self, name: &str, equity: Decimal) -> Result<()> {
+        let idx = self.account_index(name)
+            .ok_or_else(|| anyhow!("Unknown account: {}", name))?;
+        self.accounts[idx].equity = Some(equity);
+        Ok(())
+    }
+
+    /// Every account's exchange client, by name (primary first)
+    ///
+    /// For account reads (equity, income) that should not wait on, or
+    /// hold up, the task that owns the engine.
+    pub fn account_clients(&self) -> Vec<(String, Arc<E>)> {
+        self.accounts.iter()
+            .map(|account| (account.name.clone(), Arc::clone(&account.client)))
+            .collect()
+    }
+
+    /// PnL and fees every account booked locally, by name, in the form
+    /// reconciliation compares with the exchange
+    pub fn local_pnl(&self) -> Vec<(String, LocalPnl)> {
+        self.accounts.iter()
+            .map(|account| {
+                let positions = &account.position_manager;
+                let open_fees: Decimal = positions.open_positions().iter().map(|p| p.fees_paid).sum();
+                (account.name.clone(), LocalPnl {
+                    // Local realized PnL is net of entry and exit fees
+                    realized_pnl: positions.total_realized_pnl() + positions.total_fees(),
+                    fees: positions.total_fees() + open_fees,
+                })
+            })
+            .collect()
+    }
+
+    /// Compare every account's PnL and fees since `since_ms` with its
+    /// exchange income history in `asset`
+    ///
+    /// Accounts whose history cannot be read are logged and left out.
+    pub async fn reconcile(&self, asset: &str, since_ms: u64) -> Vec<ReconciliationReport> {
+        reconcile_accounts(&self.account_clients(), &self.local_pnl(), &self.symbol, asset, since_ms).await
+    }
+
+    /// Emergency close all positions on every account
     pub async fn emergency_close_all(&mut self, current_price: Decimal) -> Result<()> {
         warn!("🚨 EMERGENCY: Closing all positions");
 
-        let positions = self.position_manager.open_positions().to_vec();
+        for idx in 0..self.accounts.len() {
+            let positions = self.accounts[idx].position_manager.open_positions().to_vec();
 
-        for position in positions {
-            match self.close_position(&position.symbol, current_price).await {
-                Ok(pnl) => {
-                    info!("Emergency closed {} with PnL: {}", position.symbol, pnl);
-                }
-                Err(e) => {
-                    error!("Failed to emergency close {}: {}", position.symbol, e);
+            for position in positions {
+                match self.close_position(idx, &position.symbol, current_price, ExitReason::Risk).await {
+                    Ok(pnl) => {
+                        info!("Emergency closed {} [{}] with PnL: {}",
+                            position.symbol, self.accounts[idx].name, pnl);
+                    }
+                    Err(e) => {
+                        error!("Failed to emergency close {} [{}]: {}",
+                            position.symbol, self.accounts[idx].name, e);
+                    }
                 }
             }
         }
@@ -259,22 +1702,133 @@ impl ExecutionEngine {
         Ok(())
     }
 
-    /// Get trading statistics
+    /// Count a signal rejected before any account saw it
+    pub fn record_rejection(&mut self, reason: RejectionReason) {
+        self.rejections.record(reason);
+    }
+
+    /// Count a signal a named account did not trade (e.g. halted)
+    pub fn record_account_rejection(&mut self, name: &str, reason: RejectionReason) {
+        if let Some(idx) = self.account_index(name) {
+            self.accounts[idx].rejections.record(reason);
+        }
+    }
+
+    /// Count a tradeable composite signal for frequency analytics
+    pub fn record_signal(&mut self) {
+        self.activity.record_signal(SystemTime::now());
+    }
+
+    /// Track a detector's signal for its hit rate, traded or not
+    pub fn record_detector_signal(&mut self, detector: &str, direction: Side, mid: Decimal, now: SystemTime) {
+        self.hit_rates.record_signal(detector, direction, mid, now);
+    }
+
+    /// Resolve detector signals against the current mid
+    pub fn score_detectors(&mut self, mid: Decimal, now: SystemTime) {
+        self.hit_rates.record_mid(mid, now);
+    }
+
+    /// Rolling hit rate per detector
+    pub fn detector_hit_rates(&self) -> Vec<DetectorHitRate> {
+        self.hit_rates.stats()
+    }
+
+    /// Realized PnL of recent trades per contributing detector (USD)
+    pub fn detector_pnl(&self) -> Vec<DetectorPnl> {
+        self.attribution.stats()
+    }
+
+    /// Start `detector`'s hit rate and attributed PnL over, e.g. once an
+    /// operator switches it back on after the policy disabled it
+    pub fn reset_detector(&mut self, detector: &str) {
+        self.hit_rates.reset(detector);
+        self.attribution.reset(detector);
+    }
+
+    /// Send a detector-disabled alert to the webhooks
+    pub fn alert_detector_disabled(&self, detector: &str, reason: &str) {
+        Self::notify(&self.webhooks, WebhookEvent::DetectorDisabled {
+            time_ms: epoch_ms(SystemTime::now()),
+            detector: detector.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Append every account's risk metrics to its history if the sampling
+    /// interval has passed; called on the evaluation tick, not every exit check
+    pub fn sample_risk_metrics(&self, now: SystemTime) {
+        for account in &self.accounts {
+            account.risk_manager.write().sample_metrics_at(now);
+        }
+    }
+
+    /// A named account's risk metrics history at or after `since`, oldest
+    /// first (one sample per minute, see `sample_risk_metrics`)
+    pub fn risk_history(&self, name: &str, since: SystemTime) -> Option<Vec<RiskMetricsSample>> {
+        self.account_index(name)
+            .map(|idx| self.accounts[idx].risk_manager.read().metrics_history_since(since))
+    }
+
+    /// Get trading statistics (primary account)
     pub fn get_stats(&self) -> TradingStats {
+        self.account_stats_for(&self.accounts[0])
+    }
+
+    /// Get trading statistics for a named account
+    pub fn account_stats(&self, name: &str) -> Option<TradingStats> {
+        self.account_index(name).map(|idx| self.account_stats_for(&self.accounts[idx]))
+    }
+
+    /// Timestamped statistics snapshot (primary account)
+    pub fn stats_snapshot(&self) -> StatsSnapshot {
+        self.snapshot_for(&self.accounts[0])
+    }
+
+    /// Timestamped statistics snapshots for every account
+    pub fn stats_snapshots(&self) -> Vec<StatsSnapshot> {
+        self.accounts.iter().map(|a| self.snapshot_for(a)).collect()
+    }
+
+    fn snapshot_for(&self, account: &TradingAccount<E>) -> StatsSnapshot {
+        StatsSnapshot {
+            timestamp_ms: epoch_ms(SystemTime::now()),
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            account: account.name.clone(),
+            symbol: self.symbol.clone(),
+            stats: self.account_stats_for(account),
+            experiment: self.experiment.clone(),
+        }
+    }
+
+    fn account_stats_for(&self, account: &TradingAccount<E>) -> TradingStats {
+        let position_manager = &account.position_manager;
+
         TradingStats {
-            open_positions: self.position_manager.position_count(),
-            closed_trades: self.position_manager.closed_positions().len(),
-            total_realized_pnl: self.position_manager.total_realized_pnl(),
-            total_fees: self.position_manager.total_fees(),
-            win_rate: self.position_manager.win_rate(),
-            average_trade_pnl: self.position_manager.average_trade_pnl(),
-            risk_metrics: self.risk_manager.get_metrics(),
+            open_positions: position_manager.position_count(),
+            closed_trades: position_manager.closed_positions().len(),
+            total_realized_pnl: position_manager.total_realized_pnl(),
+            total_fees: position_manager.total_fees(),
+            win_rate: position_manager.win_rate(),
+            average_trade_pnl: position_manager.average_trade_pnl(),
+            risk_metrics: account.risk_manager.metrics(),
+            rejections: self.rejections.merged(&account.rejections),
+            activity: self.activity.stats(SystemTime::now()),
+            detector_hit_rates: self.hit_rates.stats(),
+            detector_pnl: self.attribution.stats(),
+            age_warnings: account.aging.stats(),
         }
     }
 }
 
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
 /// Trading statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingStats {
     pub open_positions: usize,
     pub closed_trades: usize,
@@ -283,12 +1837,130 @@ pub struct TradingStats {
     pub win_rate: f64,
     pub average_trade_pnl: Decimal,
     pub risk_metrics: crate::risk::RiskMetrics,
+    /// Signals not traded, by gate (shared signal-stage plus this account's)
+    #[serde(default)]
+    pub rejections: RejectionCounts,
+    /// Signal/trade frequency and edge, shared by all accounts
+    #[serde(default)]
+    pub activity: ActivityStats,
+    /// Rolling hit rate per detector, shared by all accounts
+    #[serde(default)]
+    pub detector_hit_rates: Vec<DetectorHitRate>,
+    /// Recent realized PnL per contributing detector, shared by all accounts
+    #[serde(default)]
+    pub detector_pnl: Vec<DetectorPnl>,
+    /// Stalled-position warnings, this account's
+    #[serde(default)]
+    pub age_warnings: AgeWarningStats,
+}
+
+/// Point-in-time trading statistics, suitable for appending to a time series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    /// Snapshot time in milliseconds since UNIX epoch
+    pub timestamp_ms: u64,
+    /// Seconds since the engine was created
+    pub uptime_secs: u64,
+    pub account: String,
+    pub symbol: String,
+    pub stats: TradingStats,
+    /// Experiment the engine runs under
+    #[serde(default, skip_serializing_if = "Experiment::is_empty")]
+    pub experiment: Experiment,
+}
+
+/// Compare locally booked PnL (`ExecutionEngine::local_pnl`) with each
+/// account's exchange income history for `symbol` since `since_ms`
+///
+/// Works from the clients and figures the engine handed out, so it can run
+/// on a task other than the one trading. Accounts without local figures
+/// or whose history cannot be read are logged and left out.
+pub async fn reconcile_accounts<E: Exchange>(
+    clients: &[(String, Arc<E>)],
+    local: &[(String, LocalPnl)],
+    symbol: &str,
+    asset: &str,
+    since_ms: u64,
+) -> Vec<ReconciliationReport> {
+    let now_ms = epoch_ms(SystemTime::now());
+    let mut reports = Vec::new();
+    for (name, client) in clients {
+        let Some((_, local)) = local.iter().find(|(account, _)| account == name) else {
+            warn!("No local PnL to reconcile [{}]", name);
+            continue;
+        };
+        let records = match client.income_history(symbol, since_ms, now_ms).await {
+            Ok(records) => records,
+            Err(e) => {
+                warn!("Failed to read income history [{}]: {}", name, e);
+                continue;
+            }
+        };
+        reports.push(ReconciliationReport {
+            account: name.clone(),
+            symbol: symbol.to_string(),
+            asset: asset.to_string(),
+            start_ms: since_ms,
+            end_ms: now_ms,
+            local: local.clone(),
+            exchange: IncomeSummary::from_records(&records, symbol, asset),
+        });
+    }
+    reports
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_stale_and_unfit_signals_are_typed_outcomes() {
+        let client = BinanceRestClient::new("test".into(), "test".into(), "https://test".into());
+        let risk_manager = RiskManager::new(crate::risk::RiskLimits::default(), Decimal::from(10000));
+        let mut engine = ExecutionEngine::new(
+            client,
+            risk_manager,
+            "BTCUSDT".into(),
+            Decimal::from(1000),
+            Decimal::from(10),
+            Decimal::from(5),
+            5000,
+        )
+        .with_max_signal_age(Duration::from_secs(1));
+
+        let signal = |age: Duration| {
+            let timestamp = SystemTime::now() - age;
+            CompositeSignal {
+                primary: crate::data::Signal {
+                    strength: 3.5,
+                    direction: Side::Buy,
+                    confidence: 0.8,
+                    timestamp,
+                    components: vec![],
+                },
+                confirming: vec![],
+                overall_strength: 3.5,
+                direction: Side::Buy,
+                confidence: 0.8,
+                timestamp,
+                explanation: Default::default(),
+            }
+        };
+        let book = OrderBook::new("BTCUSDT");
+
+        let outcome = engine.execute_signal(signal(Duration::from_secs(5)), &book).await;
+        assert!(matches!(outcome, ExecutionOutcome::Aborted { latency } if latency >= Duration::from_secs(5)));
+        assert_eq!(outcome.rejection(), Some(RejectionReason::LatencyAborted));
+
+        // Fresh, but no two-sided book: a market quality skip, not an error
+        let outcome = engine.execute_signal(signal(Duration::ZERO), &book).await;
+        assert!(matches!(outcome, ExecutionOutcome::SkippedMarketQuality(MarketQualityViolation::NoMarket)));
+        assert!(!outcome.is_executed());
+
+        let rejections = engine.get_stats().rejections;
+        assert_eq!((rejections.latency_aborted, rejections.thin_book), (1, 1));
+    }
+
     #[test]
     fn test_position_size_calculation() {
         let client = BinanceRestClient::new(
@@ -311,16 +1983,431 @@ mod tests {
             5000,
         );
 
+        let base = Decimal::from(1000);
+
         // Low confidence = 0.5x size
-        let size = engine.calculate_position_size(0.0);
+        let size = engine.calculate_position_size(base, 0.0);
         assert_eq!(size, Decimal::from(500));
 
         // Medium confidence = 1.25x size
-        let size = engine.calculate_position_size(0.5);
+        let size = engine.calculate_position_size(base, 0.5);
         assert_eq!(size, Decimal::from(1250));
 
         // High confidence = 2.0x size
-        let size = engine.calculate_position_size(1.0);
+        let size = engine.calculate_position_size(base, 1.0);
         assert_eq!(size, Decimal::from(2000));
+
+        // First-run safety cap clamps the scaled size
+        let engine = engine.with_max_notional(Decimal::from(750));
+        assert_eq!(engine.calculate_position_size(base, 1.0), Decimal::from(750));
+        assert_eq!(engine.calculate_position_size(base, 0.0), Decimal::from(500));
+    }
+
+    #[test]
+    fn test_sub_accounts_isolated() {
+        let client = |url: &str| BinanceRestClient::new("test".into(), "test".into(), url.into());
+        let risk_manager = || RiskManager::new(
+            crate::risk::RiskLimits::default(),
+            Decimal::from(10000),
+        );
+
+        let mut engine = ExecutionEngine::new(
+            client("https://live"),
+            risk_manager(),
+            "BTCUSDT".into(),
+            Decimal::from(1000),
+            Decimal::from(10),
+            Decimal::from(5),
+            5000,
+        );
+
+        engine.add_account("testnet", client("https://testnet"), risk_manager(), Decimal::from(100))
+            .unwrap();
+
+        // Duplicate names rejected
+        assert!(engine.add_account("testnet", client("https://x"), risk_manager(), Decimal::ONE).is_err());
+        assert_eq!(engine.account_names(), vec!["primary".to_string(), "testnet".to_string()]);
+
+        // Halting one account leaves the other untouched
+        engine.account_risk_handle("testnet").unwrap().halt("probe halted");
+        assert!(engine.account_risk_handle("testnet").unwrap().is_halted());
+        assert!(!engine.risk_handle().is_halted());
+        assert_eq!(engine.account_stats("testnet").unwrap().closed_trades, 0);
+        assert!(engine.account_stats("missing").is_none());
+
+        // One snapshot per account, serializable for the storage layer
+        let snapshots = engine.stats_snapshots();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[1].account, "testnet");
+        assert!(snapshots[1].stats.risk_metrics.trading_halted);
+
+        let json = serde_json::to_string(&engine.stats_snapshot()).unwrap();
+        assert!(json.contains(r#""account":"primary""#));
+        assert!(json.contains("timestamp_ms"));
+    }
+
+    /// In-memory venue: market orders fill at once at `price`, trigger and
+    /// limit orders rest until the test fills them
+    #[derive(Clone, Default)]
+    struct MockExchange {
+        state: Arc<std::sync::Mutex<MockState>>,
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        price: Decimal,
+        next_id: u64,
+        orders: HashMap<u64, OrderResponse>,
+        /// Orders whose trigger fires while their cancel is in flight
+        fill_before_cancel: Vec<u64>,
+        /// Orders the venue can neither cancel nor report on
+        unreachable: Vec<u64>,
+        cancels: Vec<u64>,
+        reduce_only: Vec<(Side, Decimal)>,
+        /// Venue rejects every new market order
+        reject_market: bool,
+    }
+
+    impl MockExchange {
+        fn new(price: Decimal) -> Self {
+            let mock = Self::default();
+            mock.state().price = price;
+            mock
+        }
+
+        fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+            self.state.lock().unwrap_or_else(|e| e.into_inner())
+        }
+
+        fn place(&self, side: Side, order_type: &str, price: Option<Decimal>, quantity: Decimal) -> OrderResponse {
+            let mut state = self.state();
+            state.next_id += 1;
+            let filled = price.is_none();
+            let order = OrderResponse {
+                order_id: state.next_id,
+                symbol: "BTCUSDT".to_string(),
+                client_order_id: String::new(),
+                price: price.unwrap_or(state.price).to_string(),
+                avg_price: if filled { state.price.to_string() } else { "0".to_string() },
+                orig_qty: quantity.to_string(),
+                executed_qty: if filled { quantity.to_string() } else { "0".to_string() },
+                status: if filled { "FILLED" } else { "NEW" }.to_string(),
+                time_in_force: "GTC".to_string(),
+                order_type: order_type.to_string(),
+                side: format!("{:?}", side).to_uppercase(),
+                update_time: 0,
+            };
+            state.orders.insert(order.order_id, order.clone());
+            order
+        }
+
+        /// Fill a resting order in full at its price
+        fn fill(state: &mut MockState, order_id: u64) {
+            let order = state.orders.get_mut(&order_id).unwrap();
+            order.executed_qty = order.orig_qty.clone();
+            order.avg_price = order.price.clone();
+            order.status = "FILLED".to_string();
+        }
+
+        /// (take profit, stop loss) trigger orders
+        fn triggers(&self) -> (OrderResponse, OrderResponse) {
+            let state = self.state();
+            let find = |order_type: &str| {
+                state.orders.values().find(|order| order.order_type == order_type).cloned().unwrap()
+            };
+            (find("TAKE_PROFIT_MARKET"), find("STOP_MARKET"))
+        }
+    }
+
+    impl Exchange for MockExchange {
+        async fn place_market_order(&self, _symbol: &str, side: Side, quantity: Decimal) -> Result<OrderResponse> {
+            if self.state().reject_market {
+                return Err(anyhow!("Order rejected"));
+            }
+            Ok(self.place(side, "MARKET", None, quantity))
+        }
+
+        async fn place_limit_order(&self, _symbol: &str, side: Side, price: Decimal, quantity: Decimal) -> Result<OrderResponse> {
+            Ok(self.place(side, "LIMIT", Some(price), quantity))
+        }
+
+        async fn place_reduce_only_order(&self, _symbol: &str, side: Side, quantity: Decimal) -> Result<OrderResponse> {
+            if self.state().reject_market {
+                return Err(anyhow!("Order rejected"));
+            }
+            self.state().reduce_only.push((side, quantity));
+            Ok(self.place(side, "MARKET", None, quantity))
+        }
+
+        async fn place_exit_trigger(
+            &self,
+            _symbol: &str,
+            side: Side,
+            leg: OcoLeg,
+            stop_price: Decimal,
+            quantity: Decimal,
+        ) -> Result<OrderResponse> {
+            let order_type = match leg {
+                OcoLeg::TakeProfit => "TAKE_PROFIT_MARKET",
+                OcoLeg::StopLoss => "STOP_MARKET",
+            };
+            Ok(self.place(side, order_type, Some(stop_price), quantity))
+        }
+
+        async fn cancel_order(&self, _symbol: &str, order_id: u64) -> Result<OrderResponse> {
+            let mut state = self.state();
+            state.cancels.push(order_id);
+            if state.unreachable.contains(&order_id) {
+                return Err(anyhow!("Timeout waiting for response"));
+            }
+            if state.fill_before_cancel.contains(&order_id) {
+                Self::fill(&mut state, order_id);
+            }
+            let order = state.orders.get_mut(&order_id).ok_or_else(|| anyhow!("Unknown order"))?;
+            if order.status == "FILLED" {
+                return Err(anyhow!("Unknown order sent"));
+            }
+            order.status = "CANCELED".to_string();
+            Ok(order.clone())
+        }
+
+        async fn get_order(&self, _symbol: &str, order_id: u64) -> Result<OrderResponse> {
+            let state = self.state();
+            if state.unreachable.contains(&order_id) {
+                return Err(anyhow!("Timeout waiting for response"));
+            }
+            state.orders.get(&order_id).cloned().ok_or_else(|| anyhow!("Unknown order"))
+        }
+
+        async fn account_equity(&self) -> Result<Option<Decimal>> {
+            Ok(None)
+        }
+
+        async fn income_history(&self, _symbol: &str, _start_ms: u64, _end_ms: u64) -> Result<Vec<crate::exchange::binance::IncomeRecord>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn oco_exit_orders() -> ExitOrderConfig {
+        ExitOrderConfig { exchange_tp_sl: true, poll_ms: 1, ..ExitOrderConfig::default() }
+    }
+
+    /// Engine with exchange-side TP/SL holding a BTCUSDT long from 100
+    async fn oco_engine() -> (ExecutionEngine<MockExchange>, MockExchange, OrderBook) {
+        mock_engine(oco_exit_orders(), None).await
+    }
+
+    /// Engine on the mock venue holding a BTCUSDT long from 100
+    async fn mock_engine(
+        exit_orders: ExitOrderConfig,
+        journal: Option<JsonlWriter>,
+    ) -> (ExecutionEngine<MockExchange>, MockExchange, OrderBook) {
+        let mock = MockExchange::new(Decimal::from(100));
+        let risk_manager = RiskManager::new(crate::risk::RiskLimits::default(), Decimal::from(10000));
+        let mut engine = ExecutionEngine::new(
+            mock.clone(),
+            risk_manager,
+            "BTCUSDT".into(),
+            Decimal::from(1000),
+            Decimal::from(10),
+            Decimal::from(5),
+            60_000,
+        )
+        .with_exit_orders(exit_orders);
+        if let Some(journal) = journal {
+            engine = engine.with_journal(journal);
+        }
+
+        let book = OrderBook::new("BTCUSDT");
+        book.apply_depth(&[(Decimal::new(9999, 2), Decimal::from(100))], &[(Decimal::new(10001, 2), Decimal::from(100))])
+            .unwrap();
+        assert!(engine.execute_signal(buy_signal(), &book).await.is_executed());
+        (engine, mock, book)
+    }
+
+    fn buy_signal() -> CompositeSignal {
+        CompositeSignal {
+            primary: crate::data::Signal {
+                strength: 3.5,
+                direction: Side::Buy,
+                confidence: 1.0,
+                timestamp: SystemTime::now(),
+                components: vec![],
+            },
+            confirming: vec![],
+            overall_strength: 3.5,
+            direction: Side::Buy,
+            confidence: 1.0,
+            timestamp: SystemTime::now(),
+            explanation: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oco_fill_cancels_other_leg() {
+        let (mut engine, mock, book) = oco_engine().await;
+        let (take_profit, stop_loss) = mock.triggers();
+        let quantity = engine.position_manager().open_positions()[0].quantity;
+        assert_eq!(take_profit.side, "SELL");
+        assert_eq!(take_profit.orig_qty.parse::<Decimal>().unwrap(), quantity);
+        assert!(take_profit.price.parse::<Decimal>().unwrap() > Decimal::from(100));
+        assert!(stop_loss.price.parse::<Decimal>().unwrap() < Decimal::from(100));
+
+        // Nothing filled yet: the position stays open, nothing is cancelled
+        engine.check_exits(&book, None).await.unwrap();
+        assert_eq!(engine.position_manager().position_count(), 1);
+
+        MockExchange::fill(&mut mock.state(), take_profit.order_id);
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        engine.check_exits(&book, None).await.unwrap();
+
+        let state = mock.state();
+        assert_eq!(state.cancels, vec![stop_loss.order_id]);
+        assert_eq!(state.orders[&stop_loss.order_id].status, "CANCELED");
+        assert!(state.reduce_only.is_empty());
+        assert_eq!(engine.position_manager().position_count(), 0);
+
+        // Booked at the take profit trigger, with no market close on top
+        let closed = &engine.position_manager().closed_positions()[0];
+        let gross = (take_profit.price.parse::<Decimal>().unwrap() - Decimal::from(100)) * quantity;
+        assert_eq!(closed.realized_pnl + closed.fees_paid, gross);
+        assert_eq!(state.orders.values().filter(|o| o.order_type == "MARKET").count(), 1);
+        assert_eq!(engine.accounts[0].oco.active_pairs().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oco_cancel_loses_race_to_fill() {
+        let (mut engine, mock, book) = oco_engine().await;
+        let (take_profit, stop_loss) = mock.triggers();
+        let quantity = engine.position_manager().open_positions()[0].quantity;
+
+        // The stop fires; the take profit triggers while its cancel is in flight
+        {
+            let mut state = mock.state();
+            MockExchange::fill(&mut state, stop_loss.order_id);
+            state.fill_before_cancel.push(take_profit.order_id);
+        }
+        engine.check_exits(&book, None).await.unwrap();
+
+        let state = mock.state();
+        assert_eq!(state.cancels, vec![take_profit.order_id]);
+        // Both legs sold the position: the extra short is bought back reduce-only
+        assert_eq!(state.reduce_only, vec![(Side::Buy, quantity)]);
+        assert_eq!(engine.position_manager().position_count(), 0);
+
+        // The close is the stop loss, not the leg that filled second
+        let closed = &engine.position_manager().closed_positions()[0];
+        let gross = (stop_loss.price.parse::<Decimal>().unwrap() - Decimal::from(100)) * quantity;
+        assert_eq!(closed.realized_pnl + closed.fees_paid, gross);
+        assert_eq!(engine.accounts[0].oco.active_pairs().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oco_failed_cancel_keeps_pair_tracked() {
+        let (mut engine, mock, _book) = oco_engine().await;
+        let (take_profit, stop_loss) = mock.triggers();
+        mock.state().unreachable.push(take_profit.order_id);
+
+        // The take profit can be neither cancelled nor read: no market
+        // close goes out while it may still fill
+        let close = engine.close_position(0, "BTCUSDT", Decimal::from(100), ExitReason::MaxHoldTime).await;
+        assert!(close.is_err());
+        assert_eq!(engine.position_manager().position_count(), 1);
+        assert!(engine.accounts[0].oco_exits.contains_key("BTCUSDT"));
+        assert_eq!(mock.state().orders.values().filter(|o| o.order_type == "MARKET").count(), 1);
+
+        // Reachable again: the retry cancels both legs and closes at market
+        mock.state().unreachable.clear();
+        engine.close_position(0, "BTCUSDT", Decimal::from(100), ExitReason::MaxHoldTime).await.unwrap();
+
+        let state = mock.state();
+        assert_eq!(state.orders[&take_profit.order_id].status, "CANCELED");
+        assert_eq!(state.orders[&stop_loss.order_id].status, "CANCELED");
+        assert_eq!(state.orders.values().filter(|o| o.order_type == "MARKET").count(), 2);
+        assert_eq!(engine.position_manager().position_count(), 0);
+        assert!(engine.accounts[0].oco_exits.is_empty());
+        assert_eq!(engine.accounts[0].oco.active_pairs().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oco_double_fill_is_booked() {
+        let path = std::env::temp_dir().join(format!("oco_journal_test_{}.jsonl", std::process::id()));
+        let (mut engine, mock, book) = mock_engine(oco_exit_orders(), Some(JsonlWriter::open(&path).unwrap())).await;
+        let (take_profit, stop_loss) = mock.triggers();
+        let quantity = engine.position_manager().open_positions()[0].quantity;
+        let take_profit_price = take_profit.price.parse::<Decimal>().unwrap();
+
+        // Both legs sell the position; the extra short is bought back at 99.5
+        {
+            let mut state = mock.state();
+            MockExchange::fill(&mut state, stop_loss.order_id);
+            state.fill_before_cancel.push(take_profit.order_id);
+            state.price = Decimal::new(995, 1);
+        }
+        engine.check_exits(&book, None).await.unwrap();
+
+        // The position closes at the stop, and the round trip the second
+        // leg and the flatten made is booked on its own
+        let closed = engine.position_manager().closed_positions();
+        assert_eq!(closed.len(), 2);
+        let excess = &closed[1];
+        assert_eq!((excess.side, excess.quantity, excess.entry_price), (Side::Sell, quantity, take_profit_price));
+        assert_eq!(excess.realized_pnl + excess.fees_paid, (take_profit_price - Decimal::new(995, 1)) * quantity);
+
+        let report = &engine.audit_ledger()[0];
+        assert!(report.is_consistent(), "{:?}", report.discrepancies);
+        assert_eq!((engine.accounts[0].journaled.entries, engine.accounts[0].journaled.exits), (2, 2));
+        assert_eq!(
+            engine.risk_handle().read().get_metrics().current_equity,
+            Decimal::from(10000) + engine.position_manager().total_realized_pnl(),
+        );
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_resting_exit_failed_cancel_keeps_order_tracked() {
+        let exit_orders = ExitOrderConfig { take_profit_limit_ms: 60_000, ..ExitOrderConfig::default() };
+        let (mut engine, mock, book) = mock_engine(exit_orders, None).await;
+        let (best_bid, best_ask) = (book.best_bid().unwrap(), book.best_ask().unwrap());
+        let touch = Some((best_bid, best_ask));
+        assert!(engine.rest_exit(0, "BTCUSDT", touch, ExitReason::TakeProfit).await.unwrap());
+        let limit_id = engine.accounts[0].resting_exits["BTCUSDT"].order_id;
+        mock.state().unreachable.push(limit_id);
+
+        // A stop loss comes in while the limit can be neither cancelled nor
+        // read: no market close goes out on top of it
+        let close = engine.close_position(0, "BTCUSDT", Decimal::from(100), ExitReason::StopLoss).await;
+        assert!(close.is_err());
+        assert_eq!(engine.position_manager().position_count(), 1);
+        assert!(engine.accounts[0].resting_exits.contains_key("BTCUSDT"));
+        assert_eq!(mock.state().orders.values().filter(|o| o.order_type == "MARKET").count(), 1);
+
+        // Reachable again: the retry cancels the limit and closes at market
+        mock.state().unreachable.clear();
+        engine.close_position(0, "BTCUSDT", Decimal::from(100), ExitReason::StopLoss).await.unwrap();
+
+        let state = mock.state();
+        assert_eq!(state.orders[&limit_id].status, "CANCELED");
+        assert_eq!(state.orders.values().filter(|o| o.order_type == "MARKET").count(), 2);
+        assert_eq!(engine.position_manager().position_count(), 0);
+        assert!(engine.accounts[0].resting_exits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_exit_does_not_skip_other_accounts() {
+        let (mut engine, primary, book) = mock_engine(ExitOrderConfig::default(), None).await;
+        let probe = MockExchange::new(Decimal::from(100));
+        let risk_manager = RiskManager::new(crate::risk::RiskLimits::default(), Decimal::from(10000));
+        engine.add_account("probe", probe.clone(), risk_manager, Decimal::from(1000)).unwrap();
+        assert!(engine.execute_signal_for("probe", buy_signal(), &book).await.is_executed());
+
+        // Stop loss on both; the primary account's close is rejected
+        primary.state().reject_market = true;
+        let err = engine.check_exits_at_touch(Decimal::from(95), Decimal::new(9501, 2), None).await.unwrap_err();
+        assert!(err.to_string().contains("[primary]"));
+        let positions = |name: &str| engine.account_position_manager(name).unwrap().position_count();
+        assert_eq!(positions("primary"), 1);
+        assert_eq!(positions("probe"), 0);
+        assert_eq!(probe.state().orders.values().filter(|o| o.order_type == "MARKET").count(), 2);
     }
 }
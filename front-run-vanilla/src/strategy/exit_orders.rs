@@ -0,0 +1,243 @@
+use crate::data::Side;
+use crate::risk::Instrument;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How exit orders are worked (`[strategy.exits]`)
+///
+/// Each exit first rests a passive limit at the touch for its window and
+/// sends the unfilled remainder to market once the window expires. A
+/// window of 0 goes straight to market, which is the default for both.
+/// Nothing waits on the resting order: later exit checks poll it and
+/// close the position once it fills or its window runs out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExitOrderConfig {
+    /// Limit window for take profit, max hold and signal decay exits
+    pub take_profit_limit_ms: u64,
+    /// Limit window for stop loss exits
+    pub stop_loss_limit_ms: u64,
+    /// Least time between fill polls of a resting exit or OCO legs
+    pub poll_ms: u64,
+    /// Protect every entry with exchange-side take profit and stop loss
+    /// triggers, worked as an OCO pair; other exits stay local
+    pub exchange_tp_sl: bool,
+}
+
+impl Default for ExitOrderConfig {
+    fn default() -> Self {
+        Self {
+            take_profit_limit_ms: 0,
+            stop_loss_limit_ms: 0,
+            poll_ms: 50,
+            exchange_tp_sl: false,
+        }
+    }
+}
+
+impl ExitOrderConfig {
+    /// How long `reason` may rest at the touch (None = market at once)
+    ///
+    /// Risk-forced closes and dust sweeps always go to market.
+    pub fn limit_window(&self, reason: ExitReason) -> Option<Duration> {
+        let ms = match reason {
+            ExitReason::TakeProfit | ExitReason::MaxHoldTime | ExitReason::SignalDecay => self.take_profit_limit_ms,
+            ExitReason::StopLoss => self.stop_loss_limit_ms,
+            ExitReason::Risk | ExitReason::Dust => 0,
+        };
+        (ms > 0).then(|| Duration::from_millis(ms))
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_ms.max(1))
+    }
+}
+
+/// Why a position is being closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    TakeProfit,
+    StopLoss,
+    MaxHoldTime,
+    SignalDecay,
+    /// Loss cap, drawdown or daily loss breach
+    Risk,
+    /// Position below the exchange minimum notional, closed by a sweep
+    Dust,
+}
+
+/// A limit close resting at the touch, advanced by later exit checks
+#[derive(Debug, Clone)]
+pub struct RestingExit {
+    pub order_id: u64,
+    pub side: Side,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    /// Reason the close was decided for; a more urgent one can replace it
+    pub reason: ExitReason,
+    pub decision_time: SystemTime,
+    pub placed_at: Instant,
+    /// The unfilled remainder goes to market from here
+    pub deadline: Instant,
+    /// Quantity and average price, once the order is seen filled
+    pub filled: Option<(Decimal, Decimal)>,
+    next_poll: Instant,
+}
+
+impl RestingExit {
+    pub fn new(
+        order_id: u64,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        reason: ExitReason,
+        window: Duration,
+        decision_time: SystemTime,
+    ) -> Self {
+        let placed_at = Instant::now();
+        Self {
+            order_id,
+            side,
+            price,
+            quantity,
+            reason,
+            decision_time,
+            placed_at,
+            deadline: placed_at + window,
+            filled: None,
+            next_poll: placed_at,
+        }
+    }
+
+    pub fn expired(&self, now: Instant) -> bool {
+        now >= self.deadline
+    }
+
+    /// Whether the order should be polled at `now`; schedules the next poll
+    pub fn poll_due(&mut self, now: Instant, interval: Duration) -> bool {
+        if now < self.next_poll {
+            return false;
+        }
+        self.next_poll = now + interval;
+        true
+    }
+
+    /// Shorten the wait if `reason` (hit since the order was placed) may
+    /// rest for less time, e.g. a stop loss under a resting take profit
+    pub fn escalate(&mut self, reason: ExitReason, config: &ExitOrderConfig) {
+        let deadline = self.placed_at + config.limit_window(reason).unwrap_or(Duration::ZERO);
+        if deadline < self.deadline {
+            self.deadline = deadline;
+            self.reason = reason;
+        }
+    }
+}
+
+/// Passive price for a close on `side`: join the best ask when selling,
+/// the best bid when buying
+pub fn passive_exit_price(side: Side, best_bid: Decimal, best_ask: Decimal) -> Decimal {
+    match side {
+        Side::Sell => best_ask,
+        Side::Buy => best_bid,
+    }
+}
+
+/// An exit filled partly by the resting limit and partly at market
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExitFill {
+    pub limit_qty: Decimal,
+    pub limit_price: Decimal,
+    pub market_qty: Decimal,
+    pub market_price: Decimal,
+    /// Filled by exchange-side take profit / stop loss triggers (taker)
+    pub trigger_qty: Decimal,
+    pub trigger_price: Decimal,
+}
+
+impl ExitFill {
+    pub fn quantity(&self) -> Decimal {
+        self.limit_qty + self.market_qty + self.trigger_qty
+    }
+
+    /// Quantity-weighted price over every leg
+    pub fn average_price(&self) -> Decimal {
+        let quantity = self.quantity();
+        if quantity.is_zero() {
+            return Decimal::ZERO;
+        }
+        (self.limit_qty * self.limit_price + self.market_qty * self.market_price
+            + self.trigger_qty * self.trigger_price) / quantity
+    }
+
+    /// Maker fees on the limit leg plus taker fees on the market and
+    /// trigger legs, in the settlement asset
+    pub fn fees(&self, instrument: &Instrument, maker_fee_rate: Decimal, taker_fee_rate: Decimal) -> Decimal {
+        instrument.notional(self.limit_qty, self.limit_price) * maker_fee_rate
+            + instrument.notional(self.market_qty, self.market_price) * taker_fee_rate
+            + instrument.notional(self.trigger_qty, self.trigger_price) * taker_fee_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_windows_and_blended_fill() {
+        let config = ExitOrderConfig {
+            take_profit_limit_ms: 500,
+            ..ExitOrderConfig::default()
+        };
+        assert_eq!(config.limit_window(ExitReason::TakeProfit), Some(Duration::from_millis(500)));
+        assert_eq!(config.limit_window(ExitReason::SignalDecay), Some(Duration::from_millis(500)));
+        assert_eq!(config.limit_window(ExitReason::StopLoss), None);
+        assert_eq!(config.limit_window(ExitReason::Risk), None);
+
+        assert_eq!(passive_exit_price(Side::Sell, dec!(99), dec!(101)), dec!(101));
+
+        // Rested 0.75 at the ask, the rest went to market 2 lower
+        let fill = ExitFill {
+            limit_qty: dec!(0.75),
+            limit_price: dec!(101),
+            market_qty: dec!(0.25),
+            market_price: dec!(99),
+            ..ExitFill::default()
+        };
+        assert_eq!(fill.quantity(), dec!(1));
+        assert_eq!(fill.average_price(), dec!(100.5));
+        assert_eq!(fill.fees(&Instrument::linear(), dec!(0.0002), dec!(0.0004)), dec!(0.02505));
+    }
+
+    #[test]
+    fn test_resting_exit_polls_and_escalates() {
+        let config = ExitOrderConfig {
+            take_profit_limit_ms: 60_000,
+            stop_loss_limit_ms: 0,
+            poll_ms: 50,
+            exchange_tp_sl: false,
+        };
+        let mut exit = RestingExit::new(
+            7, Side::Sell, dec!(101), dec!(1), ExitReason::TakeProfit,
+            Duration::from_secs(60), SystemTime::now(),
+        );
+        let now = Instant::now();
+        assert!(!exit.expired(now));
+
+        // Polled at most once per interval
+        assert!(exit.poll_due(now, config.poll_interval()));
+        assert!(!exit.poll_due(now, config.poll_interval()));
+        assert!(exit.poll_due(now + Duration::from_millis(50), config.poll_interval()));
+
+        // A later max hold exit may wait as long: nothing changes
+        exit.escalate(ExitReason::MaxHoldTime, &config);
+        assert_eq!(exit.reason, ExitReason::TakeProfit);
+
+        // Stop loss hit while resting: no more waiting
+        exit.escalate(ExitReason::StopLoss, &config);
+        assert_eq!(exit.reason, ExitReason::StopLoss);
+        assert!(exit.expired(Instant::now()));
+    }
+}
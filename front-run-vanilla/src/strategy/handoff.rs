@@ -0,0 +1,221 @@
+use crate::data::{OrderBook, Side};
+use crate::exchange::binance::{LocalPnl, ReceiveTime};
+use crate::risk::EventCalendar;
+use crate::strategy::{
+    CompositeSignal, LedgerReport, RejectionReason, StatsSnapshot, TradingStats,
+};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::watch;
+
+/// Engine state handed to the slow path at an evaluation tick
+///
+/// The fast path owns the execution engine (exits must never wait on
+/// anything else), so the slow path works from this copy. Cheap figures
+/// travel with every snapshot; the full engine view only when the slow
+/// path asked for it.
+#[derive(Debug, Clone)]
+pub struct FastPathSnapshot {
+    /// Handoffs published so far, this one included (set by `publish`)
+    pub handoff: u64,
+    /// Depth updates the fast path has processed
+    pub depth_updates: u64,
+    pub event_time_ms: u64,
+    pub final_update_id: u64,
+    pub received: ReceiveTime,
+    /// Exchange to local receipt, latest sample
+    pub feed_latency_ms: u64,
+    pub mid: Option<Decimal>,
+    /// Copy of the top of the book at this update (the levels the slow
+    /// path evaluates); the live one keeps changing while it does
+    pub book: Arc<OrderBook>,
+    /// Copy of every level, attached on request for book snapshots and
+    /// heatmaps (`SlowPathRequest::FullBook`)
+    pub full_book: Option<Arc<OrderBook>>,
+    /// Open notional of the primary account, for the fleet sync
+    pub exposure: Decimal,
+    /// Market events dropped because the slow path's queue was full
+    pub slow_events_dropped: u64,
+    pub engine: Option<Arc<EngineView>>,
+}
+
+/// Engine statistics for the slow path's snapshots and logging
+#[derive(Debug, Clone)]
+pub struct EngineView {
+    pub stats: TradingStats,
+    pub snapshots: Vec<StatsSnapshot>,
+    pub ledger: Vec<LedgerReport>,
+    pub resting_orders: usize,
+    pub self_trades_prevented: u64,
+    /// Locally booked PnL per account, for reconciliation
+    pub local_pnl: Vec<(String, LocalPnl)>,
+}
+
+/// What the slow path needs done on the engine, applied by the fast path
+#[derive(Debug, Clone)]
+pub enum SlowPathRequest {
+    /// Score this detector signal against later mids
+    DetectorSignal {
+        detector: &'static str,
+        direction: Side,
+        mid: Decimal,
+        at: SystemTime,
+    },
+    /// Imbalance z-score after the latest evaluation, for the decay exit
+    ImbalanceZ(Option<f64>),
+    Rejection(RejectionReason),
+    /// Signal that passed every gate; executed on every account
+    Enter(CompositeSignal),
+    DetectorDisabled { detector: String, reason: String },
+    ResetDetector(String),
+    /// Attach an `EngineView` to the next snapshot
+    EngineView,
+    /// Attach a copy of the whole book to the next snapshot
+    FullBook,
+    /// Equity the slow path read from the exchange, for equity sizing
+    AccountEquity { account: String, equity: Decimal },
+    /// USD value of the quote asset, read by the slow path
+    QuoteUsd(Decimal),
+    /// Event calendar refetched by the slow path
+    Calendar(EventCalendar),
+    /// Close dust positions and sweep sub-lot residuals (sends orders)
+    SweepDust,
+}
+
+/// Fast path end of the snapshot handoff
+///
+/// Publishing never waits: an unread snapshot is replaced by the next
+/// one, so a slow path that falls behind skips ticks instead of making
+/// the fast path queue them.
+pub struct SnapshotSender {
+    tx: watch::Sender<Option<Arc<FastPathSnapshot>>>,
+    published: u64,
+}
+
+/// Slow path end of the snapshot handoff
+pub struct SnapshotReceiver {
+    rx: watch::Receiver<Option<Arc<FastPathSnapshot>>>,
+    last_handoff: u64,
+    skipped: u64,
+}
+
+/// Full book copies the slow path asked for, taken at the next handoff
+///
+/// A copy of every level costs far more than the evaluated top, so the
+/// fast path makes one only per request, never per tick.
+#[derive(Debug, Default)]
+pub struct FullBookRequest {
+    requested: bool,
+}
+
+impl FullBookRequest {
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    /// Copy of `orderbook` if one was requested since the last take
+    pub fn take(&mut self, orderbook: &OrderBook) -> Option<Arc<OrderBook>> {
+        std::mem::take(&mut self.requested).then(|| Arc::new(orderbook.snapshot()))
+    }
+}
+
+pub fn snapshot_handoff() -> (SnapshotSender, SnapshotReceiver) {
+    let (tx, rx) = watch::channel(None);
+    (
+        SnapshotSender { tx, published: 0 },
+        SnapshotReceiver { rx, last_handoff: 0, skipped: 0 },
+    )
+}
+
+impl SnapshotSender {
+    /// Replace the pending snapshot; false once the slow path is gone
+    pub fn publish(&mut self, mut snapshot: FastPathSnapshot) -> bool {
+        self.published += 1;
+        snapshot.handoff = self.published;
+        self.tx.send(Some(Arc::new(snapshot))).is_ok()
+    }
+}
+
+impl SnapshotReceiver {
+    /// Latest snapshot not seen yet; None once the fast path is gone
+    pub async fn next(&mut self) -> Option<Arc<FastPathSnapshot>> {
+        loop {
+            self.rx.changed().await.ok()?;
+            let Some(snapshot) = self.rx.borrow_and_update().clone() else {
+                continue;
+            };
+            self.skipped += snapshot.handoff.saturating_sub(self.last_handoff + 1);
+            self.last_handoff = snapshot.handoff;
+            return Some(snapshot);
+        }
+    }
+
+    /// Snapshots replaced before the slow path got to them
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(depth_updates: u64) -> FastPathSnapshot {
+        FastPathSnapshot {
+            handoff: 0,
+            depth_updates,
+            event_time_ms: 0,
+            final_update_id: 0,
+            received: ReceiveTime::now(),
+            feed_latency_ms: 0,
+            mid: None,
+            book: Arc::new(OrderBook::new("BTCUSDT")),
+            full_book: None,
+            exposure: Decimal::ZERO,
+            slow_events_dropped: 0,
+            engine: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_reader_gets_latest_and_counts_skips() {
+        let (mut tx, mut rx) = snapshot_handoff();
+        assert!(tx.publish(snapshot(10)));
+        assert_eq!(rx.next().await.unwrap().depth_updates, 10);
+
+        // Three ticks while the slow path was busy: only the last is read
+        for updates in [20, 30, 40] {
+            assert!(tx.publish(snapshot(updates)));
+        }
+        let latest = rx.next().await.unwrap();
+        assert_eq!((latest.handoff, latest.depth_updates), (4, 40));
+        assert_eq!(rx.skipped(), 2);
+
+        drop(tx);
+        assert!(rx.next().await.is_none());
+    }
+
+    #[test]
+    fn test_full_book_copied_only_on_request() {
+        let book = OrderBook::new("BTCUSDT");
+        let bids: Vec<_> = (1..=20).map(|i| (Decimal::from(100 - i), Decimal::ONE)).collect();
+        let asks: Vec<_> = (1..=20).map(|i| (Decimal::from(100 + i), Decimal::ONE)).collect();
+        book.apply_depth(&bids, &asks).unwrap();
+        let evaluated_levels = 5;
+
+        let mut full_book = FullBookRequest::default();
+        assert!(full_book.take(&book).is_none());
+
+        // The evaluated top alone would archive 5 levels a side
+        full_book.request();
+        let copy = full_book.take(&book).unwrap();
+        let capture = crate::storage::BookSnapshot::capture(&copy, "BTCUSDT", 0, None);
+        assert!(capture.bids.len() > evaluated_levels);
+        assert_eq!((capture.bids.len(), capture.asks.len()), (20, 20));
+        assert_eq!(book.snapshot_top(evaluated_levels).get_depth(usize::MAX).0.len(), evaluated_levels);
+
+        // One copy per request
+        assert!(full_book.take(&book).is_none());
+    }
+}
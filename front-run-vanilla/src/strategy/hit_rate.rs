@@ -0,0 +1,174 @@
+use crate::data::Side;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, SystemTime};
+
+/// Per-detector hit-rate tracking (`[strategy.hit_rate]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HitRateConfig {
+    /// Resolved signals per detector in the rolling hit rate
+    pub window: usize,
+    /// A signal hits once mid moves this far its way within the hold window
+    pub min_move_bps: f64,
+}
+
+impl Default for HitRateConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            min_move_bps: 1.0,
+        }
+    }
+}
+
+/// One detector's rolling record
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DetectorHitRate {
+    pub detector: String,
+    /// Signals emitted since startup
+    pub signals: u64,
+    /// Resolved signals in the rolling window
+    pub samples: usize,
+    pub hit_rate: Option<f64>,
+}
+
+struct PendingSignal {
+    direction: Side,
+    mid: Decimal,
+    expires: SystemTime,
+}
+
+#[derive(Default)]
+struct DetectorOutcomes {
+    signals: u64,
+    pending: VecDeque<PendingSignal>,
+    outcomes: VecDeque<bool>,
+}
+
+/// Scores every detector signal by whether mid moved its way in time
+///
+/// Same rule as threshold calibration, but per detector and for every
+/// signal a detector emits, whether or not it was switched on, aggregated
+/// or traded. A detector that has stopped predicting shows up here before
+/// it shows up in PnL.
+pub struct HitRateTracker {
+    config: HitRateConfig,
+    hold: Duration,
+    detectors: BTreeMap<String, DetectorOutcomes>,
+}
+
+impl HitRateTracker {
+    pub fn new(config: HitRateConfig, hold_time_ms: u64) -> Self {
+        Self {
+            config,
+            hold: Duration::from_millis(hold_time_ms),
+            detectors: BTreeMap::new(),
+        }
+    }
+
+    /// Track a signal from `detector` emitted at the current mid
+    pub fn record_signal(&mut self, detector: &str, direction: Side, mid: Decimal, now: SystemTime) {
+        let outcomes = self.detectors.entry(detector.to_string()).or_default();
+        outcomes.signals += 1;
+        outcomes.pending.push_back(PendingSignal {
+            direction,
+            mid,
+            expires: now + self.hold,
+        });
+    }
+
+    /// Resolve pending signals against the current mid
+    pub fn record_mid(&mut self, mid: Decimal, now: SystemTime) {
+        let min_move = Decimal::from_f64_retain(self.config.min_move_bps / 10000.0)
+            .unwrap_or(Decimal::ZERO);
+
+        for outcomes in self.detectors.values_mut() {
+            let mut resolved = Vec::new();
+            outcomes.pending.retain(|signal| {
+                if signal.mid.is_zero() {
+                    return false;
+                }
+                let moved = match signal.direction {
+                    Side::Buy => (mid - signal.mid) / signal.mid,
+                    Side::Sell => (signal.mid - mid) / signal.mid,
+                };
+                if moved >= min_move {
+                    resolved.push(true);
+                    false
+                } else if now >= signal.expires {
+                    resolved.push(false);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            for outcome in resolved {
+                outcomes.outcomes.push_back(outcome);
+                if outcomes.outcomes.len() > self.config.window {
+                    outcomes.outcomes.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Rolling share of `detector`'s resolved signals that hit
+    pub fn hit_rate(&self, detector: &str) -> Option<f64> {
+        let outcomes = &self.detectors.get(detector)?.outcomes;
+        if outcomes.is_empty() {
+            return None;
+        }
+        let hits = outcomes.iter().filter(|hit| **hit).count();
+        Some(hits as f64 / outcomes.len() as f64)
+    }
+
+    /// Every detector that has emitted a signal, by name
+    pub fn stats(&self) -> Vec<DetectorHitRate> {
+        self.detectors.iter()
+            .map(|(detector, outcomes)| DetectorHitRate {
+                detector: detector.clone(),
+                signals: outcomes.signals,
+                samples: outcomes.outcomes.len(),
+                hit_rate: self.hit_rate(detector),
+            })
+            .collect()
+    }
+
+    /// Forget `detector`'s record, e.g. once an operator reinstates it
+    pub fn reset(&mut self, detector: &str) {
+        self.detectors.remove(detector);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_hit_rates_are_per_detector() {
+        let mut tracker = HitRateTracker::new(HitRateConfig::default(), 1000);
+        let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        // Imbalance calls the 2 bps rally twice; basis shorts it twice
+        for i in 0..2 {
+            let now = start + Duration::from_secs(10 * i);
+            tracker.record_signal("imbalance", Side::Buy, dec!(100), now);
+            tracker.record_signal("basis", Side::Sell, dec!(100), now);
+            tracker.record_mid(dec!(100.02), now + Duration::from_millis(500));
+            tracker.record_mid(dec!(100.02), now + Duration::from_millis(1500));
+        }
+
+        assert_eq!(tracker.hit_rate("imbalance"), Some(1.0));
+        assert_eq!(tracker.hit_rate("basis"), Some(0.0));
+        assert_eq!(tracker.hit_rate("flow"), None);
+        assert_eq!(tracker.stats()[0].signals, 2);
+
+        tracker.reset("basis");
+        assert_eq!(tracker.hit_rate("basis"), None);
+        assert_eq!(tracker.hit_rate("imbalance"), Some(1.0));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::data::Side;
+use crate::risk::{PositionManager, RiskManager};
+use crate::strategy::{JournalFill, JournalLeg};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+
+/// Running totals of the fills written to the trade journal
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LedgerTally {
+    pub entries: usize,
+    pub exits: usize,
+    pub fees: Decimal,
+    /// Sells minus buys (price × quantity)
+    pub cash_flow: Decimal,
+}
+
+impl LedgerTally {
+    pub fn record(&mut self, fill: &JournalFill) {
+        match fill.leg {
+            JournalLeg::Entry => self.entries += 1,
+            JournalLeg::Exit => self.exits += 1,
+        }
+        self.fees += fill.fees;
+        let notional = fill.executed_price * fill.executed_qty;
+        match fill.side {
+            Side::Buy => self.cash_flow -= notional,
+            Side::Sell => self.cash_flow += notional,
+        }
+    }
+}
+
+/// One ledger invariant that did not hold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Discrepancy {
+    pub check: String,
+    pub expected: Decimal,
+    pub actual: Decimal,
+}
+
+/// Result of auditing one account's books
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerReport {
+    pub account: String,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl LedgerReport {
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Decimal noise allowed between independently summed totals
+const TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 8);
+
+/// Check that positions, risk equity and the journal tell the same story
+///
+/// - realized PnL and fees equal the sums over closed positions
+/// - risk manager equity moved by exactly the realized PnL
+/// - with a journal: one entry per position, one exit per closed position,
+///   the same fees, and journal cash flow reproduces realized PnL
+pub fn audit_ledger(
+    account: &str,
+    positions: &PositionManager,
+    risk: &RiskManager,
+    journal: Option<&LedgerTally>,
+) -> LedgerReport {
+    let mut discrepancies = Vec::new();
+    let mut check = |name: &str, expected: Decimal, actual: Decimal| {
+        if (expected - actual).abs() > TOLERANCE {
+            discrepancies.push(Discrepancy { check: name.to_string(), expected, actual });
+        }
+    };
+
+    let closed = positions.closed_positions();
+    let open = positions.open_positions();
+    let realized = positions.total_realized_pnl();
+
+    check("realized_pnl", closed.iter().map(|p| p.realized_pnl).sum(), realized);
+    check("fees", closed.iter().map(|p| p.fees_paid).sum(), positions.total_fees());
+    check("equity", risk.initial_equity() + realized, risk.get_metrics().current_equity);
+
+    if let Some(tally) = journal {
+        let count = |n: usize| Decimal::from(n as u64);
+        check("journal_entries", count(open.len() + closed.len()), count(tally.entries));
+        check("journal_exits", count(closed.len()), count(tally.exits));
+
+        let open_fees: Decimal = open.iter().map(|p| p.fees_paid).sum();
+        check("journal_fees", positions.total_fees() + open_fees, tally.fees);
+
+        // Undo open entries' cash flow and fees; what's left is realized
+        let open_cash: Decimal = open.iter()
+            .map(|p| match p.side {
+                Side::Buy => p.notional_value(),
+                Side::Sell => -p.notional_value(),
+            })
+            .sum();
+        check("journal_pnl", realized, tally.cash_flow + open_cash - (tally.fees - open_fees));
+    }
+
+    LedgerReport { account: account.to_string(), discrepancies }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::{Position, RiskLimits};
+    use rust_decimal_macros::dec;
+
+    fn fill(leg: JournalLeg, side: Side, price: Decimal, qty: Decimal, fees: Decimal) -> JournalFill {
+        JournalFill {
+            decision_time_ms: 0,
+            account: "primary".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            leg,
+            side,
+            decision_mid: price,
+            executed_price: price,
+            executed_qty: qty,
+            fees,
+            latency_ms: 0,
+            explanation: None,
+            experiment: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_consistent_books_and_partial_exit_mismatch() {
+        let mut positions = PositionManager::new();
+        let mut risk = RiskManager::new(RiskLimits::default(), dec!(10000));
+        let mut tally = LedgerTally::default();
+
+        // Closed long, then an open short
+        positions.open_position(Position::new("BTCUSDT".into(), Side::Buy, dec!(100), dec!(2), dec!(0.08))).unwrap();
+        tally.record(&fill(JournalLeg::Entry, Side::Buy, dec!(100), dec!(2), dec!(0.08)));
+        let pnl = positions.close_position("BTCUSDT", dec!(101), dec!(0.0808)).unwrap();
+        risk.record_trade(pnl);
+        tally.record(&fill(JournalLeg::Exit, Side::Sell, dec!(101), dec!(2), dec!(0.0808)));
+
+        positions.open_position(Position::new("BTCUSDT".into(), Side::Sell, dec!(102), dec!(1), dec!(0.04))).unwrap();
+        tally.record(&fill(JournalLeg::Entry, Side::Sell, dec!(102), dec!(1), dec!(0.04)));
+
+        let report = audit_ledger("primary", &positions, &risk, Some(&tally));
+        assert!(report.is_consistent(), "{:?}", report.discrepancies);
+
+        // Exit only half filled on the exchange but booked as a full close
+        let pnl = positions.close_position("BTCUSDT", dec!(100), dec!(0.02)).unwrap();
+        risk.record_trade(pnl);
+        tally.record(&fill(JournalLeg::Exit, Side::Buy, dec!(100), dec!(0.5), dec!(0.02)));
+
+        let report = audit_ledger("primary", &positions, &risk, Some(&tally));
+        assert_eq!(report.discrepancies.len(), 1);
+        assert_eq!(report.discrepancies[0].check, "journal_pnl");
+    }
+}
@@ -0,0 +1,163 @@
+use crate::data::OrderBook;
+use crate::strategy::signals::{BookView, EvaluateBook};
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+/// Reason the market was judged unfit for a new entry
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum MarketQualityViolation {
+    #[error("No two-sided market available")]
+    NoMarket,
+
+    #[error("Spread {spread_bps} bps exceeds limit {limit_bps} bps")]
+    SpreadTooWide { spread_bps: Decimal, limit_bps: Decimal },
+
+    #[error("Depth {depth} within {band_bps} bps of mid below minimum {min_depth}")]
+    InsufficientDepth { depth: Decimal, band_bps: Decimal, min_depth: Decimal },
+}
+
+/// Pre-trade market quality gate
+///
+/// Entries are sized for a tight book with fixed TP/SL distances. When the
+/// spread blows out or the book thins, the round-trip cost alone can exceed
+/// the take profit, so we refuse to enter regardless of signal strength.
+///
+/// Checks:
+/// 1. Spread between best bid and best ask (bps of mid)
+/// 2. Notional resting within `depth_band_bps` of mid, on each side
+#[derive(Debug, Clone)]
+pub struct MarketQualityGate {
+    /// Maximum acceptable spread in basis points
+    max_spread_bps: Decimal,
+
+    /// Distance from mid (bps) within which depth is counted
+    depth_band_bps: Decimal,
+
+    /// Minimum notional required within the band on EACH side (0 = disabled)
+    min_depth_notional: Decimal,
+
+    /// Number of levels scanned per side when measuring depth
+    scan_levels: usize,
+}
+
+impl Default for MarketQualityGate {
+    fn default() -> Self {
+        Self::new(Decimal::from(5), Decimal::from(10), Decimal::ZERO)
+    }
+}
+
+impl MarketQualityGate {
+    /// Create new market quality gate
+    ///
+    /// # Arguments
+    /// * `max_spread_bps` - Max spread to allow entries (typically 1-5 bps)
+    /// * `depth_band_bps` - Band around mid for depth measurement (e.g., 10 bps)
+    /// * `min_depth_notional` - Min notional per side inside the band (0 disables)
+    pub fn new(max_spread_bps: Decimal, depth_band_bps: Decimal, min_depth_notional: Decimal) -> Self {
+        Self {
+            max_spread_bps,
+            depth_band_bps,
+            min_depth_notional,
+            scan_levels: 50,
+        }
+    }
+
+    /// Check whether the current book is good enough to enter
+    pub fn check(&self, orderbook: &OrderBook) -> Result<(), MarketQualityViolation> {
+        orderbook.evaluate(self.scan_levels, |view| self.check_view(view))
+    }
+
+    /// `check` against a view the caller already holds, so the gate sees
+    /// the same book as the rest of that evaluation
+    pub fn check_view(&self, view: &BookView) -> Result<(), MarketQualityViolation> {
+        let (mid, spread_bps) = match (view.mid(), view.spread_bps()) {
+            (Some(mid), Some(spread_bps)) => (mid, spread_bps),
+            _ => return Err(MarketQualityViolation::NoMarket),
+        };
+
+        // 1. Spread check
+        if spread_bps > self.max_spread_bps {
+            return Err(MarketQualityViolation::SpreadTooWide {
+                spread_bps,
+                limit_bps: self.max_spread_bps,
+            });
+        }
+
+        // 2. Depth check (thinnest side must clear the minimum)
+        if self.min_depth_notional > Decimal::ZERO {
+            let band = mid * self.depth_band_bps / Decimal::from(10000);
+
+            let bid_depth: Decimal = view.bids().iter()
+                .take_while(|level| level.price >= mid - band)
+                .map(|level| level.price * level.quantity)
+                .sum();
+            let ask_depth: Decimal = view.asks().iter()
+                .take_while(|level| level.price <= mid + band)
+                .map(|level| level.price * level.quantity)
+                .sum();
+
+            let depth = bid_depth.min(ask_depth);
+            if depth < self.min_depth_notional {
+                return Err(MarketQualityViolation::InsufficientDepth {
+                    depth,
+                    band_bps: self.depth_band_bps,
+                    min_depth: self.min_depth_notional,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Side;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_tight_book_passes() {
+        let gate = MarketQualityGate::new(dec!(5), dec!(10), dec!(1000));
+        let ob = OrderBook::new("BTCUSDT");
+
+        ob.update_level(Side::Buy, dec!(100.00), dec!(50)).unwrap();
+        ob.update_level(Side::Sell, dec!(100.01), dec!(50)).unwrap();
+
+        assert!(gate.check(&ob).is_ok());
+    }
+
+    #[test]
+    fn test_wide_spread_rejected() {
+        let gate = MarketQualityGate::new(dec!(5), dec!(10), Decimal::ZERO);
+        let ob = OrderBook::new("BTCUSDT");
+
+        // 20 bps spread
+        ob.update_level(Side::Buy, dec!(99.90), dec!(50)).unwrap();
+        ob.update_level(Side::Sell, dec!(100.10), dec!(50)).unwrap();
+
+        let result = gate.check(&ob);
+        assert!(matches!(result, Err(MarketQualityViolation::SpreadTooWide { .. })));
+    }
+
+    #[test]
+    fn test_thin_book_rejected() {
+        let gate = MarketQualityGate::new(dec!(5), dec!(10), dec!(1000));
+        let ob = OrderBook::new("BTCUSDT");
+
+        ob.update_level(Side::Buy, dec!(100.00), dec!(50)).unwrap();
+        ob.update_level(Side::Sell, dec!(100.01), dec!(1)).unwrap();   // Only ~$100 on asks
+        ob.update_level(Side::Sell, dec!(105.00), dec!(100)).unwrap(); // Outside band
+
+        let result = gate.check(&ob);
+        assert!(matches!(result, Err(MarketQualityViolation::InsufficientDepth { .. })));
+    }
+
+    #[test]
+    fn test_empty_book_rejected() {
+        let gate = MarketQualityGate::default();
+        let ob = OrderBook::new("BTCUSDT");
+
+        assert_eq!(gate.check(&ob), Err(MarketQualityViolation::NoMarket));
+    }
+}
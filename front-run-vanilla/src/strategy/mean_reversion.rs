@@ -0,0 +1,208 @@
+use crate::data::{OrderBook, Side};
+use crate::risk::Instrument;
+use crate::strategy::{ImbalanceDetector, Strategy, StrategyAction, StrategyFill, StrategyParams};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, SystemTime};
+
+/// Fading of exhausted imbalances (`[strategy.mean_reversion]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeanReversionConfig {
+    /// Imbalance |z| that marks an extreme (arms the fade)
+    pub extreme_z: f64,
+    /// Fade once |z| falls back below this, on the same side of the mean
+    pub revert_z: f64,
+    /// An extreme older than this no longer arms an entry
+    pub arm_window_ms: u64,
+    /// USD notional of each entry
+    pub position_notional_usd: f64,
+}
+
+impl Default for MeanReversionConfig {
+    fn default() -> Self {
+        Self {
+            extreme_z: 3.0,
+            revert_z: 1.5,
+            arm_window_ms: 5000,
+            position_notional_usd: 1000.0,
+        }
+    }
+}
+
+/// Counter-strategy to the front-run entries on the same detector
+///
+/// Where the front-run logic buys a bid-heavy book as the z-score crosses
+/// the threshold, this waits for the z-score to reach `extreme_z`, then
+/// sells once it has fallen back below `revert_z` (and the reverse for
+/// ask-heavy books). Exits use the same `take_profit_bps`, `stop_loss_bps`
+/// and `max_hold_time_ms` as the front-run strategy, so the two differ
+/// only in how they read the signal.
+pub struct MeanReversion {
+    config: MeanReversionConfig,
+    instrument: Instrument,
+    imbalance: ImbalanceDetector,
+    take_profit_bps: Decimal,
+    stop_loss_bps: Decimal,
+    max_hold: Duration,
+    /// Sign of the last extreme z-score (Buy = bid-heavy) and when it was seen
+    armed: Option<(Side, SystemTime)>,
+    /// Side, entry price, quantity and time of the open position
+    position: Option<(Side, Decimal, Decimal, SystemTime)>,
+    /// A market order was sent and its fill not yet reported
+    order_pending: bool,
+}
+
+impl MeanReversion {
+    pub fn new(params: &StrategyParams, instrument: Instrument) -> Self {
+        Self {
+            config: params.mean_reversion.clone(),
+            instrument,
+            imbalance: params.imbalance_detector(),
+            take_profit_bps: params.take_profit_bps(),
+            stop_loss_bps: params.stop_loss_bps(),
+            max_hold: Duration::from_millis(params.max_hold_time_ms),
+            armed: None,
+            position: None,
+            order_pending: false,
+        }
+    }
+
+    /// Side to fade into, if `z_score` completes an extreme-then-revert
+    /// sequence at `now`
+    fn entry_side(&mut self, z_score: f64, now: SystemTime) -> Option<Side> {
+        let direction = if z_score > 0.0 { Side::Buy } else { Side::Sell };
+        if z_score.abs() >= self.config.extreme_z {
+            self.armed = Some((direction, now));
+            return None;
+        }
+
+        let (extreme, seen) = self.armed?;
+        let window = Duration::from_millis(self.config.arm_window_ms);
+        if now.duration_since(seen).unwrap_or_default() > window {
+            self.armed = None;
+            return None;
+        }
+        if direction != extreme || z_score.abs() >= self.config.revert_z {
+            return None;
+        }
+        self.armed = None;
+        Some(extreme.opposite())
+    }
+
+    /// Whether the open position should be closed at `mid`
+    fn should_exit(&self, mid: Decimal, now: SystemTime) -> bool {
+        let Some((side, entry_price, _, entry_time)) = self.position else {
+            return false;
+        };
+        if entry_price.is_zero() {
+            return false;
+        }
+        let move_bps = match side {
+            Side::Buy => (mid - entry_price) / entry_price * Decimal::from(10000),
+            Side::Sell => (entry_price - mid) / entry_price * Decimal::from(10000),
+        };
+        move_bps >= self.take_profit_bps
+            || move_bps <= -self.stop_loss_bps
+            || now.duration_since(entry_time).unwrap_or_default() >= self.max_hold
+    }
+}
+
+impl Strategy for MeanReversion {
+    fn name(&self) -> &str {
+        "mean_reversion"
+    }
+
+    fn on_book(&mut self, book: &OrderBook, now: SystemTime) -> Vec<StrategyAction> {
+        self.imbalance.calculate_signal_at(book, now);
+        let (Some(z_score), Some(mid)) = (self.imbalance.last_z_score(), book.get_mid_price()) else {
+            return Vec::new();
+        };
+
+        // Keep tracking extremes while in a position, so a fresh one after
+        // the exit is not missed
+        let entry_side = self.entry_side(z_score, now);
+        if self.order_pending {
+            return Vec::new();
+        }
+
+        if let Some((side, _, quantity, _)) = self.position {
+            if self.should_exit(mid, now) {
+                self.order_pending = true;
+                return vec![StrategyAction::Market { side: side.opposite(), quantity }];
+            }
+            return Vec::new();
+        }
+
+        let Some(side) = entry_side else {
+            return Vec::new();
+        };
+        let quantity = self.instrument.quantity(
+            Decimal::from_f64_retain(self.config.position_notional_usd).unwrap_or_default(),
+            mid,
+            Decimal::ONE,
+        );
+        if quantity.is_zero() {
+            return Vec::new();
+        }
+        self.order_pending = true;
+        vec![StrategyAction::Market { side, quantity }]
+    }
+
+    fn on_fill(&mut self, fill: &StrategyFill) {
+        self.order_pending = false;
+        self.position = match self.position {
+            // Exit fill
+            Some((side, _, _, _)) if side != fill.side => None,
+            _ => Some((fill.side, fill.price, fill.quantity, fill.time)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_fades_only_after_extreme_reverts() {
+        let mut strategy = MeanReversion::new(&StrategyParams::default(), Instrument::linear());
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // Reverting without an extreme first: nothing
+        assert_eq!(strategy.entry_side(1.0, at(0)), None);
+
+        // Bid-heavy extreme, still elevated, then back below 1.5: sell
+        assert_eq!(strategy.entry_side(3.4, at(100)), None);
+        assert_eq!(strategy.entry_side(2.0, at(200)), None);
+        assert_eq!(strategy.entry_side(1.2, at(300)), Some(Side::Sell));
+        assert_eq!(strategy.entry_side(1.0, at(400)), None);
+
+        // Ask-heavy extreme that reverts too late
+        assert_eq!(strategy.entry_side(-3.1, at(1000)), None);
+        assert_eq!(strategy.entry_side(-1.0, at(7000)), None);
+
+        // Exits on the shared TP/SL/hold-time parameters
+        strategy.on_fill(&StrategyFill {
+            side: Side::Sell,
+            price: dec!(100),
+            quantity: dec!(10),
+            maker: false,
+            time: at(8000),
+        });
+        assert!(!strategy.should_exit(dec!(99.95), at(8100)));
+        assert!(strategy.should_exit(dec!(99.9), at(8100)));
+        assert!(strategy.should_exit(dec!(100.05), at(8100)));
+        assert!(strategy.should_exit(dec!(100), at(13000)));
+
+        strategy.on_fill(&StrategyFill {
+            side: Side::Buy,
+            price: dec!(99.9),
+            quantity: dec!(10),
+            maker: false,
+            time: at(8200),
+        });
+        assert!(strategy.position.is_none());
+    }
+}
@@ -1,8 +1,63 @@
 pub mod signals;
 pub mod execution;
+pub mod market_quality;
+pub mod take_profit;
+pub mod params;
+pub mod reference;
+pub mod calibration;
+pub mod rejections;
+pub mod control;
+pub mod self_trade;
+pub mod oco;
+pub mod exit_orders;
+pub mod ledger;
+pub mod sizing;
+pub mod activity;
+pub mod hit_rate;
+pub mod detector_policy;
+pub mod reentry;
+pub mod aging;
+pub mod builtin;
+pub mod spread_capture;
+pub mod mean_reversion;
+pub mod dust;
+pub mod cadence;
+pub mod handoff;
 
 pub use signals::{
-    ImbalanceDetector, FlowAnalyzer, SignalAggregator,
-    CompositeSignal, ImbalanceStats, FlowStats,
+    ImbalanceDetector, FlowAnalyzer, SignalAggregator, ImbalanceWeighting, WeightedImbalance,
+    CompositeSignal, ImbalanceStats, FlowStats, ImbalanceConfig, FlowConfig, FlowDecay, FlowVolume,
+    BasisDetector, BasisConfig, LiquidationDetector, LiquidationConfig, BookShape, ShapeConfig, BookView,
+    EvaluateBook,
+    SignalCheck, SignalExplanation, SignalRole, SourceExplanation,
+    CompositeConfig, ConfidenceCombination, ConfidenceWeights, StrengthCombination, StrengthWeights,
+};
+pub use execution::{
+    ExecutionEngine, ExecutionOutcome, ExecutionResult, JournalFill, JournalLeg, TradingStats, StatsSnapshot,
+    PRIMARY_ACCOUNT, reconcile_accounts,
+};
+pub use market_quality::{MarketQualityGate, MarketQualityViolation};
+pub use take_profit::{TakeProfitSchedule, TakeProfitTier};
+pub use params::StrategyParams;
+pub use reference::{ReferenceConfig, ReferenceFilter, ReferenceVerdict};
+pub use calibration::{CalibrationConfig, RecalibrationConfig, ThresholdAdjustment, ThresholdCalibrator};
+pub use rejections::{RejectionCounts, RejectionReason};
+pub use control::{Detector, StrategyControl};
+pub use self_trade::{RestingOrder, SelfTradeGuard};
+pub use oco::{OcoAction, OcoExit, OcoFlatten, OcoLeg, OcoPair, OcoTracker, trigger_price};
+pub use exit_orders::{ExitFill, ExitOrderConfig, ExitReason, RestingExit, passive_exit_price};
+pub use ledger::{Discrepancy, LedgerReport, LedgerTally, audit_ledger};
+pub use sizing::SizingMode;
+pub use activity::{ActivityConfig, ActivityMonitor, ActivityStats, ActivityWindow};
+pub use hit_rate::{DetectorHitRate, HitRateConfig, HitRateTracker};
+pub use detector_policy::{DetectorPnl, DetectorPolicy, DetectorPolicyConfig, PnlAttribution, PolicyAction};
+pub use reentry::{ReentryConfig, ReentryGuard};
+pub use aging::{AgeMonitor, AgeWarning, AgeWarningConfig, AgeWarningStats};
+pub use builtin::{Strategy, StrategyAction, StrategyFill, StrategyKind};
+pub use spread_capture::{SpreadCapture, SpreadCaptureConfig};
+pub use mean_reversion::{MeanReversion, MeanReversionConfig};
+pub use dust::{DustConfig, DustLedger, split_close};
+pub use cadence::{CadenceConfig, CadenceLevel, CadencePolicy};
+pub use handoff::{
+    EngineView, FastPathSnapshot, FullBookRequest, SlowPathRequest, SnapshotReceiver, SnapshotSender, snapshot_handoff,
 };
-pub use execution::{ExecutionEngine, ExecutionResult, TradingStats};
@@ -0,0 +1,363 @@
+use crate::data::Side;
+use rust_decimal::Decimal;
+use std::time::{Duration, Instant};
+
+/// Which exit of an OCO pair an order is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcoLeg {
+    TakeProfit,
+    StopLoss,
+}
+
+impl OcoLeg {
+    pub fn other(self) -> Self {
+        match self {
+            Self::TakeProfit => Self::StopLoss,
+            Self::StopLoss => Self::TakeProfit,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::TakeProfit => 0,
+            Self::StopLoss => 1,
+        }
+    }
+}
+
+/// Exchange-side take-profit and stop-loss orders closing one position
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcoPair {
+    pub symbol: String,
+    /// Side of both exit orders (opposite the position)
+    pub side: Side,
+    /// Position quantity each leg closes
+    pub quantity: Decimal,
+    pub take_profit_id: u64,
+    pub stop_loss_id: u64,
+}
+
+impl OcoPair {
+    pub fn order_id(&self, leg: OcoLeg) -> u64 {
+        match leg {
+            OcoLeg::TakeProfit => self.take_profit_id,
+            OcoLeg::StopLoss => self.stop_loss_id,
+        }
+    }
+
+    fn leg_of(&self, order_id: u64) -> Option<OcoLeg> {
+        if order_id == self.take_profit_id {
+            Some(OcoLeg::TakeProfit)
+        } else if order_id == self.stop_loss_id {
+            Some(OcoLeg::StopLoss)
+        } else {
+            None
+        }
+    }
+}
+
+/// What the caller must send to the exchange after an order update
+#[derive(Debug, Clone, PartialEq)]
+pub enum OcoAction {
+    /// Cancel the leg that has not filled
+    Cancel { symbol: String, order_id: u64, leg: OcoLeg },
+    /// Both legs filled before the cancel landed, so the position was
+    /// closed and then reopened the other way by `quantity`: flatten it
+    /// with a reduce-only order on `side`
+    Reconcile { symbol: String, side: Side, quantity: Decimal },
+}
+
+#[derive(Debug)]
+struct TrackedPair {
+    pair: OcoPair,
+    /// Cumulative filled quantity per leg
+    filled: [Decimal; 2],
+    /// Leg confirmed cancelled (or rejected by the exchange)
+    closed: [bool; 2],
+    cancel_sent: bool,
+    reconciled: Decimal,
+}
+
+impl TrackedPair {
+    fn finished(&self) -> bool {
+        [OcoLeg::TakeProfit, OcoLeg::StopLoss].iter().all(|leg| {
+            self.closed[leg.index()] || self.filled[leg.index()] >= self.pair.quantity
+        })
+    }
+}
+
+/// Tracks exchange-native TP/SL pairs so exactly one of them closes the
+/// position
+///
+/// The exchange treats the two as unrelated orders. The first fill on
+/// either leg, partial or full, cancels the other; the filled leg keeps
+/// working until the position is closed. If the other leg fills anyway
+/// (its cancel raced a trigger), whatever was exited beyond the position
+/// comes back as `OcoAction::Reconcile`. Fill reports carry cumulative
+/// quantity, so duplicate or replayed reports are harmless.
+#[derive(Debug, Default)]
+pub struct OcoTracker {
+    pairs: Vec<TrackedPair>,
+}
+
+impl OcoTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, pair: OcoPair) {
+        self.pairs.push(TrackedPair {
+            pair,
+            filled: [Decimal::ZERO; 2],
+            closed: [false; 2],
+            cancel_sent: false,
+            reconciled: Decimal::ZERO,
+        });
+    }
+
+    /// Pairs with at least one leg still working
+    pub fn active_pairs(&self) -> impl Iterator<Item = &OcoPair> {
+        self.pairs.iter().map(|tracked| &tracked.pair)
+    }
+
+    /// Whether `order_id` is a leg that may still fill: neither closed nor
+    /// filled for the whole pair quantity
+    pub fn is_working(&self, order_id: u64) -> bool {
+        self.find(order_id).is_some_and(|(idx, leg)| {
+            let tracked = &self.pairs[idx];
+            !tracked.closed[leg.index()] && tracked.filled[leg.index()] < tracked.pair.quantity
+        })
+    }
+
+    /// Order `order_id` has filled `cumulative_qty` in total
+    pub fn on_fill(&mut self, order_id: u64, cumulative_qty: Decimal) -> Vec<OcoAction> {
+        let Some((idx, leg)) = self.find(order_id) else {
+            return Vec::new();
+        };
+        let tracked = &mut self.pairs[idx];
+        let filled = &mut tracked.filled[leg.index()];
+        *filled = (*filled).max(cumulative_qty);
+
+        let mut actions = Vec::new();
+        if !tracked.cancel_sent {
+            tracked.cancel_sent = true;
+            let other = leg.other();
+            actions.push(OcoAction::Cancel {
+                symbol: tracked.pair.symbol.clone(),
+                order_id: tracked.pair.order_id(other),
+                leg: other,
+            });
+        }
+
+        let excess = tracked.filled[0] + tracked.filled[1] - tracked.pair.quantity - tracked.reconciled;
+        if excess > Decimal::ZERO {
+            tracked.reconciled += excess;
+            actions.push(OcoAction::Reconcile {
+                symbol: tracked.pair.symbol.clone(),
+                side: tracked.pair.side.opposite(),
+                quantity: excess,
+            });
+        }
+
+        self.prune(idx);
+        actions
+    }
+
+    /// Order `order_id` is no longer working: cancelled, expired or rejected
+    ///
+    /// A failed cancel is not a close; the fill that beat it arrives
+    /// through `on_fill`.
+    pub fn on_closed(&mut self, order_id: u64) {
+        if let Some((idx, leg)) = self.find(order_id) {
+            self.pairs[idx].closed[leg.index()] = true;
+            self.prune(idx);
+        }
+    }
+
+    fn find(&self, order_id: u64) -> Option<(usize, OcoLeg)> {
+        self.pairs.iter().enumerate()
+            .find_map(|(idx, tracked)| tracked.pair.leg_of(order_id).map(|leg| (idx, leg)))
+    }
+
+    fn prune(&mut self, idx: usize) {
+        if self.pairs[idx].finished() {
+            self.pairs.remove(idx);
+        }
+    }
+}
+
+/// Price `bps` from `entry` in favour of a position on `side` (negative:
+/// against it), where its take profit or stop loss triggers
+pub fn trigger_price(side: Side, entry: Decimal, bps: Decimal) -> Decimal {
+    let change = entry * bps / Decimal::from(10_000);
+    match side {
+        Side::Buy => entry + change,
+        Side::Sell => entry - change,
+    }
+}
+
+/// What both legs exited beyond the position, and the reduce-only order
+/// that flattened it again
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OcoFlatten {
+    pub quantity: Decimal,
+    /// Average price of the leg that filled second
+    pub excess_price: Decimal,
+    pub flatten_price: Decimal,
+}
+
+/// An OCO pair protecting an open position, as the execution engine
+/// works it: trigger prices, what each leg filled, and when to poll
+#[derive(Debug, Clone)]
+pub struct OcoExit {
+    pub pair: OcoPair,
+    pub take_profit_price: Decimal,
+    pub stop_loss_price: Decimal,
+    /// Filled quantity and average price per leg (take profit, stop loss)
+    fills: [(Decimal, Decimal); 2],
+    /// Leg seen filling first; the other one's fills are the excess
+    first_filled: Option<OcoLeg>,
+    /// Flattens not booked yet
+    flattens: Vec<OcoFlatten>,
+    next_poll: Instant,
+}
+
+impl OcoExit {
+    pub fn new(pair: OcoPair, take_profit_price: Decimal, stop_loss_price: Decimal) -> Self {
+        Self {
+            pair,
+            take_profit_price,
+            stop_loss_price,
+            fills: [(Decimal::ZERO, Decimal::ZERO); 2],
+            first_filled: None,
+            flattens: Vec::new(),
+            next_poll: Instant::now(),
+        }
+    }
+
+    pub fn trigger_price(&self, leg: OcoLeg) -> Decimal {
+        match leg {
+            OcoLeg::TakeProfit => self.take_profit_price,
+            OcoLeg::StopLoss => self.stop_loss_price,
+        }
+    }
+
+    /// Record a leg's cumulative fill
+    pub fn record_fill(&mut self, leg: OcoLeg, quantity: Decimal, avg_price: Decimal) {
+        let fill = &mut self.fills[leg.index()];
+        if quantity >= fill.0 {
+            *fill = (quantity, avg_price);
+        }
+        if quantity > Decimal::ZERO && self.first_filled.is_none() {
+            self.first_filled = Some(leg);
+        }
+    }
+
+    pub fn filled(&self, leg: OcoLeg) -> Decimal {
+        self.fills[leg.index()].0
+    }
+
+    /// Legs in the order they started filling
+    fn fill_order(&self) -> [OcoLeg; 2] {
+        let first = self.first_filled.unwrap_or(OcoLeg::TakeProfit);
+        [first, first.other()]
+    }
+
+    /// The leg that closed the whole position, if one has
+    pub fn closing_leg(&self) -> Option<OcoLeg> {
+        self.fill_order().into_iter()
+            .find(|leg| self.filled(*leg) >= self.pair.quantity)
+    }
+
+    /// Quantity and average price the legs closed of the position
+    ///
+    /// The leg that filled first counts first; anything beyond the position
+    /// quantity was flattened separately and is not part of the close.
+    pub fn position_fill(&self) -> (Decimal, Decimal) {
+        let (mut quantity, mut notional) = (Decimal::ZERO, Decimal::ZERO);
+        for leg in self.fill_order() {
+            let (filled, price) = self.fills[leg.index()];
+            let take = filled.min(self.pair.quantity - quantity);
+            quantity += take;
+            notional += take * price;
+        }
+        let price = if quantity.is_zero() { Decimal::ZERO } else { notional / quantity };
+        (quantity, price)
+    }
+
+    /// Record a reduce-only flatten of `quantity` the legs exited beyond
+    /// the position, filled at `flatten_price`
+    pub fn record_flatten(&mut self, quantity: Decimal, flatten_price: Decimal) {
+        let [_, second] = self.fill_order();
+        self.flattens.push(OcoFlatten {
+            quantity,
+            excess_price: self.fills[second.index()].1,
+            flatten_price,
+        });
+    }
+
+    /// Flattens recorded since the last call, to be booked
+    pub fn take_flattens(&mut self) -> Vec<OcoFlatten> {
+        std::mem::take(&mut self.flattens)
+    }
+
+    /// Whether the legs should be polled at `now`; schedules the next poll
+    pub fn poll_due(&mut self, now: Instant, interval: Duration) -> bool {
+        if now < self.next_poll {
+            return false;
+        }
+        self.next_poll = now + interval;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn pair() -> OcoPair {
+        OcoPair {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Sell,
+            quantity: dec!(1),
+            take_profit_id: 10,
+            stop_loss_id: 11,
+        }
+    }
+
+    #[test]
+    fn test_fill_cancels_other_leg() {
+        let mut tracker = OcoTracker::new();
+        tracker.register(pair());
+
+        // A partial take-profit fill already cancels the stop
+        let actions = tracker.on_fill(10, dec!(0.4));
+        assert_eq!(actions, vec![OcoAction::Cancel { symbol: "BTCUSDT".to_string(), order_id: 11, leg: OcoLeg::StopLoss }]);
+        assert!(tracker.on_fill(10, dec!(0.4)).is_empty());
+
+        tracker.on_closed(11);
+        assert_eq!(tracker.active_pairs().count(), 1);
+        assert!(tracker.on_fill(10, dec!(1)).is_empty());
+        assert_eq!(tracker.active_pairs().count(), 0);
+    }
+
+    #[test]
+    fn test_both_legs_fill_before_cancel_lands() {
+        let mut tracker = OcoTracker::new();
+        tracker.register(pair());
+
+        // Stop triggers; its cancel of the take-profit goes out...
+        assert_eq!(tracker.on_fill(11, dec!(1)).len(), 1);
+        // ...but the take-profit partially fills before it lands
+        let actions = tracker.on_fill(10, dec!(0.3));
+        assert_eq!(actions, vec![OcoAction::Reconcile { symbol: "BTCUSDT".to_string(), side: Side::Buy, quantity: dec!(0.3) }]);
+        // A replayed report does not reconcile twice
+        assert!(tracker.on_fill(10, dec!(0.3)).is_empty());
+
+        assert_eq!(tracker.active_pairs().count(), 1);
+        // The cancel takes out the rest
+        tracker.on_closed(10);
+        assert_eq!(tracker.active_pairs().count(), 0);
+    }
+}
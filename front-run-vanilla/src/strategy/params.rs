@@ -0,0 +1,278 @@
+use crate::strategy::{
+    ActivityConfig, ActivityMonitor, AgeMonitor, AgeWarningConfig, CadenceConfig, CadencePolicy,
+    CalibrationConfig, CompositeConfig, DetectorPolicyConfig, DustConfig, ExitOrderConfig, FlowAnalyzer,
+    FlowConfig, HitRateConfig, HitRateTracker, ImbalanceConfig, ImbalanceDetector, MarketQualityGate,
+    MeanReversion, MeanReversionConfig, PnlAttribution, RecalibrationConfig, ReentryConfig, ReentryGuard,
+    ShapeConfig, SignalAggregator, SpreadCapture, SpreadCaptureConfig, Strategy, StrategyKind,
+    TakeProfitSchedule, TakeProfitTier, ThresholdCalibrator,
+};
+use crate::risk::Instrument;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+
+/// Strategy parameters shared by the backtester and the live trader
+///
+/// Flattened into `[strategy]` of the TOML config, so one file drives both
+/// modes. All detector/exit components should be built through the helpers
+/// below rather than from literal arguments, otherwise backtest and live
+/// silently diverge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyParams {
+    /// Strategy run on these parameters (default `front_run`)
+    #[serde(default)]
+    pub kind: StrategyKind,
+    /// Imbalance z-score threshold (also the primary aggregator threshold)
+    pub imbalance_threshold: f64,
+    pub min_confirming_signals: usize,
+    /// Threshold for secondary (confirming) detectors
+    #[serde(default = "default_confirming_threshold")]
+    pub confirming_threshold: f64,
+
+    pub take_profit_bps: f64,
+    /// Optional strength-scaled take profit tiers (empty = fixed TP)
+    #[serde(default)]
+    pub take_profit_tiers: Vec<TakeProfitTier>,
+    pub stop_loss_bps: f64,
+    pub max_hold_time_ms: u64,
+    /// Flatten once the imbalance z-score, in the position's direction,
+    /// falls back below this, before TP/SL/timeout (None = hold to those)
+    #[serde(default)]
+    pub signal_decay_exit_z: Option<f64>,
+    /// Same-direction re-entry distance from the last exit (`[strategy.reentry]`)
+    #[serde(default)]
+    pub reentry: ReentryConfig,
+    /// Stalled-position warnings ahead of the max hold time, live only
+    /// (`[strategy.age_warnings]`)
+    #[serde(default)]
+    pub age_warnings: AgeWarningConfig,
+
+    /// Market quality gate: widest spread to enter on
+    #[serde(default = "default_max_spread_bps")]
+    pub max_spread_bps: f64,
+    /// Band around the mid the resting depth is summed over
+    #[serde(default = "default_depth_band_bps")]
+    pub depth_band_bps: f64,
+    /// Least depth per side within the band (0 = no depth check)
+    #[serde(default)]
+    pub min_depth_usd: f64,
+
+    #[serde(default)]
+    pub imbalance: ImbalanceConfig,
+    #[serde(default)]
+    pub flow: FlowConfig,
+    #[serde(default)]
+    pub shape: ShapeConfig,
+    /// Composite confidence/strength formulas (`[strategy.composite]`)
+    #[serde(default)]
+    pub composite: CompositeConfig,
+    /// Online threshold tuning (`[strategy.calibration]`, off by default)
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+    /// Walk-forward threshold re-optimization, live only (`[strategy.recalibration]`)
+    #[serde(default)]
+    pub recalibration: RecalibrationConfig,
+    /// Overtrading detection (`[strategy.activity]`)
+    #[serde(default)]
+    pub activity: ActivityConfig,
+    /// Per-detector rolling hit rates, live only (`[strategy.hit_rate]`)
+    #[serde(default)]
+    pub hit_rate: HitRateConfig,
+    /// Switch-off of losing detectors, live only (`[strategy.detector_policy]`)
+    #[serde(default)]
+    pub detector_policy: DetectorPolicyConfig,
+    /// Limit-then-market exit windows (`[strategy.exits]`)
+    #[serde(default)]
+    pub exits: ExitOrderConfig,
+    /// Maker quoting for `kind = "spread_capture"` (`[strategy.spread_capture]`)
+    #[serde(default)]
+    pub spread_capture: SpreadCaptureConfig,
+    /// Imbalance fading for `kind = "mean_reversion"` (`[strategy.mean_reversion]`)
+    #[serde(default)]
+    pub mean_reversion: MeanReversionConfig,
+    /// Residual position sweeps, live only (`[strategy.dust]`)
+    #[serde(default)]
+    pub dust: DustConfig,
+    /// Entry slow-down under feed latency or backlog, live only (`[strategy.cadence]`)
+    #[serde(default)]
+    pub cadence: CadenceConfig,
+}
+
+fn default_confirming_threshold() -> f64 {
+    1.5
+}
+
+fn default_max_spread_bps() -> f64 {
+    5.0
+}
+
+fn default_depth_band_bps() -> f64 {
+    10.0
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            kind: StrategyKind::FrontRun,
+            imbalance_threshold: 3.0,
+            min_confirming_signals: 2,
+            confirming_threshold: default_confirming_threshold(),
+            take_profit_bps: 10.0,
+            take_profit_tiers: Vec::new(),
+            stop_loss_bps: 5.0,
+            max_hold_time_ms: 5000,
+            signal_decay_exit_z: None,
+            reentry: ReentryConfig::default(),
+            age_warnings: AgeWarningConfig::default(),
+            max_spread_bps: default_max_spread_bps(),
+            depth_band_bps: default_depth_band_bps(),
+            min_depth_usd: 0.0,
+            imbalance: ImbalanceConfig::default(),
+            flow: FlowConfig::default(),
+            shape: ShapeConfig::default(),
+            composite: CompositeConfig::default(),
+            calibration: CalibrationConfig::default(),
+            recalibration: RecalibrationConfig::default(),
+            activity: ActivityConfig::default(),
+            hit_rate: HitRateConfig::default(),
+            detector_policy: DetectorPolicyConfig::default(),
+            exits: ExitOrderConfig::default(),
+            spread_capture: SpreadCaptureConfig::default(),
+            mean_reversion: MeanReversionConfig::default(),
+            dust: DustConfig::default(),
+            cadence: CadenceConfig::default(),
+        }
+    }
+}
+
+impl StrategyParams {
+    pub fn imbalance_detector(&self) -> ImbalanceDetector {
+        ImbalanceDetector::from_config(&self.imbalance, self.imbalance_threshold)
+    }
+
+    pub fn flow_analyzer(&self) -> FlowAnalyzer {
+        FlowAnalyzer::from_config(&self.flow)
+    }
+
+    pub fn signal_aggregator(&self) -> SignalAggregator {
+        SignalAggregator::new(
+            self.imbalance_threshold,
+            self.confirming_threshold,
+            self.min_confirming_signals,
+        )
+        .with_composite(self.composite.clone())
+    }
+
+    /// Outcomes are judged over the max hold time
+    pub fn threshold_calibrator(&self) -> ThresholdCalibrator {
+        ThresholdCalibrator::new(self.calibration.clone(), self.max_hold_time_ms)
+    }
+
+    pub fn activity_monitor(&self) -> ActivityMonitor {
+        ActivityMonitor::new(self.activity.clone())
+    }
+
+    pub fn hit_rate_tracker(&self) -> HitRateTracker {
+        HitRateTracker::new(self.hit_rate.clone(), self.max_hold_time_ms)
+    }
+
+    pub fn reentry_guard(&self) -> ReentryGuard {
+        ReentryGuard::new(self.reentry.clone())
+    }
+
+    pub fn age_monitor(&self) -> AgeMonitor {
+        AgeMonitor::new(self.age_warnings.clone(), self.max_hold_time_ms)
+    }
+
+    pub fn cadence_policy(&self) -> CadencePolicy {
+        CadencePolicy::new(self.cadence.clone())
+    }
+
+    pub fn pnl_attribution(&self) -> PnlAttribution {
+        PnlAttribution::new(self.detector_policy.pnl_window)
+    }
+
+    pub fn market_quality_gate(&self) -> MarketQualityGate {
+        MarketQualityGate::new(
+            to_decimal(self.max_spread_bps),
+            to_decimal(self.depth_band_bps),
+            to_decimal(self.min_depth_usd),
+        )
+    }
+
+    pub fn take_profit_schedule(&self) -> TakeProfitSchedule {
+        TakeProfitSchedule::new(self.take_profit_bps(), &self.take_profit_tiers)
+    }
+
+    pub fn take_profit_bps(&self) -> Decimal {
+        to_decimal(self.take_profit_bps)
+    }
+
+    pub fn stop_loss_bps(&self) -> Decimal {
+        to_decimal(self.stop_loss_bps)
+    }
+
+    /// `kind` as a `Strategy` for `StrategyRunner` (None for `front_run`,
+    /// which the engines run themselves)
+    pub fn builtin_strategy(&self, instrument: &Instrument) -> Option<Box<dyn Strategy>> {
+        match self.kind {
+            StrategyKind::FrontRun => None,
+            StrategyKind::SpreadCapture => Some(Box::new(SpreadCapture::new(self, *instrument))),
+            StrategyKind::MeanReversion => Some(Box::new(MeanReversion::new(self, *instrument))),
+        }
+    }
+}
+
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64_retain(value).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_parse_from_strategy_section() {
+        let toml = r#"
+            imbalance_threshold = 2.5
+            min_confirming_signals = 1
+            take_profit_bps = 12.0
+            stop_loss_bps = 6.0
+            max_hold_time_ms = 3000
+            max_spread_bps = 4.0
+            depth_band_bps = 10.0
+            min_depth_usd = 50000.0
+
+            [imbalance]
+            levels = 10
+        "#;
+
+        let params: StrategyParams = toml::from_str(toml).unwrap();
+        assert_eq!(params.imbalance.levels, 10);
+        assert_eq!(params.imbalance.window_size, 100);
+        assert_eq!(params.confirming_threshold, 1.5);
+        assert_eq!(params.stop_loss_bps(), Decimal::from(6));
+        assert_eq!(params.take_profit_schedule().take_profit_bps(5.0), Decimal::from(12));
+        assert!(params.kind.runs_live());
+
+        let maker: StrategyParams = toml::from_str(&format!("kind = \"spread_capture\"\n{}", toml)).unwrap();
+        assert_eq!(maker.kind, StrategyKind::SpreadCapture);
+        assert!(!maker.kind.runs_live());
+    }
+
+    #[test]
+    fn test_market_quality_fields_default() {
+        let toml = r#"
+            imbalance_threshold = 2.5
+            min_confirming_signals = 1
+            take_profit_bps = 12.0
+            stop_loss_bps = 6.0
+            max_hold_time_ms = 3000
+        "#;
+
+        let params: StrategyParams = toml::from_str(toml).unwrap();
+        let defaults = StrategyParams::default();
+        assert_eq!(params.max_spread_bps, defaults.max_spread_bps);
+        assert_eq!(params.depth_band_bps, defaults.depth_band_bps);
+        assert_eq!(params.min_depth_usd, defaults.min_depth_usd);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::data::Side;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, SystemTime};
+
+/// Minimum re-entry distance from the last exit (`[strategy.reentry]`)
+///
+/// A persistent imbalance keeps signalling the same direction after a stop
+/// out; without this the engine re-buys the level it just left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReentryConfig {
+    /// Same-direction entries closer than this to the last exit price are
+    /// blocked (0 = off)
+    pub min_distance_bps: f64,
+    /// How long after the exit the rule applies
+    pub window_secs: u64,
+}
+
+impl Default for ReentryConfig {
+    fn default() -> Self {
+        Self {
+            min_distance_bps: 0.0,
+            window_secs: 60,
+        }
+    }
+}
+
+/// Last exit of one account, checked against its next entry
+#[derive(Debug, Clone)]
+pub struct ReentryGuard {
+    config: ReentryConfig,
+    /// Side of the closed position, exit price and time
+    last_exit: Option<(Side, Decimal, SystemTime)>,
+}
+
+impl ReentryGuard {
+    pub fn new(config: ReentryConfig) -> Self {
+        Self {
+            config,
+            last_exit: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.min_distance_bps > 0.0
+    }
+
+    /// Remember a closed position (`side` is the position's, not the exit order's)
+    pub fn record_exit(&mut self, side: Side, exit_price: Decimal, at: SystemTime) {
+        self.last_exit = Some((side, exit_price, at));
+    }
+
+    /// Why an entry on `side` at `price` is blocked at `at`, if it is
+    pub fn check(&self, side: Side, price: Decimal, at: SystemTime) -> Result<(), String> {
+        let Some((exit_side, exit_price, exit_time)) = self.last_exit else {
+            return Ok(());
+        };
+        if !self.is_enabled() || side != exit_side || exit_price.is_zero() {
+            return Ok(());
+        }
+        let elapsed = at.duration_since(exit_time).unwrap_or(Duration::ZERO);
+        if elapsed >= Duration::from_secs(self.config.window_secs) {
+            return Ok(());
+        }
+
+        let distance_bps = ((price - exit_price).abs() / exit_price * Decimal::from(10000))
+            .to_f64()
+            .unwrap_or(f64::MAX);
+        if distance_bps < self.config.min_distance_bps {
+            return Err(format!(
+                "{:?} at {} is {:.1} bps from the {}s-old exit at {} (min {:.1} bps)",
+                side, price, distance_bps, elapsed.as_secs(), exit_price, self.config.min_distance_bps
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_blocks_same_side_near_recent_exit() {
+        let mut guard = ReentryGuard::new(ReentryConfig {
+            min_distance_bps: 10.0,
+            window_secs: 30,
+        });
+        let exit_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        guard.record_exit(Side::Buy, dec!(100.00), exit_time);
+
+        let soon = exit_time + Duration::from_secs(5);
+        assert!(guard.check(Side::Buy, dec!(100.05), soon).is_err());
+        // Opposite direction, far enough away, or after the window
+        assert!(guard.check(Side::Sell, dec!(100.05), soon).is_ok());
+        assert!(guard.check(Side::Buy, dec!(99.80), soon).is_ok());
+        assert!(guard.check(Side::Buy, dec!(100.05), exit_time + Duration::from_secs(30)).is_ok());
+
+        assert!(ReentryGuard::new(ReentryConfig::default()).check(Side::Buy, dec!(100), soon).is_ok());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::data::Side;
+use crate::strategy::CompositeSignal;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Reference symbol filter settings (`[reference]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReferenceConfig {
+    /// Leading symbol to watch, e.g. "BTCUSDT" (empty = disabled)
+    pub symbol: String,
+    /// Horizon over which the reference move is measured
+    pub horizon_ms: u64,
+    /// Block entries when the reference moved this far against the signal
+    pub block_bps: f64,
+    /// Boost entries when the reference moved this far with the signal
+    pub boost_bps: f64,
+    /// Strength multiplier applied on boost
+    pub boost_factor: f64,
+}
+
+impl Default for ReferenceConfig {
+    fn default() -> Self {
+        Self {
+            symbol: String::new(),
+            horizon_ms: 5000,
+            block_bps: 5.0,
+            boost_bps: 5.0,
+            boost_factor: 1.25,
+        }
+    }
+}
+
+/// Outcome of checking a signal against the reference move
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReferenceVerdict {
+    /// Reference moved against the signal by `move_bps`
+    Block { move_bps: f64 },
+    /// Reference confirms the signal; strength multiplied by `factor`
+    Boost { move_bps: f64, factor: f64 },
+    /// No opinion (small move or not enough history)
+    Neutral,
+}
+
+/// Cross-symbol filter: BTC leads alts
+///
+/// Altcoin book/flow signals are often just BTC's move arriving late. The
+/// filter tracks the reference mid over a short horizon; an alt signal
+/// fighting a strong reference move is blocked, one riding it is boosted.
+pub struct ReferenceFilter {
+    config: ReferenceConfig,
+    horizon: Duration,
+    /// (sample time, reference mid), oldest first
+    samples: VecDeque<(Instant, Decimal)>,
+}
+
+impl ReferenceFilter {
+    pub fn new(config: ReferenceConfig) -> Self {
+        Self {
+            horizon: Duration::from_millis(config.horizon_ms),
+            config,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Reference symbol being tracked
+    pub fn symbol(&self) -> &str {
+        &self.config.symbol
+    }
+
+    /// Record the current reference mid price
+    pub fn record_mid(&mut self, mid: Decimal) {
+        self.record_mid_at(mid, Instant::now());
+    }
+
+    /// Record a reference mid observed at `now`
+    pub fn record_mid_at(&mut self, mid: Decimal, now: Instant) {
+        self.samples.push_back((now, mid));
+
+        // Keep exactly one sample at or beyond the horizon as the baseline
+        while self.samples.len() > 1 && now.duration_since(self.samples[1].0) >= self.horizon {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Reference move over the horizon in bps (None until a full horizon is seen)
+    pub fn move_bps(&self) -> Option<f64> {
+        let (oldest_time, oldest) = *self.samples.front()?;
+        let (newest_time, newest) = *self.samples.back()?;
+
+        if newest_time.duration_since(oldest_time) < self.horizon || oldest.is_zero() {
+            return None;
+        }
+
+        let bps = (newest - oldest) / oldest * Decimal::from(10000);
+        bps.to_string().parse().ok()
+    }
+
+    /// Judge a signal direction against the reference move
+    pub fn evaluate(&self, direction: Side) -> ReferenceVerdict {
+        let move_bps = match self.move_bps() {
+            Some(m) => m,
+            None => return ReferenceVerdict::Neutral,
+        };
+
+        let aligned = match direction {
+            Side::Buy => move_bps,
+            Side::Sell => -move_bps,
+        };
+
+        if aligned <= -self.config.block_bps {
+            ReferenceVerdict::Block { move_bps }
+        } else if aligned >= self.config.boost_bps {
+            ReferenceVerdict::Boost { move_bps, factor: self.config.boost_factor }
+        } else {
+            ReferenceVerdict::Neutral
+        }
+    }
+
+    /// Apply the verdict: None if blocked, otherwise the (possibly boosted) signal
+    pub fn apply(&self, mut signal: CompositeSignal) -> Option<CompositeSignal> {
+        match self.evaluate(signal.direction) {
+            ReferenceVerdict::Block { .. } => None,
+            ReferenceVerdict::Boost { move_bps, factor } => {
+                signal.overall_strength *= factor;
+                signal.explanation.check(
+                    "reference",
+                    true,
+                    format!("{} moved {:.1} bps, strength x{:.2}", self.config.symbol, move_bps, factor),
+                );
+                Some(signal)
+            }
+            ReferenceVerdict::Neutral => {
+                signal.explanation.check("reference", true, format!("{} neutral", self.config.symbol));
+                Some(signal)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn filter() -> ReferenceFilter {
+        ReferenceFilter::new(ReferenceConfig {
+            symbol: "BTCUSDT".to_string(),
+            ..ReferenceConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_neutral_until_horizon_filled() {
+        let mut filter = filter();
+        let start = Instant::now();
+
+        filter.record_mid_at(dec!(50000), start);
+        filter.record_mid_at(dec!(51000), start + Duration::from_secs(1));
+
+        assert_eq!(filter.move_bps(), None);
+        assert_eq!(filter.evaluate(Side::Sell), ReferenceVerdict::Neutral);
+    }
+
+    #[test]
+    fn test_blocks_against_and_boosts_with_reference_move() {
+        let mut filter = filter();
+        let start = Instant::now();
+
+        // Reference up 10 bps over the 5s horizon
+        for i in 0..=6 {
+            let mid = dec!(50000) + Decimal::from(i * 10);
+            filter.record_mid_at(mid, start + Duration::from_secs(i as u64));
+        }
+
+        let move_bps = filter.move_bps().unwrap();
+        assert!((move_bps - 10.0).abs() < 0.1, "move {}", move_bps);
+
+        assert!(matches!(filter.evaluate(Side::Sell), ReferenceVerdict::Block { .. }));
+        assert!(matches!(filter.evaluate(Side::Buy), ReferenceVerdict::Boost { .. }));
+    }
+}
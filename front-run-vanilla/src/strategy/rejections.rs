@@ -0,0 +1,171 @@
+use crate::risk::RiskViolation;
+use crate::strategy::MarketQualityViolation;
+use serde::{Serialize, Deserialize};
+use std::cmp::Reverse;
+
+/// Gate that stopped a signal from becoming an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// No detector signal reached the primary threshold
+    PrimaryTooWeak,
+    InsufficientConfirming,
+    LowConfidence,
+    ReferenceBlocked,
+    BasisVeto,
+    /// Scheduled event blackout
+    Blackout,
+    SpreadTooWide,
+    /// Not enough depth near mid, or no two-sided book
+    ThinBook,
+    RiskBlocked,
+    /// Circuit breaker tripped
+    Halted,
+    /// Hourly/daily trade count limit reached
+    Cooldown,
+    /// Same direction too close to the last exit (`[strategy.reentry]`)
+    ReentryTooClose,
+    /// Signal too old by the time it reached execution
+    LatencyAborted,
+}
+
+impl RejectionReason {
+    pub fn from_market_quality(violation: &MarketQualityViolation) -> Self {
+        match violation {
+            MarketQualityViolation::SpreadTooWide { .. } => RejectionReason::SpreadTooWide,
+            MarketQualityViolation::NoMarket
+            | MarketQualityViolation::InsufficientDepth { .. } => RejectionReason::ThinBook,
+        }
+    }
+
+    /// Classify a risk manager refusal; `halted` is the breaker state after it
+    pub fn from_risk_violation(violation: &RiskViolation, halted: bool) -> Self {
+        if halted {
+            RejectionReason::Halted
+        } else if violation.is_rate_limit() {
+            RejectionReason::Cooldown
+        } else {
+            RejectionReason::RiskBlocked
+        }
+    }
+}
+
+/// Why signals did not trade, by gate
+///
+/// A bot that never trades is usually stuck behind one gate; these counts
+/// say which one without turning on debug logging.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RejectionCounts {
+    pub primary_too_weak: u64,
+    pub insufficient_confirming: u64,
+    pub low_confidence: u64,
+    pub reference_blocked: u64,
+    pub basis_veto: u64,
+    pub blackout: u64,
+    pub spread_too_wide: u64,
+    pub thin_book: u64,
+    pub risk_blocked: u64,
+    pub halted: u64,
+    pub cooldown: u64,
+    pub reentry_too_close: u64,
+    pub latency_aborted: u64,
+}
+
+impl RejectionCounts {
+    pub fn record(&mut self, reason: RejectionReason) {
+        *self.counter(reason) += 1;
+    }
+
+    fn counter(&mut self, reason: RejectionReason) -> &mut u64 {
+        match reason {
+            RejectionReason::PrimaryTooWeak => &mut self.primary_too_weak,
+            RejectionReason::InsufficientConfirming => &mut self.insufficient_confirming,
+            RejectionReason::LowConfidence => &mut self.low_confidence,
+            RejectionReason::ReferenceBlocked => &mut self.reference_blocked,
+            RejectionReason::BasisVeto => &mut self.basis_veto,
+            RejectionReason::Blackout => &mut self.blackout,
+            RejectionReason::SpreadTooWide => &mut self.spread_too_wide,
+            RejectionReason::ThinBook => &mut self.thin_book,
+            RejectionReason::RiskBlocked => &mut self.risk_blocked,
+            RejectionReason::Halted => &mut self.halted,
+            RejectionReason::Cooldown => &mut self.cooldown,
+            RejectionReason::ReentryTooClose => &mut self.reentry_too_close,
+            RejectionReason::LatencyAborted => &mut self.latency_aborted,
+        }
+    }
+
+    /// (reason, count) for every gate, in pipeline order
+    pub fn counts(&self) -> [(RejectionReason, u64); 13] {
+        [
+            (RejectionReason::PrimaryTooWeak, self.primary_too_weak),
+            (RejectionReason::InsufficientConfirming, self.insufficient_confirming),
+            (RejectionReason::LowConfidence, self.low_confidence),
+            (RejectionReason::ReferenceBlocked, self.reference_blocked),
+            (RejectionReason::BasisVeto, self.basis_veto),
+            (RejectionReason::Blackout, self.blackout),
+            (RejectionReason::SpreadTooWide, self.spread_too_wide),
+            (RejectionReason::ThinBook, self.thin_book),
+            (RejectionReason::RiskBlocked, self.risk_blocked),
+            (RejectionReason::Halted, self.halted),
+            (RejectionReason::Cooldown, self.cooldown),
+            (RejectionReason::ReentryTooClose, self.reentry_too_close),
+            (RejectionReason::LatencyAborted, self.latency_aborted),
+        ]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts().iter().map(|(_, count)| count).sum()
+    }
+
+    /// Sum of both sets of counts
+    pub fn merged(&self, other: &RejectionCounts) -> RejectionCounts {
+        let mut merged = self.clone();
+        for (reason, count) in other.counts() {
+            *merged.counter(reason) += count;
+        }
+        merged
+    }
+
+    /// Non-zero counts, most frequent first, e.g. "spread_too_wide=40 halted=2"
+    pub fn summary(&self) -> String {
+        let mut counts: Vec<_> = self.counts().into_iter().filter(|(_, count)| *count > 0).collect();
+        counts.sort_by_key(|&(_, count)| Reverse(count));
+
+        if counts.is_empty() {
+            return "none".to_string();
+        }
+        counts.iter()
+            .map(|(reason, count)| format!("{}={}", reason_name(*reason), count))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn reason_name(reason: RejectionReason) -> String {
+    serde_json::to_value(reason)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_merge_and_summary() {
+        let mut signal_stage = RejectionCounts::default();
+        signal_stage.record(RejectionReason::PrimaryTooWeak);
+        signal_stage.record(RejectionReason::SpreadTooWide);
+        signal_stage.record(RejectionReason::SpreadTooWide);
+
+        let mut account_stage = RejectionCounts::default();
+        account_stage.record(RejectionReason::Halted);
+
+        let merged = signal_stage.merged(&account_stage);
+        assert_eq!(merged.total(), 4);
+        assert_eq!(merged.summary(), "spread_too_wide=2 primary_too_weak=1 halted=1");
+        assert_eq!(RejectionCounts::default().summary(), "none");
+    }
+}
@@ -0,0 +1,113 @@
+use crate::data::Side;
+use rust_decimal::Decimal;
+
+/// One of our orders resting on the book
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestingOrder {
+    pub order_id: u64,
+    /// Account whose client placed (and must cancel) the order
+    pub account: String,
+    pub symbol: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+/// Keeps our aggressive orders from trading against our own resting orders
+///
+/// Tracks every resting order across all accounts, since a sub-account
+/// crossing the primary's quote is a wash trade just the same. Before an
+/// aggressive order goes out, `crossing` lists the resting orders it could
+/// hit so they are cancelled first. The exchange's self-trade prevention
+/// only covers a single account.
+#[derive(Debug, Default)]
+pub struct SelfTradeGuard {
+    resting: Vec<RestingOrder>,
+    /// Resting orders cancelled to avoid a self-trade
+    prevented: u64,
+}
+
+impl SelfTradeGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, order: RestingOrder) {
+        self.resting.push(order);
+    }
+
+    /// Forget an order once filled or cancelled
+    pub fn remove(&mut self, order_id: u64) -> Option<RestingOrder> {
+        let idx = self.resting.iter().position(|order| order.order_id == order_id)?;
+        Some(self.resting.remove(idx))
+    }
+
+    pub fn resting_orders(&self) -> &[RestingOrder] {
+        &self.resting
+    }
+
+    /// Resting orders an aggressive `side` order on `symbol` could trade with
+    ///
+    /// `limit` is the aggressive order's limit price; `None` (market order)
+    /// can reach any price.
+    pub fn crossing(&self, symbol: &str, side: Side, limit: Option<Decimal>) -> Vec<RestingOrder> {
+        self.resting.iter()
+            .filter(|order| order.symbol == symbol && order.side == side.opposite())
+            .filter(|order| match (side, limit) {
+                (_, None) => true,
+                (Side::Buy, Some(limit)) => order.price <= limit,
+                (Side::Sell, Some(limit)) => order.price >= limit,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Count a resting order cancelled ahead of our own aggressive order
+    pub fn record_prevented(&mut self, order_id: u64) {
+        if self.remove(order_id).is_some() {
+            self.prevented += 1;
+        }
+    }
+
+    pub fn prevented(&self) -> u64 {
+        self.prevented
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn resting(order_id: u64, account: &str, side: Side, price: Decimal) -> RestingOrder {
+        RestingOrder {
+            order_id,
+            account: account.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side,
+            price,
+            quantity: dec!(0.01),
+        }
+    }
+
+    #[test]
+    fn test_crossing_orders_across_accounts() {
+        let mut guard = SelfTradeGuard::new();
+        guard.register(resting(1, "primary", Side::Sell, dec!(50010)));
+        guard.register(resting(2, "probe", Side::Sell, dec!(50030)));
+        guard.register(resting(3, "primary", Side::Buy, dec!(49990)));
+
+        // A market buy can hit both of our asks, never our bid
+        let crossing = guard.crossing("BTCUSDT", Side::Buy, None);
+        assert_eq!(crossing.iter().map(|o| o.order_id).collect::<Vec<_>>(), vec![1, 2]);
+
+        // A limit buy only reaches asks at or below its price
+        assert_eq!(guard.crossing("BTCUSDT", Side::Buy, Some(dec!(50020))).len(), 1);
+        assert!(guard.crossing("ETHUSDT", Side::Buy, None).is_empty());
+
+        guard.record_prevented(1);
+        guard.record_prevented(1);
+        assert_eq!(guard.prevented(), 1);
+        assert_eq!(guard.resting_orders().len(), 2);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::data::{OrderBook, Signal, SignalComponent, Side};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// Spot/perp basis detector settings (`[basis]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BasisConfig {
+    /// Spot WebSocket endpoint for the same symbol (empty = disabled)
+    pub spot_ws_endpoint: String,
+    /// Rolling window of basis samples
+    pub window_size: usize,
+    /// Basis z-score needed to emit a (confirming) signal
+    pub threshold: f64,
+    /// Basis z-score against a trade direction that vetoes the entry
+    pub veto_threshold: f64,
+}
+
+impl Default for BasisConfig {
+    fn default() -> Self {
+        Self {
+            spot_ws_endpoint: String::new(),
+            window_size: 100,
+            threshold: 2.0,
+            veto_threshold: 3.0,
+        }
+    }
+}
+
+/// Spot/perp basis (premium) detector
+///
+/// CONFIRMING SIGNAL / VETO: the perp premium over spot widens when
+/// leveraged buyers are paying up and collapses when they unwind. A basis
+/// move far outside its recent range confirms a same-direction book signal;
+/// a strong move the other way vetoes it.
+///
+/// Algorithm:
+/// 1. basis_bps = (perp_mid - spot_mid) / spot_mid * 10000
+/// 2. Maintain rolling window of basis
+/// 3. z-score of the latest basis against the window
+pub struct BasisDetector {
+    history: VecDeque<f64>,
+    window_size: usize,
+    threshold: f64,
+    veto_threshold: f64,
+    min_samples: usize,
+    last_z_score: Option<f64>,
+}
+
+impl BasisDetector {
+    pub fn new(window_size: usize, threshold: f64, veto_threshold: f64) -> Self {
+        Self {
+            history: VecDeque::with_capacity(window_size),
+            window_size,
+            threshold,
+            veto_threshold,
+            min_samples: window_size / 2,
+            last_z_score: None,
+        }
+    }
+
+    pub fn from_config(config: &BasisConfig) -> Self {
+        Self::new(config.window_size, config.threshold, config.veto_threshold)
+    }
+
+    /// Current basis in bps (None without a two-sided market on both books)
+    pub fn basis_bps(perp: &OrderBook, spot: &OrderBook) -> Option<f64> {
+        let perp_mid = perp.get_mid_price()?;
+        let spot_mid = spot.get_mid_price()?;
+        if spot_mid.is_zero() {
+            return None;
+        }
+
+        let bps = (perp_mid - spot_mid) / spot_mid * Decimal::from(10000);
+        bps.to_string().parse().ok()
+    }
+
+    /// Sample both books and return a signal if the basis moved sharply
+    pub fn calculate_signal(&mut self, perp: &OrderBook, spot: &OrderBook) -> Option<Signal> {
+        self.calculate_signal_at(perp, spot, SystemTime::now())
+    }
+
+    /// `calculate_signal` stamped with the exchange time of the perp update
+    pub fn calculate_signal_at(&mut self, perp: &OrderBook, spot: &OrderBook, event_time: SystemTime) -> Option<Signal> {
+        let basis = Self::basis_bps(perp, spot)?;
+        self.process_basis(basis, event_time)
+    }
+
+    fn process_basis(&mut self, basis: f64, event_time: SystemTime) -> Option<Signal> {
+        self.history.push_back(basis);
+        if self.history.len() > self.window_size {
+            self.history.pop_front();
+        }
+
+        if self.history.len() < self.min_samples {
+            self.last_z_score = None;
+            return None;
+        }
+
+        let n = self.history.len() as f64;
+        let mean = self.history.iter().sum::<f64>() / n;
+        let variance = self.history.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / n;
+        let stddev = variance.sqrt();
+
+        if stddev < 1e-6 {
+            self.last_z_score = None;
+            return None;
+        }
+
+        let z_score = (basis - mean) / stddev;
+        self.last_z_score = Some(z_score);
+
+        if z_score.abs() < self.threshold {
+            return None;
+        }
+
+        // Widening premium = aggressive perp buying = bullish
+        let direction = if z_score > 0.0 { Side::Buy } else { Side::Sell };
+        let confidence = (z_score.abs() / (self.threshold + 1.0)).min(1.0);
+
+        Some(Signal {
+            strength: z_score,
+            direction,
+            confidence,
+            timestamp: event_time,
+            components: vec![
+                SignalComponent::new("basis_bps", basis, 1.0),
+                SignalComponent::new("mean", mean, 0.0),
+                SignalComponent::new("stddev", stddev, 0.0),
+                SignalComponent::new("z_score", z_score, 1.0),
+            ],
+        })
+    }
+
+    /// Whether the latest basis move strongly opposes `direction`
+    pub fn vetoes(&self, direction: Side) -> bool {
+        let z_score = match self.last_z_score {
+            Some(z) => z,
+            None => return false,
+        };
+
+        match direction {
+            Side::Buy => z_score <= -self.veto_threshold,
+            Side::Sell => z_score >= self.veto_threshold,
+        }
+    }
+
+    /// Latest basis z-score (None until enough history)
+    pub fn last_z_score(&self) -> Option<f64> {
+        self.last_z_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warmed_up() -> BasisDetector {
+        let mut detector = BasisDetector::new(20, 2.0, 3.0);
+        // Basis oscillating tightly around 5 bps
+        for i in 0..20 {
+            let basis = 5.0 + if i % 2 == 0 { 0.1 } else { -0.1 };
+            assert!(detector.process_basis(basis, SystemTime::UNIX_EPOCH).is_none());
+        }
+        detector
+    }
+
+    #[test]
+    fn test_widening_basis_confirms_buy() {
+        let mut detector = warmed_up();
+
+        let signal = detector.process_basis(6.0, SystemTime::UNIX_EPOCH).unwrap();
+        assert_eq!(signal.direction, Side::Buy);
+        assert!(signal.strength > 2.0);
+        assert!(!detector.vetoes(Side::Buy));
+        assert!(detector.vetoes(Side::Sell));
+    }
+
+    #[test]
+    fn test_collapsing_basis_vetoes_buy() {
+        let mut detector = warmed_up();
+
+        let signal = detector.process_basis(3.0, SystemTime::UNIX_EPOCH).unwrap();
+        assert_eq!(signal.direction, Side::Sell);
+        assert!(detector.vetoes(Side::Buy));
+    }
+
+    #[test]
+    fn test_basis_from_books() {
+        let perp = OrderBook::new("BTCUSDT");
+        let spot = OrderBook::new("BTCUSDT");
+        perp.update_level(Side::Buy, Decimal::from(50049), Decimal::ONE).unwrap();
+        perp.update_level(Side::Sell, Decimal::from(50051), Decimal::ONE).unwrap();
+        spot.update_level(Side::Buy, Decimal::from(49999), Decimal::ONE).unwrap();
+        spot.update_level(Side::Sell, Decimal::from(50001), Decimal::ONE).unwrap();
+
+        let basis = BasisDetector::basis_bps(&perp, &spot).unwrap();
+        assert!((basis - 10.0).abs() < 1e-9);
+    }
+}
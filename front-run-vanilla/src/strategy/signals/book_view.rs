@@ -0,0 +1,177 @@
+use crate::data::{OrderBook, PriceLevel};
+use crate::strategy::signals::ImbalanceWeighting;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// Copy of the top of the book, taken by one `get_depth` call
+///
+/// Everything derived from a view (best prices, mid, spread, imbalance)
+/// is computed from the same copied levels. Calling `get_mid_price`,
+/// `calculate_imbalance` and `get_depth` separately reads the book three
+/// times, so a diff applied in between leaves the spread and imbalance of
+/// one evaluation describing different books.
+///
+/// `get_depth` copies both sides under the book's read lock, so the view
+/// is one consistent snapshot: a diff applied with `apply_depth` is either
+/// wholly in it or wholly absent.
+#[derive(Debug, Clone)]
+pub struct BookView {
+    bids: Vec<PriceLevel>,
+    asks: Vec<PriceLevel>,
+}
+
+impl BookView {
+    pub fn new(bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> Self {
+        Self { bids, asks }
+    }
+
+    /// Bids, best first
+    pub fn bids(&self) -> &[PriceLevel] {
+        &self.bids
+    }
+
+    /// Asks, best first
+    pub fn asks(&self) -> &[PriceLevel] {
+        &self.asks
+    }
+
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|level| level.price)
+    }
+
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|level| level.price)
+    }
+
+    /// Mid of the best bid and ask (None unless both sides are present)
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.best_bid()? + self.best_ask()?) / Decimal::from(2))
+    }
+
+    /// Best ask minus best bid, in bps of mid
+    pub fn spread_bps(&self) -> Option<Decimal> {
+        let mid = self.mid().filter(|mid| !mid.is_zero())?;
+        Some((self.best_ask()? - self.best_bid()?) / mid * Decimal::from(10000))
+    }
+
+    /// Bid/ask depth ratio over the top `levels`, each level measured by
+    /// `weighting` and discounted by distance from mid if `distance_decay_bps > 0`
+    pub fn weighted_imbalance(
+        &self,
+        levels: usize,
+        weighting: ImbalanceWeighting,
+        distance_decay_bps: f64,
+    ) -> Option<f64> {
+        let mid = self.mid()?.to_f64()?;
+        if mid <= 0.0 {
+            return None;
+        }
+
+        let depth = |book: &[PriceLevel]| -> Option<f64> {
+            let mut total = 0.0;
+            for level in book.iter().take(levels) {
+                let price = level.price.to_f64()?;
+                let quantity = level.quantity.to_f64()?;
+                let size = match weighting {
+                    ImbalanceWeighting::Quantity => quantity,
+                    ImbalanceWeighting::Notional => price * quantity,
+                };
+                let weight = if distance_decay_bps > 0.0 {
+                    let distance_bps = (price - mid).abs() / mid * 10000.0;
+                    (-distance_bps / distance_decay_bps).exp()
+                } else {
+                    1.0
+                };
+                total += size * weight;
+            }
+            Some(total)
+        };
+
+        let bid_depth = depth(&self.bids)?;
+        let ask_depth = depth(&self.asks)?;
+        if ask_depth <= 0.0 {
+            return None;
+        }
+        Some(bid_depth / ask_depth)
+    }
+}
+
+/// Evaluate several book metrics against one copy of the book
+///
+/// Implemented for `OrderBook` like `WeightedImbalance` and `BookShape`:
+///
+/// ```ignore
+/// let (spread, ratio) = orderbook.evaluate(10, |view| {
+///     (view.spread_bps(), view.weighted_imbalance(5, ImbalanceWeighting::Quantity, 0.0))
+/// });
+/// ```
+pub trait EvaluateBook {
+    /// Run `f` over the top `levels` per side, copied by a single `get_depth`
+    /// (both sides under one read lock; see `BookView`)
+    fn evaluate<R>(&self, levels: usize, f: impl FnOnce(&BookView) -> R) -> R;
+}
+
+impl EvaluateBook for OrderBook {
+    fn evaluate<R>(&self, levels: usize, f: impl FnOnce(&BookView) -> R) -> R {
+        let (bids, asks) = self.get_depth(levels);
+        f(&BookView::new(bids, asks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Side;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_view_metrics_share_one_read() {
+        let ob = OrderBook::new("BTCUSDT");
+        ob.update_level(Side::Buy, dec!(99.99), dec!(3)).unwrap();
+        ob.update_level(Side::Buy, dec!(99.98), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(100.01), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(100.02), dec!(1)).unwrap();
+
+        let view = ob.evaluate(5, |view| view.clone());
+
+        // Changes after the read do not leak into the view
+        ob.update_level(Side::Sell, dec!(100.01), Decimal::ZERO).unwrap();
+
+        assert_eq!(view.mid(), Some(dec!(100.00)));
+        assert_eq!(view.spread_bps(), Some(dec!(2)));
+        assert_eq!(view.weighted_imbalance(5, ImbalanceWeighting::Quantity, 0.0), Some(2.0));
+        assert_eq!(view.weighted_imbalance(1, ImbalanceWeighting::Quantity, 0.0), Some(3.0));
+        assert_eq!(BookView::new(vec![], view.asks().to_vec()).mid(), None);
+    }
+
+    #[test]
+    fn test_view_never_mixes_concurrent_diffs() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // Every diff sets the same quantity on both sides, so any view
+        // that mixes two diffs shows unequal bid and ask sizes
+        let ob = Arc::new(OrderBook::new("BTCUSDT"));
+        ob.apply_depth(&[(dec!(99), dec!(1))], &[(dec!(101), dec!(1))]).unwrap();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let writer = {
+            let (ob, done) = (Arc::clone(&ob), Arc::clone(&done));
+            std::thread::spawn(move || {
+                for i in 1..=20_000u32 {
+                    let qty = Decimal::from(i % 97 + 1);
+                    ob.apply_depth(&[(dec!(99), qty)], &[(dec!(101), qty)]).unwrap();
+                }
+                done.store(true, Ordering::Release);
+            })
+        };
+
+        let mut reads = 0;
+        while !done.load(Ordering::Acquire) || reads == 0 {
+            let (bid, ask) = ob.evaluate(1, |view| (view.bids()[0].quantity, view.asks()[0].quantity));
+            assert_eq!(bid, ask, "view mixed two diffs");
+            reads += 1;
+        }
+        writer.join().unwrap();
+    }
+}
@@ -1,5 +1,169 @@
 use crate::data::{Signal, Side};
-use std::time::SystemTime;
+use crate::strategy::RejectionReason;
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, SystemTime};
+
+/// Composite confidence and strength formulas (`[strategy.composite]`)
+///
+/// Defaults reproduce the original fixed weights.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompositeConfig {
+    pub confidence: ConfidenceCombination,
+    pub strength: StrengthCombination,
+    /// Used by `confidence = "weighted_sum"`
+    pub confidence_weights: ConfidenceWeights,
+    /// Used by `strength = "weighted_sum"`
+    pub strength_weights: StrengthWeights,
+}
+
+impl Default for CompositeConfig {
+    fn default() -> Self {
+        Self {
+            confidence: ConfidenceCombination::WeightedSum,
+            strength: StrengthCombination::WeightedSum,
+            confidence_weights: ConfidenceWeights::default(),
+            strength_weights: StrengthWeights::default(),
+        }
+    }
+}
+
+/// How source confidences combine into the composite confidence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceCombination {
+    /// Primary confidence, confirming-count factor and mean confirming
+    /// confidence, by `confidence_weights` (capped at 1.0)
+    WeightedSum,
+    /// Most confident contributing signal
+    Max,
+    /// 1 - Π(1 - confidence) over contributing signals, treating each as
+    /// independent evidence; every confirmation raises the result
+    ProbabilisticOr,
+}
+
+/// How source strengths combine into `overall_strength`
+///
+/// Strengths are unbounded z-scores, so there is no probabilistic OR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrengthCombination {
+    /// Primary and mean confirming strength, by `strength_weights`
+    WeightedSum,
+    /// Primary strength alone (the strongest signal by construction)
+    Max,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfidenceWeights {
+    pub primary: f64,
+    /// Confirming count relative to `min_confirming + 2`
+    pub count: f64,
+    pub confirming: f64,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        Self {
+            primary: 0.4,
+            count: 0.3,
+            confirming: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StrengthWeights {
+    pub primary: f64,
+    pub confirming: f64,
+}
+
+impl Default for StrengthWeights {
+    fn default() -> Self {
+        Self {
+            primary: 0.6,
+            confirming: 0.4,
+        }
+    }
+}
+
+/// How a detector signal was used by the aggregator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalRole {
+    Primary,
+    Confirming,
+    /// Opposite direction or below the confirming threshold
+    Ignored,
+}
+
+/// One detector signal's part in a composite decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceExplanation {
+    /// Detector, inferred from the signal's components
+    pub source: String,
+    pub role: SignalRole,
+    pub direction: Side,
+    pub strength: f64,
+    pub confidence: f64,
+    /// Weight in `overall_strength`
+    pub strength_weight: f64,
+    /// Weight in composite `confidence` (1.0 for every contributing
+    /// signal under probabilistic OR)
+    pub confidence_weight: f64,
+    /// Raw detector components (ratio, z-score, ...)
+    pub components: Vec<(String, f64)>,
+}
+
+/// A gate the signal went through on its way to an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Why a composite signal was (or was not) acted on
+///
+/// The aggregator fills in the sources and its own checks; later gates
+/// (reference filter, market quality, risk) append theirs via `check`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignalExplanation {
+    pub sources: Vec<SourceExplanation>,
+    pub checks: Vec<SignalCheck>,
+}
+
+impl SignalExplanation {
+    /// Record the outcome of a gate
+    pub fn check(&mut self, name: &str, passed: bool, detail: impl Into<String>) {
+        self.checks.push(SignalCheck {
+            name: name.to_string(),
+            passed,
+            detail: detail.into(),
+        });
+    }
+
+    /// First failed check, if any
+    pub fn failed_check(&self) -> Option<&SignalCheck> {
+        self.checks.iter().find(|check| !check.passed)
+    }
+}
+
+/// Detector that produced a signal, from its first component's name
+fn signal_source(signal: &Signal) -> String {
+    let first = signal.components.first().map(|c| c.name.as_str()).unwrap_or("");
+    match first {
+        "imbalance_ratio" => "imbalance",
+        "basis_bps" => "basis",
+        "liquidation_notional" => "liquidation",
+        "buy_volume" => "flow",
+        "" => "unknown",
+        other => other,
+    }
+    .to_string()
+}
 
 /// Composite signal combining multiple signal sources
 /// 
@@ -25,14 +189,37 @@ pub struct CompositeSignal {
     /// Composite confidence (0.0 to 1.0)
     pub confidence: f64,
     
-    /// Timestamp of signal generation
+    /// Exchange time of the event that triggered the primary signal
     pub timestamp: SystemTime,
+
+    /// Per-source contributions and gate outcomes, for the trade journal
+    pub explanation: SignalExplanation,
 }
 
 impl CompositeSignal {
+    /// Minimum composite confidence to trade
+    const MIN_CONFIDENCE: f64 = 0.5;
+
     /// Check if this signal is strong enough to trade
     pub fn is_tradeable(&self, min_confirming: usize) -> bool {
-        self.confirming.len() >= min_confirming && self.confidence >= 0.5
+        self.rejection(min_confirming).is_none()
+    }
+
+    /// Time since the triggering exchange event (zero if `now` is earlier,
+    /// e.g. local clock behind the exchange)
+    pub fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.timestamp).unwrap_or(Duration::ZERO)
+    }
+
+    /// Why this signal is not tradeable, if it isn't
+    pub fn rejection(&self, min_confirming: usize) -> Option<RejectionReason> {
+        if self.confirming.len() < min_confirming {
+            Some(RejectionReason::InsufficientConfirming)
+        } else if self.confidence < Self::MIN_CONFIDENCE {
+            Some(RejectionReason::LowConfidence)
+        } else {
+            None
+        }
     }
 }
 
@@ -46,6 +233,9 @@ pub struct SignalAggregator {
     
     /// Minimum number of confirming signals required
     min_confirming: usize,
+
+    /// Confidence and strength formulas
+    composite: CompositeConfig,
 }
 
 impl SignalAggregator {
@@ -64,44 +254,72 @@ impl SignalAggregator {
             primary_threshold,
             confirming_threshold,
             min_confirming,
+            composite: CompositeConfig::default(),
         }
     }
 
+    /// Combine sources with `composite`'s formulas instead of the defaults
+    pub fn with_composite(mut self, composite: CompositeConfig) -> Self {
+        self.composite = composite;
+        self
+    }
+
+    pub fn primary_threshold(&self) -> f64 {
+        self.primary_threshold
+    }
+
+    pub fn confirming_threshold(&self) -> f64 {
+        self.confirming_threshold
+    }
+
+    /// Replace both strength thresholds (online calibration)
+    pub fn set_thresholds(&mut self, primary_threshold: f64, confirming_threshold: f64) {
+        self.primary_threshold = primary_threshold;
+        self.confirming_threshold = confirming_threshold;
+    }
+
     /// Aggregate multiple signals into a composite signal
     /// 
     /// Returns Some(CompositeSignal) if signals meet criteria, None otherwise
     pub fn aggregate(&self, signals: Vec<Signal>) -> Option<CompositeSignal> {
-        if signals.is_empty() {
-            return None;
-        }
+        self.aggregate_or_reject(signals).ok()
+    }
 
+    /// `aggregate`, with the failed criterion on rejection
+    pub fn aggregate_or_reject(&self, signals: Vec<Signal>) -> Result<CompositeSignal, RejectionReason> {
         // 1. Find primary signal (strongest absolute signal)
-        let primary = signals.iter()
-            .max_by(|a, b| {
+        let (primary_idx, primary) = signals.iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| {
                 a.abs_strength()
                     .partial_cmp(&b.abs_strength())
                     .unwrap()
-            })?
-            .clone();
+            })
+            .map(|(idx, signal)| (idx, signal.clone()))
+            .ok_or(RejectionReason::PrimaryTooWeak)?;
 
         // 2. Check if primary meets threshold
         if primary.abs_strength() < self.primary_threshold {
-            return None;
+            return Err(RejectionReason::PrimaryTooWeak);
         }
 
-        // 3. Find confirming signals (same direction, above threshold)
-        let confirming: Vec<Signal> = signals.iter()
-            .filter(|s| {
-                s.direction == primary.direction
+        // 3. Find confirming signals (same direction, above threshold).
+        // Detectors fed by the same exchange event share a timestamp, so
+        // the primary is excluded by position.
+        let confirming_idx: Vec<usize> = signals.iter()
+            .enumerate()
+            .filter(|(idx, s)| {
+                *idx != primary_idx  // Don't count self
+                    && s.direction == primary.direction
                     && s.abs_strength() >= self.confirming_threshold
-                    && s.timestamp != primary.timestamp  // Don't count self
             })
-            .cloned()
+            .map(|(idx, _)| idx)
             .collect();
+        let confirming: Vec<Signal> = confirming_idx.iter().map(|idx| signals[*idx].clone()).collect();
 
         // 4. Check minimum confirming signals
         if confirming.len() < self.min_confirming {
-            return None;
+            return Err(RejectionReason::InsufficientConfirming);
         }
 
         // 5. Calculate composite confidence
@@ -110,19 +328,102 @@ impl SignalAggregator {
         // 6. Calculate overall strength (weighted average)
         let overall_strength = self.calculate_overall_strength(&primary, &confirming);
 
-        Some(CompositeSignal {
+        let explanation = self.explain(&signals, primary_idx, &confirming_idx, confidence);
+
+        // Stamped with the primary's exchange event time, not ours
+        Ok(CompositeSignal {
             direction: primary.direction,
             overall_strength,
             confidence,
-            timestamp: SystemTime::now(),
+            timestamp: primary.timestamp,
             primary: primary.clone(),
             confirming,
+            explanation,
         })
     }
 
+    /// Record every input signal's role and weight plus the aggregator's checks
+    ///
+    /// Weights mirror `calculate_overall_strength` and
+    /// `calculate_composite_confidence`.
+    fn explain(
+        &self,
+        signals: &[Signal],
+        primary_idx: usize,
+        confirming_idx: &[usize],
+        confidence: f64,
+    ) -> SignalExplanation {
+        let primary = &signals[primary_idx];
+        let confirming_share = 1.0 / confirming_idx.len().max(1) as f64;
+        // Under max, the one signal the result came from
+        let max_confidence_idx = std::iter::once(primary_idx)
+            .chain(confirming_idx.iter().copied())
+            .max_by(|a, b| signals[*a].confidence.total_cmp(&signals[*b].confidence))
+            .unwrap_or(primary_idx);
+        let mut explanation = SignalExplanation::default();
+
+        for (idx, signal) in signals.iter().enumerate() {
+            let role = if idx == primary_idx {
+                SignalRole::Primary
+            } else if confirming_idx.contains(&idx) {
+                SignalRole::Confirming
+            } else {
+                SignalRole::Ignored
+            };
+            let strength_weight = match (role, self.composite.strength) {
+                (SignalRole::Ignored, _) => 0.0,
+                (SignalRole::Primary, StrengthCombination::WeightedSum) => self.composite.strength_weights.primary,
+                (SignalRole::Confirming, StrengthCombination::WeightedSum) => {
+                    self.composite.strength_weights.confirming * confirming_share
+                }
+                (SignalRole::Primary, StrengthCombination::Max) => 1.0,
+                (SignalRole::Confirming, StrengthCombination::Max) => 0.0,
+            };
+            let confidence_weight = match (role, self.composite.confidence) {
+                (SignalRole::Ignored, _) => 0.0,
+                (SignalRole::Primary, ConfidenceCombination::WeightedSum) => self.composite.confidence_weights.primary,
+                (SignalRole::Confirming, ConfidenceCombination::WeightedSum) => {
+                    self.composite.confidence_weights.confirming * confirming_share
+                }
+                (_, ConfidenceCombination::Max) => if idx == max_confidence_idx { 1.0 } else { 0.0 },
+                (_, ConfidenceCombination::ProbabilisticOr) => 1.0,
+            };
+
+            explanation.sources.push(SourceExplanation {
+                source: signal_source(signal),
+                role,
+                direction: signal.direction,
+                strength: signal.strength,
+                confidence: signal.confidence,
+                strength_weight,
+                confidence_weight,
+                components: signal.components.iter()
+                    .map(|c| (c.name.clone(), c.value))
+                    .collect(),
+            });
+        }
+
+        explanation.check(
+            "primary_threshold",
+            true,
+            format!("|{:.2}| >= {:.2}", primary.strength, self.primary_threshold),
+        );
+        explanation.check(
+            "min_confirming",
+            true,
+            format!("{} >= {}", confirming_idx.len(), self.min_confirming),
+        );
+        explanation.check(
+            "min_confidence",
+            confidence >= CompositeSignal::MIN_CONFIDENCE,
+            format!("{:.2} vs {:.2}", confidence, CompositeSignal::MIN_CONFIDENCE),
+        );
+        explanation
+    }
+
     /// Calculate composite confidence
     /// 
-    /// Factors:
+    /// Weighted sum (default) factors:
     /// - Primary signal confidence (40% weight)
     /// - Number of confirming signals (30% weight)
     /// - Average confirming signal confidence (30% weight)
@@ -131,32 +432,49 @@ impl SignalAggregator {
         primary: &Signal,
         confirming: &[Signal],
     ) -> f64 {
-        // Primary signal confidence
-        let primary_conf = primary.confidence * 0.4;
-
-        // Confirming count factor (more confirming = higher confidence)
-        let count_factor = (confirming.len() as f64 / (self.min_confirming as f64 + 2.0)).min(1.0);
-        let count_conf = count_factor * 0.3;
-
-        // Average confirming confidence
-        let avg_confirming_conf = if confirming.is_empty() {
-            0.0
-        } else {
-            confirming.iter().map(|s| s.confidence).sum::<f64>() / confirming.len() as f64
-        };
-        let confirming_conf = avg_confirming_conf * 0.3;
-
-        (primary_conf + count_conf + confirming_conf).min(1.0)
+        let sources = std::iter::once(primary).chain(confirming).map(|s| s.confidence);
+
+        match self.composite.confidence {
+            ConfidenceCombination::WeightedSum => {
+                let weights = &self.composite.confidence_weights;
+
+                // Primary signal confidence
+                let primary_conf = primary.confidence * weights.primary;
+
+                // Confirming count factor (more confirming = higher confidence)
+                let count_factor = (confirming.len() as f64 / (self.min_confirming as f64 + 2.0)).min(1.0);
+                let count_conf = count_factor * weights.count;
+
+                // Average confirming confidence
+                let avg_confirming_conf = if confirming.is_empty() {
+                    0.0
+                } else {
+                    confirming.iter().map(|s| s.confidence).sum::<f64>() / confirming.len() as f64
+                };
+                let confirming_conf = avg_confirming_conf * weights.confirming;
+
+                (primary_conf + count_conf + confirming_conf).min(1.0)
+            }
+            ConfidenceCombination::Max => sources.fold(0.0, f64::max).min(1.0),
+            ConfidenceCombination::ProbabilisticOr => {
+                1.0 - sources.map(|c| 1.0 - c.clamp(0.0, 1.0)).product::<f64>()
+            }
+        }
     }
 
-    /// Calculate overall signal strength (weighted average)
+    /// Calculate overall signal strength
     fn calculate_overall_strength(
         &self,
         primary: &Signal,
         confirming: &[Signal],
     ) -> f64 {
-        // Primary gets 60% weight
-        let primary_weighted = primary.strength * 0.6;
+        if self.composite.strength == StrengthCombination::Max {
+            return primary.strength;
+        }
+        let weights = &self.composite.strength_weights;
+
+        // Primary gets 60% weight by default
+        let primary_weighted = primary.strength * weights.primary;
 
         // Confirming get 40% weight (split evenly)
         let confirming_weighted = if confirming.is_empty() {
@@ -164,7 +482,7 @@ impl SignalAggregator {
         } else {
             let avg_strength = confirming.iter().map(|s| s.strength).sum::<f64>() 
                 / confirming.len() as f64;
-            avg_strength * 0.4
+            avg_strength * weights.confirming
         };
 
         primary_weighted + confirming_weighted
@@ -174,7 +492,6 @@ impl SignalAggregator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::data::SignalComponent;
 
     fn create_signal(strength: f64, direction: Side, confidence: f64) -> Signal {
         Signal {
@@ -204,6 +521,12 @@ mod tests {
         assert_eq!(sig.confirming.len(), 2);
         assert!(sig.confidence > 0.5);
         assert!(sig.is_tradeable(2));
+
+        let explanation = &sig.explanation;
+        assert_eq!(explanation.sources.len(), 3);
+        assert_eq!(explanation.sources[0].role, SignalRole::Primary);
+        assert!((explanation.sources[1].strength_weight - 0.2).abs() < 1e-9);
+        assert!(explanation.failed_check().is_none());
     }
 
     #[test]
@@ -216,8 +539,8 @@ mod tests {
             create_signal(2.5, Side::Buy, 0.7),
         ];
 
-        let composite = aggregator.aggregate(signals);
-        assert!(composite.is_none());
+        let composite = aggregator.aggregate_or_reject(signals);
+        assert_eq!(composite.err(), Some(RejectionReason::PrimaryTooWeak));
     }
 
     #[test]
@@ -229,8 +552,8 @@ mod tests {
             create_signal(2.0, Side::Buy, 0.6),   // Only 1 confirming
         ];
 
-        let composite = aggregator.aggregate(signals);
-        assert!(composite.is_none());  // Needs 2 confirming
+        let composite = aggregator.aggregate_or_reject(signals);
+        assert_eq!(composite.err(), Some(RejectionReason::InsufficientConfirming));  // Needs 2 confirming
     }
 
     #[test]
@@ -285,6 +608,58 @@ mod tests {
         assert!(composite.confidence > 0.7);
     }
 
+    #[test]
+    fn test_same_event_signals_confirm() {
+        let aggregator = SignalAggregator::new(3.0, 1.5, 1);
+        let event_time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+
+        // Imbalance and basis evaluated on the same depth update
+        let mut primary = create_signal(4.0, Side::Buy, 0.8);
+        let mut confirming = create_signal(2.0, Side::Buy, 0.6);
+        primary.timestamp = event_time;
+        confirming.timestamp = event_time;
+
+        let composite = aggregator.aggregate(vec![primary, confirming]).unwrap();
+        assert_eq!(composite.confirming.len(), 1);
+        assert_eq!(composite.timestamp, event_time);
+        assert_eq!(composite.age(event_time + Duration::from_millis(40)), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_configurable_combinations() {
+        let signals = vec![
+            create_signal(4.0, Side::Buy, 0.6),
+            create_signal(2.0, Side::Buy, 0.5),
+        ];
+
+        let default = SignalAggregator::new(3.0, 1.5, 1).aggregate(signals.clone()).unwrap();
+        // 0.4 * 0.6 + 0.3 * (1 / 3) + 0.3 * 0.5, and 0.6 * 4 + 0.4 * 2
+        assert!((default.confidence - 0.49).abs() < 1e-9);
+        assert!((default.overall_strength - 3.2).abs() < 1e-9);
+        assert!(!default.is_tradeable(1));
+
+        let aggregator = SignalAggregator::new(3.0, 1.5, 1).with_composite(CompositeConfig {
+            confidence: ConfidenceCombination::ProbabilisticOr,
+            strength: StrengthCombination::Max,
+            ..Default::default()
+        });
+        let composite = aggregator.aggregate(signals.clone()).unwrap();
+        // 1 - 0.4 * 0.5
+        assert!((composite.confidence - 0.8).abs() < 1e-9);
+        assert_eq!(composite.overall_strength, 4.0);
+        assert!(composite.is_tradeable(1));
+        assert_eq!(composite.explanation.sources[1].strength_weight, 0.0);
+        assert_eq!(composite.explanation.sources[1].confidence_weight, 1.0);
+
+        let aggregator = SignalAggregator::new(3.0, 1.5, 1).with_composite(CompositeConfig {
+            confidence: ConfidenceCombination::Max,
+            ..Default::default()
+        });
+        let composite = aggregator.aggregate(signals).unwrap();
+        assert_eq!(composite.confidence, 0.6);
+        assert_eq!(composite.explanation.sources[0].confidence_weight, 1.0);
+    }
+
     #[test]
     fn test_empty_signals() {
         let aggregator = SignalAggregator::new(3.0, 1.5, 2);
@@ -1,7 +1,65 @@
 use crate::data::{Trade, Signal, SignalComponent, Side};
+use crate::storage::{DetectorObservation, ObservationRecorder};
+use crate::strategy::signals::TradeTape;
 use rust_decimal::Decimal;
-use std::collections::VecDeque;
-use std::time::{SystemTime, Duration};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+
+/// Flow analyzer parameters (`[strategy.flow]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlowConfig {
+    /// Number of trades to analyze
+    pub window_size: usize,
+    /// Time window in milliseconds
+    pub time_window_ms: u64,
+    /// Flow imbalance threshold (0.6 = 60% one-sided)
+    pub threshold: f64,
+    /// How older trades are discounted
+    pub decay: FlowDecay,
+    /// Weight kept per newer trade with `decay = "per_trade"`
+    pub decay_factor: f64,
+    /// Trade age at which weight halves with `decay = "time"`
+    pub half_life_ms: u64,
+    /// What a trade's volume is measured in
+    pub volume: FlowVolume,
+}
+
+impl Default for FlowConfig {
+    fn default() -> Self {
+        Self {
+            window_size: 20,
+            time_window_ms: 5000,
+            threshold: 0.6,
+            decay: FlowDecay::PerTrade,
+            decay_factor: 0.95,
+            half_life_ms: 1000,
+            volume: FlowVolume::Quantity,
+        }
+    }
+}
+
+/// Discount applied to older trades in the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowDecay {
+    /// `decay_factor` per newer trade, regardless of time between them
+    PerTrade,
+    /// Exponential in trade age (exchange timestamps) with `half_life_ms`;
+    /// a burst counts as much as its trades, a lull decays
+    Time,
+}
+
+/// Volume measure for the buy/sell split
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowVolume {
+    /// Base asset quantity
+    Quantity,
+    /// Price × quantity, comparable across symbols and price levels
+    Notional,
+}
 
 /// Aggressive flow analyzer
 /// 
@@ -15,20 +73,28 @@ use std::time::{SystemTime, Duration};
 /// 4. Apply time decay to give more weight to recent trades
 /// 5. Generate signal if imbalance exceeds threshold
 pub struct FlowAnalyzer {
-    /// Recent trades window
-    trades: VecDeque<Trade>,
+    /// Recent trades window (time and count bounded)
+    trades: TradeTape,
     
     /// Maximum number of trades to analyze
     window_size: usize,
     
-    /// Time window in milliseconds
-    time_window_ms: u64,
-    
     /// Flow imbalance threshold (e.g., 0.6 = 60% one-sided)
     threshold: f64,
     
-    /// Decay factor for time weighting (e.g., 0.95 = 5% decay per trade)
+    /// How older trades are discounted
+    decay: FlowDecay,
+
+    /// Decay factor for per-trade weighting (e.g., 0.95 = 5% decay per trade)
     decay_factor: f64,
+
+    /// Half-life for time weighting
+    half_life: Duration,
+
+    volume: FlowVolume,
+
+    /// Optional sink for raw flow observations (research)
+    recorder: Option<ObservationRecorder>,
 }
 
 impl FlowAnalyzer {
@@ -40,23 +106,62 @@ impl FlowAnalyzer {
     /// * `threshold` - Flow imbalance threshold (typically 0.5-0.7)
     pub fn new(window_size: usize, time_window_ms: u64, threshold: f64) -> Self {
         Self {
-            trades: VecDeque::with_capacity(window_size),
+            trades: TradeTape::new(Duration::from_millis(time_window_ms))
+                .with_max_trades(window_size),
             window_size,
-            time_window_ms,
             threshold,
+            decay: FlowDecay::PerTrade,
             decay_factor: 0.95,  // Recent trades have more weight
+            half_life: Duration::from_secs(1),
+            volume: FlowVolume::Quantity,
+            recorder: None,
+        }
+    }
+
+    /// Create analyzer from config section
+    pub fn from_config(config: &FlowConfig) -> Self {
+        let analyzer = Self::new(config.window_size, config.time_window_ms, config.threshold)
+            .with_volume(config.volume);
+        match config.decay {
+            FlowDecay::PerTrade => analyzer.with_decay_factor(config.decay_factor),
+            FlowDecay::Time => analyzer.with_time_decay(Duration::from_millis(config.half_life_ms)),
         }
     }
 
+    /// Discount each older trade by `decay_factor` (1.0 = no decay)
+    pub fn with_decay_factor(mut self, decay_factor: f64) -> Self {
+        self.decay = FlowDecay::PerTrade;
+        self.decay_factor = decay_factor;
+        self
+    }
+
+    /// Discount trades by age relative to the newest trade
+    pub fn with_time_decay(mut self, half_life: Duration) -> Self {
+        self.decay = FlowDecay::Time;
+        self.half_life = half_life;
+        self
+    }
+
+    /// Measure volume as quantity or notional
+    pub fn with_volume(mut self, volume: FlowVolume) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Stream every evaluation (volumes, imbalance) to a recorder
+    pub fn with_recorder(mut self, recorder: ObservationRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     /// Process new trade and calculate flow signal
     pub fn process_trade(&mut self, trade: Trade) -> Option<Signal> {
-        // 1. Add trade to window
-        self.trades.push_back(trade.clone());
+        let trade_time = trade.timestamp;
 
-        // 2. Remove old trades (both by count and time)
-        self.cleanup_old_trades();
+        // 1. Add trade to window (tape evicts old trades by time and count)
+        self.trades.push(trade);
 
-        // 3. Need minimum trades before generating signals
+        // 2. Need minimum trades before generating signals
         if self.trades.len() < self.window_size / 4 {
             return None;
         }
@@ -71,7 +176,18 @@ impl FlowAnalyzer {
 
         // 5. Calculate flow imbalance (-1.0 to 1.0)
         let imbalance = (buy_volume - sell_volume) / total_volume;
-        let imbalance_f64 = imbalance.to_string().parse::<f64>().ok()?;
+        let imbalance_f64 = imbalance.to_f64()?;
+        // Volumes with no f64 value give no signal (nor a recorded sample)
+        let buy_volume_f64 = buy_volume.to_f64()?;
+        let sell_volume_f64 = sell_volume.to_f64()?;
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record(DetectorObservation::new("flow", trade_time)
+                .with("buy_volume", buy_volume_f64)
+                .with("sell_volume", sell_volume_f64)
+                .with("imbalance", imbalance_f64)
+                .with("trade_count", self.trades.len() as f64));
+        }
 
         // 6. Check threshold
         if imbalance_f64.abs() < self.threshold {
@@ -94,8 +210,8 @@ impl FlowAnalyzer {
 
         // 10. Build signal components
         let components = vec![
-            SignalComponent::new("buy_volume", buy_volume.to_string().parse().unwrap(), 1.0),
-            SignalComponent::new("sell_volume", sell_volume.to_string().parse().unwrap(), 1.0),
+            SignalComponent::new("buy_volume", buy_volume_f64, 1.0),
+            SignalComponent::new("sell_volume", sell_volume_f64, 1.0),
             SignalComponent::new("imbalance", imbalance_f64, 1.0),
             SignalComponent::new("trade_count", self.trades.len() as f64, 0.0),
         ];
@@ -104,7 +220,7 @@ impl FlowAnalyzer {
             strength,
             direction,
             confidence,
-            timestamp: SystemTime::now(),
+            timestamp: trade_time,
             components,
         })
     }
@@ -115,19 +231,34 @@ impl FlowAnalyzer {
         let mut buy_volume = Decimal::ZERO;
         let mut sell_volume = Decimal::ZERO;
         let mut weight = 1.0;
+        let newest = self.trades.iter().next_back().map(|trade| trade.timestamp);
 
         // Iterate from newest to oldest
         for trade in self.trades.iter().rev() {
-            let weighted_qty = trade.quantity * Decimal::from_f64_retain(weight).unwrap();
+            if self.decay == FlowDecay::Time {
+                let age = newest
+                    .and_then(|newest| newest.duration_since(trade.timestamp).ok())
+                    .unwrap_or(Duration::ZERO);
+                let half_lives = age.as_secs_f64() / self.half_life.as_secs_f64().max(1e-9);
+                weight = 0.5f64.powf(half_lives);
+            }
+
+            let volume = match self.volume {
+                FlowVolume::Quantity => trade.quantity,
+                FlowVolume::Notional => trade.quantity * trade.price,
+            };
+            let weighted_volume = volume * Decimal::from_f64_retain(weight).unwrap_or(Decimal::ZERO);
 
             if trade.is_aggressive_buy() {
-                buy_volume += weighted_qty;
+                buy_volume += weighted_volume;
             } else if trade.is_aggressive_sell() {
-                sell_volume += weighted_qty;
+                sell_volume += weighted_volume;
             }
 
             // Apply decay for older trades
-            weight *= self.decay_factor;
+            if self.decay == FlowDecay::PerTrade {
+                weight *= self.decay_factor;
+            }
         }
 
         (buy_volume, sell_volume)
@@ -145,32 +276,13 @@ impl FlowAnalyzer {
         (count_factor * 0.3 + imbalance_factor * 0.7).min(1.0)
     }
 
-    /// Remove trades that are too old (by time or count)
-    fn cleanup_old_trades(&mut self) {
-        let cutoff_time = SystemTime::now() - Duration::from_millis(self.time_window_ms);
-
-        // Remove by time
-        while let Some(trade) = self.trades.front() {
-            if trade.timestamp < cutoff_time {
-                self.trades.pop_front();
-            } else {
-                break;
-            }
-        }
-
-        // Remove by count
-        while self.trades.len() > self.window_size {
-            self.trades.pop_front();
-        }
-    }
-
     /// Get current flow statistics
     pub fn get_stats(&self) -> FlowStats {
         let (buy_vol, sell_vol) = self.calculate_weighted_volumes();
         let total = buy_vol + sell_vol;
 
         let imbalance = if !total.is_zero() {
-            ((buy_vol - sell_vol) / total).to_string().parse().ok()
+            ((buy_vol - sell_vol) / total).to_f64()
         } else {
             None
         };
@@ -202,6 +314,7 @@ pub struct FlowStats {
 mod tests {
     use super::*;
     use rust_decimal_macros::dec;
+    use std::time::SystemTime;
 
     fn create_buy_trade(qty: Decimal) -> Trade {
         Trade {
@@ -229,13 +342,13 @@ mod tests {
     fn test_aggressive_buying_signal() {
         let mut analyzer = FlowAnalyzer::new(20, 5000, 0.6);
 
-        // Create strong buying pressure
-        for _ in 0..15 {
-            analyzer.process_trade(create_buy_trade(dec!(1.0)));
-        }
+        // Create strong buying pressure after a few sells
         for _ in 0..5 {
             analyzer.process_trade(create_sell_trade(dec!(1.0)));
         }
+        for _ in 0..15 {
+            analyzer.process_trade(create_buy_trade(dec!(1.0)));
+        }
 
         let signal = analyzer.process_trade(create_buy_trade(dec!(1.0)));
 
@@ -249,13 +362,13 @@ mod tests {
     fn test_aggressive_selling_signal() {
         let mut analyzer = FlowAnalyzer::new(20, 5000, 0.6);
 
-        // Strong selling pressure
-        for _ in 0..15 {
-            analyzer.process_trade(create_sell_trade(dec!(1.0)));
-        }
+        // Strong selling pressure after a few buys
         for _ in 0..5 {
             analyzer.process_trade(create_buy_trade(dec!(1.0)));
         }
+        for _ in 0..15 {
+            analyzer.process_trade(create_sell_trade(dec!(1.0)));
+        }
 
         let signal = analyzer.process_trade(create_sell_trade(dec!(1.0)));
 
@@ -301,6 +414,38 @@ mod tests {
         assert!(stats.buy_volume > stats.sell_volume);
     }
 
+    #[test]
+    fn test_time_decay_and_notional_weighting() {
+        let start = SystemTime::now();
+        let trade = |side: Side, price: Decimal, ms: u64| Trade {
+            id: 1,
+            price,
+            quantity: dec!(1.0),
+            side,
+            timestamp: start + Duration::from_millis(ms),
+            is_buyer_maker: side == Side::Sell,
+        };
+
+        // One sell a half-life before one buy: buy weighs twice as much
+        let mut analyzer = FlowAnalyzer::new(20, 5000, 0.6)
+            .with_time_decay(Duration::from_millis(1000));
+        analyzer.process_trade(trade(Side::Sell, dec!(100), 0));
+        analyzer.process_trade(trade(Side::Buy, dec!(100), 1000));
+        let stats = analyzer.get_stats();
+        assert_eq!(stats.buy_volume, dec!(1.0));
+        assert_eq!(stats.sell_volume, dec!(0.5));
+
+        // Same quantity at a higher price is more notional
+        let mut analyzer = FlowAnalyzer::new(20, 5000, 0.6)
+            .with_decay_factor(1.0)
+            .with_volume(FlowVolume::Notional);
+        analyzer.process_trade(trade(Side::Sell, dec!(100), 0));
+        analyzer.process_trade(trade(Side::Buy, dec!(300), 0));
+        let stats = analyzer.get_stats();
+        assert_eq!(stats.buy_volume, dec!(300));
+        assert_eq!(stats.sell_volume, dec!(100));
+    }
+
     #[test]
     fn test_minimum_trades_required() {
         let mut analyzer = FlowAnalyzer::new(20, 5000, 0.6);
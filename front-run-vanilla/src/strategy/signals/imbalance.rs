@@ -1,7 +1,75 @@
 use crate::data::{OrderBook, Signal, SignalComponent, Side};
+use crate::strategy::signals::EvaluateBook;
+use crate::storage::{DetectorObservation, ObservationRecorder};
+use serde::{Serialize, Deserialize};
 use std::collections::VecDeque;
 use std::time::SystemTime;
 
+/// Imbalance detector window parameters (`[strategy.imbalance]`)
+///
+/// The z-score threshold is `strategy.imbalance_threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImbalanceConfig {
+    /// Number of order book levels to analyze
+    pub levels: usize,
+    /// Rolling window size for statistics
+    pub window_size: usize,
+    /// What each level contributes to its side's depth
+    pub weighting: ImbalanceWeighting,
+    /// Discount levels by exp(-distance/decay) from mid, in bps (0 = off)
+    pub distance_decay_bps: f64,
+}
+
+impl Default for ImbalanceConfig {
+    fn default() -> Self {
+        Self {
+            levels: 5,
+            window_size: 100,
+            weighting: ImbalanceWeighting::Quantity,
+            distance_decay_bps: 0.0,
+        }
+    }
+}
+
+/// Depth measure for the bid/ask ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImbalanceWeighting {
+    /// Base asset quantity (as `OrderBook::calculate_imbalance`)
+    Quantity,
+    /// Price × quantity; for low-priced symbols and books spanning many ticks
+    Notional,
+}
+
+/// Imbalance with per-level weighting
+///
+/// Raw quantity imbalance is skewed by walls parked away from the touch
+/// (round numbers) and by low-priced symbols where deep levels cost little.
+/// Implemented for `OrderBook` alongside `calculate_imbalance`, like
+/// `BookShape`.
+pub trait WeightedImbalance {
+    /// Bid/ask depth ratio over the top `levels`, each level measured by
+    /// `weighting` and discounted by distance from mid if `distance_decay_bps > 0`
+    fn weighted_imbalance(
+        &self,
+        levels: usize,
+        weighting: ImbalanceWeighting,
+        distance_decay_bps: f64,
+    ) -> Option<f64>;
+}
+
+impl WeightedImbalance for OrderBook {
+    fn weighted_imbalance(
+        &self,
+        levels: usize,
+        weighting: ImbalanceWeighting,
+        distance_decay_bps: f64,
+    ) -> Option<f64> {
+        self.evaluate(levels, |view| view.weighted_imbalance(levels, weighting, distance_decay_bps))
+    }
+}
+
 /// Order book imbalance detector
 /// 
 /// PRIMARY SIGNAL: Detects when bid/ask depth ratio deviates significantly
@@ -27,6 +95,16 @@ pub struct ImbalanceDetector {
     
     /// Minimum samples needed before generating signals
     min_samples: usize,
+
+    /// Optional sink for raw ratio/z-score observations (research)
+    recorder: Option<ObservationRecorder>,
+
+    /// Z-score of the latest evaluation (None while warming up)
+    last_z_score: Option<f64>,
+
+    /// Level weighting for the depth ratio
+    weighting: ImbalanceWeighting,
+    distance_decay_bps: f64,
 }
 
 impl ImbalanceDetector {
@@ -43,15 +121,52 @@ impl ImbalanceDetector {
             window_size,
             threshold,
             min_samples: window_size / 2,  // Need at least 50% of window
+            recorder: None,
+            last_z_score: None,
+            weighting: ImbalanceWeighting::Quantity,
+            distance_decay_bps: 0.0,
         }
     }
 
+    /// Create detector from config section and z-score threshold
+    pub fn from_config(config: &ImbalanceConfig, threshold: f64) -> Self {
+        Self::new(config.levels, config.window_size, threshold)
+            .with_weighting(config.weighting, config.distance_decay_bps)
+    }
+
+    /// Weight levels by notional and/or distance from mid
+    pub fn with_weighting(mut self, weighting: ImbalanceWeighting, distance_decay_bps: f64) -> Self {
+        self.weighting = weighting;
+        self.distance_decay_bps = distance_decay_bps;
+        self
+    }
+
+    /// Stream every evaluation (ratio, mean, stddev, z-score) to a recorder
+    pub fn with_recorder(mut self, recorder: ObservationRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Change the z-score threshold (kept equal to the aggregator's
+    /// primary threshold by online calibration)
+    pub fn set_threshold(&mut self, threshold: f64) {
+        self.threshold = threshold;
+    }
+
     /// Calculate imbalance signal from current order book state
     /// 
     /// Returns Some(Signal) if imbalance exceeds threshold, None otherwise
     pub fn calculate_signal(&mut self, orderbook: &OrderBook) -> Option<Signal> {
-        // 1. Calculate current imbalance ratio
-        let ratio = orderbook.calculate_imbalance(self.levels)?;
+        self.calculate_signal_at(orderbook, SystemTime::now())
+    }
+
+    /// `calculate_signal` stamped with the exchange time of the book update
+    /// (replay time in backtests)
+    pub fn calculate_signal_at(&mut self, orderbook: &OrderBook, event_time: SystemTime) -> Option<Signal> {
+        // 1. Calculate current imbalance ratio (mid and depth from one copy of the book)
+        let ratio = orderbook.evaluate(self.levels, |view| {
+            view.weighted_imbalance(self.levels, self.weighting, self.distance_decay_bps)
+        })?;
 
         // 2. Add to history
         self.history.push_back(ratio);
@@ -60,7 +175,10 @@ impl ImbalanceDetector {
         }
 
         // 3. Need minimum samples before generating signals
+        self.last_z_score = None;
         if self.history.len() < self.min_samples {
+            self.record(DetectorObservation::new("imbalance", event_time)
+                .with("ratio", ratio));
             return None;
         }
 
@@ -68,13 +186,22 @@ impl ImbalanceDetector {
         let mean = self.calculate_mean();
         let stddev = self.calculate_stddev(mean);
 
+        let mut observation = DetectorObservation::new("imbalance", event_time)
+            .with("ratio", ratio)
+            .with("mean", mean)
+            .with("stddev", stddev);
+
         // Avoid division by zero
         if stddev < 1e-6 {
+            self.record(observation);
             return None;
         }
 
         // 5. Calculate z-score (how many standard deviations from mean)
         let z_score = (ratio - mean) / stddev;
+        self.last_z_score = Some(z_score);
+        observation = observation.with("z_score", z_score);
+        self.record(observation);
 
         // 6. Check if signal exceeds threshold
         if z_score.abs() < self.threshold {
@@ -106,11 +233,18 @@ impl ImbalanceDetector {
             strength: z_score,
             direction,
             confidence,
-            timestamp: SystemTime::now(),
+            timestamp: event_time,
             components,
         })
     }
 
+    /// Forward observation to recorder, if any
+    fn record(&self, observation: DetectorObservation) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(observation);
+        }
+    }
+
     /// Calculate mean of history
     fn calculate_mean(&self) -> f64 {
         if self.history.is_empty() {
@@ -132,6 +266,12 @@ impl ImbalanceDetector {
         variance.sqrt()
     }
 
+    /// Z-score of the latest evaluation, signal or not (None until enough
+    /// history); used to tell when an entry's imbalance has reverted
+    pub fn last_z_score(&self) -> Option<f64> {
+        self.last_z_score
+    }
+
     /// Get current statistics for debugging
     pub fn get_stats(&self) -> ImbalanceStats {
         let mean = self.calculate_mean();
@@ -148,6 +288,7 @@ impl ImbalanceDetector {
     /// Reset the detector (clears history)
     pub fn reset(&mut self) {
         self.history.clear();
+        self.last_z_score = None;
     }
 }
 
@@ -227,8 +368,8 @@ mod tests {
             
             let signal = detector.calculate_signal(&ob);
             // Should not generate signal for balanced book
-            if signal.is_some() {
-                assert!(signal.unwrap().strength.abs() < 3.0);
+            if let Some(signal) = signal {
+                assert!(signal.strength.abs() < 3.0);
             }
         }
     }
@@ -246,6 +387,36 @@ mod tests {
         assert!(signal.is_none());
     }
 
+    #[test]
+    fn test_observations_recorded() {
+        let (recorder, mut rx) = ObservationRecorder::channel(16);
+        let mut detector = ImbalanceDetector::new(5, 10, 3.0).with_recorder(recorder);
+        let ob = OrderBook::new("BTCUSDT");
+
+        ob.update_level(Side::Buy, dec!(100.0), dec!(5.0)).unwrap();
+        ob.update_level(Side::Sell, dec!(101.0), dec!(5.0)).unwrap();
+
+        for _ in 0..6 {
+            detector.calculate_signal(&ob);
+        }
+
+        // Warm-up observations carry only the raw ratio
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.detector, "imbalance");
+        assert!(first.values.contains_key("ratio"));
+        assert!(!first.values.contains_key("mean"));
+
+        // Every evaluation is recorded, signal or not
+        let mut count = 1;
+        while let Ok(obs) = rx.try_recv() {
+            count += 1;
+            if count > 5 {
+                assert!(obs.values.contains_key("mean"));
+            }
+        }
+        assert_eq!(count, 6);
+    }
+
     #[test]
     fn test_statistics() {
         let mut detector = ImbalanceDetector::new(5, 100, 3.0);
@@ -263,4 +434,24 @@ mod tests {
         // Mean should be around 1.0 for balanced book
         assert!((stats.mean - 1.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_weighted_imbalance() {
+        let ob = OrderBook::new("BTCUSDT");
+        ob.update_level(Side::Buy, dec!(100.0), dec!(1.0)).unwrap();
+        ob.update_level(Side::Buy, dec!(90.0), dec!(10.0)).unwrap();  // Wall far from mid
+        ob.update_level(Side::Sell, dec!(101.0), dec!(1.0)).unwrap();
+        ob.update_level(Side::Sell, dec!(102.0), dec!(1.0)).unwrap();
+
+        let quantity = ob.weighted_imbalance(5, ImbalanceWeighting::Quantity, 0.0).unwrap();
+        assert!((quantity - 5.5).abs() < 1e-9);
+
+        // The cheap bid wall counts for less notional
+        let notional = ob.weighted_imbalance(5, ImbalanceWeighting::Notional, 0.0).unwrap();
+        assert!((notional - 1000.0 / 203.0).abs() < 1e-9);
+
+        // With distance decay the wall 10% away barely counts
+        let decayed = ob.weighted_imbalance(5, ImbalanceWeighting::Quantity, 10.0).unwrap();
+        assert!(decayed < 1.0);
+    }
 }
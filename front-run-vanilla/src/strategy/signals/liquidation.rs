@@ -0,0 +1,180 @@
+use crate::data::{Signal, SignalComponent, Side};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Liquidation cascade detector settings (`[liquidation]`)
+///
+/// Needs `exchange.streams.force_order`; without the stream it never fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiquidationConfig {
+    /// Liquidations older than this (exchange time) drop out of the cluster
+    pub window_ms: u64,
+    /// Notional (quote) liquidated on the dominant side needed to fire
+    pub min_notional: f64,
+    /// Liquidations needed on the dominant side, so one large order is not a cascade
+    pub min_count: usize,
+    /// Dominant side's share of the window's notional (0.5 - 1.0)
+    pub min_dominance: f64,
+    /// Signal strength at exactly `min_notional`; scales linearly above it
+    pub strength_at_threshold: f64,
+}
+
+impl Default for LiquidationConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 5_000,
+            min_notional: 250_000.0,
+            min_count: 3,
+            min_dominance: 0.8,
+            strength_at_threshold: 2.0,
+        }
+    }
+}
+
+/// Liquidation cascade detector
+///
+/// CONFIRMING SIGNAL: forced orders are market orders nobody chose to
+/// send, so a burst of them on one side moves price through the book and
+/// triggers the next layer of stops and liquidations. A same-direction
+/// book signal during a cascade is the move we want; an opposite one is
+/// usually the book being run over, and the (stronger) cascade signal
+/// taking the primary slot keeps it from being traded.
+///
+/// Algorithm:
+/// 1. Notional of each forced order (`avg_price * filled_qty`) by side
+/// 2. Keep those within `window_ms` of the latest event time
+/// 3. Fire in the direction of the forced orders when the dominant side
+///    clears the notional, count and share thresholds
+///
+/// The venue sends at most one liquidation per symbol per second, so the
+/// window totals are a lower bound and thresholds should be set with that
+/// in mind.
+pub struct LiquidationDetector {
+    window: VecDeque<(u64, Side, f64)>,
+    window_ms: u64,
+    min_notional: f64,
+    min_count: usize,
+    min_dominance: f64,
+    strength_at_threshold: f64,
+    last_notional: (f64, f64),
+}
+
+impl LiquidationDetector {
+    pub fn new(window_ms: u64, min_notional: f64, min_count: usize, min_dominance: f64) -> Self {
+        Self {
+            window: VecDeque::new(),
+            window_ms,
+            min_notional,
+            min_count: min_count.max(1),
+            min_dominance,
+            strength_at_threshold: LiquidationConfig::default().strength_at_threshold,
+            last_notional: (0.0, 0.0),
+        }
+    }
+
+    pub fn from_config(config: &LiquidationConfig) -> Self {
+        let mut detector = Self::new(config.window_ms, config.min_notional, config.min_count, config.min_dominance);
+        detector.strength_at_threshold = config.strength_at_threshold;
+        detector
+    }
+
+    /// Record a forced order (side of the order itself: Sell = long liquidated)
+    pub fn process_liquidation(&mut self, side: Side, price: Decimal, quantity: Decimal, event_time_ms: u64) {
+        let notional = (price * quantity).abs().to_f64().unwrap_or(0.0);
+        if notional <= 0.0 {
+            return;
+        }
+        self.window.push_back((event_time_ms, side, notional));
+    }
+
+    /// Signal if the liquidations within the window of `event_time` form a cascade
+    pub fn calculate_signal_at(&mut self, event_time: SystemTime) -> Option<Signal> {
+        let now_ms = event_time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let cutoff = now_ms.saturating_sub(self.window_ms);
+        while self.window.front().is_some_and(|(time, _, _)| *time < cutoff) {
+            self.window.pop_front();
+        }
+
+        let (mut buy, mut sell) = ((0.0, 0usize), (0.0, 0usize));
+        for (_, side, notional) in &self.window {
+            let total = match side {
+                Side::Buy => &mut buy,
+                Side::Sell => &mut sell,
+            };
+            total.0 += notional;
+            total.1 += 1;
+        }
+        self.last_notional = (buy.0, sell.0);
+
+        let (direction, (notional, count)) = if buy.0 >= sell.0 {
+            (Side::Buy, buy)
+        } else {
+            (Side::Sell, sell)
+        };
+        let share = notional / (buy.0 + sell.0).max(f64::EPSILON);
+        if notional < self.min_notional || count < self.min_count || share < self.min_dominance {
+            return None;
+        }
+
+        let magnitude = self.strength_at_threshold * notional / self.min_notional.max(f64::EPSILON);
+        let strength = match direction {
+            Side::Buy => magnitude,
+            Side::Sell => -magnitude,
+        };
+        // Full confidence at twice the threshold with one side only
+        let confidence = (share * notional / (2.0 * self.min_notional.max(f64::EPSILON))).min(1.0);
+
+        Some(Signal {
+            strength,
+            direction,
+            confidence,
+            timestamp: event_time,
+            components: vec![
+                SignalComponent::new("liquidation_notional", notional, 1.0),
+                SignalComponent::new("count", count as f64, 0.0),
+                SignalComponent::new("dominance", share, 0.0),
+            ],
+        })
+    }
+
+    /// Notional liquidated in the window at the latest evaluation, (forced buys, forced sells)
+    pub fn last_notional(&self) -> (f64, f64) {
+        self.last_notional
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+
+    fn at(ms: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_clustered_long_liquidations_signal_sell() {
+        let mut detector = LiquidationDetector::new(5_000, 250_000.0, 3, 0.8);
+
+        // Two forced sells worth 200k: below both notional and count
+        detector.process_liquidation(Side::Sell, dec!(50000), dec!(2), 1_000);
+        detector.process_liquidation(Side::Sell, dec!(50000), dec!(2), 2_000);
+        assert!(detector.calculate_signal_at(at(2_000)).is_none());
+
+        // A third at 3s clears both; a small forced buy does not break dominance
+        detector.process_liquidation(Side::Sell, dec!(50000), dec!(2), 3_000);
+        detector.process_liquidation(Side::Buy, dec!(50000), dec!(0.5), 3_500);
+        let signal = detector.calculate_signal_at(at(3_500)).unwrap();
+        assert_eq!(signal.direction, Side::Sell);
+        assert!((signal.strength + 2.0 * 300_000.0 / 250_000.0).abs() < 1e-9);
+        assert_eq!(detector.last_notional(), (25_000.0, 300_000.0));
+
+        // The first two age out of the window and the cluster is gone
+        assert!(detector.calculate_signal_at(at(7_500)).is_none());
+    }
+}
@@ -1,7 +1,20 @@
 pub mod imbalance;
 pub mod flow;
 pub mod composite;
+pub mod tape;
+pub mod basis;
+pub mod liquidation;
+pub mod shape;
+pub mod book_view;
 
-pub use imbalance::{ImbalanceDetector, ImbalanceStats};
-pub use flow::{FlowAnalyzer, FlowStats};
-pub use composite::{CompositeSignal, SignalAggregator};
+pub use imbalance::{ImbalanceDetector, ImbalanceStats, ImbalanceConfig, ImbalanceWeighting, WeightedImbalance};
+pub use flow::{FlowAnalyzer, FlowStats, FlowConfig, FlowDecay, FlowVolume};
+pub use composite::{
+    CompositeConfig, CompositeSignal, ConfidenceCombination, ConfidenceWeights, SignalAggregator,
+    SignalCheck, StrengthCombination, StrengthWeights, SignalExplanation, SignalRole, SourceExplanation,
+};
+pub use tape::{TradeTape, TapeAggregates};
+pub use basis::{BasisDetector, BasisConfig};
+pub use liquidation::{LiquidationDetector, LiquidationConfig};
+pub use shape::{BookShape, ShapeConfig};
+pub use book_view::{BookView, EvaluateBook};
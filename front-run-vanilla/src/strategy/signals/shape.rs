@@ -0,0 +1,185 @@
+use crate::data::{OrderBook, SignalComponent, Side};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+
+/// Book shape feature settings (`[strategy.shape]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShapeConfig {
+    /// Levels per side scanned for slope and concentration
+    pub levels: usize,
+    /// Top levels whose share of depth is reported as concentration
+    pub top_k: usize,
+    /// Distances from mid (bps) at which depth asymmetry is measured
+    pub distances_bps: Vec<f64>,
+}
+
+impl Default for ShapeConfig {
+    fn default() -> Self {
+        Self {
+            levels: 20,
+            top_k: 5,
+            distances_bps: vec![5.0, 10.0, 25.0],
+        }
+    }
+}
+
+/// Order book shape features
+///
+/// Imbalance looks at how much size is on each side; shape looks at where
+/// it sits. A steep book (lots of size right at the touch) absorbs flow,
+/// a shallow one gets swept. Implemented for `OrderBook` so detectors and
+/// feature exports call them like any other book method.
+pub trait BookShape {
+    /// Depth-weighted slope of one side: cumulative quantity per bp from mid
+    ///
+    /// Least-squares fit through the origin of cumulative depth against
+    /// distance from mid over the top `levels`.
+    fn book_slope(&self, side: Side, levels: usize) -> Option<f64>;
+
+    /// Share of one side's depth (top `levels`) sitting in the top `top_k` levels
+    fn depth_concentration(&self, side: Side, top_k: usize, levels: usize) -> Option<f64>;
+
+    /// (bid - ask) / (bid + ask) notional within `distance_bps` of mid, in [-1, 1]
+    fn depth_asymmetry(&self, distance_bps: f64) -> Option<f64>;
+
+    /// All shape features as signal components (missing features are skipped)
+    fn shape_components(&self, config: &ShapeConfig) -> Vec<SignalComponent>;
+}
+
+impl BookShape for OrderBook {
+    fn book_slope(&self, side: Side, levels: usize) -> Option<f64> {
+        let mid = to_f64(self.get_mid_price()?)?;
+        let (bids, asks) = self.get_depth(levels);
+        let book = match side {
+            Side::Buy => bids,
+            Side::Sell => asks,
+        };
+
+        let mut cumulative = 0.0;
+        let mut sum_dc = 0.0;
+        let mut sum_dd = 0.0;
+        for level in &book {
+            let distance_bps = (to_f64(level.price)? - mid).abs() / mid * 10000.0;
+            cumulative += to_f64(level.quantity)?;
+            sum_dc += distance_bps * cumulative;
+            sum_dd += distance_bps * distance_bps;
+        }
+
+        if sum_dd <= 0.0 {
+            return None;
+        }
+        Some(sum_dc / sum_dd)
+    }
+
+    fn depth_concentration(&self, side: Side, top_k: usize, levels: usize) -> Option<f64> {
+        let (bids, asks) = self.get_depth(levels.max(top_k));
+        let book = match side {
+            Side::Buy => bids,
+            Side::Sell => asks,
+        };
+
+        let total: Decimal = book.iter().map(|level| level.quantity).sum();
+        if total.is_zero() {
+            return None;
+        }
+        let top: Decimal = book.iter().take(top_k).map(|level| level.quantity).sum();
+        to_f64(top / total)
+    }
+
+    fn depth_asymmetry(&self, distance_bps: f64) -> Option<f64> {
+        let mid = self.get_mid_price()?;
+        let band = mid * Decimal::from_f64_retain(distance_bps)? / Decimal::from(10000);
+
+        let (bid_levels, ask_levels) = self.get_book_depth_count();
+        let (bids, asks) = self.get_depth(bid_levels.max(ask_levels));
+
+        let bid_depth: Decimal = bids.iter()
+            .take_while(|level| level.price >= mid - band)
+            .map(|level| level.price * level.quantity)
+            .sum();
+        let ask_depth: Decimal = asks.iter()
+            .take_while(|level| level.price <= mid + band)
+            .map(|level| level.price * level.quantity)
+            .sum();
+
+        let total = bid_depth + ask_depth;
+        if total.is_zero() {
+            return None;
+        }
+        to_f64((bid_depth - ask_depth) / total)
+    }
+
+    fn shape_components(&self, config: &ShapeConfig) -> Vec<SignalComponent> {
+        let mut components = Vec::new();
+
+        for (side, name) in [(Side::Buy, "bid"), (Side::Sell, "ask")] {
+            if let Some(slope) = self.book_slope(side, config.levels) {
+                components.push(SignalComponent::new(&format!("{}_slope", name), slope, 0.0));
+            }
+            if let Some(share) = self.depth_concentration(side, config.top_k, config.levels) {
+                components.push(SignalComponent::new(&format!("{}_concentration", name), share, 0.0));
+            }
+        }
+
+        for distance in &config.distances_bps {
+            if let Some(asymmetry) = self.depth_asymmetry(*distance) {
+                components.push(SignalComponent::new(&format!("asymmetry_{}bps", distance), asymmetry, 0.0));
+            }
+        }
+
+        components
+    }
+}
+
+fn to_f64(value: Decimal) -> Option<f64> {
+    value.to_string().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBook {
+        let ob = OrderBook::new("BTCUSDT");
+        // Bids: heavy at the touch; asks: evenly spread
+        ob.update_level(Side::Buy, dec!(9999), dec!(6)).unwrap();
+        ob.update_level(Side::Buy, dec!(9998), dec!(1)).unwrap();
+        ob.update_level(Side::Buy, dec!(9997), dec!(1)).unwrap();
+        ob.update_level(Side::Sell, dec!(10001), dec!(2)).unwrap();
+        ob.update_level(Side::Sell, dec!(10002), dec!(2)).unwrap();
+        ob.update_level(Side::Sell, dec!(10003), dec!(2)).unwrap();
+        ob
+    }
+
+    #[test]
+    fn test_concentration_and_slope() {
+        let ob = book();
+
+        assert_eq!(ob.depth_concentration(Side::Buy, 1, 10), Some(0.75));
+        let ask_share = ob.depth_concentration(Side::Sell, 1, 10).unwrap();
+        assert!((ask_share - 1.0 / 3.0).abs() < 1e-9);
+
+        // Size piled at the touch makes the bid side steeper
+        let bid_slope = ob.book_slope(Side::Buy, 10).unwrap();
+        let ask_slope = ob.book_slope(Side::Sell, 10).unwrap();
+        assert!(bid_slope > ask_slope);
+    }
+
+    #[test]
+    fn test_asymmetry_by_distance() {
+        let ob = book();
+
+        // 1 bp of mid: only the touch levels (6 bid vs 2 ask)
+        let near = ob.depth_asymmetry(1.0).unwrap();
+        assert!((near - 0.5).abs() < 0.01, "near {}", near);
+
+        // 5 bps: every level (8 bid vs 6 ask)
+        let far = ob.depth_asymmetry(5.0).unwrap();
+        assert!((far - 1.0 / 7.0).abs() < 0.01, "far {}", far);
+
+        let components = ob.shape_components(&ShapeConfig::default());
+        assert_eq!(components.len(), 7);
+    }
+}
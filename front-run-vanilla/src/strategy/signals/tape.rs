@@ -0,0 +1,244 @@
+use crate::data::{Side, Trade};
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// Rolling aggregates over a span of the tape
+#[derive(Debug, Clone, PartialEq)]
+pub struct TapeAggregates {
+    pub buy_volume: Decimal,
+    pub sell_volume: Decimal,
+    pub notional: Decimal,
+    pub trade_count: u64,
+    pub large_trade_count: u64,
+}
+
+impl TapeAggregates {
+    pub fn total_volume(&self) -> Decimal {
+        self.buy_volume + self.sell_volume
+    }
+
+    /// Volume-weighted average price (None if no volume)
+    pub fn vwap(&self) -> Option<Decimal> {
+        let volume = self.total_volume();
+        if volume.is_zero() {
+            None
+        } else {
+            Some(self.notional / volume)
+        }
+    }
+
+    /// (buy - sell) / total, in [-1, 1]
+    pub fn imbalance(&self) -> Option<Decimal> {
+        let volume = self.total_volume();
+        if volume.is_zero() {
+            None
+        } else {
+            Some((self.buy_volume - self.sell_volume) / volume)
+        }
+    }
+}
+
+/// Cumulative totals from the start of the tape (prefix sums)
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    buy_volume: Decimal,
+    sell_volume: Decimal,
+    notional: Decimal,
+    trade_count: u64,
+    large_trade_count: u64,
+}
+
+impl Totals {
+    fn since(&self, base: &Totals) -> TapeAggregates {
+        TapeAggregates {
+            buy_volume: self.buy_volume - base.buy_volume,
+            sell_volume: self.sell_volume - base.sell_volume,
+            notional: self.notional - base.notional,
+            trade_count: self.trade_count - base.trade_count,
+            large_trade_count: self.large_trade_count - base.large_trade_count,
+        }
+    }
+}
+
+/// Recent trade history with rolling aggregates
+///
+/// Keeps trades for `retention` (measured against the newest trade's
+/// timestamp, so replayed data behaves like live) and optionally caps the
+/// count. Each entry stores prefix sums, so aggregates over the whole tape
+/// are O(1) and over any trailing window O(log n), with no rescans as
+/// trades arrive or expire.
+pub struct TradeTape {
+    entries: VecDeque<(Trade, Totals)>,
+    retention: Duration,
+    max_trades: Option<usize>,
+    large_trade_qty: Option<Decimal>,
+
+    /// Prefix sums of everything already evicted
+    evicted: Totals,
+}
+
+impl TradeTape {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            retention,
+            max_trades: None,
+            large_trade_qty: None,
+            evicted: Totals::default(),
+        }
+    }
+
+    /// Also cap the tape at `max_trades` entries
+    pub fn with_max_trades(mut self, max_trades: usize) -> Self {
+        self.max_trades = Some(max_trades);
+        self.entries.reserve(max_trades);
+        self
+    }
+
+    /// Count trades of at least `quantity` as large trades
+    pub fn with_large_trade_qty(mut self, quantity: Decimal) -> Self {
+        self.large_trade_qty = Some(quantity);
+        self
+    }
+
+    /// Append a trade and evict anything outside retention
+    pub fn push(&mut self, trade: Trade) {
+        let mut totals = self.latest_totals();
+
+        match trade.side {
+            Side::Buy => totals.buy_volume += trade.quantity,
+            Side::Sell => totals.sell_volume += trade.quantity,
+        }
+        totals.notional += trade.price * trade.quantity;
+        totals.trade_count += 1;
+        if self.large_trade_qty.is_some_and(|qty| trade.quantity >= qty) {
+            totals.large_trade_count += 1;
+        }
+
+        let newest = trade.timestamp;
+        self.entries.push_back((trade, totals));
+        self.evict(newest);
+    }
+
+    /// Aggregates over every retained trade
+    pub fn totals(&self) -> TapeAggregates {
+        self.latest_totals().since(&self.evicted)
+    }
+
+    /// Aggregates over trades within `window` of the newest trade
+    pub fn aggregates(&self, window: Duration) -> TapeAggregates {
+        let newest = match self.entries.back() {
+            Some((trade, _)) => trade.timestamp,
+            None => return self.totals(),
+        };
+        let cutoff = newest.checked_sub(window).unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let first = self.entries.partition_point(|(trade, _)| trade.timestamp < cutoff);
+        let base = match first {
+            0 => self.evicted,
+            idx => self.entries[idx - 1].1,
+        };
+
+        self.latest_totals().since(&base)
+    }
+
+    /// Retained trades, oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Trade> {
+        self.entries.iter().map(|(trade, _)| trade)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.evicted = self.latest_totals();
+        self.entries.clear();
+    }
+
+    fn latest_totals(&self) -> Totals {
+        self.entries.back().map(|(_, totals)| *totals).unwrap_or(self.evicted)
+    }
+
+    fn evict(&mut self, newest: SystemTime) {
+        let cutoff = newest.checked_sub(self.retention).unwrap_or(SystemTime::UNIX_EPOCH);
+
+        while let Some((trade, totals)) = self.entries.front() {
+            let over_count = self.max_trades.is_some_and(|max| self.entries.len() > max);
+            if trade.timestamp >= cutoff && !over_count {
+                break;
+            }
+            self.evicted = *totals;
+            self.entries.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::time::UNIX_EPOCH;
+
+    fn trade(ms: u64, price: Decimal, quantity: Decimal, side: Side) -> Trade {
+        Trade {
+            id: ms,
+            price,
+            quantity,
+            side,
+            timestamp: UNIX_EPOCH + Duration::from_millis(ms),
+            is_buyer_maker: side == Side::Sell,
+        }
+    }
+
+    #[test]
+    fn test_rolling_aggregates_and_windows() {
+        let mut tape = TradeTape::new(Duration::from_secs(60))
+            .with_large_trade_qty(dec!(5));
+
+        tape.push(trade(0, dec!(100), dec!(1), Side::Buy));
+        tape.push(trade(1_000, dec!(102), dec!(5), Side::Sell));
+        tape.push(trade(2_000, dec!(101), dec!(2), Side::Buy));
+
+        let all = tape.totals();
+        assert_eq!(all.trade_count, 3);
+        assert_eq!(all.buy_volume, dec!(3));
+        assert_eq!(all.sell_volume, dec!(5));
+        assert_eq!(all.large_trade_count, 1);
+        assert_eq!(all.vwap(), Some(dec!(812) / dec!(8)));
+
+        // Last second only: the 1s and 2s trades
+        let recent = tape.aggregates(Duration::from_secs(1));
+        assert_eq!(recent.trade_count, 2);
+        assert_eq!(recent.buy_volume, dec!(2));
+        assert_eq!(recent.imbalance(), Some(dec!(-3) / dec!(7)));
+    }
+
+    #[test]
+    fn test_eviction_by_time_and_count() {
+        let mut tape = TradeTape::new(Duration::from_secs(5)).with_max_trades(3);
+
+        for i in 0..4 {
+            tape.push(trade(i * 1_000, dec!(100), dec!(1), Side::Buy));
+        }
+        assert_eq!(tape.len(), 3);
+        assert_eq!(tape.totals().buy_volume, dec!(3));
+
+        // 10s later everything but the new trade has aged out
+        tape.push(trade(13_000, dec!(100), dec!(2), Side::Sell));
+        assert_eq!(tape.len(), 1);
+
+        let totals = tape.totals();
+        assert_eq!(totals.buy_volume, Decimal::ZERO);
+        assert_eq!(totals.sell_volume, dec!(2));
+        assert_eq!(tape.aggregates(Duration::from_secs(60)), totals);
+
+        tape.clear();
+        assert_eq!(tape.totals().trade_count, 0);
+    }
+}
@@ -0,0 +1,68 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// How an account's base entry size is derived
+///
+/// The base is then scaled by signal confidence and capped as before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMode {
+    /// The base is the entry notional
+    #[default]
+    FixedNotional,
+    /// The base is the currency lost if the stop is hit; notional is
+    /// `risk / stop distance`, so risk per trade stays constant as the
+    /// stop widens or tightens
+    RiskPerTrade,
+    /// The base is a percentage of current account equity, so size
+    /// compounds with gains and shrinks with losses
+    EquityPercent,
+}
+
+impl SizingMode {
+    /// Entry notional before confidence scaling
+    ///
+    /// `None` under `RiskPerTrade` without a positive stop distance, or
+    /// under `EquityPercent` with no equity left.
+    pub fn base_notional(&self, base: Decimal, stop_loss_bps: Decimal, equity: Decimal) -> Option<Decimal> {
+        match self {
+            SizingMode::FixedNotional => Some(base),
+            SizingMode::RiskPerTrade if stop_loss_bps > Decimal::ZERO => {
+                Some(base * Decimal::from(10000) / stop_loss_bps)
+            }
+            SizingMode::RiskPerTrade => None,
+            SizingMode::EquityPercent if equity > Decimal::ZERO => {
+                Some(equity * base / Decimal::from(100))
+            }
+            SizingMode::EquityPercent => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_risk_per_trade_scales_with_stop() {
+        let mode = SizingMode::RiskPerTrade;
+
+        // $5 risk at a 5 bps stop is $10k notional; twice the stop, half the size
+        assert_eq!(mode.base_notional(dec!(5), dec!(5), dec!(10000)), Some(dec!(10000)));
+        assert_eq!(mode.base_notional(dec!(5), dec!(10), dec!(10000)), Some(dec!(5000)));
+        assert_eq!(mode.base_notional(dec!(5), Decimal::ZERO, dec!(10000)), None);
+
+        assert_eq!(SizingMode::FixedNotional.base_notional(dec!(1000), dec!(5), dec!(10000)), Some(dec!(1000)));
+    }
+
+    #[test]
+    fn test_equity_percent_compounds() {
+        let mode = SizingMode::EquityPercent;
+
+        assert_eq!(mode.base_notional(dec!(10), dec!(5), dec!(10000)), Some(dec!(1000)));
+        assert_eq!(mode.base_notional(dec!(10), dec!(5), dec!(12000)), Some(dec!(1200)));
+        assert_eq!(mode.base_notional(dec!(10), dec!(5), dec!(8000)), Some(dec!(800)));
+        assert_eq!(mode.base_notional(dec!(10), dec!(5), Decimal::ZERO), None);
+    }
+}
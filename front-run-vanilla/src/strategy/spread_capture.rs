@@ -0,0 +1,221 @@
+use crate::data::{OrderBook, Side};
+use crate::risk::Instrument;
+use crate::strategy::{
+    EvaluateBook, ImbalanceDetector, Strategy, StrategyAction, StrategyFill, StrategyParams,
+};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use std::time::SystemTime;
+
+/// Passive quoting while the imbalance is neutral (`[strategy.spread_capture]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpreadCaptureConfig {
+    /// Quote only while the imbalance |z| is below this
+    pub neutral_z: f64,
+    /// Quote distance from the (skewed) fair price, each side
+    pub half_spread_bps: f64,
+    /// Fair price shift toward the imbalance, per unit of z
+    pub skew_bps_per_z: f64,
+    /// USD notional of each quote
+    pub quote_notional_usd: f64,
+    /// Stop quoting the side that would grow inventory beyond this
+    pub max_inventory_usd: f64,
+}
+
+impl Default for SpreadCaptureConfig {
+    fn default() -> Self {
+        Self {
+            neutral_z: 1.0,
+            half_spread_bps: 1.0,
+            skew_bps_per_z: 0.5,
+            quote_notional_usd: 500.0,
+            max_inventory_usd: 1500.0,
+        }
+    }
+}
+
+/// Without a tick grid, quotes improve the touch by this much
+const IMPROVEMENT_BPS_WITHOUT_TICK: i64 = 1;
+
+/// Maker strategy: quotes both sides strictly inside the spread, centred
+/// on the mid shifted toward the book imbalance, and pulls both quotes
+/// once the imbalance is strong enough for the taker strategy to act on
+///
+/// Reuses the configured imbalance detector for its z-score; the detector
+/// threshold itself is not used.
+pub struct SpreadCapture {
+    config: SpreadCaptureConfig,
+    instrument: Instrument,
+    imbalance: ImbalanceDetector,
+    /// Signed inventory in instrument units
+    inventory: Decimal,
+    /// Resting (price, quantity) per side, as last sent
+    bid: Option<(Decimal, Decimal)>,
+    ask: Option<(Decimal, Decimal)>,
+}
+
+impl SpreadCapture {
+    pub fn new(params: &StrategyParams, instrument: Instrument) -> Self {
+        Self {
+            config: params.spread_capture.clone(),
+            instrument,
+            imbalance: params.imbalance_detector(),
+            inventory: Decimal::ZERO,
+            bid: None,
+            ask: None,
+        }
+    }
+
+    pub fn inventory(&self) -> Decimal {
+        self.inventory
+    }
+
+    /// Bid and ask to rest, or None on a side that should not quote
+    fn target_quotes(&self, best_bid: Decimal, best_ask: Decimal, z_score: f64) -> [Option<(Decimal, Decimal)>; 2] {
+        let mid = (best_bid + best_ask) / Decimal::from(2);
+        let bps = |value: f64| mid * to_decimal(value) / Decimal::from(10000);
+        let tick = if self.instrument.tick_size.is_zero() {
+            mid * Decimal::from(IMPROVEMENT_BPS_WITHOUT_TICK) / Decimal::from(10000)
+        } else {
+            self.instrument.tick_size
+        };
+
+        // No room for two quotes strictly inside the spread
+        let (inside_bid, inside_ask) = (best_bid + tick, best_ask - tick);
+        if inside_bid >= inside_ask {
+            return [None, None];
+        }
+
+        let fair = mid + bps(z_score * self.config.skew_bps_per_z);
+        let half_spread = bps(self.config.half_spread_bps);
+        let bid = floor_to(fair - half_spread, tick).clamp(inside_bid, inside_ask - tick);
+        let ask = ceil_to(fair + half_spread, tick).clamp(bid + tick, inside_ask);
+
+        let quantity = self.instrument.quantity(to_decimal(self.config.quote_notional_usd), mid, Decimal::ONE);
+        if quantity.is_zero() {
+            return [None, None];
+        }
+        let inventory_usd = self.instrument.quote_notional(self.inventory, mid);
+        let max_inventory = to_decimal(self.config.max_inventory_usd);
+        [
+            (inventory_usd < max_inventory).then_some((bid, quantity)),
+            (inventory_usd > -max_inventory).then_some((ask, quantity)),
+        ]
+    }
+
+    /// Actions that move the resting quotes to `targets`
+    fn requote(&mut self, targets: [Option<(Decimal, Decimal)>; 2]) -> Vec<StrategyAction> {
+        let mut actions = Vec::new();
+        for (side, target) in [Side::Buy, Side::Sell].into_iter().zip(targets) {
+            let resting = match side {
+                Side::Buy => &mut self.bid,
+                Side::Sell => &mut self.ask,
+            };
+            if *resting == target {
+                continue;
+            }
+            actions.push(match target {
+                Some((price, quantity)) => StrategyAction::Quote { side, price, quantity },
+                None => StrategyAction::CancelQuote(side),
+            });
+            *resting = target;
+        }
+        actions
+    }
+}
+
+impl Strategy for SpreadCapture {
+    fn name(&self) -> &str {
+        "spread_capture"
+    }
+
+    fn on_book(&mut self, book: &OrderBook, now: SystemTime) -> Vec<StrategyAction> {
+        self.imbalance.calculate_signal_at(book, now);
+        let touch = book.evaluate(1, |view| view.best_bid().zip(view.best_ask()));
+
+        let targets = match (touch, self.imbalance.last_z_score()) {
+            (Some((best_bid, best_ask)), Some(z_score)) if z_score.abs() < self.config.neutral_z => {
+                self.target_quotes(best_bid, best_ask, z_score)
+            }
+            // Directional book (or not enough history): stand aside
+            _ => [None, None],
+        };
+        self.requote(targets)
+    }
+
+    fn on_fill(&mut self, fill: &StrategyFill) {
+        match fill.side {
+            Side::Buy => {
+                self.inventory += fill.quantity;
+                if fill.maker {
+                    self.bid = None;
+                }
+            }
+            Side::Sell => {
+                self.inventory -= fill.quantity;
+                if fill.maker {
+                    self.ask = None;
+                }
+            }
+        }
+    }
+}
+
+fn floor_to(price: Decimal, tick: Decimal) -> Decimal {
+    (price / tick).floor() * tick
+}
+
+fn ceil_to(price: Decimal, tick: Decimal) -> Decimal {
+    (price / tick).ceil() * tick
+}
+
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64_retain(value).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn capture() -> SpreadCapture {
+        let instrument = Instrument::linear().with_increments(dec!(0.1), dec!(0.001));
+        SpreadCapture::new(&StrategyParams::default(), instrument)
+    }
+
+    #[test]
+    fn test_quotes_inside_spread_skewed_and_inventory_capped() {
+        let mut strategy = capture();
+
+        // Neutral book: symmetric around the 100.00 mid, inside 99.5/100.5
+        let [bid, ask] = strategy.target_quotes(dec!(99.5), dec!(100.5), 0.0);
+        assert_eq!(bid, Some((dec!(99.9), dec!(5))));
+        assert_eq!(ask, Some((dec!(100.1), dec!(5))));
+
+        // Bid-heavy book shifts both quotes up (0.8 z at 20 bps/z = +0.16)
+        strategy.config.skew_bps_per_z = 20.0;
+        let [bid, ask] = strategy.target_quotes(dec!(99.5), dec!(100.5), 0.8);
+        assert_eq!((bid.unwrap().0, ask.unwrap().0), (dec!(100.1), dec!(100.2)));
+
+        // Tight spread: nowhere to quote
+        assert_eq!(strategy.target_quotes(dec!(99.9), dec!(100.0), 0.0), [None, None]);
+
+        // Long at the cap: only the ask stays
+        strategy.on_fill(&StrategyFill {
+            side: Side::Buy,
+            price: dec!(99.9),
+            quantity: dec!(15),
+            maker: true,
+            time: SystemTime::UNIX_EPOCH,
+        });
+        let [bid, ask] = strategy.target_quotes(dec!(99.5), dec!(100.5), 0.0);
+        assert!(bid.is_none() && ask.is_some());
+
+        // Requoting only sends changes
+        let actions = strategy.requote([None, ask]);
+        assert_eq!(actions, vec![StrategyAction::Quote { side: Side::Sell, price: dec!(100.1), quantity: dec!(5) }]);
+        assert!(strategy.requote([None, ask]).is_empty());
+        assert_eq!(strategy.requote([None, None]), vec![StrategyAction::CancelQuote(Side::Sell)]);
+    }
+}
@@ -0,0 +1,98 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// One step of the take-profit schedule
+///
+/// Signals with |strength| >= `min_strength` use `take_profit_bps`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeProfitTier {
+    pub min_strength: f64,
+    pub take_profit_bps: f64,
+}
+
+/// Take-profit distance scaled by composite signal strength
+///
+/// A 5-sigma imbalance tends to move price further than a 3-sigma one, so
+/// a single fixed target leaves money on the table for the strongest
+/// signals. The schedule picks the highest tier the signal clears and falls
+/// back to the base target otherwise.
+#[derive(Debug, Clone)]
+pub struct TakeProfitSchedule {
+    /// Target used when no tier applies
+    base_bps: Decimal,
+
+    /// (min |strength|, target bps), sorted by ascending strength
+    tiers: Vec<(f64, Decimal)>,
+}
+
+impl TakeProfitSchedule {
+    /// Fixed take profit (no scaling)
+    pub fn fixed(base_bps: Decimal) -> Self {
+        Self {
+            base_bps,
+            tiers: Vec::new(),
+        }
+    }
+
+    /// Create schedule from configured tiers
+    pub fn new(base_bps: Decimal, tiers: &[TakeProfitTier]) -> Self {
+        let mut tiers: Vec<(f64, Decimal)> = tiers.iter()
+            .filter_map(|t| Some((t.min_strength, Decimal::from_f64_retain(t.take_profit_bps)?)))
+            .collect();
+        tiers.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        Self { base_bps, tiers }
+    }
+
+    /// Take profit distance (bps) for a signal of given strength
+    pub fn take_profit_bps(&self, strength: f64) -> Decimal {
+        let strength = strength.abs();
+
+        self.tiers.iter()
+            .rev()
+            .find(|(min_strength, _)| strength >= *min_strength)
+            .map(|(_, bps)| *bps)
+            .unwrap_or(self.base_bps)
+    }
+
+    /// Base (unscaled) take profit
+    pub fn base_bps(&self) -> Decimal {
+        self.base_bps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn tier(min_strength: f64, take_profit_bps: f64) -> TakeProfitTier {
+        TakeProfitTier { min_strength, take_profit_bps }
+    }
+
+    #[test]
+    fn test_fixed_schedule() {
+        let schedule = TakeProfitSchedule::fixed(dec!(10));
+        assert_eq!(schedule.take_profit_bps(3.0), dec!(10));
+        assert_eq!(schedule.take_profit_bps(10.0), dec!(10));
+    }
+
+    #[test]
+    fn test_stronger_signal_further_target() {
+        // Tiers deliberately out of order
+        let schedule = TakeProfitSchedule::new(
+            dec!(10),
+            &[tier(5.0, 20.0), tier(4.0, 15.0)],
+        );
+
+        assert_eq!(schedule.take_profit_bps(3.2), dec!(10));
+        assert_eq!(schedule.take_profit_bps(4.5), dec!(15));
+        assert_eq!(schedule.take_profit_bps(6.0), dec!(20));
+    }
+
+    #[test]
+    fn test_negative_strength_uses_magnitude() {
+        let schedule = TakeProfitSchedule::new(dec!(10), &[tier(4.0, 15.0)]);
+        assert_eq!(schedule.take_profit_bps(-4.5), dec!(15));
+    }
+}
@@ -0,0 +1,177 @@
+//! Fault injection for drills (`--features chaos`)
+//!
+//! Lets an operator add latency, drops and rejects to a running trader to
+//! check that the latency breaker, reconnect logic and order error paths
+//! behave before a real incident exercises them. Not compiled into normal
+//! builds.
+
+use anyhow::{Result, bail};
+use rand::Rng;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Counters exposed for monitoring
+#[derive(Debug, Default)]
+pub struct FaultMetrics {
+    pub delayed_requests: AtomicU64,
+    pub rejected_requests: AtomicU64,
+    pub delayed_frames: AtomicU64,
+    pub dropped_frames: AtomicU64,
+    pub forced_disconnects: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct Faults {
+    request_latency_ms: AtomicU64,
+    /// Stored as `f64::to_bits`
+    request_reject_rate: AtomicU64,
+    frame_latency_ms: AtomicU64,
+    frame_drop_rate: AtomicU64,
+    disconnect: AtomicBool,
+}
+
+/// Runtime-adjustable faults shared by the REST client and WS stream
+///
+/// Everything starts off. Cloning shares the settings, so one handle can
+/// be given to the connectors and another to the command reader.
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjector {
+    faults: Arc<Faults>,
+    metrics: Arc<FaultMetrics>,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared handle to the injection counters
+    pub fn metrics(&self) -> Arc<FaultMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Delay, then possibly fail, an exchange request
+    pub async fn before_request(&self) -> Result<()> {
+        let latency = self.faults.request_latency_ms.load(Ordering::Relaxed);
+        if latency > 0 {
+            self.metrics.delayed_requests.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(latency)).await;
+        }
+        if roll(&self.faults.request_reject_rate) {
+            self.metrics.rejected_requests.fetch_add(1, Ordering::Relaxed);
+            bail!("chaos: injected request reject");
+        }
+        Ok(())
+    }
+
+    /// Delay a received frame; false if it should be dropped
+    pub async fn on_frame(&self) -> bool {
+        if roll(&self.faults.frame_drop_rate) {
+            self.metrics.dropped_frames.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        let latency = self.faults.frame_latency_ms.load(Ordering::Relaxed);
+        if latency > 0 {
+            self.metrics.delayed_frames.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(latency)).await;
+        }
+        true
+    }
+
+    /// True once after a `disconnect` command; the stream should drop its
+    /// connection as if the socket had failed
+    pub fn take_disconnect(&self) -> bool {
+        let requested = self.faults.disconnect.swap(false, Ordering::Relaxed);
+        if requested {
+            self.metrics.forced_disconnects.fetch_add(1, Ordering::Relaxed);
+        }
+        requested
+    }
+
+    /// e.g. "request_latency=0ms request_reject=0.00 frame_latency=0ms frame_drop=0.00"
+    pub fn status(&self) -> String {
+        format!(
+            "request_latency={}ms request_reject={:.2} frame_latency={}ms frame_drop={:.2}",
+            self.faults.request_latency_ms.load(Ordering::Relaxed),
+            rate(&self.faults.request_reject_rate),
+            self.faults.frame_latency_ms.load(Ordering::Relaxed),
+            rate(&self.faults.frame_drop_rate),
+        )
+    }
+
+    /// Apply one `chaos ...` command and return the resulting status
+    ///
+    /// Commands: `chaos status`, `chaos off`, `chaos disconnect`,
+    /// `chaos request_latency <ms>`, `chaos request_reject <0-1>`,
+    /// `chaos frame_latency <ms>`, `chaos frame_drop <0-1>`.
+    pub fn apply_command(&self, command: &str) -> Result<String, String> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let ms = |value: &str| value.parse::<u64>().map_err(|_| format!("expected milliseconds, got '{}'", value));
+        let probability = |value: &str| match value.parse::<f64>() {
+            Ok(p) if (0.0..=1.0).contains(&p) => Ok(p.to_bits()),
+            _ => Err(format!("expected a rate between 0 and 1, got '{}'", value)),
+        };
+
+        let faults = &self.faults;
+        match words.as_slice() {
+            ["chaos", "status"] => {}
+            ["chaos", "off"] => {
+                faults.request_latency_ms.store(0, Ordering::Relaxed);
+                faults.request_reject_rate.store(0, Ordering::Relaxed);
+                faults.frame_latency_ms.store(0, Ordering::Relaxed);
+                faults.frame_drop_rate.store(0, Ordering::Relaxed);
+            }
+            ["chaos", "disconnect"] => faults.disconnect.store(true, Ordering::Relaxed),
+            ["chaos", "request_latency", value] => faults.request_latency_ms.store(ms(value)?, Ordering::Relaxed),
+            ["chaos", "request_reject", value] => faults.request_reject_rate.store(probability(value)?, Ordering::Relaxed),
+            ["chaos", "frame_latency", value] => faults.frame_latency_ms.store(ms(value)?, Ordering::Relaxed),
+            ["chaos", "frame_drop", value] => faults.frame_drop_rate.store(probability(value)?, Ordering::Relaxed),
+            _ => return Err(format!("unknown chaos command '{}'", command.trim())),
+        }
+        Ok(self.status())
+    }
+}
+
+fn rate(bits: &AtomicU64) -> f64 {
+    f64::from_bits(bits.load(Ordering::Relaxed))
+}
+
+fn roll(bits: &AtomicU64) -> bool {
+    let p = rate(bits);
+    p > 0.0 && rand::thread_rng().gen::<f64>() < p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_commands_drive_faults() {
+        let faults = FaultInjector::new();
+        let handle = faults.clone();
+
+        assert!(faults.before_request().await.is_ok());
+        assert!(faults.on_frame().await);
+
+        handle.apply_command("chaos request_reject 1").unwrap();
+        handle.apply_command("chaos frame_drop 1").unwrap();
+        assert!(faults.before_request().await.is_err());
+        assert!(!faults.on_frame().await);
+
+        handle.apply_command("chaos disconnect").unwrap();
+        assert!(faults.take_disconnect());
+        assert!(!faults.take_disconnect());
+
+        assert!(handle.apply_command("chaos frame_drop 2").is_err());
+        assert_eq!(
+            handle.apply_command("chaos off").unwrap(),
+            "request_latency=0ms request_reject=0.00 frame_latency=0ms frame_drop=0.00",
+        );
+
+        let metrics = faults.metrics();
+        assert_eq!(metrics.rejected_requests.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.dropped_frames.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.forced_disconnects.load(Ordering::Relaxed), 1);
+    }
+}
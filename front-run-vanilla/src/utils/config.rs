@@ -1,5 +1,17 @@
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
+use crate::backtest::{
+    AdverseSelection, IntrabarOrdering, OutageWindow, PaperConfig, PortfolioConfig, ShadowConfig, SharpeConfig,
+};
+use crate::exchange::binance::{
+    ContextConfig, EventFilterConfig, PreflightConfig, ReconciliationConfig, StreamConfig,
+    UserDataConfig,
+};
+use crate::risk::{BlackoutPolicy, Instrument, RiskLimits, SharedStateConfig, is_usd_quote};
+use crate::storage::{Experiment, FrameEncoding, HeatmapConfig, WebhookConfig};
+use crate::strategy::{BasisConfig, LiquidationConfig, ReferenceConfig, SizingMode, StrategyParams};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use std::path::Path;
 
 /// Main configuration structure
@@ -13,6 +25,46 @@ pub struct Config {
     pub latency: LatencyConfig,
     pub logging: LoggingConfig,
     pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    /// Leading reference symbol filter (alts only)
+    #[serde(default)]
+    pub reference: ReferenceConfig,
+    /// Spot/perp basis confirming signal and veto
+    #[serde(default)]
+    pub basis: BasisConfig,
+    /// Liquidation cascade signal (needs `exchange.streams.force_order`)
+    #[serde(default)]
+    pub liquidation: LiquidationConfig,
+    /// Startup checks before live trading
+    #[serde(default)]
+    pub preflight: PreflightConfig,
+    /// Klines and funding history loaded at startup
+    #[serde(default)]
+    pub context: ContextConfig,
+    /// Additional sub-accounts mirroring the primary account's signals
+    #[serde(default)]
+    pub accounts: Vec<AccountConfig>,
+    /// Outbound POSTs of fills, position opens/closes and halts
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+    /// Daily loss and exposure limits shared by every instance in a fleet
+    #[serde(default)]
+    pub shared_state: SharedStateConfig,
+    /// Periodic check of local PnL and fees against exchange income
+    #[serde(default)]
+    pub reconciliation: ReconciliationConfig,
+    /// Label and tags stamped onto fills, observations and stats snapshots
+    #[serde(default)]
+    pub experiment: Experiment,
+    /// Candidate strategy simulated on the live feed alongside the real one
+    #[serde(default)]
+    pub shadow: ShadowConfig,
+    /// Simulated fills of the paper trader
+    #[serde(default)]
+    pub paper: PaperConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +73,37 @@ pub struct GeneralConfig {
     pub base_currency: String,
     pub quote_currency: String,
     pub environment: String,
+    /// Contract denomination and exchange increments (`[general.instrument]`);
+    /// increments left at 0 are read from exchangeInfo at startup
+    #[serde(default)]
+    pub instrument: Instrument,
+    /// USD-quoted symbol whose mark price values the quote asset, for
+    /// linear symbols not quoted in USD(T) (e.g. "BTCUSDT" for ETHBTC)
+    #[serde(default)]
+    pub quote_usd_symbol: String,
+}
+
+impl GeneralConfig {
+    /// Symbol to read the quote asset's USD value from, if it needs one
+    pub fn quote_rate_symbol(&self) -> Result<Option<&str>> {
+        if self.instrument.inverse || is_usd_quote(&self.quote_currency) {
+            return Ok(None);
+        }
+        if self.quote_usd_symbol.is_empty() {
+            anyhow::bail!("quote currency {} needs general.quote_usd_symbol to convert to USD", self.quote_currency);
+        }
+        Ok(Some(&self.quote_usd_symbol))
+    }
+
+    /// Asset PnL and fees are paid in: the base coin for inverse
+    /// instruments, the quote asset otherwise
+    pub fn settlement_asset(&self) -> &str {
+        if self.instrument.inverse {
+            &self.base_currency
+        } else {
+            &self.quote_currency
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,28 +111,81 @@ pub struct StrategyConfig {
     pub name: String,
     pub version: String,
     pub enabled: bool,
-    pub imbalance_threshold: f64,
-    pub min_confirming_signals: usize,
     pub lookback_window_ms: u64,
-    pub take_profit_bps: f64,
-    pub stop_loss_bps: f64,
-    pub max_hold_time_ms: u64,
+    /// Parameters shared with the backtester
+    #[serde(flatten)]
+    pub params: StrategyParams,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionSizingConfig {
+    /// fixed_notional (base_notional_usd), risk_per_trade (risk_per_trade_usd)
+    /// or equity_percent (equity_percent)
+    #[serde(default)]
+    pub mode: SizingMode,
     pub base_notional_usd: f64,
+    /// Loss at the stop per trade, for `mode = "risk_per_trade"`
+    #[serde(default)]
+    pub risk_per_trade_usd: f64,
+    /// Base size as % of account equity, for `mode = "equity_percent"`
+    #[serde(default)]
+    pub equity_percent: f64,
+    /// How often live account equity is re-read from the exchange
+    #[serde(default = "default_equity_refresh_secs")]
+    pub equity_refresh_secs: u64,
     pub min_size_multiplier: f64,
     pub max_size_multiplier: f64,
     pub max_position_usd: f64,
 }
 
+fn default_equity_refresh_secs() -> u64 {
+    60
+}
+
+impl PositionSizingConfig {
+    /// Base size handed to the execution engine for `mode`
+    pub fn base_size(&self) -> f64 {
+        match self.mode {
+            SizingMode::FixedNotional => self.base_notional_usd,
+            SizingMode::RiskPerTrade => self.risk_per_trade_usd,
+            SizingMode::EquityPercent => self.equity_percent,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskConfig {
     pub max_portfolio_exposure_usd: f64,
     pub max_daily_loss_usd: f64,
     pub max_drawdown_pct: f64,
     pub max_trades_per_hour: usize,
+    #[serde(default = "default_max_trades_per_day")]
+    pub max_trades_per_day: usize,
+    /// Force-close a position once its unrealized loss exceeds this (0 = disabled)
+    #[serde(default)]
+    pub max_loss_per_trade_usd: f64,
+}
+
+fn default_max_trades_per_day() -> usize {
+    200
+}
+
+impl RiskConfig {
+    /// Limits enforced by `RiskManager`; live, paper and backtest all
+    /// build them here so a config file means the same thing everywhere
+    pub fn limits(&self, max_position_usd: f64, max_acceptable_latency_ms: u64) -> RiskLimits {
+        let decimal = |value: f64| Decimal::from_f64_retain(value).unwrap_or_default();
+        RiskLimits {
+            max_position_size: decimal(max_position_usd),
+            max_portfolio_exposure: decimal(self.max_portfolio_exposure_usd),
+            max_daily_loss: decimal(self.max_daily_loss_usd),
+            max_drawdown_percent: decimal(self.max_drawdown_pct),
+            max_loss_per_trade: decimal(self.max_loss_per_trade_usd),
+            max_trades_per_hour: self.max_trades_per_hour,
+            max_trades_per_day: self.max_trades_per_day,
+            max_acceptable_latency_ms,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +194,34 @@ pub struct ExchangeConfig {
     pub testnet: bool,
     pub api_endpoint: String,
     pub ws_endpoint: String,
+    /// Expire order book levels not updated for this long (0 = disabled)
+    #[serde(default)]
+    pub level_ttl_ms: u64,
+    /// Raw WS frames kept for spilling to disk on errors (0 = disabled)
+    #[serde(default)]
+    pub frame_capture_size: usize,
+    /// Clear the book when this share of recent depth diffs had gaps (0 = never)
+    #[serde(default)]
+    pub resync_gap_rate: f64,
+    /// Levels per side in the REST snapshot the book is (re)built from on
+    /// startup, reconnect and gaps (0 = rebuild from the diff stream alone)
+    #[serde(default)]
+    pub depth_snapshot_limit: usize,
+    /// Force a reconnect after this long without any WS message (0 = disabled)
+    #[serde(default)]
+    pub silence_timeout_ms: u64,
+    /// Book levels kept per side; farther levels are evicted (0 = unlimited)
+    #[serde(default)]
+    pub max_book_levels: usize,
+    /// Depth speed/mode and optional extra streams
+    #[serde(default)]
+    pub streams: StreamConfig,
+    /// Reduce the event stream consumers see (min trade size, dedupe, throttles)
+    #[serde(default)]
+    pub event_filter: EventFilterConfig,
+    /// Order/account updates over a listen key; fills are taken from them
+    #[serde(default)]
+    pub user_data: UserDataConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +229,10 @@ pub struct LatencyConfig {
     pub target_signal_to_order_ms: u64,
     pub max_acceptable_latency_ms: u64,
     pub ws_ping_interval_ms: u64,
+    /// Abort entries on signals older than this, exchange event to
+    /// execution (0 = never)
+    #[serde(default)]
+    pub max_signal_age_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +248,171 @@ pub struct MetricsConfig {
     pub enabled: bool,
 }
 
+/// Local recording of research/monitoring data (JSONL files)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub data_dir: String,
+    /// Detector outputs and market samples to observations.jsonl, the
+    /// input of `backtester label`
+    pub record_observations: bool,
+    /// Interval between trading stats snapshots (0 = disabled)
+    pub stats_snapshot_interval_secs: u64,
+    /// Append every live fill to journal.jsonl for fill model calibration
+    pub trade_journal: bool,
+    /// Interval between full book snapshots in book_snapshots.bin, for
+    /// backtest warm-starts (0 = disabled)
+    pub book_snapshot_interval_secs: u64,
+    /// Book density around mid to heatmap_*.parquet (`[storage.heatmap]`)
+    pub heatmap: HeatmapConfig,
+    /// Publish market events, signals and fills to bus.jsonl / bus.bin
+    /// for external consumers
+    pub event_bus: bool,
+    /// Frame encoding of the published bus
+    pub bus_encoding: FrameEncoding,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: "./data/recordings".to_string(),
+            record_observations: false,
+            stats_snapshot_interval_secs: 60,
+            trade_journal: true,
+            book_snapshot_interval_secs: 0,
+            heatmap: HeatmapConfig::default(),
+            event_bus: false,
+            bus_encoding: FrameEncoding::Json,
+        }
+    }
+}
+
+/// Sub-account with its own credentials and sizing
+///
+/// Credentials are read from the named environment variables, never from
+/// the config file itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountConfig {
+    pub name: String,
+    pub api_key_env: String,
+    pub secret_key_env: String,
+    pub api_endpoint: String,
+    pub base_notional_usd: f64,
+    /// Used instead of `base_notional_usd` under risk-per-trade sizing
+    #[serde(default)]
+    pub risk_per_trade_usd: f64,
+    pub initial_equity_usd: f64,
+}
+
+/// Scheduled event calendar (entry blackouts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+    /// Path to a TOML/JSON calendar or http(s) URL (empty = disabled)
+    pub source: String,
+    /// Blackout window per event severity
+    pub blackout: BlackoutPolicy,
+    /// How often a URL source is refetched; a failed fetch keeps the
+    /// last good calendar (0 = startup only, files are read once)
+    pub refresh_secs: u64,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            source: String::new(),
+            blackout: BlackoutPolicy::default(),
+            refresh_secs: 3600,
+        }
+    }
+}
+
+impl StrategyConfig {
+    /// Load only the `[strategy]` section of a config file
+    ///
+    /// Lets tools like the backtester share the live strategy parameters
+    /// without requiring exchange/latency sections.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let value: toml::Value = toml::from_str(&contents)?;
+        let strategy = value
+            .get("strategy")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("missing [strategy] section"))?;
+        Ok(strategy.try_into()?)
+    }
+}
+
+/// `[backtest]` settings the backtester reads alongside `[strategy]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BacktestSettings {
+    /// Same-timestamp and exit-check ordering (`[backtest.ordering]`)
+    pub ordering: IntrabarOrdering,
+    /// Multi-symbol capital allocation (`[backtest.portfolio]`)
+    pub portfolio: PortfolioConfig,
+    /// Fill penalty estimated by `backtester resimulate`
+    /// (`[backtest.adverse_selection]`)
+    pub adverse_selection: AdverseSelection,
+    /// Return bucketing and risk-free rate (`[backtest.sharpe]`)
+    pub sharpe: SharpeConfig,
+    /// Book levels kept per side, as `[exchange] max_book_levels` (0 = unlimited)
+    pub max_book_levels: usize,
+    /// Contract denomination, as `[general.instrument]` live
+    /// (`[backtest.instrument]`)
+    pub instrument: Instrument,
+    /// Simulated exchange outages (`[[backtest.outages]]`)
+    pub outages: Vec<OutageWindow>,
+    /// Limits from `[risk]`, built exactly as the live trader builds them
+    /// (`None` when the file has no `[risk]` section)
+    #[serde(skip)]
+    pub risk_limits: Option<RiskLimits>,
+    /// Sizing from `[position_sizing]`, if present
+    #[serde(skip)]
+    pub position_sizing: Option<PositionSizingConfig>,
+    /// Entry blackouts from `[calendar]`, if present
+    #[serde(skip)]
+    pub calendar: Option<CalendarConfig>,
+}
+
+impl BacktestSettings {
+    /// Load the `[backtest]` section of a config file (defaults if absent)
+    /// plus the `[risk]` limits and `[calendar]`
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let value: toml::Value = toml::from_str(&contents)?;
+        let mut settings: Self = match value.get("backtest").cloned() {
+            Some(backtest) => backtest.try_into()?,
+            None => Self::default(),
+        };
+
+        if let Some(risk) = value.get("risk").cloned() {
+            let risk: RiskConfig = risk.try_into()?;
+            let defaults = RiskLimits::default();
+            let max_position_usd = match value.get("position_sizing")
+                .and_then(|sizing| sizing.get("max_position_usd"))
+                .and_then(|max| max.as_float())
+            {
+                Some(max) => max,
+                None => defaults.max_position_size.to_f64()
+                    .ok_or_else(|| anyhow::anyhow!("default max position size is out of f64 range"))?,
+            };
+            let max_latency_ms = value.get("latency")
+                .and_then(|latency| latency.get("max_acceptable_latency_ms"))
+                .and_then(|max| max.as_integer())
+                .map_or(defaults.max_acceptable_latency_ms, |max| max as u64);
+            settings.risk_limits = Some(risk.limits(max_position_usd, max_latency_ms));
+        }
+        if let Some(sizing) = value.get("position_sizing").cloned() {
+            settings.position_sizing = Some(sizing.try_into()?);
+        }
+        if let Some(calendar) = value.get("calendar").cloned() {
+            settings.calendar = Some(calendar.try_into()?);
+        }
+        Ok(settings)
+    }
+}
+
 impl Config {
     /// Load configuration from TOML file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -107,4 +440,48 @@ mod tests {
         // Just verify the function exists and can be called
         assert!(result.is_ok() || result.is_err());
     }
+
+    #[test]
+    fn test_detector_sections_default_when_absent() {
+        let mut config = Config::from_file("config/production.toml").unwrap();
+        assert_eq!(config.strategy.params.imbalance.levels, 5);
+        assert_eq!(config.strategy.params.flow.time_window_ms, 5000);
+
+        // Older configs without the sections still load with the previous hard-coded values
+        let toml = toml::to_string(&config.strategy).unwrap();
+        let mut value: toml::Value = toml::from_str(&toml).unwrap();
+        let table = value.as_table_mut().unwrap();
+        table.remove("imbalance");
+        table.remove("flow");
+        table.remove("confirming_threshold");
+        config.strategy = value.try_into().unwrap();
+
+        assert_eq!(config.strategy.params.imbalance.window_size, 100);
+        assert_eq!(config.strategy.params.flow.threshold, 0.6);
+        assert_eq!(config.strategy.params.confirming_threshold, 1.5);
+    }
+
+    #[test]
+    fn test_backtest_settings_read_calendar() {
+        let settings = BacktestSettings::from_file("config/production.toml").unwrap();
+        let calendar = settings.calendar.unwrap();
+        assert!(calendar.source.is_empty());
+        assert_eq!(calendar.refresh_secs, 3600);
+
+        let calendar: CalendarConfig = toml::from_str("source = \"https://example.com/events.json\"").unwrap();
+        assert_eq!(calendar.refresh_secs, CalendarConfig::default().refresh_secs);
+        assert!(BacktestSettings::default().calendar.is_none());
+    }
+
+    #[test]
+    fn test_strategy_section_matches_across_configs() {
+        // Backtest and live must be driven by the same parameters
+        let production = StrategyConfig::from_file("config/production.toml").unwrap();
+        let backtest = StrategyConfig::from_file("config/backtest.toml").unwrap();
+
+        assert_eq!(
+            toml::to_string(&production.params).unwrap(),
+            toml::to_string(&backtest.params).unwrap(),
+        );
+    }
 }
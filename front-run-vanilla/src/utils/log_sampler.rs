@@ -0,0 +1,89 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rate limiter for repetitive hot-path log messages
+///
+/// Lets at most one message through per interval and counts the rest, so a
+/// persistent condition (e.g. malformed depth updates at 10/s) shows up as
+/// one line with a suppressed count instead of flooding the log or being
+/// silenced entirely. Interior mutability allows use from `&self` methods.
+#[derive(Debug)]
+pub struct LogSampler {
+    interval: Duration,
+    state: Mutex<SamplerState>,
+}
+
+#[derive(Debug, Default)]
+struct SamplerState {
+    last_emit: Option<Instant>,
+    suppressed: u64,
+}
+
+impl LogSampler {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            state: Mutex::new(SamplerState::default()),
+        }
+    }
+
+    /// Returns `Some(suppressed)` if a message should be emitted now, where
+    /// `suppressed` is how many were dropped since the last emitted one.
+    pub fn sample(&self) -> Option<u64> {
+        self.sample_at(Instant::now())
+    }
+
+    fn sample_at(&self, now: Instant) -> Option<u64> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let due = match state.last_emit {
+            None => true,
+            Some(last) => now.duration_since(last) >= self.interval,
+        };
+
+        if due {
+            state.last_emit = Some(now);
+            Some(std::mem::take(&mut state.suppressed))
+        } else {
+            state.suppressed += 1;
+            None
+        }
+    }
+}
+
+/// Log through a [`LogSampler`], appending the suppressed count
+///
+/// ```ignore
+/// sampled!(self.parse_errors, warn, "Error processing message: {}", e);
+/// ```
+#[macro_export]
+macro_rules! sampled {
+    ($sampler:expr, $level:ident, $($arg:tt)+) => {
+        if let Some(suppressed) = $sampler.sample() {
+            if suppressed > 0 {
+                tracing::$level!(suppressed, $($arg)+);
+            } else {
+                tracing::$level!($($arg)+);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_suppresses_within_interval() {
+        let sampler = LogSampler::new(Duration::from_secs(1));
+        let start = Instant::now();
+
+        assert_eq!(sampler.sample_at(start), Some(0));
+        assert_eq!(sampler.sample_at(start + Duration::from_millis(100)), None);
+        assert_eq!(sampler.sample_at(start + Duration::from_millis(500)), None);
+
+        // Next window reports what was dropped
+        assert_eq!(sampler.sample_at(start + Duration::from_secs(1)), Some(2));
+        assert_eq!(sampler.sample_at(start + Duration::from_millis(1100)), None);
+    }
+}
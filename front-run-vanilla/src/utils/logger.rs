@@ -47,8 +47,6 @@ pub fn init_from_config(config: &crate::utils::config::LoggingConfig) {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_logger_init() {
         // Just verify the function exists
@@ -1,5 +1,9 @@
 pub mod config;
 pub mod logger;
+pub mod log_sampler;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 
 pub use config::Config;
 pub use logger::{init_logger, init_from_config};
+pub use log_sampler::LogSampler;
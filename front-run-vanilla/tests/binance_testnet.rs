@@ -0,0 +1,140 @@
+//! End-to-end tests against the Binance Futures testnet
+//!
+//! Opt-in: these only run when `BINANCE_TESTNET_INTEGRATION=1` and
+//! `BINANCE_TESTNET_API_KEY` / `BINANCE_TESTNET_SECRET_KEY` are set,
+//! otherwise every test returns early. They exercise auth, request signing
+//! and response deserialization that the unit tests cannot.
+//!
+//! ```bash
+//! BINANCE_TESTNET_INTEGRATION=1 cargo test --test binance_testnet -- --test-threads=1
+//! ```
+
+use front_run_vanilla::{BinanceRestClient, Side};
+use futures_util::StreamExt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+const REST_ENDPOINT: &str = "https://testnet.binancefuture.com";
+const WS_ENDPOINT: &str = "wss://stream.binancefuture.com";
+const SYMBOL: &str = "BTCUSDT";
+
+/// Smallest quantity that clears the testnet minimum notional
+const TEST_QUANTITY: Decimal = dec!(0.002);
+
+fn testnet_client() -> Option<BinanceRestClient> {
+    if env::var("BINANCE_TESTNET_INTEGRATION").as_deref() != Ok("1") {
+        eprintln!("skipping: set BINANCE_TESTNET_INTEGRATION=1 to run testnet tests");
+        return None;
+    }
+
+    let _ = dotenv::dotenv();
+    let api_key = env::var("BINANCE_TESTNET_API_KEY").ok()?;
+    let secret_key = env::var("BINANCE_TESTNET_SECRET_KEY").ok()?;
+
+    Some(BinanceRestClient::new(api_key, secret_key, REST_ENDPOINT.to_string()))
+}
+
+/// Current mark price, read through the position endpoint
+async fn mark_price(client: &BinanceRestClient) -> Decimal {
+    let positions = client.get_position_risk(SYMBOL).await.unwrap();
+    Decimal::from_str(&positions[0].mark_price).unwrap()
+}
+
+/// Limit price far enough below market that the order rests
+async fn resting_buy_price(client: &BinanceRestClient) -> Decimal {
+    (mark_price(client).await * dec!(0.9)).round()
+}
+
+#[tokio::test]
+async fn test_connectivity_and_account() {
+    let Some(client) = testnet_client() else { return };
+
+    client.test_connectivity().await.unwrap();
+    assert!(client.get_server_time().await.unwrap() > 0);
+
+    let account = client.get_account_info().await.unwrap();
+    assert!(account.get("assets").is_some());
+}
+
+#[tokio::test]
+async fn test_place_and_cancel_limit_order() {
+    let Some(client) = testnet_client() else { return };
+
+    let price = resting_buy_price(&client).await;
+    let order = client
+        .place_limit_order(SYMBOL, Side::Buy, price, TEST_QUANTITY)
+        .await
+        .unwrap();
+
+    assert_eq!(order.symbol, SYMBOL);
+    assert_eq!(order.status, "NEW");
+    assert_eq!(order.side, "BUY");
+
+    let cancelled = client.cancel_order(SYMBOL, order.order_id).await.unwrap();
+    assert_eq!(cancelled.order_id, order.order_id);
+    assert_eq!(cancelled.status, "CANCELED");
+}
+
+#[tokio::test]
+async fn test_market_order_opens_and_closes_position() {
+    let Some(client) = testnet_client() else { return };
+
+    let before = client.get_position_risk(SYMBOL).await.unwrap();
+    let before_amt = Decimal::from_str(&before[0].position_amt).unwrap();
+
+    client.place_market_order(SYMBOL, Side::Buy, TEST_QUANTITY).await.unwrap();
+
+    let during = client.get_position_risk(SYMBOL).await.unwrap();
+    let during_amt = Decimal::from_str(&during[0].position_amt).unwrap();
+    assert_eq!(during_amt - before_amt, TEST_QUANTITY);
+
+    client.place_market_order(SYMBOL, Side::Sell, TEST_QUANTITY).await.unwrap();
+
+    let after = client.get_position_risk(SYMBOL).await.unwrap();
+    assert_eq!(Decimal::from_str(&after[0].position_amt).unwrap(), before_amt);
+}
+
+#[tokio::test]
+async fn test_user_data_stream_receives_order_updates() {
+    let Some(client) = testnet_client() else { return };
+
+    let listen_key = client.start_user_data_stream().await.unwrap();
+    assert!(!listen_key.is_empty());
+
+    let url = format!("{}/ws/{}", WS_ENDPOINT, listen_key);
+    let (mut ws, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+
+    // Generate an event: place and cancel a resting order
+    let price = resting_buy_price(&client).await;
+    let order = client
+        .place_limit_order(SYMBOL, Side::Buy, price, TEST_QUANTITY)
+        .await
+        .unwrap();
+    client.cancel_order(SYMBOL, order.order_id).await.unwrap();
+
+    let update = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(msg) = ws.next().await {
+            let text = match msg.unwrap() {
+                tokio_tungstenite::tungstenite::Message::Text(text) => text,
+                _ => continue,
+            };
+            let event: serde_json::Value = serde_json::from_str(&text).unwrap();
+            if event["e"] == "ORDER_TRADE_UPDATE" {
+                return Some(event);
+            }
+        }
+        None
+    })
+    .await
+    .expect("no ORDER_TRADE_UPDATE within 10s")
+    .expect("user data stream closed");
+
+    assert_eq!(update["o"]["s"], SYMBOL);
+    assert_eq!(update["o"]["i"], order.order_id);
+
+    client.keepalive_user_data_stream().await.unwrap();
+    client.close_user_data_stream().await.unwrap();
+}